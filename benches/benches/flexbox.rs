@@ -4,6 +4,7 @@ use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
 use taffy::prelude::*;
 use taffy::style::Style;
+use taffy::testing::{RandomTreeGenerator, StyleDistribution, TreeShape};
 use taffy_benchmarks::{build_deep_tree, Randomizeable};
 
 #[cfg(feature = "yoga")]
@@ -143,6 +144,14 @@ fn build_yoga_huge_nested_hierarchy(node_count: u32, branching_factor: u32) -> (
     (tree, root)
 }
 
+/// A tree with random shape and flexbox styles, built with the `taffy::testing` generators
+fn build_taffy_random_hierarchy(node_count: usize) -> (Taffy, NodeId) {
+    let mut taffy = Taffy::new();
+    let shape = TreeShape { max_nodes: node_count, ..Default::default() };
+    let root = RandomTreeGenerator::new(12345, shape, StyleDistribution::flexbox()).generate(&mut taffy).unwrap();
+    (taffy, root)
+}
+
 fn taffy_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("yoga 'huge nested'");
     for node_count in [1_000u32, 10_000, 100_000].iter() {
@@ -240,6 +249,19 @@ fn taffy_benchmarks(c: &mut Criterion) {
         });
     }
     group.finish();
+
+    let mut group = c.benchmark_group("random trees");
+    group.sample_size(10);
+    for node_count in [1_000usize, 10_000, 100_000].iter() {
+        group.bench_with_input(BenchmarkId::new("Taffy", node_count), node_count, |b, &node_count| {
+            b.iter_batched(
+                || build_taffy_random_hierarchy(node_count),
+                |(mut taffy, root)| taffy.compute_layout(root, Size::MAX_CONTENT).unwrap(),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, taffy_benchmarks);
@@ -5,6 +5,7 @@ use rand_chacha::ChaCha8Rng;
 use std::iter;
 use taffy::prelude::*;
 use taffy::style::Style;
+use taffy::testing::{RandomTreeGenerator, StyleDistribution, TreeShape};
 
 /// Build a random leaf node
 fn build_random_leaf(taffy: &mut Taffy, _rng: &mut ChaCha8Rng) -> NodeId {
@@ -100,6 +101,14 @@ fn build_taffy_deep_grid_hierarchy(levels: usize, track_count: usize) -> (Taffy,
     (taffy, root)
 }
 
+/// A tree with random shape and grid styles, built with the `taffy::testing` generators
+fn build_taffy_random_grid_hierarchy(node_count: usize) -> (Taffy, NodeId) {
+    let mut taffy = Taffy::new();
+    let shape = TreeShape { max_nodes: node_count, ..Default::default() };
+    let root = RandomTreeGenerator::new(12345, shape, StyleDistribution::grid()).generate(&mut taffy).unwrap();
+    (taffy, root)
+}
+
 fn taffy_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("grid/wide");
     group.sample_size(10);
@@ -148,6 +157,19 @@ fn taffy_benchmarks(c: &mut Criterion) {
         });
     }
     group.finish();
+
+    let mut group = c.benchmark_group("grid/random");
+    group.sample_size(10);
+    for node_count in [1_000usize, 10_000].iter() {
+        group.bench_with_input(BenchmarkId::new("Taffy", node_count), node_count, |b, &node_count| {
+            b.iter_batched(
+                || build_taffy_random_grid_hierarchy(node_count),
+                |(mut taffy, root)| taffy.compute_layout(root, max_content()).unwrap(),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, taffy_benchmarks);
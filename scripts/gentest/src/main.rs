@@ -295,7 +295,7 @@ fn generate_node(ident: &str, node: &Value) -> TokenStream {
         match style[prop_name_camel_case] {
             Value::Array(ref value) => {
                 let prop_value = quoter(value);
-                quote!(#prop_name_ident: #prop_value,)
+                quote!(#prop_name_ident: #prop_value.into(),)
             }
             _ => quote!(),
         }
@@ -391,7 +391,11 @@ fn generate_node(ident: &str, node: &Value) -> TokenStream {
         let overflow_x = overflow_x.unwrap_or(quote!(taffy::style::Overflow::Visible));
         let overflow_y = overflow_y.unwrap_or(quote!(taffy::style::Overflow::Visible));
         let overflow = quote!(overflow: taffy::geometry::Point { x: #overflow_x, y: #overflow_y },);
-        let scrollbar_width = quote_number_prop("scrollbar_width", style, |value: f32| quote!(#value));
+        let scrollbar_width = quote_number_prop(
+            "scrollbar_width",
+            style,
+            |value: f32| quote!(taffy::geometry::Size { width: #value, height: #value }),
+        );
         (overflow, scrollbar_width)
     } else {
         (quote!(), quote!())
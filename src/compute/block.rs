@@ -1,17 +1,19 @@
 //! Computes the CSS block layout algorithm in the case that the block container being laid out contains only block-level boxes
 use crate::compute::LayoutAlgorithm;
-use crate::geometry::{Line, Point, Rect, Size};
-use crate::style::{AvailableSpace, Display, LengthPercentageAuto, Overflow, Position};
+use crate::geometry::{AbsoluteAxis, Line, Point, Rect, Size};
+use crate::style::{AvailableSpace, Display, LengthPercentageAuto, Overflow, Position, VerticalPercentageBasis};
 use crate::style_helpers::TaffyMaxContent;
-use crate::tree::{CollapsibleMarginSet, Layout, RunMode, SizeBaselinesAndMargins, SizingMode};
+use crate::tree::{CollapsibleMarginSet, Layout, RunMode, SizeBaselinesAndMargins, SizeConstraint, SizingMode};
 use crate::tree::{LayoutTree, NodeId};
 use crate::util::sys::f32_max;
 use crate::util::sys::Vec;
 use crate::util::MaybeMath;
 use crate::util::{MaybeResolve, ResolveOrZero};
 
+#[cfg(feature = "alloc_audit")]
+use crate::tree::{audit_allocation, AllocationSite};
 #[cfg(feature = "debug")]
-use crate::util::debug::NODE_LOGGER;
+use crate::util::debug::debug_event;
 
 /// The public interface to Taffy's Block algorithm implementation
 pub struct BlockAlgorithm;
@@ -82,6 +84,8 @@ struct BlockItem {
     inset: Rect<LengthPercentageAuto>,
     /// The margin of this item
     margin: Rect<LengthPercentageAuto>,
+    /// Which dimension of the containing block percentages in the item's vertical margin are resolved against
+    vertical_percentage_basis: VerticalPercentageBasis,
     /// The sum of padding and border for this item
     padding_border_sum: Size<f32>,
 
@@ -108,11 +112,12 @@ pub fn compute(
 
     // Pull these out earlier to avoid borrowing issues
     let aspect_ratio = style.aspect_ratio;
-    let margin = style.margin.resolve_or_zero(parent_size.width);
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let margin = style.margin.resolve_or_zero(edge_basis);
     let min_size = style.min_size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio);
     let max_size = style.max_size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio);
-    let padding = style.padding.resolve_or_zero(parent_size.width);
-    let border = style.border.resolve_or_zero(parent_size.width);
+    let padding = style.padding.resolve_or_zero(edge_basis);
+    let border = style.border.resolve_or_zero(edge_basis);
     let padding_border_size = (padding + border).sum_axes();
     let clamped_style_size =
         style.size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio).maybe_clamp(min_size, max_size);
@@ -142,7 +147,7 @@ pub fn compute(
     }
 
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("BLOCK");
+    debug_event!(tree, node_id, Some(BlockAlgorithm::NAME), "compute");
     compute_inner(
         tree,
         node_id,
@@ -168,21 +173,23 @@ fn compute_inner(
     let raw_padding = style.padding;
     let raw_border = style.border;
     let raw_margin = style.margin;
+    let vertical_percentage_basis = style.vertical_percentage_basis;
     let aspect_ratio = style.aspect_ratio;
     let size = style.size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio);
     let min_size = style.min_size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio);
     let max_size = style.max_size.maybe_resolve(parent_size).maybe_apply_aspect_ratio(aspect_ratio);
-    let padding = style.padding.resolve_or_zero(parent_size.width);
-    let border = style.border.resolve_or_zero(parent_size.width);
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let padding = style.padding.resolve_or_zero(edge_basis);
+    let border = style.border.resolve_or_zero(edge_basis);
 
     // Scrollbar gutters are reserved when the `overflow` property is set to `Overflow::Scroll`.
     // However, the axis are switched (transposed) because a node that scrolls vertically needs
-    // *horizontal* space to be reserved for a scrollbar
+    // *horizontal* space to be reserved for its (vertical) scrollbar, whose thickness is the width of `scrollbar_width`
     let scrollbar_gutter = {
-        let offsets = style.overflow.transpose().map(|overflow| match overflow {
-            Overflow::Scroll => style.scrollbar_width,
-            _ => 0.0,
-        });
+        let offsets = Point {
+            x: if style.overflow.y == Overflow::Scroll { style.scrollbar_width.width } else { 0.0 },
+            y: if style.overflow.x == Overflow::Scroll { style.scrollbar_width.height } else { 0.0 },
+        };
         // TODO: make side configurable based on the `direction` property
         Rect { top: 0.0, left: 0.0, right: offsets.x, bottom: offsets.y }
     };
@@ -192,14 +199,17 @@ fn compute_inner(
     let container_content_box_size = known_dimensions.maybe_sub(content_box_inset.sum_axes());
 
     // Determine margin collapsing behaviour
+    let margins_collapse = !style.disable_margin_collapsing;
     let own_margins_collapse_with_children = Line {
-        start: vertical_margins_are_collapsible.start
+        start: margins_collapse
+            && vertical_margins_are_collapsible.start
             && !style.overflow.x.is_scroll_container()
             && !style.overflow.y.is_scroll_container()
             && style.position == Position::Relative
             && padding.top == 0.0
             && border.top == 0.0,
-        end: vertical_margins_are_collapsible.end
+        end: margins_collapse
+            && vertical_margins_are_collapsible.end
             && !style.overflow.x.is_scroll_container()
             && !style.overflow.y.is_scroll_container()
             && style.position == Position::Relative
@@ -207,7 +217,8 @@ fn compute_inner(
             && border.bottom == 0.0
             && size.height.is_none(),
     };
-    let has_styles_preventing_being_collapsed_through = style.display != Display::Block
+    let has_styles_preventing_being_collapsed_through = !margins_collapse
+        || style.display != Display::Block
         || style.overflow.x.is_scroll_container()
         || style.overflow.y.is_scroll_container()
         || style.position == Position::Absolute
@@ -218,6 +229,10 @@ fn compute_inner(
 
     // 1. Generate items
     let mut items = generate_item_list(tree, node_id, container_content_box_size);
+    // Row gaps between block children are non-standard, and are zero unless explicitly set
+    let row_gap = style.gap.height.resolve_or_zero(container_content_box_size.height);
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, node_id, AllocationSite::BlockItems, items.capacity());
 
     // 2. Compute container width
     let container_outer_width = known_dimensions.width.unwrap_or_else(|| {
@@ -233,8 +248,10 @@ fn compute_inner(
     }
 
     // 3. Perform final item layout and return content height
-    let resolved_padding = raw_padding.resolve_or_zero(Some(container_outer_width));
-    let resolved_border = raw_border.resolve_or_zero(Some(container_outer_width));
+    let resolved_edge_basis =
+        vertical_percentage_basis.resolve(Size { width: Some(container_outer_width), height: parent_size.height });
+    let resolved_padding = raw_padding.resolve_or_zero(resolved_edge_basis);
+    let resolved_border = raw_border.resolve_or_zero(resolved_edge_basis);
     let resolved_content_box_inset = resolved_padding + resolved_border + scrollbar_gutter;
     let (intrinsic_outer_height, first_child_top_margin_set, last_child_bottom_margin_set) =
         perform_final_layout_on_in_flow_children(
@@ -243,7 +260,10 @@ fn compute_inner(
             container_outer_width,
             content_box_inset,
             resolved_content_box_inset,
+            container_content_box_size.height,
             own_margins_collapse_with_children,
+            margins_collapse,
+            row_gap,
         );
     let container_outer_height = known_dimensions
         .height
@@ -282,8 +302,11 @@ fn compute_inner(
     // 7. Determine whether this node can be collapsed through
     let all_in_flow_children_can_be_collapsed_through =
         items.iter().all(|item| item.position == Position::Absolute || item.can_be_collapsed_through);
-    let can_be_collapsed_through =
-        !has_styles_preventing_being_collapsed_through && all_in_flow_children_can_be_collapsed_through;
+    let has_row_gaps =
+        row_gap != 0.0 && items.iter().filter(|item| item.position != Position::Absolute).nth(1).is_some();
+    let can_be_collapsed_through = !has_styles_preventing_being_collapsed_through
+        && all_in_flow_children_can_be_collapsed_through
+        && !has_row_gaps;
 
     SizeBaselinesAndMargins {
         size: final_outer_size,
@@ -291,13 +314,13 @@ fn compute_inner(
         top_margin: if own_margins_collapse_with_children.start {
             first_child_top_margin_set
         } else {
-            let margin_top = raw_margin.top.resolve_or_zero(parent_size.width);
+            let margin_top = raw_margin.top.resolve_or_zero(edge_basis.height);
             CollapsibleMarginSet::from_margin(margin_top)
         },
         bottom_margin: if own_margins_collapse_with_children.end {
             last_child_bottom_margin_set
         } else {
-            let margin_bottom = raw_margin.bottom.resolve_or_zero(parent_size.width);
+            let margin_bottom = raw_margin.bottom.resolve_or_zero(edge_basis.height);
             CollapsibleMarginSet::from_margin(margin_bottom)
         },
         margins_can_collapse_through: can_be_collapsed_through,
@@ -313,8 +336,9 @@ fn generate_item_list(tree: &impl LayoutTree, node: NodeId, node_inner_size: Siz
         .enumerate()
         .map(|(order, (child_node_id, child_style))| {
             let aspect_ratio = child_style.aspect_ratio;
-            let padding = child_style.padding.resolve_or_zero(node_inner_size);
-            let border = child_style.border.resolve_or_zero(node_inner_size);
+            let edge_basis = child_style.edge_percentage_basis(node_inner_size);
+            let padding = child_style.padding.resolve_or_zero(edge_basis);
+            let border = child_style.border.resolve_or_zero(edge_basis);
             BlockItem {
                 node_id: child_node_id,
                 order: order as u32,
@@ -325,6 +349,7 @@ fn generate_item_list(tree: &impl LayoutTree, node: NodeId, node_inner_size: Siz
                 position: child_style.position,
                 inset: child_style.inset,
                 margin: child_style.margin,
+                vertical_percentage_basis: child_style.vertical_percentage_basis,
                 padding_border_sum: (padding + border).sum_axes(),
 
                 // Fields to be computed later (for now we initialise with dummy values)
@@ -349,9 +374,10 @@ fn determine_content_based_container_width(
     for item in items.iter().filter(|item| item.position != Position::Absolute) {
         let known_dimensions = item.size.maybe_clamp(item.min_size, item.max_size);
 
+        // The margin sum is added to the item's border-box width even when it is negative, so that negative
+        // margins reduce the item's contribution to the container's width as they do in browsers
+        let item_x_margin_sum = item.margin.resolve_or_zero(available_space.width.into_option()).horizontal_axis_sum();
         let width = known_dimensions.width.unwrap_or_else(|| {
-            let item_x_margin_sum =
-                item.margin.resolve_or_zero(available_space.width.into_option()).horizontal_axis_sum();
             let size_and_baselines = tree.perform_child_layout(
                 item.node_id,
                 known_dimensions,
@@ -361,9 +387,9 @@ fn determine_content_based_container_width(
                 Line::TRUE,
             );
 
-            size_and_baselines.size.width + item_x_margin_sum
+            size_and_baselines.size.width
         });
-        let width = f32_max(width, item.padding_border_sum.width);
+        let width = f32_max(width, item.padding_border_sum.width) + item_x_margin_sum;
 
         max_child_width = f32_max(max_child_width, width);
     }
@@ -373,13 +399,17 @@ fn determine_content_based_container_width(
 
 /// Compute each child's final size and position
 #[inline]
+#[allow(clippy::too_many_arguments)]
 fn perform_final_layout_on_in_flow_children(
     tree: &mut impl LayoutTree,
     items: &mut [BlockItem],
     container_outer_width: f32,
     content_box_inset: Rect<f32>,
     resolved_content_box_inset: Rect<f32>,
+    container_inner_height: Option<f32>,
     own_margins_collapse_with_children: Line<bool>,
+    children_margins_collapse: bool,
+    row_gap: f32,
 ) -> (f32, CollapsibleMarginSet, CollapsibleMarginSet) {
     // Resolve container_inner_width for sizing child nodes using intial content_box_inset
     let container_inner_width = container_outer_width - content_box_inset.horizontal_axis_sum();
@@ -391,11 +421,17 @@ fn perform_final_layout_on_in_flow_children(
     let mut first_child_top_margin_set = CollapsibleMarginSet::ZERO;
     let mut active_collapsible_margin_set = CollapsibleMarginSet::ZERO;
     let mut is_collapsing_with_first_margin_set = true;
+    let mut is_first_in_flow_item = true;
     for item in items.iter_mut() {
         if item.position == Position::Absolute {
             item.static_position.y = committed_y_offset;
         } else {
-            let item_margin = item.margin.map(|margin| margin.resolve_to_option(container_outer_width));
+            // Items that resolve vertical percentages against the height of the container are given its height (if known)
+            let item_parent_size = match item.vertical_percentage_basis {
+                VerticalPercentageBasis::Width => parent_size,
+                VerticalPercentageBasis::Height => Size { height: container_inner_height, ..parent_size },
+            };
+            let item_margin = item.margin.maybe_resolve(item.vertical_percentage_basis.resolve(item_parent_size));
             let item_non_auto_margin = item_margin.map(|m| m.unwrap_or(0.0));
             let item_non_auto_x_margin_sum = item_non_auto_margin.horizontal_axis_sum();
             let known_dimensions = item
@@ -403,13 +439,20 @@ fn perform_final_layout_on_in_flow_children(
                 .map_width(|width| Some(width.unwrap_or(container_inner_width - item_non_auto_x_margin_sum)))
                 .maybe_clamp(item.min_size, item.max_size);
 
+            // Items with an auto width that wasn't clamped fill the width of the container
+            if item.size.width.is_none()
+                && known_dimensions.width == Some(container_inner_width - item_non_auto_x_margin_sum)
+            {
+                tree.record_size_constraint(item.node_id, AbsoluteAxis::Horizontal, SizeConstraint::Stretch);
+            }
+
             let item_layout = tree.perform_child_layout(
                 item.node_id,
                 known_dimensions,
-                parent_size,
+                item_parent_size,
                 available_space.map_width(|w| w.maybe_sub(item_non_auto_x_margin_sum)),
                 SizingMode::InherentSize,
-                Line::TRUE,
+                Line { start: children_margins_collapse, end: children_margins_collapse },
             );
             let final_size = item_layout.size;
 
@@ -437,6 +480,18 @@ fn perform_final_layout_on_in_flow_children(
                 bottom: bottom_margin_set.resolve(),
             };
 
+            // Insert the row gap between this item and the previous in-flow item. The margins on either side of the gap
+            // still collapse with each other, but not with the container's top margin as the gap separates them from it.
+            if !is_first_in_flow_item && row_gap != 0.0 {
+                committed_y_offset += row_gap;
+                if is_collapsing_with_first_margin_set && own_margins_collapse_with_children.start {
+                    // These margins have already collapsed with the container's top margin
+                    active_collapsible_margin_set = CollapsibleMarginSet::ZERO;
+                }
+                is_collapsing_with_first_margin_set = false;
+            }
+            is_first_in_flow_item = false;
+
             // Resolve item inset
             let inset =
                 item.inset.zip_size(Size { width: container_inner_width, height: 0.0 }, |p, s| p.maybe_resolve(s));
@@ -447,12 +502,14 @@ fn perform_final_layout_on_in_flow_children(
 
             let y_margin_offset = if is_collapsing_with_first_margin_set && own_margins_collapse_with_children.start {
                 0.0
-            } else {
+            } else if children_margins_collapse {
                 active_collapsible_margin_set.collapse_with_margin(resolved_margin.top).resolve()
+            } else {
+                active_collapsible_margin_set.resolve() + resolved_margin.top
             };
 
             item.computed_size = item_layout.size;
-            item.can_be_collapsed_through = item_layout.margins_can_collapse_through;
+            item.can_be_collapsed_through = children_margins_collapse && item_layout.margins_can_collapse_through;
             item.static_position = Point {
                 x: resolved_content_box_inset.left,
                 y: committed_y_offset + active_collapsible_margin_set.resolve(),
@@ -520,9 +577,10 @@ fn perform_absolute_layout_on_absolute_children(
         }
 
         let aspect_ratio = child_style.aspect_ratio;
-        let margin = child_style.margin.map(|margin| margin.resolve_to_option(area_width));
-        let padding = child_style.padding.resolve_or_zero(Some(area_width));
-        let border = child_style.border.resolve_or_zero(Some(area_width));
+        let edge_basis = child_style.edge_percentage_basis(area_size);
+        let margin = child_style.margin.maybe_resolve(edge_basis);
+        let padding = child_style.padding.resolve_or_zero(edge_basis.map(Some));
+        let border = child_style.border.resolve_or_zero(edge_basis.map(Some));
         let padding_border_sum = (padding + border).sum_axes();
 
         // Resolve inset
@@ -1,5 +1,45 @@
 //! Generic CSS alignment code that is shared between both the Flexbox and CSS Grid algorithms.
-use crate::style::AlignContent;
+use crate::style::{AlignContent, AlignSelf};
+
+/// Implements the fallback alignment of the distributed alignment modes, which can't distribute negative free space,
+/// nor distribute free space between fewer than two alignment subjects
+///
+/// `stretch` and `space-between` fall back to `flex-start` (which is `start` outside of flexbox), while `space-around`
+/// and `space-evenly` fall back to `safe center`: they center a single subject but align overflowing subjects to the
+/// start. See <https://www.w3.org/TR/css-align-3/#distribution-values>
+pub(crate) fn apply_alignment_fallback(
+    free_space: f32,
+    num_items: usize,
+    alignment_mode: AlignContent,
+) -> AlignContent {
+    match alignment_mode {
+        // Stretching is performed by growing the alignment subjects, so they are then positioned as for flex-start
+        AlignContent::Stretch => AlignContent::FlexStart,
+        AlignContent::SpaceBetween if free_space < 0.0 || num_items <= 1 => AlignContent::FlexStart,
+        AlignContent::SpaceAround | AlignContent::SpaceEvenly if free_space < 0.0 => AlignContent::Start,
+        AlignContent::SpaceAround | AlignContent::SpaceEvenly if num_items <= 1 => AlignContent::Center,
+        alignment_mode => alignment_mode,
+    }
+}
+
+/// Implements the fallback alignment of the self-alignment modes (`align-self` and `justify-self`) for an alignment
+/// subject that can't be aligned as specified
+///
+/// `baseline` falls back to `flex-start` (which is `start` outside of flexbox) for subjects that don't participate in
+/// baseline alignment, and `stretch` falls back to `flex-start` for subjects whose size in the axis is definite, as they
+/// can't be stretched. See <https://www.w3.org/TR/css-align-3/#baseline-values> and
+/// <https://www.w3.org/TR/css-align-3/#valdef-align-self-stretch>
+pub(crate) fn apply_self_alignment_fallback(
+    alignment_mode: AlignSelf,
+    participates_in_baseline_alignment: bool,
+    has_definite_size: bool,
+) -> AlignSelf {
+    match alignment_mode {
+        AlignSelf::Baseline if !participates_in_baseline_alignment => AlignSelf::FlexStart,
+        AlignSelf::Stretch if has_definite_size => AlignSelf::FlexStart,
+        alignment_mode => alignment_mode,
+    }
+}
 
 /// Generic alignment function that is used:
 ///   - For both align-content and justify-content alignment
@@ -14,6 +54,7 @@ pub(crate) fn compute_alignment_offset(
     layout_is_flex_reversed: bool,
     is_first: bool,
 ) -> f32 {
+    let alignment_mode = apply_alignment_fallback(free_space, num_items, alignment_mode);
     if is_first {
         match alignment_mode {
             AlignContent::Start => 0.0,
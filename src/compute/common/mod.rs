@@ -1,2 +1,3 @@
 //! Generic code that is shared between multiple layout algorithms
+#[cfg(any(feature = "flexbox", feature = "grid"))]
 pub(crate) mod alignment;
@@ -1,24 +1,26 @@
 //! Computes the [flexbox](https://css-tricks.com/snippets/css/a-guide-to-flexbox/) layout algorithm on [`Taffy`](crate::Taffy) according to the [spec](https://www.w3.org/TR/css-flexbox-1/)
 use core::f32;
 
-use crate::compute::common::alignment::compute_alignment_offset;
+use crate::compute::common::alignment::{apply_self_alignment_fallback, compute_alignment_offset};
 use crate::compute::LayoutAlgorithm;
-use crate::geometry::{Line, Point, Rect, Size};
+use crate::geometry::{AbsoluteAxis, Line, Point, Rect, Size};
 use crate::prelude::{TaffyMaxContent, TaffyMinContent};
 use crate::style::{
     AlignContent, AlignItems, AlignSelf, AvailableSpace, Dimension, Display, FlexWrap, JustifyContent,
     LengthPercentageAuto, Overflow, Position,
 };
 use crate::style::{FlexDirection, Style};
-use crate::tree::{Layout, RunMode, SizeBaselinesAndMargins, SizingMode};
+use crate::tree::{Layout, RunMode, SizeBaselinesAndMargins, SizeConstraint, SizingMode};
 use crate::tree::{LayoutTree, NodeId};
 use crate::util::sys::Vec;
 use crate::util::sys::{f32_max, new_vec_with_capacity};
 use crate::util::MaybeMath;
 use crate::util::{MaybeResolve, ResolveOrZero};
 
+#[cfg(feature = "alloc_audit")]
+use crate::tree::{audit_allocation, AllocationSite};
 #[cfg(feature = "debug")]
-use crate::util::debug::NODE_LOGGER;
+use crate::util::debug::debug_event;
 
 /// The public interface to Taffy's Flexbox algorithm implementation
 pub struct FlexboxAlgorithm;
@@ -50,6 +52,22 @@ impl LayoutAlgorithm for FlexboxAlgorithm {
     }
 }
 
+/// A list of flex items whose allocation is reused by the flexbox algorithm across the computations of flex containers,
+/// see [`LayoutTree::take_flex_items`]
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Default)]
+pub struct FlexItemBuffer {
+    /// The items of the flex container being laid out, which are cleared before the list is handed back to the tree
+    items: Vec<FlexItem>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl core::fmt::Debug for FlexItemBuffer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlexItemBuffer").field("capacity", &self.items.capacity()).finish()
+    }
+}
+
 /// The intermediate results of a flexbox calculation for a single item
 struct FlexItem {
     /// The identifier for the associated node
@@ -61,11 +79,15 @@ struct FlexItem {
     min_size: Size<Option<f32>>,
     /// The maximum allowable size of this item
     max_size: Size<Option<f32>>,
+    /// The index of this item among the children of the flex container
+    order: u32,
     /// The cross-alignment of this item
     align_self: AlignSelf,
 
     /// The overflow style of the item
     overflow: Point<Overflow>,
+    /// Whether the automatic minimum size of the item is zero rather than content-based
+    disable_automatic_min_size: bool,
     /// The flex shrink style of the item
     flex_shrink: f32,
     /// The flex grow style of the item
@@ -81,6 +103,9 @@ struct FlexItem {
     margin: Rect<f32>,
     /// Whether each margin is an auto margin or not
     margin_is_auto: Rect<bool>,
+    /// Whether the free space of the item's line is added to its main-axis start and end margins (in physical
+    /// terms, left/top and right/bottom) because of its `justify_self`, see [`Style::flex_justify_self`]
+    justify_self_margins: Line<bool>,
     /// The padding of this item
     padding: Rect<f32>,
     /// The border of this item
@@ -167,6 +192,8 @@ struct AlgoConstants {
     align_content: AlignContent,
     /// The justify_content property of this node
     justify_content: Option<JustifyContent>,
+    /// Whether the flex items are aligned in the main axis by their justify_self property
+    justify_self: bool,
 
     /// The border-box size of the node being laid out (if known)
     node_outer_size: Size<Option<f32>>,
@@ -213,8 +240,52 @@ pub fn compute(
     }
 
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("FLEX: single-pass");
-    compute_preliminary(tree, node, styled_based_known_dimensions, parent_size, available_space, run_mode)
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "single_pass");
+    let mut flex_items = take_flex_items(tree, node);
+    let result = compute_preliminary(
+        tree,
+        node,
+        styled_based_known_dimensions,
+        parent_size,
+        available_space,
+        run_mode,
+        &mut flex_items,
+    );
+    recycle_flex_items(tree, flex_items);
+    result
+}
+
+/// Takes a list to collect the flex items of `node` into from the tree (see [`LayoutTree::take_flex_items`]), with
+/// room for all of its children so that it doesn't grow as the items are collected
+#[inline]
+fn take_flex_items(tree: &mut impl LayoutTree, node: NodeId) -> Vec<FlexItem> {
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    {
+        let mut flex_items = tree.take_flex_items().items;
+        #[cfg(feature = "alloc_audit")]
+        let capacity = flex_items.capacity();
+        flex_items.reserve(tree.child_count(node));
+        #[cfg(feature = "alloc_audit")]
+        if flex_items.capacity() > capacity {
+            tree.record_allocation(node, AllocationSite::FlexItems);
+        }
+        flex_items
+    }
+    #[cfg(not(any(feature = "std", feature = "alloc")))]
+    {
+        let _ = (tree, node);
+        Vec::new()
+    }
+}
+
+/// Hands the list of flex items taken with [`take_flex_items`] back to the tree, see [`LayoutTree::recycle_flex_items`]
+#[inline]
+fn recycle_flex_items(tree: &mut impl LayoutTree, mut flex_items: Vec<FlexItem>) {
+    flex_items.clear();
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    tree.recycle_flex_items(FlexItemBuffer { items: flex_items });
+    #[cfg(not(any(feature = "std", feature = "alloc")))]
+    let _ = tree;
 }
 
 /// Compute a preliminary size for an item
@@ -225,6 +296,7 @@ fn compute_preliminary(
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     run_mode: RunMode,
+    flex_items: &mut Vec<FlexItem>,
 ) -> SizeBaselinesAndMargins {
     // Define some general constants we will need for the remainder of the algorithm.
     let mut constants = compute_constants(tree.style(node), known_dimensions, parent_size);
@@ -235,28 +307,34 @@ fn compute_preliminary(
 
     // 1. Generate anonymous flex items as described in §4 Flex Items.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("generate_anonymous_flex_items");
-    let mut flex_items = generate_anonymous_flex_items(tree, node, &constants);
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "generate_anonymous_flex_items");
+    generate_anonymous_flex_items(tree, node, &constants, flex_items);
 
     // 9.2. Line Length Determination
 
     // 2. Determine the available main and cross space for the flex items
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("determine_available_space");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "determine_available_space");
     let available_space = determine_available_space(known_dimensions, available_space, &constants);
 
     // 3. Determine the flex base size and hypothetical main size of each item.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("determine_flex_base_size");
-    determine_flex_base_size(tree, &constants, available_space, &mut flex_items);
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "determine_flex_base_size");
+    determine_flex_base_size(tree, &constants, available_space, flex_items);
 
     #[cfg(feature = "debug")]
     for item in flex_items.iter() {
-        NODE_LOGGER.labelled_log("item.flex_basis", item.flex_basis);
-        NODE_LOGGER.labelled_log("item.inner_flex_basis", item.inner_flex_basis);
-        NODE_LOGGER.labelled_debug_log("item.hypothetical_outer_size", item.hypothetical_outer_size);
-        NODE_LOGGER.labelled_debug_log("item.hypothetical_inner_size", item.hypothetical_inner_size);
-        NODE_LOGGER.labelled_debug_log("item.resolved_minimum_main_size", item.resolved_minimum_main_size);
+        debug_event!(
+            tree,
+            item.node,
+            Some(FlexboxAlgorithm::NAME),
+            "flex_base_size",
+            "flex_basis" => item.flex_basis,
+            "inner_flex_basis" => item.inner_flex_basis,
+            "hypothetical_outer_size" => item.hypothetical_outer_size,
+            "hypothetical_inner_size" => item.hypothetical_inner_size,
+            "resolved_minimum_main_size" => item.resolved_minimum_main_size,
+        );
     }
 
     // 4. Determine the main size of the flex container
@@ -266,8 +344,10 @@ fn compute_preliminary(
 
     // 5. Collect flex items into flex lines.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("collect_flex_lines");
-    let mut flex_lines = collect_flex_lines(&constants, available_space, &mut flex_items);
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "collect_flex_lines");
+    let mut flex_lines = collect_flex_lines(&constants, available_space, flex_items);
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, node, AllocationSite::FlexLines, flex_lines.capacity());
 
     // If container size is undefined, determine the container's main size
     // and then re-resolve gaps based on newly determined size
@@ -291,7 +371,7 @@ fn compute_preliminary(
 
     // 6. Resolve the flexible lengths of all the flex items to find their used main size.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("resolve_flexible_lengths");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "resolve_flexible_lengths");
     for line in &mut flex_lines {
         resolve_flexible_lengths(line, &constants, original_gap);
     }
@@ -300,7 +380,7 @@ fn compute_preliminary(
 
     // 7. Determine the hypothetical cross size of each item.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("determine_hypothetical_cross_size");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "determine_hypothetical_cross_size");
     for line in &mut flex_lines {
         determine_hypothetical_cross_size(tree, line, &constants, available_space);
     }
@@ -308,17 +388,17 @@ fn compute_preliminary(
     // Calculate child baselines. This function is internally smart and only computes child baselines
     // if they are necessary.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("calculate_children_base_lines");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "calculate_children_base_lines");
     calculate_children_base_lines(tree, known_dimensions, available_space, &mut flex_lines, &constants);
 
     // 8. Calculate the cross size of each flex line.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("calculate_cross_size");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "calculate_cross_size");
     calculate_cross_size(&mut flex_lines, known_dimensions, &constants);
 
     // 9. Handle 'align-content: stretch'.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("handle_align_content_stretch");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "handle_align_content_stretch");
     handle_align_content_stretch(&mut flex_lines, known_dimensions, &constants);
 
     // 10. Collapse visibility:collapse items. If any flex items have visibility: collapse,
@@ -338,26 +418,26 @@ fn compute_preliminary(
 
     // 11. Determine the used cross size of each flex item.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("determine_used_cross_size");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "determine_used_cross_size");
     determine_used_cross_size(tree, &mut flex_lines, &constants);
 
     // 9.5. Main-Axis Alignment
 
     // 12. Distribute any remaining free space.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("distribute_remaining_free_space");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "distribute_remaining_free_space");
     distribute_remaining_free_space(&mut flex_lines, &constants);
 
     // 9.6. Cross-Axis Alignment
 
     // 13. Resolve cross-axis auto margins (also includes 14).
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("resolve_cross_axis_auto_margins");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "resolve_cross_axis_auto_margins");
     resolve_cross_axis_auto_margins(&mut flex_lines, &constants);
 
     // 15. Determine the flex container’s used cross size.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("determine_container_cross_size");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "determine_container_cross_size");
     let total_line_cross_size = determine_container_cross_size(&flex_lines, known_dimensions, &mut constants);
 
     // We have the container size.
@@ -368,21 +448,21 @@ fn compute_preliminary(
 
     // 16. Align all flex lines per align-content.
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("align_flex_lines_per_align_content");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "align_flex_lines_per_align_content");
     align_flex_lines_per_align_content(&mut flex_lines, &constants, total_line_cross_size);
 
     // Do a final layout pass and gather the resulting layouts
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("final_layout_pass");
-    final_layout_pass(tree, node, &mut flex_lines, &constants);
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "final_layout_pass");
+    final_layout_pass(tree, &mut flex_lines, &constants);
 
     // Before returning we perform absolute layout on all absolutely positioned children
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("perform_absolute_layout_on_absolute_children");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "perform_absolute_layout_on_absolute_children");
     perform_absolute_layout_on_absolute_children(tree, node, &constants);
 
     #[cfg(feature = "debug")]
-    NODE_LOGGER.log("hidden_layout");
+    debug_event!(tree, node, Some(FlexboxAlgorithm::NAME), "hidden_layout");
     let len = tree.child_count(node);
     for order in 0..len {
         let child = tree.child(node, order);
@@ -409,10 +489,7 @@ fn compute_preliminary(
             .iter()
             .find(|item| constants.is_column || item.align_self == AlignSelf::Baseline)
             .or_else(|| flex_lines[0].items.iter().next())
-            .map(|child| {
-                let offset_vertical = if constants.is_row { child.offset_cross } else { child.offset_main };
-                offset_vertical + child.baseline
-            })
+            .map(|child| child.baseline)
     };
 
     SizeBaselinesAndMargins::from_size_and_baselines(
@@ -435,20 +512,22 @@ fn compute_constants(
     let is_wrap_reverse = style.flex_wrap == FlexWrap::WrapReverse;
 
     let aspect_ratio = style.aspect_ratio;
-    let margin = style.margin.resolve_or_zero(parent_size.width);
-    let padding = style.padding.resolve_or_zero(parent_size.width);
-    let border = style.border.resolve_or_zero(parent_size.width);
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let margin = style.margin.resolve_or_zero(edge_basis);
+    let padding = style.padding.resolve_or_zero(edge_basis);
+    let border = style.border.resolve_or_zero(edge_basis);
     let align_items = style.align_items.unwrap_or(AlignItems::Stretch);
     let align_content = style.align_content.unwrap_or(AlignContent::Stretch);
     let justify_content = style.justify_content;
+    let justify_self = style.flex_justify_self;
 
     // Scrollbar gutters are reserved when the `overflow` property is set to `Overflow::Scroll`.
     // However, the axis are switched (transposed) because a node that scrolls vertically needs
-    // *horizontal* space to be reserved for a scrollbar
-    let scrollbar_gutter = style.overflow.transpose().map(|overflow| match overflow {
-        Overflow::Scroll => style.scrollbar_width,
-        _ => 0.0,
-    });
+    // *horizontal* space to be reserved for its (vertical) scrollbar, whose thickness is the width of `scrollbar_width`
+    let scrollbar_gutter = Point {
+        x: if style.overflow.y == Overflow::Scroll { style.scrollbar_width.width } else { 0.0 },
+        y: if style.overflow.x == Overflow::Scroll { style.scrollbar_width.height } else { 0.0 },
+    };
     // TODO: make side configurable based on the `direction` property
     let mut content_box_inset = padding + border;
     content_box_inset.right += scrollbar_gutter.x;
@@ -477,6 +556,7 @@ fn compute_constants(
         align_items,
         align_content,
         justify_content,
+        justify_self,
         node_outer_size,
         node_inner_size,
         container_size,
@@ -490,13 +570,21 @@ fn compute_constants(
 ///
 /// - [**Generate anonymous flex items**](https://www.w3.org/TR/css-flexbox-1/#algo-anon-box) as described in [§4 Flex Items](https://www.w3.org/TR/css-flexbox-1/#flex-items).
 #[inline]
-fn generate_anonymous_flex_items(tree: &impl LayoutTree, node: NodeId, constants: &AlgoConstants) -> Vec<FlexItem> {
-    tree.children(node)
-        .map(|child| (child, tree.style(child)))
-        .filter(|(_, style)| style.position != Position::Absolute)
-        .filter(|(_, style)| style.display != Display::None)
-        .map(|(child, child_style)| {
+fn generate_anonymous_flex_items(
+    tree: &impl LayoutTree,
+    node: NodeId,
+    constants: &AlgoConstants,
+    flex_items: &mut Vec<FlexItem>,
+) {
+    let items = tree
+        .children(node)
+        .enumerate()
+        .map(|(order, child)| (order, child, tree.style(child)))
+        .filter(|(_, _, style)| style.position != Position::Absolute)
+        .filter(|(_, _, style)| style.display != Display::None)
+        .map(|(order, child, child_style)| {
             let aspect_ratio = child_style.aspect_ratio;
+            let edge_basis = child_style.edge_percentage_basis(constants.node_inner_size);
             FlexItem {
                 node: child,
                 size: child_style.size.maybe_resolve(constants.node_inner_size).maybe_apply_aspect_ratio(aspect_ratio),
@@ -508,14 +596,20 @@ fn generate_anonymous_flex_items(tree: &impl LayoutTree, node: NodeId, constants
                     .max_size
                     .maybe_resolve(constants.node_inner_size)
                     .maybe_apply_aspect_ratio(aspect_ratio),
+                order: order as u32,
 
                 inset: child_style.inset.zip_size(constants.node_inner_size, |p, s| p.maybe_resolve(s)),
-                margin: child_style.margin.resolve_or_zero(constants.node_inner_size.width),
+                margin: child_style.margin.resolve_or_zero(edge_basis),
                 margin_is_auto: child_style.margin.map(|m| m == LengthPercentageAuto::Auto),
-                padding: child_style.padding.resolve_or_zero(constants.node_inner_size.width),
-                border: child_style.border.resolve_or_zero(constants.node_inner_size.width),
+                justify_self_margins: match child_style.justify_self.filter(|_| constants.justify_self) {
+                    Some(justify_self) => justify_self_margins(justify_self, constants.dir.is_reverse()),
+                    None => Line::FALSE,
+                },
+                padding: child_style.padding.resolve_or_zero(edge_basis),
+                border: child_style.border.resolve_or_zero(edge_basis),
                 align_self: child_style.align_self.unwrap_or(constants.align_items),
                 overflow: child_style.overflow,
+                disable_automatic_min_size: child_style.disable_automatic_min_size,
                 flex_grow: child_style.flex_grow,
                 flex_shrink: child_style.flex_shrink,
                 flex_basis: 0.0,
@@ -535,8 +629,27 @@ fn generate_anonymous_flex_items(tree: &impl LayoutTree, node: NodeId, constants
                 offset_main: 0.0,
                 offset_cross: 0.0,
             }
-        })
-        .collect()
+        });
+    flex_items.extend(items);
+}
+
+/// Returns whether the free space of a flex line is added to the main-axis start and end margins (in physical terms)
+/// of an item that is aligned by the given `justify_self`
+///
+/// `Start` and `End` refer to the physical start and end of the main axis, while `FlexStart` and `FlexEnd` follow
+/// the flex direction. Other alignments don't move the item.
+#[inline]
+fn justify_self_margins(justify_self: AlignSelf, is_reverse: bool) -> Line<bool> {
+    match (justify_self, is_reverse) {
+        (AlignSelf::Start, _) | (AlignSelf::FlexStart, false) | (AlignSelf::FlexEnd, true) => {
+            Line { start: false, end: true }
+        }
+        (AlignSelf::End, _) | (AlignSelf::FlexEnd, false) | (AlignSelf::FlexStart, true) => {
+            Line { start: true, end: false }
+        }
+        (AlignSelf::Center, _) => Line::TRUE,
+        (AlignSelf::Baseline | AlignSelf::Stretch, _) => Line::FALSE,
+    }
 }
 
 /// Determine the available main and cross space for the flex items.
@@ -739,10 +852,14 @@ fn determine_flex_base_size(
         // be set to their usual values in the cross axis so that wrapping content can wrap correctly.
         //
         // See https://drafts.csswg.org/css-sizing-3/#min-percentage-contribution
-        let style_min_main_size =
-            child.min_size.or(child.overflow.map(Overflow::maybe_into_automatic_min_size).into()).main(dir);
+        let style_min_main_size = child
+            .min_size
+            .or(child.overflow.map(Overflow::maybe_into_automatic_min_size).into())
+            .main(dir)
+            .or(child.disable_automatic_min_size.then_some(0.0));
 
-        child.resolved_minimum_main_size = style_min_main_size.unwrap_or({
+        // The item's content is only measured if its automatic minimum size is content-based
+        child.resolved_minimum_main_size = style_min_main_size.unwrap_or_else(|| {
             let min_content_size = {
                 let child_parent_size = Size::NONE.with_cross(dir, cross_axis_parent_size);
                 let child_available_space = Size::MIN_CONTENT.with_cross(dir, cross_axis_available_space);
@@ -1132,10 +1249,10 @@ fn resolve_flexible_lengths(line: &mut FlexLine, constants: &AlgoConstants, orig
                 })
                 .sum::<f32>();
 
-        let mut unfrozen: Vec<&mut FlexItem> = line.items.iter_mut().filter(|child| !child.frozen).collect();
-
+        // The set of unfrozen items only changes in step e, so it is re-filtered in each step rather than
+        // collected into a new vector on every iteration, which is costly for lines with thousands of items
         let (sum_flex_grow, sum_flex_shrink): (f32, f32) =
-            unfrozen.iter().fold((0.0, 0.0), |(flex_grow, flex_shrink), item| {
+            line.items.iter().filter(|child| !child.frozen).fold((0.0, 0.0), |(flex_grow, flex_shrink), item| {
                 (flex_grow + item.flex_grow, flex_shrink + item.flex_shrink)
             });
 
@@ -1171,17 +1288,21 @@ fn resolve_flexible_lengths(line: &mut FlexLine, constants: &AlgoConstants, orig
 
         if free_space.is_normal() {
             if growing && sum_flex_grow > 0.0 {
-                for child in &mut unfrozen {
+                for child in line.items.iter_mut().filter(|child| !child.frozen) {
                     child
                         .target_size
                         .set_main(constants.dir, child.flex_basis + free_space * (child.flex_grow / sum_flex_grow));
                 }
             } else if shrinking && sum_flex_shrink > 0.0 {
-                let sum_scaled_shrink_factor: f32 =
-                    unfrozen.iter().map(|child| child.inner_flex_basis * child.flex_shrink).sum();
+                let sum_scaled_shrink_factor: f32 = line
+                    .items
+                    .iter()
+                    .filter(|child| !child.frozen)
+                    .map(|child| child.inner_flex_basis * child.flex_shrink)
+                    .sum();
 
                 if sum_scaled_shrink_factor > 0.0 {
-                    for child in &mut unfrozen {
+                    for child in line.items.iter_mut().filter(|child| !child.frozen) {
                         let scaled_shrink_factor = child.inner_flex_basis * child.flex_shrink;
                         child.target_size.set_main(
                             constants.dir,
@@ -1197,7 +1318,7 @@ fn resolve_flexible_lengths(line: &mut FlexLine, constants: &AlgoConstants, orig
         //    item’s target main size was made smaller by this, it’s a max violation.
         //    If the item’s target main size was made larger by this, it’s a min violation.
 
-        let total_violation = unfrozen.iter_mut().fold(0.0, |acc, child| -> f32 {
+        let total_violation = line.items.iter_mut().filter(|child| !child.frozen).fold(0.0, |acc, child| -> f32 {
             let resolved_min_main: Option<f32> = child.resolved_minimum_main_size.into();
             let max_main = child.max_size.main(constants.dir);
             let clamped = child.target_size.main(constants.dir).maybe_clamp(resolved_min_main, max_main).max(0.0);
@@ -1220,7 +1341,7 @@ fn resolve_flexible_lengths(line: &mut FlexLine, constants: &AlgoConstants, orig
         //    - Negative
         //        Freeze all the items with max violations.
 
-        for child in &mut unfrozen {
+        for child in line.items.iter_mut().filter(|child| !child.frozen) {
             match total_violation {
                 v if v > 0.0 => child.frozen = child.violation > 0.0,
                 v if v < 0.0 => child.frozen = child.violation < 0.0,
@@ -1412,15 +1533,17 @@ fn calculate_cross_size(flex_lines: &mut [FlexLine], node_size: Size<Option<f32>
             //    3. The used cross-size of the flex line is the largest of the numbers found in the
             //       previous two steps and zero.
 
-            let max_baseline: f32 = line.items.iter().map(|child| child.baseline).fold(0.0, |acc, x| acc.max(x));
+            let max_baseline: f32 = line
+                .items
+                .iter()
+                .filter(|child| participates_in_baseline_alignment(child, constants))
+                .map(|child| child.baseline)
+                .fold(0.0, |acc, x| acc.max(x));
             line.cross_size = line
                 .items
                 .iter()
                 .map(|child| {
-                    if child.align_self == AlignSelf::Baseline
-                        && !child.margin_is_auto.cross_start(constants.dir)
-                        && !child.margin_is_auto.cross_end(constants.dir)
-                    {
+                    if participates_in_baseline_alignment(child, constants) {
                         max_baseline - child.baseline + child.hypothetical_outer_size.cross(constants.dir)
                     } else {
                         child.hypothetical_outer_size.cross(constants.dir)
@@ -1431,6 +1554,15 @@ fn calculate_cross_size(flex_lines: &mut [FlexLine], node_size: Size<Option<f32>
     }
 }
 
+/// Whether the item participates in the baseline alignment of its flex line: its align-self is baseline and neither of
+/// its cross-axis margins are auto
+#[inline]
+fn participates_in_baseline_alignment(child: &FlexItem, constants: &AlgoConstants) -> bool {
+    child.align_self == AlignSelf::Baseline
+        && !child.margin_is_auto.cross_start(constants.dir)
+        && !child.margin_is_auto.cross_end(constants.dir)
+}
+
 /// Handle 'align-content: stretch'.
 ///
 /// # [9.4. Cross Size Determination](https://www.w3.org/TR/css-flexbox-1/#cross-sizing)
@@ -1481,12 +1613,12 @@ fn determine_used_cross_size(tree: &impl LayoutTree, flex_lines: &mut [FlexLine]
 
         for child in line.items.iter_mut() {
             let child_style = tree.style(child.node);
+            let has_definite_size = child_style.size.cross(constants.dir) != Dimension::Auto;
             child.target_size.set_cross(
                 constants.dir,
-                if child.align_self == AlignSelf::Stretch
+                if apply_self_alignment_fallback(child.align_self, false, has_definite_size) == AlignSelf::Stretch
                     && !child.margin_is_auto.cross_start(constants.dir)
                     && !child.margin_is_auto.cross_end(constants.dir)
-                    && child_style.size.cross(constants.dir) == Dimension::Auto
                 {
                     // For some reason this particular usage of max_width is an exception to the rule that max_width's transfer
                     // using the aspect_ratio (if set). Both Chrome and Firefox agree on this. And reading the spec, it seems like
@@ -1530,10 +1662,10 @@ fn distribute_remaining_free_space(flex_lines: &mut [FlexLine], constants: &Algo
         let mut num_auto_margins = 0;
 
         for child in line.items.iter_mut() {
-            if child.margin_is_auto.main_start(constants.dir) {
+            if child.margin_is_auto.main_start(constants.dir) || child.justify_self_margins.start {
                 num_auto_margins += 1;
             }
-            if child.margin_is_auto.main_end(constants.dir) {
+            if child.margin_is_auto.main_end(constants.dir) || child.justify_self_margins.end {
                 num_auto_margins += 1;
             }
         }
@@ -1541,19 +1673,20 @@ fn distribute_remaining_free_space(flex_lines: &mut [FlexLine], constants: &Algo
         if free_space > 0.0 && num_auto_margins > 0 {
             let margin = free_space / num_auto_margins as f32;
 
+            // Items aligned by their justify_self keep their margins, with their share of the free space added on
             for child in line.items.iter_mut() {
-                if child.margin_is_auto.main_start(constants.dir) {
+                if child.margin_is_auto.main_start(constants.dir) || child.justify_self_margins.start {
                     if constants.is_row {
-                        child.margin.left = margin;
+                        child.margin.left += margin;
                     } else {
-                        child.margin.top = margin;
+                        child.margin.top += margin;
                     }
                 }
-                if child.margin_is_auto.main_end(constants.dir) {
+                if child.margin_is_auto.main_end(constants.dir) || child.justify_self_margins.end {
                     if constants.is_row {
-                        child.margin.right = margin;
+                        child.margin.right += margin;
                     } else {
-                        child.margin.bottom = margin;
+                        child.margin.bottom += margin;
                     }
                 }
             }
@@ -1593,7 +1726,16 @@ fn distribute_remaining_free_space(flex_lines: &mut [FlexLine], constants: &Algo
 fn resolve_cross_axis_auto_margins(flex_lines: &mut [FlexLine], constants: &AlgoConstants) {
     for line in flex_lines {
         let line_cross_size = line.cross_size;
-        let max_baseline: f32 = line.items.iter_mut().map(|child| child.baseline).fold(0.0, |acc, x| acc.max(x));
+
+        // Baseline-aligned items are placed flush against the cross-start edge of the line, which is its bottom edge
+        // in wrap-reverse containers. The largest distance between a participating item's baseline and its cross-start
+        // margin edge is therefore measured from the top of the item, or from its bottom in wrap-reverse containers.
+        let max_baseline: f32 = line
+            .items
+            .iter()
+            .filter(|child| participates_in_baseline_alignment(child, constants))
+            .map(|child| baseline_distance_from_cross_start(child, constants))
+            .fold(0.0, |acc, x| acc.max(x));
 
         for child in line.items.iter_mut() {
             let free_space = line_cross_size - child.outer_target_size.cross(constants.dir);
@@ -1626,6 +1768,17 @@ fn resolve_cross_axis_auto_margins(flex_lines: &mut [FlexLine], constants: &Algo
     }
 }
 
+/// The distance between the item's baseline and its outer cross-start edge, which is its bottom edge in wrap-reverse
+/// containers
+#[inline]
+fn baseline_distance_from_cross_start(child: &FlexItem, constants: &AlgoConstants) -> f32 {
+    if constants.is_wrap_reverse {
+        child.outer_target_size.cross(constants.dir) - child.baseline
+    } else {
+        child.baseline
+    }
+}
+
 /// Align all flex items along the cross-axis.
 ///
 /// # [9.6. Cross-Axis Alignment](https://www.w3.org/TR/css-flexbox-1/#cross-alignment)
@@ -1639,7 +1792,11 @@ fn align_flex_items_along_cross_axis(
     max_baseline: f32,
     constants: &AlgoConstants,
 ) -> f32 {
-    match child.align_self {
+    // Until we support vertical writing modes, baseline alignment only makes sense if the direction is row, so items
+    // in columns fall back to flex-start alignment
+    let participates_in_baseline_alignment = constants.is_row && participates_in_baseline_alignment(child, constants);
+    let has_definite_size = child.size.cross(constants.dir).is_some();
+    match apply_self_alignment_fallback(child.align_self, participates_in_baseline_alignment, has_definite_size) {
         AlignSelf::Start => 0.0,
         AlignSelf::FlexStart => {
             if constants.is_wrap_reverse {
@@ -1658,18 +1815,14 @@ fn align_flex_items_along_cross_axis(
         }
         AlignSelf::Center => free_space / 2.0,
         AlignSelf::Baseline => {
-            if constants.is_row {
-                max_baseline - child.baseline
+            let offset_from_cross_start = max_baseline - baseline_distance_from_cross_start(child, constants);
+            if constants.is_wrap_reverse {
+                free_space - offset_from_cross_start
             } else {
-                // Until we support vertical writing modes, baseline alignment only makes sense if
-                // the constants.direction is row, so we treat it as flex-start alignment in columns.
-                if constants.is_wrap_reverse {
-                    free_space
-                } else {
-                    0.0
-                }
+                offset_from_cross_start
             }
         }
+        // Stretched items fill their line, so they are positioned as for flex-start
         AlignSelf::Stretch => {
             if constants.is_wrap_reverse {
                 free_space
@@ -1745,7 +1898,6 @@ fn align_flex_lines_per_align_content(flex_lines: &mut [FlexLine], constants: &A
 #[allow(clippy::too_many_arguments)]
 fn calculate_flex_item(
     tree: &mut impl LayoutTree,
-    node: NodeId,
     item: &mut FlexItem,
     total_offset_main: &mut f32,
     total_offset_cross: f32,
@@ -1754,6 +1906,7 @@ fn calculate_flex_item(
     node_inner_size: Size<Option<f32>>,
     direction: FlexDirection,
 ) {
+    record_item_size_constraints(tree, item, direction);
     let preliminary_size_and_baselines = tree.perform_child_layout(
         item.node,
         item.target_size.map(|s| s.into()),
@@ -1776,7 +1929,8 @@ fn calculate_flex_item(
         + (item.inset.cross_start(direction).or(item.inset.cross_end(direction).map(|pos| -pos)).unwrap_or(0.0));
 
     if direction.is_row() {
-        let baseline_offset_cross = total_offset_cross + item.offset_cross + item.margin.cross_start(direction);
+        let baseline_offset_cross =
+            total_offset_cross + line_offset_cross + item.offset_cross + item.margin.cross_start(direction);
         let inner_baseline = preliminary_size_and_baselines.first_baselines.y.unwrap_or(preliminary_size.height);
         item.baseline = baseline_offset_cross + inner_baseline;
     } else {
@@ -1785,10 +1939,8 @@ fn calculate_flex_item(
         item.baseline = baseline_offset_main + inner_baseline;
     }
 
-    let order = tree.children(node).position(|n| n == item.node).unwrap() as u32;
-
     *tree.layout_mut(item.node) = Layout {
-        order,
+        order: item.order,
         size: preliminary_size_and_baselines.size,
         location: Point {
             x: if direction.is_row() { offset_main } else { offset_cross },
@@ -1799,11 +1951,51 @@ fn calculate_flex_item(
     *total_offset_main += item.offset_main + item.margin.main_axis_sum(direction) + preliminary_size.main(direction);
 }
 
+/// Records which constraints determined the final size of a flex-item, see [`LayoutTree::record_size_constraint`]
+fn record_item_size_constraints(tree: &mut impl LayoutTree, item: &FlexItem, direction: FlexDirection) {
+    let (main_axis, cross_axis) = match direction.is_row() {
+        true => (AbsoluteAxis::Horizontal, AbsoluteAxis::Vertical),
+        false => (AbsoluteAxis::Vertical, AbsoluteAxis::Horizontal),
+    };
+
+    let main_size = item.target_size.main(direction);
+    let main_constraint = if main_size == item.flex_basis {
+        // Items that didn't flex are explained by their own size styles, unless their basis was set explicitly
+        (tree.style(item.node).flex_basis != Dimension::Auto).then_some(SizeConstraint::FlexBasis)
+    } else if Some(main_size) == item.min_size.main(direction) || main_size == item.resolved_minimum_main_size {
+        Some(SizeConstraint::MinSize)
+    } else if Some(main_size) == item.max_size.main(direction) {
+        Some(SizeConstraint::MaxSize)
+    } else if main_size > item.flex_basis {
+        Some(SizeConstraint::FlexGrow)
+    } else {
+        Some(SizeConstraint::FlexShrink)
+    };
+    if let Some(constraint) = main_constraint {
+        tree.record_size_constraint(item.node, main_axis, constraint);
+    }
+
+    let cross_size = item.target_size.cross(direction);
+    let is_stretched = item.align_self == AlignSelf::Stretch
+        && item.size.cross(direction).is_none()
+        && !item.margin_is_auto.cross_start(direction)
+        && !item.margin_is_auto.cross_end(direction);
+    if is_stretched {
+        let cross_constraint = if Some(cross_size) == item.min_size.cross(direction) {
+            SizeConstraint::MinSize
+        } else if Some(cross_size) == item.max_size.cross(direction) {
+            SizeConstraint::MaxSize
+        } else {
+            SizeConstraint::Stretch
+        };
+        tree.record_size_constraint(item.node, cross_axis, cross_constraint);
+    }
+}
+
 /// Calculates the layout line
 #[allow(clippy::too_many_arguments)]
 fn calculate_layout_line(
     tree: &mut impl LayoutTree,
-    node: NodeId,
     line: &mut FlexLine,
     total_offset_cross: &mut f32,
     container_size: Size<f32>,
@@ -1818,7 +2010,6 @@ fn calculate_layout_line(
         for item in line.items.iter_mut().rev() {
             calculate_flex_item(
                 tree,
-                node,
                 item,
                 &mut total_offset_main,
                 *total_offset_cross,
@@ -1832,7 +2023,6 @@ fn calculate_layout_line(
         for item in line.items.iter_mut() {
             calculate_flex_item(
                 tree,
-                node,
                 item,
                 &mut total_offset_main,
                 *total_offset_cross,
@@ -1849,14 +2039,13 @@ fn calculate_layout_line(
 
 /// Do a final layout pass and collect the resulting layouts.
 #[inline]
-fn final_layout_pass(tree: &mut impl LayoutTree, node: NodeId, flex_lines: &mut [FlexLine], constants: &AlgoConstants) {
+fn final_layout_pass(tree: &mut impl LayoutTree, flex_lines: &mut [FlexLine], constants: &AlgoConstants) {
     let mut total_offset_cross = constants.content_box_inset.cross_start(constants.dir);
 
     if constants.is_wrap_reverse {
         for line in flex_lines.iter_mut().rev() {
             calculate_layout_line(
                 tree,
-                node,
                 line,
                 &mut total_offset_cross,
                 constants.container_size,
@@ -1869,7 +2058,6 @@ fn final_layout_pass(tree: &mut impl LayoutTree, node: NodeId, flex_lines: &mut
         for line in flex_lines.iter_mut() {
             calculate_layout_line(
                 tree,
-                node,
                 line,
                 &mut total_offset_cross,
                 constants.container_size,
@@ -1898,9 +2086,10 @@ fn perform_absolute_layout_on_absolute_children(tree: &mut impl LayoutTree, node
 
         let aspect_ratio = child_style.aspect_ratio;
         let align_self = child_style.align_self.unwrap_or(constants.align_items);
-        let margin = child_style.margin.map(|margin| margin.resolve_to_option(container_width));
-        let padding = child_style.padding.resolve_or_zero(Some(container_width));
-        let border = child_style.border.resolve_or_zero(Some(container_width));
+        let edge_basis = child_style.edge_percentage_basis(constants.container_size);
+        let margin = child_style.margin.maybe_resolve(edge_basis);
+        let padding = child_style.padding.resolve_or_zero(edge_basis.map(Some));
+        let border = child_style.border.resolve_or_zero(edge_basis.map(Some));
         let padding_border_sum = (padding + border).sum_axes();
 
         // Resolve inset
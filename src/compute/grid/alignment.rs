@@ -1,10 +1,12 @@
 //! Alignment of tracks and final positioning of items
-use super::types::GridTrack;
-use crate::compute::common::alignment::compute_alignment_offset;
-use crate::geometry::InBothAbsAxis;
+use super::types::{inner_gutters_size, GridTrack};
+use crate::compute::common::alignment::{
+    apply_alignment_fallback, apply_self_alignment_fallback, compute_alignment_offset,
+};
+use crate::geometry::{AbsoluteAxis, InBothAbsAxis};
 use crate::geometry::{Line, Point, Rect, Size};
 use crate::style::{AlignContent, AlignItems, AlignSelf, AvailableSpace, Position};
-use crate::tree::{Layout, SizingMode};
+use crate::tree::{Layout, SizeConstraint, SizingMode};
 use crate::tree::{LayoutTree, NodeId};
 use crate::util::sys::{f32_max, f32_min};
 use crate::util::MaybeMath;
@@ -13,18 +15,23 @@ use crate::util::{MaybeResolve, ResolveOrZero};
 /// Align the grid tracks within the grid according to the align-content (rows) or
 /// justify-content (columns) property. This only does anything if the size of the
 /// grid is not equal to the size of the grid container in the axis being aligned.
+/// Returns the offset of the first grid line.
 pub(super) fn align_tracks(
     grid_container_content_box_size: f32,
     padding: Line<f32>,
     border: Line<f32>,
     tracks: &mut [GridTrack],
     track_alignment_style: AlignContent,
-) {
-    let used_size: f32 = tracks.iter().map(|track| track.base_size).sum();
+) -> f32 {
+    let used_size: f32 = tracks.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(tracks);
     let size_diff = grid_container_content_box_size - used_size;
     let free_space = f32_max(size_diff, 0.0);
     let overflow = f32_min(size_diff, 0.0);
 
+    // Count the number of non-collapsed tracks
+    let num_tracks = tracks.iter().filter(|track| !track.is_collapsed).count();
+    let track_alignment_style = apply_alignment_fallback(size_diff, num_tracks, track_alignment_style);
+
     // If the used_size > grid_container_size then the tracks must overflow their container
     // The direction in which they do so is determined by the alignment style
     let origin = padding.start
@@ -41,10 +48,7 @@ pub(super) fn align_tracks(
             AlignContent::SpaceAround => 0.0,
         };
 
-    // Count the number of non-collapsed tracks (not counting gutters)
-    let num_tracks = tracks.iter().skip(1).step_by(2).filter(|track| !track.is_collapsed).count();
-
-    // Grid layout treats gaps as full tracks rather than applying them at alignment so we
+    // Grid layout adds the gutters to the offsets of the tracks rather than applying them at alignment so we
     // simply pass zero here. Grid layout is never reversed.
     let gap = 0.0;
     let layout_is_reversed = false;
@@ -52,21 +56,26 @@ pub(super) fn align_tracks(
     // Compute offsets
     let mut total_offset = origin;
     tracks.iter_mut().enumerate().for_each(|(i, track)| {
-        // Odd tracks are gutters (but slices are zero-indexed, so odd tracks have even indicies)
-        let is_gutter = i % 2 == 0;
-
-        // The first non-gutter track is index 1
-        let is_first = i == 1;
-
-        let offset = if is_gutter {
-            0.0
-        } else {
-            compute_alignment_offset(free_space, num_tracks, gap, track_alignment_style, layout_is_reversed, is_first)
-        };
+        let is_first = i == 0;
+        let offset =
+            compute_alignment_offset(free_space, num_tracks, gap, track_alignment_style, layout_is_reversed, is_first);
 
         track.offset = total_offset + offset;
-        total_offset = total_offset + offset + track.base_size;
+        total_offset = total_offset + offset + track.base_size + track.gutter_size;
     });
+
+    origin
+}
+
+/// Returns the offset of the grid line at the given index, given the aligned tracks of its axis and the offset of the first grid line
+pub(super) fn grid_line_offset(tracks: &[GridTrack], first_line_offset: f32, line_index: usize) -> f32 {
+    match line_index.checked_sub(1) {
+        Some(previous_track_index) => {
+            let previous_track = &tracks[previous_track_index];
+            previous_track.offset + previous_track.base_size
+        }
+        None => first_line_offset,
+    }
 }
 
 /// Align and size a grid item into it's final position
@@ -76,6 +85,7 @@ pub(super) fn align_and_position_item(
     order: u32,
     grid_area: Rect<f32>,
     container_alignment_styles: InBothAbsAxis<Option<AlignItems>>,
+    participates_in_baseline_alignment: bool,
     baseline_shim: f32,
 ) {
     let grid_area_size = Size { width: grid_area.right - grid_area.left, height: grid_area.bottom - grid_area.top };
@@ -88,8 +98,9 @@ pub(super) fn align_and_position_item(
     let position = style.position;
     let inset_horizontal = style.inset.horizontal_components().map(|size| size.resolve_to_option(grid_area_size.width));
     let inset_vertical = style.inset.vertical_components().map(|size| size.resolve_to_option(grid_area_size.height));
-    let padding = style.padding.map(|p| p.resolve_or_zero(Some(grid_area_size.width)));
-    let border = style.border.map(|p| p.resolve_or_zero(Some(grid_area_size.width)));
+    let edge_basis = style.edge_percentage_basis(grid_area_size);
+    let padding = style.padding.resolve_or_zero(edge_basis.map(Some));
+    let border = style.border.resolve_or_zero(edge_basis.map(Some));
     let padding_border_size = (padding + border).sum_axes();
     let inherent_size = style.size.maybe_resolve(grid_area_size).maybe_apply_aspect_ratio(aspect_ratio);
     let min_size = style
@@ -122,8 +133,8 @@ pub(super) fn align_and_position_item(
     };
 
     // Note: This is not a bug. It is part of the CSS spec that both horizontal and vertical margins
-    // resolve against the WIDTH of the grid area.
-    let margin = style.margin.map(|margin| margin.resolve_to_option(grid_area_size.width));
+    // resolve against the WIDTH of the grid area (unless the node opts into resolving vertical margins against the height).
+    let margin = style.margin.maybe_resolve(edge_basis);
 
     let grid_area_minus_item_margins_size = Size {
         width: grid_area_size.width.maybe_sub(margin.left).maybe_sub(margin.right),
@@ -186,6 +197,16 @@ pub(super) fn align_and_position_item(
     // Clamp size by min and max width/height
     let Size { width, height } = Size { width, height }.maybe_clamp(min_size, max_size);
 
+    // Items that were stretched (and not clamped) have been sized by the tracks that they span
+    if position != Position::Absolute {
+        if inherent_size.width.is_none() && width == Some(grid_area_minus_item_margins_size.width) {
+            tree.record_size_constraint(node, AbsoluteAxis::Horizontal, SizeConstraint::GridArea);
+        }
+        if inherent_size.height.is_none() && height == Some(grid_area_minus_item_margins_size.height) {
+            tree.record_size_constraint(node, AbsoluteAxis::Vertical, SizeConstraint::GridArea);
+        }
+    }
+
     // Layout node
     let measured_size_and_baselines = tree.perform_child_layout(
         node,
@@ -202,7 +223,8 @@ pub(super) fn align_and_position_item(
 
     let x = align_item_within_area(
         Line { start: grid_area.left, end: grid_area.right },
-        justify_self.unwrap_or(alignment_styles.horizontal),
+        // Baseline alignment is only supported in the vertical axis
+        apply_self_alignment_fallback(alignment_styles.horizontal, false, inherent_size.width.is_some()),
         width,
         position,
         inset_horizontal,
@@ -211,7 +233,11 @@ pub(super) fn align_and_position_item(
     );
     let y = align_item_within_area(
         Line { start: grid_area.top, end: grid_area.bottom },
-        align_self.unwrap_or(alignment_styles.vertical),
+        apply_self_alignment_fallback(
+            alignment_styles.vertical,
+            participates_in_baseline_alignment,
+            inherent_size.height.is_some(),
+        ),
         height,
         position,
         inset_vertical,
@@ -250,7 +276,7 @@ pub(super) fn align_item_within_area(
         AlignSelf::Start | AlignSelf::FlexStart => resolved_margin.start,
         AlignSelf::End | AlignSelf::FlexEnd => grid_area_size - resolved_size - resolved_margin.end,
         AlignSelf::Center => (grid_area_size - resolved_size + resolved_margin.start - resolved_margin.end) / 2.0,
-        // TODO: Add support for baseline alignment. For now we treat it as "start".
+        // Participating items are aligned to the baseline of their row by their baseline shim
         AlignSelf::Baseline => resolved_margin.start,
         AlignSelf::Stretch => resolved_margin.start,
     };
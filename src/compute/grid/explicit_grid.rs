@@ -2,9 +2,10 @@
 //! This mainly consists of evaluating GridAutoTracks
 use super::types::{GridTrack, TrackCounts};
 use crate::geometry::AbsoluteAxis;
-use crate::style::{GridTrackRepetition, LengthPercentage, NonRepeatedTrackSizingFunction, Style, TrackSizingFunction};
+use crate::style::{GridTrackRepetition, NonRepeatedTrackSizingFunction, Style, TrackSizingFunction};
 use crate::style_helpers::TaffyAuto;
-use crate::util::sys::{GridTrackVec, Vec};
+use crate::tree::AutoRepetitions;
+use crate::util::sys::Vec;
 use crate::util::MaybeMath;
 use crate::util::ResolveOrZero;
 
@@ -176,31 +177,28 @@ pub(crate) fn compute_explicit_grid_size_in_axis(style: &Style, axis: AbsoluteAx
     non_auto_repeating_track_count + (repetition_track_count * num_repetitions)
 }
 
-/// Resolve the track sizing functions of explicit tracks, automatically created tracks, and gutters
+/// Resolve the track sizing functions of explicit tracks and automatically created tracks
 /// given a set of track counts and all of the relevant styles
 pub(super) fn initialize_grid_tracks(
     tracks: &mut Vec<GridTrack>,
     counts: TrackCounts,
-    track_template: &GridTrackVec<TrackSizingFunction>,
-    auto_tracks: &Vec<NonRepeatedTrackSizingFunction>,
-    gap: LengthPercentage,
+    track_template: &[TrackSizingFunction],
+    auto_tracks: &[NonRepeatedTrackSizingFunction],
     track_has_items: impl Fn(usize) -> bool,
 ) {
-    // Clear vector (in case this is a re-layout), reserve space for all tracks ahead of time to reduce allocations,
-    // and push the initial gutter
+    // Clear vector (in case this is a re-layout) and reserve space for all tracks ahead of time to reduce allocations
     tracks.clear();
-    tracks.reserve((counts.len() * 2) + 1);
-    tracks.push(GridTrack::gutter(gap));
+    tracks.reserve(counts.len());
 
     // Create negative implicit tracks
     if counts.negative_implicit > 0 {
         if auto_tracks.is_empty() {
             let iter = core::iter::repeat(NonRepeatedTrackSizingFunction::AUTO);
-            create_implicit_tracks(tracks, counts.negative_implicit, iter, gap)
+            create_implicit_tracks(tracks, counts.negative_implicit, iter)
         } else {
             let offset = auto_tracks.len() - (counts.negative_implicit as usize % auto_tracks.len());
             let iter = auto_tracks.iter().copied().cycle().skip(offset);
-            create_implicit_tracks(tracks, counts.negative_implicit, iter, gap)
+            create_implicit_tracks(tracks, counts.negative_implicit, iter)
         }
     }
 
@@ -218,7 +216,6 @@ pub(super) fn initialize_grid_tracks(
                         sizing_function.min_sizing_function(),
                         sizing_function.max_sizing_function(),
                     ));
-                    tracks.push(GridTrack::gutter(gap));
                     current_track_index += 1;
                 }
                 TrackSizingFunction::Repeat(Count(count), repeated_tracks) => {
@@ -228,7 +225,6 @@ pub(super) fn initialize_grid_tracks(
                             sizing_function.min_sizing_function(),
                             sizing_function.max_sizing_function(),
                         ));
-                        tracks.push(GridTrack::gutter(gap));
                         current_track_index += 1;
                     });
                 }
@@ -238,16 +234,13 @@ pub(super) fn initialize_grid_tracks(
                     for track_def in iter.take(auto_repeated_track_count) {
                         let mut track =
                             GridTrack::new(track_def.min_sizing_function(), track_def.max_sizing_function());
-                        let mut gutter = GridTrack::gutter(gap);
 
                         // Auto-fit tracks that don't contain should be collapsed.
                         if *repetition_kind == AutoFit && !track_has_items(current_track_index) {
                             track.collapse();
-                            gutter.collapse();
                         }
 
                         tracks.push(track);
-                        tracks.push(gutter);
 
                         current_track_index += 1;
                     }
@@ -259,15 +252,52 @@ pub(super) fn initialize_grid_tracks(
     // Create positive implicit tracks
     if auto_tracks.is_empty() {
         let iter = core::iter::repeat(NonRepeatedTrackSizingFunction::AUTO);
-        create_implicit_tracks(tracks, counts.positive_implicit, iter, gap)
+        create_implicit_tracks(tracks, counts.positive_implicit, iter)
     } else {
         let iter = auto_tracks.iter().copied().cycle();
-        create_implicit_tracks(tracks, counts.positive_implicit, iter, gap)
+        create_implicit_tracks(tracks, counts.positive_implicit, iter)
+    }
+}
+
+/// Describe the tracks produced by the auto-repeated track definition of `track_template`, if it has one, given the
+/// tracks initialized from it by [`initialize_grid_tracks`]
+pub(crate) fn auto_repetitions(
+    tracks: &[GridTrack],
+    counts: TrackCounts,
+    track_template: &[TrackSizingFunction],
+) -> Option<AutoRepetitions> {
+    use GridTrackRepetition::{AutoFill, AutoFit, Count};
+    if counts.explicit == 0 {
+        return None;
+    }
+
+    let mut first_track = 0;
+    for track_sizing_function in track_template {
+        match track_sizing_function {
+            TrackSizingFunction::Single(_) => first_track += 1,
+            TrackSizingFunction::Repeat(Count(count), repeated_tracks) => {
+                first_track += count * repeated_tracks.len() as u16
+            }
+            TrackSizingFunction::Repeat(kind @ (AutoFit | AutoFill), repeated_tracks) => {
+                // Matches the number of tracks created by initialize_grid_tracks
+                let track_count = counts.explicit - (track_template.len() as u16 - 1);
+                let first_track_index = (counts.negative_implicit + first_track) as usize;
+                let collapsed = tracks[first_track_index..]
+                    .iter()
+                    .take(track_count as usize)
+                    .map(|track| track.is_collapsed)
+                    .collect();
+                return Some(AutoRepetitions {
+                    kind: *kind,
+                    repetitions: track_count / repeated_tracks.len() as u16,
+                    first_track,
+                    collapsed,
+                });
+            }
+        }
     }
 
-    // Mark first and last grid lines as collapsed
-    tracks.first_mut().unwrap().collapse();
-    tracks.last_mut().unwrap().collapse();
+    None
 }
 
 /// Utility function for repeating logic of creating implicit tracks
@@ -275,12 +305,10 @@ fn create_implicit_tracks(
     tracks: &mut Vec<GridTrack>,
     count: u16,
     mut auto_tracks_iter: impl Iterator<Item = NonRepeatedTrackSizingFunction>,
-    gap: LengthPercentage,
 ) {
     for _ in 0..count {
         let track_def = auto_tracks_iter.next().unwrap();
         tracks.push(GridTrack::new(track_def.min_sizing_function(), track_def.max_sizing_function()));
-        tracks.push(GridTrack::gutter(gap));
     }
 }
 
@@ -288,7 +316,6 @@ fn create_implicit_tracks(
 mod test {
     use super::compute_explicit_grid_size_in_axis;
     use super::initialize_grid_tracks;
-    use crate::compute::grid::types::GridTrackKind;
     use crate::compute::grid::types::TrackCounts;
     use crate::compute::grid::util::*;
     use crate::geometry::AbsoluteAxis;
@@ -309,8 +336,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             size: Size { width: length(120.0), height: length(80.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -325,8 +352,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             size: Size { width: length(140.0), height: length(90.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -341,8 +368,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             min_size: Size { width: length(120.0), height: length(80.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -357,8 +384,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             min_size: Size { width: length(140.0), height: length(90.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -373,8 +400,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             size: Size { width: length(140.0), height: length(100.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0), length(20.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0), length(10.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0), length(20.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0), length(10.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -389,8 +416,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             size: Size { width: length(140.0), height: length(100.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             gap: length(20.0),
             ..Default::default()
         };
@@ -405,8 +432,8 @@ mod test {
         use GridTrackRepetition::AutoFill;
         let grid_style = Style {
             display: Display::Grid,
-            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0), percent(0.5), length(20.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(40.0), percent(0.5), length(20.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             gap: length(20.0),
             ..Default::default()
         };
@@ -422,8 +449,8 @@ mod test {
         let grid_style = Style {
             display: Display::Grid,
             size: Size { width: length(140.0), height: length(100.0) },
-            grid_template_columns: vec![length(20.0), repeat(AutoFill, vec![length(40.0)])],
-            grid_template_rows: vec![length(40.0), repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![length(20.0), repeat(AutoFill, vec![length(40.0)])].into(),
+            grid_template_rows: vec![length(40.0), repeat(AutoFill, vec![length(20.0)])].into(),
             gap: length(20.0),
             ..Default::default()
         };
@@ -440,8 +467,8 @@ mod test {
             display: Display::Grid,
             size: Size { width: length(120.0), height: length(120.0) },
             padding: Rect { left: length(10.0), right: length(10.0), top: length(20.0), bottom: length(20.0) },
-            grid_template_columns: vec![repeat(AutoFill, vec![length(20.0)])],
-            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])],
+            grid_template_columns: vec![repeat(AutoFill, vec![length(20.0)])].into(),
+            grid_template_rows: vec![repeat(AutoFill, vec![length(20.0)])].into(),
             ..Default::default()
         };
         let width = compute_explicit_grid_size_in_axis(&grid_style, AbsoluteAxis::Horizontal);
@@ -452,8 +479,6 @@ mod test {
 
     #[test]
     fn test_initialize_grid_tracks() {
-        let px0 = LengthPercentage::Length(0.0);
-        let px20 = LengthPercentage::Length(20.0);
         let px100 = LengthPercentage::Length(100.0);
 
         // Setup test
@@ -461,43 +486,30 @@ mod test {
         let track_counts =
             TrackCounts { negative_implicit: 3, explicit: track_template.len() as u16, positive_implicit: 3 };
         let auto_tracks = vec![auto(), length(100.0)];
-        let gap = px20;
 
         // Call function
         let mut tracks = Vec::new();
-        initialize_grid_tracks(&mut tracks, track_counts, &track_template, &auto_tracks, gap, |_| false);
+        initialize_grid_tracks(&mut tracks, track_counts, &track_template, &auto_tracks, |_| false);
 
         // Assertions
         let expected = vec![
-            // Gutter
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px0), MaxTrackSizingFunction::Fixed(px0)),
             // Negative implict tracks
-            (GridTrackKind::Track, MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
+            (MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
+            (MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
+            (MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
             // Explicit tracks
-            (GridTrackKind::Track, MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fraction(2.0)), // Note: separate min-max functions
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Fraction(1.0)), // Note: min sizing function of flex sizing functions is auto
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
+            (MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
+            (MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fraction(2.0)), // Note: separate min-max functions
+            (MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Fraction(1.0)), // Note: min sizing function of flex sizing functions is auto
             // Positive implict tracks
-            (GridTrackKind::Track, MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px20), MaxTrackSizingFunction::Fixed(px20)),
-            (GridTrackKind::Track, MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
-            (GridTrackKind::Gutter, MinTrackSizingFunction::Fixed(px0), MaxTrackSizingFunction::Fixed(px0)),
+            (MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
+            (MinTrackSizingFunction::Fixed(px100), MaxTrackSizingFunction::Fixed(px100)),
+            (MinTrackSizingFunction::Auto, MaxTrackSizingFunction::Auto),
         ];
 
         assert_eq!(tracks.len(), expected.len(), "Number of tracks doesn't match");
 
-        for (idx, (actual, (kind, min, max))) in tracks.into_iter().zip(expected).enumerate() {
-            assert_eq!(actual.kind, kind, "Track {idx} (0-based index)");
+        for (idx, (actual, (min, max))) in tracks.into_iter().zip(expected).enumerate() {
             assert_eq!(actual.min_track_sizing_function, min, "Track {idx} (0-based index)");
             assert_eq!(actual.max_track_sizing_function, max, "Track {idx} (0-based index)");
         }
@@ -2,26 +2,29 @@
 //! <https://www.w3.org/TR/css-grid-1>
 use crate::geometry::{AbsoluteAxis, AbstractAxis, InBothAbsAxis};
 use crate::geometry::{Line, Point, Rect, Size};
-use crate::style::{AlignContent, AlignItems, AlignSelf, AvailableSpace, Display, Overflow, Position};
+use crate::style::{AlignContent, AlignItems, AvailableSpace, Display, LengthPercentage, Overflow, Position};
 use crate::style_helpers::*;
 use crate::tree::{Layout, RunMode, SizeBaselinesAndMargins, SizingMode};
 use crate::tree::{LayoutTree, NodeId};
 use crate::util::sys::{f32_max, GridTrackVec, Vec};
 use crate::util::MaybeMath;
 use crate::util::{MaybeResolve, ResolveOrZero};
-use alignment::{align_and_position_item, align_tracks};
-use explicit_grid::{compute_explicit_grid_size_in_axis, initialize_grid_tracks};
+use alignment::{align_and_position_item, align_tracks, grid_line_offset};
+use explicit_grid::{auto_repetitions, compute_explicit_grid_size_in_axis, initialize_grid_tracks};
 use implicit_grid::compute_grid_size_estimate;
 use placement::place_grid_items;
 use track_sizing::{
-    determine_if_item_crosses_flexible_or_intrinsic_tracks, resolve_item_track_indexes, track_sizing_algorithm,
+    determine_if_item_crosses_flexible_or_intrinsic_tracks, resolve_gutter_sizes, resolve_item_track_indexes,
+    track_sizing_algorithm,
 };
-use types::{CellOccupancyMatrix, GridTrack};
+use types::{inner_gutters_size, CellOccupancyMatrix, GridTrack};
 
 pub(crate) use types::{GridCoordinate, GridLine, OriginZeroLine};
 
+#[cfg(feature = "alloc_audit")]
+use crate::tree::{audit_allocation, AllocationSite};
 #[cfg(feature = "debug")]
-use crate::util::debug::NODE_LOGGER;
+use crate::util::debug::debug_event;
 
 use super::LayoutAlgorithm;
 
@@ -95,7 +98,8 @@ pub fn compute(
     // 2. Grid Item Placement
     // Match items (children) to a definite grid position (row start/end and column start/end position)
     let mut items = Vec::with_capacity(tree.child_count(node));
-    let mut cell_occupancy_matrix = CellOccupancyMatrix::with_track_counts(est_col_counts, est_row_counts);
+    let mut cell_occupancy_matrix =
+        CellOccupancyMatrix::with_track_counts(est_col_counts, est_row_counts, style.grid_auto_flow.primary_axis());
     let in_flow_children_iter = || {
         tree.children(node)
             .enumerate()
@@ -112,12 +116,14 @@ pub fn compute(
     );
 
     // Extract track counts from previous step (auto-placement can expand the number of tracks)
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, node, AllocationSite::GridItems, items.capacity());
     let final_col_counts = *cell_occupancy_matrix.track_counts(AbsoluteAxis::Horizontal);
     let final_row_counts = *cell_occupancy_matrix.track_counts(AbsoluteAxis::Vertical);
 
     // 3. Initialize Tracks
-    // Initialize (explicit and implicit) grid tracks (and gutters)
-    // This resolves the min and max track sizing functions for all tracks and gutters
+    // Initialize (explicit and implicit) grid tracks
+    // This resolves the min and max track sizing functions for all tracks
     let mut columns = GridTrackVec::new();
     let mut rows = GridTrackVec::new();
     initialize_grid_tracks(
@@ -125,7 +131,6 @@ pub fn compute(
         final_col_counts,
         &style.grid_template_columns,
         &style.grid_auto_columns,
-        style.gap.width,
         |column_index| cell_occupancy_matrix.column_is_occupied(column_index),
     );
     initialize_grid_tracks(
@@ -133,14 +138,18 @@ pub fn compute(
         final_row_counts,
         &style.grid_template_rows,
         &style.grid_auto_rows,
-        style.gap.height,
         |row_index| cell_occupancy_matrix.row_is_occupied(row_index),
     );
+    #[cfg(feature = "alloc_audit")]
+    for tracks in [&columns, &rows] {
+        audit_allocation(tree, node, AllocationSite::GridTracks, tracks.capacity());
+    }
 
     // 4. Compute "available grid space"
     // https://www.w3.org/TR/css-grid-1/#available-grid-space
-    let padding = style.padding.resolve_or_zero(parent_size.width);
-    let border = style.border.resolve_or_zero(parent_size.width);
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let padding = style.padding.resolve_or_zero(edge_basis);
+    let border = style.border.resolve_or_zero(edge_basis);
     let padding_border = padding + border;
     let padding_border_size = padding_border.sum_axes();
     let aspect_ratio = style.aspect_ratio;
@@ -150,11 +159,11 @@ pub fn compute(
 
     // Scrollbar gutters are reserved when the `overflow` property is set to `Overflow::Scroll`.
     // However, the axis are switched (transposed) because a node that scrolls vertically needs
-    // *horizontal* space to be reserved for a scrollbar
-    let scrollbar_gutter = style.overflow.transpose().map(|overflow| match overflow {
-        Overflow::Scroll => style.scrollbar_width,
-        _ => 0.0,
-    });
+    // *horizontal* space to be reserved for its (vertical) scrollbar, whose thickness is the width of `scrollbar_width`
+    let scrollbar_gutter = Point {
+        x: if style.overflow.y == Overflow::Scroll { style.scrollbar_width.width } else { 0.0 },
+        y: if style.overflow.x == Overflow::Scroll { style.scrollbar_width.height } else { 0.0 },
+    };
     // TODO: make side configurable based on the `direction` property
     let mut content_box_inset = padding_border;
     content_box_inset.right += scrollbar_gutter.x;
@@ -187,14 +196,23 @@ pub fn compute(
     };
 
     #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("parent_size", parent_size);
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("outer_node_size", outer_node_size);
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("inner_node_size", inner_node_size);
+    debug_event!(
+        tree,
+        node,
+        Some(CssGridAlgorithm::NAME),
+        "resolve_container_size",
+        "parent_size" => parent_size,
+        "outer_node_size" => outer_node_size,
+        "inner_node_size" => inner_node_size,
+    );
 
     // 5. Track Sizing
 
+    // Resolve the gutters in both axis up front, as the sizes of the gutters in the opposite axis are used when
+    // estimating the size of items while sizing the columns
+    resolve_gutter_sizes(&mut columns, style.gap.width, inner_node_size.width);
+    resolve_gutter_sizes(&mut rows, style.gap.height, inner_node_size.height);
+
     // Convert grid placements in origin-zero coordinates to indexes into the GridTrack (rows and columns) vectors
     // This computation is relatively trivial, but it requires the final number of negative (implicit) tracks in
     // each axis, and doing it up-front here means we don't have to keep repeating that calculation
@@ -205,7 +223,7 @@ pub fn compute(
     determine_if_item_crosses_flexible_or_intrinsic_tracks(&mut items, &columns, &rows);
 
     // Determine if the grid has any baseline aligned items
-    let has_baseline_aligned_item = items.iter().any(|item| item.align_self == AlignSelf::Baseline);
+    let has_baseline_aligned_item = items.iter().any(|item| item.participates_in_baseline_alignment());
 
     // Run track sizing algorithm for Inline axis
     track_sizing_algorithm(
@@ -213,6 +231,7 @@ pub fn compute(
         AbstractAxis::Inline,
         min_size.get(AbstractAxis::Inline),
         max_size.get(AbstractAxis::Inline),
+        style.gap.width,
         style.grid_align_content(AbstractAxis::Block),
         available_grid_space,
         inner_node_size,
@@ -222,7 +241,7 @@ pub fn compute(
         |track: &GridTrack, parent_size: Option<f32>| track.max_track_sizing_function.definite_value(parent_size),
         has_baseline_aligned_item,
     );
-    let initial_column_sum = columns.iter().map(|track| track.base_size).sum::<f32>();
+    let initial_column_sum = columns.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(&columns);
     inner_node_size.width = inner_node_size.width.or_else(|| initial_column_sum.into());
 
     items.iter_mut().for_each(|item| item.available_space_cache = None);
@@ -233,6 +252,7 @@ pub fn compute(
         AbstractAxis::Block,
         min_size.get(AbstractAxis::Block),
         max_size.get(AbstractAxis::Block),
+        style.gap.height,
         style.grid_align_content(AbstractAxis::Inline),
         available_grid_space,
         inner_node_size,
@@ -242,7 +262,7 @@ pub fn compute(
         |track: &GridTrack, _| Some(track.base_size),
         false, // TODO: Support baseline alignment in the vertical axis
     );
-    let initial_row_sum = rows.iter().map(|track| track.base_size).sum::<f32>();
+    let initial_row_sum = rows.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(&rows);
     inner_node_size.height = inner_node_size.height.or_else(|| initial_row_sum.into());
 
     // 6. Compute container size
@@ -269,6 +289,14 @@ pub fn compute(
         return container_border_box.into();
     }
 
+    tree.record_auto_repetitions(
+        node,
+        Size {
+            width: auto_repetitions(&columns, final_col_counts, &style.grid_template_columns),
+            height: auto_repetitions(&rows, final_row_counts, &style.grid_template_rows),
+        },
+    );
+
     // 7. Resolve percentage track base sizes
     // In the case of an indefinitely sized container these resolve to zero during the "Initialise Tracks" step
     // and therefore need to be re-resolved here based on the content-sized content box of the container
@@ -280,6 +308,7 @@ pub fn compute(
                 column.max_track_sizing_function.resolved_percentage_size(container_content_box.width);
             column.base_size = column.base_size.maybe_clamp(min, max);
         }
+        resolve_gutter_sizes(&mut columns, style.gap.width, Some(container_content_box.width));
     }
     if !available_grid_space.height.is_definite() {
         for row in &mut rows {
@@ -287,6 +316,17 @@ pub fn compute(
             let max: Option<f32> = row.max_track_sizing_function.resolved_percentage_size(container_content_box.height);
             row.base_size = row.base_size.maybe_clamp(min, max);
         }
+        resolve_gutter_sizes(&mut rows, style.gap.height, Some(container_content_box.height));
+    }
+
+    // Percentages that couldn't be resolved during the initial track sizing because the grid container's size was
+    // indefinite (including fit-content() limits, which were treated as infinite) are resolved against the content
+    // box of the container when track sizing is re-run below
+    if outer_node_size.width.is_none() {
+        inner_node_size.width = Some(container_content_box.width);
+    }
+    if outer_node_size.height.is_none() {
+        inner_node_size.height = Some(container_content_box.height);
     }
 
     // Column sizing must be re-run (once) if:
@@ -295,8 +335,9 @@ pub fn compute(
     // TODO: Only rerun sizing for tracks that actually require it rather than for all tracks if any need it.
     let mut rerun_column_sizing;
 
-    let has_percentage_column = columns.iter().any(|track| track.uses_percentage());
-    let parent_width_indefinite = !available_space.width.is_definite();
+    let has_percentage_column = columns.iter().any(|track| track.uses_percentage())
+        || (columns.len() > 1 && matches!(style.gap.width, LengthPercentage::Percent(_)));
+    let parent_width_indefinite = outer_node_size.width.is_none();
     rerun_column_sizing = parent_width_indefinite && has_percentage_column;
 
     if !rerun_column_sizing {
@@ -341,6 +382,7 @@ pub fn compute(
             AbstractAxis::Inline,
             min_size.get(AbstractAxis::Inline),
             max_size.get(AbstractAxis::Inline),
+            style.gap.width,
             style.grid_align_content(AbstractAxis::Block),
             available_grid_space,
             inner_node_size,
@@ -350,75 +392,78 @@ pub fn compute(
             |track: &GridTrack, _| Some(track.base_size),
             has_baseline_aligned_item,
         );
+    }
 
-        // Row sizing must be re-run (once) if:
-        //   - The grid container's height was initially indefinite and there are any rows with percentage track sizing functions
-        //   - Any grid item crossing an intrinsically sized track's min content contribution height has changed
-        // TODO: Only rerun sizing for tracks that actually require it rather than for all tracks if any need it.
-        let mut rerun_row_sizing;
-
-        let has_percentage_row = rows.iter().any(|track| track.uses_percentage());
-        let parent_height_indefinite = !available_space.height.is_definite();
-        rerun_row_sizing = parent_height_indefinite && has_percentage_row;
-
-        if !rerun_row_sizing {
-            let min_content_contribution_changed = items
-                .iter_mut()
-                .filter(|item| item.crosses_intrinsic_column)
-                .map(|item| {
-                    let available_space = item.available_space(
-                        AbstractAxis::Block,
-                        &columns,
-                        inner_node_size.width,
-                        |track: &GridTrack, _| Some(track.base_size),
-                    );
-                    let new_min_content_contribution =
-                        item.min_content_contribution(AbstractAxis::Block, tree, available_space, inner_node_size);
-
-                    let has_changed = Some(new_min_content_contribution) != item.min_content_contribution_cache.height;
-
-                    item.available_space_cache = Some(available_space);
-                    item.min_content_contribution_cache.height = Some(new_min_content_contribution);
-                    item.max_content_contribution_cache.height = None;
-                    item.minimum_contribution_cache.height = None;
-
-                    has_changed
-                })
-                .any(|has_changed| has_changed);
-            rerun_row_sizing = min_content_contribution_changed;
-        } else {
-            items.iter_mut().for_each(|item| {
-                // Clear intrisic height caches
-                item.available_space_cache = None;
-                item.min_content_contribution_cache.height = None;
+    // Row sizing must be re-run (once) if:
+    //   - The grid container's height was initially indefinite and there are any rows with percentage track sizing functions
+    //   - Column sizing was re-run, and any grid item crossing an intrinsically sized track's min content contribution
+    //     height has changed as a result
+    // TODO: Only rerun sizing for tracks that actually require it rather than for all tracks if any need it.
+    let mut rerun_row_sizing;
+
+    let has_percentage_row = rows.iter().any(|track| track.uses_percentage())
+        || (rows.len() > 1 && matches!(style.gap.height, LengthPercentage::Percent(_)));
+    let parent_height_indefinite = outer_node_size.height.is_none();
+    rerun_row_sizing = parent_height_indefinite && has_percentage_row;
+
+    if rerun_row_sizing {
+        items.iter_mut().for_each(|item| {
+            // Clear intrisic height caches
+            item.available_space_cache = None;
+            item.min_content_contribution_cache.height = None;
+            item.max_content_contribution_cache.height = None;
+            item.minimum_contribution_cache.height = None;
+        });
+    } else if rerun_column_sizing {
+        let min_content_contribution_changed = items
+            .iter_mut()
+            .filter(|item| item.crosses_intrinsic_column)
+            .map(|item| {
+                let available_space = item.available_space(
+                    AbstractAxis::Block,
+                    &columns,
+                    inner_node_size.width,
+                    |track: &GridTrack, _| Some(track.base_size),
+                );
+                let new_min_content_contribution =
+                    item.min_content_contribution(AbstractAxis::Block, tree, available_space, inner_node_size);
+
+                let has_changed = Some(new_min_content_contribution) != item.min_content_contribution_cache.height;
+
+                item.available_space_cache = Some(available_space);
+                item.min_content_contribution_cache.height = Some(new_min_content_contribution);
                 item.max_content_contribution_cache.height = None;
                 item.minimum_contribution_cache.height = None;
-            });
-        }
 
-        if rerun_row_sizing {
-            // Re-run track sizing algorithm for Block axis
-            track_sizing_algorithm(
-                tree,
-                AbstractAxis::Block,
-                min_size.get(AbstractAxis::Block),
-                max_size.get(AbstractAxis::Block),
-                style.grid_align_content(AbstractAxis::Inline),
-                available_grid_space,
-                inner_node_size,
-                &mut rows,
-                &mut columns,
-                &mut items,
-                |track: &GridTrack, _| Some(track.base_size),
-                false, // TODO: Support baseline alignment in the vertical axis
-            );
-        }
+                has_changed
+            })
+            .any(|has_changed| has_changed);
+        rerun_row_sizing = min_content_contribution_changed;
+    }
+
+    if rerun_row_sizing {
+        // Re-run track sizing algorithm for Block axis
+        track_sizing_algorithm(
+            tree,
+            AbstractAxis::Block,
+            min_size.get(AbstractAxis::Block),
+            max_size.get(AbstractAxis::Block),
+            style.gap.height,
+            style.grid_align_content(AbstractAxis::Inline),
+            available_grid_space,
+            inner_node_size,
+            &mut rows,
+            &mut columns,
+            &mut items,
+            |track: &GridTrack, _| Some(track.base_size),
+            false, // TODO: Support baseline alignment in the vertical axis
+        );
     }
 
     // 8. Track Alignment
 
     // Align columns
-    align_tracks(
+    let first_column_line_offset = align_tracks(
         container_content_box.get(AbstractAxis::Inline),
         Line { start: padding.left, end: padding.right },
         Line { start: border.left, end: border.right },
@@ -426,7 +471,7 @@ pub fn compute(
         style.justify_content.unwrap_or(AlignContent::Stretch),
     );
     // Align rows
-    align_tracks(
+    let first_row_line_offset = align_tracks(
         container_content_box.get(AbstractAxis::Block),
         Line { start: padding.top, end: padding.bottom },
         Line { start: border.top, end: border.bottom },
@@ -444,10 +489,10 @@ pub fn compute(
     // Position in-flow children (stored in items vector)
     for (index, item) in items.iter().enumerate() {
         let grid_area = Rect {
-            top: rows[item.row_indexes.start as usize + 1].offset,
-            bottom: rows[item.row_indexes.end as usize].offset,
-            left: columns[item.column_indexes.start as usize + 1].offset,
-            right: columns[item.column_indexes.end as usize].offset,
+            top: rows[item.row_indexes.start as usize].offset,
+            bottom: grid_line_offset(&rows, first_row_line_offset, item.row_indexes.end as usize),
+            left: columns[item.column_indexes.start as usize].offset,
+            right: grid_line_offset(&columns, first_column_line_offset, item.column_indexes.end as usize),
         };
         align_and_position_item(
             tree,
@@ -455,6 +500,7 @@ pub fn compute(
             index as u32,
             grid_area,
             container_alignment_styles,
+            item.participates_in_baseline_alignment(),
             item.baseline_shim,
         );
     }
@@ -502,19 +548,25 @@ pub fn compute(
                 });
 
             let grid_area = Rect {
-                top: maybe_row_indexes.start.map(|index| rows[index].offset).unwrap_or(border.top),
+                top: maybe_row_indexes
+                    .start
+                    .map(|index| grid_line_offset(&rows, first_row_line_offset, index))
+                    .unwrap_or(border.top),
                 bottom: maybe_row_indexes
                     .end
-                    .map(|index| rows[index].offset)
+                    .map(|index| grid_line_offset(&rows, first_row_line_offset, index))
                     .unwrap_or(container_border_box.height - border.bottom),
-                left: maybe_col_indexes.start.map(|index| columns[index].offset).unwrap_or(border.left),
+                left: maybe_col_indexes
+                    .start
+                    .map(|index| grid_line_offset(&columns, first_column_line_offset, index))
+                    .unwrap_or(border.left),
                 right: maybe_col_indexes
                     .end
-                    .map(|index| columns[index].offset)
+                    .map(|index| grid_line_offset(&columns, first_column_line_offset, index))
                     .unwrap_or(container_border_box.width - border.right),
             };
             // TODO: Baseline alignment support for absolutely positioned items (should check if is actuallty specified)
-            align_and_position_item(tree, child, order, grid_area, container_alignment_styles, 0.0);
+            align_and_position_item(tree, child, order, grid_area, container_alignment_styles, false, 0.0);
             order += 1;
         }
     });
@@ -536,10 +588,10 @@ pub fn compute(
         let first_row_items = &items[0..].split(|item| item.row_indexes.start != first_row).next().unwrap();
 
         // Check if any items in *this row* are baseline aligned
-        let row_has_baseline_item = first_row_items.iter().any(|item| item.align_self == AlignSelf::Baseline);
+        let row_has_baseline_item = first_row_items.iter().any(|item| item.participates_in_baseline_alignment());
 
         let item = if row_has_baseline_item {
-            first_row_items.iter().find(|item| item.align_self == AlignSelf::Baseline).unwrap()
+            first_row_items.iter().find(|item| item.participates_in_baseline_alignment()).unwrap()
         } else {
             &first_row_items[0]
         };
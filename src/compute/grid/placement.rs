@@ -196,16 +196,12 @@ fn place_definite_secondary_axis_item(
     loop {
         let primary_axis_placement = placement.get(primary_axis).resolve_indefinite_grid_tracks(position);
 
-        let does_fit = cell_occupancy_matrix.line_area_is_unoccupied(
-            primary_axis,
-            primary_axis_placement,
-            secondary_axis_placement,
-        );
-
-        if does_fit {
-            return (primary_axis_placement, secondary_axis_placement);
-        } else {
-            position += 1;
+        let occupied_area_end =
+            cell_occupancy_matrix.occupied_area_end(primary_axis, primary_axis_placement, secondary_axis_placement);
+
+        match occupied_area_end {
+            None => return (primary_axis_placement, secondary_axis_placement),
+            Some(end) => position = end,
         }
     }
 }
@@ -231,8 +227,8 @@ fn place_indefinitely_positioned_item(
     let secondary_axis_grid_start_line =
         cell_occupancy_matrix.track_counts(primary_axis.other_axis()).implicit_start_line();
 
-    let line_area_is_occupied = |primary_span, secondary_span| {
-        !cell_occupancy_matrix.line_area_is_unoccupied(primary_axis, primary_span, secondary_span)
+    let occupied_area_end = |primary_span, secondary_span| {
+        cell_occupancy_matrix.occupied_area_end(primary_axis, primary_span, secondary_span)
     };
 
     let (mut primary_idx, mut secondary_idx) = grid_position;
@@ -256,7 +252,7 @@ fn place_indefinitely_positioned_item(
             let secondary_span = Line { start: secondary_idx, end: secondary_idx + secondary_span };
 
             // If area is occupied, increment the index and try again
-            if line_area_is_occupied(primary_span, secondary_span) {
+            if occupied_area_end(primary_span, secondary_span).is_some() {
                 secondary_idx += 1;
                 continue;
             }
@@ -281,9 +277,9 @@ fn place_indefinitely_positioned_item(
                 continue;
             }
 
-            // If area is occupied, increment the primary index and try again
-            if line_area_is_occupied(primary_span, secondary_span) {
-                primary_idx += 1;
+            // If area is occupied, skip the primary index past the occupied cells and try again
+            if let Some(end) = occupied_area_end(primary_span, secondary_span) {
+                primary_idx = end;
                 continue;
             }
 
@@ -372,7 +368,7 @@ mod tests {
             let estimated_sizes = compute_grid_size_estimate(explicit_col_count, explicit_row_count, child_styles_iter);
             let mut items = Vec::new();
             let mut cell_occupancy_matrix =
-                CellOccupancyMatrix::with_track_counts(estimated_sizes.0, estimated_sizes.1);
+                CellOccupancyMatrix::with_track_counts(estimated_sizes.0, estimated_sizes.1, flow.primary_axis());
 
             // Run placement algorithm
             place_grid_items(
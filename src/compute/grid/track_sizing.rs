@@ -1,12 +1,10 @@
 //! Implements the track sizing algorithm
 //! <https://www.w3.org/TR/css-grid-1/#layout-algorithm>
-use super::types::{GridItem, GridTrack, TrackCounts};
+use super::types::{inner_gutters_size, GridItem, GridTrack, TrackCounts};
 use crate::geometry::AbstractAxis;
 use crate::geometry::{Line, Size};
 use crate::prelude::{LayoutTree, TaffyMinContent};
-use crate::style::{
-    AlignContent, AlignSelf, AvailableSpace, LengthPercentage, MaxTrackSizingFunction, MinTrackSizingFunction,
-};
+use crate::style::{AlignContent, AvailableSpace, LengthPercentage, MaxTrackSizingFunction, MinTrackSizingFunction};
 use crate::tree::SizingMode;
 use crate::util::sys::{f32_max, f32_min, Vec};
 use crate::util::MaybeMath;
@@ -108,7 +106,7 @@ where
     /// to zero if the container size is indefinite as otherwise this would introduce a cyclic dependency.
     #[inline(always)]
     fn margins_axis_sums_with_baseline_shims(&self, item: &GridItem) -> Size<f32> {
-        item.margins_axis_sums_with_baseline_shims(self.inner_node_size.width)
+        item.margins_axis_sums_with_baseline_shims(self.inner_node_size)
     }
 
     /// Retrieve the item's min content contribution from the cache or compute it using the provided parameters
@@ -131,6 +129,44 @@ where
         contribution + margin_axis_sums.get(self.axis)
     }
 
+    /// Measure the min-content and max-content contributions of the items that cross an intrinsic track in a single
+    /// batch, filling their contribution caches
+    ///
+    /// The contributions of the items don't depend on each other at this stage, which allows the tree to measure them
+    /// in parallel.
+    #[cfg(feature = "rayon")]
+    fn measure_content_contributions(&mut self, items: &mut [GridItem]) {
+        let axis = self.axis;
+        let mut requests = Vec::new();
+        let mut contributions = Vec::new();
+        for (index, item) in items.iter_mut().enumerate().filter(|(_, item)| item.crosses_intrinsic_track(axis)) {
+            let available_space = self.available_space(item);
+            for (is_max_content, cache, intrinsic_space) in [
+                (false, item.min_content_contribution_cache, AvailableSpace::MinContent),
+                (true, item.max_content_contribution_cache, AvailableSpace::MaxContent),
+            ] {
+                if cache.get(axis).is_none() {
+                    requests.push(item.content_contribution_request(
+                        available_space,
+                        self.inner_node_size,
+                        intrinsic_space,
+                    ));
+                    contributions.push((index, is_max_content));
+                }
+            }
+        }
+
+        let mut sizes = vec![Size::ZERO; requests.len()];
+        self.tree.measure_child_sizes(&requests, &mut sizes);
+        for ((index, is_max_content), size) in contributions.into_iter().zip(sizes) {
+            let item = &mut items[index];
+            match is_max_content {
+                false => item.min_content_contribution_cache.set(axis, Some(size.get(axis))),
+                true => item.max_content_contribution_cache.set(axis, Some(size.get(axis))),
+            }
+        }
+    }
+
     /// The minimum contribution of an item is the smallest outer size it can have.
     /// Specifically:
     ///   - If the item’s computed preferred size behaves as auto or depends on the size of its containing block in the relevant axis:
@@ -180,7 +216,7 @@ pub(super) fn compute_alignment_gutter_adjustment(
     get_track_size_estimate: impl Fn(&GridTrack, Option<f32>) -> Option<f32>,
     tracks: &[GridTrack],
 ) -> f32 {
-    if tracks.len() <= 1 {
+    if tracks.is_empty() {
         return 0.0;
     }
 
@@ -219,11 +255,11 @@ pub(super) fn compute_alignment_gutter_adjustment(
             .iter()
             .map(|track| get_track_size_estimate(track, Some(axis_inner_node_size)))
             .sum::<Option<f32>>()
-            .map(|track_size_sum| f32_max(0.0, axis_inner_node_size - track_size_sum))
+            .map(|track_size_sum| f32_max(0.0, axis_inner_node_size - track_size_sum - inner_gutters_size(tracks)))
             .unwrap_or(0.0);
 
         let weighted_track_count =
-            (((tracks.len() - 3) / 2) * inner_gutter_weight as usize) + (2 * outer_gutter_weight as usize);
+            ((tracks.len() - 1) * inner_gutter_weight as usize) + (2 * outer_gutter_weight as usize);
 
         return (free_space / weighted_track_count as f32) * inner_gutter_weight as f32;
     }
@@ -248,19 +284,32 @@ pub(super) fn determine_if_item_crosses_flexible_or_intrinsic_tracks(
     rows: &[GridTrack],
 ) {
     for item in items {
-        item.crosses_flexible_column =
-            item.track_range_excluding_lines(AbstractAxis::Inline).any(|i| columns[i].is_flexible());
+        item.crosses_flexible_column = item.track_range(AbstractAxis::Inline).any(|i| columns[i].is_flexible());
         item.crosses_intrinsic_column =
-            item.track_range_excluding_lines(AbstractAxis::Inline).any(|i| columns[i].has_intrinsic_sizing_function());
-        item.crosses_flexible_row =
-            item.track_range_excluding_lines(AbstractAxis::Block).any(|i| rows[i].is_flexible());
+            item.track_range(AbstractAxis::Inline).any(|i| columns[i].has_intrinsic_sizing_function());
+        item.crosses_flexible_row = item.track_range(AbstractAxis::Block).any(|i| rows[i].is_flexible());
         item.crosses_intrinsic_row =
-            item.track_range_excluding_lines(AbstractAxis::Block).any(|i| rows[i].has_intrinsic_sizing_function());
+            item.track_range(AbstractAxis::Block).any(|i| rows[i].has_intrinsic_sizing_function());
+    }
+}
+
+/// Resolve the size of the gutter following each track in an axis, given the size of the gap between tracks
+/// Percentage gaps resolve to zero if the size of the grid container is indefinite.
+pub(super) fn resolve_gutter_sizes(
+    axis_tracks: &mut [GridTrack],
+    gap: LengthPercentage,
+    axis_inner_node_size: Option<f32>,
+) {
+    let gap = gap.resolve_or_zero(axis_inner_node_size);
+    let last_track_idx = axis_tracks.len().saturating_sub(1);
+    for (index, track) in axis_tracks.iter_mut().enumerate() {
+        // The gutters at the start and end of the grid, and those following collapsed tracks, are always zero-sized.
+        track.gutter_size = if track.is_collapsed || index == last_track_idx { 0.0 } else { gap };
     }
 }
 
 /// Track sizing algorithm
-/// Note: Gutters are treated as empty fixed-size tracks for the purpose of the track sizing algorithm.
+/// Note: Gutters are treated as fixed amounts of space between tracks for the purpose of the track sizing algorithm.
 #[allow(clippy::too_many_arguments)]
 #[inline(always)]
 pub(super) fn track_sizing_algorithm<Tree: LayoutTree>(
@@ -268,6 +317,7 @@ pub(super) fn track_sizing_algorithm<Tree: LayoutTree>(
     axis: AbstractAxis,
     axis_min_size: Option<f32>,
     axis_max_size: Option<f32>,
+    axis_gap: LengthPercentage,
     other_axis_alignment: AlignContent,
     available_grid_space: Size<AvailableSpace>,
     inner_node_size: Size<Option<f32>>,
@@ -280,6 +330,7 @@ pub(super) fn track_sizing_algorithm<Tree: LayoutTree>(
     // 11.4 Initialise Track sizes
     // Initialize each track’s base size and growth limit.
     initialize_track_sizes(axis_tracks, inner_node_size.get(axis));
+    resolve_gutter_sizes(axis_tracks, axis_gap, inner_node_size.get(axis));
 
     // 11.5.1 Shim item baselines
     if has_baseline_aligned_item {
@@ -302,12 +353,8 @@ pub(super) fn track_sizing_algorithm<Tree: LayoutTree>(
         &get_track_size_estimate,
         other_axis_tracks,
     );
-    if other_axis_tracks.len() > 3 {
-        let len = other_axis_tracks.len();
-        let inner_gutter_tracks = other_axis_tracks[2..len].iter_mut().step_by(2);
-        for track in inner_gutter_tracks {
-            track.content_alignment_adjustment = gutter_alignment_adjustment;
-        }
+    for track in other_axis_tracks.iter_mut() {
+        track.content_alignment_adjustment = gutter_alignment_adjustment;
     }
 
     // 11.5 Resolve Intrinsic Track Sizes
@@ -388,16 +435,7 @@ fn flush_planned_growth_limit_increases(tracks: &mut [GridTrack], set_infinitely
 /// Initialize each track’s base size and growth limit.
 #[inline(always)]
 fn initialize_track_sizes(axis_tracks: &mut [GridTrack], axis_inner_node_size: Option<f32>) {
-    let last_track_idx = axis_tracks.len() - 1;
-
-    // First and last grid lines are always zero-sized.
-    axis_tracks[0].base_size = 0.0;
-    axis_tracks[0].growth_limit = 0.0;
-    axis_tracks[last_track_idx].base_size = 0.0;
-    axis_tracks[last_track_idx].growth_limit = 0.0;
-
-    let all_but_first_and_last = 1..last_track_idx;
-    for track in axis_tracks[all_but_first_and_last].iter_mut() {
+    for track in axis_tracks.iter_mut() {
         // For each track, if the track’s min track sizing function is:
         // - A fixed sizing function
         //     Resolve to an absolute length and use that size as the track’s initial base size.
@@ -462,13 +500,13 @@ fn resolve_item_baselines(
         // Count how many items in *this row* are baseline aligned
         // If a row has one or zero items participating in baseline alignment then baseline alignment is a no-op
         // for those items and we skip further computations for that row
-        let row_baseline_item_count = row_items.iter().filter(|item| item.align_self == AlignSelf::Baseline).count();
+        let row_baseline_item_count = row_items.iter().filter(|item| item.participates_in_baseline_alignment()).count();
         if row_baseline_item_count <= 1 {
             continue;
         }
 
-        // Compute the baselines of all items in the row
-        for item in row_items.iter_mut() {
+        // Compute the baselines of the items in the row that participate in baseline alignment
+        for item in row_items.iter_mut().filter(|item| item.participates_in_baseline_alignment()) {
             let measured_size_and_baselines = tree.perform_child_layout(
                 item.node,
                 Size::NONE,
@@ -481,15 +519,17 @@ fn resolve_item_baselines(
             let baseline = measured_size_and_baselines.first_baselines.y;
             let height = measured_size_and_baselines.size.height;
 
-            item.baseline = Some(baseline.unwrap_or(height) + item.margin.top.resolve_or_zero(inner_node_size.width));
+            let margin_top =
+                item.margin.top.resolve_or_zero(item.vertical_percentage_basis.resolve(inner_node_size).height);
+            item.baseline = Some(baseline.unwrap_or(height) + margin_top);
         }
 
         // Compute the max baseline of all items in the row
         let row_max_baseline =
             row_items.iter().map(|item| item.baseline.unwrap_or(0.0)).max_by(|a, b| a.total_cmp(b)).unwrap();
 
-        // Compute the baseline shim for each item in the row
-        for item in row_items.iter_mut() {
+        // Compute the baseline shim for each participating item in the row
+        for item in row_items.iter_mut().filter(|item| item.participates_in_baseline_alignment()) {
             item.baseline_shim = row_max_baseline - item.baseline.unwrap_or(0.0);
         }
     }
@@ -534,6 +574,9 @@ fn resolve_intrinsic_track_sizes(
     let mut item_sizer =
         IntrisicSizeMeasurer { tree, other_axis_tracks, axis, inner_node_size, get_track_size_estimate };
 
+    #[cfg(feature = "rayon")]
+    item_sizer.measure_content_contributions(items);
+
     let mut batched_item_iterator = ItemBatcher::new(axis);
     while let Some((batch, is_flex)) = batched_item_iterator.next(items) {
         // 2. Size tracks to fit non-spanning items: For each track with an intrinsic track sizing function and not a flexible sizing function,
@@ -541,7 +584,7 @@ fn resolve_intrinsic_track_sizes(
         let batch_span = batch[0].placement(axis).span();
         if !is_flex && batch_span == 1 {
             for item in batch.iter_mut() {
-                let track_index = item.placement_indexes(axis).start + 1;
+                let track_index = item.placement_indexes(axis).start;
                 let track = &axis_tracks[track_index as usize];
 
                 // Handle base sizes
@@ -666,7 +709,7 @@ fn resolve_intrinsic_track_sizes(
                 }
                 _ => item_sizer.minimum_contribution(item, axis_tracks),
             };
-            let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+            let tracks = &mut axis_tracks[item.track_range(axis)];
             if space > 0.0 {
                 if item.overflow.get(axis).is_scroll_container() {
                     let fit_content_limit = move |track: &GridTrack| track.fit_content_limit(axis_inner_node_size);
@@ -703,7 +746,7 @@ fn resolve_intrinsic_track_sizes(
         };
         for item in batch.iter_mut() {
             let space = item_sizer.min_content_contribution(item);
-            let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+            let tracks = &mut axis_tracks[item.track_range(axis)];
             if space > 0.0 {
                 if item.overflow.get(axis).is_scroll_container() {
                     let fit_content_limit = move |track: &GridTrack| track.fit_content_limit(axis_inner_node_size);
@@ -765,7 +808,7 @@ fn resolve_intrinsic_track_sizes(
                 let axis_max_content_size = item_sizer.max_content_contribution(item);
                 let limit = item.spanned_track_limit(axis, axis_tracks, axis_inner_node_size);
                 let space = axis_max_content_size.maybe_min(limit);
-                let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+                let tracks = &mut axis_tracks[item.track_range(axis)];
                 if space > 0.0 {
                     // If any of the tracks spanned by the item have a MaxContent min track sizing function then
                     // distribute space only to those tracks. Otherwise distribute space to tracks with an Auto min
@@ -812,7 +855,7 @@ fn resolve_intrinsic_track_sizes(
         for item in batch.iter_mut() {
             let axis_max_content_size = item_sizer.max_content_contribution(item);
             let space = axis_max_content_size;
-            let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+            let tracks = &mut axis_tracks[item.track_range(axis)];
             if space > 0.0 {
                 distribute_item_space_to_base_size(
                     is_flex,
@@ -844,7 +887,7 @@ fn resolve_intrinsic_track_sizes(
             for item in batch.iter_mut() {
                 let axis_min_content_size = item_sizer.min_content_contribution(item);
                 let space = axis_min_content_size;
-                let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+                let tracks = &mut axis_tracks[item.track_range(axis)];
                 if space > 0.0 {
                     distribute_item_space_to_growth_limit(
                         space,
@@ -867,7 +910,7 @@ fn resolve_intrinsic_track_sizes(
             for item in batch.iter_mut() {
                 let axis_max_content_size = item_sizer.max_content_contribution(item);
                 let space = axis_max_content_size;
-                let tracks = &mut axis_tracks[item.track_range_excluding_lines(axis)];
+                let tracks = &mut axis_tracks[item.track_range(axis)];
                 if space > 0.0 {
                     distribute_item_space_to_growth_limit(
                         space,
@@ -957,7 +1000,7 @@ fn distribute_item_space_to_base_size(
         let get_base_size = |track: &GridTrack| track.base_size;
 
         // 1. Find the space to distribute
-        let track_sizes: f32 = tracks.iter().map(|track| track.base_size).sum();
+        let track_sizes: f32 = tracks.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(tracks);
         let extra_space: f32 = f32_max(0.0, space - track_sizes);
 
         // 2. Distribute space up to limits:
@@ -1047,7 +1090,8 @@ fn distribute_item_space_to_growth_limit(
     let track_sizes: f32 = tracks
         .iter()
         .map(|track| if track.growth_limit == f32::INFINITY { track.base_size } else { track.growth_limit })
-        .sum();
+        .sum::<f32>()
+        + inner_gutters_size(tracks);
     let extra_space: f32 = f32_max(0.0, space - track_sizes);
 
     // 2. Distribute space up to limits:
@@ -1102,7 +1146,8 @@ fn maximise_tracks(
     axis_inner_node_size: Option<f32>,
     axis_available_grid_space: AvailableSpace,
 ) {
-    let used_space: f32 = axis_tracks.iter().map(|track| track.base_size).sum();
+    let used_space: f32 =
+        axis_tracks.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(axis_tracks);
     let free_space = axis_available_grid_space.compute_free_space(used_space);
     if free_space == f32::INFINITY {
         axis_tracks.iter_mut().for_each(|track| track.base_size = track.growth_limit);
@@ -1144,7 +1189,8 @@ fn expand_flexible_tracks(
         //   The used flex fraction is the result of finding the size of an fr using all of the grid tracks and
         //   a space to fill of the available grid space.
         AvailableSpace::Definite(available_space) => {
-            let used_space: f32 = axis_tracks.iter().map(|track| track.base_size).sum();
+            let used_space: f32 =
+                axis_tracks.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(axis_tracks);
             let free_space = available_space - used_space;
             if free_space <= 0.0 {
                 0.0
@@ -1179,7 +1225,7 @@ fn expand_flexible_tracks(
                     .iter_mut()
                     .filter(|item| item.crosses_flexible_track(axis))
                     .map(|item| {
-                        let tracks = &axis_tracks[item.track_range_excluding_lines(axis)];
+                        let tracks = &axis_tracks[item.track_range(axis)];
                         // TODO: plumb estimate of other axis size (known_dimensions) in here rather than just passing Size::NONE?
                         let max_content_contribution =
                             item.max_content_contribution_cached(axis, tree, Size::NONE, inner_node_size);
@@ -1201,7 +1247,8 @@ fn expand_flexible_tracks(
                     }
                     _ => track.base_size,
                 })
-                .sum();
+                .sum::<f32>()
+                + inner_gutters_size(axis_tracks);
             let axis_min_size = axis_min_size.unwrap_or(0.0);
             let axis_max_size = axis_max_size.unwrap_or(f32::INFINITY);
             if hypothetical_grid_size < axis_min_size {
@@ -1244,7 +1291,7 @@ fn find_size_of_fr(tracks: &[GridTrack], space_to_fill: f32) -> f32 {
         // Let leftover space be the space to fill minus the base sizes of the non-flexible grid tracks.
         // Let flex factor sum be the sum of the flex factors of the flexible tracks. If this value is less than 1, set it to 1 instead.
         // We compute both of these in a single loop to avoid iterating over the data twice
-        let mut used_space = 0.0;
+        let mut used_space = inner_gutters_size(tracks);
         let mut naive_flex_factor_sum = 0.0;
         for track in tracks.iter() {
             match track.max_track_sizing_function {
@@ -1295,7 +1342,8 @@ fn stretch_auto_tracks(
     let num_auto_tracks =
         axis_tracks.iter().filter(|track| track.max_track_sizing_function == MaxTrackSizingFunction::Auto).count();
     if num_auto_tracks > 0 {
-        let used_space: f32 = axis_tracks.iter().map(|track| track.base_size).sum();
+        let used_space: f32 =
+            axis_tracks.iter().map(|track| track.base_size).sum::<f32>() + inner_gutters_size(axis_tracks);
 
         // If the free space is indefinite, but the grid container has a definite min-width/height
         // use that size to calculate the free space for this step instead.
@@ -3,11 +3,10 @@ use super::TrackCounts;
 use crate::compute::grid::OriginZeroLine;
 use crate::geometry::AbsoluteAxis;
 use crate::geometry::Line;
-use crate::util::sys::Vec;
+use crate::util::sys::{BTreeMap, Vec};
 use core::cmp::{max, min};
 use core::fmt::Debug;
 use core::ops::Range;
-use grid::Grid;
 
 /// The occupancy state of a single grid cell
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
@@ -21,11 +20,29 @@ pub(crate) enum CellOccupancyState {
     AutoPlaced,
 }
 
-/// A dynamically sized matrix (2d grid) which tracks the occupancy of each grid cell during auto-placement
+/// A run of consecutive occupied cells within a single track, which all have the same occupancy state
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OccupiedRun {
+    /// The OriginZero line at which the run starts
+    start: i16,
+    /// The OriginZero line at which the run ends (exclusive)
+    end: i16,
+    /// The occupancy state of the cells in the run
+    state: CellOccupancyState,
+}
+
+/// A sparse, dynamically sized matrix (2d grid) which tracks the occupancy of each grid cell during auto-placement
 /// It also keeps tabs on how many tracks there are and which tracks are implicit and which are explicit.
+///
+/// Cells are stored per track in the secondary axis of the auto-placement (i.e. per row when items flow along rows),
+/// as sorted runs of occupied cells along the primary axis. Only occupied cells take up space, expanding the grid
+/// doesn't move any data, and the auto-placement cursor can skip past an occupied run in a single step, which keeps
+/// placing large numbers of items close to linear.
 pub(crate) struct CellOccupancyMatrix {
-    /// The grid of occupancy states
-    inner: Grid<CellOccupancyState>,
+    /// The axis along which the runs of each track extend
+    run_axis: AbsoluteAxis,
+    /// The occupied runs of each track in the other axis, keyed by the OriginZero line at the start of the track
+    tracks: BTreeMap<i16, Vec<OccupiedRun>>,
     /// The counts of implicit and explicit columns
     columns: TrackCounts,
     /// The counts of implicit and explicit rows
@@ -47,9 +64,11 @@ impl Debug for CellOccupancyMatrix {
         )?;
         writeln!(f, "State:")?;
 
-        for row_idx in 0..self.inner.rows() {
-            for cell in self.inner.iter_row(row_idx) {
-                let letter = match *cell {
+        for row_idx in 0..self.rows.len() as u16 {
+            let row = self.rows.track_to_prev_oz_line(row_idx);
+            for col_idx in 0..self.columns.len() as u16 {
+                let column = self.columns.track_to_prev_oz_line(col_idx);
+                let letter = match self.cell_state(row, column) {
                     CellOccupancyState::Unoccupied => '_',
                     CellOccupancyState::DefinitelyPlaced => 'D',
                     CellOccupancyState::AutoPlaced => 'A',
@@ -64,10 +83,10 @@ impl Debug for CellOccupancyMatrix {
 }
 
 impl CellOccupancyMatrix {
-    /// Create a CellOccupancyMatrix given a set of provisional track counts. The grid can expand as needed to fit more tracks,
-    /// the provisional track counts represent a best effort attempt to avoid the extra allocations this requires.
-    pub fn with_track_counts(columns: TrackCounts, rows: TrackCounts) -> Self {
-        Self { inner: Grid::new(rows.len(), columns.len()), rows, columns }
+    /// Create a CellOccupancyMatrix given a set of provisional track counts, for items that are auto-placed along
+    /// `primary_axis`. The grid expands as needed to fit more tracks.
+    pub fn with_track_counts(columns: TrackCounts, rows: TrackCounts, primary_axis: AbsoluteAxis) -> Self {
+        Self { run_axis: primary_axis, tracks: BTreeMap::new(), rows, columns }
     }
 
     /// Determines whether the specified area fits within the tracks currently represented by the matrix
@@ -88,54 +107,35 @@ impl CellOccupancyMatrix {
     }
 
     /// Expands the grid (potentially in all 4 directions) in order to ensure that the specified range fits within the allocated space
+    ///
+    /// Cells are stored in OriginZero coordinates, so only the track counts need to be updated.
     fn expand_to_fit_range(&mut self, row_range: Range<i16>, col_range: Range<i16>) {
         // Calculate number of rows and columns missing to accomodate ranges (if any)
-        let req_negative_rows = min(row_range.start, 0);
+        let req_negative_rows = -min(row_range.start, 0);
         let req_positive_rows = max(row_range.end - self.rows.len() as i16, 0);
-        let req_negative_cols = min(col_range.start, 0);
+        let req_negative_cols = -min(col_range.start, 0);
         let req_positive_cols = max(col_range.end - self.columns.len() as i16, 0);
 
-        let old_row_count = self.rows.len();
-        let old_col_count = self.columns.len();
-        let new_row_count = old_row_count + (req_negative_rows + req_positive_rows) as usize;
-        let new_col_count = old_col_count + (req_negative_cols + req_positive_cols) as usize;
-
-        let mut data = Vec::with_capacity(new_row_count * new_col_count);
-
-        // Push new negative rows
-        for _ in 0..(req_negative_rows as usize * new_col_count) {
-            data.push(CellOccupancyState::Unoccupied);
-        }
-
-        // Push existing rows
-        for row in 0..old_row_count {
-            // Push new negative columns
-            for _ in 0..req_negative_cols {
-                data.push(CellOccupancyState::Unoccupied);
-            }
-            // Push existing columns
-            for col in 0..old_col_count {
-                data.push(*self.inner.get(row, col).unwrap());
-            }
-            // Push new positive columns
-            for _ in 0..req_positive_cols {
-                data.push(CellOccupancyState::Unoccupied);
-            }
-        }
-
-        // Push new negative rows
-        for _ in 0..(req_positive_rows as usize * new_col_count) {
-            data.push(CellOccupancyState::Unoccupied);
-        }
-
-        // Update self with new data
-        self.inner = Grid::from_vec(data, new_col_count);
         self.rows.negative_implicit += req_negative_rows as u16;
         self.rows.positive_implicit += req_positive_rows as u16;
         self.columns.negative_implicit += req_negative_cols as u16;
         self.columns.positive_implicit += req_positive_cols as u16;
     }
 
+    /// Converts spans in the given primary axis into spans along the runs of the matrix and across its tracks
+    fn to_run_spans(
+        &self,
+        primary_axis: AbsoluteAxis,
+        primary_span: Line<OriginZeroLine>,
+        secondary_span: Line<OriginZeroLine>,
+    ) -> (Line<OriginZeroLine>, Line<OriginZeroLine>) {
+        if primary_axis == self.run_axis {
+            (primary_span, secondary_span)
+        } else {
+            (secondary_span, primary_span)
+        }
+    }
+
     /// Mark an area of the matrix as occupied, expanding the allocated space as necessary to accomodate the passed area.
     pub fn mark_area_as(
         &mut self,
@@ -149,72 +149,87 @@ impl CellOccupancyMatrix {
             AbsoluteAxis::Vertical => (primary_span, secondary_span),
         };
 
-        let mut col_range = self.columns.oz_line_range_to_track_range(column_span);
-        let mut row_range = self.rows.oz_line_range_to_track_range(row_span);
-
         // Check that if the resolved ranges fit within the allocated grid. And if they don't then expand the grid to fit
-        // and then re-resolve the ranges once the grid has been expanded as the resolved indexes may have changed
+        let col_range = self.columns.oz_line_range_to_track_range(column_span);
+        let row_range = self.rows.oz_line_range_to_track_range(row_span);
         let is_in_range = self.is_area_in_range(AbsoluteAxis::Horizontal, col_range.clone(), row_range.clone());
         if !is_in_range {
-            self.expand_to_fit_range(row_range.clone(), col_range.clone());
-            col_range = self.columns.oz_line_range_to_track_range(column_span);
-            row_range = self.rows.oz_line_range_to_track_range(row_span);
+            self.expand_to_fit_range(row_range, col_range);
         }
 
-        for x in row_range {
-            for y in col_range.clone() {
-                *self.inner.get_mut(x as usize, y as usize).unwrap() = value;
-            }
+        let (run_span, track_span) = self.to_run_spans(primary_axis, primary_span, secondary_span);
+        for track in track_span.start.0..track_span.end.0 {
+            mark_run(self.tracks.entry(track).or_default(), run_span.start.0, run_span.end.0, value);
         }
     }
 
-    /// Determines whether a grid area specified by the bounding grid lines in OriginZero coordinates
-    /// is entirely unnocupied. Returns true if all grid cells within the grid area are unnocupied, else false.
-    pub fn line_area_is_unoccupied(
+    /// Determines whether a grid area specified by the bounding grid lines in OriginZero coordinates is occupied.
+    /// Returns `None` if all grid cells within the grid area are unoccupied, else the line in the primary axis up to
+    /// which an area of the same size that starts anywhere from `primary_span.start` would also be occupied.
+    ///
+    /// This allows auto-placement to skip over occupied cells in a single step rather than one track at a time.
+    pub fn occupied_area_end(
         &self,
         primary_axis: AbsoluteAxis,
         primary_span: Line<OriginZeroLine>,
         secondary_span: Line<OriginZeroLine>,
-    ) -> bool {
-        let primary_range = self.track_counts(primary_axis).oz_line_range_to_track_range(primary_span);
-        let secondary_range = self.track_counts(primary_axis.other_axis()).oz_line_range_to_track_range(secondary_span);
-        self.track_area_is_unoccupied(primary_axis, primary_range, secondary_range)
+    ) -> Option<OriginZeroLine> {
+        let (run_span, track_span) = self.to_run_spans(primary_axis, primary_span, secondary_span);
+        let mut occupied_until: Option<i16> = None;
+        for (_, runs) in self.tracks.range(track_span.start.0..track_span.end.0) {
+            // Every run overlapping the area also overlaps any area that starts before the end of that run
+            let first = runs.partition_point(|run| run.end <= run_span.start.0);
+            let last = runs.partition_point(|run| run.start < run_span.end.0);
+            if first < last {
+                occupied_until = Some(max(occupied_until.unwrap_or(i16::MIN), runs[last - 1].end));
+            }
+        }
+
+        // Runs only extend along the run axis, so areas can only be skipped past in that axis
+        occupied_until.map(|end| match primary_axis == self.run_axis {
+            true => OriginZeroLine(end),
+            false => primary_span.start + 1,
+        })
     }
 
-    /// Determines whether a grid area specified by a range of indexes into this CellOccupancyMatrix
-    /// is entirely unnocupied. Returns true if all grid cells within the grid area are unnocupied, else false.
-    pub fn track_area_is_unoccupied(
-        &self,
-        primary_axis: AbsoluteAxis,
-        primary_range: Range<i16>,
-        secondary_range: Range<i16>,
-    ) -> bool {
-        let (row_range, col_range) = match primary_axis {
-            AbsoluteAxis::Horizontal => (secondary_range, primary_range),
-            AbsoluteAxis::Vertical => (primary_range, secondary_range),
+    /// Returns the occupancy state of the cell at the given row and column, in OriginZero coordinates
+    fn cell_state(&self, row: OriginZeroLine, column: OriginZeroLine) -> CellOccupancyState {
+        let (run_position, track) = match self.run_axis {
+            AbsoluteAxis::Horizontal => (column.0, row.0),
+            AbsoluteAxis::Vertical => (row.0, column.0),
         };
-
-        // Search for occupied cells in the specified area. Out of bounds cells are considered unoccupied.
-        for x in row_range {
-            for y in col_range.clone() {
-                match self.inner.get(x as usize, y as usize) {
-                    None | Some(CellOccupancyState::Unoccupied) => continue,
-                    _ => return false,
-                }
-            }
+        let runs = match self.tracks.get(&track) {
+            Some(runs) => runs,
+            None => return CellOccupancyState::Unoccupied,
+        };
+        let index = runs.partition_point(|run| run.end <= run_position);
+        match runs.get(index) {
+            Some(run) if run.start <= run_position => run.state,
+            _ => CellOccupancyState::Unoccupied,
         }
+    }
 
-        true
+    /// Determines whether the track at the given index in the given axis contains any items
+    fn track_is_occupied(&self, axis: AbsoluteAxis, track_index: usize) -> bool {
+        let line = self.track_counts(axis).track_to_prev_oz_line(track_index as u16).0;
+        if axis != self.run_axis {
+            self.tracks.get(&line).is_some_and(|runs| !runs.is_empty())
+        } else {
+            self.tracks.values().any(|runs| {
+                let index = runs.partition_point(|run| run.end <= line);
+                runs.get(index).is_some_and(|run| run.start <= line)
+            })
+        }
     }
 
     /// Determines whether the specified row contains any items
     pub fn row_is_occupied(&self, row_index: usize) -> bool {
-        self.inner.iter_row(row_index).any(|cell| !matches!(cell, CellOccupancyState::Unoccupied))
+        self.track_is_occupied(AbsoluteAxis::Vertical, row_index)
     }
 
     /// Determines whether the specified column contains any items
     pub fn column_is_occupied(&self, column_index: usize) -> bool {
-        self.inner.iter_col(column_index).any(|cell| !matches!(cell, CellOccupancyState::Unoccupied))
+        self.track_is_occupied(AbsoluteAxis::Horizontal, column_index)
     }
 
     /// Returns the track counts of this CellOccunpancyMatrix in the relevant axis
@@ -234,18 +249,58 @@ impl CellOccupancyMatrix {
         start_at: OriginZeroLine,
         kind: CellOccupancyState,
     ) -> Option<OriginZeroLine> {
-        let track_counts = self.track_counts(track_type.other_axis());
-        let track_computed_index = track_counts.oz_line_to_next_track(start_at);
+        if track_type == self.run_axis {
+            let runs = self.tracks.get(&start_at.0)?;
+            runs.iter().rev().find(|run| run.state == kind).map(|run| OriginZeroLine(run.end - 1))
+        } else {
+            self.tracks
+                .iter()
+                .rev()
+                .find(|(_, runs)| {
+                    let index = runs.partition_point(|run| run.end <= start_at.0);
+                    runs.get(index).is_some_and(|run| run.start <= start_at.0 && run.state == kind)
+                })
+                .map(|(track, _)| OriginZeroLine(*track))
+        }
+    }
+}
 
-        let maybe_index = match track_type {
-            AbsoluteAxis::Horizontal => {
-                self.inner.iter_row(track_computed_index as usize).rposition(|item| *item == kind)
-            }
-            AbsoluteAxis::Vertical => {
-                self.inner.iter_col(track_computed_index as usize).rposition(|item| *item == kind)
-            }
-        };
+/// Marks the cells from `start` to `end` within a track as having the given `state`, replacing the state of any cells
+/// in that range and merging the run with adjacent runs of the same state
+fn mark_run(runs: &mut Vec<OccupiedRun>, start: i16, end: i16, state: CellOccupancyState) {
+    if start >= end {
+        return;
+    }
+
+    // The runs that overlap the marked range are replaced, keeping the parts of them that lie outside of it
+    let first = runs.partition_point(|run| run.end <= start);
+    let last = runs.partition_point(|run| run.start < end);
+    let mut replacement = [None, None, None];
+    if first < last {
+        let (first_run, last_run) = (runs[first], runs[last - 1]);
+        if first_run.start < start {
+            replacement[0] = Some(OccupiedRun { end: start, ..first_run });
+        }
+        if last_run.end > end {
+            replacement[2] = Some(OccupiedRun { start: end, ..last_run });
+        }
+    }
+    replacement[1] = Some(OccupiedRun { start, end, state });
+    let inserted = replacement.iter().flatten().count();
+    runs.splice(first..last, replacement.into_iter().flatten());
 
-        maybe_index.map(|idx| track_counts.track_to_prev_oz_line(idx as u16))
+    // Merge touching runs of the same state around the replaced runs
+    let mut index = first.saturating_sub(1);
+    let merge_end = min(first + inserted, runs.len() - 1);
+    let mut remaining = merge_end - index;
+    while remaining > 0 {
+        let (current, next) = (runs[index], runs[index + 1]);
+        if current.end == next.start && current.state == next.state {
+            runs[index].end = next.end;
+            runs.remove(index + 1);
+        } else {
+            index += 1;
+        }
+        remaining -= 1;
     }
 }
@@ -85,7 +85,8 @@ impl Sub<u16> for OriginZeroLine {
 }
 
 impl OriginZeroLine {
-    /// Converts a grid line in OriginZero coordinates into the index of that same grid line in the GridTrackVec.
+    /// Converts a grid line in OriginZero coordinates into the index in the GridTrackVec of the track that starts at
+    /// that grid line (which is one past the end of the GridTrackVec for the last grid line).
     pub(crate) fn into_track_vec_index(self, track_counts: TrackCounts) -> usize {
         assert!(
             self.0 >= -(track_counts.negative_implicit as i16),
@@ -95,7 +96,7 @@ impl OriginZeroLine {
             self.0 <= (track_counts.explicit + track_counts.positive_implicit) as i16,
             "OriginZero grid line cannot be more than the number of positive grid lines"
         );
-        2 * ((self.0 + track_counts.negative_implicit as i16) as usize)
+        (self.0 + track_counts.negative_implicit as i16) as usize
     }
 
     /// The minimum number of negative implicit track there must be if a grid item starts at this line.
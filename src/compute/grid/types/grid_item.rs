@@ -1,15 +1,15 @@
 //! Contains GridItem used to represent a single grid item during layout
-use super::GridTrack;
+use super::{inner_gutters_size, GridTrack};
 use crate::compute::grid::OriginZeroLine;
 use crate::geometry::AbstractAxis;
 use crate::geometry::{Line, Point, Rect, Size};
 use crate::prelude::LayoutTree;
 use crate::style::{
     AlignItems, AlignSelf, AvailableSpace, Dimension, LengthPercentageAuto, MaxTrackSizingFunction,
-    MinTrackSizingFunction, Overflow, Style,
+    MinTrackSizingFunction, Overflow, Style, VerticalPercentageBasis,
 };
 use crate::tree::NodeId;
-use crate::tree::SizingMode;
+use crate::tree::{MeasureRequest, SizingMode};
 use crate::util::MaybeMath;
 use crate::util::{MaybeResolve, ResolveOrZero};
 use core::ops::Range;
@@ -35,6 +35,8 @@ pub(in super::super) struct GridItem {
 
     /// The item's overflow style
     pub overflow: Point<Overflow>,
+    /// Whether the item's automatic minimum size is zero rather than content-based
+    pub disable_automatic_min_size: bool,
     /// The item's size style
     pub size: Size<Dimension>,
     /// The item's min_size style
@@ -45,6 +47,8 @@ pub(in super::super) struct GridItem {
     pub aspect_ratio: Option<f32>,
     /// The item's margin style
     pub margin: Rect<LengthPercentageAuto>,
+    /// The item's vertical_percentage_basis style
+    pub vertical_percentage_basis: VerticalPercentageBasis,
     /// The item's align_self property, or the parent's align_items property is not set
     pub align_self: AlignSelf,
     /// The item's justify_self property, or the parent's justify_items property is not set
@@ -99,11 +103,13 @@ impl GridItem {
             row: row_span,
             column: col_span,
             overflow: style.overflow,
+            disable_automatic_min_size: style.disable_automatic_min_size,
             size: style.size,
             min_size: style.min_size,
             max_size: style.max_size,
             aspect_ratio: style.aspect_ratio,
             margin: style.margin,
+            vertical_percentage_basis: style.vertical_percentage_basis,
             align_self: style.align_self.unwrap_or(parent_align_items),
             justify_self: style.justify_self.unwrap_or(parent_justify_items),
             baseline: None,
@@ -138,11 +144,19 @@ impl GridItem {
     }
 
     /// Returns a range which can be used as an index into the GridTrackVec in the specified axis
-    /// which will produce a sub-slice of covering all the tracks and lines that this item spans
-    /// excluding the lines that bound it.
-    pub fn track_range_excluding_lines(&self, axis: AbstractAxis) -> Range<usize> {
+    /// which will produce a sub-slice of covering all the tracks that this item spans.
+    pub fn track_range(&self, axis: AbstractAxis) -> Range<usize> {
         let indexes = self.placement_indexes(axis);
-        (indexes.start as usize + 1)..(indexes.end as usize)
+        (indexes.start as usize)..(indexes.end as usize)
+    }
+
+    /// Whether the item participates in the baseline alignment of its row: its align-self is baseline, it spans a single
+    /// row, and neither of its vertical margins are auto. Other baseline-aligned items fall back to start alignment.
+    pub fn participates_in_baseline_alignment(&self) -> bool {
+        self.align_self == AlignSelf::Baseline
+            && self.row.span() == 1
+            && !self.margin.top.is_auto()
+            && !self.margin.bottom.is_auto()
     }
 
     /// Returns the number of tracks that this item spans in the specified axis
@@ -179,7 +193,7 @@ impl GridItem {
         axis_tracks: &[GridTrack],
         axis_parent_size: Option<f32>,
     ) -> Option<f32> {
-        let spanned_tracks = &axis_tracks[self.track_range_excluding_lines(axis)];
+        let spanned_tracks = &axis_tracks[self.track_range(axis)];
         let tracks_all_fixed = spanned_tracks
             .iter()
             .all(|track| track.max_track_sizing_function.definite_limit(axis_parent_size).is_some());
@@ -188,7 +202,7 @@ impl GridItem {
                 .iter()
                 .map(|track| track.max_track_sizing_function.definite_limit(axis_parent_size).unwrap())
                 .sum();
-            Some(limit)
+            Some(limit + inner_gutters_size(spanned_tracks))
         } else {
             None
         }
@@ -202,7 +216,7 @@ impl GridItem {
         axis_tracks: &[GridTrack],
         axis_parent_size: Option<f32>,
     ) -> Option<f32> {
-        let spanned_tracks = &axis_tracks[self.track_range_excluding_lines(axis)];
+        let spanned_tracks = &axis_tracks[self.track_range(axis)];
         let tracks_all_fixed = spanned_tracks
             .iter()
             .all(|track| track.max_track_sizing_function.definite_value(axis_parent_size).is_some());
@@ -211,7 +225,7 @@ impl GridItem {
                 .iter()
                 .map(|track| track.max_track_sizing_function.definite_value(axis_parent_size).unwrap())
                 .sum();
-            Some(limit)
+            Some(limit + inner_gutters_size(spanned_tracks))
         } else {
             None
         }
@@ -225,7 +239,7 @@ impl GridItem {
         inner_node_size: Size<Option<f32>>,
         grid_area_size: Size<Option<f32>>,
     ) -> Size<Option<f32>> {
-        let margins = self.margins_axis_sums_with_baseline_shims(inner_node_size.width);
+        let margins = self.margins_axis_sums_with_baseline_shims(inner_node_size);
 
         let aspect_ratio = self.aspect_ratio;
         let inherent_size = self.size.maybe_resolve(grid_area_size).maybe_apply_aspect_ratio(aspect_ratio);
@@ -289,13 +303,16 @@ impl GridItem {
         get_track_size_estimate: impl Fn(&GridTrack, Option<f32>) -> Option<f32>,
     ) -> Size<Option<f32>> {
         let item_other_axis_size: Option<f32> = {
-            other_axis_tracks[self.track_range_excluding_lines(axis.other())]
+            let spanned_tracks = &other_axis_tracks[self.track_range(axis.other())];
+            let inner_gutters_adjustment = match spanned_tracks.split_last() {
+                Some((_, inner_tracks)) => inner_tracks.iter().map(|track| track.content_alignment_adjustment).sum(),
+                None => 0.0,
+            };
+            spanned_tracks
                 .iter()
-                .map(|track| {
-                    get_track_size_estimate(track, other_axis_available_space)
-                        .map(|size| size + track.content_alignment_adjustment)
-                })
+                .map(|track| get_track_size_estimate(track, other_axis_available_space))
                 .sum::<Option<f32>>()
+                .map(|size| size + inner_gutters_size(spanned_tracks) + inner_gutters_adjustment)
         };
 
         let mut size = Size::NONE;
@@ -322,16 +339,38 @@ impl GridItem {
     /// Compute the item's resolved margins for size contributions. Horizontal percentage margins always resolve
     /// to zero if the container size is indefinite as otherwise this would introduce a cyclic dependency.
     #[inline(always)]
-    pub fn margins_axis_sums_with_baseline_shims(&self, inner_node_width: Option<f32>) -> Size<f32> {
+    pub fn margins_axis_sums_with_baseline_shims(&self, inner_node_size: Size<Option<f32>>) -> Size<f32> {
+        let vertical_basis = self.vertical_percentage_basis.resolve(inner_node_size).height;
         Rect {
             left: self.margin.left.resolve_or_zero(Some(0.0)),
             right: self.margin.right.resolve_or_zero(Some(0.0)),
-            top: self.margin.top.resolve_or_zero(inner_node_width) + self.baseline_shim,
-            bottom: self.margin.bottom.resolve_or_zero(inner_node_width),
+            top: self.margin.top.resolve_or_zero(vertical_basis) + self.baseline_shim,
+            bottom: self.margin.bottom.resolve_or_zero(vertical_basis),
         }
         .sum_axes()
     }
 
+    /// The constraints that the item is measured under to compute its content contribution, where `intrinsic_space` is
+    /// the space available in axes that have no definite available space (min-content or max-content)
+    pub fn content_contribution_request(
+        &self,
+        available_space: Size<Option<f32>>,
+        inner_node_size: Size<Option<f32>>,
+        intrinsic_space: AvailableSpace,
+    ) -> MeasureRequest {
+        MeasureRequest {
+            node: self.node,
+            known_dimensions: self.known_dimensions(inner_node_size, available_space),
+            parent_size: available_space,
+            available_space: available_space.map(|opt| match opt {
+                Some(size) => AvailableSpace::Definite(size),
+                None => intrinsic_space,
+            }),
+            sizing_mode: SizingMode::InherentSize,
+            vertical_margins_are_collapsible: Line::FALSE,
+        }
+    }
+
     /// Compute the item's min content contribution from the provided parameters
     pub fn min_content_contribution(
         &self,
@@ -340,17 +379,14 @@ impl GridItem {
         available_space: Size<Option<f32>>,
         inner_node_size: Size<Option<f32>>,
     ) -> f32 {
-        let known_dimensions = self.known_dimensions(inner_node_size, available_space);
+        let request = self.content_contribution_request(available_space, inner_node_size, AvailableSpace::MinContent);
         tree.measure_child_size(
-            self.node,
-            known_dimensions,
-            available_space,
-            available_space.map(|opt| match opt {
-                Some(size) => AvailableSpace::Definite(size),
-                None => AvailableSpace::MinContent,
-            }),
-            SizingMode::InherentSize,
-            Line::FALSE,
+            request.node,
+            request.known_dimensions,
+            request.parent_size,
+            request.available_space,
+            request.sizing_mode,
+            request.vertical_margins_are_collapsible,
         )
         .get(axis)
     }
@@ -379,17 +415,14 @@ impl GridItem {
         available_space: Size<Option<f32>>,
         inner_node_size: Size<Option<f32>>,
     ) -> f32 {
-        let known_dimensions = self.known_dimensions(inner_node_size, available_space);
+        let request = self.content_contribution_request(available_space, inner_node_size, AvailableSpace::MaxContent);
         tree.measure_child_size(
-            self.node,
-            known_dimensions,
-            available_space,
-            available_space.map(|opt| match opt {
-                Some(size) => AvailableSpace::Definite(size),
-                None => AvailableSpace::MaxContent,
-            }),
-            SizingMode::InherentSize,
-            Line::FALSE,
+            request.node,
+            request.known_dimensions,
+            request.parent_size,
+            request.available_space,
+            request.sizing_mode,
+            request.vertical_margins_are_collapsible,
         )
         .get(axis)
     }
@@ -434,12 +467,13 @@ impl GridItem {
                 self.min_size.maybe_resolve(inner_node_size).maybe_apply_aspect_ratio(self.aspect_ratio).get(axis)
             })
             .or_else(|| self.overflow.get(axis).maybe_into_automatic_min_size())
+            .or_else(|| self.disable_automatic_min_size.then_some(0.0))
             .unwrap_or_else(|| {
                 // Automatic minimum size. See https://www.w3.org/TR/css-grid-1/#min-size-auto
 
                 // To provide a more reasonable default minimum size for grid items, the used value of its automatic minimum size
                 // in a given axis is the content-based minimum size if all of the following are true:
-                let item_axis_tracks = &axis_tracks[self.track_range_excluding_lines(axis)];
+                let item_axis_tracks = &axis_tracks[self.track_range(axis)];
 
                 // it is not a scroll container
                 // TODO: support overflow propety
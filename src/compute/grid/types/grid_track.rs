@@ -4,26 +4,13 @@ use crate::{
     util::sys::f32_min,
 };
 
-/// Whether a GridTrack represents an actual track or a gutter.
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub(in super::super) enum GridTrackKind {
-    /// Track is an actual track
-    Track,
-    /// Track is a gutter (aka grid line) (aka gap)
-    Gutter, // { name: Option<u16> },
-}
-
 /// Internal sizing information for a single grid track (row/column)
-/// Gutters between tracks are sized similarly to actual tracks, so they
-/// are also represented by this struct
+/// Gutters are not represented by their own GridTrack. Instead each track records the size of the gutter
+/// that follows it, as gutters are fixed-size and never grow during track sizing.
 #[derive(Debug, Clone)]
 pub(in super::super) struct GridTrack {
-    #[allow(dead_code)] // Used in tests + may be useful in future
-    /// Whether the track is a full track, a gutter, or a placeholder that has not yet been initialised
-    pub kind: GridTrackKind,
-
-    /// Whether the track is a collapsed track/gutter. Collapsed tracks are effectively treated as if
-    /// they don't exist for the purposes of grid sizing. Gutters between collapsed tracks are also collapsed.
+    /// Whether the track is a collapsed track. Collapsed tracks are effectively treated as if
+    /// they don't exist for the purposes of grid sizing. The gutter following a collapsed track is also collapsed.
     pub is_collapsed: bool,
 
     /// The minimum track sizing function of the track
@@ -38,12 +25,16 @@ pub(in super::super) struct GridTrack {
     /// The size (width/height as applicable) of the track
     pub base_size: f32,
 
+    /// The size of the gutter between this track and the next one. Zero for the last track and collapsed tracks.
+    pub gutter_size: f32,
+
     /// A temporary scratch value when sizing tracks
     /// Note: can be infinity
     pub growth_limit: f32,
 
     /// A temporary scratch value when sizing tracks. Is used as an additional amount to add to the
-    /// estimate for the available space in the opposite axis when content sizing items
+    /// estimate of the gutter following the track when computing the available space in the opposite
+    /// axis for content sizing items
     pub content_alignment_adjustment: f32,
 
     /// A temporary scratch value when "distributing space" to avoid clobbering planned increase variable
@@ -58,19 +49,18 @@ pub(in super::super) struct GridTrack {
 }
 
 impl GridTrack {
-    /// GridTrack constructor with all configuration parameters for the other constructors exposed
-    fn new_with_kind(
-        kind: GridTrackKind,
+    /// Create new GridTrack from its min and max track sizing functions
+    pub fn new(
         min_track_sizing_function: MinTrackSizingFunction,
         max_track_sizing_function: MaxTrackSizingFunction,
     ) -> GridTrack {
         GridTrack {
-            kind,
             is_collapsed: false,
             min_track_sizing_function,
             max_track_sizing_function,
             offset: 0.0,
             base_size: 0.0,
+            gutter_size: 0.0,
             growth_limit: 0.0,
             content_alignment_adjustment: 0.0,
             item_incurred_increase: 0.0,
@@ -80,24 +70,7 @@ impl GridTrack {
         }
     }
 
-    /// Create new GridTrack representing an actual track (not a gutter)
-    pub fn new(
-        min_track_sizing_function: MinTrackSizingFunction,
-        max_track_sizing_function: MaxTrackSizingFunction,
-    ) -> GridTrack {
-        Self::new_with_kind(GridTrackKind::Track, min_track_sizing_function, max_track_sizing_function)
-    }
-
-    /// Create a new GridTrack representing a gutter
-    pub fn gutter(size: LengthPercentage) -> GridTrack {
-        Self::new_with_kind(
-            GridTrackKind::Gutter,
-            MinTrackSizingFunction::Fixed(size),
-            MaxTrackSizingFunction::Fixed(size),
-        )
-    }
-
-    /// Mark a GridTrack as collapsed. Also sets both of the track's sizing functions
+    /// Mark a GridTrack (and the gutter following it) as collapsed. Also sets both of the track's sizing functions
     /// to fixed zero-sized sizing functions.
     pub fn collapse(&mut self) {
         self.is_collapsed = true;
@@ -153,3 +126,12 @@ impl GridTrack {
         }
     }
 }
+
+/// Returns the combined size of the gutters between the given (consecutive) tracks
+#[inline]
+pub(in super::super) fn inner_gutters_size(tracks: &[GridTrack]) -> f32 {
+    match tracks.split_last() {
+        Some((_, inner_tracks)) => inner_tracks.iter().map(|track| track.gutter_size).sum(),
+        None => 0.0,
+    }
+}
@@ -9,12 +9,9 @@ mod grid_track_counts;
 pub(super) use cell_occupancy::{CellOccupancyMatrix, CellOccupancyState};
 pub(crate) use coordinates::{GridCoordinate, GridLine, OriginZeroLine};
 pub(super) use grid_item::GridItem;
-pub(super) use grid_track::GridTrack;
+pub(super) use grid_track::{inner_gutters_size, GridTrack};
 pub(super) use grid_track_counts::TrackCounts;
 
-#[allow(unused_imports)]
-pub(super) use grid_track::GridTrackKind;
-
 // pub(super) enum GridPosition {
 //     Auto,
 //     LineIndex(i16),
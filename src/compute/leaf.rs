@@ -2,39 +2,36 @@
 
 use crate::geometry::{Line, Point, Size};
 use crate::style::{AvailableSpace, Display, Overflow, Position, Style};
-use crate::tree::{CollapsibleMarginSet, Measurable};
+use crate::tree::{CollapsibleMarginSet, Measurable, ReplacedContent};
 use crate::tree::{SizeBaselinesAndMargins, SizingMode};
-use crate::util::sys::f32_max;
+use crate::util::sys::{f32_max, f32_min};
 use crate::util::MaybeMath;
 use crate::util::{MaybeResolve, ResolveOrZero};
 
-#[cfg(feature = "debug")]
-use crate::util::debug::NODE_LOGGER;
-
 /// Perform full layout on a leaf node
 pub(crate) fn perform_layout(
     style: &Style,
-    measurable: Option<&impl Measurable>,
+    measure: Option<impl FnOnce(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
     known_dimensions: Size<Option<f32>>,
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     sizing_mode: SizingMode,
     _vertical_margins_are_collapsible: Line<bool>,
 ) -> SizeBaselinesAndMargins {
-    compute(style, measurable, known_dimensions, parent_size, available_space, sizing_mode)
+    compute_with_measure(style, measure, known_dimensions, parent_size, available_space, sizing_mode)
 }
 
 /// Measure a leaf node's size
 pub(crate) fn measure_size(
     style: &Style,
-    measurable: Option<&impl Measurable>,
+    measure: Option<impl FnOnce(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
     known_dimensions: Size<Option<f32>>,
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     sizing_mode: SizingMode,
     _vertical_margins_are_collapsible: Line<bool>,
 ) -> Size<f32> {
-    compute(style, measurable, known_dimensions, parent_size, available_space, sizing_mode).size
+    compute_with_measure(style, measure, known_dimensions, parent_size, available_space, sizing_mode).size
 }
 
 /// Compute the size of a leaf node (node with no children)
@@ -45,6 +42,21 @@ pub fn compute(
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     sizing_mode: SizingMode,
+) -> SizeBaselinesAndMargins {
+    let measure = measurable.map(|measurable| {
+        |known_dimensions, available_space| measurable.measure_with_style(known_dimensions, available_space, style)
+    });
+    compute_with_measure(style, measure, known_dimensions, parent_size, available_space, sizing_mode)
+}
+
+/// Compute the size of a leaf node (node with no children), using the supplied `measure` function to measure its content
+pub(crate) fn compute_with_measure(
+    style: &Style,
+    measure: Option<impl FnOnce(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    sizing_mode: SizingMode,
 ) -> SizeBaselinesAndMargins {
     // Resolve node's preferred/min/max sizes (width/heights) against the available space (percentages resolve to pixel values)
     // For ContentSize mode, we pretend that the node has no size styles as these should be ignored.
@@ -68,17 +80,19 @@ pub fn compute(
 
     // Note: both horizontal and vertical percentage padding/borders are resolved against the container's inline size (i.e. width).
     // This is not a bug, but is how CSS is specified (see: https://developer.mozilla.org/en-US/docs/Web/CSS/padding#values)
-    let padding = style.padding.resolve_or_zero(parent_size.width);
-    let border = style.border.resolve_or_zero(parent_size.width);
+    // Nodes may opt into resolving vertical percentages against the container's height instead.
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let padding = style.padding.resolve_or_zero(edge_basis);
+    let border = style.border.resolve_or_zero(edge_basis);
     let padding_border = padding + border;
 
     // Scrollbar gutters are reserved when the `overflow` property is set to `Overflow::Scroll`.
     // However, the axis are switched (transposed) because a node that scrolls vertically needs
-    // *horizontal* space to be reserved for a scrollbar
-    let scrollbar_gutter = style.overflow.transpose().map(|overflow| match overflow {
-        Overflow::Scroll => style.scrollbar_width,
-        _ => 0.0,
-    });
+    // *horizontal* space to be reserved for its (vertical) scrollbar, whose thickness is the width of `scrollbar_width`
+    let scrollbar_gutter = Point {
+        x: if style.overflow.y == Overflow::Scroll { style.scrollbar_width.width } else { 0.0 },
+        y: if style.overflow.x == Overflow::Scroll { style.scrollbar_width.height } else { 0.0 },
+    };
     // TODO: make side configurable based on the `direction` property
     let mut content_box_inset = padding_border;
     content_box_inset.right += scrollbar_gutter.x;
@@ -98,15 +112,6 @@ pub fn compute(
         || border.top > 0.0
         || border.bottom > 0.0;
 
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.log("LEAF");
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("node_size", node_size);
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("min_size ", node_min_size);
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("max_size ", node_max_size);
-
     // Return early if both width and height are known
     if let Size { width: Some(width), height: Some(height) } = node_size {
         let size = Size { width, height }
@@ -119,11 +124,11 @@ pub fn compute(
             bottom_margin: CollapsibleMarginSet::ZERO,
             margins_can_collapse_through: !has_styles_preventing_being_collapsed_through
                 && size.height == 0.0
-                && measurable.is_none(),
+                && measure.is_none(),
         };
     };
 
-    if let Some(measurable) = measurable {
+    if let Some(measure) = measure {
         // Compute available space
         let available_space = Size {
             width: available_space.width.maybe_set(node_size.width).maybe_set(node_max_size.width).map_definite_value(
@@ -141,7 +146,7 @@ pub fn compute(
         };
 
         // Measure node
-        let measured_size = measurable.measure(known_dimensions, available_space);
+        let measured_size = measure(known_dimensions, available_space);
         let clamped_size =
             node_size.unwrap_or(measured_size + content_box_inset.sum_axes()).maybe_clamp(node_min_size, node_max_size);
         let size = Size {
@@ -189,3 +194,121 @@ pub fn compute(
         margins_can_collapse_through: !has_styles_preventing_being_collapsed_through && size.height == 0.0,
     }
 }
+
+/// Compute the size of a leaf node whose content is [`ReplacedContent`], such as an image
+///
+/// Follows the sizing rules of CSS replaced elements: a dimension missing from the node's size is derived from the other
+/// through the aspect ratio of the node (or of its content), and the natural size of the content is used when neither
+/// dimension is set. In that case, the size is clamped by the node's min and max sizes while preserving the aspect ratio.
+/// See <https://www.w3.org/TR/CSS2/visudet.html#min-max-widths>
+pub fn compute_replaced(
+    style: &Style,
+    content: &ReplacedContent,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+) -> SizeBaselinesAndMargins {
+    let edge_basis = style.edge_percentage_basis(parent_size);
+    let padding_border =
+        (style.padding.resolve_or_zero(edge_basis) + style.border.resolve_or_zero(edge_basis)).sum_axes();
+
+    // Sizes in the style are border-box sizes, while the natural size and aspect ratio apply to the content box
+    let content_box = |size: Size<Option<f32>>| Size {
+        width: size.width.map(|width| f32_max(width - padding_border.width, 0.0)),
+        height: size.height.map(|height| f32_max(height - padding_border.height, 0.0)),
+    };
+    // Unlike other leaves, the specified size of a replaced element also determines its content size: its min-content
+    // and max-content contributions are the size it would have, so the style applies regardless of the sizing mode
+    let node_size = known_dimensions.or(style.size.maybe_resolve(parent_size));
+    let min_size = style.min_size.maybe_resolve(parent_size);
+    let max_size = style.max_size.maybe_resolve(parent_size);
+    let aspect_ratio = style.aspect_ratio.or(content.natural_aspect_ratio());
+    let (node_size, min_size, max_size) = (content_box(node_size), content_box(min_size), content_box(max_size));
+    let natural_size = content.natural_size;
+
+    let size = match (node_size.width, node_size.height) {
+        (Some(width), Some(height)) => Size { width, height }.maybe_clamp(min_size, max_size),
+        (Some(width), None) => {
+            let width = width.maybe_clamp(min_size.width, max_size.width);
+            let height = aspect_ratio.map(|ratio| width / ratio).or(natural_size.height).unwrap_or(0.0);
+            Size { width, height: height.maybe_clamp(min_size.height, max_size.height) }
+        }
+        (None, Some(height)) => {
+            let height = height.maybe_clamp(min_size.height, max_size.height);
+            let width = aspect_ratio.map(|ratio| height * ratio).or(natural_size.width).unwrap_or(0.0);
+            Size { width: width.maybe_clamp(min_size.width, max_size.width), height }
+        }
+        (None, None) => {
+            let size = match (natural_size.width, natural_size.height, aspect_ratio) {
+                (Some(width), Some(height), _) => Size { width, height },
+                (Some(width), None, Some(ratio)) => Size { width, height: width / ratio },
+                (None, Some(height), Some(ratio)) => Size { width: height * ratio, height },
+                // Content without a natural size fills the available width
+                (None, None, Some(ratio)) => {
+                    let width = available_space.width.into_option().map_or(0.0, |width| width - padding_border.width);
+                    let width = f32_max(width, 0.0);
+                    Size { width, height: width / ratio }
+                }
+                (width, height, None) => Size { width: width.unwrap_or(0.0), height: height.unwrap_or(0.0) },
+            };
+            match aspect_ratio {
+                Some(_) => clamp_preserving_aspect_ratio(size, min_size, max_size),
+                None => size.maybe_clamp(min_size, max_size),
+            }
+        }
+    };
+
+    SizeBaselinesAndMargins {
+        size: size + padding_border,
+        first_baselines: Point::NONE,
+        top_margin: CollapsibleMarginSet::ZERO,
+        bottom_margin: CollapsibleMarginSet::ZERO,
+        margins_can_collapse_through: false,
+    }
+}
+
+/// Clamps `size` by the min and max sizes while preserving its aspect ratio where possible, following the table of
+/// constraint violations for replaced elements in <https://www.w3.org/TR/CSS2/visudet.html#min-max-widths>
+fn clamp_preserving_aspect_ratio(
+    size: Size<f32>,
+    min_size: Size<Option<f32>>,
+    max_size: Size<Option<f32>>,
+) -> Size<f32> {
+    let Size { width, height } = size;
+    if width <= 0.0 || height <= 0.0 {
+        return size.maybe_clamp(min_size, max_size);
+    }
+    let min_width = min_size.width.unwrap_or(0.0);
+    let min_height = min_size.height.unwrap_or(0.0);
+    let max_width = f32_max(max_size.width.unwrap_or(f32::INFINITY), min_width);
+    let max_height = f32_max(max_size.height.unwrap_or(f32::INFINITY), min_height);
+
+    let (width, height) = if width > max_width && height > max_height {
+        if max_width / width <= max_height / height {
+            (max_width, f32_max(min_height, max_width * height / width))
+        } else {
+            (f32_max(min_width, max_height * width / height), max_height)
+        }
+    } else if width < min_width && height < min_height {
+        if min_width / width <= min_height / height {
+            (f32_min(max_width, min_height * width / height), min_height)
+        } else {
+            (min_width, f32_min(max_height, min_width * height / width))
+        }
+    } else if width < min_width && height > max_height {
+        (min_width, max_height)
+    } else if width > max_width && height < min_height {
+        (max_width, min_height)
+    } else if width > max_width {
+        (max_width, f32_max(max_width * height / width, min_height))
+    } else if width < min_width {
+        (min_width, f32_min(min_width * height / width, max_height))
+    } else if height > max_height {
+        (f32_max(max_height * width / height, min_width), max_height)
+    } else if height < min_height {
+        (f32_min(min_height * width / height, max_width), min_height)
+    } else {
+        (width, height)
+    };
+    Size { width, height }
+}
@@ -3,7 +3,7 @@
 pub(crate) mod common;
 pub(crate) mod leaf;
 
-pub use leaf::compute;
+pub use leaf::{compute, compute_replaced};
 
 #[cfg(feature = "block_layout")]
 pub(crate) mod block;
@@ -16,11 +16,14 @@ pub(crate) mod grid;
 
 use crate::geometry::{Line, Size};
 use crate::style::AvailableSpace;
-use crate::tree::{Layout, LayoutTree, NodeId, SizeBaselinesAndMargins, SizingMode};
+use crate::tree::{Cache, Layout, LayoutTree, NodeId, RunMode, SizeBaselinesAndMargins, SizingMode};
+use crate::util::sys::{round, Vec};
 
 #[cfg(feature = "block_layout")]
 pub use self::block::BlockAlgorithm;
 
+#[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+pub use self::flexbox::FlexItemBuffer;
 #[cfg(feature = "flexbox")]
 pub use self::flexbox::FlexboxAlgorithm;
 
@@ -30,6 +33,11 @@ pub use self::grid::CssGridAlgorithm;
 #[cfg(feature = "taffy_tree")]
 pub(crate) mod taffy_tree;
 
+#[cfg(feature = "alloc_audit")]
+use crate::tree::{audit_allocation, AllocationSite};
+#[cfg(feature = "debug")]
+use crate::util::debug::debug_event;
+
 /// A common interface that all Taffy layout algorithms conform to
 pub trait LayoutAlgorithm {
     /// The name of the algorithm (mainly used for debug purposes)
@@ -58,6 +66,46 @@ pub trait LayoutAlgorithm {
     ) -> SizeBaselinesAndMargins;
 }
 
+/// Looks up the result of laying out or sizing `node` in its [`Cache`], computing and storing the result if it is not cached
+///
+/// This encapsulates the rules that Taffy uses to decide whether a cached result can be reused for a given set of
+/// constraints, and is intended to be used by custom [`LayoutTree`] implementations that store a [`Cache`] per node:
+///
+///   - `cache` should return a mutable reference to the [`Cache`] of the supplied node
+///   - `compute_uncached` should compute the layout (or size) of the node without consulting the cache, typically by
+///     dispatching to a [`LayoutAlgorithm`] based on the node's style
+///
+/// Leaf nodes (nodes without children) always cache the result of a full layout, as sizing a leaf node is no cheaper
+/// than laying it out.
+pub fn compute_cached_layout<Tree: LayoutTree>(
+    tree: &mut Tree,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    cache: impl Fn(&mut Tree, NodeId) -> &mut Cache,
+    compute_uncached: impl FnOnce(&mut Tree) -> SizeBaselinesAndMargins,
+) -> SizeBaselinesAndMargins {
+    let cache_run_mode = if tree.child_count(node) == 0 { RunMode::PerformLayout } else { run_mode };
+
+    // First we check if we have a cached result for the given input
+    if let Some(cached_size_and_baselines) = cache(tree, node).get(known_dimensions, available_space, cache_run_mode) {
+        #[cfg(feature = "debug")]
+        debug_event!(tree, node, None, "cache_hit", "size" => cached_size_and_baselines.size);
+        return cached_size_and_baselines;
+    }
+
+    let computed_size_and_baselines = compute_uncached(tree);
+
+    // Cache result
+    cache(tree, node).store(known_dimensions, available_space, cache_run_mode, computed_size_and_baselines);
+
+    #[cfg(feature = "debug")]
+    debug_event!(tree, node, None, "result", "size" => computed_size_and_baselines.size);
+
+    computed_size_and_baselines
+}
+
 /// The public interface to Taffy's hidden node algorithm implementation
 pub struct HiddenAlgorithm;
 impl LayoutAlgorithm for HiddenAlgorithm {
@@ -92,19 +140,85 @@ impl LayoutAlgorithm for HiddenAlgorithm {
 /// Creates a layout for this node and its children, recursively.
 /// Each hidden node has zero size and is placed at the origin
 fn perform_hidden_layout(tree: &mut impl LayoutTree, node: NodeId) {
-    /// Recursive function to apply hidden layout to all descendents
-    fn perform_hidden_layout_inner(tree: &mut impl LayoutTree, node: NodeId, order: u32) {
+    // Descendants are visited using an explicit stack (rather than recursion) so that very deep trees can't overflow the call stack
+    let mut stack: Vec<(NodeId, u32)> = Vec::new();
+    stack.extend(tree.children(node).enumerate().map(|(order, child)| (child, order as u32)));
+    while let Some((node, order)) = stack.pop() {
         *tree.layout_mut(node) = Layout::with_order(order);
-        for order in 0..tree.child_count(node) {
-            perform_hidden_layout_inner(tree, tree.child(node, order), order as _);
-        }
+        stack.extend(tree.children(node).enumerate().map(|(order, child)| (child, order as u32)));
     }
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, node, AllocationSite::Children, stack.capacity());
+}
+
+/// Inlined function generic over the LayoutAlgorithm to reduce code duplication
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn perform_computations<Algorithm: LayoutAlgorithm>(
+    tree: &mut impl LayoutTree,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins {
+    #[cfg(feature = "debug")]
+    debug_event!(tree, node, Some(Algorithm::NAME), "start");
 
-    for order in 0..tree.child_count(node) {
-        perform_hidden_layout_inner(tree, tree.child(node, order), order as _);
+    match run_mode {
+        RunMode::PerformLayout => Algorithm::perform_layout(
+            tree,
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        ),
+        RunMode::ComputeSize => Algorithm::measure_size(
+            tree,
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+        .into(),
     }
 }
 
+/// Rounds the calculated [`Layout`] to exact pixel values, with `scale_factor` physical pixels per unit
+/// In order to ensure that no gaps in the layout are introduced we:
+///   - Always round based on the absolute coordinates rather than parent-relative coordinates
+///   - Compute width/height by first rounding the top/bottom/left/right and then computing the difference
+///     rather than rounding the width/height directly
+///
+/// See <https://github.com/facebook/yoga/commit/aa5b296ac78f7a22e1aeaf4891243c6bb76488e2> for more context
+pub(crate) fn round_layout(tree: &mut impl LayoutTree, root: NodeId, scale_factor: f32) {
+    let round_to_pixels = |value: f32| round(value * scale_factor) / scale_factor;
+    // Nodes are visited using an explicit stack (rather than recursion) so that very deep trees can't overflow the call stack.
+    // Each entry holds a node along with the absolute position of its parent.
+    let mut stack: Vec<(NodeId, f32, f32)> = Vec::new();
+    stack.push((root, 0.0, 0.0));
+    while let Some((node, abs_x, abs_y)) = stack.pop() {
+        let layout = tree.layout_mut(node);
+        let abs_x = abs_x + layout.location.x;
+        let abs_y = abs_y + layout.location.y;
+
+        layout.location.x = round_to_pixels(layout.location.x);
+        layout.location.y = round_to_pixels(layout.location.y);
+        layout.size.width = round_to_pixels(abs_x + layout.size.width) - round_to_pixels(abs_x);
+        layout.size.height = round_to_pixels(abs_y + layout.size.height) - round_to_pixels(abs_y);
+
+        stack.extend(tree.children(node).map(|child| (child, abs_x, abs_y)));
+    }
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, root, AllocationSite::Children, stack.capacity());
+}
+
 #[cfg(test)]
 mod tests {
     use super::perform_hidden_layout;
@@ -112,6 +226,35 @@ mod tests {
     use crate::style::{Display, Style};
     use crate::Taffy;
 
+    #[test]
+    fn compute_cached_layout_should_reuse_cached_results() {
+        use super::compute_cached_layout;
+        use crate::style_helpers::TaffyMaxContent;
+        use crate::tree::{RunMode, SizeBaselinesAndMargins};
+
+        let mut taffy = Taffy::new();
+        let node = taffy.new_leaf(Style::default()).unwrap();
+
+        let mut compute_count = 0;
+        for _ in 0..3 {
+            let result = compute_cached_layout(
+                &mut taffy,
+                node,
+                Size::NONE,
+                Size::MAX_CONTENT,
+                RunMode::ComputeSize,
+                |tree, node| &mut tree.nodes[node.into()].cache,
+                |_| {
+                    compute_count += 1;
+                    SizeBaselinesAndMargins::from(Size { width: 10.0, height: 20.0 })
+                },
+            );
+            assert_eq!(result.size, Size { width: 10.0, height: 20.0 });
+        }
+
+        assert_eq!(compute_count, 1);
+    }
+
     #[test]
     fn hidden_layout_should_hide_recursively() {
         let mut taffy = Taffy::new();
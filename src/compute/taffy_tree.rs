@@ -1,10 +1,32 @@
 //! Computation specific for the default `Taffy` tree implementation
 
-use crate::compute::{leaf, LayoutAlgorithm};
+use crate::compute::{compute_cached_layout, leaf, perform_computations, round_layout, LayoutAlgorithm};
 use crate::geometry::{Line, Point, Size};
-use crate::style::{AvailableSpace, Display};
-use crate::tree::{Layout, LayoutTree, NodeId, RunMode, SizeBaselinesAndMargins, SizingMode, Taffy, TaffyError};
-use crate::util::sys::round;
+use crate::style::{AvailableSpace, Contain, Display, Position, Style};
+use crate::tree::{
+    Cache, CellMode, CoordinateOrigin, Layout, LayoutMap, LayoutProgress, LayoutStats, LayoutTree, Measurable,
+    MeasureCache, MeasureFunc, NodeId, RunMode, SizeBaselinesAndMargins, SizingMode, Taffy, TaffyError, TaffyView,
+};
+use crate::util::sys::{f32_max, Vec};
+use crate::util::ResolveOrZero;
+use slotmap::{DefaultKey, SecondaryMap};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::tree::{FingerprintHasher, MemoConstraints, MemoEntry, MemoizedNode, TracedMeasurement};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::util::sys::ChildrenVec;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::hash::Hasher;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use slotmap::SlotMap;
+
+#[cfg(feature = "rayon")]
+use crate::tree::MeasureRequest;
+
+#[cfg(all(feature = "alloc_audit", feature = "std"))]
+use crate::tree::HeapAllocationScope;
+#[cfg(feature = "alloc_audit")]
+use crate::tree::{audit_allocation, AllocationSite};
 
 #[cfg(feature = "block_layout")]
 use crate::compute::BlockAlgorithm;
@@ -15,33 +37,160 @@ use crate::compute::FlexboxAlgorithm;
 #[cfg(feature = "grid")]
 use crate::compute::CssGridAlgorithm;
 
-#[cfg(any(feature = "debug", feature = "profile"))]
-use crate::util::debug::NODE_LOGGER;
-
 #[cfg(feature = "debug")]
-fn debug_log_node(
-    known_dimensions: Size<Option<f32>>,
-    parent_size: Size<Option<f32>>,
+use crate::util::debug::debug_event;
+#[cfg(feature = "debug")]
+use crate::util::DebugEvent;
+
+/// The measure function used when none is supplied by the caller, which measures nodes without a [`MeasureFunc`] as zero-sized
+pub(crate) fn no_measure(
+    _node: NodeId,
+    _style: &Style,
+    _known_dimensions: Size<Option<f32>>,
+    _available_space: Size<AvailableSpace>,
+) -> Size<f32> {
+    Size::ZERO
+}
+
+/// Updates the stored layout of the provided `node` and its children, and records which layouts have changed
+///
+/// Nodes that need measuring but don't have a [`MeasureFunc`] are measured using `measure_function`.
+///
+/// If `should_yield` is provided, it is called before each node that isn't served from the cache is laid out, and the
+/// computation is interrupted once it returns true. The layouts of an interrupted computation are set aside, so the
+/// previously computed layouts remain in place until a later computation completes.
+pub(crate) fn compute_layout<MeasureFunction>(
+    taffy: &mut Taffy,
+    root: NodeId,
     available_space: Size<AvailableSpace>,
-    run_mode: RunMode,
-    sizing_mode: SizingMode,
-) {
-    NODE_LOGGER.debug_log(run_mode);
-    NODE_LOGGER.labelled_debug_log("sizing_mode", sizing_mode);
-    NODE_LOGGER.labelled_debug_log("known_dimensions", known_dimensions);
-    NODE_LOGGER.labelled_debug_log("parent_size", parent_size);
-    NODE_LOGGER.labelled_debug_log("available_space", available_space);
+    measure_function: MeasureFunction,
+    should_yield: Option<&mut dyn FnMut() -> bool>,
+) -> Result<LayoutProgress, TaffyError>
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    // The depth is checked before the tree is traversed in any other way, as a tree that contains a cycle is infinitely deep
+    check_depth(taffy, root)?;
+    taffy.stats = LayoutStats::default();
+    #[cfg(all(feature = "alloc_audit", feature = "std"))]
+    let heap_allocations = HeapAllocationScope::start();
+
+    // The layout algorithms work in the top-left coordinate convention, so the stored layouts are converted into it
+    // for the duration of the computation
+    let origin = taffy.config.coordinate_origin;
+    convert_locations(taffy, root, origin, CoordinateOrigin::TopLeft);
+
+    // Interruptible computations snapshot the current layouts, so that they can be restored if the computation is interrupted
+    let mut snapshot: Vec<(NodeId, Layout)> = Vec::new();
+    if should_yield.is_some() {
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            snapshot.push((node, taffy.nodes[node.into()].layout));
+            stack.extend(taffy.children[node.into()].iter().copied());
+        }
+        #[cfg(feature = "alloc_audit")]
+        for capacity in [snapshot.capacity(), stack.capacity()] {
+            taffy.stats.allocations.record_capacity(AllocationSite::Children, capacity);
+        }
+    }
+
+    taffy.resolve_context_units(available_space);
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    taffy.update_virtual_lists();
+    apply_interrupted_layouts(taffy);
+    let progress = compute_layout_inner(taffy, None, root, available_space, measure_function, should_yield);
+    match progress {
+        Ok(LayoutProgress::Complete) => {
+            record_changed_layouts(taffy, root);
+            taffy.record_damage(root);
+        }
+        Ok(LayoutProgress::Interrupted) => {
+            for (node, layout) in snapshot {
+                let node_data = &mut taffy.nodes[node.into()];
+                if node_data.layout != layout {
+                    taffy.interrupted_layouts.push((node, core::mem::replace(&mut node_data.layout, layout)));
+                }
+            }
+        }
+        Err(_) => {}
+    }
+    convert_locations(taffy, root, CoordinateOrigin::TopLeft, origin);
+
+    #[cfg(feature = "std")]
+    if matches!(progress, Ok(LayoutProgress::Complete)) {
+        taffy.publish_layouts(root);
+    }
+
+    #[cfg(all(feature = "alloc_audit", feature = "std"))]
+    {
+        taffy.stats.heap_allocations = heap_allocations.finish();
+    }
+
+    progress
 }
 
-/// Updates the stored layout of the provided `node` and its children
-pub(crate) fn compute_layout(
+/// Converts the locations of the descendants of `root` from the `from` coordinate convention to the `to` convention
+fn convert_locations(tree: &mut impl LayoutTree, root: NodeId, from: CoordinateOrigin, to: CoordinateOrigin) {
+    if from == to {
+        return;
+    }
+
+    let mut stack: Vec<NodeId> = Vec::new();
+    stack.push(root);
+    while let Some(node) = stack.pop() {
+        let parent_size = tree.layout(node).size;
+        for index in 0..tree.child_count(node) {
+            let child = tree.child(node, index);
+            let layout = tree.layout_mut(child);
+            let location = from.location_to_top_left(layout.location, layout.size, parent_size);
+            layout.location = to.top_left_to_location(location, layout.size, parent_size);
+            stack.push(child);
+        }
+    }
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, root, AllocationSite::Children, stack.capacity());
+}
+
+/// Writes back the layouts that were set aside by an interrupted layout computation
+///
+/// The cached results of the nodes that were laid out before the computation was interrupted correspond to these
+/// layouts, so they must be in place before those results can be reused by a later computation.
+fn apply_interrupted_layouts(taffy: &mut Taffy) {
+    for (node, layout) in core::mem::take(&mut taffy.interrupted_layouts) {
+        if let Some(node_data) = taffy.nodes.get_mut(node.into()) {
+            node_data.layout = layout;
+        }
+    }
+}
+
+/// Updates the stored layout of the provided `node` and its children, or their layouts in `detached` if provided
+fn compute_layout_inner<MeasureFunction>(
     taffy: &mut Taffy,
+    detached: Option<&mut DetachedLayout>,
     root: NodeId,
     available_space: Size<AvailableSpace>,
-) -> Result<(), TaffyError> {
+    measure_function: MeasureFunction,
+    mut should_yield: Option<&mut dyn FnMut() -> bool>,
+) -> Result<LayoutProgress, TaffyError>
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    if taffy.config.use_subtree_memoization && detached.is_none() {
+        taffy.subtree_memo.start_pass();
+    }
+
     // Recursively compute node layout
+    let mut tree = TaffyView::new(taffy, measure_function);
+    tree.detached = detached;
+    // The callback is reborrowed as a shorter-lived trait object, so that the view doesn't borrow the tree for as long as the callback lives
+    tree.should_yield = should_yield.as_mut().map(|should_yield| &mut **should_yield as &mut dyn FnMut() -> bool);
     let size_and_baselines = perform_node_layout(
-        taffy,
+        &mut tree,
         root,
         Size::NONE,
         available_space.into_options(),
@@ -50,27 +199,234 @@ pub(crate) fn compute_layout(
         Line::FALSE,
     );
 
+    // The root's size is incomplete if the computation was interrupted, so it isn't stored or rounded
+    if tree.interrupted_at.is_some() {
+        return Ok(LayoutProgress::Interrupted);
+    }
+
     let layout = Layout { order: 0, size: size_and_baselines.size, location: Point::ZERO };
-    *taffy.layout_mut(root) = layout;
+    *tree.layout_mut(root) = layout;
 
     // If rounding is enabled, recursively round the layout's of this node and all children
-    if taffy.config.use_rounding {
-        round_layout(taffy, root, 0.0, 0.0);
+    let (cell_mode, use_rounding, scale_factor) =
+        (tree.taffy.config.cell_mode, tree.taffy.config.use_rounding, tree.taffy.config.scale_factor);
+    if let Some(cell_mode) = cell_mode {
+        snap_layout_to_cells(&mut tree, root, cell_mode);
+    } else if use_rounding {
+        round_layout(&mut tree, root, scale_factor);
+    }
+
+    #[cfg(feature = "std")]
+    {
+        tree.stats_mut().duration = start.elapsed();
+    }
+
+    Ok(LayoutProgress::Complete)
+}
+
+/// Checks that no node in the subtree of `root` is deeper than the maximum depth set with
+/// [`Taffy::set_max_depth`], returning the path to the first such node otherwise
+pub(crate) fn check_depth(taffy: &Taffy, root: NodeId) -> Result<(), TaffyError> {
+    let Some(max_depth) = taffy.config.max_depth else { return Ok(()) };
+    let mut path: Vec<NodeId> = Vec::new();
+    let mut stack: Vec<(NodeId, usize)> = Vec::new();
+    stack.push((root, 0));
+    while let Some((node, depth)) = stack.pop() {
+        path.truncate(depth);
+        path.push(node);
+        if depth > max_depth {
+            return Err(TaffyError::MaxDepthExceeded { max_depth, path });
+        }
+        stack.extend(taffy.children[node.into()].iter().map(|child| (*child, depth + 1)));
     }
 
     Ok(())
 }
 
+/// The state of a layout computation that is kept apart from the tree, see [`compute_detached_layout`]
+///
+/// The layouts and cached results of the nodes being laid out are copied from the tree when the computation starts,
+/// and the styles of the nodes whose context-dependent units or virtual children resolve differently for the
+/// computation are overridden. A [`TaffyView`] with a detached layout reads and writes this state in place of the
+/// tree's, and skips the state that is only recorded for the tree (such as memoized subtree layouts).
+pub(crate) struct DetachedLayout {
+    /// The layout of each node in the subtree being laid out
+    layouts: LayoutMap,
+    /// The cached results of each node in the subtree being laid out
+    caches: SecondaryMap<DefaultKey, Cache>,
+    /// The resolved style of each node whose context-dependent units resolve differently than in the tree
+    resolved_styles: SecondaryMap<DefaultKey, Style>,
+    /// The style of each node with virtual children whose style differs from the one stored in the tree
+    list_styles: SecondaryMap<DefaultKey, Style>,
+    /// The statistics of the computation
+    pub(crate) stats: LayoutStats,
+}
+
+impl DetachedLayout {
+    /// Copies the state of `root` and its descendants from the tree, resolving their styles against `available_space`
+    ///
+    /// Locations are converted to the top-left coordinate convention that the layout algorithms work in.
+    fn new(taffy: &mut Taffy, root: NodeId, available_space: Size<AvailableSpace>) -> Self {
+        let mut detached = DetachedLayout {
+            layouts: LayoutMap::new(),
+            caches: SecondaryMap::new(),
+            resolved_styles: SecondaryMap::new(),
+            list_styles: SecondaryMap::new(),
+            stats: LayoutStats::default(),
+        };
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            let node_data = &taffy.nodes[node.into()];
+            detached.layouts.insert(node, node_data.layout);
+            detached.caches.insert(node.into(), node_data.cache.clone());
+            stack.extend(taffy.children[node.into()].iter().copied());
+        }
+
+        let origin = taffy.config.coordinate_origin;
+        let mut view = TaffyView::new(taffy, no_measure);
+        view.detached = Some(&mut detached);
+        convert_locations(&mut view, root, origin, CoordinateOrigin::TopLeft);
+
+        // The layouts set aside by an interrupted layout computation are those that the cached results correspond to
+        for (node, layout) in taffy.interrupted_layouts.iter() {
+            if let Some(detached_layout) = detached.layouts.get_mut(*node) {
+                *detached_layout = *layout;
+            }
+        }
+
+        // Styles that resolve differently invalidate the cached results of the node and its ancestors, as
+        // `Taffy::mark_dirty` would
+        for (node, style) in taffy.context_unit_changes(available_space) {
+            detached.resolved_styles.insert(node.into(), style);
+            detached.invalidate(taffy, node);
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        for (node, style) in taffy.virtual_list_changes(|key| detached.resolved_styles.get(key)) {
+            detached.list_styles.insert(node.into(), style);
+            detached.invalidate(taffy, node);
+        }
+
+        detached
+    }
+
+    /// Discards the cached results of `node` and its ancestors
+    fn invalidate(&mut self, taffy: &Taffy, node: NodeId) {
+        let mut current = Some(node);
+        while let Some(ancestor) = current {
+            if let Some(cache) = self.caches.get_mut(ancestor.into()) {
+                cache.clear();
+            }
+            current = taffy.parents[ancestor.into()];
+        }
+    }
+
+    /// Returns the layout of the node, which must be part of the computation
+    #[inline(always)]
+    pub(crate) fn layout(&self, node: NodeId) -> &Layout {
+        self.layouts.get(node).unwrap()
+    }
+
+    /// Returns the layout of the node mutably, which must be part of the computation
+    #[inline(always)]
+    pub(crate) fn layout_mut(&mut self, node: NodeId) -> &mut Layout {
+        self.layouts.get_mut(node).unwrap()
+    }
+
+    /// Returns the cached results of the node, which must be part of the computation
+    #[inline(always)]
+    pub(crate) fn cache_mut(&mut self, node: NodeId) -> &mut Cache {
+        &mut self.caches[node.into()]
+    }
+
+    /// Returns the style that `node` is laid out with, taking the styles of the `detached` computation into account
+    #[inline(always)]
+    pub(crate) fn layout_style<'a>(detached: Option<&'a Self>, taffy: &'a Taffy, node: NodeId) -> &'a Style {
+        if let Some(detached) = detached {
+            let key = node.into();
+            if let Some(style) = detached.list_styles.get(key) {
+                return style;
+            }
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            let has_virtual_children = taffy.virtual_lists.contains_key(key);
+            #[cfg(not(any(feature = "std", feature = "alloc")))]
+            let has_virtual_children = false;
+            if !has_virtual_children {
+                if let Some(style) = detached.resolved_styles.get(key) {
+                    return style;
+                }
+            }
+        }
+        taffy.layout_style(node)
+    }
+
+    /// Returns the style of `node` with its context-dependent units resolved, taking the styles of the `detached`
+    /// computation into account
+    #[inline(always)]
+    fn resolved_style<'a>(detached: Option<&'a Self>, taffy: &'a Taffy, node: NodeId) -> &'a Style {
+        detached
+            .and_then(|detached| detached.resolved_styles.get(node.into()))
+            .unwrap_or_else(|| taffy.resolved_style(node.into()))
+    }
+}
+
+/// Computes the layout of the provided `root` and its descendants, returning the results in a [`LayoutMap`]
+///
+/// The computation runs with a [`DetachedLayout`], so it reads the tree but never writes to it.
+pub(crate) fn compute_detached_layout(
+    taffy: &mut Taffy,
+    root: NodeId,
+    available_space: Size<AvailableSpace>,
+) -> Result<LayoutMap, TaffyError> {
+    check_depth(taffy, root)?;
+    let mut detached = DetachedLayout::new(taffy, root, available_space);
+    compute_layout_inner(taffy, Some(&mut detached), root, available_space, no_measure, None)?;
+
+    let origin = taffy.config.coordinate_origin;
+    let mut view = TaffyView::new(taffy, no_measure);
+    view.detached = Some(&mut detached);
+    convert_locations(&mut view, root, CoordinateOrigin::TopLeft, origin);
+    Ok(detached.layouts)
+}
+
+/// Compares the layout of every node in the subtree of `root` against its layout as of the previous layout computation,
+/// and records the nodes whose layout has changed
+fn record_changed_layouts(taffy: &mut Taffy, root: NodeId) {
+    // Reset the flags set by the previous layout computation
+    for node in core::mem::take(&mut taffy.changed_layouts) {
+        if let Some(node_data) = taffy.nodes.get_mut(node.into()) {
+            node_data.layout_changed = false;
+        }
+    }
+
+    let mut stack: Vec<NodeId> = Vec::new();
+    stack.push(root);
+    while let Some(node) = stack.pop() {
+        let node_data = &mut taffy.nodes[node.into()];
+        if node_data.previous_layout != Some(node_data.layout) {
+            node_data.previous_layout = Some(node_data.layout);
+            node_data.layout_changed = true;
+            taffy.changed_layouts.push(node);
+        }
+        stack.extend(taffy.children[node.into()].iter().copied());
+    }
+    #[cfg(feature = "alloc_audit")]
+    taffy.stats.allocations.record_capacity(AllocationSite::Children, stack.capacity());
+}
+
 /// Perform full layout on a node. Chooses which algorithm to use based on the `display` property.
-pub(crate) fn perform_node_layout(
-    tree: &mut Taffy,
+pub(crate) fn perform_node_layout<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
     node: NodeId,
     known_dimensions: Size<Option<f32>>,
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     sizing_mode: SizingMode,
     vertical_margins_are_collapsible: Line<bool>,
-) -> SizeBaselinesAndMargins {
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
     compute_node_layout(
         tree,
         node,
@@ -84,15 +440,18 @@ pub(crate) fn perform_node_layout(
 }
 
 /// Measure a node's size. Chooses which algorithm to use based on the `display` property.
-pub(crate) fn measure_node_size(
-    tree: &mut Taffy,
+pub(crate) fn measure_node_size<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
     node: NodeId,
     known_dimensions: Size<Option<f32>>,
     parent_size: Size<Option<f32>>,
     available_space: Size<AvailableSpace>,
     sizing_mode: SizingMode,
     vertical_margins_are_collapsible: Line<bool>,
-) -> Size<f32> {
+) -> Size<f32>
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
     compute_node_layout(
         tree,
         node,
@@ -106,10 +465,110 @@ pub(crate) fn measure_node_size(
     .size
 }
 
+/// Lays out the leaf nodes among the measured children in parallel, storing the results in their caches so that
+/// measuring the children afterwards is served from the caches
+///
+/// Only leaves that are measured with a stored [`MeasureFunc`] (which can be called from any thread) are laid out in
+/// parallel. Other children, and computations that are recorded, detached or can be interrupted, are left to be measured
+/// in turn.
+#[cfg(feature = "rayon")]
+pub(crate) fn measure_leaves_in_parallel<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    requests: &[MeasureRequest],
+) {
+    use rayon::prelude::*;
+
+    let taffy = &mut *tree.taffy;
+    if tree.should_yield.is_some() || tree.detached.is_some() || taffy.measure_recording.is_some() {
+        return;
+    }
+
+    let leaves: Vec<&MeasureRequest> = requests
+        .iter()
+        .filter(|request| {
+            let node_key = request.node.into();
+            let node_data = &taffy.nodes[node_key];
+            node_data.needs_measure
+                && node_data.style.display != Display::None
+                && node_data.style.contain == Contain::None
+                && taffy.children[node_key].is_empty()
+                && taffy.measure_funcs.contains_key(node_key)
+                && !taffy.replaced_contents.contains_key(node_key)
+                && !taffy.skipped_layouts.contains_key(node_key)
+                && !taffy.virtual_lists.contains_key(node_key)
+                && node_data
+                    .cache
+                    .get(request.known_dimensions, request.available_space, RunMode::PerformLayout)
+                    .is_none()
+        })
+        .collect();
+    if leaves.len() < 2 {
+        return;
+    }
+
+    let (nodes, measure_funcs, resolved_styles) = (&taffy.nodes, &taffy.measure_funcs, &taffy.resolved_styles);
+    let shared_cache = |node_key| shared_measure_cache(&taffy.measure_cache, &taffy.content_hashes, node_key);
+    let results: Vec<(SizeBaselinesAndMargins, bool)> = leaves
+        .par_iter()
+        .map(|request| {
+            let node_key = request.node.into();
+            let style = resolved_styles.get(node_key).unwrap_or(&nodes[node_key].style);
+            let measure_func = &measure_funcs[node_key];
+            let shared_cache = shared_cache(node_key);
+            let mut measured = false;
+            let measure = |known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>| {
+                shared_cache
+                    .and_then(|(cache, content_hash)| cache.get(content_hash, known_dimensions, available_space))
+                    .unwrap_or_else(|| {
+                        measured = true;
+                        let size = measure_func.measure_node(request.node, known_dimensions, available_space, style);
+                        if let Some((cache, content_hash)) = shared_cache {
+                            cache.insert(content_hash, known_dimensions, available_space, size);
+                        }
+                        size
+                    })
+            };
+            let result = leaf::perform_layout(
+                style,
+                Some(measure),
+                request.known_dimensions,
+                request.parent_size,
+                request.available_space,
+                request.sizing_mode,
+                request.vertical_margins_are_collapsible,
+            );
+            (result, measured)
+        })
+        .collect();
+
+    for (request, (result, measured)) in leaves.into_iter().zip(results) {
+        taffy.nodes[request.node.into()].cache.store(
+            request.known_dimensions,
+            request.available_space,
+            RunMode::PerformLayout,
+            result,
+        );
+        taffy.stats.measure_calls += measured as usize;
+    }
+}
+
+/// The amount of remaining stack space below which the stack is grown before laying out a node
+#[cfg(feature = "stacker")]
+const STACK_RED_ZONE: usize = 64 * 1024;
+/// The size of each new stack segment allocated when the stack is grown
+#[cfg(feature = "stacker")]
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
 /// Updates the stored layout of the provided `node` and its children
+///
+/// With the `stacker` feature enabled (the default), the call stack is grown on the heap whenever it is close to being
+/// exhausted, so that trees which are tens of thousands of levels deep can be laid out without overflowing the stack.
+/// Without it, layout recurses once per level of the tree and the depth that can be laid out is limited by the stack
+/// size of the calling thread.
 #[allow(clippy::too_many_arguments)]
-fn compute_node_layout(
-    tree: &mut Taffy,
+#[inline(always)]
+fn compute_node_layout<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
     node: NodeId,
     known_dimensions: Size<Option<f32>>,
     parent_size: Size<Option<f32>>,
@@ -117,72 +576,532 @@ fn compute_node_layout(
     run_mode: RunMode,
     sizing_mode: SizingMode,
     vertical_margins_are_collapsible: Line<bool>,
-) -> SizeBaselinesAndMargins {
-    #[cfg(any(feature = "debug", feature = "profile"))]
-    NODE_LOGGER.push_node(node);
-    #[cfg(feature = "debug")]
-    println!();
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    #[cfg(feature = "stacker")]
+    return stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+        compute_node_layout_inner(
+            tree,
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            run_mode,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+    });
 
-    let node_key = node.into();
-    let has_children = !tree.children[node_key].is_empty();
+    #[cfg(not(feature = "stacker"))]
+    return compute_node_layout_inner(
+        tree,
+        node,
+        known_dimensions,
+        parent_size,
+        available_space,
+        run_mode,
+        sizing_mode,
+        vertical_margins_are_collapsible,
+    );
+}
 
-    // First we check if we have a cached result for the given input
-    let cache_run_mode = if !has_children { RunMode::PerformLayout } else { run_mode };
-    if let Some(cached_size_and_baselines) =
-        tree.nodes[node_key].cache.get(known_dimensions, available_space, cache_run_mode)
-    {
-        #[cfg(feature = "debug")]
-        NODE_LOGGER.labelled_debug_log("CACHE", cached_size_and_baselines.size);
-        #[cfg(feature = "debug")]
-        debug_log_node(known_dimensions, parent_size, available_space, run_mode, sizing_mode);
-        #[cfg(any(feature = "debug", feature = "profile"))]
-        NODE_LOGGER.pop_node();
-        return cached_size_and_baselines;
+/// Updates the stored layout of the provided `node` and its children
+#[allow(clippy::too_many_arguments)]
+fn compute_node_layout_inner<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    // Once the computation has been interrupted no further work is done, as its results will be discarded
+    if tree.interrupted_at.is_some() {
+        return SizeBaselinesAndMargins::HIDDEN;
     }
 
     #[cfg(feature = "debug")]
-    debug_log_node(known_dimensions, parent_size, available_space, run_mode, sizing_mode);
+    if let Some(sink) = &mut tree.taffy.debug_sink {
+        sink.enter_node(node);
+    }
 
-    /// Inlined function generic over the LayoutAlgorithm to reduce code duplication
-    #[inline(always)]
-    fn perform_computations<Algorithm: LayoutAlgorithm>(
-        tree: &mut impl LayoutTree,
-        node: NodeId,
-        known_dimensions: Size<Option<f32>>,
-        parent_size: Size<Option<f32>>,
-        available_space: Size<AvailableSpace>,
-        run_mode: RunMode,
-        sizing_mode: SizingMode,
-        vertical_margins_are_collapsible: Line<bool>,
-    ) -> SizeBaselinesAndMargins {
-        #[cfg(feature = "debug")]
-        NODE_LOGGER.log(Algorithm::NAME);
-
-        match run_mode {
-            RunMode::PerformLayout => Algorithm::perform_layout(
+    let stats = tree.stats_mut();
+    stats.nodes_visited += 1;
+    stats.current_depth += 1;
+    stats.deepest_recursion = stats.deepest_recursion.max(stats.current_depth);
+
+    // The results of nodes whose computation is interrupted are incomplete, so their previous cache is restored afterwards
+    let previous_cache = tree.should_yield.is_some().then(|| tree.cache_mut(node).clone());
+
+    let mut cache_hit = true;
+    let size_and_baselines = compute_cached_layout(
+        tree,
+        node,
+        known_dimensions,
+        available_space,
+        run_mode,
+        |tree, node| tree.cache_mut(node),
+        |tree| {
+            cache_hit = false;
+            // Only nodes that aren't served from the cache count against the budget, so that resumed computations make progress
+            if let Some(should_yield) = &mut tree.should_yield {
+                if should_yield() {
+                    tree.interrupted_at = Some(node);
+                    return SizeBaselinesAndMargins::HIDDEN;
+                }
+            }
+            compute_memoized_node_layout(
                 tree,
                 node,
                 known_dimensions,
                 parent_size,
                 available_space,
+                run_mode,
+                sizing_mode,
+                vertical_margins_are_collapsible,
+            )
+        },
+    );
+
+    if run_mode == RunMode::PerformLayout && tree.interrupted_at.is_none() && tree.detached.is_none() {
+        tree.taffy.explain_size(node, known_dimensions, parent_size, size_and_baselines.size);
+    }
+
+    let stats = tree.stats_mut();
+    stats.current_depth -= 1;
+    if cache_hit {
+        stats.cache_hits += 1;
+    }
+    if let Some(previous_cache) = previous_cache.filter(|_| tree.interrupted_at.is_some()) {
+        *tree.cache_mut(node) = previous_cache;
+    }
+
+    #[cfg(feature = "debug")]
+    if let Some(sink) = &mut tree.taffy.debug_sink {
+        sink.exit_node(node);
+    }
+
+    size_and_baselines
+}
+
+/// Computes the layout of the provided `node` without consulting its cache
+///
+/// If the node is a [memoization root](Taffy::set_memoization_root) and subtree memoization is enabled, the memoized
+/// layout of a structurally identical subtree that was laid out under the same constraints is reused if there is one.
+/// Otherwise the layout is computed and memoized.
+#[allow(clippy::too_many_arguments)]
+fn compute_memoized_node_layout<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    if tree.taffy.config.use_subtree_memoization
+        && tree.detached.is_none()
+        && tree.taffy.nodes[node.into()].is_memoization_root
+        && !tree.taffy.children[node.into()].is_empty()
+        && !explains_node_within(tree.taffy, node)
+    {
+        if let Some(fingerprint) = subtree_fingerprint(tree.taffy, node) {
+            let constraints = MemoConstraints {
+                known_dimensions,
+                parent_size,
+                available_space,
+                run_mode,
                 sizing_mode,
                 vertical_margins_are_collapsible,
-            ),
-            RunMode::ComputeSize => Algorithm::measure_size(
+                use_rounding: tree.taffy.config.use_rounding,
+                scale_factor: tree.taffy.config.scale_factor,
+                cell_mode: tree.taffy.config.cell_mode,
+                coordinate_origin: tree.taffy.config.coordinate_origin,
+            };
+
+            let taffy = &mut *tree.taffy;
+            if let Some(entry) = taffy.subtree_memo.get(fingerprint, &constraints) {
+                let result = entry.result;
+                // The state of every node in the subtree is restored, as if the subtree had been laid out
+                if run_mode == RunMode::PerformLayout {
+                    for (subtree_node, memoized) in subtree_nodes(&taffy.children, node).into_iter().zip(&entry.nodes) {
+                        let key = subtree_node.into();
+                        #[cfg(feature = "grid")]
+                        match &memoized.auto_repetitions {
+                            Some(repetitions) => taffy.auto_repetitions.insert(key, repetitions.clone()),
+                            None => taffy.auto_repetitions.remove(key),
+                        };
+                        let node_data = &mut taffy.nodes[key];
+                        node_data.cache = memoized.cache.clone();
+                        if subtree_node != node {
+                            node_data.layout = memoized.layout;
+                        }
+                    }
+                }
+                #[cfg(feature = "debug")]
+                debug_event!(tree, node, None, "memoized");
+                return result;
+            }
+
+            let result = compute_uncached_node_layout(
                 tree,
                 node,
                 known_dimensions,
                 parent_size,
                 available_space,
+                run_mode,
                 sizing_mode,
                 vertical_margins_are_collapsible,
-            )
-            .into(),
+            );
+            if tree.interrupted_at.is_none() {
+                // Descendants are not laid out when only the size is computed, so there is no state to memoize for them
+                let nodes = match run_mode {
+                    RunMode::PerformLayout => subtree_nodes(&tree.taffy.children, node)
+                        .into_iter()
+                        .map(|subtree_node| {
+                            let node_data = &tree.taffy.nodes[subtree_node.into()];
+                            MemoizedNode {
+                                layout: node_data.layout,
+                                cache: node_data.cache.clone(),
+                                #[cfg(feature = "grid")]
+                                auto_repetitions: tree.taffy.auto_repetitions.get(subtree_node.into()).cloned(),
+                            }
+                        })
+                        .collect(),
+                    RunMode::ComputeSize => Vec::new(),
+                };
+                tree.taffy.subtree_memo.insert(fingerprint, MemoEntry { constraints, result, nodes });
+            }
+            return result;
+        }
+    }
+
+    compute_uncached_node_layout(
+        tree,
+        node,
+        known_dimensions,
+        parent_size,
+        available_space,
+        run_mode,
+        sizing_mode,
+        vertical_margins_are_collapsible,
+    )
+}
+
+/// Returns whether the node chosen with [`Taffy::explain_node`] is `node` or one of its descendants
+///
+/// The constraints of that node are recorded while its parent is laid out, which doesn't happen if the layout of the
+/// subtree is reused, so such subtrees aren't memoized.
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn explains_node_within(taffy: &Taffy, node: NodeId) -> bool {
+    let mut current = taffy.explanation.map(|explanation| explanation.node);
+    while let Some(ancestor) = current {
+        if ancestor == node {
+            return true;
+        }
+        current = taffy.parents.get(ancestor.into()).copied().flatten();
+    }
+    false
+}
+
+/// Returns the structural fingerprint of the subtree rooted at `node`, computing it if necessary
+///
+/// The fingerprint is derived from the style, font size, scroll offset and number of children of each node in the
+/// subtree, and is cached on each node until it is marked dirty. Returns `None` if the subtree contains nodes that need
+/// measuring or are laid out by custom layout algorithms, as the results of measure functions and custom algorithms
+/// can't be fingerprinted.
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn subtree_fingerprint(taffy: &mut Taffy, node: NodeId) -> Option<u64> {
+    // Nodes are visited in post-order using an explicit stack, so that the fingerprints of a node's children are
+    // computed before the fingerprint of the node itself
+    let mut stack: Vec<(NodeId, bool)> = Vec::new();
+    stack.push((node, false));
+    while let Some((current, children_visited)) = stack.pop() {
+        let current_key = current.into();
+        if taffy.nodes[current_key].fingerprint.is_some() {
+            continue;
+        }
+        if !children_visited {
+            stack.push((current, true));
+            stack.extend(taffy.children[current_key].iter().map(|child| (*child, false)));
+            continue;
+        }
+
+        let node_data = &taffy.nodes[current_key];
+        // Nodes with virtual children or context-dependent units are laid out with a style that depends on more than the
+        // node's own style, which can't be hashed
+        let fingerprint = if node_data.needs_measure
+            || matches!(node_data.style.display, Display::Custom(_))
+            || taffy.replaced_contents.contains_key(current_key)
+            || taffy.skipped_layouts.contains_key(current_key)
+            || taffy.virtual_lists.contains_key(current_key)
+            || taffy.resolved_styles.contains_key(current_key)
+        {
+            None
+        } else {
+            let mut hasher = FingerprintHasher::new();
+            hasher.write_fingerprint(&node_data.style);
+            hasher.write_fingerprint(&taffy.font_sizes.get(current_key).copied());
+            hasher.write_fingerprint(&taffy.scroll_offsets.get(current_key).copied());
+            hasher.write_usize(taffy.children[current_key].len());
+            taffy.children[current_key]
+                .iter()
+                .try_for_each(|child| {
+                    let child_fingerprint = taffy.nodes[(*child).into()].fingerprint.flatten()?;
+                    hasher.write_u64(child_fingerprint);
+                    Some(())
+                })
+                .map(|_| hasher.finish())
+        };
+        taffy.nodes[current_key].fingerprint = Some(fingerprint);
+    }
+
+    taffy.nodes[node.into()].fingerprint.flatten()
+}
+
+/// Returns `node` followed by its descendants in depth-first order
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn subtree_nodes(children: &SlotMap<DefaultKey, ChildrenVec<NodeId>>, node: NodeId) -> Vec<NodeId> {
+    let mut nodes: Vec<NodeId> = Vec::new();
+    let mut stack: Vec<NodeId> = Vec::new();
+    stack.push(node);
+    while let Some(current) = stack.pop() {
+        nodes.push(current);
+        stack.extend(children[current.into()].iter().copied());
+    }
+    nodes
+}
+
+/// The type-erased measure function of the views that custom layout algorithms are run with
+pub(crate) type DynMeasureFunction<'m> =
+    &'m mut dyn FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>;
+
+/// A custom layout algorithm registered with [`Taffy::register_layout_algorithm`], instantiated for the type-erased
+/// view that custom algorithms are run with
+pub(crate) type CustomLayoutFunction = for<'t, 'm> fn(
+    &mut TaffyView<'t, DynMeasureFunction<'m>>,
+    NodeId,
+    Size<Option<f32>>,
+    Size<Option<f32>>,
+    Size<AvailableSpace>,
+    RunMode,
+    SizingMode,
+    Line<bool>,
+) -> SizeBaselinesAndMargins;
+
+/// Runs the custom layout `Algorithm`, coerces into a [`CustomLayoutFunction`]
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn perform_custom_layout<Algorithm: LayoutAlgorithm>(
+    tree: &mut TaffyView<'_, DynMeasureFunction<'_>>,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins {
+    perform_computations::<Algorithm>(
+        tree,
+        node,
+        known_dimensions,
+        parent_size,
+        available_space,
+        run_mode,
+        sizing_mode,
+        vertical_margins_are_collapsible,
+    )
+}
+
+/// Computes the layout of the provided `node` without consulting its cache. Chooses which algorithm to use based on the `display` property.
+#[allow(clippy::too_many_arguments)]
+fn compute_uncached_node_layout<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    node: NodeId,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    let node_key = node.into();
+    let has_children = !tree.taffy.children[node_key].is_empty();
+
+    #[cfg(feature = "debug")]
+    debug_event!(
+        tree,
+        node,
+        None,
+        "compute",
+        "run_mode" => run_mode,
+        "sizing_mode" => sizing_mode,
+        "known_dimensions" => known_dimensions,
+        "parent_size" => parent_size,
+        "available_space" => available_space,
+    );
+
+    let display_mode = match tree.taffy.nodes[node_key].style.display {
+        // Nodes whose custom layout algorithm hasn't been registered are laid out as if they had `Display::None`
+        Display::Custom(key) if tree.taffy.layout_algorithm(key).is_none() => Display::None,
+        display => display,
+    };
+
+    // Nodes whose layout is skipped keep their previous size, and their descendants are left untouched
+    if display_mode != Display::None {
+        if let Some(size) = tree.taffy.skipped_layouts.get(node_key) {
+            return known_dimensions.unwrap_or(*size).into();
+        }
+    }
+
+    // Nodes with size containment are sized as if they were empty leaves, without consulting their children or their
+    // measure function. Their content is then laid out within that size, which only happens when performing a layout.
+    let mut known_dimensions = known_dimensions;
+    if display_mode != Display::None && tree.taffy.nodes[node_key].style.contain == Contain::Size {
+        let size = leaf::compute_with_measure(
+            DetachedLayout::layout_style(tree.detached.as_deref(), tree.taffy, node),
+            None::<fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+        )
+        .size;
+        if run_mode == RunMode::ComputeSize {
+            return size.into();
         }
+        known_dimensions = size.map(Some);
+    }
+
+    // Container nodes may also have a measure function. The measured size is combined with the size derived from the
+    // node's children, so that the node is at least as large as both in each axis that isn't already known.
+    let measured_size = if has_children && display_mode != Display::None && tree.taffy.nodes[node_key].needs_measure {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        let mut recording = take_measure_recording(tree);
+        let style = DetachedLayout::layout_style(tree.detached.as_deref(), tree.taffy, node);
+        let mut measure_calls = 0;
+        let measure = node_measure_function(
+            node,
+            style,
+            true,
+            tree.taffy.measure_funcs.get(node_key),
+            &mut tree.measure_function,
+            &mut measure_calls,
+            #[cfg(feature = "std")]
+            shared_measure_cache(&tree.taffy.measure_cache, &tree.taffy.content_hashes, node_key),
+            #[cfg(not(feature = "std"))]
+            None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            recording.as_mut(),
+        );
+        let size = leaf::measure_size(
+            style,
+            measure,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        );
+        tree.stats_mut().measure_calls += measure_calls;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        restore_measure_recording(tree, recording);
+        Some(size)
+    } else {
+        None
+    };
+
+    let mut result = perform_node_algorithm(
+        tree,
+        node,
+        display_mode,
+        has_children,
+        known_dimensions,
+        parent_size,
+        available_space,
+        run_mode,
+        sizing_mode,
+        vertical_margins_are_collapsible,
+    );
+    if let Some(measured_size) = measured_size {
+        let size = known_dimensions.unwrap_or(Size {
+            width: f32_max(result.size.width, measured_size.width),
+            height: f32_max(result.size.height, measured_size.height),
+        });
+        // If the measured size is larger than the size derived from the children, then the children are laid out again
+        // within the final size, so that they are aligned and their percentages resolved against it
+        if run_mode == RunMode::PerformLayout && size != result.size {
+            result = perform_node_algorithm(
+                tree,
+                node,
+                display_mode,
+                has_children,
+                size.map(Some),
+                parent_size,
+                available_space,
+                run_mode,
+                sizing_mode,
+                vertical_margins_are_collapsible,
+            );
+        }
+        result.size = size;
+    }
+
+    if run_mode == RunMode::PerformLayout && has_children && display_mode != Display::None {
+        position_anchored_children(tree, node, result.size);
+    }
+
+    result
+}
+
+/// Runs the layout algorithm of `node` that corresponds to its display mode, or lays it out as a leaf if it has no
+/// children
+#[allow(clippy::too_many_arguments)]
+fn perform_node_algorithm<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    node: NodeId,
+    display_mode: Display,
+    has_children: bool,
+    known_dimensions: Size<Option<f32>>,
+    parent_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    run_mode: RunMode,
+    sizing_mode: SizingMode,
+    vertical_margins_are_collapsible: Line<bool>,
+) -> SizeBaselinesAndMargins
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    let node_key = node.into();
+
+    // Grids record their auto-repeated tracks when they are laid out, so the tracks recorded for a node that used to
+    // be laid out as a grid are forgotten once it is laid out by any other algorithm
+    #[cfg(feature = "grid")]
+    if run_mode == RunMode::PerformLayout
+        && !(display_mode == Display::Grid && has_children)
+        && !tree.taffy.auto_repetitions.is_empty()
+        && tree.detached.is_none()
+    {
+        tree.taffy.auto_repetitions.remove(node_key);
     }
 
-    let display_mode = tree.nodes[node_key].style.display;
-    let computed_size_and_baselines = match (display_mode, has_children) {
+    match (display_mode, has_children) {
         (Display::None, _) => {
             perform_taffy_tree_hidden_layout(tree, node);
             SizeBaselinesAndMargins::HIDDEN
@@ -220,78 +1139,259 @@ fn compute_node_layout(
             sizing_mode,
             vertical_margins_are_collapsible,
         ),
-        (_, false) => match run_mode {
-            RunMode::PerformLayout => leaf::perform_layout(
-                &tree.nodes[node_key].style,
-                tree.nodes[node_key].needs_measure.then(|| &tree.measure_funcs[node_key]),
+        (Display::Custom(key), true) => {
+            // Custom algorithms are run with a view whose measure function is type-erased, so that registering them
+            // doesn't depend on the measure function of each layout computation
+            let algorithm = tree.taffy.layout_algorithm(key).unwrap();
+            let mut view = TaffyView {
+                taffy: &mut *tree.taffy,
+                measure_function: &mut tree.measure_function as DynMeasureFunction<'_>,
+                should_yield: tree
+                    .should_yield
+                    .as_mut()
+                    .map(|should_yield| &mut **should_yield as &mut dyn FnMut() -> bool),
+                interrupted_at: tree.interrupted_at,
+                detached: tree.detached.as_deref_mut(),
+            };
+            let result = algorithm(
+                &mut view,
+                node,
                 known_dimensions,
                 parent_size,
                 available_space,
+                run_mode,
                 sizing_mode,
                 vertical_margins_are_collapsible,
-            ),
-            RunMode::ComputeSize => leaf::measure_size(
-                &tree.nodes[node_key].style,
-                tree.nodes[node_key].needs_measure.then(|| &tree.measure_funcs[node_key]),
+            );
+            tree.interrupted_at = view.interrupted_at;
+            result
+        }
+        (_, false) if tree.taffy.replaced_contents.contains_key(node_key) => {
+            let style = DetachedLayout::resolved_style(tree.detached.as_deref(), tree.taffy, node);
+            leaf::compute_replaced(
+                style,
+                &tree.taffy.replaced_contents[node_key],
                 known_dimensions,
                 parent_size,
                 available_space,
-                sizing_mode,
-                vertical_margins_are_collapsible,
             )
-            .into(),
-        },
-    };
+        }
+        (_, false) => {
+            #[cfg(feature = "debug")]
+            if tree.taffy.debug_sink.is_some() {
+                let style = DetachedLayout::layout_style(tree.detached.as_deref(), tree.taffy, node);
+                let (size, min_size, max_size) = (style.size, style.min_size, style.max_size);
+                if let Some(sink) = &mut tree.taffy.debug_sink {
+                    sink.event(&DebugEvent {
+                        node,
+                        algorithm: Some("LEAF"),
+                        phase: "start",
+                        values: &[("size", &size), ("min_size", &min_size), ("max_size", &max_size)],
+                    });
+                }
+            }
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            let mut recording = take_measure_recording(tree);
+            let style = DetachedLayout::layout_style(tree.detached.as_deref(), tree.taffy, node);
+            let mut measure_calls = 0;
+            let measure = node_measure_function(
+                node,
+                style,
+                tree.taffy.nodes[node_key].needs_measure,
+                tree.taffy.measure_funcs.get(node_key),
+                &mut tree.measure_function,
+                &mut measure_calls,
+                #[cfg(feature = "std")]
+                shared_measure_cache(&tree.taffy.measure_cache, &tree.taffy.content_hashes, node_key),
+                #[cfg(not(feature = "std"))]
+                None,
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                recording.as_mut(),
+            );
+            let result = match run_mode {
+                RunMode::PerformLayout => leaf::perform_layout(
+                    style,
+                    measure,
+                    known_dimensions,
+                    parent_size,
+                    available_space,
+                    sizing_mode,
+                    vertical_margins_are_collapsible,
+                ),
+                RunMode::ComputeSize => leaf::measure_size(
+                    style,
+                    measure,
+                    known_dimensions,
+                    parent_size,
+                    available_space,
+                    sizing_mode,
+                    vertical_margins_are_collapsible,
+                )
+                .into(),
+            };
+            tree.stats_mut().measure_calls += measure_calls;
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            restore_measure_recording(tree, recording);
+            result
+        }
+    }
+}
 
-    // Cache result
-    tree.nodes[node_key].cache.store(known_dimensions, available_space, cache_run_mode, computed_size_and_baselines);
+/// Takes the recording of the layout computation being recorded by [`Taffy::record_layout`] out of the tree, so that
+/// the measurements of a node can be appended to it while the node's style is borrowed from the tree
+///
+/// Measurements made by detached computations aren't recorded, as they don't affect the layout of the tree.
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn take_measure_recording<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+) -> Option<Vec<(NodeId, TracedMeasurement)>> {
+    match tree.detached {
+        Some(_) => None,
+        None => tree.taffy.measure_recording.take(),
+    }
+}
 
-    #[cfg(feature = "debug")]
-    NODE_LOGGER.labelled_debug_log("RESULT", computed_size_and_baselines.size);
-    #[cfg(any(feature = "debug", feature = "profile"))]
-    NODE_LOGGER.pop_node();
+/// Puts back the recording taken with [`take_measure_recording`]
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn restore_measure_recording<MeasureFunction>(
+    tree: &mut TaffyView<'_, MeasureFunction>,
+    recording: Option<Vec<(NodeId, TracedMeasurement)>>,
+) {
+    if recording.is_some() {
+        tree.taffy.measure_recording = recording;
+    }
+}
+
+/// Positions the absolutely positioned children of `node` that have an [`Anchor`](crate::style::Anchor), now that the
+/// final size of `node` is known
+fn position_anchored_children<MeasureFunction>(tree: &mut TaffyView<'_, MeasureFunction>, node: NodeId, size: Size<f32>)
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    for index in 0..tree.taffy.children[node.into()].len() {
+        let child = tree.taffy.children[node.into()][index];
+        let style = DetachedLayout::layout_style(tree.detached.as_deref(), tree.taffy, child);
+        let anchor = match style.anchor {
+            Some(anchor) if style.position == Position::Absolute && style.display != Display::None => anchor,
+            _ => continue,
+        };
 
-    computed_size_and_baselines
+        let layout = tree.layout_mut(child);
+        let offset_x = anchor.offset.x.resolve_or_zero(Some(size.width));
+        let offset_y = anchor.offset.y.resolve_or_zero(Some(size.height));
+        layout.location = Point {
+            x: anchor.parent_point.x * size.width + offset_x - anchor.point.x * layout.size.width,
+            y: anchor.parent_point.y * size.height + offset_y - anchor.point.y * layout.size.height,
+        };
+    }
+}
+
+/// Returns the function used to measure the content of `node`, if the node needs measuring
+///
+/// Nodes with a stored [`MeasureFunc`] are measured using it. Other nodes are measured using the `measure_function` of the tree.
+/// Both are passed the `style` that the node is laid out with.
+///
+/// If `shared_cache` is provided (along with the node's content hash), it is consulted before measuring the node, and
+/// updated with the measured size. If `recording` is provided, each measurement is appended to it.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn node_measure_function<'a, MeasureFunction>(
+    node: NodeId,
+    style: &'a Style,
+    needs_measure: bool,
+    measure_func: Option<&'a MeasureFunc>,
+    measure_function: &'a mut MeasureFunction,
+    measure_calls: &'a mut usize,
+    shared_cache: Option<(&'a dyn MeasureCache, u64)>,
+    #[cfg(any(feature = "std", feature = "alloc"))] recording: Option<&'a mut Vec<(NodeId, TracedMeasurement)>>,
+) -> Option<impl FnOnce(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a>
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    if !needs_measure {
+        return None;
+    }
+
+    Some(move |known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>| {
+        let cached_size =
+            shared_cache.and_then(|(cache, content_hash)| cache.get(content_hash, known_dimensions, available_space));
+        let size = cached_size.unwrap_or_else(|| {
+            *measure_calls += 1;
+            let size = match measure_func {
+                Some(measure_func) => measure_func.measure_node(node, known_dimensions, available_space, style),
+                None => measure_function(node, style, known_dimensions, available_space),
+            };
+
+            if let Some((cache, content_hash)) = shared_cache {
+                cache.insert(content_hash, known_dimensions, available_space, size);
+            }
+            size
+        });
+
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(recording) = recording {
+            recording.push((node, TracedMeasurement { known_dimensions, available_space, size }));
+        }
+        size
+    })
+}
+
+/// Returns the shared [`MeasureCache`] of the tree along with the content hash of `node`, if both have been set
+#[cfg(feature = "std")]
+fn shared_measure_cache<'a>(
+    measure_cache: &'a Option<std::sync::Arc<dyn MeasureCache>>,
+    content_hashes: &slotmap::SparseSecondaryMap<DefaultKey, u64>,
+    node_key: DefaultKey,
+) -> Option<(&'a dyn MeasureCache, u64)> {
+    measure_cache.as_deref().zip(content_hashes.get(node_key).copied())
 }
 
 /// Creates a layout for this node and its children, recursively.
 /// Each hidden node has zero size and is placed at the origin
-fn perform_taffy_tree_hidden_layout(tree: &mut Taffy, node: NodeId) {
-    /// Recursive function to apply hidden layout to all descendents
-    fn perform_hidden_layout_inner(tree: &mut Taffy, node: NodeId, order: u32) {
+fn perform_taffy_tree_hidden_layout<MeasureFunction>(tree: &mut TaffyView<'_, MeasureFunction>, node: NodeId)
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    // Descendants are visited using an explicit stack (rather than recursion) so that very deep trees can't overflow the call stack
+    let mut stack: Vec<(NodeId, u32)> = Vec::new();
+    stack.extend(tree.taffy.children[node.into()].iter().enumerate().map(|(order, child)| (*child, order as u32)));
+    while let Some((node, order)) = stack.pop() {
         let node_key = node.into();
         *tree.layout_mut(node) = Layout::with_order(order);
-        tree.nodes[node_key].cache.clear();
-        for order in 0..tree.children[node_key].len() {
-            perform_hidden_layout_inner(tree, tree.child(node, order), order as _);
+        tree.cache_mut(node).clear();
+        #[cfg(feature = "grid")]
+        if tree.detached.is_none() {
+            tree.taffy.auto_repetitions.remove(node_key);
         }
+        stack.extend(tree.taffy.children[node_key].iter().enumerate().map(|(order, child)| (*child, order as u32)));
     }
-
-    for order in 0..tree.children[node.into()].len() {
-        perform_hidden_layout_inner(tree, tree.child(node, order), order as _);
-    }
+    #[cfg(feature = "alloc_audit")]
+    tree.stats_mut().allocations.record_capacity(AllocationSite::Children, stack.capacity());
 }
 
-/// Rounds the calculated [`Layout`] to exact pixel values
-/// In order to ensure that no gaps in the layout are introduced we:
-///   - Always round based on the absolute coordinates rather than parent-relative coordinates
-///   - Compute width/height by first rounding the top/bottom/left/right and then computing the difference
-///     rather than rounding the width/height directly
-///
-/// See <https://github.com/facebook/yoga/commit/aa5b296ac78f7a22e1aeaf4891243c6bb76488e2> for more context
-fn round_layout(tree: &mut impl LayoutTree, node: NodeId, abs_x: f32, abs_y: f32) {
-    let layout = tree.layout_mut(node);
-    let abs_x = abs_x + layout.location.x;
-    let abs_y = abs_y + layout.location.y;
-
-    layout.location.x = round(layout.location.x);
-    layout.location.y = round(layout.location.y);
-    layout.size.width = round(abs_x + layout.size.width) - round(abs_x);
-    layout.size.height = round(abs_y + layout.size.height) - round(abs_y);
-
-    let child_count = tree.child_count(node);
-    for index in 0..child_count {
-        let child = tree.child(node, index);
-        round_layout(tree, child, abs_x, abs_y);
+/// Snaps the layouts of the provided `root` and its descendants to whole cells, see [`CellMode`]
+fn snap_layout_to_cells(tree: &mut impl LayoutTree, root: NodeId, cell_mode: CellMode) {
+    // Each entry holds a node along with the absolute position of its parent, both before and after snapping
+    let mut stack: Vec<(NodeId, Point<f32>, Point<f32>)> = Vec::new();
+    stack.push((root, Point::ZERO, Point::ZERO));
+    while let Some((node, parent_position, snapped_parent_position)) = stack.pop() {
+        let layout = tree.layout_mut(node);
+        let cell_size = cell_mode.cell_size;
+        let position = Point { x: parent_position.x + layout.location.x, y: parent_position.y + layout.location.y };
+        let snapped_position =
+            Point { x: cell_mode.snap(position.x, cell_size.width), y: cell_mode.snap(position.y, cell_size.height) };
+        let snapped_end = Point {
+            x: cell_mode.snap(position.x + layout.size.width, cell_size.width),
+            y: cell_mode.snap(position.y + layout.size.height, cell_size.height),
+        };
+
+        layout.location = Point {
+            x: snapped_position.x - snapped_parent_position.x,
+            y: snapped_position.y - snapped_parent_position.y,
+        };
+        layout.size = Size { width: snapped_end.x - snapped_position.x, height: snapped_end.y - snapped_position.y };
+
+        stack.extend(tree.children(node).map(|child| (child, position, snapped_position)));
     }
+    #[cfg(feature = "alloc_audit")]
+    audit_allocation(tree, root, AllocationSite::Children, stack.capacity());
 }
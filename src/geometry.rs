@@ -1,8 +1,8 @@
 //! Geometric primitives useful for layout
 
 use crate::style::Dimension;
-use crate::util::sys::f32_max;
-use core::ops::{Add, Sub};
+use crate::util::sys::{f32_max, f32_min};
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 #[cfg(feature = "flexbox")]
 use crate::style::FlexDirection;
@@ -127,13 +127,37 @@ impl<U, T: Add<U>> Add<Rect<U>> for Rect<T> {
     }
 }
 
+impl<U: Copy, T: Mul<U>> Mul<U> for Rect<T> {
+    type Output = Rect<T::Output>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        Rect { left: self.left * rhs, right: self.right * rhs, top: self.top * rhs, bottom: self.bottom * rhs }
+    }
+}
+
+impl<U: Copy, T: Div<U>> Div<U> for Rect<T> {
+    type Output = Rect<T::Output>;
+
+    fn div(self, rhs: U) -> Self::Output {
+        Rect { left: self.left / rhs, right: self.right / rhs, top: self.top / rhs, bottom: self.bottom / rhs }
+    }
+}
+
+impl<T: Neg> Neg for Rect<T> {
+    type Output = Rect<T::Output>;
+
+    fn neg(self) -> Self::Output {
+        Rect { left: -self.left, right: -self.right, top: -self.top, bottom: -self.bottom }
+    }
+}
+
 impl<T> Rect<T> {
     /// Applies the function `f` to all four sides of the rect
     ///
     /// When applied to the left and right sides, the width is used
     /// as the second parameter of `f`.
     /// When applied to the top or bottom sides, the height is used instead.
-    #[cfg(feature = "flexbox")]
+    #[cfg(any(feature = "flexbox", feature = "block_layout"))]
     pub(crate) fn zip_size<R, F, U>(self, size: Size<U>, f: F) -> Rect<R>
     where
         F: Fn(T, U) -> R,
@@ -284,6 +308,50 @@ impl Rect<f32> {
     pub const fn new(start: f32, end: f32, top: f32, bottom: f32) -> Self {
         Self { left: start, right: end, top, bottom }
     }
+
+    /// Returns true if `point` lies within the rectangle bounded by these edges
+    ///
+    /// The left and top edges are inclusive, while the right and bottom edges are exclusive, so that a point on the
+    /// boundary between two adjacent rectangles is only contained by one of them.
+    pub fn contains(&self, point: Point<f32>) -> bool {
+        point.x >= self.left && point.x < self.right && point.y >= self.top && point.y < self.bottom
+    }
+
+    /// Returns the overlap of the rectangles bounded by these edges and by `other`, or `None` if they don't overlap
+    pub fn intersection(&self, other: Rect<f32>) -> Option<Rect<f32>> {
+        let intersection = Rect {
+            left: f32_max(self.left, other.left),
+            right: f32_min(self.right, other.right),
+            top: f32_max(self.top, other.top),
+            bottom: f32_min(self.bottom, other.bottom),
+        };
+        (intersection.left < intersection.right && intersection.top < intersection.bottom).then_some(intersection)
+    }
+
+    /// Returns the smallest rectangle that contains both the rectangles bounded by these edges and by `other`
+    pub fn union(&self, other: Rect<f32>) -> Rect<f32> {
+        Rect {
+            left: f32_min(self.left, other.left),
+            right: f32_max(self.right, other.right),
+            top: f32_min(self.top, other.top),
+            bottom: f32_max(self.bottom, other.bottom),
+        }
+    }
+}
+
+impl<T> From<[T; 4]> for Rect<T> {
+    /// Creates a rect from a `[left, right, top, bottom]` array, in the same order as the fields of [`Rect`] and the
+    /// parameters of [`Rect::new`]
+    fn from([left, right, top, bottom]: [T; 4]) -> Self {
+        Rect { left, right, top, bottom }
+    }
+}
+
+impl From<(Point<f32>, Size<f32>)> for Rect<f32> {
+    /// Creates the edges of the rectangle with the given top-left corner and size
+    fn from((origin, size): (Point<f32>, Size<f32>)) -> Self {
+        Rect { left: origin.x, right: origin.x + size.width, top: origin.y, bottom: origin.y + size.height }
+    }
 }
 
 /// An abstract "line". Represents any type that has a start and an end
@@ -351,6 +419,24 @@ impl<U, T: Sub<U>> Sub<Size<U>> for Size<T> {
     }
 }
 
+// Scales both the width and height by a scalar
+impl<U: Copy, T: Mul<U>> Mul<U> for Size<T> {
+    type Output = Size<T::Output>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        Size { width: self.width * rhs, height: self.height * rhs }
+    }
+}
+
+// Divides both the width and height by a scalar
+impl<U: Copy, T: Div<U>> Div<U> for Size<T> {
+    type Output = Size<T::Output>;
+
+    fn div(self, rhs: U) -> Self::Output {
+        Size { width: self.width / rhs, height: self.height / rhs }
+    }
+}
+
 // Note: we allow dead_code here as we want to provide a complete API of helpers that is symetrical in all axes,
 // but sometimes we only currently have a use for the helper in a single axis
 #[allow(dead_code)]
@@ -515,6 +601,13 @@ impl<T> Size<T> {
     }
 }
 
+impl<T: Mul + Copy> Size<T> {
+    /// Returns the area of the size (its width multiplied by its height)
+    pub fn area(self) -> T::Output {
+        self.width * self.height
+    }
+}
+
 impl Size<f32> {
     /// A [`Size`] with zero width and height
     pub const ZERO: Size<f32> = Self { width: 0.0, height: 0.0 };
@@ -615,6 +708,32 @@ impl<U, T: Add<U>> Add<Point<U>> for Point<T> {
     }
 }
 
+// Scales both the x and y coordinates by a scalar
+impl<U: Copy, T: Mul<U>> Mul<U> for Point<T> {
+    type Output = Point<T::Output>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        Point { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+// Divides both the x and y coordinates by a scalar
+impl<U: Copy, T: Div<U>> Div<U> for Point<T> {
+    type Output = Point<T::Output>;
+
+    fn div(self, rhs: U) -> Self::Output {
+        Point { x: self.x / rhs, y: self.y / rhs }
+    }
+}
+
+impl<T: Neg> Neg for Point<T> {
+    type Output = Point<T::Output>;
+
+    fn neg(self) -> Self::Output {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
 impl<T> Point<T> {
     /// Applies the function `f` to both the x and y
     ///
@@ -682,6 +801,62 @@ impl<T> From<Point<T>> for Size<T> {
     }
 }
 
+impl<T> From<(T, T)> for Size<T> {
+    /// Creates a size from a `(width, height)` tuple
+    fn from((width, height): (T, T)) -> Self {
+        Size { width, height }
+    }
+}
+
+impl<T> From<[T; 2]> for Size<T> {
+    /// Creates a size from a `[width, height]` array
+    fn from([width, height]: [T; 2]) -> Self {
+        Size { width, height }
+    }
+}
+
+impl<T> From<Size<T>> for (T, T) {
+    /// Converts the size into a `(width, height)` tuple
+    fn from(size: Size<T>) -> Self {
+        (size.width, size.height)
+    }
+}
+
+impl<T> From<Size<T>> for [T; 2] {
+    /// Converts the size into a `[width, height]` array
+    fn from(size: Size<T>) -> Self {
+        [size.width, size.height]
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    /// Creates a point from an `(x, y)` tuple
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<[T; 2]> for Point<T> {
+    /// Creates a point from an `[x, y]` array
+    fn from([x, y]: [T; 2]) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    /// Converts the point into an `(x, y)` tuple
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<Point<T>> for [T; 2] {
+    /// Converts the point into an `[x, y]` array
+    fn from(point: Point<T>) -> Self {
+        [point.x, point.y]
+    }
+}
+
 /// Generic struct which holds a "min" value and a "max" value
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -23,7 +23,11 @@ pub mod geometry;
 pub mod prelude;
 pub mod style;
 pub mod style_helpers;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod tree;
+#[cfg(feature = "upstream")]
+pub mod upstream;
 pub mod util;
 
 #[cfg(feature = "flexbox")]
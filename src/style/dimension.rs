@@ -15,6 +15,21 @@ pub enum LengthPercentage {
     Length(f32),
     /// The dimension is stored in percentage relative to the parent item.
     Percent(f32),
+    /// The dimension is stored as a fraction of the width of the viewport (see [`Taffy::set_viewport_size`](crate::Taffy::set_viewport_size))
+    Vw(f32),
+    /// The dimension is stored as a fraction of the height of the viewport
+    Vh(f32),
+    /// The dimension is stored as a fraction of the smaller of the viewport's width and height
+    Vmin(f32),
+    /// The dimension is stored as a fraction of the larger of the viewport's width and height
+    Vmax(f32),
+    /// The dimension is stored as a multiple of the root font size (see [`Taffy::set_root_font_size`](crate::Taffy::set_root_font_size))
+    Rem(f32),
+    /// The dimension is stored as a multiple of the node's font size (see [`Taffy::set_font_size`](crate::Taffy::set_font_size))
+    Em(f32),
+    /// The dimension is stored in an application-defined unit with the given id, which is resolved into a length by
+    /// the resolver set with [`Taffy::set_custom_unit_resolver`](crate::Taffy::set_custom_unit_resolver)
+    Custom(u32),
 }
 impl TaffyZero for LengthPercentage {
     const ZERO: Self = Self::Length(0.0);
@@ -41,6 +56,21 @@ pub enum LengthPercentageAuto {
     Length(f32),
     /// The dimension is stored in percentage relative to the parent item.
     Percent(f32),
+    /// The dimension is stored as a fraction of the width of the viewport (see [`Taffy::set_viewport_size`](crate::Taffy::set_viewport_size))
+    Vw(f32),
+    /// The dimension is stored as a fraction of the height of the viewport
+    Vh(f32),
+    /// The dimension is stored as a fraction of the smaller of the viewport's width and height
+    Vmin(f32),
+    /// The dimension is stored as a fraction of the larger of the viewport's width and height
+    Vmax(f32),
+    /// The dimension is stored as a multiple of the root font size (see [`Taffy::set_root_font_size`](crate::Taffy::set_root_font_size))
+    Rem(f32),
+    /// The dimension is stored as a multiple of the node's font size (see [`Taffy::set_font_size`](crate::Taffy::set_font_size))
+    Em(f32),
+    /// The dimension is stored in an application-defined unit with the given id, which is resolved into a length by
+    /// the resolver set with [`Taffy::set_custom_unit_resolver`](crate::Taffy::set_custom_unit_resolver)
+    Custom(u32),
     /// The dimension should be automatically computed
     Auto,
 }
@@ -66,6 +96,13 @@ impl From<LengthPercentage> for LengthPercentageAuto {
         match input {
             LengthPercentage::Length(value) => Self::Length(value),
             LengthPercentage::Percent(value) => Self::Percent(value),
+            LengthPercentage::Vw(value) => Self::Vw(value),
+            LengthPercentage::Vh(value) => Self::Vh(value),
+            LengthPercentage::Vmin(value) => Self::Vmin(value),
+            LengthPercentage::Vmax(value) => Self::Vmax(value),
+            LengthPercentage::Rem(value) => Self::Rem(value),
+            LengthPercentage::Em(value) => Self::Em(value),
+            LengthPercentage::Custom(unit) => Self::Custom(unit),
         }
     }
 }
@@ -74,13 +111,20 @@ impl LengthPercentageAuto {
     /// Returns:
     ///   - Some(length) for Length variants
     ///   - Some(resolved) using the provided context for Percent variants
-    ///   - None for Auto variants
+    ///   - None for Auto variants, and for viewport-relative, font-relative and custom variants (which are resolved before layout)
     #[inline(always)]
     pub fn resolve_to_option(self, context: f32) -> Option<f32> {
         match self {
             Self::Length(length) => Some(length),
             Self::Percent(percent) => Some(context * percent),
-            Self::Auto => None,
+            Self::Vw(_)
+            | Self::Vh(_)
+            | Self::Vmin(_)
+            | Self::Vmax(_)
+            | Self::Rem(_)
+            | Self::Em(_)
+            | Self::Custom(_)
+            | Self::Auto => None,
         }
     }
 
@@ -102,6 +146,21 @@ pub enum Dimension {
     Length(f32),
     /// The dimension is stored in percentage relative to the parent item.
     Percent(f32),
+    /// The dimension is stored as a fraction of the width of the viewport (see [`Taffy::set_viewport_size`](crate::Taffy::set_viewport_size))
+    Vw(f32),
+    /// The dimension is stored as a fraction of the height of the viewport
+    Vh(f32),
+    /// The dimension is stored as a fraction of the smaller of the viewport's width and height
+    Vmin(f32),
+    /// The dimension is stored as a fraction of the larger of the viewport's width and height
+    Vmax(f32),
+    /// The dimension is stored as a multiple of the root font size (see [`Taffy::set_root_font_size`](crate::Taffy::set_root_font_size))
+    Rem(f32),
+    /// The dimension is stored as a multiple of the node's font size (see [`Taffy::set_font_size`](crate::Taffy::set_font_size))
+    Em(f32),
+    /// The dimension is stored in an application-defined unit with the given id, which is resolved into a length by
+    /// the resolver set with [`Taffy::set_custom_unit_resolver`](crate::Taffy::set_custom_unit_resolver)
+    Custom(u32),
     /// The dimension should be automatically computed
     Auto,
 }
@@ -127,6 +186,13 @@ impl From<LengthPercentage> for Dimension {
         match input {
             LengthPercentage::Length(value) => Self::Length(value),
             LengthPercentage::Percent(value) => Self::Percent(value),
+            LengthPercentage::Vw(value) => Self::Vw(value),
+            LengthPercentage::Vh(value) => Self::Vh(value),
+            LengthPercentage::Vmin(value) => Self::Vmin(value),
+            LengthPercentage::Vmax(value) => Self::Vmax(value),
+            LengthPercentage::Rem(value) => Self::Rem(value),
+            LengthPercentage::Em(value) => Self::Em(value),
+            LengthPercentage::Custom(unit) => Self::Custom(unit),
         }
     }
 }
@@ -136,6 +202,13 @@ impl From<LengthPercentageAuto> for Dimension {
         match input {
             LengthPercentageAuto::Length(value) => Self::Length(value),
             LengthPercentageAuto::Percent(value) => Self::Percent(value),
+            LengthPercentageAuto::Vw(value) => Self::Vw(value),
+            LengthPercentageAuto::Vh(value) => Self::Vh(value),
+            LengthPercentageAuto::Vmin(value) => Self::Vmin(value),
+            LengthPercentageAuto::Vmax(value) => Self::Vmax(value),
+            LengthPercentageAuto::Rem(value) => Self::Rem(value),
+            LengthPercentageAuto::Em(value) => Self::Em(value),
+            LengthPercentageAuto::Custom(unit) => Self::Custom(unit),
             LengthPercentageAuto::Auto => Self::Auto,
         }
     }
@@ -179,6 +252,7 @@ impl Rect<Dimension> {
 /// The amount of space available to a node in a given axis
 /// <https://www.w3.org/TR/css-sizing-3/#available>
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AvailableSpace {
     /// The amount of space available is the specified number of pixels
     Definite(f32),
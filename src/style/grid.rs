@@ -4,7 +4,8 @@ use crate::compute::grid::{GridCoordinate, GridLine, OriginZeroLine};
 use crate::geometry::{AbsoluteAxis, AbstractAxis};
 use crate::geometry::{Line, MinMax};
 use crate::style_helpers::*;
-use crate::util::sys::GridTrackVec;
+use crate::util::sys::{Box, GridTrackVec};
+use crate::util::MaybeResolve;
 use core::cmp::{max, min};
 use core::convert::Infallible;
 
@@ -316,8 +317,7 @@ impl MaxTrackSizingFunction {
     pub fn definite_value(self, parent_size: Option<f32>) -> Option<f32> {
         use MaxTrackSizingFunction::*;
         match self {
-            Fixed(LengthPercentage::Length(size)) => Some(size),
-            Fixed(LengthPercentage::Percent(fraction)) => parent_size.map(|size| fraction * size),
+            Fixed(length_percentage) => length_percentage.maybe_resolve(parent_size),
             MinContent | MaxContent | FitContent(_) | Auto | Fraction(_) => None,
         }
     }
@@ -332,8 +332,7 @@ impl MaxTrackSizingFunction {
     pub fn definite_limit(self, parent_size: Option<f32>) -> Option<f32> {
         use MaxTrackSizingFunction::FitContent;
         match self {
-            FitContent(LengthPercentage::Length(size)) => Some(size),
-            FitContent(LengthPercentage::Percent(fraction)) => parent_size.map(|size| fraction * size),
+            FitContent(length_percentage) => length_percentage.maybe_resolve(parent_size),
             _ => self.definite_value(parent_size),
         }
     }
@@ -345,7 +344,7 @@ impl MaxTrackSizingFunction {
         use MaxTrackSizingFunction::*;
         match self {
             Fixed(LengthPercentage::Percent(fraction)) => Some(fraction * parent_size),
-            Fixed(LengthPercentage::Length(_)) | MinContent | MaxContent | FitContent(_) | Auto | Fraction(_) => None,
+            Fixed(_) | MinContent | MaxContent | FitContent(_) | Auto | Fraction(_) => None,
         }
     }
 
@@ -411,8 +410,7 @@ impl MinTrackSizingFunction {
     pub fn definite_value(self, parent_size: Option<f32>) -> Option<f32> {
         use MinTrackSizingFunction::*;
         match self {
-            Fixed(LengthPercentage::Length(size)) => Some(size),
-            Fixed(LengthPercentage::Percent(fraction)) => parent_size.map(|size| fraction * size),
+            Fixed(length_percentage) => length_percentage.maybe_resolve(parent_size),
             MinContent | MaxContent | Auto => None,
         }
     }
@@ -424,7 +422,7 @@ impl MinTrackSizingFunction {
         use MinTrackSizingFunction::*;
         match self {
             Fixed(LengthPercentage::Percent(fraction)) => Some(fraction * parent_size),
-            Fixed(LengthPercentage::Length(_)) | MinContent | MaxContent | Auto => None,
+            Fixed(_) | MinContent | MaxContent | Auto => None,
         }
     }
 
@@ -589,10 +587,118 @@ impl From<MinMax<MinTrackSizingFunction, MaxTrackSizingFunction>> for TrackSizin
     }
 }
 
+/// A list of grid tracks, as used by the grid template and auto track properties of [`Style`]
+///
+/// Most nodes aren't grid containers, so the tracks are stored behind a single pointer that is only allocated once
+/// the list is non-empty. This keeps [`Style`] small for the nodes that don't use them. The list dereferences to a
+/// slice of its tracks, and can be created from a `Vec` or an iterator of tracks.
+#[derive(Clone, PartialEq)]
+// Boxing the vector rather than the slice keeps the pointer thin, which is the point of this type
+#[allow(clippy::box_collection)]
+pub struct GridTrackList<T>(Option<Box<GridTrackVec<T>>>);
+
+impl<T> GridTrackList<T> {
+    /// Creates an empty list of tracks, without allocating
+    pub const fn new() -> Self {
+        Self(None)
+    }
+
+    /// Appends a track to the end of the list
+    pub fn push(&mut self, track: T) {
+        self.0.get_or_insert_with(Default::default).push(track);
+    }
+
+    /// Removes every track from the list, freeing its allocation
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// The tracks in the list
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_deref().map_or(&[], GridTrackVec::as_slice)
+    }
+
+    /// The tracks in the list, mutably
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.0.as_deref_mut().map_or(&mut [], GridTrackVec::as_mut_slice)
+    }
+}
+
+impl<T> Default for GridTrackList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> core::ops::Deref for GridTrackList<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> core::ops::DerefMut for GridTrackList<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for GridTrackList<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> From<GridTrackVec<T>> for GridTrackList<T> {
+    fn from(tracks: GridTrackVec<T>) -> Self {
+        if tracks.is_empty() {
+            Self(None)
+        } else {
+            Self(Some(Box::new(tracks)))
+        }
+    }
+}
+
+impl<T> From<GridTrackList<T>> for GridTrackVec<T> {
+    fn from(tracks: GridTrackList<T>) -> Self {
+        tracks.0.map_or_else(GridTrackVec::new, |tracks| *tracks)
+    }
+}
+
+impl<T> FromIterator<T> for GridTrackList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().collect::<GridTrackVec<T>>().into()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a GridTrackList<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for GridTrackList<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(self.as_slice(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for GridTrackList<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <GridTrackVec<T> as serde::Deserialize>::deserialize(deserializer).map(Self::from)
+    }
+}
+
 // Grid extensions to the Style struct
 impl Style {
     /// Get a grid item's row or column placement depending on the axis passed
-    pub(crate) fn grid_template_tracks(&self, axis: AbsoluteAxis) -> &GridTrackVec<TrackSizingFunction> {
+    pub(crate) fn grid_template_tracks(&self, axis: AbsoluteAxis) -> &GridTrackList<TrackSizingFunction> {
         match axis {
             AbsoluteAxis::Horizontal => &self.grid_template_columns,
             AbsoluteAxis::Vertical => &self.grid_template_rows,
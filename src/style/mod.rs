@@ -13,21 +13,23 @@ pub use self::flex::{FlexDirection, FlexWrap};
 
 #[cfg(feature = "grid")]
 mod grid;
+#[cfg(feature = "taffy_tree")]
+mod units;
 #[cfg(feature = "grid")]
 pub(crate) use self::grid::{GenericGridPlacement, OriginZeroGridPlacement};
 #[cfg(feature = "grid")]
 pub use self::grid::{
-    GridAutoFlow, GridPlacement, GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
+    GridAutoFlow, GridPlacement, GridTrackList, GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
     NonRepeatedTrackSizingFunction, TrackSizingFunction,
 };
-use crate::geometry::{Point, Rect, Size};
+#[cfg(feature = "taffy_tree")]
+pub(crate) use self::units::{ContextUnits, UnitContext};
+use crate::geometry::{MinMax, Point, Rect, Size};
 
 #[cfg(feature = "grid")]
 use crate::geometry::Line;
 #[cfg(feature = "serde")]
 use crate::style_helpers;
-#[cfg(feature = "grid")]
-use crate::util::sys::GridTrackVec;
 
 /// Sets the layout used for the children of this node
 ///
@@ -46,6 +48,11 @@ pub enum Display {
     Grid,
     /// The children will not be laid out, and will follow absolute positioning
     None,
+    /// The children will follow the application-defined layout algorithm registered with the given key, see
+    /// [`Taffy::register_layout_algorithm`](crate::Taffy::register_layout_algorithm)
+    ///
+    /// Nodes whose key has no registered algorithm are laid out as if they had [`Display::None`].
+    Custom(u16),
 }
 
 impl Display {
@@ -101,6 +108,44 @@ impl Default for Position {
     }
 }
 
+/// Positions an absolutely positioned node by anchoring a point of the node to a point of its parent
+///
+/// Points are specified as fractions of the size of the node (or its parent), so `(0.0, 0.0)` is the top-left corner,
+/// `(0.5, 0.5)` is the center and `(1.0, 1.0)` is the bottom-right corner. This is not part of CSS, but is the common
+/// positioning model of game UI. It replaces the offsets computed from the node's [`Style::inset`], which should be left
+/// as `auto`, and the node's margins are ignored.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Anchor {
+    /// The anchored point of the node, as a fraction of the node's size
+    pub point: Point<f32>,
+    /// The point of the parent that the node is anchored to, as a fraction of the size of the parent's border box
+    pub parent_point: Point<f32>,
+    /// The offset of the node's anchored point from the parent's point. Percentages are of the size of the parent.
+    pub offset: Point<LengthPercentage>,
+}
+
+impl Anchor {
+    /// Anchors the center of the node to the center of its parent
+    pub const CENTER: Self = Self::new(Point { x: 0.5, y: 0.5 }, Point { x: 0.5, y: 0.5 });
+
+    /// Anchors `point` of the node to `parent_point` of its parent, without an offset
+    #[must_use]
+    pub const fn new(point: Point<f32>, parent_point: Point<f32>) -> Self {
+        Self {
+            point,
+            parent_point,
+            offset: Point { x: LengthPercentage::Length(0.0), y: LengthPercentage::Length(0.0) },
+        }
+    }
+
+    /// Returns the anchor with its offset replaced by `offset`
+    #[must_use]
+    pub const fn with_offset(self, offset: Point<LengthPercentage>) -> Self {
+        Self { offset, ..self }
+    }
+}
+
 /// How children overflowing their container should affect layout
 ///
 /// In CSS the primary effect of this property is to control whether contents of a parent container that overflow that container should
@@ -108,10 +153,10 @@ impl Default for Position {
 /// the main ones being:
 ///
 ///   - The automatic minimum size Flexbox/CSS Grid items with non-`Visible` overflow is `0` rather than being content based
-///   - `Overflow::Scroll` nodes have space in the layout reserved for a scrollbar (width controlled by the `scrollbar_width` property)
+///   - `Overflow::Scroll` nodes have space in the layout reserved for a scrollbar (thickness controlled by the `scrollbar_width` property)
 ///
 /// In Taffy, we only implement the layout related secondary effects as we are not concerned with drawing/painting. The amount of space reserved for
-/// a scrollbar is controlled by the `scrollbar_width` property. If this is `0` then `Scroll` behaves identically to `Hidden`.
+/// a scrollbar is controlled by the `scrollbar_width` property. If it is `0` in an axis then `Scroll` behaves identically to `Hidden` in that axis.
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/overflow>
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
@@ -146,6 +191,52 @@ impl Overflow {
     }
 }
 
+/// The dimension of the containing block that percentages in the vertical margin, border and padding of a node are
+/// resolved against
+///
+/// CSS resolves percentages in all four edges against the width of the containing block. `Height` instead resolves the
+/// top and bottom edges against its height, which is often more intuitive outside of documents (e.g. in game UI).
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VerticalPercentageBasis {
+    /// Vertical percentages are resolved against the width of the containing block, as in CSS
+    #[default]
+    Width,
+    /// Vertical percentages are resolved against the height of the containing block, or zero if it is indefinite
+    Height,
+}
+
+impl VerticalPercentageBasis {
+    /// Returns the sizes that horizontal and vertical percentages are resolved against, given the size of the
+    /// containing block
+    #[inline(always)]
+    pub(crate) fn resolve<T: Copy>(self, containing_block: Size<T>) -> Size<T> {
+        match self {
+            Self::Width => Size { width: containing_block.width, height: containing_block.width },
+            Self::Height => containing_block,
+        }
+    }
+}
+
+/// Whether the size of a node is independent of its content, like the CSS `contain` property
+///
+/// Only size containment affects layout, so the other kinds of containment supported by CSS are not represented.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/contain>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Contain {
+    /// The node is sized from its content as usual
+    #[default]
+    None,
+    /// The node is sized as if it had no content: its children and measure function are ignored when computing its
+    /// intrinsic size, although its children are still laid out within the resulting size
+    ///
+    /// The size of the node is then determined only by its own style and by the constraints of its parent, so changes
+    /// to its subtree never affect the layout of the rest of the tree.
+    Size,
+}
+
 /// The flexbox layout information for a single node.
 ///
 /// The most important idea in flexbox is the notion of a "main" and "cross" axis, which are always perpendicular to each other.
@@ -170,8 +261,11 @@ pub struct Style {
     // Overflow properties
     /// How children overflowing their container should affect layout
     pub overflow: Point<Overflow>,
-    /// How much space (in points) should be reserved for the scrollbars of `Overflow::Scroll` and `Overflow::Auto` nodes.
-    pub scrollbar_width: f32,
+    /// How much space (in points) should be reserved for the scrollbars of `Overflow::Scroll` nodes.
+    ///
+    /// The `width` is the thickness of the vertical scrollbar, reserved at the right of nodes that scroll vertically, and
+    /// the `height` is the thickness of the horizontal scrollbar, reserved at the bottom of nodes that scroll horizontally.
+    pub scrollbar_width: Size<f32>,
 
     // Position properties
     /// What should the `position` value of this struct use as a base offset?
@@ -179,6 +273,8 @@ pub struct Style {
     /// How should the position of this element be tweaked relative to the layout defined?
     #[cfg_attr(feature = "serde", serde(default = "style_helpers::auto"))]
     pub inset: Rect<LengthPercentageAuto>,
+    /// Positions the node relative to its parent by an anchor point instead of its inset, if the node is absolutely positioned
+    pub anchor: Option<Anchor>,
 
     // Size properies
     /// Sets the initial size of the item
@@ -190,10 +286,19 @@ pub struct Style {
     /// Controls the maximum size of the item
     #[cfg_attr(feature = "serde", serde(default = "style_helpers::auto"))]
     pub max_size: Size<Dimension>,
+    /// Whether the automatic minimum size of this node as a flexbox or grid item is zero rather than content-based
+    ///
+    /// This is non-standard. It is equivalent to setting an `auto` `min_size` to zero in both axes, or to the automatic
+    /// minimum size of an item with non-`Visible` overflow, without the other effects of either. The item's content
+    /// isn't measured to find its minimum size. Defaults to `false`.
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    pub disable_automatic_min_size: bool,
     /// Sets the preferred aspect ratio for the item
     ///
     /// The ratio is calculated as width divided by height.
     pub aspect_ratio: Option<f32>,
+    /// Whether the node is sized as if it had no content
+    pub contain: Contain,
 
     // Spacing Properties
     /// How large should the margin be on each side?
@@ -205,6 +310,15 @@ pub struct Style {
     /// How large should the border be on each side?
     #[cfg_attr(feature = "serde", serde(default = "style_helpers::zero"))]
     pub border: Rect<LengthPercentage>,
+    /// Which dimension of the containing block percentages in the vertical margin, border and padding are resolved against
+    pub vertical_percentage_basis: VerticalPercentageBasis,
+    /// Whether CSS margin collapsing is disabled for this node and its in-flow block children
+    ///
+    /// This is non-standard. When enabled, the margins of this node don't collapse with those of its children, and the
+    /// margins of its children don't collapse with each other or with those of their own children, so that children
+    /// are stacked with their full margins between them. Only affects block layout. Defaults to `false`.
+    #[cfg(feature = "block_layout")]
+    pub disable_margin_collapsing: bool,
 
     // Alignment properties
     /// How this node's children aligned in the cross/block axis?
@@ -219,7 +333,9 @@ pub struct Style {
     pub justify_items: Option<AlignItems>,
     /// How this node should be aligned in the inline axis
     /// Falls back to the parents [`JustifyItems`] if not set
-    #[cfg(feature = "grid")]
+    ///
+    /// Flex items are only aligned by this property if their parent opts into it with [`Style::flex_justify_self`]
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
     pub justify_self: Option<AlignSelf>,
     /// How should content contained within this item be aligned in the cross/block axis
     #[cfg(any(feature = "flexbox", feature = "grid"))]
@@ -228,7 +344,11 @@ pub struct Style {
     #[cfg(any(feature = "flexbox", feature = "grid"))]
     pub justify_content: Option<JustifyContent>,
     /// How large should the gaps between items in a grid or flex container be?
-    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    ///
+    /// This is non-standard for block containers, where the `height` (row gap) is inserted between each pair of
+    /// adjacent in-flow children, in addition to their (possibly collapsed) margins. Browsers ignore `gap` on block
+    /// containers, so it has no effect unless set.
+    #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
     #[cfg_attr(feature = "serde", serde(default = "style_helpers::zero"))]
     pub gap: Size<LengthPercentage>,
 
@@ -239,6 +359,13 @@ pub struct Style {
     /// Should elements wrap, or stay in a single line?
     #[cfg(feature = "flexbox")]
     pub flex_wrap: FlexWrap,
+    /// Whether the children of this node are aligned within the free space of their flex line by their `justify_self`
+    ///
+    /// This is non-standard: CSS ignores `justify_self` on flex items. When enabled, an item aligned to the start, end
+    /// or center places the free space of its line after it, before it or around it respectively, in the same way as
+    /// `auto` margins do. Defaults to `false`.
+    #[cfg(feature = "flexbox")]
+    pub flex_justify_self: bool,
     /// Sets the initial main axis size of the item
     #[cfg(feature = "flexbox")]
     pub flex_basis: Dimension,
@@ -256,16 +383,16 @@ pub struct Style {
     // Grid container properies
     /// Defines the track sizing functions (widths) of the grid rows
     #[cfg(feature = "grid")]
-    pub grid_template_rows: GridTrackVec<TrackSizingFunction>,
+    pub grid_template_rows: GridTrackList<TrackSizingFunction>,
     /// Defines the track sizing functions (heights) of the grid columns
     #[cfg(feature = "grid")]
-    pub grid_template_columns: GridTrackVec<TrackSizingFunction>,
+    pub grid_template_columns: GridTrackList<TrackSizingFunction>,
     /// Defines the size of implicitly created rows
     #[cfg(feature = "grid")]
-    pub grid_auto_rows: GridTrackVec<NonRepeatedTrackSizingFunction>,
+    pub grid_auto_rows: GridTrackList<NonRepeatedTrackSizingFunction>,
     /// Defined the size of implicitly created columns
     #[cfg(feature = "grid")]
-    pub grid_auto_columns: GridTrackVec<NonRepeatedTrackSizingFunction>,
+    pub grid_auto_columns: GridTrackList<NonRepeatedTrackSizingFunction>,
     /// Controls how items get placed into the grid for auto-placed items
     #[cfg(feature = "grid")]
     pub grid_auto_flow: GridAutoFlow,
@@ -284,17 +411,24 @@ impl Style {
     pub const DEFAULT: Style = Style {
         display: Display::DEFAULT,
         overflow: Point { x: Overflow::Visible, y: Overflow::Visible },
-        scrollbar_width: 0.0,
+        scrollbar_width: Size { width: 0.0, height: 0.0 },
         position: Position::Relative,
         inset: Rect::auto(),
+        anchor: None,
         margin: Rect::zero(),
         padding: Rect::zero(),
         border: Rect::zero(),
+        vertical_percentage_basis: VerticalPercentageBasis::Width,
+        #[cfg(feature = "block_layout")]
+        disable_margin_collapsing: false,
         size: Size::auto(),
         min_size: Size::auto(),
         max_size: Size::auto(),
-        aspect_ratio: None,
         #[cfg(any(feature = "flexbox", feature = "grid"))]
+        disable_automatic_min_size: false,
+        aspect_ratio: None,
+        contain: Contain::None,
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
         gap: Size::zero(),
         // Aligment
         #[cfg(any(feature = "flexbox", feature = "grid"))]
@@ -303,7 +437,7 @@ impl Style {
         align_self: None,
         #[cfg(feature = "grid")]
         justify_items: None,
-        #[cfg(feature = "grid")]
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
         justify_self: None,
         #[cfg(any(feature = "flexbox", feature = "grid"))]
         align_content: None,
@@ -315,6 +449,8 @@ impl Style {
         #[cfg(feature = "flexbox")]
         flex_wrap: FlexWrap::NoWrap,
         #[cfg(feature = "flexbox")]
+        flex_justify_self: false,
+        #[cfg(feature = "flexbox")]
         flex_grow: 0.0,
         #[cfg(feature = "flexbox")]
         flex_shrink: 1.0,
@@ -322,13 +458,13 @@ impl Style {
         flex_basis: Dimension::Auto,
         // Grid
         #[cfg(feature = "grid")]
-        grid_template_rows: GridTrackVec::new(),
+        grid_template_rows: GridTrackList::new(),
         #[cfg(feature = "grid")]
-        grid_template_columns: GridTrackVec::new(),
+        grid_template_columns: GridTrackList::new(),
         #[cfg(feature = "grid")]
-        grid_auto_rows: GridTrackVec::new(),
+        grid_auto_rows: GridTrackList::new(),
         #[cfg(feature = "grid")]
-        grid_auto_columns: GridTrackVec::new(),
+        grid_auto_columns: GridTrackList::new(),
         #[cfg(feature = "grid")]
         grid_auto_flow: GridAutoFlow::Row,
         #[cfg(feature = "grid")]
@@ -344,6 +480,126 @@ impl Default for Style {
     }
 }
 
+impl Style {
+    /// Returns the sizes that horizontal and vertical percentages in the node's margin, border and padding are resolved
+    /// against, given the size of its containing block
+    #[inline(always)]
+    pub(crate) fn edge_percentage_basis<T: Copy>(&self, containing_block: Size<T>) -> Size<T> {
+        self.vertical_percentage_basis.resolve(containing_block)
+    }
+
+    /// Sets both [`Style::align_items`] and [`Style::justify_items`], like the CSS `place-items` shorthand
+    #[cfg(feature = "grid")]
+    pub fn set_place_items(&mut self, align: Option<AlignItems>, justify: Option<JustifyItems>) {
+        self.align_items = align;
+        self.justify_items = justify;
+    }
+
+    /// Sets both [`Style::align_content`] and [`Style::justify_content`], like the CSS `place-content` shorthand
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    pub fn set_place_content(&mut self, align: Option<AlignContent>, justify: Option<JustifyContent>) {
+        self.align_content = align;
+        self.justify_content = justify;
+    }
+
+    /// Sets both [`Style::align_self`] and [`Style::justify_self`], like the CSS `place-self` shorthand
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    pub fn set_place_self(&mut self, align: Option<AlignSelf>, justify: Option<JustifySelf>) {
+        self.align_self = align;
+        self.justify_self = justify;
+    }
+
+    /// Returns the style with its [`Style::min_size`], [`Style::size`] and [`Style::max_size`] replaced
+    #[must_use]
+    pub fn with_size_range(self, min: Size<Dimension>, preferred: Size<Dimension>, max: Size<Dimension>) -> Self {
+        Self { min_size: min, size: preferred, max_size: max, ..self }
+    }
+
+    /// Sets [`Style::min_size`], [`Style::size`] and [`Style::max_size`] together from a [`SizeRange`]
+    pub fn set_size_range(&mut self, range: SizeRange) {
+        self.min_size = range.limits.min;
+        self.size = range.preferred;
+        self.max_size = range.limits.max;
+    }
+
+    /// Returns the [`Style::min_size`], [`Style::size`] and [`Style::max_size`] of the style as a [`SizeRange`]
+    #[must_use]
+    pub fn size_range(&self) -> SizeRange {
+        SizeRange::new(self.min_size, self.size, self.max_size)
+    }
+}
+
+/// A preferred size, clamped between a minimum and a maximum size
+///
+/// This groups the [`Style::min_size`], [`Style::size`] and [`Style::max_size`] of a style, which are set together with
+/// [`Style::set_size_range`]. The constructors cover the common constraints, leaving the other sizes `auto`:
+///
+/// ```
+/// use taffy::prelude::*;
+/// use taffy::style::SizeRange;
+///
+/// let mut style = Style::default();
+/// style.set_size_range(SizeRange::at_least(Size::from_lengths(100.0, 20.0)));
+/// assert_eq!(style.min_size, Size::from_lengths(100.0, 20.0));
+/// assert_eq!(style.size, Size::auto());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SizeRange {
+    /// The preferred size, which corresponds to [`Style::size`]
+    pub preferred: Size<Dimension>,
+    /// The minimum and maximum sizes, which correspond to [`Style::min_size`] and [`Style::max_size`]
+    pub limits: MinMax<Size<Dimension>, Size<Dimension>>,
+}
+
+impl SizeRange {
+    /// A range that doesn't constrain the size at all
+    pub const AUTO: Self = Self::new(Size::auto(), Size::auto(), Size::auto());
+
+    /// Creates a range with the given minimum, preferred and maximum sizes
+    #[must_use]
+    pub const fn new(min: Size<Dimension>, preferred: Size<Dimension>, max: Size<Dimension>) -> Self {
+        Self { preferred, limits: MinMax { min, max } }
+    }
+
+    /// A range whose minimum, preferred and maximum sizes are all `size`, so that the node has exactly that size
+    #[must_use]
+    pub const fn exactly(size: Size<Dimension>) -> Self {
+        Self::new(size, size, size)
+    }
+
+    /// A range with an `auto` preferred size that is at least `min`
+    #[must_use]
+    pub const fn at_least(min: Size<Dimension>) -> Self {
+        Self::new(min, Size::auto(), Size::auto())
+    }
+
+    /// A range with an `auto` preferred size that is at most `max`
+    #[must_use]
+    pub const fn at_most(max: Size<Dimension>) -> Self {
+        Self::new(Size::auto(), Size::auto(), max)
+    }
+
+    /// A range with an `auto` preferred size that is between `min` and `max`
+    #[must_use]
+    pub const fn between(min: Size<Dimension>, max: Size<Dimension>) -> Self {
+        Self::new(min, Size::auto(), max)
+    }
+}
+
+impl Default for SizeRange {
+    fn default() -> Self {
+        Self::AUTO
+    }
+}
+
+impl From<SizeRange> for MinMax<Size<Dimension>, Size<Dimension>> {
+    /// Returns the minimum and maximum sizes of the range
+    fn from(range: SizeRange) -> Self {
+        range.limits
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Style;
@@ -357,28 +613,34 @@ mod tests {
         let old_defaults = Style {
             display: Default::default(),
             overflow: Default::default(),
-            scrollbar_width: 0.0,
+            scrollbar_width: Size::ZERO,
             position: Default::default(),
             #[cfg(feature = "flexbox")]
             flex_direction: Default::default(),
             #[cfg(feature = "flexbox")]
             flex_wrap: Default::default(),
+            #[cfg(feature = "flexbox")]
+            flex_justify_self: false,
             #[cfg(any(feature = "flexbox", feature = "grid"))]
             align_items: Default::default(),
             #[cfg(any(feature = "flexbox", feature = "grid"))]
             align_self: Default::default(),
             #[cfg(feature = "grid")]
             justify_items: Default::default(),
-            #[cfg(feature = "grid")]
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
             justify_self: Default::default(),
             #[cfg(any(feature = "flexbox", feature = "grid"))]
             align_content: Default::default(),
             #[cfg(any(feature = "flexbox", feature = "grid"))]
             justify_content: Default::default(),
             inset: Rect::auto(),
+            anchor: None,
             margin: Rect::zero(),
             padding: Rect::zero(),
             border: Rect::zero(),
+            vertical_percentage_basis: Default::default(),
+            #[cfg(feature = "block_layout")]
+            disable_margin_collapsing: false,
             gap: Size::zero(),
             #[cfg(feature = "flexbox")]
             flex_grow: 0.0,
@@ -389,7 +651,10 @@ mod tests {
             size: Size::auto(),
             min_size: Size::auto(),
             max_size: Size::auto(),
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            disable_automatic_min_size: false,
             aspect_ratio: Default::default(),
+            contain: Default::default(),
             #[cfg(feature = "grid")]
             grid_template_rows: Default::default(),
             #[cfg(feature = "grid")]
@@ -435,7 +700,7 @@ mod tests {
         }
 
         // Display and Position
-        assert_type_size::<Display>(1);
+        assert_type_size::<Display>(4);
         assert_type_size::<Position>(1);
         assert_type_size::<Overflow>(1);
 
@@ -468,12 +733,14 @@ mod tests {
         assert_type_size::<TrackSizingFunction>(32);
         assert_type_size::<Vec<NonRepeatedTrackSizingFunction>>(24);
         assert_type_size::<Vec<TrackSizingFunction>>(24);
+        assert_type_size::<GridTrackList<NonRepeatedTrackSizingFunction>>(8);
+        assert_type_size::<GridTrackList<TrackSizingFunction>>(8);
 
         // CSS Grid Item
         assert_type_size::<GridPlacement>(4);
         assert_type_size::<Line<GridPlacement>>(8);
 
         // Overall
-        assert_type_size::<Style>(352);
+        assert_type_size::<Style>(328);
     }
 }
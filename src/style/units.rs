@@ -0,0 +1,242 @@
+//! Resolution of units that depend on the context that a tree is laid out in, such as viewport- and font-relative units
+//!
+//! The layout algorithms only understand lengths and percentages, so the [`Taffy`](crate::Taffy) tree resolves
+//! context-dependent units into lengths before laying out the nodes whose styles use them.
+use super::{Anchor, Dimension, LengthPercentage, LengthPercentageAuto, Style};
+#[cfg(feature = "grid")]
+use super::{GridTrackList, MaxTrackSizingFunction, MinTrackSizingFunction, TrackSizingFunction};
+#[cfg(feature = "grid")]
+use crate::geometry::MinMax;
+use crate::geometry::{Point, Rect, Size};
+#[cfg(feature = "grid")]
+use crate::util::sys::GridTrackVec;
+
+/// The values that context-dependent units are resolved against
+pub(crate) struct UnitContext<'a> {
+    /// The size of the viewport, which viewport-relative units are resolved against
+    pub(crate) viewport: Size<f32>,
+    /// The font size of the root of the document, which `rem` units are resolved against
+    pub(crate) root_font_size: f32,
+    /// The font size of the node whose style is being resolved, which `em` units are resolved against
+    pub(crate) font_size: f32,
+    /// Resolves the custom unit with the given id into a length for the node whose style is being resolved
+    pub(crate) resolve_custom: &'a dyn Fn(u32) -> f32,
+}
+
+/// A style value that may contain context-dependent units
+pub(crate) trait ContextUnits {
+    /// Returns true if the value contains a unit that is resolved against a [`UnitContext`]
+    fn uses_context_units(&self) -> bool;
+
+    /// Replaces every context-dependent unit in the value with the length it resolves to
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>);
+}
+
+/// Implements [`ContextUnits`] for a length type with context-dependent variants
+macro_rules! impl_context_units {
+    ($ty:ident) => {
+        impl ContextUnits for $ty {
+            fn uses_context_units(&self) -> bool {
+                matches!(
+                    self,
+                    $ty::Vw(_) | $ty::Vh(_) | $ty::Vmin(_) | $ty::Vmax(_) | $ty::Rem(_) | $ty::Em(_) | $ty::Custom(_)
+                )
+            }
+
+            fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+                let viewport = context.viewport;
+                *self = match *self {
+                    $ty::Vw(fraction) => $ty::Length(fraction * viewport.width),
+                    $ty::Vh(fraction) => $ty::Length(fraction * viewport.height),
+                    $ty::Vmin(fraction) => $ty::Length(fraction * viewport.width.min(viewport.height)),
+                    $ty::Vmax(fraction) => $ty::Length(fraction * viewport.width.max(viewport.height)),
+                    $ty::Rem(multiple) => $ty::Length(multiple * context.root_font_size),
+                    $ty::Em(multiple) => $ty::Length(multiple * context.font_size),
+                    $ty::Custom(unit) => $ty::Length((context.resolve_custom)(unit)),
+                    other => other,
+                };
+            }
+        }
+    };
+}
+
+impl_context_units!(LengthPercentage);
+impl_context_units!(LengthPercentageAuto);
+impl_context_units!(Dimension);
+
+impl<T: ContextUnits> ContextUnits for Size<T> {
+    fn uses_context_units(&self) -> bool {
+        self.width.uses_context_units() || self.height.uses_context_units()
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        self.width.resolve_context_units(context);
+        self.height.resolve_context_units(context);
+    }
+}
+
+impl<T: ContextUnits> ContextUnits for Rect<T> {
+    fn uses_context_units(&self) -> bool {
+        self.left.uses_context_units()
+            || self.right.uses_context_units()
+            || self.top.uses_context_units()
+            || self.bottom.uses_context_units()
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        self.left.resolve_context_units(context);
+        self.right.resolve_context_units(context);
+        self.top.resolve_context_units(context);
+        self.bottom.resolve_context_units(context);
+    }
+}
+
+impl<T: ContextUnits> ContextUnits for Point<T> {
+    fn uses_context_units(&self) -> bool {
+        self.x.uses_context_units() || self.y.uses_context_units()
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        self.x.resolve_context_units(context);
+        self.y.resolve_context_units(context);
+    }
+}
+
+impl ContextUnits for Option<Anchor> {
+    fn uses_context_units(&self) -> bool {
+        self.is_some_and(|anchor| anchor.offset.uses_context_units())
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        if let Some(anchor) = self {
+            anchor.offset.resolve_context_units(context);
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl ContextUnits for MinTrackSizingFunction {
+    fn uses_context_units(&self) -> bool {
+        matches!(self, Self::Fixed(length) if length.uses_context_units())
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        if let Self::Fixed(length) = self {
+            length.resolve_context_units(context);
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl ContextUnits for MaxTrackSizingFunction {
+    fn uses_context_units(&self) -> bool {
+        matches!(self, Self::Fixed(length) | Self::FitContent(length) if length.uses_context_units())
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        if let Self::Fixed(length) | Self::FitContent(length) = self {
+            length.resolve_context_units(context);
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl<Min: ContextUnits, Max: ContextUnits> ContextUnits for MinMax<Min, Max> {
+    fn uses_context_units(&self) -> bool {
+        self.min.uses_context_units() || self.max.uses_context_units()
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        self.min.resolve_context_units(context);
+        self.max.resolve_context_units(context);
+    }
+}
+
+#[cfg(feature = "grid")]
+impl ContextUnits for TrackSizingFunction {
+    fn uses_context_units(&self) -> bool {
+        match self {
+            Self::Single(track) => track.uses_context_units(),
+            Self::Repeat(_, tracks) => tracks.uses_context_units(),
+        }
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        match self {
+            Self::Single(track) => track.resolve_context_units(context),
+            Self::Repeat(_, tracks) => tracks.resolve_context_units(context),
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl<T: ContextUnits> ContextUnits for GridTrackVec<T> {
+    fn uses_context_units(&self) -> bool {
+        self.iter().any(ContextUnits::uses_context_units)
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        for track in self.iter_mut() {
+            track.resolve_context_units(context);
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl<T: ContextUnits> ContextUnits for GridTrackList<T> {
+    fn uses_context_units(&self) -> bool {
+        self.iter().any(ContextUnits::uses_context_units)
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        for track in self.iter_mut() {
+            track.resolve_context_units(context);
+        }
+    }
+}
+
+impl ContextUnits for Style {
+    fn uses_context_units(&self) -> bool {
+        let uses_context_units = self.inset.uses_context_units()
+            || self.anchor.uses_context_units()
+            || self.size.uses_context_units()
+            || self.min_size.uses_context_units()
+            || self.max_size.uses_context_units()
+            || self.margin.uses_context_units()
+            || self.padding.uses_context_units()
+            || self.border.uses_context_units();
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        let uses_context_units = uses_context_units || self.gap.uses_context_units();
+        #[cfg(feature = "flexbox")]
+        let uses_context_units = uses_context_units || self.flex_basis.uses_context_units();
+        #[cfg(feature = "grid")]
+        let uses_context_units = uses_context_units
+            || self.grid_template_rows.uses_context_units()
+            || self.grid_template_columns.uses_context_units()
+            || self.grid_auto_rows.uses_context_units()
+            || self.grid_auto_columns.uses_context_units();
+        uses_context_units
+    }
+
+    fn resolve_context_units(&mut self, context: &UnitContext<'_>) {
+        self.inset.resolve_context_units(context);
+        self.anchor.resolve_context_units(context);
+        self.size.resolve_context_units(context);
+        self.min_size.resolve_context_units(context);
+        self.max_size.resolve_context_units(context);
+        self.margin.resolve_context_units(context);
+        self.padding.resolve_context_units(context);
+        self.border.resolve_context_units(context);
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        self.gap.resolve_context_units(context);
+        #[cfg(feature = "flexbox")]
+        self.flex_basis.resolve_context_units(context);
+        #[cfg(feature = "grid")]
+        {
+            self.grid_template_rows.resolve_context_units(context);
+            self.grid_template_columns.resolve_context_units(context);
+            self.grid_auto_rows.resolve_context_units(context);
+            self.grid_auto_columns.resolve_context_units(context);
+        }
+    }
+}
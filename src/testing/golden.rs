@@ -0,0 +1,375 @@
+//! Golden-layout regression testing: recording the layouts of a tree and checking later layouts against them
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::geometry::{Point, Size};
+use crate::tree::{NodeId, Taffy, TaffyResult};
+use crate::util::sys::{abs, BTreeMap, String, Vec};
+
+/// The expected layout of a single node in [`GoldenLayouts`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenNode {
+    /// The index of each node on the way from the root to this node, among the children of its parent.
+    /// The path of the root is empty.
+    pub path: Vec<usize>,
+    /// The key of the node, if it has one. This is informational, and isn't compared.
+    pub label: Option<String>,
+    /// The location of the node relative to its parent
+    pub location: Point<f32>,
+    /// The size of the node
+    pub size: Size<f32>,
+    /// The tolerance used when comparing this node, overriding [`GoldenLayouts::tolerance`]
+    pub tolerance: Option<f32>,
+}
+
+impl GoldenNode {
+    /// Returns whether the layout of `actual` is within `tolerance` of this node's layout
+    fn matches(&self, actual: &GoldenNode, tolerance: f32) -> bool {
+        let close = |expected: f32, actual: f32| {
+            (expected.is_nan() && actual.is_nan()) || expected == actual || abs(expected - actual) <= tolerance
+        };
+        close(self.location.x, actual.location.x)
+            && close(self.location.y, actual.location.y)
+            && close(self.size.width, actual.size.width)
+            && close(self.size.height, actual.size.height)
+    }
+
+    /// Writes the path of the node
+    fn write_path(&self, f: &mut Formatter) -> FmtResult {
+        if self.path.is_empty() {
+            return f.write_str("/");
+        }
+        for index in &self.path {
+            write!(f, "/{index}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the layout of the node
+    fn write_layout(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{} {} {} {}", self.location.x, self.location.y, self.size.width, self.size.height)
+    }
+}
+
+/// The expected layouts of a tree, which pin its layout behaviour in regression tests
+///
+/// Layouts are recorded with [`GoldenLayouts::record`], stored in golden files using their [`Display`]
+/// implementation, and read back with [`GoldenLayouts::parse`].
+///
+/// Golden files use a line-based text format which is readable in diffs and can be edited by hand:
+///
+/// ```text
+/// tolerance 0.01
+/// / 0 0 200 100 # root
+/// /0 0 0 50 100 # sidebar
+/// /1 50 0 150 100 ~0.5 # content
+/// ```
+///
+/// Each node line starts with the node's path, which is the list of child indices leading to it from the root, and
+/// is followed by its x and y location, its width and its height. A `~` value overrides the tolerance for that node,
+/// and a `#` starts a comment, which is used to record the node's key when it has one. The `tolerance` line sets the
+/// default tolerance, and blank lines are ignored.
+///
+/// ```
+/// use taffy::prelude::*;
+/// use taffy::testing::GoldenLayouts;
+///
+/// let mut taffy = Taffy::new();
+/// let child = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+/// let root_style = Style { size: Size::from_lengths(100.0, 10.0), ..Default::default() };
+/// let root = taffy.new_with_children(root_style, &[child]).unwrap();
+/// taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+///
+/// let golden = GoldenLayouts::parse("/ 0 0 100 10\n/0 0 0 100 10 ~0.5\n").unwrap();
+/// assert!(golden.compare(&GoldenLayouts::record(&taffy, root).unwrap()).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenLayouts {
+    /// The largest difference in each of a node's location and size values that is accepted by
+    /// [`compare`](Self::compare), unless the node has its own tolerance
+    pub tolerance: f32,
+    /// The nodes of the tree, in depth-first order
+    pub nodes: Vec<GoldenNode>,
+}
+
+impl GoldenLayouts {
+    /// The default tolerance, which absorbs floating point differences without hiding changes in layout
+    pub const DEFAULT_TOLERANCE: f32 = 0.01;
+
+    /// Records the computed layouts of `root` and its descendants
+    pub fn record(taffy: &Taffy, root: NodeId) -> TaffyResult<Self> {
+        let mut nodes = Vec::new();
+        let mut stack = Vec::new();
+        stack.push((root, Vec::new()));
+        while let Some((node, path)) = stack.pop() {
+            let layout = taffy.layout(node)?;
+            let children = taffy.children(node)?;
+            // Children are pushed in reverse so that they are recorded in order
+            for (index, child) in children.into_iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                stack.push((child, child_path));
+            }
+            nodes.push(GoldenNode {
+                path,
+                label: taffy.key(node).map(String::from),
+                location: layout.location,
+                size: layout.size,
+                tolerance: None,
+            });
+        }
+        Ok(Self { tolerance: Self::DEFAULT_TOLERANCE, nodes })
+    }
+
+    /// Parses golden layouts from the format written by their [`Display`] implementation
+    pub fn parse(text: &str) -> Result<Self, GoldenParseError> {
+        let mut golden = Self { tolerance: Self::DEFAULT_TOLERANCE, nodes: Vec::new() };
+        for (index, line) in text.lines().enumerate() {
+            let error = |kind| GoldenParseError { line: index + 1, kind };
+            let (content, comment) = match line.split_once('#') {
+                Some((content, comment)) => (content, Some(comment.trim())),
+                None => (line, None),
+            };
+            let mut words = content.split_whitespace();
+            let Some(first) = words.next() else { continue };
+
+            if first == "tolerance" {
+                golden.tolerance =
+                    parse_tolerance(words.next()).ok_or(error(GoldenParseErrorKind::InvalidTolerance))?;
+                continue;
+            }
+            if !first.starts_with('/') {
+                return Err(error(GoldenParseErrorKind::InvalidPath));
+            }
+            let path = first
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| segment.parse().map_err(|_| error(GoldenParseErrorKind::InvalidPath)))
+                .collect::<Result<Vec<usize>, _>>()?;
+
+            let mut values = [0.0; 4];
+            for value in &mut values {
+                let word = words.next().ok_or(error(GoldenParseErrorKind::MissingValue))?;
+                *value = word.parse().map_err(|_| error(GoldenParseErrorKind::InvalidNumber))?;
+            }
+            let tolerance = match words.next() {
+                Some(word) => {
+                    Some(parse_tolerance(word.strip_prefix('~')).ok_or(error(GoldenParseErrorKind::InvalidTolerance))?)
+                }
+                None => None,
+            };
+            if words.next().is_some() {
+                return Err(error(GoldenParseErrorKind::UnexpectedValue));
+            }
+
+            golden.nodes.push(GoldenNode {
+                path,
+                label: comment.filter(|comment| !comment.is_empty()).map(String::from),
+                location: Point { x: values[0], y: values[1] },
+                size: Size { width: values[2], height: values[3] },
+                tolerance,
+            });
+        }
+        Ok(golden)
+    }
+
+    /// Compares these expected layouts with `actual`, typically freshly [recorded](Self::record) layouts.
+    /// Nodes are matched by their path, and their layouts must be within the node's tolerance of each other.
+    pub fn compare(&self, actual: &GoldenLayouts) -> Result<(), GoldenMismatch> {
+        let actual_nodes: BTreeMap<&[usize], &GoldenNode> =
+            actual.nodes.iter().map(|node| (node.path.as_slice(), node)).collect();
+        let expected_paths: BTreeMap<&[usize], ()> = self.nodes.iter().map(|node| (node.path.as_slice(), ())).collect();
+
+        let mut differences = Vec::new();
+        for expected in &self.nodes {
+            match actual_nodes.get(expected.path.as_slice()) {
+                Some(actual) if expected.matches(actual, expected.tolerance.unwrap_or(self.tolerance)) => {}
+                Some(actual) => differences
+                    .push(GoldenDifference::Changed { expected: expected.clone(), actual: (*actual).clone() }),
+                None => differences.push(GoldenDifference::Missing(expected.clone())),
+            }
+        }
+        for node in actual.nodes.iter().filter(|node| !expected_paths.contains_key(node.path.as_slice())) {
+            differences.push(GoldenDifference::Unexpected(node.clone()));
+        }
+
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            Err(GoldenMismatch { node_count: self.nodes.len(), differences })
+        }
+    }
+
+    /// Copies the tolerances of `other` into these layouts, for nodes that have the same path.
+    /// This keeps hand-edited tolerances when a golden file is re-recorded.
+    pub fn copy_tolerances_from(&mut self, other: &GoldenLayouts) {
+        self.tolerance = other.tolerance;
+        let tolerances: BTreeMap<&[usize], f32> =
+            other.nodes.iter().filter_map(|node| Some((node.path.as_slice(), node.tolerance?))).collect();
+        for node in &mut self.nodes {
+            node.tolerance = tolerances.get(node.path.as_slice()).copied();
+        }
+    }
+}
+
+/// Parses a non-negative tolerance value
+fn parse_tolerance(word: Option<&str>) -> Option<f32> {
+    word?.parse().ok().filter(|tolerance: &f32| *tolerance >= 0.0)
+}
+
+impl Display for GoldenLayouts {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        writeln!(f, "tolerance {}", self.tolerance)?;
+        for node in &self.nodes {
+            node.write_path(f)?;
+            f.write_str(" ")?;
+            node.write_layout(f)?;
+            if let Some(tolerance) = node.tolerance {
+                write!(f, " ~{tolerance}")?;
+            }
+            // Labels containing newlines would break the format, so only their first line is kept
+            if let Some(label) = node.label.as_deref().and_then(|label| label.lines().next()) {
+                write!(f, " # {label}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error encountered by [`GoldenLayouts::parse`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldenParseError {
+    /// The line on which the error was found, starting at 1
+    pub line: usize,
+    /// What went wrong
+    pub kind: GoldenParseErrorKind,
+}
+
+/// The kinds of [`GoldenParseError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoldenParseErrorKind {
+    /// A line didn't start with a valid node path or `tolerance`
+    InvalidPath,
+    /// A node had fewer than four layout values
+    MissingValue,
+    /// A layout value wasn't a number
+    InvalidNumber,
+    /// A tolerance wasn't a non-negative number
+    InvalidTolerance,
+    /// A node line had more values than expected
+    UnexpectedValue,
+}
+
+impl Display for GoldenParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let message = match self.kind {
+            GoldenParseErrorKind::InvalidPath => "expected a node path or `tolerance`",
+            GoldenParseErrorKind::MissingValue => "expected x, y, width and height values",
+            GoldenParseErrorKind::InvalidNumber => "invalid number",
+            GoldenParseErrorKind::InvalidTolerance => "invalid tolerance",
+            GoldenParseErrorKind::UnexpectedValue => "unexpected value after the layout",
+        };
+        write!(f, "line {}: {message}", self.line)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GoldenParseError {}
+
+/// A difference between golden layouts and the actual layouts of a tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoldenDifference {
+    /// The layout of a node differs by more than its tolerance
+    Changed {
+        /// The expected layout
+        expected: GoldenNode,
+        /// The actual layout
+        actual: GoldenNode,
+    },
+    /// A node in the golden layouts doesn't exist in the tree
+    Missing(GoldenNode),
+    /// A node in the tree doesn't exist in the golden layouts
+    Unexpected(GoldenNode),
+}
+
+/// The differences found by [`GoldenLayouts::compare`]
+///
+/// Its [`Display`] implementation renders a readable diff of the differing nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenMismatch {
+    /// The number of nodes in the golden layouts
+    pub node_count: usize,
+    /// The differences, in the order of the golden layouts followed by the unexpected nodes
+    pub differences: Vec<GoldenDifference>,
+}
+
+impl Display for GoldenMismatch {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        writeln!(f, "{} of {} nodes differ from the golden layouts:", self.differences.len(), self.node_count)?;
+        let write_heading = |f: &mut Formatter, node: &GoldenNode, suffix: &str| {
+            f.write_str("  ")?;
+            node.write_path(f)?;
+            if let Some(label) = &node.label {
+                write!(f, " ({label})")?;
+            }
+            writeln!(f, "{suffix}")
+        };
+        for difference in &self.differences {
+            match difference {
+                GoldenDifference::Changed { expected, actual } => {
+                    write_heading(f, expected, "")?;
+                    f.write_str("    - ")?;
+                    expected.write_layout(f)?;
+                    f.write_str("\n    + ")?;
+                    actual.write_layout(f)?;
+                    writeln!(f)?;
+                }
+                GoldenDifference::Missing(node) => write_heading(f, node, ": missing from the tree")?,
+                GoldenDifference::Unexpected(node) => write_heading(f, node, ": not in the golden layouts")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GoldenMismatch {}
+
+/// Asserts that the layouts of `root` and its descendants match the golden file at `path`, panicking with a diff of
+/// the differing nodes if they don't
+///
+/// If the file doesn't exist, or the `TAFFY_UPDATE_GOLDEN` environment variable is set, the layouts are recorded to
+/// the file instead. Tolerances in an existing file are kept when it is re-recorded.
+#[cfg(feature = "std")]
+#[track_caller]
+pub fn assert_golden_layouts(taffy: &Taffy, root: NodeId, path: impl AsRef<std::path::Path>) {
+    let path = path.as_ref();
+    let mut actual = GoldenLayouts::record(taffy, root).expect("the root node should exist");
+    let existing = match std::fs::read_to_string(path) {
+        Ok(text) => Some(
+            GoldenLayouts::parse(&text)
+                .unwrap_or_else(|error| panic!("invalid golden file {}: {error}", path.display())),
+        ),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => panic!("couldn't read golden file {}: {error}", path.display()),
+    };
+
+    match existing {
+        Some(expected) if std::env::var_os("TAFFY_UPDATE_GOLDEN").is_none() => {
+            if let Err(mismatch) = expected.compare(&actual) {
+                panic!("layouts don't match golden file {}\n{mismatch}", path.display());
+            }
+        }
+        existing => {
+            if let Some(existing) = existing {
+                actual.copy_tolerances_from(&existing);
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .unwrap_or_else(|error| panic!("couldn't create {}: {error}", parent.display()));
+            }
+            std::fs::write(path, actual.to_string())
+                .unwrap_or_else(|error| panic!("couldn't write golden file {}: {error}", path.display()));
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Utilities for benchmarking and testing code that embeds Taffy
+//!
+//! - [`RandomTreeGenerator`] builds reproducible pseudo-random trees, for performance and fuzz testing.
+//! - [`GoldenLayouts`] records the layouts of a tree to a golden file, and checks later layouts against it, to pin
+//!   layout behaviour across upgrades.
+
+mod golden;
+mod random;
+
+#[cfg(feature = "std")]
+pub use golden::assert_golden_layouts;
+pub use golden::{GoldenDifference, GoldenLayouts, GoldenMismatch, GoldenNode, GoldenParseError, GoldenParseErrorKind};
+pub use random::{RandomTreeGenerator, StyleDistribution, TestRng, TreeShape};
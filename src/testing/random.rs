@@ -0,0 +1,459 @@
+//! Pseudo-random tree generators for benchmarking and fuzz testing
+//!
+//! A [`RandomTreeGenerator`] builds trees in a [`Taffy`] whose shape is controlled by a [`TreeShape`] and whose styles
+//! are drawn from a [`StyleDistribution`]. Generation is fully determined by the seed, so a tree that triggers a bug
+//! or a performance problem can be reproduced from its seed alone.
+//!
+//! ```
+//! use taffy::prelude::*;
+//! use taffy::testing::{RandomTreeGenerator, StyleDistribution, TreeShape};
+//!
+//! let mut taffy = Taffy::new();
+//! let shape = TreeShape { max_nodes: 500, ..Default::default() };
+//! let mut generator = RandomTreeGenerator::new(12345, shape, StyleDistribution::flexbox());
+//! let root = generator.generate(&mut taffy).unwrap();
+//!
+//! taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+//! ```
+use core::ops::{Range, RangeInclusive};
+
+#[cfg(feature = "grid")]
+use crate::geometry::Line;
+use crate::geometry::{Rect, Size};
+#[cfg(any(feature = "block_layout", feature = "flexbox", feature = "grid"))]
+use crate::style::Display;
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+use crate::style::{AlignContent, AlignItems, JustifyContent};
+use crate::style::{Dimension, LengthPercentage, LengthPercentageAuto, Position, Style};
+#[cfg(feature = "flexbox")]
+use crate::style::{FlexDirection, FlexWrap};
+#[cfg(feature = "grid")]
+use crate::style::{GridPlacement, TrackSizingFunction};
+#[cfg(feature = "grid")]
+use crate::style_helpers::{auto, fr, length, line, max_content, min_content, minmax, percent, span};
+use crate::tree::{NodeId, Taffy, TaffyResult};
+use crate::util::sys::Vec;
+
+/// A small, fast pseudo-random number generator (SplitMix64)
+///
+/// It is not suitable for cryptographic use, but produces the same sequence on every platform for a given seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRng {
+    /// The internal state, which is advanced by every generated number
+    state: u64,
+}
+
+impl TestRng {
+    /// Creates a generator whose sequence is determined by `seed`
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates a uniformly distributed `u64`
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a uniformly distributed `f32` in `0.0..1.0`
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Generates a uniformly distributed `f32` within `range`
+    pub fn range_f32(&mut self, range: Range<f32>) -> f32 {
+        range.start + self.next_f32() * (range.end - range.start)
+    }
+
+    /// Generates a uniformly distributed `u32` within `range`. Returns the start of the range if it is empty.
+    pub fn range_u32(&mut self, range: RangeInclusive<u32>) -> u32 {
+        let (start, end) = range.into_inner();
+        if end <= start {
+            return start;
+        }
+        let count = u64::from(end - start) + 1;
+        start + (self.next_u64() % count) as u32
+    }
+
+    /// Returns `true` with the given probability
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Picks an index into `weights` with a probability proportional to its weight.
+    /// Returns `None` if no weight is positive.
+    pub fn weighted_index(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().filter(|weight| **weight > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut choice = self.next_f32() * total;
+        let mut last_positive = None;
+        for (index, weight) in weights.iter().enumerate().filter(|(_, weight)| **weight > 0.0) {
+            if choice < *weight {
+                return Some(index);
+            }
+            choice -= weight;
+            last_positive = Some(index);
+        }
+        // Rounding may leave a tiny remainder after the last weight
+        last_positive
+    }
+}
+
+/// Controls the depth and breadth of trees built by a [`RandomTreeGenerator`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeShape {
+    /// The maximum number of nodes in the tree, including the root
+    pub max_nodes: usize,
+    /// The maximum depth of the tree. The root is at depth 0, so a depth of 1 produces a root with only leaf children.
+    pub max_depth: u32,
+    /// The number of children of each container node is uniformly distributed within this range
+    pub children: RangeInclusive<u32>,
+    /// The probability that a node other than the root is a leaf, even though the maximum depth hasn't been reached
+    pub leaf_probability: f32,
+}
+
+impl Default for TreeShape {
+    fn default() -> Self {
+        Self { max_nodes: 1_000, max_depth: 6, children: 1..=8, leaf_probability: 0.3 }
+    }
+}
+
+impl TreeShape {
+    /// A root with `max_nodes - 1` leaf children
+    #[must_use]
+    pub fn wide(max_nodes: usize) -> Self {
+        let children = max_nodes.saturating_sub(1).min(u32::MAX as usize) as u32;
+        Self { max_nodes, max_depth: 1, children: children..=children, leaf_probability: 0.0 }
+    }
+
+    /// A balanced tree in which every container has `branching_factor` children, which is as deep as `max_nodes` allows
+    #[must_use]
+    pub fn deep(max_nodes: usize, branching_factor: u32) -> Self {
+        Self { max_nodes, max_depth: u32::MAX, children: branching_factor..=branching_factor, leaf_probability: 0.0 }
+    }
+}
+
+/// Controls the styles of the nodes built by a [`RandomTreeGenerator`]
+///
+/// The presets ([`block`](Self::block), [`flexbox`](Self::flexbox), [`grid`](Self::grid) and [`mixed`](Self::mixed))
+/// cover the common cases, and their fields can be adjusted to focus on the styles of interest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleDistribution {
+    /// The relative weight of block containers. Ignored unless the `block_layout` feature is enabled.
+    pub block_weight: f32,
+    /// The relative weight of flex containers. Ignored unless the `flexbox` feature is enabled.
+    pub flex_weight: f32,
+    /// The relative weight of grid containers. Ignored unless the `grid` feature is enabled.
+    pub grid_weight: f32,
+    /// The probability that each dimension of a node's size is `auto`
+    pub auto_size_probability: f32,
+    /// The probability that each dimension of a node's size that isn't `auto` is a percentage rather than a length
+    pub percent_size_probability: f32,
+    /// The range from which lengths are drawn
+    pub lengths: Range<f32>,
+    /// The probability that each side of a node's margin, padding and border is non-zero
+    pub spacing_probability: f32,
+    /// The probability that each dimension of a node's min and max size is set
+    pub min_max_probability: f32,
+    /// The probability that a node is absolutely positioned
+    pub absolute_probability: f32,
+    /// The probability that a node has an aspect ratio
+    pub aspect_ratio_probability: f32,
+    /// The maximum number of explicit tracks in each axis of a grid container
+    pub max_grid_tracks: u32,
+}
+
+impl Default for StyleDistribution {
+    fn default() -> Self {
+        Self::mixed()
+    }
+}
+
+impl StyleDistribution {
+    /// Containers of every enabled layout algorithm, in equal proportions
+    #[must_use]
+    pub fn mixed() -> Self {
+        Self {
+            block_weight: 1.0,
+            flex_weight: 1.0,
+            grid_weight: 1.0,
+            auto_size_probability: 0.2,
+            percent_size_probability: 0.25,
+            lengths: 0.0..500.0,
+            spacing_probability: 0.1,
+            min_max_probability: 0.05,
+            absolute_probability: 0.02,
+            aspect_ratio_probability: 0.02,
+            max_grid_tracks: 4,
+        }
+    }
+
+    /// Only block containers
+    #[must_use]
+    pub fn block() -> Self {
+        Self { flex_weight: 0.0, grid_weight: 0.0, ..Self::mixed() }
+    }
+
+    /// Only flex containers
+    #[must_use]
+    pub fn flexbox() -> Self {
+        Self { block_weight: 0.0, grid_weight: 0.0, ..Self::mixed() }
+    }
+
+    /// Only grid containers
+    #[must_use]
+    pub fn grid() -> Self {
+        Self { block_weight: 0.0, flex_weight: 0.0, ..Self::mixed() }
+    }
+}
+
+/// Builds pseudo-random trees in a [`Taffy`]
+///
+/// Each call to [`generate`](Self::generate) continues the generator's random sequence, so successive trees differ
+/// while the whole sequence is reproducible from the seed.
+#[derive(Debug, Clone)]
+pub struct RandomTreeGenerator {
+    /// The shape of the generated trees
+    pub shape: TreeShape,
+    /// The distribution of the styles of the generated nodes
+    pub styles: StyleDistribution,
+    /// The source of randomness
+    pub rng: TestRng,
+}
+
+impl RandomTreeGenerator {
+    /// Creates a generator whose output is determined by `seed`
+    #[must_use]
+    pub fn new(seed: u64, shape: TreeShape, styles: StyleDistribution) -> Self {
+        Self { shape, styles, rng: TestRng::new(seed) }
+    }
+
+    /// Builds a tree in `taffy` and returns its root
+    ///
+    /// The tree is built breadth-first, so the node budget of the [`TreeShape`] is spread across the levels of the tree
+    /// rather than being used up by the first branch.
+    pub fn generate(&mut self, taffy: &mut Taffy) -> TaffyResult<NodeId> {
+        let root = taffy.new_leaf(Style::DEFAULT)?;
+        let mut node_count = 1;
+        let mut queue = Vec::new();
+        queue.push((root, 0));
+        let mut next = 0;
+
+        while let Some(&(node, depth)) = queue.get(next) {
+            next += 1;
+            let is_container = depth < self.shape.max_depth
+                && node_count < self.shape.max_nodes
+                && (depth == 0 || !self.rng.chance(self.shape.leaf_probability));
+            let child_count = if is_container {
+                let child_count = self.rng.range_u32(self.shape.children.clone()) as usize;
+                child_count.min(self.shape.max_nodes - node_count)
+            } else {
+                0
+            };
+
+            let mut style = self.random_style(child_count > 0);
+            if node == root {
+                // The root can't be positioned against anything
+                style.position = Position::Relative;
+            }
+            taffy.set_style(node, style)?;
+            if child_count > 0 {
+                let children =
+                    (0..child_count).map(|_| taffy.new_leaf(Style::DEFAULT)).collect::<TaffyResult<Vec<_>>>()?;
+                taffy.set_children(node, &children)?;
+                queue.extend(children.into_iter().map(|child| (child, depth + 1)));
+                node_count += child_count;
+            }
+        }
+
+        Ok(root)
+    }
+
+    /// Generates a random style. Containers are given the display mode and container styles of one of the layout
+    /// algorithms, and every node is given random sizing, spacing and item styles.
+    pub fn random_style(&mut self, is_container: bool) -> Style {
+        let mut style = Style {
+            size: self.random_size(),
+            margin: self.random_rect(LengthPercentageAuto::Length),
+            padding: self.random_rect(|length| LengthPercentage::Length(length / 10.0)),
+            border: self.random_rect(|length| LengthPercentage::Length(length / 50.0)),
+            ..Style::DEFAULT
+        };
+        if self.rng.chance(self.styles.min_max_probability) {
+            style.min_size = self.random_size();
+        }
+        if self.rng.chance(self.styles.min_max_probability) {
+            style.max_size = self.random_size();
+        }
+        if self.rng.chance(self.styles.aspect_ratio_probability) {
+            style.aspect_ratio = Some(self.rng.range_f32(0.25..4.0));
+        }
+        if self.rng.chance(self.styles.absolute_probability) {
+            style.position = Position::Absolute;
+        }
+        self.randomize_item_style(&mut style);
+
+        if is_container {
+            let weights = [
+                if cfg!(feature = "block_layout") { self.styles.block_weight } else { 0.0 },
+                if cfg!(feature = "flexbox") { self.styles.flex_weight } else { 0.0 },
+                if cfg!(feature = "grid") { self.styles.grid_weight } else { 0.0 },
+            ];
+            match self.rng.weighted_index(&weights) {
+                #[cfg(feature = "block_layout")]
+                Some(0) => style.display = Display::Block,
+                #[cfg(feature = "flexbox")]
+                Some(1) => self.randomize_flex_container(&mut style),
+                #[cfg(feature = "grid")]
+                Some(2) => self.randomize_grid_container(&mut style),
+                _ => {}
+            }
+        }
+
+        style
+    }
+
+    /// Generates a random size, where each dimension may be `auto`, a percentage or a length
+    fn random_size(&mut self) -> Size<Dimension> {
+        Size { width: self.random_dimension(), height: self.random_dimension() }
+    }
+
+    /// Generates a random dimension, which may be `auto`, a percentage or a length
+    fn random_dimension(&mut self) -> Dimension {
+        if self.rng.chance(self.styles.auto_size_probability) {
+            Dimension::Auto
+        } else if self.rng.chance(self.styles.percent_size_probability) {
+            Dimension::Percent(self.rng.next_f32())
+        } else {
+            Dimension::Length(self.rng.range_f32(self.styles.lengths.clone()))
+        }
+    }
+
+    /// Generates a rect whose sides are each zero, or a length converted with `length`
+    fn random_rect<T>(&mut self, length: impl Fn(f32) -> T) -> Rect<T> {
+        let mut side = || {
+            let value = if self.rng.chance(self.styles.spacing_probability) {
+                self.rng.range_f32(self.styles.lengths.clone())
+            } else {
+                0.0
+            };
+            length(value)
+        };
+        Rect { left: side(), right: side(), top: side(), bottom: side() }
+    }
+
+    /// Sets the styles that a node's parent uses to lay it out as a flex or grid item
+    #[allow(unused_variables)]
+    fn randomize_item_style(&mut self, style: &mut Style) {
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        {
+            style.align_self = self.random_align_items();
+        }
+        #[cfg(feature = "flexbox")]
+        {
+            style.flex_grow = if self.rng.chance(0.3) { self.rng.range_f32(0.0..3.0) } else { 0.0 };
+            style.flex_shrink = if self.rng.chance(0.8) { 1.0 } else { self.rng.range_f32(0.0..3.0) };
+            if self.rng.chance(0.2) {
+                style.flex_basis = self.random_dimension();
+            }
+        }
+        #[cfg(feature = "grid")]
+        {
+            style.grid_row = self.random_grid_placement();
+            style.grid_column = self.random_grid_placement();
+        }
+    }
+
+    /// Generates a random cross-axis alignment, which is `None` half of the time
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn random_align_items(&mut self) -> Option<AlignItems> {
+        const VALUES: [AlignItems; 5] =
+            [AlignItems::Start, AlignItems::End, AlignItems::Center, AlignItems::Baseline, AlignItems::Stretch];
+        if self.rng.chance(0.5) {
+            None
+        } else {
+            Some(VALUES[self.rng.range_u32(0..=VALUES.len() as u32 - 1) as usize])
+        }
+    }
+
+    /// Generates random container alignment styles
+    #[cfg(any(feature = "flexbox", feature = "grid"))]
+    fn randomize_container_alignment(&mut self, style: &mut Style) {
+        const CONTENT: [AlignContent; 7] = [
+            AlignContent::Start,
+            AlignContent::End,
+            AlignContent::Center,
+            AlignContent::Stretch,
+            AlignContent::SpaceBetween,
+            AlignContent::SpaceAround,
+            AlignContent::SpaceEvenly,
+        ];
+        style.align_items = self.random_align_items();
+        if self.rng.chance(0.5) {
+            style.align_content = Some(CONTENT[self.rng.range_u32(0..=CONTENT.len() as u32 - 1) as usize]);
+        }
+        if self.rng.chance(0.5) {
+            let justify_content: JustifyContent = CONTENT[self.rng.range_u32(0..=CONTENT.len() as u32 - 1) as usize];
+            style.justify_content = Some(justify_content);
+        }
+        if self.rng.chance(self.styles.spacing_probability) {
+            let gap = self.rng.range_f32(self.styles.lengths.clone()) / 10.0;
+            style.gap = Size { width: LengthPercentage::Length(gap), height: LengthPercentage::Length(gap) };
+        }
+    }
+
+    /// Makes `style` a flex container with a random direction, wrapping and alignment
+    #[cfg(feature = "flexbox")]
+    fn randomize_flex_container(&mut self, style: &mut Style) {
+        const DIRECTIONS: [FlexDirection; 4] =
+            [FlexDirection::Row, FlexDirection::Column, FlexDirection::RowReverse, FlexDirection::ColumnReverse];
+        const WRAPS: [FlexWrap; 3] = [FlexWrap::NoWrap, FlexWrap::Wrap, FlexWrap::WrapReverse];
+        style.display = Display::Flex;
+        style.flex_direction = DIRECTIONS[self.rng.range_u32(0..=DIRECTIONS.len() as u32 - 1) as usize];
+        style.flex_wrap = WRAPS[self.rng.range_u32(0..=WRAPS.len() as u32 - 1) as usize];
+        self.randomize_container_alignment(style);
+    }
+
+    /// Makes `style` a grid container with random explicit tracks and alignment
+    #[cfg(feature = "grid")]
+    fn randomize_grid_container(&mut self, style: &mut Style) {
+        style.display = Display::Grid;
+        let max_tracks = self.styles.max_grid_tracks;
+        let columns = self.rng.range_u32(1..=max_tracks.max(1));
+        style.grid_template_columns = (0..columns).map(|_| self.random_grid_track()).collect();
+        let rows = self.rng.range_u32(1..=max_tracks.max(1));
+        style.grid_template_rows = (0..rows).map(|_| self.random_grid_track()).collect();
+        self.randomize_container_alignment(style);
+    }
+
+    /// Generates a random track sizing function
+    #[cfg(feature = "grid")]
+    fn random_grid_track(&mut self) -> TrackSizingFunction {
+        match self.rng.range_u32(0..=6) {
+            0 => auto(),
+            1 => min_content(),
+            2 => max_content(),
+            3 => fr(1.0),
+            4 => minmax(length(0.0), fr(1.0)),
+            5 => percent(self.rng.next_f32() / 2.0),
+            _ => length(self.rng.range_f32(self.styles.lengths.clone()) / 5.0),
+        }
+    }
+
+    /// Generates a random grid placement, which is auto-placed most of the time
+    #[cfg(feature = "grid")]
+    fn random_grid_placement(&mut self) -> Line<GridPlacement> {
+        let max_line = self.styles.max_grid_tracks.max(1) as i16 + 1;
+        match self.rng.range_u32(0..=9) {
+            0 => Line { start: line(self.rng.range_u32(1..=max_line as u32) as i16), end: auto() },
+            1 => Line { start: auto(), end: span(self.rng.range_u32(1..=2) as u16) },
+            _ => Line { start: auto(), end: auto() },
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Counting of the heap allocations performed while computing a layout, enabled by the `alloc_audit` feature
+#[cfg(feature = "std")]
+use core::cell::Cell;
+
+use crate::tree::{LayoutTree, NodeId};
+
+/// The place at which a collection was allocated on the heap while computing a layout, see
+/// [`LayoutTree::record_allocation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllocationSite {
+    /// The list of the items of a flex container
+    FlexItems,
+    /// The list of the lines of a flex container
+    FlexLines,
+    /// The list of the items of a grid container
+    GridItems,
+    /// The lists of the rows and columns of a grid container
+    GridTracks,
+    /// The list of the items of a block container
+    BlockItems,
+    /// A list of nodes collected while traversing the tree, such as the stack used to visit the descendants of a node
+    Children,
+}
+
+/// The number of collections allocated on the heap while computing a layout, by [`AllocationSite`]
+///
+/// This is a breakdown of where the layout algorithms allocate, rather than an exact count of heap allocations (which
+/// is [`LayoutStats::heap_allocations`](crate::tree::LayoutStats::heap_allocations)). Each collection is counted once,
+/// when it is first allocated, even if it is reallocated as it grows. The lists of flex items that the tree hands out
+/// for reuse (see [`LayoutTree::take_flex_items`]) are only counted when they have to grow. The map of the cells
+/// occupied by the items of a grid isn't counted, and neither are the collections of optional features such as
+/// subtree memoization and measuring in parallel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationCounts {
+    /// The lists of the items of flex containers that were allocated
+    pub flex_items: usize,
+    /// The lists of the lines of flex containers that were allocated
+    pub flex_lines: usize,
+    /// The lists of the items of grid containers that were allocated
+    pub grid_items: usize,
+    /// The lists of the rows and columns of grid containers that were allocated
+    pub grid_tracks: usize,
+    /// The lists of the items of block containers that were allocated
+    pub block_items: usize,
+    /// The lists of nodes collected while traversing the tree that were allocated
+    pub children: usize,
+}
+
+impl AllocationCounts {
+    /// Returns the total number of collections allocated, across all sites
+    pub fn total(&self) -> usize {
+        self.flex_items + self.flex_lines + self.grid_items + self.grid_tracks + self.block_items + self.children
+    }
+
+    /// Returns the number of collections allocated at `site`
+    pub fn get(&self, site: AllocationSite) -> usize {
+        match site {
+            AllocationSite::FlexItems => self.flex_items,
+            AllocationSite::FlexLines => self.flex_lines,
+            AllocationSite::GridItems => self.grid_items,
+            AllocationSite::GridTracks => self.grid_tracks,
+            AllocationSite::BlockItems => self.block_items,
+            AllocationSite::Children => self.children,
+        }
+    }
+
+    /// Counts a collection allocated at `site`
+    pub fn record(&mut self, site: AllocationSite) {
+        match site {
+            AllocationSite::FlexItems => self.flex_items += 1,
+            AllocationSite::FlexLines => self.flex_lines += 1,
+            AllocationSite::GridItems => self.grid_items += 1,
+            AllocationSite::GridTracks => self.grid_tracks += 1,
+            AllocationSite::BlockItems => self.block_items += 1,
+            AllocationSite::Children => self.children += 1,
+        }
+    }
+
+    /// Counts a collection allocated at `site` if a collection with the given capacity is stored on the heap
+    #[cfg(feature = "taffy_tree")]
+    pub(crate) fn record_capacity(&mut self, site: AllocationSite, capacity: usize) {
+        if capacity > 0 {
+            self.record(site);
+        }
+    }
+}
+
+/// Reports the allocation of a collection with the given capacity at `site` by the layout of `node` to the tree, unless
+/// the collection is empty and so hasn't been allocated
+pub(crate) fn audit_allocation(tree: &mut impl LayoutTree, node: NodeId, site: AllocationSite, capacity: usize) {
+    if capacity > 0 {
+        tree.record_allocation(node, site);
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The number of heap allocations made by the thread since the innermost [`HeapAllocationScope`] was started, or
+    /// `None` if the thread isn't computing a layout
+    static HEAP_ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Counts a heap allocation towards the layout being computed on the current thread, if any
+///
+/// Taffy doesn't contain any unsafe code, so it can't provide a [`GlobalAlloc`](std::alloc::GlobalAlloc) itself.
+/// Instead, call this function from each allocation (and reallocation) of a wrapper around the global allocator, so
+/// that [`LayoutStats::heap_allocations`](crate::tree::LayoutStats::heap_allocations) reports the exact number of heap
+/// allocations made by [`Taffy::compute_layout`](crate::Taffy::compute_layout):
+///
+/// ```
+/// use std::alloc::{GlobalAlloc, Layout, System};
+///
+/// struct CountingAllocator;
+///
+/// unsafe impl GlobalAlloc for CountingAllocator {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         taffy::tree::record_heap_allocation();
+///         System.alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         System.dealloc(ptr, layout)
+///     }
+///
+///     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+///         taffy::tree::record_heap_allocation();
+///         System.realloc(ptr, layout, new_size)
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+///
+/// Only the allocations made by the thread that computes the layout are counted, so allocations made by other threads
+/// (such as those that measure nodes in parallel) aren't.
+#[cfg(feature = "std")]
+pub fn record_heap_allocation() {
+    // The thread's storage may already have been destroyed if the thread is exiting
+    let _ = HEAP_ALLOCATIONS.try_with(|count| {
+        if let Some(allocations) = count.get() {
+            count.set(Some(allocations + 1));
+        }
+    });
+}
+
+/// Counts the heap allocations reported by [`record_heap_allocation`] on the current thread until it is finished
+///
+/// Scopes can be nested, in which case the allocations counted by the inner scope are counted by the outer one as well.
+#[cfg(all(feature = "std", feature = "taffy_tree"))]
+pub(crate) struct HeapAllocationScope {
+    /// The count of the enclosing scope, if any, which is restored when the scope ends
+    outer: Option<usize>,
+}
+
+#[cfg(all(feature = "std", feature = "taffy_tree"))]
+impl HeapAllocationScope {
+    /// Starts counting the heap allocations of the current thread
+    pub(crate) fn start() -> Self {
+        Self { outer: HEAP_ALLOCATIONS.with(|count| count.replace(Some(0))) }
+    }
+
+    /// Stops counting, and returns the number of heap allocations made since the scope was started
+    pub(crate) fn finish(self) -> usize {
+        HEAP_ALLOCATIONS.with(Cell::get).unwrap_or(0)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "taffy_tree"))]
+impl Drop for HeapAllocationScope {
+    fn drop(&mut self) {
+        HEAP_ALLOCATIONS.with(|count| {
+            let inner = count.get().unwrap_or(0);
+            count.set(self.outer.map(|outer| outer + inner));
+        });
+    }
+}
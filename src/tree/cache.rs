@@ -4,7 +4,40 @@ use crate::style::AvailableSpace;
 use crate::tree::{RunMode, SizeBaselinesAndMargins};
 
 /// The number of cache entries for each node in the tree
-const CACHE_SIZE: usize = 7;
+const CACHE_SIZE: usize = 9;
+
+/// How much of a node's cached layout state should be discarded when the node is marked dirty
+///
+/// Ordered from the least to the most invasive level of invalidation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InvalidationLevel {
+    /// Only the positions of the node's children may have changed (for example due to a change of `justify_content`).
+    ///
+    /// Cached sizes of the node and its ancestors are retained, and only their cached full layouts are discarded.
+    Position,
+    /// Only the measured content size of the node may have changed (for example the text of a text node).
+    ///
+    /// Cached results that were computed with both dimensions known are retained as they do not depend on the content
+    /// size of the node. The cached results of all ancestors are discarded.
+    Measure,
+    /// The size of the node itself may have changed.
+    ///
+    /// All cached results of the node and its ancestors are discarded. The caches of its descendants are retained.
+    SelfSize,
+    /// All cached results of the node, its descendants and its ancestors are discarded
+    Subtree,
+}
+
+impl InvalidationLevel {
+    /// The level of invalidation that must be applied to the ancestors of a node invalidated at this level
+    #[inline]
+    pub const fn ancestor_level(self) -> Self {
+        match self {
+            Self::Position => Self::Position,
+            Self::Measure | Self::SelfSize | Self::Subtree => Self::SelfSize,
+        }
+    }
+}
 
 /// Cached intermediate layout results
 #[derive(Debug, Clone, Copy)]
@@ -20,12 +53,49 @@ pub struct CacheEntry {
     cached_size_and_baselines: SizeBaselinesAndMargins,
 }
 
+impl CacheEntry {
+    /// The known dimensions that the result was computed with
+    #[inline]
+    pub fn known_dimensions(&self) -> Size<Option<f32>> {
+        self.known_dimensions
+    }
+
+    /// The available space that the result was computed with
+    #[inline]
+    pub fn available_space(&self) -> Size<AvailableSpace> {
+        self.available_space
+    }
+
+    /// Whether the result is that of a full layout, or of only sizing the node
+    #[inline]
+    pub fn run_mode(&self) -> RunMode {
+        self.run_mode
+    }
+
+    /// The cached size, baselines and margins of the node
+    #[inline]
+    pub fn size_and_baselines(&self) -> SizeBaselinesAndMargins {
+        self.cached_size_and_baselines
+    }
+}
+
 /// A cache for caching the results of a sizing a Grid Item or Flexbox Item
+///
+/// Custom [`LayoutTree`](crate::tree::LayoutTree) implementations can store a `Cache` per node and use
+/// [`compute_cached_layout`](crate::compute::compute_cached_layout) to get the same caching behaviour as the
+/// built-in [`Taffy`](crate::Taffy) tree.
+#[derive(Debug, Clone)]
 pub struct Cache {
     /// An array of entries in the cache
     entries: [Option<CacheEntry>; CACHE_SIZE],
 }
 
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Cache {
     /// Create a new empty cache
     pub const fn new() -> Self {
@@ -53,8 +123,14 @@ impl Cache {
     /// - Slot 2: width but not height known_dimension was set and the other dimension was a MinContent constraint
     /// - Slot 3: height but not width known_dimension was set and the other dimension was either a MaxContent or Definite available space constraintable space constraint
     /// - Slot 4: height but not width known_dimension was set and the other dimension was a MinContent constraint
-    /// - Slot 5: Neither known_dimensions were set and we are sizing under a MaxContent or Definite available space constraint
-    /// - Slot 6: Neither known_dimensions were set and we are sizing under a MinContent constraint
+    /// - Slot 5: Neither known_dimensions were set and we are sizing under a MaxContent or Definite available space constraint in both axes
+    /// - Slot 6: Neither known_dimensions were set and we are sizing under a MinContent constraint in the width only
+    /// - Slot 7: Neither known_dimensions were set and we are sizing under a MinContent constraint in the height only
+    /// - Slot 8: Neither known_dimensions were set and we are sizing under a MinContent constraint in both axes
+    ///
+    /// The last four slots are distinguished by both axes because the items of a flex column are sized under a
+    /// MinContent height (to find their automatic minimum size) as well as under a MaxContent one (to find their flex
+    /// basis), and neither result should clobber the other.
     #[inline]
     fn compute_cache_slot(known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> usize {
         let has_known_width = known_dimensions.width.is_some();
@@ -77,9 +153,9 @@ impl Cache {
             return 3 + (available_space.width == AvailableSpace::MinContent) as usize;
         }
 
-        // Slot 5: Neither known_dimensions were set and we are sizing under a MaxContent or Definite available space constraint
-        // Slot 6: Neither known_dimensions were set and we are sizing under a MinContent constraint
+        // Slots 5-8: Neither known_dimensions were set, distinguished by which axes are sized under a MinContent constraint
         5 + (available_space.width == AvailableSpace::MinContent) as usize
+            + 2 * (available_space.height == AvailableSpace::MinContent) as usize
     }
 
     /// Try to retrieve a cached result from the cache
@@ -132,6 +208,39 @@ impl Cache {
         self.entries = [None; CACHE_SIZE];
     }
 
+    /// Discard the cache entries that are invalidated by the given [`InvalidationLevel`]
+    pub fn invalidate(&mut self, level: InvalidationLevel) {
+        match level {
+            InvalidationLevel::Position => self.retain(|entry| entry.run_mode == RunMode::ComputeSize),
+            InvalidationLevel::Measure => self.retain(|entry| entry.known_dimensions.both_axis_defined()),
+            InvalidationLevel::SelfSize | InvalidationLevel::Subtree => self.clear(),
+        }
+    }
+
+    /// Retain only the cache entries for which `predicate` returns true
+    fn retain(&mut self, predicate: impl Fn(&CacheEntry) -> bool) {
+        for entry in self.entries.iter_mut() {
+            if entry.as_ref().is_some_and(|entry| !predicate(entry)) {
+                *entry = None;
+            }
+        }
+    }
+
+    /// Returns true if the cache contains the result of a full layout (as opposed to only sizing results)
+    pub fn has_layout(&self) -> bool {
+        self.entries.iter().flatten().any(|entry| entry.run_mode == RunMode::PerformLayout)
+    }
+
+    /// Returns the entries of the cache, along with the index of the slot that each is stored in
+    ///
+    /// The slot of an entry depends on which of its known dimensions are set, and on whether it was computed under a
+    /// min-content constraint. A result stored in an occupied slot replaces the result already in it. An entry is
+    /// reused for a request whose known dimensions match, and whose available space is roughly equal (see
+    /// [`AvailableSpace::is_roughly_equal`]) in the axes without a known dimension.
+    pub fn entries(&self) -> impl Iterator<Item = (usize, &CacheEntry)> {
+        self.entries.iter().enumerate().filter_map(|(slot, entry)| entry.as_ref().map(|entry| (slot, entry)))
+    }
+
     /// Returns true if all cache entries are None, else false
     pub fn is_empty(&self) -> bool {
         !self.entries.iter().any(|entry| entry.is_some())
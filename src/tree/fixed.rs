@@ -0,0 +1,602 @@
+//! A node tree with a fixed capacity, stored inline without any heap allocation
+#[cfg(feature = "std")]
+use core::fmt::{Display as DisplayTrait, Formatter, Result as FmtResult};
+use core::ops::Range;
+
+use crate::compute::{compute_cached_layout, leaf, perform_computations, round_layout, HiddenAlgorithm};
+use crate::geometry::{Line, Point, Size};
+use crate::style::{AvailableSpace, Display, Style};
+use crate::tree::{
+    Cache, Layout, LayoutTree, Measurable, MeasureFunc, NodeId, RunMode, SizeBaselinesAndMargins, SizingMode,
+};
+
+#[cfg(feature = "block_layout")]
+use crate::compute::BlockAlgorithm;
+#[cfg(feature = "grid")]
+use crate::compute::CssGridAlgorithm;
+#[cfg(feature = "flexbox")]
+use crate::compute::FlexboxAlgorithm;
+
+/// The error [`TaffyFixed`] generates on invalid operations
+pub type FixedResult<T> = core::result::Result<T, FixedError>;
+
+/// An error that occurs while modifying or laying out a [`TaffyFixed`] tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedError {
+    /// Every one of the tree's slots already holds a node
+    CapacityExceeded {
+        /// The number of nodes that the tree can hold
+        capacity: usize,
+    },
+    /// The parent node was not found in the tree
+    InvalidParentNode(NodeId),
+    /// The child node was not found in the tree
+    InvalidChildNode(NodeId),
+    /// The supplied node was not found in the tree
+    InvalidInputNode(NodeId),
+    /// The child node is the parent node or one of its ancestors, so adding it would create a cycle
+    CyclicChild {
+        /// The node the child was being added to
+        parent: NodeId,
+        /// The child that was being added
+        child: NodeId,
+    },
+}
+
+#[cfg(feature = "std")]
+impl DisplayTrait for FixedError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            FixedError::CapacityExceeded { capacity } => write!(f, "The tree is full (capacity {capacity})"),
+            FixedError::InvalidParentNode(parent) => write!(f, "Parent Node {parent:?} is not in the tree"),
+            FixedError::InvalidChildNode(child) => write!(f, "Child Node {child:?} is not in the tree"),
+            FixedError::InvalidInputNode(node) => write!(f, "Supplied Node {node:?} is not in the tree"),
+            FixedError::CyclicChild { parent, child } => {
+                write!(f, "Child Node {child:?} is Node {parent:?} or one of its ancestors")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FixedError {}
+
+/// A node of a [`TaffyFixed`] tree, whose children are a contiguous range of the tree's list of children
+struct FixedNode {
+    /// The node's style
+    style: Style,
+    /// The node's computed layout
+    layout: Layout,
+    /// The cache of the node's sizes and layouts
+    cache: Cache,
+    /// The function that measures the node, if it is a leaf with content
+    measure: Option<MeasureFunc>,
+    /// The slot of the node's parent
+    parent: Option<usize>,
+    /// The index of the node's first child in the tree's list of children, if the node has any children
+    first_child: usize,
+    /// The number of children of the node
+    child_count: usize,
+}
+
+impl FixedNode {
+    /// Creates a detached node without children
+    fn new(style: Style, measure: Option<MeasureFunc>) -> Self {
+        Self {
+            style,
+            layout: Layout::new(),
+            cache: Cache::new(),
+            measure,
+            parent: None,
+            first_child: 0,
+            child_count: 0,
+        }
+    }
+}
+
+/// A node tree that can hold up to `N` nodes, which are stored inline rather than on the heap
+///
+/// This is an alternative to [`Taffy`](crate::Taffy) for targets without an allocator, such as the firmware of embedded
+/// displays with a known maximum number of widgets. Its nodes are laid out using the same algorithms, but it only
+/// supports their core features: nodes have a [`Style`] and, if they are leaves, an optional [`MeasureFunc`]. As custom
+/// layout algorithms can't be registered, nodes with [`Display::Custom`] and children are laid out as if they had
+/// [`Display::None`].
+///
+/// The tree itself never allocates. To lay it out without any heap allocation at all, build Taffy without the `std`
+/// and `alloc` features, so that the layout algorithms use fixed-capacity buffers as well. Those limit a layout to 256
+/// nodes, and each node to 16 children.
+///
+/// A node's id is the index of its slot, which is reused once the node has been removed. The children of all nodes are
+/// stored in a single list, in which the children of each node are contiguous, so that they can be looked up by index in
+/// constant time.
+///
+/// ```
+/// use taffy::prelude::*;
+/// use taffy::tree::TaffyFixed;
+///
+/// let mut tree = TaffyFixed::<4>::new();
+/// let child = tree.new_leaf(Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+/// let root = tree.new_with_children(Style::default(), &[child]).unwrap();
+/// tree.compute_layout(root, Size::MAX_CONTENT).unwrap();
+/// assert_eq!(tree.layout(root).unwrap().size, Size { width: 20.0, height: 10.0 });
+/// ```
+pub struct TaffyFixed<const N: usize> {
+    /// The slots of the tree, which hold its nodes
+    nodes: [Option<FixedNode>; N],
+    /// The slots of the children of every node, as one contiguous range per node. Each node has at most one parent,
+    /// so the list never holds more than `N` children.
+    children: [usize; N],
+    /// The number of entries of `children` that are in use
+    children_len: usize,
+    /// The number of nodes in the tree
+    len: usize,
+    /// Whether layouts are rounded to whole pixels, see [`TaffyFixed::enable_rounding`]
+    use_rounding: bool,
+}
+
+impl<const N: usize> Default for TaffyFixed<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TaffyFixed<N> {
+    /// Creates an empty tree
+    #[must_use]
+    pub fn new() -> Self {
+        Self { nodes: core::array::from_fn(|_| None), children: [0; N], children_len: 0, len: 0, use_rounding: true }
+    }
+
+    /// Returns the number of nodes that the tree can hold
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of nodes in the tree
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the tree has no nodes
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Enables rounding of layout values, which is enabled by default
+    pub fn enable_rounding(&mut self) {
+        self.use_rounding = true;
+    }
+
+    /// Disables rounding of layout values
+    pub fn disable_rounding(&mut self) {
+        self.use_rounding = false;
+    }
+
+    /// Creates and adds a new unattached leaf node to the tree, and returns its id
+    pub fn new_leaf(&mut self, style: Style) -> FixedResult<NodeId> {
+        self.insert(FixedNode::new(style, None))
+    }
+
+    /// Creates and adds a new unattached leaf node to the tree, which is measured with `measure`, and returns its id
+    pub fn new_leaf_with_measure(&mut self, style: Style, measure: MeasureFunc) -> FixedResult<NodeId> {
+        self.insert(FixedNode::new(style, Some(measure)))
+    }
+
+    /// Creates and adds a new node with the given children, and returns its id
+    ///
+    /// No node is created if one of the children is invalid, or if the tree is full.
+    pub fn new_with_children(&mut self, style: Style, children: &[NodeId]) -> FixedResult<NodeId> {
+        if let Some(child) = children.iter().find(|child| !self.contains(**child)) {
+            return Err(FixedError::InvalidChildNode(*child));
+        }
+        let parent = self.insert(FixedNode::new(style, None))?;
+        for child in children {
+            self.add_child(parent, *child)?;
+        }
+        Ok(parent)
+    }
+
+    /// Appends the child node to the children of the parent node, detaching it from its previous parent if it had one
+    pub fn add_child(&mut self, parent: NodeId, child: NodeId) -> FixedResult<()> {
+        if !self.contains(parent) {
+            return Err(FixedError::InvalidParentNode(parent));
+        }
+        if !self.contains(child) {
+            return Err(FixedError::InvalidChildNode(child));
+        }
+        let mut ancestor = Some(parent.into());
+        while let Some(slot) = ancestor {
+            if slot == usize::from(child) {
+                return Err(FixedError::CyclicChild { parent, child });
+            }
+            ancestor = self.slot(slot).parent;
+        }
+
+        self.detach(child.into());
+        let (parent_slot, child_slot) = (parent.into(), child.into());
+        let index = self.grow_children(parent_slot);
+        self.children[index] = child_slot;
+        self.slot_mut(child_slot).parent = Some(parent_slot);
+        self.mark_dirty_slot(parent_slot);
+        Ok(())
+    }
+
+    /// Removes the child node from the children of its parent, leaving it in the tree as an unattached node
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> FixedResult<()> {
+        if !self.contains(parent) {
+            return Err(FixedError::InvalidParentNode(parent));
+        }
+        if !self.contains(child) || self.slot(child.into()).parent != Some(parent.into()) {
+            return Err(FixedError::InvalidChildNode(child));
+        }
+        self.detach(child.into());
+        Ok(())
+    }
+
+    /// Removes the node from the tree, freeing its slot. Its children are left in the tree as unattached nodes.
+    ///
+    /// The node's id may be reused by the next node to be created.
+    pub fn remove(&mut self, node: NodeId) -> FixedResult<()> {
+        if !self.contains(node) {
+            return Err(FixedError::InvalidInputNode(node));
+        }
+        self.detach(node.into());
+        let children = self.child_range(node.into());
+        for index in children.clone() {
+            self.slot_mut(self.children[index]).parent = None;
+        }
+        self.remove_children(children);
+        self.nodes[usize::from(node)] = None;
+        self.len -= 1;
+        Ok(())
+    }
+
+    /// Sets the style of the node, and marks it dirty
+    pub fn set_style(&mut self, node: NodeId, style: Style) -> FixedResult<()> {
+        if !self.contains(node) {
+            return Err(FixedError::InvalidInputNode(node));
+        }
+        self.slot_mut(node.into()).style = style;
+        self.mark_dirty_slot(node.into());
+        Ok(())
+    }
+
+    /// Sets the function that measures the node, and marks it dirty
+    pub fn set_measure(&mut self, node: NodeId, measure: Option<MeasureFunc>) -> FixedResult<()> {
+        if !self.contains(node) {
+            return Err(FixedError::InvalidInputNode(node));
+        }
+        self.slot_mut(node.into()).measure = measure;
+        self.mark_dirty_slot(node.into());
+        Ok(())
+    }
+
+    /// Marks the node and its ancestors dirty, so that they are laid out again by the next call to
+    /// [`TaffyFixed::compute_layout`]
+    pub fn mark_dirty(&mut self, node: NodeId) -> FixedResult<()> {
+        if !self.contains(node) {
+            return Err(FixedError::InvalidInputNode(node));
+        }
+        self.mark_dirty_slot(node.into());
+        Ok(())
+    }
+
+    /// Returns the style of the node
+    pub fn style(&self, node: NodeId) -> FixedResult<&Style> {
+        self.get(node).map(|node| &node.style)
+    }
+
+    /// Returns the layout of the node, as computed by the last call to [`TaffyFixed::compute_layout`]
+    pub fn layout(&self, node: NodeId) -> FixedResult<&Layout> {
+        self.get(node).map(|node| &node.layout)
+    }
+
+    /// Returns the parent of the node, if it has one
+    pub fn parent(&self, node: NodeId) -> FixedResult<Option<NodeId>> {
+        self.get(node).map(|node| node.parent.map(NodeId::from))
+    }
+
+    /// Returns an iterator over the children of the node
+    pub fn children(&self, node: NodeId) -> FixedResult<FixedChildIter<'_>> {
+        self.get(node)?;
+        Ok(FixedChildIter(self.children[self.child_range(node.into())].iter()))
+    }
+
+    /// Returns the number of children of the node
+    pub fn child_count(&self, node: NodeId) -> FixedResult<usize> {
+        self.get(node).map(|node| node.child_count)
+    }
+
+    /// Computes the layout of the node and its descendants, within the given available space
+    pub fn compute_layout(&mut self, root: NodeId, available_space: Size<AvailableSpace>) -> FixedResult<()> {
+        if !self.contains(root) {
+            return Err(FixedError::InvalidInputNode(root));
+        }
+        let size_and_baselines = self.compute_node_layout(
+            root,
+            Size::NONE,
+            available_space.into_options(),
+            available_space,
+            RunMode::PerformLayout,
+            SizingMode::InherentSize,
+            Line::FALSE,
+        );
+        self.slot_mut(root.into()).layout = Layout { order: 0, size: size_and_baselines.size, location: Point::ZERO };
+        if self.use_rounding {
+            round_layout(self, root, 1.0);
+        }
+        Ok(())
+    }
+
+    /// Returns whether the node is in the tree
+    fn contains(&self, node: NodeId) -> bool {
+        self.nodes.get(usize::from(node)).is_some_and(Option::is_some)
+    }
+
+    /// Returns the node, if it is in the tree
+    fn get(&self, node: NodeId) -> FixedResult<&FixedNode> {
+        self.nodes.get(usize::from(node)).and_then(Option::as_ref).ok_or(FixedError::InvalidInputNode(node))
+    }
+
+    /// Returns the node in the occupied slot
+    fn slot(&self, slot: usize) -> &FixedNode {
+        self.nodes[slot].as_ref().expect("slot is occupied")
+    }
+
+    /// Returns the node in the occupied slot mutably
+    fn slot_mut(&mut self, slot: usize) -> &mut FixedNode {
+        self.nodes[slot].as_mut().expect("slot is occupied")
+    }
+
+    /// Stores the node in the first free slot
+    fn insert(&mut self, node: FixedNode) -> FixedResult<NodeId> {
+        let slot = self.nodes.iter().position(Option::is_none).ok_or(FixedError::CapacityExceeded { capacity: N })?;
+        self.nodes[slot] = Some(node);
+        self.len += 1;
+        Ok(NodeId::from(slot))
+    }
+
+    /// Returns the range of the list of children that holds the children of the node in the slot
+    fn child_range(&self, slot: usize) -> Range<usize> {
+        let node = self.slot(slot);
+        node.first_child..node.first_child + node.child_count
+    }
+
+    /// Makes room for one more child at the end of the children of the node in the slot, and returns its index in the
+    /// list of children
+    fn grow_children(&mut self, slot: usize) -> usize {
+        let node = self.slot(slot);
+        let end = if node.child_count == 0 { self.children_len } else { node.first_child + node.child_count };
+
+        // The children of the nodes that follow the range are moved back by one entry
+        self.children.copy_within(end..self.children_len, end + 1);
+        self.children_len += 1;
+        for other in self.nodes.iter_mut().flatten() {
+            if other.child_count > 0 && other.first_child >= end {
+                other.first_child += 1;
+            }
+        }
+
+        let node = self.slot_mut(slot);
+        if node.child_count == 0 {
+            node.first_child = end;
+        }
+        node.child_count += 1;
+        end
+    }
+
+    /// Removes the entries in the range from the list of children, which must all belong to the same node
+    fn remove_children(&mut self, range: Range<usize>) {
+        let removed = range.len();
+        self.children.copy_within(range.end..self.children_len, range.start);
+        self.children_len -= removed;
+        for node in self.nodes.iter_mut().flatten() {
+            if node.child_count > 0 && node.first_child >= range.end {
+                node.first_child -= removed;
+            }
+        }
+    }
+
+    /// Unlinks the node in the slot from its parent, if it has one
+    fn detach(&mut self, slot: usize) {
+        let Some(parent) = self.slot_mut(slot).parent.take() else { return };
+        let children = self.child_range(parent);
+        let offset = self.children[children.clone()]
+            .iter()
+            .position(|child| *child == slot)
+            .expect("child is listed by its parent");
+        let index = children.start + offset;
+
+        self.remove_children(index..index + 1);
+        self.slot_mut(parent).child_count -= 1;
+        self.mark_dirty_slot(parent);
+    }
+
+    /// Clears the caches of the node in the slot and its ancestors
+    fn mark_dirty_slot(&mut self, slot: usize) {
+        let mut current = Some(slot);
+        while let Some(slot) = current {
+            let node = self.slot_mut(slot);
+            node.cache.clear();
+            current = node.parent;
+        }
+    }
+
+    /// Computes the layout or size of the node, reusing a cached result if there is one
+    #[allow(clippy::too_many_arguments)]
+    fn compute_node_layout(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        run_mode: RunMode,
+        sizing_mode: SizingMode,
+        vertical_margins_are_collapsible: Line<bool>,
+    ) -> SizeBaselinesAndMargins {
+        compute_cached_layout(
+            self,
+            node,
+            known_dimensions,
+            available_space,
+            run_mode,
+            |tree, node| &mut tree.slot_mut(node.into()).cache,
+            |tree| {
+                let has_children = tree.slot(node.into()).child_count > 0;
+                match (tree.slot(node.into()).style.display, has_children) {
+                    (Display::None, _) => perform_computations::<HiddenAlgorithm>(
+                        tree,
+                        node,
+                        known_dimensions,
+                        parent_size,
+                        available_space,
+                        run_mode,
+                        sizing_mode,
+                        vertical_margins_are_collapsible,
+                    ),
+                    #[cfg(feature = "block_layout")]
+                    (Display::Block, true) => perform_computations::<BlockAlgorithm>(
+                        tree,
+                        node,
+                        known_dimensions,
+                        parent_size,
+                        available_space,
+                        run_mode,
+                        sizing_mode,
+                        vertical_margins_are_collapsible,
+                    ),
+                    #[cfg(feature = "flexbox")]
+                    (Display::Flex, true) => perform_computations::<FlexboxAlgorithm>(
+                        tree,
+                        node,
+                        known_dimensions,
+                        parent_size,
+                        available_space,
+                        run_mode,
+                        sizing_mode,
+                        vertical_margins_are_collapsible,
+                    ),
+                    #[cfg(feature = "grid")]
+                    (Display::Grid, true) => perform_computations::<CssGridAlgorithm>(
+                        tree,
+                        node,
+                        known_dimensions,
+                        parent_size,
+                        available_space,
+                        run_mode,
+                        sizing_mode,
+                        vertical_margins_are_collapsible,
+                    ),
+                    (_, false) => {
+                        let node_data = tree.slot(node.into());
+                        let style = &node_data.style;
+                        let measure = node_data.measure.as_ref().map(|measure| {
+                            |known_dimensions, available_space| {
+                                measure.measure_node(node, known_dimensions, available_space, style)
+                            }
+                        });
+                        leaf::compute_with_measure(
+                            style,
+                            measure,
+                            known_dimensions,
+                            parent_size,
+                            available_space,
+                            sizing_mode,
+                        )
+                    }
+                    // There are no registered algorithms to lay out custom nodes with
+                    (_, true) => perform_computations::<HiddenAlgorithm>(
+                        tree,
+                        node,
+                        known_dimensions,
+                        parent_size,
+                        available_space,
+                        run_mode,
+                        sizing_mode,
+                        vertical_margins_are_collapsible,
+                    ),
+                }
+            },
+        )
+    }
+}
+
+/// An iterator over the children of a node of a [`TaffyFixed`] tree
+pub struct FixedChildIter<'a>(core::slice::Iter<'a, usize>);
+
+impl Iterator for FixedChildIter<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.0.next().map(|slot| NodeId::from(*slot))
+    }
+}
+
+impl<const N: usize> LayoutTree for TaffyFixed<N> {
+    type ChildIter<'a> = FixedChildIter<'a>;
+
+    fn children(&self, node: NodeId) -> Self::ChildIter<'_> {
+        FixedChildIter(self.children[self.child_range(node.into())].iter())
+    }
+
+    fn child_count(&self, node: NodeId) -> usize {
+        self.slot(node.into()).child_count
+    }
+
+    fn child(&self, node: NodeId, index: usize) -> NodeId {
+        NodeId::from(self.children[self.child_range(node.into())][index])
+    }
+
+    fn style(&self, node: NodeId) -> &Style {
+        &self.slot(node.into()).style
+    }
+
+    fn layout(&self, node: NodeId) -> &Layout {
+        &self.slot(node.into()).layout
+    }
+
+    fn layout_mut(&mut self, node: NodeId) -> &mut Layout {
+        &mut self.slot_mut(node.into()).layout
+    }
+
+    fn measure_child_size(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        sizing_mode: SizingMode,
+        vertical_margins_are_collapsible: Line<bool>,
+    ) -> Size<f32> {
+        self.compute_node_layout(
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            RunMode::ComputeSize,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+        .size
+    }
+
+    fn perform_child_layout(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        sizing_mode: SizingMode,
+        vertical_margins_are_collapsible: Line<bool>,
+    ) -> SizeBaselinesAndMargins {
+        self.compute_node_layout(
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            RunMode::PerformLayout,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+    }
+}
@@ -1,7 +1,15 @@
 //! Final data structures that represent the high-level UI layout
 
+#[cfg(feature = "grid")]
+use crate::style::GridTrackRepetition;
+#[cfg(feature = "taffy_tree")]
+use crate::util::sys::round;
+#[cfg(feature = "grid")]
+use crate::util::sys::Vec;
 use crate::{
-    geometry::{Point, Size},
+    geometry::{Line, Point, Rect, Size},
+    style::AvailableSpace,
+    tree::NodeId,
     util::sys::{f32_max, f32_min},
 };
 
@@ -127,8 +135,29 @@ impl From<Size<f32>> for SizeBaselinesAndMargins {
     }
 }
 
+/// The constraints that a child node is measured under, for measuring several children in a single batch
+///
+/// See [`LayoutTree::measure_child_sizes`](crate::tree::LayoutTree::measure_child_sizes). The fields correspond to the
+/// arguments of [`LayoutTree::measure_child_size`](crate::tree::LayoutTree::measure_child_size).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MeasureRequest {
+    /// The child node to measure
+    pub node: NodeId,
+    /// The sizes of the child that are already known
+    pub known_dimensions: Size<Option<f32>>,
+    /// The size of the parent that percentages are resolved against
+    pub parent_size: Size<Option<f32>>,
+    /// The space available to the child
+    pub available_space: Size<AvailableSpace>,
+    /// Whether the child's size styles should be taken into account
+    pub sizing_mode: SizingMode,
+    /// Whether the child's vertical margins can collapse with those of its parent
+    pub vertical_margins_are_collapsible: Line<bool>,
+}
+
 /// The final result of a layout algorithm for a single node.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Layout {
     /// The relative ordering of the node
     ///
@@ -137,7 +166,10 @@ pub struct Layout {
     pub order: u32,
     /// The width and height of the node
     pub size: Size<f32>,
-    /// The top-left corner of the node
+    /// The position of the node relative to its parent
+    ///
+    /// By default this is the offset of the node's top-left corner from its parent's top-left corner, with y
+    /// increasing downwards. See [`CoordinateOrigin`] for the other supported conventions.
     pub location: Point<f32>,
 }
 
@@ -160,4 +192,237 @@ impl Layout {
     pub const fn with_order(order: u32) -> Self {
         Self { order, size: Size::zero(), location: Point::ZERO }
     }
+
+    /// The x coordinate of the right edge of the node, in the coordinate space of its parent
+    ///
+    /// This and the other edge and bounds accessors assume [`CoordinateOrigin::TopLeft`] locations.
+    #[must_use]
+    pub fn right(&self) -> f32 {
+        self.location.x + self.size.width
+    }
+
+    /// The y coordinate of the bottom edge of the node, in the coordinate space of its parent
+    #[must_use]
+    pub fn bottom(&self) -> f32 {
+        self.location.y + self.size.height
+    }
+
+    /// The center of the node, in the coordinate space of its parent
+    #[must_use]
+    pub fn center(&self) -> Point<f32> {
+        Point { x: self.location.x + self.size.width / 2.0, y: self.location.y + self.size.height / 2.0 }
+    }
+
+    /// The edges of the node's border box, in the coordinate space of its parent
+    #[must_use]
+    pub fn rect(&self) -> Rect<f32> {
+        Rect { left: self.location.x, right: self.right(), top: self.location.y, bottom: self.bottom() }
+    }
+
+    /// Whether `point`, in the coordinate space of the node's parent, lies within the node's border box
+    ///
+    /// The left and top edges are inside the node while the right and bottom edges are not, so that a point on the
+    /// boundary between two adjacent nodes is only contained by one of them.
+    #[must_use]
+    pub fn contains(&self, point: Point<f32>) -> bool {
+        point.x >= self.location.x && point.x < self.right() && point.y >= self.location.y && point.y < self.bottom()
+    }
+}
+
+/// The coordinate convention used for [`Layout::location`]
+///
+/// Set with [`Taffy::set_coordinate_origin`](crate::Taffy::set_coordinate_origin). The layout algorithms always work
+/// with [`CoordinateOrigin::TopLeft`], and locations are converted once the layout has been computed. The root node
+/// of a layout computation is always located at the origin.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CoordinateOrigin {
+    /// The offset of the node's top-left corner from its parent's top-left corner, with y increasing downwards
+    #[default]
+    TopLeft,
+    /// The offset of the node's bottom-left corner from its parent's bottom-left corner, with y increasing upwards
+    BottomLeft,
+    /// The offset of the node's center from its parent's center, with y increasing upwards
+    Center,
+}
+
+/// Snaps computed layouts to a grid of character cells, so that every size and position is a whole number of cells
+///
+/// Set with [`Taffy::set_cell_mode`](crate::Taffy::set_cell_mode), which replaces the usual rounding of layout
+/// values. Both edges of each node are snapped to a cell boundary based on their unrounded position relative to the
+/// root, rather than relative to their parent, so rounding errors don't accumulate across nested nodes and siblings
+/// that touch before snapping still touch afterwards.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellMode {
+    /// The size of a single cell
+    pub cell_size: Size<f32>,
+    /// Which nodes receive the cells left over when a length isn't a whole number of cells
+    pub remainder: CellRemainder,
+}
+
+impl CellMode {
+    /// 1x1 cells, for layouts measured in terminal character cells, with leftover cells distributed by rounding
+    pub const TERMINAL: CellMode =
+        CellMode { cell_size: Size { width: 1.0, height: 1.0 }, remainder: CellRemainder::Nearest };
+
+    /// Cells of the given size, with leftover cells distributed by rounding
+    pub fn new(cell_size: Size<f32>) -> Self {
+        Self { cell_size, remainder: CellRemainder::Nearest }
+    }
+
+    /// Sets which nodes receive the cells left over when a length isn't a whole number of cells
+    #[must_use]
+    pub fn with_remainder(self, remainder: CellRemainder) -> Self {
+        Self { remainder, ..self }
+    }
+}
+
+/// Which nodes of a [`CellMode`] layout receive the cells left over when lengths aren't whole numbers of cells
+///
+/// For example, three flexible items sharing 10 cells equally are each 3⅓ cells wide, and one of them has to be given
+/// the 10th cell.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CellRemainder {
+    /// Each edge is snapped to the nearest cell boundary, so the leftover cells are spread out (3, 4 and 3 cells)
+    #[default]
+    Nearest,
+    /// Each edge is snapped to the next cell boundary, so the first nodes receive the leftover cells (4, 3 and 3 cells)
+    Start,
+    /// Each edge is snapped to the previous cell boundary, so the last nodes receive the leftover cells (3, 3 and 4 cells)
+    End,
+}
+
+#[cfg(feature = "taffy_tree")]
+impl CellMode {
+    /// Snaps a position along an axis, in points, to a boundary between cells of the given size
+    pub(crate) fn snap(self, position: f32, cell_size: f32) -> f32 {
+        // Positions within this fraction of a cell of a boundary are considered to be on it, so that floating point
+        // errors don't push exact positions into the next or previous cell
+        const TOLERANCE: f32 = 0.001;
+        if cell_size <= 0.0 {
+            return position;
+        }
+        let cells = position / cell_size;
+        let snapped = match self.remainder {
+            CellRemainder::Nearest => round(cells),
+            CellRemainder::Start => -floor(TOLERANCE - cells),
+            CellRemainder::End => floor(cells + TOLERANCE),
+        };
+        snapped * cell_size
+    }
+}
+
+/// Rounds `value` down to a whole number, without relying on `std`
+#[cfg(feature = "taffy_tree")]
+fn floor(value: f32) -> f32 {
+    let truncated = value as i64 as f32;
+    if truncated > value {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+#[cfg(feature = "taffy_tree")]
+impl CoordinateOrigin {
+    /// Converts a `location` using this convention into the [`CoordinateOrigin::TopLeft`] convention
+    pub(crate) fn location_to_top_left(
+        self,
+        location: Point<f32>,
+        size: Size<f32>,
+        parent_size: Size<f32>,
+    ) -> Point<f32> {
+        match self {
+            Self::TopLeft => location,
+            Self::BottomLeft => Point { x: location.x, y: parent_size.height - location.y - size.height },
+            Self::Center => Point {
+                x: location.x + (parent_size.width - size.width) / 2.0,
+                y: (parent_size.height - size.height) / 2.0 - location.y,
+            },
+        }
+    }
+
+    /// Converts a `location` using the [`CoordinateOrigin::TopLeft`] convention into this convention
+    pub(crate) fn top_left_to_location(
+        self,
+        location: Point<f32>,
+        size: Size<f32>,
+        parent_size: Size<f32>,
+    ) -> Point<f32> {
+        match self {
+            Self::TopLeft => location,
+            Self::BottomLeft => Point { x: location.x, y: parent_size.height - location.y - size.height },
+            Self::Center => Point {
+                x: location.x - (parent_size.width - size.width) / 2.0,
+                y: (parent_size.height - size.height) / 2.0 - location.y,
+            },
+        }
+    }
+}
+
+/// The constraint that determined the size of a node in one axis
+///
+/// Recorded for the node chosen with [`Taffy::explain_node`](crate::Taffy::explain_node) when its layout is computed,
+/// to answer why a node ended up with the size it has.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SizeConstraint {
+    /// The node's `size` style
+    StyleSize,
+    /// The node's `min_size` style, or for flex items their automatic minimum size
+    MinSize,
+    /// The node's `max_size` style
+    MaxSize,
+    /// The node's `aspect_ratio` applied to its size in the other axis
+    AspectRatio,
+    /// The size returned by the node's measure function
+    Measured,
+    /// The size of the node's children, as laid out by the node's own layout algorithm
+    Content,
+    /// The flex item's `flex_basis` style
+    FlexBasis,
+    /// The flex item grew to fill the free space of its flex line
+    FlexGrow,
+    /// The flex item shrank to fit within its flex line
+    FlexShrink,
+    /// The node was stretched to fill its flex line or the width of its containing block
+    Stretch,
+    /// The grid item was sized to fill the grid area formed by the tracks that it spans
+    GridArea,
+    /// The size was imposed by the parent's layout algorithm for some other reason
+    Parent,
+}
+
+/// The tracks produced by the `repeat(auto-fill, ...)` or `repeat(auto-fit, ...)` definition of a grid in one axis
+///
+/// Recorded for each grid with such a definition when its layout is computed, see
+/// [`Taffy::auto_repetitions`](crate::Taffy::auto_repetitions).
+#[cfg(feature = "grid")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoRepetitions {
+    /// Whether the tracks were repeated with `auto-fill` or `auto-fit`
+    pub kind: GridTrackRepetition,
+    /// The number of times the repeated track list was repeated
+    pub repetitions: u16,
+    /// The index of the first repeated track among the tracks of the explicit grid, starting from zero
+    pub first_track: u16,
+    /// Whether each repeated track was collapsed, in order. `auto-fit` tracks that contain no items are collapsed
+    /// to zero size, along with their gutters.
+    pub collapsed: Vec<bool>,
+}
+
+#[cfg(feature = "grid")]
+impl AutoRepetitions {
+    /// The number of repeated tracks
+    #[must_use]
+    pub fn track_count(&self) -> usize {
+        self.collapsed.len()
+    }
+
+    /// The number of repeated tracks that weren't collapsed
+    #[must_use]
+    pub fn visible_track_count(&self) -> usize {
+        self.collapsed.iter().filter(|collapsed| !**collapsed).count()
+    }
 }
@@ -0,0 +1,102 @@
+//! Caches of measured content sizes that can be shared between trees
+
+use crate::geometry::Size;
+use crate::style::AvailableSpace;
+
+/// A cache of measured content sizes, consulted before a node's measure function is called
+///
+/// Entries are keyed by a hash of the node's content (see [`Taffy::set_content_hash`](crate::Taffy::set_content_hash))
+/// along with the constraints it was measured under, so a single cache can be shared by several trees (e.g. one per
+/// window) that display the same content. Implementations must use interior mutability, as the cache is shared.
+pub trait MeasureCache: Send + Sync {
+    /// Returns the size of the content with the given hash measured under the given constraints, if it has been cached
+    fn get(
+        &self,
+        content_hash: u64,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Option<Size<f32>>;
+
+    /// Stores the size of the content with the given hash measured under the given constraints
+    fn insert(
+        &self,
+        content_hash: u64,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        size: Size<f32>,
+    );
+}
+
+#[cfg(feature = "std")]
+pub use self::shared::SharedMeasureCache;
+
+#[cfg(feature = "std")]
+mod shared {
+    //! A thread-safe [`MeasureCache`] implementation
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::MeasureCache;
+    use crate::geometry::Size;
+    use crate::style::AvailableSpace;
+
+    /// The sizes measured for a single piece of content, along with the constraints they were measured under
+    type Entries = Vec<(Size<Option<f32>>, Size<AvailableSpace>, Size<f32>)>;
+
+    /// A simple thread-safe [`MeasureCache`], which stores every size that is inserted until it is cleared
+    #[derive(Debug, Default)]
+    pub struct SharedMeasureCache {
+        /// The measured sizes of each piece of content, keyed by content hash
+        entries: Mutex<HashMap<u64, Entries>>,
+    }
+
+    impl SharedMeasureCache {
+        /// Creates a new empty cache
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Discards all cached sizes
+        pub fn clear(&self) {
+            self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+        }
+
+        /// Returns the number of cached sizes
+        pub fn len(&self) -> usize {
+            self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).values().map(Vec::len).sum()
+        }
+
+        /// Returns true if the cache contains no sizes
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+    }
+
+    impl MeasureCache for SharedMeasureCache {
+        fn get(
+            &self,
+            content_hash: u64,
+            known_dimensions: Size<Option<f32>>,
+            available_space: Size<AvailableSpace>,
+        ) -> Option<Size<f32>> {
+            let entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            entries.get(&content_hash)?.iter().find_map(|(known, available, size)| {
+                (*known == known_dimensions && *available == available_space).then_some(*size)
+            })
+        }
+
+        fn insert(
+            &self,
+            content_hash: u64,
+            known_dimensions: Size<Option<f32>>,
+            available_space: Size<AvailableSpace>,
+            size: Size<f32>,
+        ) {
+            let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let entries = entries.entry(content_hash).or_default();
+            entries.retain(|(known, available, _)| *known != known_dimensions || *available != available_space);
+            entries.push((known_dimensions, available_space, size));
+        }
+    }
+}
@@ -1,16 +1,61 @@
 //! Measure function type and trait definitions
 
 use crate::geometry::Size;
-use crate::style::AvailableSpace;
+use crate::style::{AvailableSpace, Style};
+use crate::tree::NodeId;
 #[cfg(any(feature = "std", feature = "alloc"))]
-use crate::util::sys::Box;
+use crate::util::sys::{Arc, Box};
 
 /// A function type that can be used in a [`MeasureFunc`]
 ///
 /// This trait is automatically implemented for all types (including closures) that define a function with the appropriate type signature.
+///
+/// # Which method to implement
+///
+/// Only [`Measurable::measure`] is required. The other two methods pass more context about the node being measured,
+/// and each defaults to the method before it with that context dropped. Implement the one that is passed the context
+/// the measurer needs, and leave the others to their defaults:
+///
+///   - [`Measurable::measure`] measures the node from its constraints alone.
+///   - [`Measurable::measure_with_style`] is also passed the [`Style`] of the node, for hints such as its overflow
+///     (e.g. to decide whether text should wrap) or its aspect ratio.
+///   - [`Measurable::measure_node`] is also passed the id of the node, so that a measurer shared between many nodes
+///     (see [`MeasureFunc::Shared`]) can look up the content of the node being measured.
+///
+/// Layout calls the most specific method that it has the context for: trees that know the id of the node being
+/// measured, such as [`Taffy`](crate::Taffy), call `measure_node`, while [`compute`](crate::compute::compute), which
+/// lays out a single leaf without a tree, calls `measure_with_style`. This is why the methods aren't merged into
+/// `measure_node`: leaves can be measured without a node id, and measurers that only implement `measure` keep working.
 pub trait Measurable: Send + Sync {
     /// Measure node
     fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32>;
+
+    /// Measure node, given the [`Style`] that it is laid out with
+    ///
+    /// Defaults to [`Measurable::measure`]. Called when a leaf is laid out without a tree, and by the default
+    /// implementation of [`Measurable::measure_node`].
+    fn measure_with_style(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        _style: &Style,
+    ) -> Size<f32> {
+        self.measure(known_dimensions, available_space)
+    }
+
+    /// Measure the given node, given the [`Style`] that it is laid out with
+    ///
+    /// Defaults to [`Measurable::measure_with_style`]. Called when laying out a tree, such as a [`Taffy`](crate::Taffy)
+    /// tree.
+    fn measure_node(
+        &self,
+        _node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        style: &Style,
+    ) -> Size<f32> {
+        self.measure_with_style(known_dimensions, available_space, style)
+    }
 }
 
 /// A function that can be used to compute the intrinsic size of a node
@@ -21,6 +66,13 @@ pub enum MeasureFunc {
     /// Stores a boxed function
     #[cfg(any(feature = "std", feature = "alloc"))]
     Boxed(Box<dyn Measurable>),
+
+    /// Stores a reference to a function that is shared between nodes
+    ///
+    /// Sharing a single measurer avoids allocating a function per node. The measurer is passed the id of the node being
+    /// measured by [`Measurable::measure_node`], which it can use to look up the node's content.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    Shared(Arc<dyn Measurable>),
 }
 
 impl Measurable for MeasureFunc {
@@ -31,6 +83,45 @@ impl Measurable for MeasureFunc {
             Self::Raw(measure) => measure(known_dimensions, available_space),
             #[cfg(any(feature = "std", feature = "alloc"))]
             Self::Boxed(measurable) => measurable.measure(known_dimensions, available_space),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Shared(measurable) => measurable.measure(known_dimensions, available_space),
+        }
+    }
+
+    /// Call the measure function to measure to the node, passing it the node's style
+    #[inline(always)]
+    #[cfg_attr(not(any(feature = "std", feature = "alloc")), allow(unused_variables))]
+    fn measure_with_style(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        style: &Style,
+    ) -> Size<f32> {
+        match self {
+            Self::Raw(measure) => measure(known_dimensions, available_space),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Boxed(measurable) => measurable.measure_with_style(known_dimensions, available_space, style),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Shared(measurable) => measurable.measure_with_style(known_dimensions, available_space, style),
+        }
+    }
+
+    /// Call the measure function to measure the node, passing it the node's id and style
+    #[inline(always)]
+    #[cfg_attr(not(any(feature = "std", feature = "alloc")), allow(unused_variables))]
+    fn measure_node(
+        &self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        style: &Style,
+    ) -> Size<f32> {
+        match self {
+            Self::Raw(measure) => measure(known_dimensions, available_space),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Boxed(measurable) => measurable.measure_node(node, known_dimensions, available_space, style),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Shared(measurable) => measurable.measure_node(node, known_dimensions, available_space, style),
         }
     }
 }
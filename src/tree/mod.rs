@@ -1,23 +1,76 @@
 //! Contains both [a high-level interface to Taffy](crate::Taffy) using a ready-made node tree, and [a trait for defining a custom node trees](crate::tree::LayoutTree) / utility types to help with that.
 
-use crate::geometry::{Line, Size};
+#[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+use crate::compute::FlexItemBuffer;
+use crate::geometry::{AbsoluteAxis, Line, Size};
 use crate::style::{AvailableSpace, Style};
+#[cfg(feature = "debug")]
+use crate::util::DebugEvent;
 
 // Submodules
+#[cfg(feature = "alloc_audit")]
+mod alloc_audit;
+#[cfg(feature = "alloc_audit")]
+pub(crate) use alloc_audit::audit_allocation;
+#[cfg(all(feature = "alloc_audit", feature = "std"))]
+pub use alloc_audit::record_heap_allocation;
+#[cfg(all(feature = "alloc_audit", feature = "std", feature = "taffy_tree"))]
+pub(crate) use alloc_audit::HeapAllocationScope;
+#[cfg(feature = "alloc_audit")]
+pub use alloc_audit::{AllocationCounts, AllocationSite};
 mod cache;
-pub use cache::{Cache, CacheEntry};
+pub use cache::{Cache, CacheEntry, InvalidationLevel};
+mod fixed;
+pub use fixed::{FixedChildIter, FixedError, FixedResult, TaffyFixed};
+mod measure_cache;
+pub use measure_cache::MeasureCache;
+#[cfg(feature = "std")]
+pub use measure_cache::SharedMeasureCache;
 mod measure_func;
 pub use measure_func::{Measurable, MeasureFunc};
+#[cfg(feature = "monospace_text")]
+mod monospace_text;
+#[cfg(feature = "monospace_text")]
+pub use monospace_text::MonospaceText;
 mod node;
+mod replaced;
 #[cfg(feature = "taffy_tree")]
 use node::NodeData;
 pub use node::NodeId;
+pub use replaced::ReplacedContent;
 #[cfg(feature = "taffy_tree")]
 mod taffy_tree;
+#[cfg(feature = "browser_harness")]
+pub use taffy_tree::browser_harness;
+#[cfg(feature = "markup")]
+pub use taffy_tree::markup;
+#[cfg(feature = "async_layout")]
+pub use taffy_tree::LayoutFuture;
+#[cfg(all(feature = "taffy_tree", any(feature = "std", feature = "alloc")))]
+pub use taffy_tree::{
+    AccessibleNode, AccessibleVisibility, ContextualError, CustomUnitResolver, FlatLayout, FocusDirection, LayoutTrace,
+    NodeDescription, PendingNode, RelayoutReport, RelayoutScheduler, SnapshotNode, SubtreeBuilder, TaggedDescendants,
+    TracedMeasurement, TracedNode, Transaction, TreeDelta, TreeSnapshot, VirtualChildren, QUANTIZED_HEADER_SIZE,
+    QUANTIZED_RECORD_SIZE,
+};
 #[cfg(feature = "taffy_tree")]
-pub use taffy_tree::{Taffy, TaffyChildIter, TaffyError, TaffyResult};
+pub(crate) use taffy_tree::{Explanation, TaffyView};
+#[cfg(all(feature = "taffy_tree", any(feature = "std", feature = "alloc")))]
+pub(crate) use taffy_tree::{FingerprintHasher, MemoConstraints, MemoEntry, MemoizedNode, SubtreeMemo};
+#[cfg(feature = "taffy_tree")]
+pub use taffy_tree::{
+    LayoutMap, LayoutProgress, LayoutStats, SpeculativeFork, StyleGuard, Taffy, TaffyChildIter, TaffyError, TaffyIter,
+    TaffyResult, MAX_DAMAGE_RECTS,
+};
+#[cfg(all(feature = "taffy_tree", feature = "std"))]
+pub use taffy_tree::{LayoutReader, LayoutSnapshot, LayoutWorker};
 mod layout;
-pub use layout::{CollapsibleMarginSet, Layout, RunMode, SizeBaselinesAndMargins, SizingMode};
+#[cfg(feature = "grid")]
+pub use layout::AutoRepetitions;
+pub use layout::{
+    CellMode, CellRemainder, CollapsibleMarginSet, CoordinateOrigin, Layout, MeasureRequest, RunMode,
+    SizeBaselinesAndMargins, SizeConstraint, SizingMode,
+};
 
 /// Any item that implements the LayoutTree can be layed out using Taffy's algorithms.
 ///
@@ -68,4 +121,70 @@ pub trait LayoutTree {
         sizing_mode: SizingMode,
         vertical_margins_are_collapsible: Line<bool>,
     ) -> SizeBaselinesAndMargins;
+
+    /// Compute the sizes of several child nodes, each under the constraints of its [`MeasureRequest`]
+    ///
+    /// Used by layout algorithms to measure children whose sizes don't depend on each other in a single batch, writing
+    /// the size of each requested child to the same index of `sizes`. The default implementation measures the children
+    /// one after another using [`LayoutTree::measure_child_size`]. Implementations may measure them in parallel.
+    fn measure_child_sizes(&mut self, requests: &[MeasureRequest], sizes: &mut [Size<f32>]) {
+        for (request, size) in requests.iter().zip(sizes.iter_mut()) {
+            *size = self.measure_child_size(
+                request.node,
+                request.known_dimensions,
+                request.parent_size,
+                request.available_space,
+                request.sizing_mode,
+                request.vertical_margins_are_collapsible,
+            );
+        }
+    }
+
+    /// Records the constraint that determined the size of the child `node` in the given `axis`
+    ///
+    /// Called by the layout algorithm of the node's parent just before it performs the final layout of the node, for
+    /// each axis in which the parent imposes a size on it. Used to explain computed sizes. The default implementation
+    /// discards the constraint.
+    #[inline(always)]
+    fn record_size_constraint(&mut self, _node: NodeId, _axis: AbsoluteAxis, _constraint: SizeConstraint) {}
+
+    /// Records the tracks produced by the auto-repeated track definitions of the grid `node`, in each axis
+    ///
+    /// Called by the CSS Grid algorithm each time it performs the final layout of a grid. The default implementation
+    /// discards the repetitions.
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn record_auto_repetitions(&mut self, _node: NodeId, _repetitions: Size<Option<AutoRepetitions>>) {}
+
+    /// Takes a list that the flexbox algorithm collects the items of a flex container into
+    ///
+    /// The algorithm hands the list back with [`LayoutTree::recycle_flex_items`] once it is done with the container, so
+    /// that trees which keep these lists can hand out their allocations again rather than have a new list allocated each
+    /// time a flex container is measured or laid out. The default implementation returns a new, empty list.
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn take_flex_items(&mut self) -> FlexItemBuffer {
+        FlexItemBuffer::default()
+    }
+
+    /// Receives a list taken with [`LayoutTree::take_flex_items`], which has been emptied but keeps its allocation
+    ///
+    /// The default implementation frees the list.
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn recycle_flex_items(&mut self, _items: FlexItemBuffer) {}
+
+    /// Receives a structured debugging event emitted by the layout algorithms
+    ///
+    /// Only called when the `debug` feature is enabled. The default implementation discards the event.
+    #[cfg(feature = "debug")]
+    #[inline(always)]
+    fn debug_event(&mut self, _event: &DebugEvent<'_>) {}
+
+    /// Records that a collection was allocated on the heap at the given `site` while laying out `node`
+    ///
+    /// Only called when the `alloc_audit` feature is enabled. The default implementation discards the allocation.
+    #[cfg(feature = "alloc_audit")]
+    #[inline(always)]
+    fn record_allocation(&mut self, _node: NodeId, _site: AllocationSite) {}
 }
@@ -0,0 +1,107 @@
+//! A ready-made [`Measurable`] for monospace text, such as the text of a terminal user interface
+use crate::geometry::Size;
+use crate::style::AvailableSpace;
+use crate::tree::Measurable;
+use crate::util::sys::{f32_max, String};
+
+/// Text in which every character takes up a single cell of the same size, which is wrapped between words to fit the
+/// available width
+///
+/// The text is split into lines at each `'\n'`, and each line is wrapped greedily at spaces. Like CSS text, a word that
+/// is wider than the available width isn't broken but overflows instead. Measured under a max-content constraint, the
+/// text is as wide as its longest line. Under a min-content constraint it is as wide as its longest word.
+///
+/// Characters are counted as `char`s, so wide characters such as CJK characters and emoji take up a single cell.
+///
+/// ```
+/// use taffy::prelude::*;
+/// use taffy::tree::{MeasureFunc, MonospaceText};
+///
+/// let mut taffy = Taffy::new();
+/// let text = MeasureFunc::Boxed(Box::new(MonospaceText::new("hello wide world")));
+/// let label = taffy.new_leaf_with_measure(Style::default(), text).unwrap();
+/// taffy.compute_layout(label, Size { width: AvailableSpace::Definite(10.0), height: AvailableSpace::MaxContent }).unwrap();
+/// assert_eq!(taffy.layout(label).unwrap().size, Size { width: 10.0, height: 2.0 });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonospaceText {
+    /// The text
+    pub text: String,
+    /// The size of the cell taken up by each character. Defaults to a 1x1 cell, which measures the text in characters.
+    pub cell_size: Size<f32>,
+}
+
+/// The extent of some wrapped text, in cells
+struct WrappedExtent {
+    /// The number of characters in the longest wrapped line
+    columns: usize,
+    /// The number of wrapped lines
+    lines: usize,
+}
+
+impl MonospaceText {
+    /// Text measured in characters, with a 1x1 cell per character
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), cell_size: Size { width: 1.0, height: 1.0 } }
+    }
+
+    /// Sets the size of the cell taken up by each character
+    #[must_use]
+    pub fn with_cell_size(self, cell_size: Size<f32>) -> Self {
+        Self { cell_size, ..self }
+    }
+
+    /// The number of characters in the longest word of the text
+    fn longest_word(&self) -> usize {
+        self.text.split(['\n', ' ']).map(|word| word.chars().count()).max().unwrap_or(0)
+    }
+
+    /// Wraps the text so that its lines are at most `max_columns` characters wide, unless a single word is wider
+    fn wrap(&self, max_columns: usize) -> WrappedExtent {
+        if self.text.is_empty() {
+            return WrappedExtent { columns: 0, lines: 0 };
+        }
+
+        let mut extent = WrappedExtent { columns: 0, lines: 0 };
+        for line in self.text.split('\n') {
+            let mut line_columns = 0;
+            extent.lines += 1;
+            for (index, word) in line.split(' ').enumerate() {
+                let word_columns = word.chars().count();
+                if index == 0 {
+                    line_columns = word_columns;
+                } else if line_columns + 1 + word_columns <= max_columns {
+                    line_columns += 1 + word_columns;
+                } else {
+                    // The space at which the line is wrapped doesn't take up a cell on either line
+                    extent.columns = extent.columns.max(line_columns);
+                    extent.lines += 1;
+                    line_columns = word_columns;
+                }
+            }
+            extent.columns = extent.columns.max(line_columns);
+        }
+        extent
+    }
+}
+
+impl Measurable for MonospaceText {
+    fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+        if let Size { width: Some(width), height: Some(height) } = known_dimensions {
+            return Size { width, height };
+        }
+
+        let max_columns = match known_dimensions.width.map(AvailableSpace::Definite).unwrap_or(available_space.width) {
+            AvailableSpace::MinContent => self.longest_word(),
+            AvailableSpace::MaxContent => usize::MAX,
+            // The small tolerance stops widths that are a multiple of the cell width from losing a column to rounding
+            AvailableSpace::Definite(width) => (f32_max(0.0, width) / self.cell_size.width + 0.001) as usize,
+        };
+        let extent = self.wrap(max_columns);
+
+        Size {
+            width: known_dimensions.width.unwrap_or(extent.columns as f32 * self.cell_size.width),
+            height: known_dimensions.height.unwrap_or(extent.lines as f32 * self.cell_size.height),
+        }
+    }
+}
@@ -2,8 +2,8 @@
 //!
 //! Layouts are composed of multiple nodes, which live in a tree-like data structure.
 use crate::style::Style;
-use crate::tree::Cache;
 use crate::tree::Layout;
+use crate::tree::{Cache, InvalidationLevel};
 
 #[cfg(feature = "taffy_tree")]
 use slotmap::{DefaultKey, Key, KeyData};
@@ -12,7 +12,18 @@ use slotmap::{DefaultKey, Key, KeyData};
 ///
 /// Internally it is a wrapper around a u64 and a `NodeId` can be converted to and from
 /// and u64 if needed.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///
+/// # Round-tripping
+///
+/// Converting a `NodeId` to a `u64` and back yields the same id, so ids can be persisted and logged as `u64`s (they
+/// are also displayed, parsed and serialized as one). In a [`Taffy`](crate::Taffy) tree the lower 32 bits of the id
+/// hold the index of the node's slot and the upper 32 bits hold the generation of the slot, which is bumped every time
+/// the slot is reused. An id therefore doesn't refer to a node created after the node it identified was removed.
+/// Converting to a `usize` truncates the generation on 32-bit targets, so `u64` should be used for persistence.
+///
+/// Ids are ordered by their `u64` value, which is stable but carries no meaning (such as the order of creation).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
 pub struct NodeId(u64);
 impl NodeId {
     /// Create a new NodeId from a u64 value
@@ -21,6 +32,21 @@ impl NodeId {
     }
 }
 
+impl core::fmt::Display for NodeId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl core::str::FromStr for NodeId {
+    type Err = core::num::ParseIntError;
+
+    /// Parses a `NodeId` from its [`Display`](core::fmt::Display) representation
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
 impl From<u64> for NodeId {
     #[inline]
     fn from(raw: u64) -> Self {
@@ -76,20 +102,76 @@ pub(crate) struct NodeData {
 
     /// The cached results of the layout computation
     pub(crate) cache: Cache,
+
+    /// The layout of the node as of the end of the previous layout computation, if any
+    pub(crate) previous_layout: Option<Layout>,
+    /// Whether the layout of the node changed during the most recent layout computation
+    pub(crate) layout_changed: bool,
+    /// The structural fingerprint of the subtree rooted at this node, if it has been computed
+    ///
+    /// `Some(None)` indicates that the subtree can't be fingerprinted because it contains nodes that need measuring.
+    pub(crate) fingerprint: Option<Option<u64>>,
+    /// Whether the layout of the subtree rooted at this node is memoized, see
+    /// [`Taffy::set_memoization_root`](crate::Taffy::set_memoization_root)
+    pub(crate) is_memoization_root: bool,
+    /// The user-defined tags of the node, see [`Taffy::set_tags`](crate::Taffy::set_tags)
+    pub(crate) tags: u32,
 }
 
 impl NodeData {
     /// Create the data for a new node
     #[must_use]
     pub const fn new(style: Style) -> Self {
-        Self { style, cache: Cache::new(), layout: Layout::new(), needs_measure: false }
+        Self {
+            style,
+            cache: Cache::new(),
+            layout: Layout::new(),
+            needs_measure: false,
+            previous_layout: None,
+            layout_changed: false,
+            fingerprint: None,
+            is_memoization_root: false,
+            tags: 0,
+        }
     }
 
-    /// Marks a node and all of its parents (recursively) as dirty
+    /// Marks the node as dirty at the given [`InvalidationLevel`]
     ///
-    /// This clears any cached data and signals that the data must be recomputed.
+    /// This clears the cached data invalidated by `level` and signals that the data must be recomputed.
+    /// Propagating the invalidation to the node's ancestors and descendants is the responsibility of the caller.
     #[inline]
-    pub fn mark_dirty(&mut self) {
-        self.cache.clear()
+    pub fn mark_dirty(&mut self, level: InvalidationLevel) {
+        self.cache.invalidate(level);
+        self.fingerprint = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodeId;
+
+    #[test]
+    fn node_id_round_trips_through_its_display_representation() {
+        let id = NodeId::new((7 << 32) | 3);
+        assert_eq!(id.to_string(), "30064771075");
+        assert_eq!("30064771075".parse::<NodeId>(), Ok(id));
+        assert!("3v7".parse::<NodeId>().is_err());
+    }
+
+    #[cfg(feature = "taffy_tree")]
+    #[test]
+    fn node_id_round_trip_keeps_the_slot_generation() {
+        use crate::style::Style;
+        use crate::Taffy;
+
+        let mut taffy = Taffy::new();
+        let removed = taffy.new_leaf(Style::default()).unwrap();
+        taffy.remove(removed).unwrap();
+        let reused = taffy.new_leaf(Style::default()).unwrap();
+
+        let persisted = u64::from(removed);
+        assert_eq!(NodeId::from(persisted), removed);
+        assert_ne!(NodeId::from(persisted), reused);
+        assert_eq!(persisted as u32, u64::from(reused) as u32);
     }
 }
@@ -0,0 +1,40 @@
+//! The content of replaced elements, such as images, which have a natural size of their own
+use crate::geometry::Size;
+
+/// The natural dimensions of the content of a replaced element, such as an image or a video
+///
+/// A leaf with replaced content is sized like a CSS replaced element: the size set in its style wins, any missing
+/// dimension is derived from the other through the aspect ratio, and the natural size is used when neither is set.
+/// Unlike a [`MeasureFunc`](crate::tree::MeasureFunc), the aspect ratio is preserved when the size is clamped by the
+/// min and max sizes of the node. Natural sizes describe the content box, excluding padding and border.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplacedContent {
+    /// The natural width and height of the content, if it has them
+    pub natural_size: Size<Option<f32>>,
+    /// The natural aspect ratio of the content (width divided by height), if it has one
+    ///
+    /// Defaults to the ratio of the natural size when both of its dimensions are known.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl ReplacedContent {
+    /// Content with the given natural width and height, and the aspect ratio between them
+    pub fn from_size(width: f32, height: f32) -> Self {
+        Self { natural_size: Size { width: Some(width), height: Some(height) }, aspect_ratio: None }
+    }
+
+    /// Content without a natural size, that keeps the given aspect ratio (width divided by height)
+    pub fn from_aspect_ratio(aspect_ratio: f32) -> Self {
+        Self { natural_size: Size::NONE, aspect_ratio: Some(aspect_ratio) }
+    }
+
+    /// The aspect ratio of the content, which is either set explicitly or derived from its natural size
+    pub fn natural_aspect_ratio(&self) -> Option<f32> {
+        match (self.aspect_ratio, self.natural_size) {
+            (Some(ratio), _) => Some(ratio),
+            (None, Size { width: Some(width), height: Some(height) }) if height > 0.0 => Some(width / height),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Export of computed layouts as the geometry of an accessibility tree, for accessibility backends such as AccessKit
+use crate::geometry::{Point, Rect};
+use crate::style::{Display, Style};
+use crate::tree::NodeId;
+use crate::util::sys::{f32_max, f32_min, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// How much of a node exported by [`Taffy::accessibility_tree`] can be seen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AccessibleVisibility {
+    /// The node lies entirely within the areas that its ancestors clip their contents to
+    Visible,
+    /// Part of the node lies outside of an area that one of its ancestors clips its contents to
+    PartiallyClipped,
+    /// The node lies entirely outside of an area that one of its ancestors clips its contents to, such as a
+    /// scrolled-out item of a list
+    Clipped,
+    /// The node or one of its ancestors has [`Display::None`]
+    Hidden,
+}
+
+/// A node exported by [`Taffy::accessibility_tree`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibleNode<M> {
+    /// The node of the tree that this entry describes
+    pub node: NodeId,
+    /// The index of the entry of the closest exported ancestor of the node, if any
+    pub parent: Option<usize>,
+    /// The indexes of the entries of the node's exported children, in order
+    pub children: Vec<usize>,
+    /// The edges of the node relative to the top-left corner of the root, after its ancestors have been scrolled
+    pub bounds: Rect<f32>,
+    /// How much of the node can be seen
+    pub visibility: AccessibleVisibility,
+    /// The metadata returned for the node by the callback passed to [`Taffy::accessibility_tree`]
+    pub metadata: M,
+}
+
+/// A node that remains to be visited by [`Taffy::accessibility_tree`]
+struct Visit {
+    /// The node to visit
+    node: NodeId,
+    /// The index of the entry of the node's closest exported ancestor
+    parent: Option<usize>,
+    /// The position of the parent's contents, after they have been scrolled
+    parent_position: Point<f32>,
+    /// The area that the node's ancestors clip it to
+    clip: Rect<f32>,
+    /// Whether one of the node's ancestors is hidden
+    ancestor_hidden: bool,
+}
+
+impl Taffy {
+    /// Exports the geometry of `root` and its descendants for an accessibility backend, in logical order
+    ///
+    /// `metadata` is called for each node in depth-first order, which is the order that assistive technologies
+    /// present the nodes in. It returns the data that the backend needs to describe the node (such as its role and
+    /// label), or `None` for nodes that are only there for layout. Nodes for which it returns `None` are left out, and
+    /// their exported descendants become the children of their closest exported ancestor. The entries are returned in
+    /// logical order, and link to each other by index.
+    ///
+    /// Bounds are absolute, with y increasing downwards whichever [`CoordinateOrigin`](crate::tree::CoordinateOrigin)
+    /// the tree uses, and take the [scroll offsets](Taffy::set_scroll_offset) of ancestors into account like
+    /// [`Taffy::absolute_location`]. Nodes are clipped by ancestors that are scroll containers in the axes in which
+    /// they are (see [`Overflow`](crate::style::Overflow)).
+    pub fn accessibility_tree<M>(
+        &self,
+        root: NodeId,
+        mut metadata: impl FnMut(NodeId, &Style) -> Option<M>,
+    ) -> TaffyResult<Vec<AccessibleNode<M>>> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+
+        let unclipped =
+            Rect { left: f32::NEG_INFINITY, right: f32::INFINITY, top: f32::NEG_INFINITY, bottom: f32::INFINITY };
+        let mut entries: Vec<AccessibleNode<M>> = Vec::new();
+        let mut stack: Vec<Visit> = Vec::new();
+        stack.push(Visit {
+            node: root,
+            parent: None,
+            parent_position: Point::ZERO,
+            clip: unclipped,
+            ancestor_hidden: false,
+        });
+        while let Some(Visit { node, parent, parent_position, clip, ancestor_hidden }) = stack.pop() {
+            let node_data = &self.nodes[node.into()];
+            let location = match self.parents[node.into()] {
+                Some(parent) if node != root => self.top_left_location(node, parent),
+                _ => Point::ZERO,
+            };
+            let position = Point { x: parent_position.x + location.x, y: parent_position.y + location.y };
+            let bounds = Rect::from((position, node_data.layout.size));
+            let hidden = ancestor_hidden || node_data.style.display == Display::None;
+
+            let visibility = if hidden {
+                AccessibleVisibility::Hidden
+            } else if bounds.left >= clip.left
+                && bounds.right <= clip.right
+                && bounds.top >= clip.top
+                && bounds.bottom <= clip.bottom
+            {
+                AccessibleVisibility::Visible
+            } else if bounds.intersection(clip).is_some() {
+                AccessibleVisibility::PartiallyClipped
+            } else {
+                AccessibleVisibility::Clipped
+            };
+
+            let mut exported_parent = parent;
+            if let Some(data) = metadata(node, &node_data.style) {
+                let index = entries.len();
+                if let Some(parent) = parent {
+                    entries[parent].children.push(index);
+                }
+                entries.push(AccessibleNode { node, parent, children: Vec::new(), bounds, visibility, metadata: data });
+                exported_parent = Some(index);
+            }
+
+            // Scroll containers clip their contents to their own bounds in the axes in which they scroll
+            let overflow = node_data.style.overflow;
+            let mut child_clip = clip;
+            if overflow.x.is_scroll_container() {
+                child_clip.left = f32_max(child_clip.left, bounds.left);
+                child_clip.right = f32_min(child_clip.right, bounds.right);
+            }
+            if overflow.y.is_scroll_container() {
+                child_clip.top = f32_max(child_clip.top, bounds.top);
+                child_clip.bottom = f32_min(child_clip.bottom, bounds.bottom);
+            }
+            let scroll_offset = self.scroll_offset(node);
+            let content_position = Point { x: position.x - scroll_offset.x, y: position.y - scroll_offset.y };
+
+            // Children are pushed in reverse so that they are visited in order
+            stack.extend(self.children[node.into()].iter().rev().map(|child| Visit {
+                node: *child,
+                parent: exported_parent,
+                parent_position: content_position,
+                clip: child_clip,
+                ancestor_hidden: hidden,
+            }));
+        }
+        Ok(entries)
+    }
+}
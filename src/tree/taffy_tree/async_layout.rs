@@ -0,0 +1,84 @@
+//! Computing layouts as a future that yields to the executor at node boundaries, enabled by the `async_layout` feature
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::geometry::Size;
+use crate::style::AvailableSpace;
+use crate::tree::NodeId;
+
+use super::{LayoutProgress, Taffy, TaffyError};
+
+/// A layout computation that yields to the executor after laying out a number of nodes, returned by
+/// [`Taffy::compute_layout_async`]
+///
+/// Each poll lays out up to `nodes_per_poll` nodes with [`Taffy::compute_layout_with_yield`]. If the layout isn't
+/// complete by then, the future wakes itself and returns [`Poll::Pending`], so that the executor can run other tasks
+/// before the next poll continues from the results cached so far. Dropping the future before it completes leaves the
+/// stored layouts as they were before it was first polled.
+///
+/// A node's cache only holds one result per kind of sizing request, so some of the results cached by an interrupted
+/// poll may be displaced by later results before the computation resumes. To guarantee that the computation completes,
+/// the number of nodes that may be laid out doubles after each interrupted poll.
+#[must_use = "futures do nothing unless polled"]
+pub struct LayoutFuture<'a> {
+    /// The tree that is being laid out
+    taffy: &'a mut Taffy,
+    /// The root node of the computation
+    node: NodeId,
+    /// The space available to `node`
+    available_space: Size<AvailableSpace>,
+    /// The maximum number of nodes laid out by the next poll
+    nodes_per_poll: usize,
+}
+
+impl core::fmt::Debug for LayoutFuture<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LayoutFuture")
+            .field("node", &self.node)
+            .field("available_space", &self.available_space)
+            .field("nodes_per_poll", &self.nodes_per_poll)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Future for LayoutFuture<'_> {
+    type Output = Result<(), TaffyError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let nodes_per_poll = this.nodes_per_poll;
+        let mut nodes_laid_out = 0;
+        let progress = this.taffy.compute_layout_with_yield(this.node, this.available_space, || {
+            nodes_laid_out += 1;
+            nodes_laid_out > nodes_per_poll
+        })?;
+
+        match progress {
+            LayoutProgress::Complete => Poll::Ready(Ok(())),
+            LayoutProgress::Interrupted => {
+                this.nodes_per_poll = nodes_per_poll.saturating_mul(2);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Taffy {
+    /// Returns a future that updates the stored layout of the provided `node` and its children, yielding to the
+    /// executor after laying out `nodes_per_poll` nodes, and after twice as many nodes each following poll (see
+    /// [`LayoutFuture`])
+    ///
+    /// This lets the layout of huge trees run inside of an async executor without starving its other tasks. The nodes
+    /// counted are those that can't be served from their cache, so a poll also has to walk the cached part of the tree
+    /// laid out by previous polls. A `nodes_per_poll` of zero is treated as one.
+    pub fn compute_layout_async(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        nodes_per_poll: usize,
+    ) -> LayoutFuture<'_> {
+        LayoutFuture { taffy: self, node, available_space, nodes_per_poll: nodes_per_poll.max(1) }
+    }
+}
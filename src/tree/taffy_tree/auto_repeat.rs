@@ -0,0 +1,35 @@
+//! Recording the tracks produced by the auto-repeated track definitions of grids
+use crate::geometry::{AbsoluteAxis, Size};
+use crate::tree::{AutoRepetitions, NodeId};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+impl Taffy {
+    /// Returns the tracks produced by the `repeat(auto-fill, ...)` or `repeat(auto-fit, ...)` definition of the grid
+    /// `node` in the given `axis`, as of its most recent layout
+    ///
+    /// This includes the number of repetitions and which repeated tracks were collapsed, from which the effective
+    /// number of columns or rows of the grid can be derived. Returns `None` if the node wasn't laid out as a grid with
+    /// an auto-repeated track definition in that axis by its most recent layout, for example because it hasn't been
+    /// laid out since it became one, or because it has since been laid out with a different display mode.
+    pub fn auto_repetitions(&self, node: NodeId, axis: AbsoluteAxis) -> TaffyResult<Option<&AutoRepetitions>> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        let repetitions = self.auto_repetitions.get(node.into());
+        Ok(repetitions.and_then(|repetitions| match axis {
+            AbsoluteAxis::Horizontal => repetitions.width.as_ref(),
+            AbsoluteAxis::Vertical => repetitions.height.as_ref(),
+        }))
+    }
+
+    /// Records the tracks produced by the auto-repeated track definitions of the grid `node`, see
+    /// [`LayoutTree::record_auto_repetitions`](crate::tree::LayoutTree::record_auto_repetitions)
+    pub(crate) fn record_auto_repetitions(&mut self, node: NodeId, repetitions: Size<Option<AutoRepetitions>>) {
+        if repetitions.width.is_some() || repetitions.height.is_some() {
+            self.auto_repetitions.insert(node.into(), repetitions);
+        } else {
+            self.auto_repetitions.remove(node.into());
+        }
+    }
+}
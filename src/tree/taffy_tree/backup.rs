@@ -0,0 +1,69 @@
+//! Backups of the state of nodes, from which changes to a [`Taffy`] tree can be undone
+use slotmap::DefaultKey;
+
+use crate::style::Style;
+use crate::tree::Cache;
+
+use super::Taffy;
+
+/// The state of a node before it was first changed, which is restored to undo the change
+///
+/// The cached results of the node are always saved, as they are invalidated by changes to the node and to its
+/// descendants. The node's style is only saved once it is about to be replaced, see [`NodeBackup::save_style`].
+pub(super) struct NodeBackup {
+    /// The node's own style, if it has been saved
+    style: Option<Style>,
+    /// The node's style before its style classes are applied, if it has been assigned any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    own_style: Option<Style>,
+    /// The node's style with its context-dependent units resolved, if it uses any
+    resolved_style: Option<Style>,
+    /// The node's cached layout results
+    cache: Cache,
+    /// The node's subtree fingerprint
+    fingerprint: Option<Option<u64>>,
+}
+
+impl NodeBackup {
+    /// Saves the cached results of the node, which must be in the tree
+    pub(super) fn new(taffy: &Taffy, key: DefaultKey) -> Self {
+        let node_data = &taffy.nodes[key];
+        Self {
+            style: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            own_style: None,
+            resolved_style: taffy.resolved_styles.get(key).cloned(),
+            cache: node_data.cache.clone(),
+            fingerprint: node_data.fingerprint,
+        }
+    }
+
+    /// Saves the style of the node before it is replaced, unless it has already been saved
+    pub(super) fn save_style(&mut self, taffy: &Taffy, key: DefaultKey) {
+        if self.style.is_none() {
+            self.style = Some(taffy.nodes[key].style.clone());
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            {
+                self.own_style = taffy.node_classes.get(key).map(|node_classes| node_classes.own_style.clone());
+            }
+        }
+    }
+
+    /// Restores the saved state of the node
+    pub(super) fn restore(self, taffy: &mut Taffy, key: DefaultKey) {
+        if let Some(style) = self.style {
+            taffy.nodes[key].style = style;
+            match self.resolved_style {
+                Some(resolved_style) => taffy.resolved_styles.insert(key, resolved_style),
+                None => taffy.resolved_styles.remove(key),
+            };
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            if let (Some(node_classes), Some(own_style)) = (taffy.node_classes.get_mut(key), self.own_style) {
+                node_classes.own_style = own_style;
+            }
+        }
+        let node_data = &mut taffy.nodes[key];
+        node_data.cache = self.cache;
+        node_data.fingerprint = self.fingerprint;
+    }
+}
@@ -0,0 +1,664 @@
+//! A harness for checking that Taffy lays out a tree the same way as a web browser
+//!
+//! [`render_html`] converts a tree into an equivalent HTML document, which a [`Browser`] renders and measures. The
+//! [`WebDriver`] browser drives a real browser through a [WebDriver](https://www.w3.org/TR/webdriver2/) server such as
+//! `chromedriver`, while custom implementations can be used to replay recorded results. [`compare_with_browser`]
+//! then checks the layouts computed by Taffy against the browser's layouts:
+//!
+//! ```no_run
+//! use taffy::prelude::*;
+//! use taffy::tree::browser_harness::{compare_with_browser, WebDriver};
+//!
+//! let mut taffy = Taffy::new();
+//! let child = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+//! let root = taffy.new_with_children(Style { size: Size::from_lengths(100.0, 50.0), ..Default::default() }, &[child]).unwrap();
+//!
+//! let mut browser = WebDriver::connect("localhost:4444").unwrap();
+//! compare_with_browser(&mut taffy, root, &mut browser, 0.01).unwrap();
+//! ```
+//!
+//! Nodes are rendered as `<div>` elements styled like the fixtures of Taffy's own test suite, so the tree is laid out
+//! under a max-content constraint. Measure functions and custom layout algorithms can't be rendered, so nodes that are
+//! measured or use [`Display::Custom`](crate::style::Display::Custom) should be avoided.
+use std::fmt::{self, Display, Formatter, Write as _};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+use crate::geometry::{Point, Rect, Size};
+use crate::style::{
+    AlignContent, AlignItems, Contain, Dimension, Display as DisplayMode, FlexDirection, FlexWrap, LengthPercentage,
+    Overflow, Position, Style,
+};
+#[cfg(feature = "grid")]
+use crate::style::{
+    GridAutoFlow, GridPlacement, GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
+    NonRepeatedTrackSizingFunction, TrackSizingFunction,
+};
+use crate::style_helpers::TaffyMaxContent;
+use crate::tree::NodeId;
+
+use super::{Taffy, TaffyError};
+
+/// The styles applied to every element of a rendered document, matching the fixtures of Taffy's test suite
+const BASE_STYLE: &str = "body { padding: 0; margin: 0; } \
+    div { box-sizing: border-box; position: relative; border: 0 solid red; margin: 0; padding: 0; display: flex; } \
+    body > * { position: absolute; }";
+
+/// Measures each element below the test root, returning `x,y,width,height` lists separated by `;` in depth-first order
+const MEASURE_SCRIPT: &str = "const results = []; \
+    const visit = (element) => { \
+      const rect = element.getBoundingClientRect(); \
+      const parent = element.parentNode.getBoundingClientRect(); \
+      results.push([rect.left - parent.left, rect.top - parent.top, rect.width, rect.height].join(',')); \
+      for (const child of element.children) { visit(child); } \
+    }; \
+    visit(document.getElementById('test-root')); \
+    return results.join(';');";
+
+/// The layout of an element as measured by a [`Browser`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrowserLayout {
+    /// The position of the element's border box relative to the border box of its parent
+    pub location: Point<f32>,
+    /// The size of the element's border box
+    pub size: Size<f32>,
+}
+
+/// A node whose layout computed by Taffy differs from its layout in the browser
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutMismatch {
+    /// The node whose layout differs
+    pub node: NodeId,
+    /// The layout computed by Taffy
+    pub taffy: BrowserLayout,
+    /// The layout measured by the browser
+    pub browser: BrowserLayout,
+}
+
+/// An error produced by the browser harness
+#[derive(Debug)]
+pub enum HarnessError {
+    /// Communicating with the browser failed
+    Io(io::Error),
+    /// The browser returned an error or an unexpected response
+    Browser(String),
+    /// Laying out the tree with Taffy failed
+    Taffy(TaffyError),
+    /// The browser measured a different number of elements than there are nodes in the tree
+    NodeCount {
+        /// The number of nodes in the tree
+        expected: usize,
+        /// The number of elements measured by the browser
+        found: usize,
+    },
+    /// The layouts of some nodes differ by more than the tolerance
+    Mismatch(Vec<LayoutMismatch>),
+}
+
+impl Display for HarnessError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            HarnessError::Io(error) => write!(f, "Failed to communicate with the browser: {error}"),
+            HarnessError::Browser(message) => write!(f, "The browser returned an error: {message}"),
+            HarnessError::Taffy(error) => write!(f, "Failed to compute the layout: {error}"),
+            HarnessError::NodeCount { expected, found } => {
+                write!(f, "Expected the browser to measure {expected} elements, but it measured {found}")
+            }
+            HarnessError::Mismatch(mismatches) => {
+                write!(f, "The layouts of {} nodes differ from the browser:", mismatches.len())?;
+                for mismatch in mismatches {
+                    write!(f, "\n  {:?}: taffy {:?}, browser {:?}", mismatch.node, mismatch.taffy, mismatch.browser)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HarnessError {}
+
+impl From<io::Error> for HarnessError {
+    fn from(error: io::Error) -> Self {
+        HarnessError::Io(error)
+    }
+}
+
+impl From<TaffyError> for HarnessError {
+    fn from(error: TaffyError) -> Self {
+        HarnessError::Taffy(error)
+    }
+}
+
+/// Renders HTML documents and measures the resulting layout
+pub trait Browser {
+    /// Renders the `html` document, returning the layout of the element with the id `test-root` and of each of its
+    /// descendant elements in depth-first order
+    fn measure(&mut self, html: &str) -> Result<Vec<BrowserLayout>, HarnessError>;
+}
+
+/// Computes the layout of `root` and its descendants and checks it against the layout measured by `browser`
+///
+/// Returns [`HarnessError::Mismatch`] listing every node whose location or size differs from the browser's by more
+/// than `tolerance` in either axis.
+pub fn compare_with_browser(
+    taffy: &mut Taffy,
+    root: NodeId,
+    browser: &mut impl Browser,
+    tolerance: f32,
+) -> Result<(), HarnessError> {
+    taffy.compute_layout(root, Size::MAX_CONTENT)?;
+    let browser_layouts = browser.measure(&render_html(taffy, root))?;
+
+    let mut nodes = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        nodes.push(node);
+        stack.extend(taffy.children[node.into()].iter().rev().copied());
+    }
+    if nodes.len() != browser_layouts.len() {
+        return Err(HarnessError::NodeCount { expected: nodes.len(), found: browser_layouts.len() });
+    }
+
+    let mismatches: Vec<LayoutMismatch> = nodes
+        .into_iter()
+        .zip(browser_layouts)
+        .filter_map(|(node, browser)| {
+            let layout = taffy.nodes[node.into()].layout;
+            let taffy = BrowserLayout { location: layout.location, size: layout.size };
+            let differs = |a: f32, b: f32| (a - b).abs() > tolerance;
+            let mismatched = differs(taffy.location.x, browser.location.x)
+                || differs(taffy.location.y, browser.location.y)
+                || differs(taffy.size.width, browser.size.width)
+                || differs(taffy.size.height, browser.size.height);
+            mismatched.then_some(LayoutMismatch { node, taffy, browser })
+        })
+        .collect();
+
+    match mismatches.is_empty() {
+        true => Ok(()),
+        false => Err(HarnessError::Mismatch(mismatches)),
+    }
+}
+
+/// Renders `root` and its descendants as an HTML document, in which `root` is the element with the id `test-root`
+pub fn render_html(taffy: &Taffy, root: NodeId) -> String {
+    let mut html = format!("<!DOCTYPE html><html><head><style>{BASE_STYLE}</style></head><body>");
+    render_node(taffy, root, true, &mut html);
+    html.push_str("</body></html>");
+    html
+}
+
+/// Renders the element for `node` and its descendants
+fn render_node(taffy: &Taffy, node: NodeId, is_root: bool, html: &mut String) {
+    html.push_str("<div");
+    if is_root {
+        html.push_str(" id=\"test-root\"");
+    }
+    let css = style_to_css(&taffy.nodes[node.into()].style);
+    if !css.is_empty() {
+        let _ = write!(html, " style=\"{css}\"");
+    }
+    html.push('>');
+    for child in taffy.children[node.into()].iter() {
+        render_node(taffy, *child, false, html);
+    }
+    html.push_str("</div>");
+}
+
+/// Converts the properties of `style` that differ from the default style into CSS declarations
+fn style_to_css(style: &Style) -> String {
+    let default = Style::default();
+    let mut css = String::new();
+    let mut declare = |property: &str, value: &dyn Display| {
+        let _ = write!(css, "{property}: {value}; ");
+    };
+
+    if style.display != default.display {
+        declare("display", &display(style.display));
+    }
+    if style.position != default.position {
+        declare("position", &if style.position == Position::Absolute { "absolute" } else { "relative" });
+    }
+    if style.overflow != default.overflow {
+        declare("overflow-x", &overflow(style.overflow.x));
+        declare("overflow-y", &overflow(style.overflow.y));
+    }
+
+    let insets = rect_sides(style.inset).into_iter().zip(rect_sides(default.inset));
+    for (name, (value, default_value)) in ["top", "right", "bottom", "left"].iter().zip(insets) {
+        if value != default_value {
+            declare(name, &css_dimension(value.into()));
+        }
+    }
+    // Browsers have no switch for the automatic minimum size, but an explicit zero minimum size is equivalent
+    let min_size = match style.disable_automatic_min_size {
+        true => style.min_size.map(|min| if min == Dimension::Auto { Dimension::Length(0.0) } else { min }),
+        false => style.min_size,
+    };
+    let sizes = [
+        ("width", style.size.width, default.size.width),
+        ("height", style.size.height, default.size.height),
+        ("min-width", min_size.width, default.min_size.width),
+        ("min-height", min_size.height, default.min_size.height),
+        ("max-width", style.max_size.width, default.max_size.width),
+        ("max-height", style.max_size.height, default.max_size.height),
+        ("flex-basis", style.flex_basis, default.flex_basis),
+    ];
+    for (name, value, default_value) in sizes {
+        if value != default_value {
+            declare(name, &css_dimension(value));
+        }
+    }
+    if let Some(ratio) = style.aspect_ratio {
+        declare("aspect-ratio", &ratio);
+    }
+    if style.contain == Contain::Size {
+        declare("contain", &"size");
+    }
+    let margins = rect_sides(style.margin).into_iter().zip(rect_sides(default.margin));
+    for (name, (value, default_value)) in
+        ["margin-top", "margin-right", "margin-bottom", "margin-left"].iter().zip(margins)
+    {
+        if value != default_value {
+            declare(name, &css_dimension(value.into()));
+        }
+    }
+    for (prefix, rect, suffix) in [("padding", style.padding, ""), ("border", style.border, "-width")] {
+        for (side, value) in ["top", "right", "bottom", "left"].iter().zip(rect_sides(rect)) {
+            if value != LengthPercentage::Length(0.0) {
+                declare(&format!("{prefix}-{side}{suffix}"), &css_dimension(value.into()));
+            }
+        }
+    }
+
+    if let Some(align) = style.align_items {
+        declare("align-items", &align_items(align));
+    }
+    if let Some(align) = style.align_self {
+        declare("align-self", &align_items(align));
+    }
+    if let Some(align) = style.align_content {
+        declare("align-content", &align_content(align));
+    }
+    if let Some(align) = style.justify_content {
+        declare("justify-content", &align_content(align));
+    }
+    if style.gap.height != default.gap.height {
+        declare("row-gap", &css_dimension(style.gap.height.into()));
+    }
+    if style.gap.width != default.gap.width {
+        declare("column-gap", &css_dimension(style.gap.width.into()));
+    }
+
+    if style.flex_direction != default.flex_direction {
+        let direction = match style.flex_direction {
+            FlexDirection::Row => "row",
+            FlexDirection::Column => "column",
+            FlexDirection::RowReverse => "row-reverse",
+            FlexDirection::ColumnReverse => "column-reverse",
+        };
+        declare("flex-direction", &direction);
+    }
+    if style.flex_wrap != default.flex_wrap {
+        let wrap = match style.flex_wrap {
+            FlexWrap::NoWrap => "nowrap",
+            FlexWrap::Wrap => "wrap",
+            FlexWrap::WrapReverse => "wrap-reverse",
+        };
+        declare("flex-wrap", &wrap);
+    }
+    if style.flex_grow != default.flex_grow {
+        declare("flex-grow", &style.flex_grow);
+    }
+    if style.flex_shrink != default.flex_shrink {
+        declare("flex-shrink", &style.flex_shrink);
+    }
+
+    #[cfg(feature = "grid")]
+    {
+        if let Some(align) = style.justify_items {
+            declare("justify-items", &align_items(align));
+        }
+        if let Some(align) = style.justify_self {
+            declare("justify-self", &align_items(align));
+        }
+        let templates = [
+            ("grid-template-rows", &style.grid_template_rows),
+            ("grid-template-columns", &style.grid_template_columns),
+        ];
+        for (name, tracks) in templates {
+            if !tracks.is_empty() {
+                let tracks: Vec<String> = tracks.iter().map(css_track_sizing_function).collect();
+                declare(name, &tracks.join(" "));
+            }
+        }
+        for (name, tracks) in
+            [("grid-auto-rows", &style.grid_auto_rows), ("grid-auto-columns", &style.grid_auto_columns)]
+        {
+            if !tracks.is_empty() {
+                let tracks: Vec<String> = tracks.iter().map(css_track).collect();
+                declare(name, &tracks.join(" "));
+            }
+        }
+        if style.grid_auto_flow != default.grid_auto_flow {
+            let flow = match style.grid_auto_flow {
+                GridAutoFlow::Row => "row",
+                GridAutoFlow::Column => "column",
+                GridAutoFlow::RowDense => "row dense",
+                GridAutoFlow::ColumnDense => "column dense",
+            };
+            declare("grid-auto-flow", &flow);
+        }
+        let placements = [
+            ("grid-row-start", style.grid_row.start),
+            ("grid-row-end", style.grid_row.end),
+            ("grid-column-start", style.grid_column.start),
+            ("grid-column-end", style.grid_column.end),
+        ];
+        for (name, placement) in placements {
+            match placement {
+                GridPlacement::Auto => {}
+                GridPlacement::Line(line) => declare(name, &line.as_i16()),
+                GridPlacement::Span(span) => declare(name, &format!("span {span}")),
+            }
+        }
+    }
+
+    css.truncate(css.trim_end().len());
+    css
+}
+
+/// Returns the sides of a rect in CSS order (top, right, bottom, left)
+fn rect_sides<T>(rect: Rect<T>) -> [T; 4] {
+    [rect.top, rect.right, rect.bottom, rect.left]
+}
+
+/// Converts a display mode into CSS
+fn display(display: DisplayMode) -> &'static str {
+    match display {
+        #[cfg(feature = "block_layout")]
+        DisplayMode::Block => "block",
+        #[cfg(feature = "flexbox")]
+        DisplayMode::Flex => "flex",
+        #[cfg(feature = "grid")]
+        DisplayMode::Grid => "grid",
+        DisplayMode::None => "none",
+        // Browsers can't run custom layout algorithms, so their nodes are rendered as blocks
+        DisplayMode::Custom(_) => "block",
+    }
+}
+
+/// Converts an overflow mode into CSS
+fn overflow(overflow: Overflow) -> &'static str {
+    match overflow {
+        Overflow::Visible => "visible",
+        Overflow::Hidden => "hidden",
+        Overflow::Scroll => "scroll",
+    }
+}
+
+/// Converts an item alignment into CSS
+fn align_items(align: AlignItems) -> &'static str {
+    match align {
+        AlignItems::Start => "start",
+        AlignItems::End => "end",
+        AlignItems::FlexStart => "flex-start",
+        AlignItems::FlexEnd => "flex-end",
+        AlignItems::Center => "center",
+        AlignItems::Baseline => "baseline",
+        AlignItems::Stretch => "stretch",
+    }
+}
+
+/// Converts a content alignment into CSS
+fn align_content(align: AlignContent) -> &'static str {
+    match align {
+        AlignContent::Start => "start",
+        AlignContent::End => "end",
+        AlignContent::FlexStart => "flex-start",
+        AlignContent::FlexEnd => "flex-end",
+        AlignContent::Center => "center",
+        AlignContent::Stretch => "stretch",
+        AlignContent::SpaceBetween => "space-between",
+        AlignContent::SpaceEvenly => "space-evenly",
+        AlignContent::SpaceAround => "space-around",
+    }
+}
+
+/// Converts a dimension into CSS
+///
+/// Custom units can't be expressed in CSS, so they are rendered as `auto`.
+fn css_dimension(dimension: Dimension) -> String {
+    match dimension {
+        Dimension::Length(length) => format!("{length}px"),
+        Dimension::Percent(fraction) => format!("{}%", fraction * 100.0),
+        Dimension::Vw(fraction) => format!("{}vw", fraction * 100.0),
+        Dimension::Vh(fraction) => format!("{}vh", fraction * 100.0),
+        Dimension::Vmin(fraction) => format!("{}vmin", fraction * 100.0),
+        Dimension::Vmax(fraction) => format!("{}vmax", fraction * 100.0),
+        Dimension::Rem(multiple) => format!("{multiple}rem"),
+        Dimension::Em(multiple) => format!("{multiple}em"),
+        Dimension::Custom(_) | Dimension::Auto => String::from("auto"),
+    }
+}
+
+/// Converts a grid track into CSS
+#[cfg(feature = "grid")]
+fn css_track(track: &NonRepeatedTrackSizingFunction) -> String {
+    use MaxTrackSizingFunction as Max;
+    use MinTrackSizingFunction as Min;
+
+    let min = match track.min {
+        Min::Fixed(length) => css_dimension(length.into()),
+        Min::MinContent => String::from("min-content"),
+        Min::MaxContent => String::from("max-content"),
+        Min::Auto => String::from("auto"),
+    };
+    match (track.min, track.max) {
+        (Min::Fixed(fixed_min), Max::Fixed(fixed_max)) if fixed_min == fixed_max => min,
+        (Min::MinContent, Max::MinContent) | (Min::MaxContent, Max::MaxContent) | (Min::Auto, Max::Auto) => min,
+        (Min::Auto, Max::Fraction(fraction)) => format!("{fraction}fr"),
+        (Min::Auto, Max::FitContent(limit)) => format!("fit-content({})", css_dimension(limit.into())),
+        (_, max) => {
+            let max = match max {
+                Max::Fixed(length) => css_dimension(length.into()),
+                Max::MinContent => String::from("min-content"),
+                Max::MaxContent => String::from("max-content"),
+                Max::FitContent(limit) => format!("fit-content({})", css_dimension(limit.into())),
+                Max::Auto => String::from("auto"),
+                Max::Fraction(fraction) => format!("{fraction}fr"),
+            };
+            format!("minmax({min}, {max})")
+        }
+    }
+}
+
+/// Converts a grid track or repetition of grid tracks into CSS
+#[cfg(feature = "grid")]
+fn css_track_sizing_function(track: &TrackSizingFunction) -> String {
+    match track {
+        TrackSizingFunction::Single(track) => css_track(track),
+        TrackSizingFunction::Repeat(repetition, tracks) => {
+            let repetition = match repetition {
+                GridTrackRepetition::AutoFill => String::from("auto-fill"),
+                GridTrackRepetition::AutoFit => String::from("auto-fit"),
+                GridTrackRepetition::Count(count) => count.to_string(),
+            };
+            let tracks: Vec<String> = tracks.iter().map(css_track).collect();
+            format!("repeat({repetition}, {})", tracks.join(" "))
+        }
+    }
+}
+
+/// A [`Browser`] controlled through a WebDriver server, such as `chromedriver`
+///
+/// A headless Chrome session is started when connecting, and ended when the browser is dropped.
+#[derive(Debug)]
+pub struct WebDriver {
+    /// The `host:port` address of the WebDriver server
+    address: String,
+    /// The id of the browser session
+    session_id: String,
+}
+
+impl WebDriver {
+    /// Starts a browser session on the WebDriver server listening at `address` (e.g. `localhost:4444`)
+    pub fn connect(address: &str) -> Result<Self, HarnessError> {
+        let capabilities = r#"{"capabilities":{"alwaysMatch":{"goog:chromeOptions":{"args":["--headless","--disable-gpu","--hide-scrollbars"]}}}}"#;
+        let response = request(address, "POST", "/session", capabilities)?;
+        let session_id = json_string_field(&response, "sessionId")
+            .ok_or_else(|| HarnessError::Browser(browser_error_message(&response)))?;
+        Ok(Self { address: String::from(address), session_id })
+    }
+
+    /// Sends a command to the browser session, returning the response body
+    fn command(&self, method: &str, command: &str, body: &str) -> Result<String, HarnessError> {
+        let path = format!("/session/{}/{command}", self.session_id);
+        let response = request(&self.address, method, &path, body)?;
+        if response.contains("\"error\"") {
+            return Err(HarnessError::Browser(browser_error_message(&response)));
+        }
+        Ok(response)
+    }
+}
+
+impl Browser for WebDriver {
+    fn measure(&mut self, html: &str) -> Result<Vec<BrowserLayout>, HarnessError> {
+        let url = format!("data:text/html;charset=utf-8,{}", percent_encode(html));
+        self.command("POST", "url", &format!("{{\"url\":{}}}", json_string(&url)))?;
+        let response = self.command(
+            "POST",
+            "execute/sync",
+            &format!("{{\"script\":{},\"args\":[]}}", json_string(MEASURE_SCRIPT)),
+        )?;
+        let value = json_string_field(&response, "value")
+            .ok_or_else(|| HarnessError::Browser(format!("Unexpected response: {response}")))?;
+        parse_layouts(&value).ok_or_else(|| HarnessError::Browser(format!("Unexpected measurements: {value}")))
+    }
+}
+
+impl Drop for WebDriver {
+    fn drop(&mut self) {
+        // The session can't be ended if the server has gone away, in which case there is nothing left to clean up
+        let _ = request(&self.address, "DELETE", &format!("/session/{}", self.session_id), "");
+    }
+}
+
+/// Parses the measurements returned by [`MEASURE_SCRIPT`]
+fn parse_layouts(measurements: &str) -> Option<Vec<BrowserLayout>> {
+    measurements
+        .split(';')
+        .map(|element| {
+            let mut values = element.split(',').map(|value| value.parse::<f32>().ok());
+            let layout = BrowserLayout {
+                location: Point { x: values.next()??, y: values.next()?? },
+                size: Size { width: values.next()??, height: values.next()?? },
+            };
+            values.next().is_none().then_some(layout)
+        })
+        .collect()
+}
+
+/// Sends an HTTP request to `address` and returns the body of the response
+fn request(address: &str, method: &str, path: &str, body: &str) -> Result<String, HarnessError> {
+    let mut stream = TcpStream::connect(address)?;
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+
+    let mut reader = BufReader::new(stream);
+    let mut chunked = false;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" {
+            break;
+        }
+        let header = line.to_ascii_lowercase();
+        chunked |= header.starts_with("transfer-encoding:") && header.contains("chunked");
+    }
+
+    let mut response = String::new();
+    if !chunked {
+        reader.read_to_string(&mut response)?;
+        return Ok(response);
+    }
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let length = usize::from_str_radix(line.trim(), 16)
+            .map_err(|_| HarnessError::Browser(String::from("Invalid chunked response")))?;
+        if length == 0 {
+            return Ok(response);
+        }
+        let mut chunk = vec![0; length + 2];
+        reader.read_exact(&mut chunk)?;
+        chunk.truncate(length);
+        response.push_str(&String::from_utf8_lossy(&chunk));
+    }
+}
+
+/// Returns the error message of a WebDriver error response, or the whole response if it doesn't have one
+fn browser_error_message(response: &str) -> String {
+    json_string_field(response, "message").unwrap_or_else(|| String::from(response))
+}
+
+/// Returns the value of the first string field with the given name in `json`
+fn json_string_field(json: &str, field: &str) -> Option<String> {
+    let key = format!("\"{field}\"");
+    let after_key = &json[json.find(&key)? + key.len()..];
+    let value = after_key.trim_start().strip_prefix(':')?.trim_start().strip_prefix('"')?;
+
+    let mut result = String::new();
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' => return Some(result),
+            '\\' => match characters.next()? {
+                'n' => result.push('\n'),
+                't' => result.push('\t'),
+                'r' => result.push('\r'),
+                'u' => {
+                    let code: String = characters.by_ref().take(4).collect();
+                    result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?).unwrap_or('\u{fffd}'));
+                }
+                other => result.push(other),
+            },
+            other => result.push(other),
+        }
+    }
+    None
+}
+
+/// Encodes `string` as a JSON string literal
+fn json_string(string: &str) -> String {
+    let mut json = String::from("\"");
+    for character in string.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            control if control.is_control() => {
+                let _ = write!(json, "\\u{:04x}", control as u32);
+            }
+            other => json.push(other),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Percent-encodes every byte of `string` that isn't an unreserved URL character
+fn percent_encode(string: &str) -> String {
+    let mut encoded = String::new();
+    for byte in string.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,126 @@
+//! Defragmenting the storage of a tree after many nodes have been removed
+use slotmap::{DefaultKey, SecondaryMap, SlotMap, SparseSecondaryMap};
+
+use crate::tree::NodeId;
+use crate::util::sys::{BTreeMap, Vec};
+
+use super::Taffy;
+
+impl Taffy {
+    /// Rebuilds the storage of the tree so that its nodes are stored densely and in depth-first order, returning the
+    /// new id of every node keyed by its previous id
+    ///
+    /// Removing nodes leaves gaps in the storage, which are only filled as new nodes are created. Trees that have
+    /// had many nodes removed can be compacted to improve the locality of layout computations and of [`Taffy::iter`].
+    ///
+    /// Every node gets a new id: ids obtained before compacting must be translated with the returned map, as they may
+    /// identify a different node afterwards. Layouts that have already been published to a
+    /// [`LayoutReader`](crate::tree::LayoutReader) keep their previous ids. Memoized subtree layouts, the allocations
+    /// kept in the [node pool](Taffy::set_node_pool_capacity) and the lists of flex items kept for reuse are discarded.
+    pub fn compact(&mut self) -> BTreeMap<NodeId, NodeId> {
+        // Nodes are stored in depth-first order, starting from the roots of the tree in the order they are stored in
+        let mut order: Vec<DefaultKey> = Vec::with_capacity(self.nodes.len());
+        let mut visited: SecondaryMap<DefaultKey, ()> = SecondaryMap::with_capacity(self.nodes.len());
+        let mut stack: Vec<DefaultKey> = Vec::new();
+        let roots: Vec<DefaultKey> =
+            self.parents.iter().filter(|(_, parent)| parent.is_none()).map(|(key, _)| key).collect();
+        let all_keys: Vec<DefaultKey> = self.nodes.keys().collect();
+        for root in roots.into_iter().chain(all_keys) {
+            stack.push(root);
+            while let Some(key) = stack.pop() {
+                if visited.insert(key, ()).is_some() {
+                    continue;
+                }
+                order.push(key);
+                stack.extend(self.children[key].iter().rev().map(|child| DefaultKey::from(*child)));
+            }
+        }
+
+        let mut nodes = SlotMap::with_capacity(order.len());
+        let mut children = SlotMap::with_capacity(order.len());
+        let mut parents = SlotMap::with_capacity(order.len());
+        let mut new_keys: SecondaryMap<DefaultKey, DefaultKey> = SecondaryMap::with_capacity(order.len());
+        for old_key in order.iter().copied() {
+            // Nodes are inserted into each map in the same order, so that they are given the same key in each of them
+            let new_key = nodes.insert(self.nodes.remove(old_key).unwrap());
+            new_keys.insert(old_key, new_key);
+        }
+        let new_id = |id: NodeId| new_keys.get(id.into()).map(|key| NodeId::from(*key));
+        for old_key in order.iter().copied() {
+            let mut node_children = self.children.remove(old_key).unwrap();
+            for child in node_children.iter_mut() {
+                *child = new_id(*child).unwrap();
+            }
+            let _ = children.insert(node_children);
+            let _ = parents.insert(self.parents.remove(old_key).unwrap().and_then(new_id));
+        }
+        self.nodes = nodes;
+        self.children = children;
+        self.parents = parents;
+
+        remap_keys(&mut self.measure_funcs, &new_keys);
+        remap_keys(&mut self.resolved_styles, &new_keys);
+        #[cfg(feature = "grid")]
+        remap_keys(&mut self.auto_repetitions, &new_keys);
+        remap_keys(&mut self.font_sizes, &new_keys);
+        remap_keys(&mut self.replaced_contents, &new_keys);
+        remap_keys(&mut self.skipped_layouts, &new_keys);
+        remap_keys(&mut self.scroll_offsets, &new_keys);
+        remap_keys(&mut self.keys, &new_keys);
+        remap_keys(&mut self.node_classes, &new_keys);
+        remap_keys(&mut self.virtual_lists, &new_keys);
+        #[cfg(feature = "std")]
+        remap_keys(&mut self.content_hashes, &new_keys);
+        if let Some(damage) = &mut self.damage {
+            remap_keys(&mut damage.painted, &new_keys);
+            for painted in damage.painted.values_mut() {
+                if let Some(root) = new_id(painted.root) {
+                    painted.root = root;
+                }
+            }
+        }
+        for node in self.nodes_by_key.values_mut() {
+            *node = new_id(*node).unwrap();
+        }
+
+        self.changed_layouts = self.changed_layouts.iter().filter_map(|node| new_id(*node)).collect();
+        self.interrupted_layouts =
+            self.interrupted_layouts.iter().filter_map(|(node, layout)| Some((new_id(*node)?, *layout))).collect();
+        if let Some(pending_dirty) = &mut self.pending_dirty {
+            *pending_dirty = pending_dirty.iter().filter_map(|(node, level)| Some((new_id(*node)?, *level))).collect();
+        }
+        if let Some(explanation) = &mut self.explanation {
+            match new_id(explanation.node) {
+                Some(node) => explanation.node = node,
+                None => self.explanation = None,
+            }
+        }
+        if let Some(measure_recording) = &mut self.measure_recording {
+            *measure_recording = measure_recording
+                .iter()
+                .filter_map(|(node, measurement)| Some((new_id(*node)?, *measurement)))
+                .collect();
+        }
+
+        // The memoized subtree layouts and the pooled allocations are released along with the rest of the storage
+        // freed by compacting, rather than carried over to the rebuilt storage
+        self.subtree_memo.clear();
+        self.node_pool.clear();
+        #[cfg(feature = "flexbox")]
+        {
+            self.flex_item_buffers = Vec::new();
+        }
+
+        order.iter().map(|old_key| (NodeId::from(*old_key), NodeId::from(new_keys[*old_key]))).collect()
+    }
+}
+
+/// Moves the values of `map` from the previous keys of the nodes to their new keys
+fn remap_keys<T>(map: &mut SparseSecondaryMap<DefaultKey, T>, new_keys: &SecondaryMap<DefaultKey, DefaultKey>) {
+    let previous = core::mem::take(map);
+    for (old_key, value) in previous {
+        if let Some(new_key) = new_keys.get(old_key) {
+            map.insert(*new_key, value);
+        }
+    }
+}
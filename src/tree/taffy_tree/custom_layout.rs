@@ -0,0 +1,48 @@
+//! Application-defined layout algorithms, which lay out the children of nodes with [`Display::Custom`]
+use crate::compute::taffy_tree::perform_custom_layout;
+use crate::compute::LayoutAlgorithm;
+use crate::style::Display;
+use crate::tree::NodeId;
+use crate::util::sys::Vec;
+
+use super::Taffy;
+
+impl Taffy {
+    /// Registers `Algorithm` as the layout algorithm of nodes whose display is [`Display::Custom`] with the given key,
+    /// replacing any algorithm previously registered with that key
+    ///
+    /// The algorithm lays out the children of those nodes through the [`LayoutTree`](crate::tree::LayoutTree) it is
+    /// passed, in the same way as the built-in algorithms do. Children are laid out with the algorithm of their own
+    /// display mode, so standard flexbox or grid containers can be nested inside custom containers, and the results
+    /// of custom containers are cached like those of any other node.
+    ///
+    /// Every node using the key is marked dirty, so that it is laid out with the new algorithm.
+    pub fn register_layout_algorithm<Algorithm: LayoutAlgorithm>(&mut self, key: u16) {
+        self.layout_algorithms.insert(key, perform_custom_layout::<Algorithm>);
+        self.mark_custom_layouts_dirty(key);
+    }
+
+    /// Removes the layout algorithm registered with the given key, returning whether there was one
+    ///
+    /// Nodes using the key are laid out as if they had [`Display::None`] until another algorithm is registered.
+    pub fn unregister_layout_algorithm(&mut self, key: u16) -> bool {
+        let removed = self.layout_algorithms.remove(&key).is_some();
+        if removed {
+            self.mark_custom_layouts_dirty(key);
+        }
+        removed
+    }
+
+    /// Marks every node whose display is [`Display::Custom`] with the given key as dirty
+    fn mark_custom_layouts_dirty(&mut self, key: u16) {
+        let nodes: Vec<NodeId> = self
+            .nodes
+            .iter()
+            .filter(|(_, data)| data.style.display == Display::Custom(key))
+            .map(|(node, _)| node.into())
+            .collect();
+        for node in nodes {
+            let _ = self.mark_dirty(node);
+        }
+    }
+}
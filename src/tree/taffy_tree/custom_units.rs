@@ -0,0 +1,31 @@
+//! Application-defined units, which are resolved into lengths by a user-provided resolver
+use crate::tree::NodeId;
+use crate::util::sys::Box;
+
+use super::Taffy;
+
+/// Resolves application-defined units (see [`Dimension::Custom`](crate::style::Dimension::Custom)) into lengths
+///
+/// Custom units are resolved at the start of each layout computation, for each node whose style uses them. The
+/// resolver is passed the tree, so units may depend on other nodes (e.g. a percentage of a sibling's size). Note that
+/// the layouts in the tree are those from the previous layout computation at that point.
+pub trait CustomUnitResolver: Send + Sync {
+    /// Returns the length that the custom unit with id `unit` resolves to in the style of `node`
+    fn resolve(&self, unit: u32, node: NodeId, taffy: &Taffy) -> f32;
+}
+
+impl<F: Fn(u32, NodeId, &Taffy) -> f32 + Send + Sync> CustomUnitResolver for F {
+    fn resolve(&self, unit: u32, node: NodeId, taffy: &Taffy) -> f32 {
+        self(unit, node, taffy)
+    }
+}
+
+impl Taffy {
+    /// Sets the [`CustomUnitResolver`] that custom units are resolved with, or removes it if `resolver` is `None`
+    ///
+    /// Custom units resolve to zero while no resolver is set. Every node whose style uses custom units is resolved again
+    /// during the next layout computation, and relaid out if the resolved lengths changed.
+    pub fn set_custom_unit_resolver(&mut self, resolver: Option<Box<dyn CustomUnitResolver>>) {
+        self.custom_unit_resolver = resolver;
+    }
+}
@@ -0,0 +1,181 @@
+//! Tracking the screen-space rectangles that changed between successive layout computations, so that renderers which
+//! repaint only part of the screen know what to redraw
+use slotmap::{DefaultKey, SparseSecondaryMap};
+
+use crate::geometry::{Point, Rect};
+use crate::tree::NodeId;
+use crate::util::sys::Vec;
+
+use super::Taffy;
+
+/// The largest number of rectangles that the damage of a layout computation is reported as, see [`Taffy::damage_rects`]
+pub const MAX_DAMAGE_RECTS: usize = 8;
+
+/// Returns the area of the rectangle bounded by `rect`
+fn area(rect: Rect<f32>) -> f32 {
+    (rect.right - rect.left) * (rect.bottom - rect.top)
+}
+
+/// A small set of rectangles that covers every rectangle added to it
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DamageRects {
+    /// The rectangles of the set, which don't overlap each other
+    rects: Vec<Rect<f32>>,
+}
+
+impl DamageRects {
+    /// Adds `rect` to the set, merging it with the rectangles it overlaps
+    ///
+    /// Once the set holds [`MAX_DAMAGE_RECTS`] rectangles, new rectangles are merged with the rectangle whose area grows
+    /// the least by doing so.
+    fn add(&mut self, mut rect: Rect<f32>) {
+        if !(rect.left < rect.right && rect.top < rect.bottom) {
+            return;
+        }
+
+        loop {
+            let merged = match self.rects.iter().position(|existing| existing.intersection(rect).is_some()) {
+                Some(index) => index,
+                None if self.rects.len() < MAX_DAMAGE_RECTS => break,
+                None => {
+                    let growth = |existing: &Rect<f32>| area(existing.union(rect)) - area(*existing);
+                    let mut cheapest = 0;
+                    for (index, existing) in self.rects.iter().enumerate() {
+                        if growth(existing) < growth(&self.rects[cheapest]) {
+                            cheapest = index;
+                        }
+                    }
+                    cheapest
+                }
+            };
+            // The merged rectangle may now overlap other rectangles of the set, so it is checked again
+            rect = rect.union(self.rects.swap_remove(merged));
+        }
+        self.rects.push(rect);
+    }
+}
+
+/// The rectangle that a node covered at the end of the last layout computation that included it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PaintedRect {
+    /// The root of the layout computation
+    pub(crate) root: NodeId,
+    /// The rectangle covered by the node, relative to the top-left corner of `root`
+    rect: Rect<f32>,
+    /// The layout computation that the rectangle was recorded by
+    pass: u32,
+}
+
+/// The state kept by a tree once [`Taffy::enable_damage_tracking`] has been called
+#[derive(Debug, Default)]
+pub(crate) struct DamageTracker {
+    /// The rectangle that each node covered at the end of the last layout computation that included it
+    pub(crate) painted: SparseSecondaryMap<DefaultKey, PaintedRect>,
+    /// The damage caused by removing nodes since the most recent layout computation
+    pending: DamageRects,
+    /// The damage of the most recent layout computation
+    damage: DamageRects,
+    /// The number of layout computations that damage was recorded for, which identifies the rectangles they recorded
+    pass: u32,
+}
+
+impl DamageTracker {
+    /// Damages the rectangle covered by a node that is being removed from the tree
+    pub(crate) fn remove_node(&mut self, key: DefaultKey) {
+        if let Some(painted) = self.painted.remove(key) {
+            self.pending.add(painted.rect);
+        }
+    }
+
+    /// Damages the rectangles covered by every node, as the tree is being cleared
+    pub(crate) fn remove_all_nodes(&mut self) {
+        for (_, painted) in self.painted.drain() {
+            self.pending.add(painted.rect);
+        }
+    }
+}
+
+impl Taffy {
+    /// Enable tracking of the rectangles that change between layout computations. Damage tracking is disabled by
+    /// default.
+    ///
+    /// While enabled, each call to [`Taffy::compute_layout`] compares the rectangle covered by each node with the one
+    /// it covered at the end of the previous computation, and reports the rectangles that need to be repainted through
+    /// [`Taffy::damage_rects`]. The first computation after enabling tracking damages every node.
+    pub fn enable_damage_tracking(&mut self) {
+        if self.damage.is_none() {
+            self.damage = Some(DamageTracker::default());
+        }
+    }
+
+    /// Disable tracking of the rectangles that change between layout computations. Damage tracking is disabled by
+    /// default.
+    pub fn disable_damage_tracking(&mut self) {
+        self.damage = None;
+    }
+
+    /// Returns the rectangles that changed during the most recent call to [`Taffy::compute_layout`], relative to the
+    /// top-left corner of its root, if damage tracking is enabled (see [`Taffy::enable_damage_tracking`])
+    ///
+    /// The area previously covered by a node and the area it now covers are both damaged when its absolute position or
+    /// size changes, as are the areas of nodes that were added, and of nodes that were removed or detached from the
+    /// root since the previous computation. Rather than listing each of these, overlapping rectangles are merged, and
+    /// at most [`MAX_DAMAGE_RECTS`] rectangles are returned. The returned rectangles don't overlap each other.
+    ///
+    /// Like other exported coordinates, y increases downwards whichever
+    /// [`CoordinateOrigin`](crate::tree::CoordinateOrigin) the tree uses, and scroll offsets aren't taken into account.
+    /// Only changes in layout are tracked: nodes whose content changed without affecting their layout have to be
+    /// repainted separately.
+    pub fn damage_rects(&self) -> &[Rect<f32>] {
+        self.damage.as_ref().map_or(&[], |tracker| &tracker.damage.rects)
+    }
+
+    /// Returns the smallest rectangle containing all of the [`Taffy::damage_rects`], or `None` if nothing changed
+    pub fn damage_bounds(&self) -> Option<Rect<f32>> {
+        self.damage_rects().iter().copied().reduce(|bounds, rect| bounds.union(rect))
+    }
+
+    /// Records the damage of a completed layout computation of `root`, if damage tracking is enabled
+    ///
+    /// This must be called while the locations of the layouts are in the top-left coordinate convention.
+    pub(crate) fn record_damage(&mut self, root: NodeId) {
+        let Some(tracker) = &mut self.damage else {
+            return;
+        };
+        tracker.pass = tracker.pass.wrapping_add(1);
+        let pass = tracker.pass;
+        let mut damage = core::mem::take(&mut tracker.pending);
+
+        // Each entry holds a node along with the absolute position of its parent
+        let mut stack: Vec<(NodeId, Point<f32>)> = Vec::new();
+        stack.push((root, Point::ZERO));
+        while let Some((node, parent_position)) = stack.pop() {
+            let layout = &self.nodes[node.into()].layout;
+            let position = match node == root {
+                true => Point::ZERO,
+                false => Point { x: parent_position.x + layout.location.x, y: parent_position.y + layout.location.y },
+            };
+            let rect = Rect::from((position, layout.size));
+            match tracker.painted.insert(node.into(), PaintedRect { root, rect, pass }) {
+                Some(previous) if previous.rect == rect => {}
+                Some(previous) => {
+                    damage.add(previous.rect);
+                    damage.add(rect);
+                }
+                None => damage.add(rect),
+            }
+            stack.extend(self.children[node.into()].iter().rev().map(|child| (*child, position)));
+        }
+
+        // Nodes that were laid out from this root during the previous computation but weren't this time have been
+        // detached from it
+        tracker.painted.retain(|_, painted| {
+            let detached = painted.root == root && painted.pass != pass;
+            if detached {
+                damage.add(painted.rect);
+            }
+            !detached
+        });
+        tracker.damage = damage;
+    }
+}
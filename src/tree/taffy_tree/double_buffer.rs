@@ -0,0 +1,83 @@
+//! Double-buffered layout results, which can be read from another thread while the next layout is computed
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::tree::NodeId;
+
+use super::{LayoutMap, LayoutSnapshot, Taffy};
+
+/// The slot that the most recently published layouts are stored in, shared between the tree and its readers
+type FrontBuffer = Arc<Mutex<Option<Arc<LayoutSnapshot>>>>;
+
+/// The buffers that a [`Taffy`] tree publishes its computed layouts to
+#[derive(Debug)]
+pub(crate) struct LayoutBuffers {
+    /// The most recently published layouts
+    front: FrontBuffer,
+    /// The previously published layouts, whose memory is reused for the next layouts once no reader holds them
+    back: Option<Arc<LayoutSnapshot>>,
+}
+
+/// A handle for reading the layouts published by a [`Taffy`] tree, which may be sent to another thread
+///
+/// Obtained from [`Taffy::layout_reader`]. Each call to [`Taffy::compute_layout`] that completes publishes the
+/// layouts of the laid out subtree as a new [`LayoutSnapshot`], replacing the previous one in a single step. A render
+/// thread can therefore keep reading one frame's layouts while the next frame's are being computed.
+#[derive(Debug, Clone)]
+pub struct LayoutReader {
+    /// The most recently published layouts
+    front: FrontBuffer,
+}
+
+impl LayoutReader {
+    /// Returns the most recently published layouts, or `None` if no layouts have been published yet
+    ///
+    /// The returned snapshot is unaffected by later layout computations. Dropping it promptly allows its memory to be
+    /// reused for the layouts published after the next one.
+    pub fn latest(&self) -> Option<Arc<LayoutSnapshot>> {
+        self.front.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Returns the generation of the most recently published layouts, which increases with every publication, or `0`
+    /// if no layouts have been published yet
+    pub fn generation(&self) -> u64 {
+        self.front.lock().unwrap_or_else(PoisonError::into_inner).as_ref().map_or(0, |snapshot| snapshot.generation)
+    }
+}
+
+impl Taffy {
+    /// Returns a [`LayoutReader`] that the layouts computed by [`Taffy::compute_layout`] are published to
+    ///
+    /// Layouts are only published once this has been called, and every reader returned by this method reads the same
+    /// publications.
+    pub fn layout_reader(&mut self) -> LayoutReader {
+        let buffers =
+            self.layout_buffers.get_or_insert_with(|| LayoutBuffers { front: Arc::new(Mutex::new(None)), back: None });
+        LayoutReader { front: Arc::clone(&buffers.front) }
+    }
+
+    /// Publishes the layouts of `root` and its descendants to the readers returned by [`Taffy::layout_reader`], if any
+    pub(crate) fn publish_layouts(&mut self, root: NodeId) {
+        let buffers = match &mut self.layout_buffers {
+            Some(buffers) => buffers,
+            None => return,
+        };
+
+        // Reuse the memory of the back buffer if no reader still holds it
+        let mut layouts =
+            buffers.back.take().and_then(|back| Arc::try_unwrap(back).ok()).map_or_else(LayoutMap::new, |mut back| {
+                back.layouts.clear();
+                back.layouts
+            });
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            layouts.insert(node, self.nodes[node.into()].layout);
+            stack.extend(self.children[node.into()].iter().copied());
+        }
+
+        let mut front = buffers.front.lock().unwrap_or_else(PoisonError::into_inner);
+        let generation = front.as_ref().map_or(0, |snapshot| snapshot.generation) + 1;
+        let previous = front.replace(Arc::new(LayoutSnapshot { generation, root, layouts }));
+        drop(front);
+        buffers.back = previous;
+    }
+}
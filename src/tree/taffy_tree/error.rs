@@ -2,7 +2,17 @@
 #[cfg(feature = "std")]
 use core::fmt::{Display, Formatter, Result};
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::geometry::Size;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::style::AvailableSpace;
 use crate::tree::NodeId;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::util::sys::String;
+use crate::util::sys::Vec;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::Taffy;
 
 /// The error Taffy generates on invalid operations
 pub type TaffyResult<T> = core::result::Result<T, TaffyError>;
@@ -25,6 +35,84 @@ pub enum TaffyError {
     InvalidChildNode(NodeId),
     /// The supplied node was not found in the [`Taffy`](crate::Taffy) instance.
     InvalidInputNode(NodeId),
+    /// The tree being laid out is deeper than the maximum depth configured with
+    /// [`Taffy::set_max_depth`](crate::Taffy::set_max_depth). This usually indicates that the tree contains a cycle.
+    MaxDepthExceeded {
+        /// The configured maximum depth
+        max_depth: usize,
+        /// The path from the root node of the layout to the first node found beyond the maximum depth
+        path: Vec<NodeId>,
+    },
+    /// The key is already assigned to another node in the [`Taffy`](crate::Taffy) instance.
+    DuplicateKey {
+        /// The node that the key is currently assigned to
+        node: NodeId,
+    },
+}
+
+impl TaffyError {
+    /// The node that the error concerns: the parent node for [`TaffyError::ChildIndexOutOfBounds`], the last node of
+    /// the path for [`TaffyError::MaxDepthExceeded`] and the node that already has the key for [`TaffyError::DuplicateKey`]
+    pub fn node(&self) -> Option<NodeId> {
+        match self {
+            TaffyError::ChildIndexOutOfBounds { parent, .. } => Some(*parent),
+            TaffyError::InvalidParentNode(node)
+            | TaffyError::InvalidChildNode(node)
+            | TaffyError::InvalidInputNode(node) => Some(*node),
+            TaffyError::MaxDepthExceeded { path, .. } => path.last().copied(),
+            TaffyError::DuplicateKey { node } => Some(*node),
+        }
+    }
+}
+
+/// A [`TaffyError`] along with the location in the tree of the node that it concerns, returned by
+/// [`Taffy::compute_layout_with_context`] and [`Taffy::error_context`]
+///
+/// This makes errors from large trees actionable: the node is identified by its debug label, which is its key (see
+/// [`Taffy::set_key`]) if it has one, and by its path from the root of the tree.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug)]
+pub struct ContextualError {
+    /// The error itself
+    pub error: TaffyError,
+    /// The node that the error concerns, if any (see [`TaffyError::node`])
+    pub node: Option<NodeId>,
+    /// The debug label of the node: the key assigned to it, if any
+    pub label: Option<String>,
+    /// The path from the root of the tree to the node (including the node itself), along with the label of each node on
+    /// the path. Empty if the node isn't in the tree.
+    pub path: Vec<(NodeId, Option<String>)>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Taffy {
+    /// Attaches the location in the tree of the node that `error` concerns to the error
+    pub fn error_context(&self, error: TaffyError) -> ContextualError {
+        let node = error.node();
+        let label = node.and_then(|node| self.key(node)).map(String::from);
+
+        let mut path = Vec::new();
+        let mut current = node.filter(|node| self.nodes.contains_key((*node).into()));
+        while let Some(node) = current {
+            path.push((node, self.key(node).map(String::from)));
+            current = self.parents[node.into()];
+        }
+        path.reverse();
+
+        ContextualError { error, node, label, path }
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, see [`Taffy::compute_layout`]
+    ///
+    /// Errors are returned along with the location in the tree of the node that they concern (see
+    /// [`Taffy::error_context`]), which locates the offending subtree of a deep tree for example.
+    pub fn compute_layout_with_context(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+    ) -> core::result::Result<(), ContextualError> {
+        self.compute_layout(node, available_space).map_err(|error| self.error_context(error))
+    }
 }
 
 #[cfg(feature = "std")]
@@ -39,9 +127,43 @@ impl Display for TaffyError {
             }
             TaffyError::InvalidChildNode(child) => write!(f, "Child Node {child:?} is not in the Taffy instance"),
             TaffyError::InvalidInputNode(node) => write!(f, "Supplied Node {node:?} is not in the Taffy instance"),
+            TaffyError::MaxDepthExceeded { max_depth, path } => {
+                write!(f, "Maximum tree depth ({max_depth}) exceeded by node path {path:?}")
+            }
+            TaffyError::DuplicateKey { node } => write!(f, "Key is already assigned to Node {node:?}"),
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for TaffyError {}
+
+#[cfg(feature = "std")]
+impl Display for ContextualError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.error)?;
+        if let Some(label) = &self.label {
+            write!(f, " (node \"{label}\")")?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " at ")?;
+            for (index, (node, label)) in self.path.iter().enumerate() {
+                if index > 0 {
+                    write!(f, " > ")?;
+                }
+                match label {
+                    Some(label) => write!(f, "\"{label}\"")?,
+                    None => write!(f, "{node:?}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ContextualError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
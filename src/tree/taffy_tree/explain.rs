@@ -0,0 +1,136 @@
+//! Recording which constraints determined the size of a chosen node
+use crate::geometry::{AbsoluteAxis, Size};
+use crate::tree::{NodeId, SizeConstraint};
+use crate::util::MaybeResolve;
+
+use super::{Taffy, TaffyResult};
+
+/// The node chosen with [`Taffy::explain_node`], and what has been recorded about it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Explanation {
+    /// The node whose size is explained
+    pub(crate) node: NodeId,
+    /// The constraints imposed by the node's parent ahead of its final layout, in each axis
+    pub(crate) parent_constraints: Size<Option<SizeConstraint>>,
+    /// The constraints that determined the size of the node in its most recent layout, if it has been laid out
+    pub(crate) constraints: Option<Size<SizeConstraint>>,
+}
+
+impl Taffy {
+    /// Chooses the node whose size is explained by [`Taffy::size_explanation`], or stops explaining sizes if `None`
+    ///
+    /// While a node is chosen, each layout computation records which constraint determined the final size of the node in
+    /// each axis: its size style, a min or max clamp, flexing, stretching, the grid area it is placed in, or the result
+    /// of measuring it. The node is marked dirty so that it is laid out, and explained, by the next computation.
+    pub fn explain_node(&mut self, node: Option<NodeId>) -> TaffyResult<()> {
+        self.explanation = node.map(|node| Explanation {
+            node,
+            parent_constraints: Size { width: None, height: None },
+            constraints: None,
+        });
+        match node {
+            Some(node) => self.mark_dirty(node),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the constraints that determined the width and height of the node chosen with [`Taffy::explain_node`]
+    ///
+    /// Returns `None` if no node has been chosen, or if it hasn't been laid out since it was chosen.
+    #[must_use]
+    pub fn size_explanation(&self) -> Option<Size<SizeConstraint>> {
+        self.explanation.and_then(|explanation| explanation.constraints)
+    }
+
+    /// Records a constraint imposed on the explained node by its parent, see
+    /// [`LayoutTree::record_size_constraint`](crate::tree::LayoutTree::record_size_constraint)
+    #[inline]
+    pub(crate) fn record_size_constraint(&mut self, node: NodeId, axis: AbsoluteAxis, constraint: SizeConstraint) {
+        if let Some(explanation) = &mut self.explanation {
+            if explanation.node == node {
+                match axis {
+                    AbsoluteAxis::Horizontal => explanation.parent_constraints.width = Some(constraint),
+                    AbsoluteAxis::Vertical => explanation.parent_constraints.height = Some(constraint),
+                }
+            }
+        }
+    }
+
+    /// Records the constraints that determined the `size` of the explained node, once its final layout is computed
+    ///
+    /// Constraints imposed by the parent take precedence. In axes where the parent imposed none, the size is compared
+    /// with the node's own size styles to find the one it was resolved from.
+    pub(crate) fn explain_size(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        size: Size<f32>,
+    ) {
+        let Some(explanation) = self.explanation.filter(|explanation| explanation.node == node) else {
+            return;
+        };
+
+        let style = self.layout_style(node);
+        let style_size = style.size.maybe_resolve(parent_size);
+        let min_size = style.min_size.maybe_resolve(parent_size);
+        let max_size = style.max_size.maybe_resolve(parent_size);
+        let aspect_ratio_size = Size {
+            width: style.aspect_ratio.and_then(|ratio| style_size.height.map(|height| height * ratio)),
+            height: style.aspect_ratio.and_then(|ratio| style_size.width.map(|width| width / ratio)),
+        };
+        let needs_measure = self.nodes[node.into()].needs_measure;
+
+        let own_constraint = |size: f32,
+                              known: Option<f32>,
+                              style_size: Option<f32>,
+                              min_size: Option<f32>,
+                              max_size: Option<f32>,
+                              aspect_ratio_size: Option<f32>| {
+            if style_size == Some(size) {
+                SizeConstraint::StyleSize
+            } else if min_size == Some(size) {
+                SizeConstraint::MinSize
+            } else if max_size == Some(size) {
+                SizeConstraint::MaxSize
+            } else if aspect_ratio_size == Some(size) {
+                SizeConstraint::AspectRatio
+            } else if known.is_some() {
+                SizeConstraint::Parent
+            } else if needs_measure {
+                SizeConstraint::Measured
+            } else {
+                SizeConstraint::Content
+            }
+        };
+
+        let constraints = Size {
+            width: explanation.parent_constraints.width.unwrap_or_else(|| {
+                own_constraint(
+                    size.width,
+                    known_dimensions.width,
+                    style_size.width,
+                    min_size.width,
+                    max_size.width,
+                    aspect_ratio_size.width,
+                )
+            }),
+            height: explanation.parent_constraints.height.unwrap_or_else(|| {
+                own_constraint(
+                    size.height,
+                    known_dimensions.height,
+                    style_size.height,
+                    min_size.height,
+                    max_size.height,
+                    aspect_ratio_size.height,
+                )
+            }),
+        };
+
+        self.explanation = Some(Explanation {
+            node,
+            parent_constraints: Size { width: None, height: None },
+            constraints: Some(constraints),
+        });
+    }
+}
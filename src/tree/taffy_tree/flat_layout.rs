@@ -0,0 +1,76 @@
+//! Export of computed layouts to a flat array of rectangles, which renderers can upload to the GPU as is
+use crate::tree::NodeId;
+use crate::util::sys::Vec;
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// The layout of a node in a buffer filled by [`Taffy::write_flat_layout`]
+///
+/// Entries are `#[repr(C)]` and made up of eight 4 byte fields without any padding, so that a buffer of entries can
+/// be uploaded as an instance or storage buffer without any conversion (for example by casting it with `bytemuck`).
+/// Their 32 byte size also satisfies the alignment rules of `std430` storage buffers.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FlatLayout {
+    /// The x coordinate of the node's top-left corner, relative to the top-left of the root
+    pub x: f32,
+    /// The y coordinate of the node's top-left corner, relative to the top-left of the root
+    pub y: f32,
+    /// The width of the node
+    pub width: f32,
+    /// The height of the node
+    pub height: f32,
+    /// The index of the node in the tree, which is the index of its slot and is stable for the lifetime of the node
+    ///
+    /// Slots are reused once their node is removed, so after nodes are removed an index can refer to a different node.
+    pub node: u32,
+    /// The index of the entry of the node's parent in the buffer, or `u32::MAX` for the root
+    pub parent: u32,
+    /// The relative ordering of the node among its siblings, see [`Layout::order`](crate::tree::Layout::order)
+    pub order: u32,
+    /// The number of ancestors of the node below the root, so 0 for the root
+    pub depth: u32,
+}
+
+impl FlatLayout {
+    /// The index that [`FlatLayout::parent`] is set to for the root of the buffer
+    pub const NO_PARENT: u32 = u32::MAX;
+
+    /// Returns true if the entry is that of the root of the buffer
+    pub fn is_root(&self) -> bool {
+        self.parent == Self::NO_PARENT
+    }
+}
+
+impl Taffy {
+    /// Fills `buffer` with the computed layouts of `root` and its descendants, in depth-first order starting with `root`
+    ///
+    /// The order only depends on the structure of the tree, so it is the same after every layout computation as long
+    /// as no nodes were added, removed or moved. The buffer is cleared first and its allocation is reused, so that
+    /// calling this after every layout computation doesn't allocate once the buffer has grown to the size of the tree.
+    /// This moves the tree walk that renderers would otherwise do every frame into a single tight loop.
+    ///
+    /// Coordinates are absolute, and y increases downwards whichever
+    /// [`CoordinateOrigin`](crate::tree::CoordinateOrigin) the tree uses. They don't take scroll offsets into account.
+    pub fn write_flat_layout(&self, root: NodeId, buffer: &mut Vec<FlatLayout>) -> TaffyResult<()> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+        buffer.clear();
+
+        self.walk_absolute_layouts(root, |visited| {
+            buffer.push(FlatLayout {
+                x: visited.position.x,
+                y: visited.position.y,
+                width: visited.layout.size.width,
+                height: visited.layout.size.height,
+                // The low 32 bits of a node id are the index of its slot
+                node: u64::from(visited.node) as u32,
+                parent: visited.parent_index,
+                order: visited.layout.order,
+                depth: visited.depth,
+            });
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Directional focus navigation between nodes based on their computed geometry, for gamepad and keyboard driven UIs
+use crate::geometry::{Point, Rect};
+use crate::tree::NodeId;
+use crate::util::sys::{abs, f32_max, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// How much further away a candidate offset sideways from the focused node is considered than one in line with it, per
+/// unit of offset. Favors moving along rows and columns over jumping diagonally to a slightly closer node.
+const ORTHOGONAL_WEIGHT: f32 = 2.0;
+
+/// The direction in which [`Taffy::focus_neighbor`] moves focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FocusDirection {
+    /// Towards the top of the screen
+    Up,
+    /// Towards the bottom of the screen
+    Down,
+    /// Towards the left of the screen
+    Left,
+    /// Towards the right of the screen
+    Right,
+}
+
+impl FocusDirection {
+    /// Returns how well `candidate` suits as the next node when moving focus from `from` in this direction, lower being
+    /// better, or `None` if it isn't in this direction
+    ///
+    /// The score is the distance from the edge of `from` that focus leaves through to the facing edge of `candidate`,
+    /// plus the weighted distance between their extents in the other axis (zero if they overlap in it). Ties are broken
+    /// by the distance between their centers in the other axis.
+    fn score(self, from: Rect<f32>, candidate: Rect<f32>) -> Option<(f32, f32)> {
+        let (distance, from_extent, candidate_extent) = match self {
+            FocusDirection::Up => {
+                (from.top - candidate.bottom, (from.left, from.right), (candidate.left, candidate.right))
+            }
+            FocusDirection::Down => {
+                (candidate.top - from.bottom, (from.left, from.right), (candidate.left, candidate.right))
+            }
+            FocusDirection::Left => {
+                (from.left - candidate.right, (from.top, from.bottom), (candidate.top, candidate.bottom))
+            }
+            FocusDirection::Right => {
+                (candidate.left - from.right, (from.top, from.bottom), (candidate.top, candidate.bottom))
+            }
+        };
+        if distance < 0.0 {
+            return None;
+        }
+
+        let orthogonal_distance =
+            f32_max(0.0, f32_max(candidate_extent.0 - from_extent.1, from_extent.0 - candidate_extent.1));
+        let center_offset = abs((candidate_extent.0 + candidate_extent.1) - (from_extent.0 + from_extent.1)) / 2.0;
+        Some((distance + ORTHOGONAL_WEIGHT * orthogonal_distance, center_offset))
+    }
+}
+
+impl Taffy {
+    /// Returns the node that focus should move to from `current` when navigating in `direction`, chosen among the
+    /// descendants of `root` that have all of the given [`tags`](Taffy::set_tags) (such as a tag marking focusable
+    /// nodes), or `None` if there is none in that direction
+    ///
+    /// Candidates are compared by their computed layouts, after the contents of scroll containers have been offset by
+    /// their [scroll offset](Taffy::set_scroll_offset). Only nodes lying entirely beyond the edge of `current` that
+    /// focus leaves through are candidates, so nodes overlapping `current` (such as its ancestors and descendants) are
+    /// never chosen, and nodes with no area (such as those with [`Display::None`](crate::style::Display::None)) are
+    /// skipped. Of the remaining candidates, the closest is chosen, where a candidate offset sideways from `current`
+    /// counts as further away than one directly in line with it. Ties go to the candidate that comes first in tree
+    /// order. Candidates outside of the area that a scroll container clips its contents to are still considered, so
+    /// that focus can move to items that have been scrolled out of view.
+    ///
+    /// `current` doesn't need to be a descendant of `root` or have the given tags.
+    ///
+    /// ```
+    /// use taffy::prelude::*;
+    /// use taffy::tree::FocusDirection;
+    ///
+    /// const FOCUSABLE: u32 = 1 << 0;
+    ///
+    /// let mut taffy = Taffy::new();
+    /// let button = Style { size: Size::from_lengths(40.0, 20.0), ..Default::default() };
+    /// let buttons = [(); 3].map(|_| taffy.new_leaf(button.clone()).unwrap());
+    /// let row = Style { gap: length(10.0), ..Default::default() };
+    /// let root = taffy.new_with_children(row, &buttons).unwrap();
+    /// for button in buttons {
+    ///     taffy.set_tags(button, FOCUSABLE).unwrap();
+    /// }
+    /// taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    ///
+    /// assert_eq!(taffy.focus_neighbor(root, buttons[0], FocusDirection::Right, FOCUSABLE).unwrap(), Some(buttons[1]));
+    /// assert_eq!(taffy.focus_neighbor(root, buttons[0], FocusDirection::Left, FOCUSABLE).unwrap(), None);
+    /// ```
+    pub fn focus_neighbor(
+        &self,
+        root: NodeId,
+        current: NodeId,
+        direction: FocusDirection,
+        tags: u32,
+    ) -> TaffyResult<Option<NodeId>> {
+        if !self.nodes.contains_key(current.into()) {
+            return Err(TaffyError::InvalidInputNode(current));
+        }
+        let root_location = self.absolute_location(root)?;
+        let current_location = self.absolute_location(current)?;
+        let current_location =
+            Point { x: current_location.x - root_location.x, y: current_location.y - root_location.y };
+        let from = Rect::from((current_location, self.nodes[current.into()].layout.size));
+
+        let mut best: Option<(NodeId, (f32, f32))> = None;
+        let mut stack = Vec::new();
+        self.push_focus_candidates(root, Point::ZERO, &mut stack);
+        while let Some((node, location)) = stack.pop() {
+            self.push_focus_candidates(node, location, &mut stack);
+
+            let data = &self.nodes[node.into()];
+            if node == current || data.tags & tags != tags || data.layout.size.width * data.layout.size.height <= 0.0 {
+                continue;
+            }
+            let Some(score) = direction.score(from, Rect::from((location, data.layout.size))) else {
+                continue;
+            };
+            if best.is_none_or(|(_, best_score)| score < best_score) {
+                best = Some((node, score));
+            }
+        }
+        Ok(best.map(|(node, _)| node))
+    }
+
+    /// Pushes the children of `node` onto `stack` along with their locations relative to the root of the search, such
+    /// that they are popped in tree order
+    fn push_focus_candidates(&self, node: NodeId, location: Point<f32>, stack: &mut Vec<(NodeId, Point<f32>)>) {
+        let scroll_offset = self.scroll_offset(node);
+        stack.extend(self.children[node.into()].iter().rev().map(|&child| {
+            let offset = self.top_left_location(child, node);
+            (child, Point { x: location.x + offset.x - scroll_offset.x, y: location.y + offset.y - scroll_offset.y })
+        }));
+    }
+}
@@ -0,0 +1,128 @@
+//! Speculative changes to a tree, which can be laid out and then either discarded or committed
+use slotmap::{DefaultKey, SecondaryMap};
+
+use crate::compute::taffy_tree::compute_detached_layout;
+use crate::geometry::Size;
+use crate::style::{AvailableSpace, Style};
+use crate::tree::{Layout, NodeId};
+
+use super::backup::NodeBackup;
+use super::{LayoutMap, Taffy, TaffyError, TaffyResult};
+
+/// A copy-on-write view of a [`Taffy`] tree, in which styles can be changed and layouts computed without affecting
+/// the tree
+///
+/// Created by [`Taffy::speculative_fork`]. Only the state of the nodes that are changed (and of their ancestors, whose
+/// cached results are invalidated) is copied, and layouts are computed into a separate [`LayoutMap`]. Once done, the
+/// fork is either [discarded](SpeculativeFork::discard), restoring the tree to exactly its previous state, or
+/// [committed](SpeculativeFork::commit). Dropping the fork discards it.
+pub struct SpeculativeFork<'a> {
+    /// The tree that the fork was created from
+    taffy: &'a mut Taffy,
+    /// The state of each node before it was first changed by the fork
+    saved: SecondaryMap<DefaultKey, NodeBackup>,
+    /// The layouts computed in the fork
+    layouts: LayoutMap,
+    /// Whether the fork has been committed, in which case the tree isn't restored when the fork is dropped
+    committed: bool,
+}
+
+impl core::fmt::Debug for SpeculativeFork<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SpeculativeFork").field("changed_nodes", &self.saved.len()).finish_non_exhaustive()
+    }
+}
+
+impl Taffy {
+    /// Creates a [`SpeculativeFork`] of the tree, in which styles can be changed and layouts computed without
+    /// affecting the tree unless the fork is committed
+    ///
+    /// This is useful for animation previews and "would this fit?" checks. Rather than cloning the tree, the fork saves
+    /// the state of the nodes whose style it changes, and layouts computed in the fork are computed on a separate copy
+    /// of the layouts and caches of the subtree being laid out.
+    pub fn speculative_fork(&mut self) -> SpeculativeFork<'_> {
+        SpeculativeFork { taffy: self, saved: SecondaryMap::new(), layouts: LayoutMap::new(), committed: false }
+    }
+}
+
+impl SpeculativeFork<'_> {
+    /// Gets the [`Style`] of the provided `node` in the fork
+    pub fn style(&self, node: NodeId) -> TaffyResult<&Style> {
+        self.taffy.style(node)
+    }
+
+    /// Sets the [`Style`] of the provided `node` in the fork
+    pub fn set_style(&mut self, node: NodeId, style: Style) -> TaffyResult<()> {
+        // Setting the style invalidates the cached results of the node and its ancestors
+        let mut current = Some(node);
+        while let Some(ancestor) = current {
+            self.save(ancestor)?;
+            current = self.taffy.parents[ancestor.into()];
+        }
+        self.saved[node.into()].save_style(self.taffy, node.into());
+
+        self.taffy.set_style(node, style)
+    }
+
+    /// Computes the layout of the provided `node` and its descendants in the fork
+    ///
+    /// The layouts can be read with [`SpeculativeFork::layout`], and replace any layouts previously computed in the fork.
+    pub fn compute_layout(&mut self, node: NodeId, available_space: Size<AvailableSpace>) -> TaffyResult<()> {
+        self.layouts = compute_detached_layout(self.taffy, node, available_space)?;
+        Ok(())
+    }
+
+    /// Returns the layout of the provided `node` in the fork
+    ///
+    /// This is the layout computed by [`SpeculativeFork::compute_layout`] if the node was part of the computation, or
+    /// otherwise the node's layout in the tree.
+    pub fn layout(&self, node: NodeId) -> TaffyResult<&Layout> {
+        match self.layouts.get(node) {
+            Some(layout) => Ok(layout),
+            None => self.taffy.layout(node),
+        }
+    }
+
+    /// Keeps the changes made in the fork, writing the layouts computed in the fork into the tree
+    ///
+    /// Nodes whose style was changed remain dirty, so they are laid out again by the next call to
+    /// [`Taffy::compute_layout`].
+    pub fn commit(mut self) {
+        for (node, layout) in self.layouts.iter() {
+            if let Some(node_data) = self.taffy.nodes.get_mut(node.into()) {
+                node_data.layout = *layout;
+            }
+        }
+        self.committed = true;
+    }
+
+    /// Discards the changes made in the fork, restoring the tree to its state before the fork was created
+    pub fn discard(self) {
+        // The tree is restored when the fork is dropped
+    }
+
+    /// Saves the state of the node before it is changed, unless it has already been saved
+    fn save(&mut self, node: NodeId) -> TaffyResult<()> {
+        let key = node.into();
+        if self.saved.contains_key(key) {
+            return Ok(());
+        }
+        if !self.taffy.nodes.contains_key(key) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        self.saved.insert(key, NodeBackup::new(self.taffy, key));
+        Ok(())
+    }
+}
+
+impl Drop for SpeculativeFork<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+
+        for (key, saved) in self.saved.drain() {
+            saved.restore(self.taffy, key);
+        }
+    }
+}
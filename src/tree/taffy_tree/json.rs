@@ -0,0 +1,141 @@
+//! Export of computed layouts to JSON, for use by external inspection tools and in bug reports
+use core::fmt::Write;
+
+use crate::style::{Dimension, Display, Position};
+use crate::tree::NodeId;
+use crate::util::sys::{String, Vec};
+
+use super::Taffy;
+
+impl Taffy {
+    /// Serializes the computed layouts of `root` and its descendants to JSON
+    ///
+    /// The result is an object with the id of `root` and a `nodes` array listing every node of the subtree in
+    /// depth-first order. Each node is described by its id, its label (the key assigned with [`Taffy::set_key`], if
+    /// any), the id of its parent, a summary of its style (display, position and size) and its layout:
+    ///
+    /// ```json
+    /// {"root":1,"nodes":[{"id":1,"label":"root","parent":null,
+    ///   "style":{"display":"flex","position":"relative","width":"100px","height":"auto"},
+    ///   "layout":{"order":0,"x":0,"y":0,"width":100,"height":40}}]}
+    /// ```
+    ///
+    /// Node ids are the `u64` representation of each [`NodeId`]. Non-finite numbers are written as `null`.
+    pub fn layout_to_json(&self, root: NodeId) -> String {
+        let mut json = String::new();
+        // Writing to a `String` can't fail
+        let _ = self.write_layout_json(&mut json, root);
+        json
+    }
+
+    /// Writes the JSON representation of the layouts of `root` and its descendants to `json`
+    fn write_layout_json(&self, json: &mut String, root: NodeId) -> core::fmt::Result {
+        write!(json, "{{\"root\":{},\"nodes\":[", u64::from(root))?;
+
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(root);
+        let mut first = true;
+        while let Some(node) = stack.pop() {
+            if !first {
+                json.push(',');
+            }
+            first = false;
+
+            write!(json, "{{\"id\":{},\"label\":", u64::from(node))?;
+            match self.key(node) {
+                Some(label) => write_string(json, label)?,
+                None => json.push_str("null"),
+            }
+            json.push_str(",\"parent\":");
+            match self.parents[node.into()] {
+                Some(parent) => write!(json, "{}", u64::from(parent))?,
+                None => json.push_str("null"),
+            }
+
+            let style = &self.nodes[node.into()].style;
+            let position = match style.position {
+                Position::Relative => "relative",
+                Position::Absolute => "absolute",
+            };
+            json.push_str(",\"style\":{\"display\":");
+            write_display(json, style.display)?;
+            write!(json, ",\"position\":\"{position}\",\"width\":")?;
+            write_dimension(json, style.size.width)?;
+            json.push_str(",\"height\":");
+            write_dimension(json, style.size.height)?;
+
+            let layout = &self.nodes[node.into()].layout;
+            write!(json, "}},\"layout\":{{\"order\":{},\"x\":", layout.order)?;
+            write_number(json, layout.location.x)?;
+            json.push_str(",\"y\":");
+            write_number(json, layout.location.y)?;
+            json.push_str(",\"width\":");
+            write_number(json, layout.size.width)?;
+            json.push_str(",\"height\":");
+            write_number(json, layout.size.height)?;
+            json.push_str("}}");
+
+            // Children are pushed in reverse so that they are written in order
+            stack.extend(self.children[node.into()].iter().rev().copied());
+        }
+
+        json.push_str("]}");
+        Ok(())
+    }
+}
+
+/// Writes a number, or `null` if it isn't finite
+fn write_number(json: &mut String, number: f32) -> core::fmt::Result {
+    match number.is_finite() {
+        true => write!(json, "{number}"),
+        false => write!(json, "null"),
+    }
+}
+
+/// Writes a display mode as a CSS-like string, such as `"flex"` or `"custom(1)"` for custom layout algorithms
+fn write_display(json: &mut String, display: Display) -> core::fmt::Result {
+    match display {
+        #[cfg(feature = "block_layout")]
+        Display::Block => write!(json, "\"block\""),
+        #[cfg(feature = "flexbox")]
+        Display::Flex => write!(json, "\"flex\""),
+        #[cfg(feature = "grid")]
+        Display::Grid => write!(json, "\"grid\""),
+        Display::None => write!(json, "\"none\""),
+        Display::Custom(key) => write!(json, "\"custom({key})\""),
+    }
+}
+
+/// Writes a dimension as a CSS-like string, such as `"100px"`, `"50%"` or `"auto"`
+fn write_dimension(json: &mut String, dimension: Dimension) -> core::fmt::Result {
+    match dimension {
+        Dimension::Length(length) => write!(json, "\"{length}px\""),
+        Dimension::Percent(fraction) => write!(json, "\"{}%\"", fraction * 100.0),
+        Dimension::Vw(fraction) => write!(json, "\"{}vw\"", fraction * 100.0),
+        Dimension::Vh(fraction) => write!(json, "\"{}vh\"", fraction * 100.0),
+        Dimension::Vmin(fraction) => write!(json, "\"{}vmin\"", fraction * 100.0),
+        Dimension::Vmax(fraction) => write!(json, "\"{}vmax\"", fraction * 100.0),
+        Dimension::Rem(multiple) => write!(json, "\"{multiple}rem\""),
+        Dimension::Em(multiple) => write!(json, "\"{multiple}em\""),
+        Dimension::Custom(unit) => write!(json, "\"custom({unit})\""),
+        Dimension::Auto => write!(json, "\"auto\""),
+    }
+}
+
+/// Writes a string literal, escaping the characters that JSON requires to be escaped
+fn write_string(json: &mut String, string: &str) -> core::fmt::Result {
+    json.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            control if control.is_control() => write!(json, "\\u{:04x}", control as u32)?,
+            other => json.push(other),
+        }
+    }
+    json.push('"');
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! A map of computed layouts that is stored separately from the [`Taffy`](crate::Taffy) tree
+use slotmap::{DefaultKey, SecondaryMap};
+
+use crate::tree::{Layout, NodeId};
+
+/// A set of computed [`Layout`]s, keyed by [`NodeId`]
+///
+/// Returned by [`Taffy::compute_layout_detached`](crate::Taffy::compute_layout_detached), which computes layouts
+/// without writing them into the tree.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutMap {
+    /// The computed layout of each node
+    layouts: SecondaryMap<DefaultKey, Layout>,
+}
+
+impl LayoutMap {
+    /// Creates a new empty [`LayoutMap`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { layouts: SecondaryMap::new() }
+    }
+
+    /// Stores the layout of the provided `node`, replacing any existing layout for that node
+    pub(crate) fn insert(&mut self, node: NodeId, layout: Layout) {
+        self.layouts.insert(node.into(), layout);
+    }
+
+    /// Returns the layout of the provided `node` mutably, if it is in the map
+    pub(crate) fn get_mut(&mut self, node: NodeId) -> Option<&mut Layout> {
+        self.layouts.get_mut(node.into())
+    }
+
+    /// Removes every layout from the map, keeping the allocated memory
+    #[cfg(feature = "std")]
+    pub(crate) fn clear(&mut self) {
+        self.layouts.clear();
+    }
+
+    /// Returns the computed layout of the provided `node`, if it was part of the computation
+    pub fn get(&self, node: NodeId) -> Option<&Layout> {
+        self.layouts.get(node.into())
+    }
+
+    /// Returns true if the map contains a layout for the provided `node`
+    pub fn contains(&self, node: NodeId) -> bool {
+        self.layouts.contains_key(node.into())
+    }
+
+    /// Returns the number of layouts in the map
+    pub fn len(&self) -> usize {
+        self.layouts.len()
+    }
+
+    /// Returns true if the map contains no layouts
+    pub fn is_empty(&self) -> bool {
+        self.layouts.is_empty()
+    }
+
+    /// Returns an iterator over every node and its computed layout
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &Layout)> {
+        self.layouts.iter().map(|(key, layout)| (key.into(), layout))
+    }
+}
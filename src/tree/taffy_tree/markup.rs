@@ -0,0 +1,699 @@
+//! Building trees from a simple HTML-like markup format, which is convenient for tests and prototypes
+//!
+//! Each element becomes a node. The tag names are ignored, the `name` attribute makes the node available by name, and
+//! the `style` attribute contains CSS-like declarations that are applied to the node's [`Style`]:
+//!
+//! ```text
+//! <div name="root" style="display: flex; width: 200px; padding: 10px">
+//!   <div name="sidebar" style="width: 25%" />
+//!   <div name="content" style="flex-grow: 1; margin: 0 auto" />
+//! </div>
+//! ```
+//!
+//! Lengths may be written as plain numbers or with a `px` suffix, and percentages, `auto`, and the viewport and
+//! font-relative units (`vw`, `vh`, `vmin`, `vmax`, `rem` and `em`) are supported. Other attributes and `<!-- -->`
+//! comments are ignored.
+#[cfg(feature = "std")]
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+#[cfg(feature = "grid")]
+use crate::geometry::Line;
+use crate::geometry::{Point, Rect, Size};
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+use crate::style::{AlignContent, AlignItems};
+use crate::style::{
+    Contain, Dimension, Display as DisplayMode, LengthPercentage, LengthPercentageAuto, Overflow, Position, Style,
+};
+#[cfg(feature = "flexbox")]
+use crate::style::{FlexDirection, FlexWrap};
+#[cfg(feature = "grid")]
+use crate::style::{GridAutoFlow, GridPlacement, NonRepeatedTrackSizingFunction, TrackSizingFunction};
+#[cfg(feature = "grid")]
+use crate::style_helpers::{auto, fr, length, line, max_content, min_content, percent, span};
+use crate::tree::NodeId;
+use crate::util::sys::{BTreeMap, String, Vec};
+
+use super::{Taffy, Transaction};
+
+/// A tree loaded with [`Taffy::load_markup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupTree {
+    /// The node created for the root element
+    pub root: NodeId,
+    /// The nodes created for the elements that have a `name` attribute, keyed by name
+    pub names: BTreeMap<String, NodeId>,
+}
+
+/// An error encountered while loading markup with [`Taffy::load_markup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    /// The line of the markup on which the error was found, starting at 1
+    pub line: usize,
+    /// What went wrong
+    pub kind: MarkupErrorKind,
+}
+
+/// The kinds of [`MarkupError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// The markup ended in the middle of an element
+    UnexpectedEnd,
+    /// A different character was expected
+    Expected(char),
+    /// The markup contains text outside of a tag
+    UnexpectedText,
+    /// The markup doesn't contain exactly one root element
+    ExpectedSingleRoot,
+    /// A closing tag doesn't match the element that it closes
+    MismatchedClosingTag {
+        /// The name of the element being closed
+        expected: String,
+        /// The name in the closing tag
+        found: String,
+    },
+    /// The same name was given to several elements
+    DuplicateName(String),
+    /// A style declaration sets a property that isn't supported
+    UnknownProperty(String),
+    /// A style declaration sets a property to a value that isn't valid for it
+    InvalidValue {
+        /// The property being set
+        property: String,
+        /// The value that couldn't be parsed
+        value: String,
+    },
+}
+
+#[cfg(feature = "std")]
+impl Display for MarkupError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.kind {
+            MarkupErrorKind::UnexpectedEnd => write!(f, "Unexpected end of markup"),
+            MarkupErrorKind::Expected(expected) => write!(f, "Expected '{expected}'"),
+            MarkupErrorKind::UnexpectedText => write!(f, "Text is only allowed inside of tags"),
+            MarkupErrorKind::ExpectedSingleRoot => write!(f, "Expected a single root element"),
+            MarkupErrorKind::MismatchedClosingTag { expected, found } => {
+                write!(f, "Expected closing tag for '{expected}', found '{found}'")
+            }
+            MarkupErrorKind::DuplicateName(name) => write!(f, "The name '{name}' is used more than once"),
+            MarkupErrorKind::UnknownProperty(property) => write!(f, "Unknown property '{property}'"),
+            MarkupErrorKind::InvalidValue { property, value } => {
+                write!(f, "Invalid value '{value}' for property '{property}'")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MarkupError {}
+
+impl Taffy {
+    /// Builds a tree from `markup` (see the [module documentation](crate::tree::markup) for the format), returning its root along with
+    /// the nodes that were given a name
+    ///
+    /// If the markup is invalid, no nodes are added to the tree.
+    pub fn load_markup(&mut self, markup: &str) -> Result<MarkupTree, MarkupError> {
+        let mut parser = Parser { source: markup, position: 0 };
+        let mut names = BTreeMap::new();
+        let mut transaction = self.transaction();
+
+        parser.skip_trivia()?;
+        if !parser.rest().starts_with('<') {
+            return Err(parser.error(MarkupErrorKind::ExpectedSingleRoot));
+        }
+        let root = parser.parse_element(&mut transaction, &mut names)?;
+        parser.skip_trivia()?;
+        if !parser.rest().is_empty() {
+            return Err(parser.error(MarkupErrorKind::ExpectedSingleRoot));
+        }
+
+        transaction.commit();
+        Ok(MarkupTree { root, names })
+    }
+}
+
+/// A parser over markup
+struct Parser<'s> {
+    /// The markup being parsed
+    source: &'s str,
+    /// The byte offset of the next character to parse
+    position: usize,
+}
+
+impl<'s> Parser<'s> {
+    /// Returns the markup that hasn't been parsed yet
+    fn rest(&self) -> &'s str {
+        &self.source[self.position..]
+    }
+
+    /// Creates an error at the current position
+    fn error(&self, kind: MarkupErrorKind) -> MarkupError {
+        let line = self.source[..self.position].matches('\n').count() + 1;
+        MarkupError { line, kind }
+    }
+
+    /// Skips whitespace and comments
+    fn skip_trivia(&mut self) -> Result<(), MarkupError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+
+            if !trimmed.starts_with("<!--") {
+                return Ok(());
+            }
+            match trimmed.find("-->") {
+                Some(end) => self.position += end + "-->".len(),
+                None => {
+                    self.position = self.source.len();
+                    return Err(self.error(MarkupErrorKind::UnexpectedEnd));
+                }
+            }
+        }
+    }
+
+    /// Skips whitespace
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `expected` if the remaining markup starts with it
+    fn eat(&mut self, expected: &str) -> bool {
+        let found = self.rest().starts_with(expected);
+        if found {
+            self.position += expected.len();
+        }
+        found
+    }
+
+    /// Consumes the character `expected`, or returns an error if the remaining markup doesn't start with it
+    fn expect(&mut self, expected: char) -> Result<(), MarkupError> {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else if self.rest().is_empty() {
+            Err(self.error(MarkupErrorKind::UnexpectedEnd))
+        } else {
+            Err(self.error(MarkupErrorKind::Expected(expected)))
+        }
+    }
+
+    /// Parses a tag or attribute name
+    fn parse_name(&mut self) -> Result<&'s str, MarkupError> {
+        let rest = self.rest();
+        let length = rest.find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_')).unwrap_or(rest.len());
+        if length == 0 {
+            return Err(match rest.is_empty() {
+                true => self.error(MarkupErrorKind::UnexpectedEnd),
+                false => self.error(MarkupErrorKind::UnexpectedText),
+            });
+        }
+        self.position += length;
+        Ok(&rest[..length])
+    }
+
+    /// Parses a quoted attribute value
+    fn parse_attribute_value(&mut self) -> Result<&'s str, MarkupError> {
+        let quote = match self.rest().chars().next() {
+            Some(quote @ ('"' | '\'')) => quote,
+            Some(_) => return Err(self.error(MarkupErrorKind::Expected('"'))),
+            None => return Err(self.error(MarkupErrorKind::UnexpectedEnd)),
+        };
+        self.position += 1;
+
+        let rest = self.rest();
+        match rest.find(quote) {
+            Some(length) => {
+                self.position += length + 1;
+                Ok(&rest[..length])
+            }
+            None => {
+                self.position = self.source.len();
+                Err(self.error(MarkupErrorKind::UnexpectedEnd))
+            }
+        }
+    }
+
+    /// Parses an element and its descendants, creating a node for each of them
+    ///
+    /// The elements that have been opened but not yet closed are kept on an explicit stack rather than being parsed
+    /// recursively, so that deeply nested markup doesn't overflow the call stack.
+    fn parse_element(
+        &mut self,
+        transaction: &mut Transaction<'_>,
+        names: &mut BTreeMap<String, NodeId>,
+    ) -> Result<NodeId, MarkupError> {
+        let mut open_elements: Vec<OpenElement<'s>> = Vec::new();
+        loop {
+            let (element, self_closing) = self.parse_start_tag()?;
+            let mut closed = match self_closing {
+                true => Some(element),
+                false => {
+                    open_elements.push(element);
+                    None
+                }
+            };
+
+            // Parse the contents of the innermost open element until the start tag of its next child is found
+            loop {
+                if let Some(element) = closed.take() {
+                    let node = self.create_node(element, transaction, names)?;
+                    match open_elements.last_mut() {
+                        Some(parent) => parent.children.push(node),
+                        None => return Ok(node),
+                    }
+                }
+
+                self.skip_trivia()?;
+                if self.eat("</") {
+                    // There is an open element, as the root element has been returned once it was closed
+                    let element = open_elements.pop().unwrap();
+                    let closing_tag = self.parse_name()?;
+                    if closing_tag != element.tag {
+                        return Err(self.error(MarkupErrorKind::MismatchedClosingTag {
+                            expected: String::from(element.tag),
+                            found: String::from(closing_tag),
+                        }));
+                    }
+                    self.skip_whitespace();
+                    self.expect('>')?;
+                    closed = Some(element);
+                    continue;
+                }
+                match self.rest().chars().next() {
+                    Some('<') => break,
+                    Some(_) => return Err(self.error(MarkupErrorKind::UnexpectedText)),
+                    None => return Err(self.error(MarkupErrorKind::UnexpectedEnd)),
+                }
+            }
+        }
+    }
+
+    /// Parses the start tag of an element, returning the element and whether the tag is self-closing
+    fn parse_start_tag(&mut self) -> Result<(OpenElement<'s>, bool), MarkupError> {
+        self.expect('<')?;
+        let tag = self.parse_name()?;
+
+        let mut name = None;
+        let mut style = Style::default();
+        let self_closing = loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                break true;
+            }
+            if self.eat(">") {
+                break false;
+            }
+
+            let attribute = self.parse_name()?;
+            self.skip_whitespace();
+            self.expect('=')?;
+            self.skip_whitespace();
+            let value = self.parse_attribute_value()?;
+            match attribute {
+                "name" => name = Some(value),
+                "style" => parse_declarations(&mut style, value).map_err(|kind| self.error(kind))?,
+                _ => {}
+            }
+        };
+
+        Ok((OpenElement { tag, name, style, children: Vec::new() }, self_closing))
+    }
+
+    /// Creates the node for an element that has been closed
+    fn create_node(
+        &self,
+        element: OpenElement<'s>,
+        transaction: &mut Transaction<'_>,
+        names: &mut BTreeMap<String, NodeId>,
+    ) -> Result<NodeId, MarkupError> {
+        // Creating nodes can't fail, as the children have just been created
+        let node = transaction.new_with_children(element.style, &element.children).unwrap();
+        if let Some(name) = element.name {
+            if names.insert(String::from(name), node).is_some() {
+                return Err(self.error(MarkupErrorKind::DuplicateName(String::from(name))));
+            }
+        }
+        Ok(node)
+    }
+}
+
+/// An element whose start tag has been parsed, along with the nodes created for its children so far
+struct OpenElement<'s> {
+    /// The tag name of the element
+    tag: &'s str,
+    /// The value of the element's `name` attribute, if any
+    name: Option<&'s str>,
+    /// The style given by the element's `style` attribute
+    style: Style,
+    /// The nodes created for the children of the element that have been closed
+    children: Vec<NodeId>,
+}
+
+/// Applies the `;`-separated CSS-like declarations to `style`
+fn parse_declarations(style: &mut Style, declarations: &str) -> Result<(), MarkupErrorKind> {
+    for declaration in declarations.split(';') {
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+
+        let (property, value) = declaration.split_once(':').unwrap_or((declaration, ""));
+        let (property, value) = (property.trim(), value.trim());
+        let invalid = || MarkupErrorKind::InvalidValue { property: String::from(property), value: String::from(value) };
+        apply_declaration(style, property, value)?.ok_or_else(invalid)?;
+    }
+    Ok(())
+}
+
+/// Applies a single declaration to `style`
+///
+/// Returns `Ok(None)` if the value is invalid for the property.
+fn apply_declaration(style: &mut Style, property: &str, value: &str) -> Result<Option<()>, MarkupErrorKind> {
+    let values: Vec<&str> = value.split_whitespace().collect();
+    let applied = match property {
+        "display" => keyword(
+            value,
+            &[
+                #[cfg(feature = "block_layout")]
+                ("block", DisplayMode::Block),
+                #[cfg(feature = "flexbox")]
+                ("flex", DisplayMode::Flex),
+                #[cfg(feature = "grid")]
+                ("grid", DisplayMode::Grid),
+                ("none", DisplayMode::None),
+            ],
+        )
+        .map(|display| style.display = display),
+        "position" => keyword(value, &[("relative", Position::Relative), ("absolute", Position::Absolute)])
+            .map(|position| style.position = position),
+        "overflow" => pair(&values, overflow).map(|(x, y)| style.overflow = Point { x, y }),
+        "overflow-x" => overflow(value).map(|overflow| style.overflow.x = overflow),
+        "overflow-y" => overflow(value).map(|overflow| style.overflow.y = overflow),
+        "scrollbar-width" => {
+            pair(&values, length_value).map(|(width, height)| style.scrollbar_width = Size { width, height })
+        }
+
+        "width" => dimension(value).map(|width| style.size.width = width),
+        "height" => dimension(value).map(|height| style.size.height = height),
+        "min-width" => dimension(value).map(|width| style.min_size.width = width),
+        "min-height" => dimension(value).map(|height| style.min_size.height = height),
+        "max-width" => dimension(value).map(|width| style.max_size.width = width),
+        "max-height" => dimension(value).map(|height| style.max_size.height = height),
+        "aspect-ratio" => aspect_ratio(value).map(|ratio| style.aspect_ratio = ratio),
+        "contain" => {
+            keyword(value, &[("none", Contain::None), ("size", Contain::Size)]).map(|contain| style.contain = contain)
+        }
+
+        "inset" => sides(&values, length_percentage_auto).map(|inset| style.inset = inset),
+        "top" => length_percentage_auto(value).map(|top| style.inset.top = top),
+        "right" => length_percentage_auto(value).map(|right| style.inset.right = right),
+        "bottom" => length_percentage_auto(value).map(|bottom| style.inset.bottom = bottom),
+        "left" => length_percentage_auto(value).map(|left| style.inset.left = left),
+        "margin" => sides(&values, length_percentage_auto).map(|margin| style.margin = margin),
+        "margin-top" => length_percentage_auto(value).map(|top| style.margin.top = top),
+        "margin-right" => length_percentage_auto(value).map(|right| style.margin.right = right),
+        "margin-bottom" => length_percentage_auto(value).map(|bottom| style.margin.bottom = bottom),
+        "margin-left" => length_percentage_auto(value).map(|left| style.margin.left = left),
+        "padding" => sides(&values, length_percentage).map(|padding| style.padding = padding),
+        "padding-top" => length_percentage(value).map(|top| style.padding.top = top),
+        "padding-right" => length_percentage(value).map(|right| style.padding.right = right),
+        "padding-bottom" => length_percentage(value).map(|bottom| style.padding.bottom = bottom),
+        "padding-left" => length_percentage(value).map(|left| style.padding.left = left),
+        "border-width" => sides(&values, length_percentage).map(|border| style.border = border),
+        "border-top-width" => length_percentage(value).map(|top| style.border.top = top),
+        "border-right-width" => length_percentage(value).map(|right| style.border.right = right),
+        "border-bottom-width" => length_percentage(value).map(|bottom| style.border.bottom = bottom),
+        "border-left-width" => length_percentage(value).map(|left| style.border.left = left),
+
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "align-items" => align_items(value).map(|align| style.align_items = Some(align)),
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "align-self" => align_items(value).map(|align| style.align_self = Some(align)),
+        #[cfg(feature = "grid")]
+        "justify-items" => align_items(value).map(|align| style.justify_items = Some(align)),
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "justify-self" => align_items(value).map(|align| style.justify_self = Some(align)),
+        #[cfg(feature = "grid")]
+        "place-items" => {
+            pair(&values, align_items).map(|(align, justify)| style.set_place_items(Some(align), Some(justify)))
+        }
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "place-self" => {
+            pair(&values, align_items).map(|(align, justify)| style.set_place_self(Some(align), Some(justify)))
+        }
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "align-content" => align_content(value).map(|align| style.align_content = Some(align)),
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "justify-content" => align_content(value).map(|align| style.justify_content = Some(align)),
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        "place-content" => {
+            pair(&values, align_content).map(|(align, justify)| style.set_place_content(Some(align), Some(justify)))
+        }
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        "gap" => pair(&values, length_percentage).map(|(row, column)| style.gap = Size { width: column, height: row }),
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        "row-gap" => length_percentage(value).map(|gap| style.gap.height = gap),
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        "column-gap" => length_percentage(value).map(|gap| style.gap.width = gap),
+
+        #[cfg(feature = "flexbox")]
+        "flex-direction" => keyword(
+            value,
+            &[
+                ("row", FlexDirection::Row),
+                ("column", FlexDirection::Column),
+                ("row-reverse", FlexDirection::RowReverse),
+                ("column-reverse", FlexDirection::ColumnReverse),
+            ],
+        )
+        .map(|direction| style.flex_direction = direction),
+        #[cfg(feature = "flexbox")]
+        "flex-wrap" => keyword(
+            value,
+            &[("nowrap", FlexWrap::NoWrap), ("wrap", FlexWrap::Wrap), ("wrap-reverse", FlexWrap::WrapReverse)],
+        )
+        .map(|wrap| style.flex_wrap = wrap),
+        #[cfg(feature = "flexbox")]
+        "flex-basis" => dimension(value).map(|basis| style.flex_basis = basis),
+        #[cfg(feature = "flexbox")]
+        "flex-grow" => number(value).map(|grow| style.flex_grow = grow),
+        #[cfg(feature = "flexbox")]
+        "flex-shrink" => number(value).map(|shrink| style.flex_shrink = shrink),
+
+        #[cfg(feature = "grid")]
+        "grid-template-rows" => track_list(&values)
+            .map(|tracks| style.grid_template_rows = tracks.into_iter().map(TrackSizingFunction::Single).collect()),
+        #[cfg(feature = "grid")]
+        "grid-template-columns" => track_list(&values)
+            .map(|tracks| style.grid_template_columns = tracks.into_iter().map(TrackSizingFunction::Single).collect()),
+        #[cfg(feature = "grid")]
+        "grid-auto-rows" => track_list(&values).map(|tracks| style.grid_auto_rows = tracks.into()),
+        #[cfg(feature = "grid")]
+        "grid-auto-columns" => track_list(&values).map(|tracks| style.grid_auto_columns = tracks.into()),
+        #[cfg(feature = "grid")]
+        "grid-auto-flow" => keyword(
+            value,
+            &[
+                ("row", GridAutoFlow::Row),
+                ("column", GridAutoFlow::Column),
+                ("row dense", GridAutoFlow::RowDense),
+                ("column dense", GridAutoFlow::ColumnDense),
+            ],
+        )
+        .map(|flow| style.grid_auto_flow = flow),
+        #[cfg(feature = "grid")]
+        "grid-row" => grid_line(value).map(|placement| style.grid_row = placement),
+        #[cfg(feature = "grid")]
+        "grid-column" => grid_line(value).map(|placement| style.grid_column = placement),
+        #[cfg(feature = "grid")]
+        "grid-row-start" => grid_placement(value).map(|placement| style.grid_row.start = placement),
+        #[cfg(feature = "grid")]
+        "grid-row-end" => grid_placement(value).map(|placement| style.grid_row.end = placement),
+        #[cfg(feature = "grid")]
+        "grid-column-start" => grid_placement(value).map(|placement| style.grid_column.start = placement),
+        #[cfg(feature = "grid")]
+        "grid-column-end" => grid_placement(value).map(|placement| style.grid_column.end = placement),
+
+        _ => return Err(MarkupErrorKind::UnknownProperty(String::from(property))),
+    };
+    Ok(applied)
+}
+
+/// Parses one of the provided keywords
+fn keyword<T: Copy>(value: &str, keywords: &[(&str, T)]) -> Option<T> {
+    keywords.iter().find(|(keyword, _)| *keyword == value).map(|(_, result)| *result)
+}
+
+/// Parses a number
+fn number(value: &str) -> Option<f32> {
+    value.parse().ok()
+}
+
+/// Parses a length in pixels, with or without a `px` suffix
+fn length_value(value: &str) -> Option<f32> {
+    number(value.strip_suffix("px").unwrap_or(value))
+}
+
+/// Converts a number written with a unit suffix into a length
+type UnitConstructor = fn(f32) -> LengthPercentageAuto;
+
+/// Parses a length, percentage, context-dependent length or `auto`
+fn length_percentage_auto(value: &str) -> Option<LengthPercentageAuto> {
+    if value == "auto" {
+        return Some(LengthPercentageAuto::Auto);
+    }
+    // Longer suffixes are checked first, as `vmin` and `vmax` end with the same letters as other units
+    let units: [(&str, UnitConstructor); 7] = [
+        ("%", |percent| LengthPercentageAuto::Percent(percent / 100.0)),
+        ("vmin", |vmin| LengthPercentageAuto::Vmin(vmin / 100.0)),
+        ("vmax", |vmax| LengthPercentageAuto::Vmax(vmax / 100.0)),
+        ("vw", |vw| LengthPercentageAuto::Vw(vw / 100.0)),
+        ("vh", |vh| LengthPercentageAuto::Vh(vh / 100.0)),
+        ("rem", LengthPercentageAuto::Rem),
+        ("em", LengthPercentageAuto::Em),
+    ];
+    for (suffix, unit) in units {
+        if let Some(number) = value.strip_suffix(suffix) {
+            return self::number(number).map(unit);
+        }
+    }
+    length_value(value).map(LengthPercentageAuto::Length)
+}
+
+/// Parses a length, percentage or context-dependent length
+fn length_percentage(value: &str) -> Option<LengthPercentage> {
+    Some(match length_percentage_auto(value)? {
+        LengthPercentageAuto::Length(length) => LengthPercentage::Length(length),
+        LengthPercentageAuto::Percent(percent) => LengthPercentage::Percent(percent),
+        LengthPercentageAuto::Vw(vw) => LengthPercentage::Vw(vw),
+        LengthPercentageAuto::Vh(vh) => LengthPercentage::Vh(vh),
+        LengthPercentageAuto::Vmin(vmin) => LengthPercentage::Vmin(vmin),
+        LengthPercentageAuto::Vmax(vmax) => LengthPercentage::Vmax(vmax),
+        LengthPercentageAuto::Rem(rem) => LengthPercentage::Rem(rem),
+        LengthPercentageAuto::Em(em) => LengthPercentage::Em(em),
+        LengthPercentageAuto::Custom(_) | LengthPercentageAuto::Auto => return None,
+    })
+}
+
+/// Parses a length, percentage, context-dependent length or `auto` as a [`Dimension`]
+fn dimension(value: &str) -> Option<Dimension> {
+    length_percentage_auto(value).map(Dimension::from)
+}
+
+/// Parses an aspect ratio, written as `auto`, a number, or a `width / height` ratio
+fn aspect_ratio(value: &str) -> Option<Option<f32>> {
+    if value == "auto" {
+        return Some(None);
+    }
+    match value.split_once('/') {
+        Some((width, height)) => Some(Some(number(width.trim())? / number(height.trim())?)),
+        None => number(value).map(Some),
+    }
+}
+
+/// Parses an overflow keyword
+fn overflow(value: &str) -> Option<Overflow> {
+    keyword(value, &[("visible", Overflow::Visible), ("hidden", Overflow::Hidden), ("scroll", Overflow::Scroll)])
+}
+
+/// Parses an alignment keyword for items
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+fn align_items(value: &str) -> Option<AlignItems> {
+    keyword(
+        value,
+        &[
+            ("start", AlignItems::Start),
+            ("end", AlignItems::End),
+            ("flex-start", AlignItems::FlexStart),
+            ("flex-end", AlignItems::FlexEnd),
+            ("center", AlignItems::Center),
+            ("baseline", AlignItems::Baseline),
+            ("stretch", AlignItems::Stretch),
+        ],
+    )
+}
+
+/// Parses an alignment keyword for content
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+fn align_content(value: &str) -> Option<AlignContent> {
+    keyword(
+        value,
+        &[
+            ("start", AlignContent::Start),
+            ("end", AlignContent::End),
+            ("flex-start", AlignContent::FlexStart),
+            ("flex-end", AlignContent::FlexEnd),
+            ("center", AlignContent::Center),
+            ("stretch", AlignContent::Stretch),
+            ("space-between", AlignContent::SpaceBetween),
+            ("space-evenly", AlignContent::SpaceEvenly),
+            ("space-around", AlignContent::SpaceAround),
+        ],
+    )
+}
+
+/// Parses one or two values, where a single value applies to both
+fn pair<T: Copy>(values: &[&str], parse: impl Fn(&str) -> Option<T>) -> Option<(T, T)> {
+    match values {
+        [both] => parse(both).map(|both| (both, both)),
+        [first, second] => Some((parse(first)?, parse(second)?)),
+        _ => None,
+    }
+}
+
+/// Parses one to four values for the sides of a box, in the order top, right, bottom, left as in CSS
+fn sides<T: Copy>(values: &[&str], parse: impl Fn(&str) -> Option<T>) -> Option<Rect<T>> {
+    let values = values.iter().map(|value| parse(value)).collect::<Option<Vec<T>>>()?;
+    let (top, right, bottom, left) = match values[..] {
+        [all] => (all, all, all, all),
+        [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+        [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return None,
+    };
+    Some(Rect { left, right, top, bottom })
+}
+
+/// Parses a space-separated list of grid tracks
+#[cfg(feature = "grid")]
+fn track_list(values: &[&str]) -> Option<Vec<NonRepeatedTrackSizingFunction>> {
+    values
+        .iter()
+        .map(|value| match *value {
+            "auto" => Some(auto()),
+            "min-content" => Some(min_content()),
+            "max-content" => Some(max_content()),
+            _ => match value.strip_suffix("fr") {
+                Some(flex) => number(flex).map(fr),
+                None => match value.strip_suffix('%') {
+                    Some(percentage) => number(percentage).map(|percentage| percent(percentage / 100.0)),
+                    None => length_value(value).map(length),
+                },
+            },
+        })
+        .collect()
+}
+
+/// Parses a grid placement: `auto`, a line index, or `span` followed by a number of tracks
+#[cfg(feature = "grid")]
+fn grid_placement(value: &str) -> Option<GridPlacement> {
+    let values: Vec<&str> = value.split_whitespace().collect();
+    match values[..] {
+        ["auto"] => Some(GridPlacement::Auto),
+        ["span", tracks] => tracks.parse().ok().map(span),
+        [index] => index.parse().ok().map(line),
+        _ => None,
+    }
+}
+
+/// Parses the start and end placements of a grid item, separated by a `/`
+#[cfg(feature = "grid")]
+fn grid_line(value: &str) -> Option<Line<GridPlacement>> {
+    match value.split_once('/') {
+        Some((start, end)) => Some(Line { start: grid_placement(start.trim())?, end: grid_placement(end.trim())? }),
+        None => Some(Line { start: grid_placement(value)?, end: GridPlacement::Auto }),
+    }
+}
@@ -0,0 +1,506 @@
+//! Memoization of subtree layouts by structural fingerprint
+//!
+//! See [`Taffy::set_memoization_root`](crate::Taffy::set_memoization_root)
+use core::hash::{Hash, Hasher};
+use core::mem::discriminant;
+
+use crate::geometry::{Line, MinMax, Point, Rect, Size};
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+use crate::style::{AlignContent, AlignItems};
+use crate::style::{
+    Anchor, AvailableSpace, Contain, Dimension, Display, LengthPercentage, LengthPercentageAuto, Overflow, Position,
+    Style, VerticalPercentageBasis,
+};
+#[cfg(feature = "flexbox")]
+use crate::style::{FlexDirection, FlexWrap};
+#[cfg(feature = "grid")]
+use crate::style::{
+    GridAutoFlow, GridPlacement, GridTrackList, GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
+    TrackSizingFunction,
+};
+#[cfg(feature = "grid")]
+use crate::tree::AutoRepetitions;
+use crate::tree::{Cache, CellMode, CoordinateOrigin, Layout, RunMode, SizeBaselinesAndMargins, SizingMode};
+use crate::util::sys::{BTreeMap, Vec};
+
+/// A [`Hasher`] implementing the 64-bit FNV-1a algorithm, which is used to compute subtree fingerprints
+///
+/// Unlike the standard library's default hasher this is available without `std`, and is stable across runs.
+pub(crate) struct FingerprintHasher(u64);
+
+impl FingerprintHasher {
+    /// The FNV-1a 64-bit offset basis
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    /// The FNV-1a 64-bit prime
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Creates a new hasher
+    pub(crate) const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    /// Feeds a value that affects layout, such as the style of a node, into the hasher
+    pub(crate) fn write_fingerprint<T: Fingerprint + ?Sized>(&mut self, value: &T) {
+        value.write_fingerprint(self);
+    }
+}
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+/// Values that can be fed into a [`FingerprintHasher`]
+///
+/// Styles contain floats and so can't implement [`Hash`]. Floats are hashed by their bit patterns instead, so values
+/// that compare equal but differ in representation (such as `0.0` and `-0.0`) only cost a missed memoization.
+pub(crate) trait Fingerprint {
+    /// Feeds the value into the hasher
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher);
+}
+
+impl Fingerprint for f32 {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_u32(self.to_bits());
+    }
+}
+
+impl Fingerprint for bool {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_u8(u8::from(*self));
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for Option<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        match self {
+            Some(value) => {
+                hasher.write_u8(1);
+                value.write_fingerprint(hasher);
+            }
+            None => hasher.write_u8(0),
+        }
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for [T] {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_usize(self.len());
+        for value in self {
+            value.write_fingerprint(hasher);
+        }
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for Point<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.x.write_fingerprint(hasher);
+        self.y.write_fingerprint(hasher);
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for Size<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.width.write_fingerprint(hasher);
+        self.height.write_fingerprint(hasher);
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for Rect<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.left.write_fingerprint(hasher);
+        self.right.write_fingerprint(hasher);
+        self.top.write_fingerprint(hasher);
+        self.bottom.write_fingerprint(hasher);
+    }
+}
+
+impl<T: Fingerprint> Fingerprint for Line<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.start.write_fingerprint(hasher);
+        self.end.write_fingerprint(hasher);
+    }
+}
+
+impl<Min: Fingerprint, Max: Fingerprint> Fingerprint for MinMax<Min, Max> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.min.write_fingerprint(hasher);
+        self.max.write_fingerprint(hasher);
+    }
+}
+
+/// Implements [`Fingerprint`] for enums without fields by hashing their discriminant
+macro_rules! impl_fingerprint_for_unit_enums {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Fingerprint for $ty {
+                fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+                    discriminant(self).hash(hasher);
+                }
+            }
+        )*
+    };
+}
+
+impl_fingerprint_for_unit_enums!(Overflow, Position, Contain, VerticalPercentageBasis);
+#[cfg(any(feature = "flexbox", feature = "grid"))]
+impl_fingerprint_for_unit_enums!(AlignItems, AlignContent);
+#[cfg(feature = "flexbox")]
+impl_fingerprint_for_unit_enums!(FlexDirection, FlexWrap);
+#[cfg(feature = "grid")]
+impl_fingerprint_for_unit_enums!(GridAutoFlow);
+
+impl Fingerprint for Display {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        if let Display::Custom(key) = self {
+            hasher.write_u16(*key);
+        }
+    }
+}
+
+impl Fingerprint for LengthPercentage {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match *self {
+            LengthPercentage::Length(value)
+            | LengthPercentage::Percent(value)
+            | LengthPercentage::Vw(value)
+            | LengthPercentage::Vh(value)
+            | LengthPercentage::Vmin(value)
+            | LengthPercentage::Vmax(value)
+            | LengthPercentage::Rem(value)
+            | LengthPercentage::Em(value) => value.write_fingerprint(hasher),
+            LengthPercentage::Custom(unit) => hasher.write_u32(unit),
+        }
+    }
+}
+
+impl Fingerprint for LengthPercentageAuto {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match *self {
+            LengthPercentageAuto::Length(value)
+            | LengthPercentageAuto::Percent(value)
+            | LengthPercentageAuto::Vw(value)
+            | LengthPercentageAuto::Vh(value)
+            | LengthPercentageAuto::Vmin(value)
+            | LengthPercentageAuto::Vmax(value)
+            | LengthPercentageAuto::Rem(value)
+            | LengthPercentageAuto::Em(value) => value.write_fingerprint(hasher),
+            LengthPercentageAuto::Custom(unit) => hasher.write_u32(unit),
+            LengthPercentageAuto::Auto => {}
+        }
+    }
+}
+
+impl Fingerprint for Dimension {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match *self {
+            Dimension::Length(value)
+            | Dimension::Percent(value)
+            | Dimension::Vw(value)
+            | Dimension::Vh(value)
+            | Dimension::Vmin(value)
+            | Dimension::Vmax(value)
+            | Dimension::Rem(value)
+            | Dimension::Em(value) => value.write_fingerprint(hasher),
+            Dimension::Custom(unit) => hasher.write_u32(unit),
+            Dimension::Auto => {}
+        }
+    }
+}
+
+impl Fingerprint for Anchor {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.point.write_fingerprint(hasher);
+        self.parent_point.write_fingerprint(hasher);
+        self.offset.write_fingerprint(hasher);
+    }
+}
+
+#[cfg(feature = "grid")]
+impl Fingerprint for MinTrackSizingFunction {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        if let MinTrackSizingFunction::Fixed(value) = self {
+            value.write_fingerprint(hasher);
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl Fingerprint for MaxTrackSizingFunction {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match self {
+            MaxTrackSizingFunction::Fixed(value) | MaxTrackSizingFunction::FitContent(value) => {
+                value.write_fingerprint(hasher);
+            }
+            MaxTrackSizingFunction::Fraction(fraction) => fraction.write_fingerprint(hasher),
+            MaxTrackSizingFunction::MinContent | MaxTrackSizingFunction::MaxContent | MaxTrackSizingFunction::Auto => {}
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl Fingerprint for TrackSizingFunction {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match self {
+            TrackSizingFunction::Single(track) => track.write_fingerprint(hasher),
+            TrackSizingFunction::Repeat(repetition, tracks) => {
+                discriminant(repetition).hash(hasher);
+                if let GridTrackRepetition::Count(count) = repetition {
+                    hasher.write_u16(*count);
+                }
+                tracks.as_slice().write_fingerprint(hasher);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "grid")]
+impl<T: Fingerprint> Fingerprint for GridTrackList<T> {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        self.as_slice().write_fingerprint(hasher);
+    }
+}
+
+#[cfg(feature = "grid")]
+impl Fingerprint for GridPlacement {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        discriminant(self).hash(hasher);
+        match self {
+            GridPlacement::Line(line) => hasher.write_i16(line.as_i16()),
+            GridPlacement::Span(span) => hasher.write_u16(*span),
+            GridPlacement::Auto => {}
+        }
+    }
+}
+
+impl Fingerprint for Style {
+    fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        // Destructuring makes adding a field to `Style` without hashing it here a compile error
+        let Style {
+            display,
+            overflow,
+            scrollbar_width,
+            position,
+            inset,
+            anchor,
+            size,
+            min_size,
+            max_size,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            disable_automatic_min_size,
+            aspect_ratio,
+            contain,
+            margin,
+            padding,
+            border,
+            vertical_percentage_basis,
+            #[cfg(feature = "block_layout")]
+            disable_margin_collapsing,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            align_items,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            align_self,
+            #[cfg(feature = "grid")]
+            justify_items,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            justify_self,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            align_content,
+            #[cfg(any(feature = "flexbox", feature = "grid"))]
+            justify_content,
+            #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+            gap,
+            #[cfg(feature = "flexbox")]
+            flex_direction,
+            #[cfg(feature = "flexbox")]
+            flex_wrap,
+            #[cfg(feature = "flexbox")]
+            flex_justify_self,
+            #[cfg(feature = "flexbox")]
+            flex_basis,
+            #[cfg(feature = "flexbox")]
+            flex_grow,
+            #[cfg(feature = "flexbox")]
+            flex_shrink,
+            #[cfg(feature = "grid")]
+            grid_template_rows,
+            #[cfg(feature = "grid")]
+            grid_template_columns,
+            #[cfg(feature = "grid")]
+            grid_auto_rows,
+            #[cfg(feature = "grid")]
+            grid_auto_columns,
+            #[cfg(feature = "grid")]
+            grid_auto_flow,
+            #[cfg(feature = "grid")]
+            grid_row,
+            #[cfg(feature = "grid")]
+            grid_column,
+        } = self;
+
+        display.write_fingerprint(hasher);
+        overflow.write_fingerprint(hasher);
+        scrollbar_width.write_fingerprint(hasher);
+        position.write_fingerprint(hasher);
+        inset.write_fingerprint(hasher);
+        anchor.write_fingerprint(hasher);
+        size.write_fingerprint(hasher);
+        min_size.write_fingerprint(hasher);
+        max_size.write_fingerprint(hasher);
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        disable_automatic_min_size.write_fingerprint(hasher);
+        aspect_ratio.write_fingerprint(hasher);
+        contain.write_fingerprint(hasher);
+        margin.write_fingerprint(hasher);
+        padding.write_fingerprint(hasher);
+        border.write_fingerprint(hasher);
+        vertical_percentage_basis.write_fingerprint(hasher);
+        #[cfg(feature = "block_layout")]
+        disable_margin_collapsing.write_fingerprint(hasher);
+        #[cfg(any(feature = "flexbox", feature = "grid"))]
+        {
+            align_items.write_fingerprint(hasher);
+            align_self.write_fingerprint(hasher);
+            justify_self.write_fingerprint(hasher);
+            align_content.write_fingerprint(hasher);
+            justify_content.write_fingerprint(hasher);
+        }
+        #[cfg(any(feature = "flexbox", feature = "grid", feature = "block_layout"))]
+        gap.write_fingerprint(hasher);
+        #[cfg(feature = "flexbox")]
+        {
+            flex_direction.write_fingerprint(hasher);
+            flex_wrap.write_fingerprint(hasher);
+            flex_justify_self.write_fingerprint(hasher);
+            flex_basis.write_fingerprint(hasher);
+            flex_grow.write_fingerprint(hasher);
+            flex_shrink.write_fingerprint(hasher);
+        }
+        #[cfg(feature = "grid")]
+        {
+            justify_items.write_fingerprint(hasher);
+            grid_template_rows.write_fingerprint(hasher);
+            grid_template_columns.write_fingerprint(hasher);
+            grid_auto_rows.write_fingerprint(hasher);
+            grid_auto_columns.write_fingerprint(hasher);
+            grid_auto_flow.write_fingerprint(hasher);
+            grid_row.write_fingerprint(hasher);
+            grid_column.write_fingerprint(hasher);
+        }
+    }
+}
+
+/// The constraints that a memoized subtree layout was computed under
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MemoConstraints {
+    /// The known dimensions of the subtree's root node
+    pub(crate) known_dimensions: Size<Option<f32>>,
+    /// The size of the parent of the subtree's root node
+    pub(crate) parent_size: Size<Option<f32>>,
+    /// The space available to the subtree's root node
+    pub(crate) available_space: Size<AvailableSpace>,
+    /// Whether the layout was a full layout or only computed the size
+    pub(crate) run_mode: RunMode,
+    /// Whether the style of the subtree's root node was taken into account
+    pub(crate) sizing_mode: SizingMode,
+    /// Whether the vertical margins of the subtree's root node could collapse with its parent's
+    pub(crate) vertical_margins_are_collapsible: Line<bool>,
+    /// Whether layout values were rounded
+    pub(crate) use_rounding: bool,
+    /// The number of physical pixels per logical unit that layout values were rounded to
+    pub(crate) scale_factor: f32,
+    /// The grid of cells that layout values were snapped to, if any
+    pub(crate) cell_mode: Option<CellMode>,
+    /// The coordinate convention of the computed locations
+    pub(crate) coordinate_origin: CoordinateOrigin,
+}
+
+/// The state that laying out a subtree leaves on one of its nodes
+#[derive(Debug, Clone)]
+pub(crate) struct MemoizedNode {
+    /// The layout of the node, which is left untouched for the subtree's root as it is set by its parent
+    pub(crate) layout: Layout,
+    /// The cached results of the node
+    pub(crate) cache: Cache,
+    /// The tracks produced by the auto-repeated track definitions of the node, if it is such a grid
+    #[cfg(feature = "grid")]
+    pub(crate) auto_repetitions: Option<Size<Option<AutoRepetitions>>>,
+}
+
+/// A memoized subtree layout
+#[derive(Debug, Clone)]
+pub(crate) struct MemoEntry {
+    /// The constraints the layout was computed under
+    pub(crate) constraints: MemoConstraints,
+    /// The size, baselines and margins computed for the subtree's root node
+    pub(crate) result: SizeBaselinesAndMargins,
+    /// The state of the subtree's nodes, in depth-first order starting with the root
+    ///
+    /// Empty if the entry was computed with [`RunMode::ComputeSize`], as descendants are not laid out in that case.
+    pub(crate) nodes: Vec<MemoizedNode>,
+}
+
+/// Memoized subtree layouts, keyed by the fingerprint of the subtree
+///
+/// Entries are kept for one layout pass after they were last used, so that identical subtrees that are regenerated
+/// between passes can reuse the layouts of the subtrees they replaced without the store growing without bound.
+#[derive(Debug, Default)]
+pub(crate) struct SubtreeMemo {
+    /// Entries that were stored or used during the previous layout pass
+    previous: BTreeMap<u64, Vec<MemoEntry>>,
+    /// Entries that have been stored or used during the current layout pass
+    current: BTreeMap<u64, Vec<MemoEntry>>,
+}
+
+impl SubtreeMemo {
+    /// Starts a new layout pass, discarding entries that were not used during the previous pass
+    pub(crate) fn start_pass(&mut self) {
+        self.previous = core::mem::take(&mut self.current);
+    }
+
+    /// Discards all entries
+    pub(crate) fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// Returns the memoized layout of a subtree with the given `fingerprint` under the given `constraints`, if any
+    pub(crate) fn get(&mut self, fingerprint: u64, constraints: &MemoConstraints) -> Option<&MemoEntry> {
+        let in_current = self
+            .current
+            .get(&fingerprint)
+            .is_some_and(|entries| entries.iter().any(|entry| entry.constraints == *constraints));
+
+        // Entries that are reused are carried over into the current pass
+        if !in_current {
+            let previous_entries = self.previous.get_mut(&fingerprint)?;
+            let index = previous_entries.iter().position(|entry| entry.constraints == *constraints)?;
+            let entry = previous_entries.swap_remove(index);
+            self.current.entry(fingerprint).or_default().push(entry);
+        }
+
+        self.current.get(&fingerprint)?.iter().find(|entry| entry.constraints == *constraints)
+    }
+
+    /// Stores the layout of a subtree with the given `fingerprint`
+    pub(crate) fn insert(&mut self, fingerprint: u64, entry: MemoEntry) {
+        let entries = self.current.entry(fingerprint).or_default();
+        entries.retain(|existing| existing.constraints != entry.constraints);
+        entries.push(entry);
+    }
+}
@@ -1,7 +1,117 @@
 //! Contains the default implementation of [LayoutTree](crate::tree::LayoutTree), [Taffy](crate::tree::Taffy), and the error type for Taffy.
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod accessibility;
+#[cfg(feature = "async_layout")]
+mod async_layout;
+#[cfg(feature = "grid")]
+mod auto_repeat;
+mod backup;
+#[cfg(feature = "browser_harness")]
+pub mod browser_harness;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod compact;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod custom_layout;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod custom_units;
+mod damage;
+#[cfg(feature = "std")]
+mod double_buffer;
 mod error;
+mod explain;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod flat_layout;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod focus;
+mod fork;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod json;
+mod layout_map;
+#[cfg(feature = "markup")]
+pub mod markup;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod memo;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod pool;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod quantized;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod reconcile;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod scheduler;
+mod scroll;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod snapshot;
+mod stats;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod style_classes;
+mod style_guard;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod subtree_builder;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod tags;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod trace;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod transaction;
 mod tree;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod virtual_list;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod walk;
+#[cfg(feature = "std")]
+mod worker;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use accessibility::{AccessibleNode, AccessibleVisibility};
+#[cfg(feature = "async_layout")]
+pub use async_layout::LayoutFuture;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use custom_units::CustomUnitResolver;
+pub(crate) use damage::DamageTracker;
+pub use damage::MAX_DAMAGE_RECTS;
+#[cfg(feature = "std")]
+pub(crate) use double_buffer::LayoutBuffers;
+#[cfg(feature = "std")]
+pub use double_buffer::LayoutReader;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use error::ContextualError;
 pub use error::{TaffyError, TaffyResult};
-pub use tree::{Taffy, TaffyChildIter};
+pub(crate) use explain::Explanation;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use flat_layout::FlatLayout;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use focus::FocusDirection;
+pub use fork::SpeculativeFork;
+pub use layout_map::LayoutMap;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) use memo::{FingerprintHasher, MemoConstraints, MemoEntry, MemoizedNode, SubtreeMemo};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) use pool::NodePool;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use quantized::{QUANTIZED_HEADER_SIZE, QUANTIZED_RECORD_SIZE};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use reconcile::NodeDescription;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use scheduler::{RelayoutReport, RelayoutScheduler};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use snapshot::{SnapshotNode, TreeDelta, TreeSnapshot};
+pub use stats::LayoutStats;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) use style_classes::{NodeClasses, StyleClass};
+pub use style_guard::StyleGuard;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use subtree_builder::{PendingNode, SubtreeBuilder};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use tags::TaggedDescendants;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use trace::{LayoutTrace, TracedMeasurement, TracedNode};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use transaction::Transaction;
+pub(crate) use tree::TaffyView;
+pub use tree::{LayoutProgress, Taffy, TaffyChildIter, TaffyIter};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use virtual_list::VirtualChildren;
+#[cfg(feature = "std")]
+pub use worker::{LayoutSnapshot, LayoutWorker};
@@ -0,0 +1,54 @@
+//! Reusing the allocations of removed nodes for the nodes created after them
+use crate::tree::NodeId;
+use crate::util::sys::{ChildrenVec, Vec};
+
+use super::Taffy;
+
+/// The allocations kept from removed nodes, see [`Taffy::set_node_pool_capacity`]
+#[derive(Default)]
+pub(crate) struct NodePool {
+    /// The maximum number of allocations kept
+    capacity: usize,
+    /// The emptied lists of children of removed nodes
+    children: Vec<ChildrenVec<NodeId>>,
+}
+
+impl NodePool {
+    /// Takes a list of children out of the pool, if there is one
+    #[inline]
+    pub(crate) fn take_children(&mut self) -> Option<ChildrenVec<NodeId>> {
+        self.children.pop()
+    }
+
+    /// Keeps the list of children of a removed node in the pool, unless the pool is full or the list never allocated
+    #[inline]
+    pub(crate) fn recycle_children(&mut self, mut children: ChildrenVec<NodeId>) {
+        if self.children.len() < self.capacity && children.capacity() > 0 {
+            children.clear();
+            self.children.push(children);
+        }
+    }
+
+    /// Frees the allocations kept in the pool
+    pub(crate) fn clear(&mut self) {
+        self.children = Vec::new();
+    }
+}
+
+impl Taffy {
+    /// Sets the number of removed nodes whose allocations are kept to be reused by the nodes created after them
+    ///
+    /// The storage of a removed node's style, layout and cache is always reused by the next node created, but the list of
+    /// its children is freed unless the pool has room for it. Keeping these lists avoids reallocating them when nodes
+    /// are recreated often (for example list rows that are rebuilt every frame). The pool is disabled (with a capacity
+    /// of zero) by default. Reducing the capacity frees the allocations that no longer fit.
+    pub fn set_node_pool_capacity(&mut self, capacity: usize) {
+        self.node_pool.capacity = capacity;
+        self.node_pool.children.truncate(capacity);
+    }
+
+    /// Returns the number of allocations currently kept in the node pool, see [`Taffy::set_node_pool_capacity`]
+    pub fn node_pool_len(&self) -> usize {
+        self.node_pool.children.len()
+    }
+}
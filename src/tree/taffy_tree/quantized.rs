@@ -0,0 +1,73 @@
+//! Export of computed layouts to a compact binary buffer of fixed-point rectangles, for use by other processes such
+//! as a compositor
+use crate::tree::NodeId;
+use crate::util::sys::{round, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// The size in bytes of the header of a buffer produced by [`Taffy::layout_to_quantized`]
+pub const QUANTIZED_HEADER_SIZE: usize = 8;
+
+/// The size in bytes of each node record of a buffer produced by [`Taffy::layout_to_quantized`]
+pub const QUANTIZED_RECORD_SIZE: usize = 20;
+
+/// The largest number of fractional bits that quantized values can have
+const MAX_FRACTIONAL_BITS: u8 = 15;
+
+impl Taffy {
+    /// Serializes the computed layouts of `root` and its descendants to a tightly packed buffer of quantized rectangles
+    ///
+    /// Positions and sizes are stored as fixed-point numbers with `fractional_bits` fractional bits (at most 15; larger
+    /// values are treated as 15), so that with 0 fractional bits they are whole pixels and with 4 they are multiples of
+    /// 1/16 of a pixel. Values are rounded to the nearest representable number, and saturate at the bounds of their
+    /// type. Non-finite values are stored as 0.
+    ///
+    /// The buffer starts with an 8 byte header, followed by one 20 byte record for each node of the subtree in
+    /// depth-first order, starting with `root`. All integers are little-endian.
+    ///
+    /// | Offset | Type    | Header field                                   |
+    /// |--------|---------|------------------------------------------------|
+    /// | 0      | `u32`   | The number of records                          |
+    /// | 4      | `u8`    | The number of fractional bits                  |
+    /// | 5      | 3 bytes | Reserved, always 0                             |
+    ///
+    /// | Offset | Type  | Record field                                                                        |
+    /// |--------|-------|-------------------------------------------------------------------------------------|
+    /// | 0      | `u64` | The [`NodeId`] of the node, as a `u64`                                              |
+    /// | 8      | `u32` | The index of the record of the node's parent, or `u32::MAX` for `root`              |
+    /// | 12     | `i16` | The x coordinate of the node's top-left corner, relative to the top-left of `root`  |
+    /// | 14     | `i16` | The y coordinate of the node's top-left corner, relative to the top-left of `root`  |
+    /// | 16     | `u16` | The width of the node                                                               |
+    /// | 18     | `u16` | The height of the node                                                              |
+    ///
+    /// Coordinates are absolute so that the rectangles can be drawn without walking the tree, and y increases
+    /// downwards whichever [`CoordinateOrigin`](crate::tree::CoordinateOrigin) the tree uses. They don't take scroll
+    /// offsets into account.
+    pub fn layout_to_quantized(&self, root: NodeId, fractional_bits: u8) -> TaffyResult<Vec<u8>> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+        let fractional_bits = fractional_bits.min(MAX_FRACTIONAL_BITS);
+        let scale = (1u32 << fractional_bits) as f32;
+        let quantize = |value: f32| round(value * scale);
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&[0; QUANTIZED_HEADER_SIZE]);
+        buffer[4] = fractional_bits;
+
+        let mut record_count: u32 = 0;
+        self.walk_absolute_layouts(root, |visited| {
+            let size = visited.layout.size;
+            buffer.extend_from_slice(&u64::from(visited.node).to_le_bytes());
+            buffer.extend_from_slice(&visited.parent_index.to_le_bytes());
+            buffer.extend_from_slice(&(quantize(visited.position.x) as i16).to_le_bytes());
+            buffer.extend_from_slice(&(quantize(visited.position.y) as i16).to_le_bytes());
+            buffer.extend_from_slice(&(quantize(size.width) as u16).to_le_bytes());
+            buffer.extend_from_slice(&(quantize(size.height) as u16).to_le_bytes());
+            record_count += 1;
+        });
+
+        buffer[0..4].copy_from_slice(&record_count.to_le_bytes());
+        Ok(buffer)
+    }
+}
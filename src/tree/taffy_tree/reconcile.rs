@@ -0,0 +1,151 @@
+//! Reconciliation of a [`Taffy`] tree against a declarative description of the desired tree
+use slotmap::{DefaultKey, SecondaryMap};
+
+use crate::style::Style;
+use crate::tree::NodeId;
+use crate::util::sys::{String, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult, Transaction};
+
+/// A declarative description of a node and its descendants, used to update a [`Taffy`] tree with [`Taffy::reconcile`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeDescription {
+    /// The key of the node, used to match it against the existing nodes of the tree (see [`Taffy::set_key`])
+    pub key: Option<String>,
+    /// The style of the node
+    pub style: Style,
+    /// Whether the node needs to be measured (see [`Taffy::set_needs_measure`])
+    pub needs_measure: bool,
+    /// The descriptions of the node's children
+    pub children: Vec<NodeDescription>,
+}
+
+impl NodeDescription {
+    /// Creates a description of an unkeyed node without children
+    #[must_use]
+    pub fn new(style: Style) -> Self {
+        Self { style, ..Default::default() }
+    }
+}
+
+impl Taffy {
+    /// Updates the tree in place to match the provided `description`, returning the root node of the updated tree
+    ///
+    /// Existing nodes are reused wherever possible, which retains their caches and layouts if they are unchanged:
+    ///
+    ///   - Keyed descriptions are matched against the node with the same key anywhere in the tree (so keyed nodes may
+    ///     move between parents, and are detached from their previous parent). Newly created nodes are assigned the
+    ///     key of their description.
+    ///   - Unkeyed descriptions are matched by position against the unkeyed children of the matched parent node.
+    ///     An unkeyed root description is matched against `root`.
+    ///
+    /// Styles and children are only updated (and nodes only marked dirty) where they differ from the description. Nodes
+    /// that were part of the subtree of `root` but were not matched against any description are removed from the tree.
+    ///
+    /// Returns [`TaffyError::DuplicateKey`] if the description contains the same key more than once. The changes are
+    /// made in a [`Transaction`], so the tree is left unchanged if an error is returned.
+    pub fn reconcile(&mut self, root: Option<NodeId>, description: &NodeDescription) -> TaffyResult<NodeId> {
+        let mut transaction = self.transaction();
+        let new_root = reconcile_tree(&mut transaction, root, description)?;
+        transaction.commit();
+        Ok(new_root)
+    }
+}
+
+/// Reconciles the subtree of `root` against the `description` within the `transaction`
+fn reconcile_tree(
+    transaction: &mut Transaction<'_>,
+    root: Option<NodeId>,
+    description: &NodeDescription,
+) -> TaffyResult<NodeId> {
+    // Collect the existing subtree so that nodes which are no longer described can be removed afterwards
+    let mut stale: Vec<NodeId> = Vec::new();
+    if let Some(root) = root {
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            stale.push(node);
+            stack.extend(transaction.tree().children[node.into()].iter().copied());
+        }
+    }
+
+    let mut used: SecondaryMap<DefaultKey, ()> = SecondaryMap::new();
+    let root_candidate = root.filter(|root| transaction.tree().key(*root).is_none());
+    let new_root = reconcile_node(transaction, root_candidate, None, description, &mut used)?;
+
+    stale.retain(|node| !used.contains_key((*node).into()));
+    for node in stale {
+        transaction.remove(node)?;
+    }
+
+    Ok(new_root)
+}
+
+/// Reconciles a single node (and recursively, its children) against its `description`
+///
+/// `candidate` is the existing node that the description is matched against if it is unkeyed, and `parent` is the node
+/// that the reconciled node will be a child of (`None` for the root, which is left where it is).
+fn reconcile_node(
+    transaction: &mut Transaction<'_>,
+    candidate: Option<NodeId>,
+    parent: Option<NodeId>,
+    description: &NodeDescription,
+    used: &mut SecondaryMap<DefaultKey, ()>,
+) -> TaffyResult<NodeId> {
+    let existing = match &description.key {
+        Some(key) => transaction.tree().node_by_key(key),
+        None => candidate,
+    };
+
+    let node = match existing {
+        Some(node) => {
+            if used.insert(node.into(), ()).is_some() {
+                return Err(TaffyError::DuplicateKey { node });
+            }
+            // A keyed node may be matched anywhere in the tree, so it is detached from the parent it is moving away from
+            if let Some(previous_parent) = transaction.tree().parents[node.into()] {
+                if parent.is_some() && parent != Some(previous_parent) {
+                    transaction.remove_child(previous_parent, node)?;
+                }
+            }
+            if transaction.tree().nodes[node.into()].style != description.style {
+                transaction.set_style(node, description.style.clone())?;
+            }
+            if transaction.tree().nodes[node.into()].needs_measure != description.needs_measure {
+                transaction.set_needs_measure(node, description.needs_measure)?;
+            }
+            node
+        }
+        None => {
+            let node = transaction.new_leaf(description.style.clone())?;
+            used.insert(node.into(), ());
+            if let Some(key) = &description.key {
+                transaction.set_key(node, Some(key))?;
+            }
+            if description.needs_measure {
+                transaction.set_needs_measure(node, true)?;
+            }
+            node
+        }
+    };
+
+    // Unkeyed children are matched by position against the existing unkeyed children
+    let mut unkeyed_candidates: Vec<NodeId> = Vec::new();
+    let tree = transaction.tree();
+    unkeyed_candidates.extend(tree.children[node.into()].iter().copied().filter(|child| tree.key(*child).is_none()));
+    let mut unkeyed_candidates = unkeyed_candidates.into_iter();
+
+    let mut children: Vec<NodeId> = Vec::new();
+    for child_description in &description.children {
+        let candidate = match child_description.key {
+            Some(_) => None,
+            None => unkeyed_candidates.next(),
+        };
+        children.push(reconcile_node(transaction, candidate, Some(node), child_description, used)?);
+    }
+
+    // Setting an unchanged list of children leaves the node untouched
+    transaction.set_children(node, &children)?;
+
+    Ok(node)
+}
@@ -0,0 +1,135 @@
+//! Coalescing the dirty notifications of a frame into at most one relayout
+use crate::geometry::Size;
+use crate::style::AvailableSpace;
+use crate::tree::{InvalidationLevel, NodeId};
+use crate::util::sys::{BTreeMap, Vec};
+
+use super::{LayoutStats, Taffy, TaffyError, TaffyResult};
+
+/// What a relayout run by [`RelayoutScheduler::run`] did
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayoutReport {
+    /// The number of notifications received since the previous relayout, including duplicates
+    pub notifications: usize,
+    /// The number of distinct nodes that were marked dirty
+    pub dirty_nodes: usize,
+    /// The nodes whose layout changed, see [`Taffy::changed_layouts`]
+    pub changed: Vec<NodeId>,
+    /// The statistics of the layout computation, see [`Taffy::layout_stats`]
+    pub stats: LayoutStats,
+}
+
+/// Collects the changes that affect the layout of a tree over the course of a frame, and relayouts the tree at most
+/// once when the frame ends
+///
+/// Changes made through the methods of [`Taffy`] (such as [`Taffy::set_style`]) mark the affected nodes dirty
+/// themselves, but changes that happen outside of the tree, such as the text of a measured node changing, have to be
+/// reported. The scheduler receives these notifications from anywhere in the embedder through
+/// [`RelayoutScheduler::notify`], keeping only the most invasive [`InvalidationLevel`] reported for each node. At the
+/// end of the frame [`RelayoutScheduler::run`] marks each node dirty once, and computes the layout of the root if
+/// anything changed.
+#[derive(Debug, Clone)]
+pub struct RelayoutScheduler {
+    /// The root node that is laid out
+    root: NodeId,
+    /// The space available to the root
+    available_space: Size<AvailableSpace>,
+    /// The most invasive level of invalidation reported for each node since the previous relayout
+    dirty: BTreeMap<NodeId, InvalidationLevel>,
+    /// The number of notifications received since the previous relayout
+    notifications: usize,
+    /// Whether a relayout has been requested regardless of the notifications received
+    forced: bool,
+}
+
+impl RelayoutScheduler {
+    /// Creates a scheduler that lays out `root` in the given `available_space`
+    ///
+    /// The first call to [`RelayoutScheduler::run`] always computes the layout.
+    #[must_use]
+    pub fn new(root: NodeId, available_space: Size<AvailableSpace>) -> Self {
+        Self { root, available_space, dirty: BTreeMap::new(), notifications: 0, forced: true }
+    }
+
+    /// Returns the root node that is laid out
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Returns the space available to the root
+    pub fn available_space(&self) -> Size<AvailableSpace> {
+        self.available_space
+    }
+
+    /// Sets the space available to the root, scheduling a relayout if it changed (e.g. because the window was resized)
+    pub fn set_available_space(&mut self, available_space: Size<AvailableSpace>) {
+        if self.available_space != available_space {
+            self.available_space = available_space;
+            self.forced = true;
+        }
+    }
+
+    /// Reports that the layout of `node` is outdated, see [`Taffy::mark_dirty`]
+    pub fn notify(&mut self, node: NodeId) {
+        self.notify_with(node, InvalidationLevel::SelfSize);
+    }
+
+    /// Reports that the layout of `node` is outdated at the given [`InvalidationLevel`], see
+    /// [`Taffy::mark_dirty_with`]
+    ///
+    /// Reporting a node more than once only marks it dirty once, at the most invasive of the reported levels.
+    pub fn notify_with(&mut self, node: NodeId, level: InvalidationLevel) {
+        self.notifications += 1;
+        let entry = self.dirty.entry(node).or_insert(level);
+        *entry = (*entry).max(level);
+    }
+
+    /// Schedules a relayout even if no nodes have been reported
+    pub fn request_relayout(&mut self) {
+        self.forced = true;
+    }
+
+    /// Returns true if [`RelayoutScheduler::run`] would compute the layout of the root
+    ///
+    /// This is the case if nodes have been reported, a relayout has been requested, or the root of `taffy` is dirty
+    /// because the tree was changed directly.
+    pub fn needs_relayout(&self, taffy: &Taffy) -> bool {
+        self.forced
+            || !self.dirty.is_empty()
+            || (taffy.nodes.contains_key(self.root.into()) && taffy.dirty(self.root).unwrap_or(false))
+    }
+
+    /// Marks the reported nodes dirty and computes the layout of the root if needed, returning what the relayout did,
+    /// or `None` if no relayout was needed
+    ///
+    /// Reported nodes that have since been removed from the tree are ignored. The notifications are cleared once the
+    /// layout has been computed, so if the computation fails they are kept for the next call.
+    pub fn run(&mut self, taffy: &mut Taffy) -> TaffyResult<Option<RelayoutReport>> {
+        if !taffy.nodes.contains_key(self.root.into()) {
+            return Err(TaffyError::InvalidInputNode(self.root));
+        }
+        if !self.needs_relayout(taffy) {
+            return Ok(None);
+        }
+
+        let dirty_nodes = self.dirty.keys().filter(|node| taffy.nodes.contains_key((**node).into())).count();
+        // Marking the nodes dirty within a batch walks each ancestor chain at most once
+        taffy.update(|taffy| {
+            for (node, level) in &self.dirty {
+                let _ = taffy.mark_dirty_with(*node, *level);
+            }
+        });
+        taffy.compute_layout(self.root, self.available_space)?;
+
+        let report = RelayoutReport {
+            notifications: self.notifications,
+            dirty_nodes,
+            changed: taffy.changed_layouts().collect(),
+            stats: taffy.layout_stats(),
+        };
+        self.dirty.clear();
+        self.notifications = 0;
+        self.forced = false;
+        Ok(Some(report))
+    }
+}
@@ -0,0 +1,175 @@
+//! Scroll offsets of scroll containers, and the queries that take them into account
+use crate::geometry::Point;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::geometry::Rect;
+use crate::tree::NodeId;
+use crate::util::sys::Vec;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::util::sys::{f32_max, f32_min};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+impl Taffy {
+    /// Sets the scroll offset of the node: the distance by which its contents have been scrolled to the right and
+    /// downwards. Setting a zero offset removes it.
+    ///
+    /// Scroll offsets don't affect layout, so no node is marked dirty: [`Layout::location`](crate::tree::Layout) stays
+    /// relative to the unscrolled parent. Instead the offsets are applied by [`Taffy::absolute_location`] and
+    /// [`Taffy::hit_test`], so that descendants of scrolled containers are reported where they are displayed.
+    pub fn set_scroll_offset(&mut self, node: NodeId, offset: Point<f32>) -> TaffyResult<()> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        if offset == Point::ZERO {
+            self.scroll_offsets.remove(node.into());
+        } else {
+            self.scroll_offsets.insert(node.into(), offset);
+        }
+        self.clear_fingerprints(node);
+        Ok(())
+    }
+
+    /// Returns the scroll offset that has been set for the node with [`Taffy::set_scroll_offset`], or zero
+    pub fn scroll_offset(&self, node: NodeId) -> Point<f32> {
+        self.scroll_offsets.get(node.into()).copied().unwrap_or(Point::ZERO)
+    }
+
+    /// Returns the offset of the node's top-left corner from the top-left corner of the root of its tree, with y
+    /// increasing downwards, after the contents of each of its ancestors have been scrolled by their scroll offset
+    pub fn absolute_location(&self, node: NodeId) -> TaffyResult<Point<f32>> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+
+        let mut location = Point::ZERO;
+        let mut current = node;
+        while let Some(parent) = self.parents[current.into()] {
+            let offset = self.top_left_location(current, parent);
+            let scroll_offset = self.scroll_offset(parent);
+            location.x += offset.x - scroll_offset.x;
+            location.y += offset.y - scroll_offset.y;
+            current = parent;
+        }
+        Ok(location)
+    }
+
+    /// Returns the deepest node under `point`, searching the subtree of `root`, or `None` if `point` is outside of it.
+    /// `point` is relative to the top-left corner of `root`, with y increasing downwards.
+    ///
+    /// Later siblings are drawn on top of earlier ones and so are hit first. The contents of scroll containers are
+    /// offset by their [scroll offset](Taffy::set_scroll_offset), and can only be hit inside of the container in the
+    /// axes in which it clips them (see [`Overflow`](crate::style::Overflow)). Other nodes can be hit where their
+    /// descendants overflow them.
+    pub fn hit_test(&self, root: NodeId, point: Point<f32>) -> TaffyResult<Option<NodeId>> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+        Ok(self.hit_test_node(root, point))
+    }
+
+    /// Returns the deepest node under `point` in the subtree of `root`, where `point` is relative to the top-left
+    /// corner of `root`
+    ///
+    /// The subtree is searched with an explicit stack, so that arbitrarily deep trees don't overflow the call stack.
+    /// Each entry holds a node along with the point relative to it if its subtree is still to be searched, or `None` if
+    /// the point is inside the node and none of its descendants were hit.
+    fn hit_test_node(&self, root: NodeId, point: Point<f32>) -> Option<NodeId> {
+        let mut stack: Vec<(NodeId, Option<Point<f32>>)> = Vec::new();
+        stack.push((root, Some(point)));
+        while let Some((node, point)) = stack.pop() {
+            let Some(point) = point else { return Some(node) };
+            let size = self.nodes[node.into()].layout.size;
+            let overflow = self.nodes[node.into()].style.overflow;
+            let inside =
+                Point { x: point.x >= 0.0 && point.x < size.width, y: point.y >= 0.0 && point.y < size.height };
+            if inside.x && inside.y {
+                stack.push((node, None));
+            }
+
+            let children_are_clipped =
+                (overflow.x.is_scroll_container() && !inside.x) || (overflow.y.is_scroll_container() && !inside.y);
+            if !children_are_clipped {
+                // Children are pushed in order, so that later siblings are searched first
+                let scroll_offset = self.scroll_offset(node);
+                for child in self.children[node.into()].iter() {
+                    let offset = self.top_left_location(*child, node);
+                    let child_point =
+                        Point { x: point.x + scroll_offset.x - offset.x, y: point.y + scroll_offset.y - offset.y };
+                    stack.push((*child, Some(child_point)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the nodes of the subtree of `root`, including `root` itself, whose boxes overlap `rect`, in tree order.
+    /// `rect` is relative to the top-left corner of `root`, with y increasing downwards.
+    ///
+    /// As for [`Taffy::hit_test`], the contents of scroll containers are offset by their scroll offset, and are only
+    /// found inside of the container in the axes in which it clips them. Boxes only overlap if they share some area, so
+    /// nodes that merely touch the edges of `rect` aren't returned. This is useful for culling nodes outside of the
+    /// visible area, or for rubber-band selection. Every node of the subtree that isn't clipped away by a scroll
+    /// container is visited, as any node may overflow its ancestors.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn nodes_in_rect(&self, root: NodeId, rect: Rect<f32>) -> TaffyResult<Vec<NodeId>> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+        let mut nodes = Vec::new();
+        self.collect_nodes_in_rect(root, rect, &mut nodes);
+        Ok(nodes)
+    }
+
+    /// Appends the nodes of the subtree of `root` whose boxes overlap `rect` to `nodes`, where `rect` is relative to
+    /// the top-left corner of `root`
+    ///
+    /// The subtree is traversed with an explicit stack, so that arbitrarily deep trees don't overflow the call stack.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn collect_nodes_in_rect(&self, root: NodeId, rect: Rect<f32>, nodes: &mut Vec<NodeId>) {
+        let mut stack: Vec<(NodeId, Rect<f32>)> = Vec::new();
+        stack.push((root, rect));
+        while let Some((node, rect)) = stack.pop() {
+            let size = self.nodes[node.into()].layout.size;
+            let overflow = self.nodes[node.into()].style.overflow;
+            if rect.left < size.width && rect.right > 0.0 && rect.top < size.height && rect.bottom > 0.0 {
+                nodes.push(node);
+            }
+
+            // Scroll containers only show their children inside of themselves in the axes in which they clip them
+            let mut visible = rect;
+            if overflow.x.is_scroll_container() {
+                visible.left = f32_max(visible.left, 0.0);
+                visible.right = f32_min(visible.right, size.width);
+            }
+            if overflow.y.is_scroll_container() {
+                visible.top = f32_max(visible.top, 0.0);
+                visible.bottom = f32_min(visible.bottom, size.height);
+            }
+            if visible.left >= visible.right || visible.top >= visible.bottom {
+                continue;
+            }
+
+            // Children are pushed in reverse, so that they are visited in tree order
+            let scroll_offset = self.scroll_offset(node);
+            for child in self.children[node.into()].iter().rev() {
+                let offset = self.top_left_location(*child, node);
+                let shift = Point { x: scroll_offset.x - offset.x, y: scroll_offset.y - offset.y };
+                let child_rect = Rect {
+                    left: visible.left + shift.x,
+                    right: visible.right + shift.x,
+                    top: visible.top + shift.y,
+                    bottom: visible.bottom + shift.y,
+                };
+                stack.push((*child, child_rect));
+            }
+        }
+    }
+
+    /// Returns the offset of the node's top-left corner from its parent's top-left corner, whichever
+    /// [`CoordinateOrigin`](crate::tree::CoordinateOrigin) its location uses
+    pub(super) fn top_left_location(&self, node: NodeId, parent: NodeId) -> Point<f32> {
+        let layout = &self.nodes[node.into()].layout;
+        let parent_size = self.nodes[parent.into()].layout.size;
+        self.config.coordinate_origin.location_to_top_left(layout.location, layout.size, parent_size)
+    }
+}
@@ -0,0 +1,148 @@
+//! Snapshots of the structure, styles and layouts of a subtree, and the deltas between two snapshots
+use crate::style::Style;
+use crate::tree::{Layout, NodeId};
+use crate::util::sys::{BTreeMap, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// The structure, styles and layouts of a subtree at a point in time, taken with [`Taffy::snapshot`]
+///
+/// Snapshots are keyed by [`NodeId`], so that two snapshots of the same tree can be compared with
+/// [`TreeSnapshot::delta`]. Rather than sending a whole snapshot every time the tree changes, tools such as remote
+/// inspectors can send a snapshot once and then send the [`TreeDelta`]s between successive snapshots, which the
+/// receiving side applies with [`TreeSnapshot::apply`]. With the `serde` feature enabled both can be serialized in
+/// any format supported by serde.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TreeSnapshot {
+    /// The root node of the subtree
+    pub root: NodeId,
+    /// Every node of the subtree, including the root
+    pub nodes: BTreeMap<NodeId, SnapshotNode>,
+}
+
+/// A node recorded in a [`TreeSnapshot`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapshotNode {
+    /// The style of the node
+    pub style: Style,
+    /// The children of the node, in order
+    pub children: Vec<NodeId>,
+    /// The layout of the node, as of the last layout computation
+    pub layout: Layout,
+}
+
+/// The changes between two [`TreeSnapshot`]s of the same tree, computed by [`TreeSnapshot::delta`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TreeDelta {
+    /// The root node of the newer snapshot
+    pub root: NodeId,
+    /// The nodes that are only in the newer snapshot, along with their contents
+    pub added: Vec<(NodeId, SnapshotNode)>,
+    /// The nodes that are only in the older snapshot
+    pub removed: Vec<NodeId>,
+    /// The new styles of the nodes whose style changed
+    pub styles: Vec<(NodeId, Style)>,
+    /// The new children of the nodes whose children changed
+    pub children: Vec<(NodeId, Vec<NodeId>)>,
+    /// The new layouts of the nodes whose layout changed
+    pub layouts: Vec<(NodeId, Layout)>,
+}
+
+impl TreeDelta {
+    /// Returns true if the snapshots that the delta was computed from are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.styles.is_empty()
+            && self.children.is_empty()
+            && self.layouts.is_empty()
+    }
+}
+
+impl TreeSnapshot {
+    /// Computes the changes from this snapshot to a `newer` snapshot of the same tree
+    ///
+    /// Nodes are matched by id. The changes of nodes that are in both snapshots are listed in order of their ids.
+    #[must_use]
+    pub fn delta(&self, newer: &TreeSnapshot) -> TreeDelta {
+        let mut delta = TreeDelta {
+            root: newer.root,
+            added: Vec::new(),
+            removed: self.nodes.keys().filter(|node| !newer.nodes.contains_key(*node)).copied().collect(),
+            styles: Vec::new(),
+            children: Vec::new(),
+            layouts: Vec::new(),
+        };
+        for (node, new) in &newer.nodes {
+            let Some(old) = self.nodes.get(node) else {
+                delta.added.push((*node, new.clone()));
+                continue;
+            };
+            if old.style != new.style {
+                delta.styles.push((*node, new.style.clone()));
+            }
+            if old.children != new.children {
+                delta.children.push((*node, new.children.clone()));
+            }
+            if old.layout != new.layout {
+                delta.layouts.push((*node, new.layout));
+            }
+        }
+        delta
+    }
+
+    /// Applies a delta computed by [`TreeSnapshot::delta`], turning this snapshot into the newer snapshot that the
+    /// delta was computed from
+    ///
+    /// The delta should be applied to the same snapshot that it was computed from. Changes to nodes that aren't in this
+    /// snapshot are ignored.
+    pub fn apply(&mut self, delta: &TreeDelta) {
+        self.root = delta.root;
+        for node in &delta.removed {
+            self.nodes.remove(node);
+        }
+        for (node, added) in &delta.added {
+            self.nodes.insert(*node, added.clone());
+        }
+        for (node, style) in &delta.styles {
+            if let Some(snapshot_node) = self.nodes.get_mut(node) {
+                snapshot_node.style = style.clone();
+            }
+        }
+        for (node, children) in &delta.children {
+            if let Some(snapshot_node) = self.nodes.get_mut(node) {
+                snapshot_node.children = children.clone();
+            }
+        }
+        for (node, layout) in &delta.layouts {
+            if let Some(snapshot_node) = self.nodes.get_mut(node) {
+                snapshot_node.layout = *layout;
+            }
+        }
+    }
+}
+
+impl Taffy {
+    /// Takes a snapshot of the structure, styles and layouts of `root` and its descendants
+    ///
+    /// Successive snapshots can be compared with [`TreeSnapshot::delta`] to find what changed in between.
+    pub fn snapshot(&self, root: NodeId) -> TaffyResult<TreeSnapshot> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+
+        let mut nodes = BTreeMap::new();
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            let children: Vec<NodeId> = self.children[node.into()].to_vec();
+            stack.extend(children.iter().copied());
+            let node_data = &self.nodes[node.into()];
+            nodes.insert(node, SnapshotNode { style: node_data.style.clone(), children, layout: node_data.layout });
+        }
+        Ok(TreeSnapshot { root, nodes })
+    }
+}
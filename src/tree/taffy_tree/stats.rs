@@ -0,0 +1,32 @@
+//! Statistics about the most recent layout computation of a [`Taffy`](crate::Taffy) tree
+#[cfg(feature = "alloc_audit")]
+use crate::tree::AllocationCounts;
+
+/// Statistics gathered during the most recent call to [`Taffy::compute_layout`](crate::Taffy::compute_layout)
+///
+/// Returned by [`Taffy::layout_stats`](crate::Taffy::layout_stats). Useful for monitoring layout performance.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayoutStats {
+    /// The number of times a node was laid out or sized, including results that were served from the cache
+    pub nodes_visited: usize,
+    /// The number of times a node's measure function was called
+    pub measure_calls: usize,
+    /// The number of times a node's layout or size was served from its cache
+    pub cache_hits: usize,
+    /// The deepest level of recursion reached, where the root node is at depth `1`
+    pub deepest_recursion: usize,
+    /// The wall time taken to compute the layout
+    #[cfg(feature = "std")]
+    pub duration: std::time::Duration,
+    /// The number of collections allocated on the heap while computing the layout, by site
+    #[cfg(feature = "alloc_audit")]
+    pub allocations: AllocationCounts,
+    /// The number of heap allocations (including reallocations) made by the thread that computed the layout
+    ///
+    /// Always zero unless the global allocator reports its allocations, see
+    /// [`record_heap_allocation`](crate::tree::record_heap_allocation).
+    #[cfg(all(feature = "alloc_audit", feature = "std"))]
+    pub heap_allocations: usize,
+    /// The current level of recursion, used to compute `deepest_recursion`
+    pub(crate) current_depth: usize,
+}
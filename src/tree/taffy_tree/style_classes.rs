@@ -0,0 +1,139 @@
+//! Named style classes that are defined once and applied on top of the styles of the nodes assigned them
+use slotmap::DefaultKey;
+
+use crate::style::Style;
+use crate::tree::NodeId;
+use crate::util::sys::{Box, String, Vec};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// The rule of a style class, which sets the properties of the class on a style
+pub(crate) type StyleClass = Box<dyn Fn(&mut Style) + Send + Sync>;
+
+/// The style classes assigned to a node, along with the node's own style
+pub(crate) struct NodeClasses {
+    /// The names of the classes, in the order that they are applied
+    classes: Vec<String>,
+    /// The style of the node before its classes are applied
+    pub(crate) own_style: Style,
+}
+
+impl Taffy {
+    /// Defines the style class called `name`, replacing any existing class of that name
+    ///
+    /// A class is a rule that sets some properties of a style, such as a theme's padding and colors of borders, and is
+    /// applied on top of the own style of each node assigned the class with [`Taffy::set_style_classes`]. The style of
+    /// every node assigned the class is updated, and the nodes whose style changes are marked dirty, so themes and
+    /// design tokens can be switched without setting the style of each node. Rules should set properties rather than
+    /// adjust them, as they are applied again each time the node's own style or classes change.
+    ///
+    /// ```
+    /// use taffy::prelude::*;
+    ///
+    /// let mut taffy = Taffy::new();
+    /// taffy.define_style_class("card", |style| style.padding = length(8.0));
+    /// let node = taffy.new_leaf(Style::default()).unwrap();
+    /// taffy.set_style_classes(node, &["card"]).unwrap();
+    /// assert_eq!(taffy.style(node).unwrap().padding, length(8.0));
+    ///
+    /// taffy.define_style_class("card", |style| style.padding = length(12.0));
+    /// assert_eq!(taffy.style(node).unwrap().padding, length(12.0));
+    /// ```
+    pub fn define_style_class(&mut self, name: &str, rule: impl Fn(&mut Style) + Send + Sync + 'static) {
+        self.style_classes.insert(String::from(name), Box::new(rule));
+        self.restyle_nodes_with_class(name);
+    }
+
+    /// Removes the definition of the style class called `name`, returning whether it was defined
+    ///
+    /// Nodes keep their assignment to the class, which no longer affects their style unless it is defined again.
+    pub fn remove_style_class(&mut self, name: &str) -> bool {
+        let removed = self.style_classes.remove(name).is_some();
+        if removed {
+            self.restyle_nodes_with_class(name);
+        }
+        removed
+    }
+
+    /// Returns whether a style class called `name` has been defined with [`Taffy::define_style_class`]
+    pub fn has_style_class(&self, name: &str) -> bool {
+        self.style_classes.contains_key(name)
+    }
+
+    /// Assigns the style classes called `classes` to the node, which are applied on top of its own style in order
+    ///
+    /// The node's own style is the style it had before it was first assigned classes, or the style set with
+    /// [`Taffy::set_style`] or modified with [`Taffy::style_mut`] since, while [`Taffy::style`] returns the style with
+    /// its classes applied. Classes that haven't been defined are ignored until they are. Passing no classes restores
+    /// the node's own style.
+    pub fn set_style_classes(&mut self, node: NodeId, classes: &[&str]) -> TaffyResult<()> {
+        let key = node.into();
+        if !self.nodes.contains_key(key) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+
+        let classes: Vec<String> = classes.iter().map(|class| String::from(*class)).collect();
+        if classes.is_empty() {
+            return match self.node_classes.remove(key) {
+                Some(node_classes) => self.replace_style(node, node_classes.own_style),
+                None => Ok(()),
+            };
+        }
+        match self.node_classes.get_mut(key) {
+            Some(node_classes) => node_classes.classes = classes,
+            None => {
+                let own_style = self.nodes[key].style.clone();
+                self.node_classes.insert(key, NodeClasses { classes, own_style });
+            }
+        }
+        self.apply_style_classes(key)
+    }
+
+    /// Returns the names of the style classes assigned to the node with [`Taffy::set_style_classes`], in the order that
+    /// they are applied
+    pub fn style_classes(&self, node: NodeId) -> &[String] {
+        self.node_classes.get(node.into()).map_or(&[], |node_classes| node_classes.classes.as_slice())
+    }
+
+    /// Returns the style of the node before its style classes are applied, which is its [`Style`] if it has none
+    pub fn own_style(&self, node: NodeId) -> TaffyResult<&Style> {
+        let key = node.into();
+        if !self.nodes.contains_key(key) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        Ok(self.node_classes.get(key).map_or(&self.nodes[key].style, |node_classes| &node_classes.own_style))
+    }
+
+    /// Sets the style of a node with style classes to its own style with its classes applied, marking it dirty if the
+    /// style changes
+    pub(crate) fn apply_style_classes(&mut self, key: DefaultKey) -> TaffyResult<()> {
+        let Some(node_classes) = self.node_classes.get(key) else {
+            return Ok(());
+        };
+        let mut style = node_classes.own_style.clone();
+        for class in &node_classes.classes {
+            if let Some(rule) = self.style_classes.get(class) {
+                rule(&mut style);
+            }
+        }
+
+        if style == self.nodes[key].style {
+            return Ok(());
+        }
+        self.replace_style(NodeId::from(key), style)
+    }
+
+    /// Applies the style classes of every node that has been assigned the class called `name` again
+    fn restyle_nodes_with_class(&mut self, name: &str) {
+        let keys: Vec<DefaultKey> = self
+            .node_classes
+            .iter()
+            .filter(|(_, node_classes)| node_classes.classes.iter().any(|class| class == name))
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            // The node is known to exist, as it has style classes
+            let _ = self.apply_style_classes(key);
+        }
+    }
+}
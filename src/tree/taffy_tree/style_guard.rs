@@ -0,0 +1,82 @@
+//! In-place modification of a node's style, which marks the node dirty once the modification is done
+use core::ops::{Deref, DerefMut};
+
+use crate::style::Style;
+use crate::tree::{InvalidationLevel, NodeId};
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// Mutable access to the [`Style`] of a node, created by [`Taffy::style_mut`]
+///
+/// The style is modified in place, which avoids cloning it (including its grid track lists). When the guard is
+/// dropped the node is marked dirty, unless the style was never accessed mutably. If the node has style classes (see
+/// [`Taffy::set_style_classes`]), the guard gives access to its own style, and its classes are applied again on drop.
+pub struct StyleGuard<'a> {
+    /// The tree containing the node
+    taffy: &'a mut Taffy,
+    /// The node whose style is being modified
+    node: NodeId,
+    /// Whether the style has been accessed mutably, in which case it may have changed
+    modified: bool,
+}
+
+impl Taffy {
+    /// Returns a [`StyleGuard`] giving mutable access to the [`Style`] of the provided `node`, which marks the node dirty
+    /// when it is dropped
+    ///
+    /// ```
+    /// use taffy::prelude::*;
+    ///
+    /// let mut taffy = Taffy::new();
+    /// let node = taffy.new_leaf(Style::default()).unwrap();
+    /// taffy.style_mut(node).unwrap().size.width = length(100.0);
+    /// assert_eq!(taffy.style(node).unwrap().size.width, length(100.0));
+    /// ```
+    pub fn style_mut(&mut self, node: NodeId) -> TaffyResult<StyleGuard<'_>> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        Ok(StyleGuard { taffy: self, node, modified: false })
+    }
+}
+
+impl Deref for StyleGuard<'_> {
+    type Target = Style;
+
+    fn deref(&self) -> &Style {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(node_classes) = self.taffy.node_classes.get(self.node.into()) {
+            return &node_classes.own_style;
+        }
+        &self.taffy.nodes[self.node.into()].style
+    }
+}
+
+impl DerefMut for StyleGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Style {
+        self.modified = true;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if self.taffy.node_classes.contains_key(self.node.into()) {
+            return &mut self.taffy.node_classes[self.node.into()].own_style;
+        }
+        &mut self.taffy.nodes[self.node.into()].style
+    }
+}
+
+impl Drop for StyleGuard<'_> {
+    fn drop(&mut self) {
+        if !self.modified {
+            return;
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if self.taffy.node_classes.contains_key(self.node.into()) {
+            // The node is known to exist, so applying its classes can't fail
+            let _ = self.taffy.apply_style_classes(self.node.into());
+            return;
+        }
+
+        self.taffy.track_context_units(self.node.into());
+        // The node is known to exist, so marking it dirty can't fail
+        let _ = self.taffy.mark_dirty_with(self.node, InvalidationLevel::SelfSize);
+    }
+}
@@ -0,0 +1,132 @@
+//! Building subtrees away from the tree, so that they can be built on other threads and inserted in a single step
+use crate::style::Style;
+use crate::tree::{MeasureFunc, NodeId};
+use crate::util::sys::Vec;
+
+use super::{Taffy, TaffyResult};
+
+/// A node of a [`SubtreeBuilder`], which becomes a [`NodeId`] once the subtree is inserted into a tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PendingNode(usize);
+
+impl PendingNode {
+    /// Returns the index of the node in its [`SubtreeBuilder`], which is the index of its id in the list returned by
+    /// [`Taffy::insert_subtree`]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A node of a [`SubtreeBuilder`]
+struct BuilderNode {
+    /// The style of the node
+    style: Style,
+    /// The function that measures the node, if it is a leaf with content
+    measure: Option<MeasureFunc>,
+    /// The children of the node
+    children: Vec<PendingNode>,
+    /// The parent of the node
+    parent: Option<PendingNode>,
+}
+
+/// Nodes that are created without access to a [`Taffy`] tree, and inserted into one later with
+/// [`Taffy::insert_subtree`]
+///
+/// Node ids can only be allocated by the tree itself, which makes building a large tree a single-threaded task.
+/// Builders instead number their nodes themselves, and are `Send`, so that loaders can build the subtrees of a large
+/// document on several threads and then insert each subtree into the tree on the thread that owns it. Only the
+/// insertion, which moves the styles and measure functions that were built into the tree, happens on that thread.
+///
+/// Subtrees are built bottom-up: the children of a node have to be created before it.
+#[derive(Default)]
+pub struct SubtreeBuilder {
+    /// The nodes of the subtree, in the order they were created
+    nodes: Vec<BuilderNode>,
+}
+
+impl core::fmt::Debug for SubtreeBuilder {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SubtreeBuilder").field("len", &self.nodes.len()).finish_non_exhaustive()
+    }
+}
+
+impl SubtreeBuilder {
+    /// Creates an empty builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty builder with room for `capacity` nodes
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { nodes: Vec::with_capacity(capacity) }
+    }
+
+    /// Returns the number of nodes created by the builder
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the builder hasn't created any nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Creates a new unattached leaf node, see [`Taffy::new_leaf`]
+    pub fn new_leaf(&mut self, style: Style) -> PendingNode {
+        self.push(style, None, Vec::new())
+    }
+
+    /// Creates a new unattached leaf node which is measured with `measure`, see [`Taffy::new_leaf_with_measure`]
+    pub fn new_leaf_with_measure(&mut self, style: Style, measure: MeasureFunc) -> PendingNode {
+        self.push(style, Some(measure), Vec::new())
+    }
+
+    /// Creates a new node with the given children, see [`Taffy::new_with_children`]
+    ///
+    /// Each node can be the child of only one node, and can appear only once in its list of children.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the children wasn't created by this builder, already has a parent or is listed twice.
+    pub fn new_with_children(&mut self, style: Style, children: &[PendingNode]) -> PendingNode {
+        let parent = PendingNode(self.nodes.len());
+        for child in children {
+            assert!(child.0 < self.nodes.len(), "{child:?} wasn't created by this builder");
+            let previous_parent = self.nodes[child.0].parent.replace(parent);
+            assert!(previous_parent.is_none(), "{child:?} is used as a child more than once");
+        }
+        self.push(style, None, children.to_vec())
+    }
+
+    /// Adds a node to the builder
+    fn push(&mut self, style: Style, measure: Option<MeasureFunc>, children: Vec<PendingNode>) -> PendingNode {
+        self.nodes.push(BuilderNode { style, measure, children, parent: None });
+        PendingNode(self.nodes.len() - 1)
+    }
+}
+
+impl Taffy {
+    /// Inserts the nodes created by a [`SubtreeBuilder`] into the tree, returning the id of each node in the order that
+    /// they were created (see [`PendingNode::index`])
+    ///
+    /// The nodes are left unattached to the rest of the tree: the roots of the subtree can then be added as children of
+    /// existing nodes.
+    pub fn insert_subtree(&mut self, builder: SubtreeBuilder) -> TaffyResult<Vec<NodeId>> {
+        let mut ids: Vec<NodeId> = Vec::with_capacity(builder.nodes.len());
+        for node in builder.nodes {
+            let id = match node.measure {
+                Some(measure) => self.new_leaf_with_measure(node.style, measure)?,
+                None if node.children.is_empty() => self.new_leaf(node.style)?,
+                None => {
+                    // Children are always created before their parent, so they already have an id
+                    let children: Vec<NodeId> = node.children.iter().map(|child| ids[child.0]).collect();
+                    self.new_with_children(node.style, &children)?
+                }
+            };
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+}
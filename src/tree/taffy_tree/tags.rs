@@ -0,0 +1,115 @@
+//! User-defined bitflags stored with each node, and traversals that only visit the nodes with certain flags
+use crate::tree::NodeId;
+use crate::util::sys::Vec;
+
+use super::{Taffy, TaffyError, TaffyResult};
+
+/// Iterator over the descendants of a node that have all of a set of tags, returned by [`Taffy::tagged_descendants`]
+///
+/// Descendants are visited depth-first in tree order, so a node is yielded before its own descendants and after its
+/// previous siblings and their descendants.
+pub struct TaggedDescendants<'a> {
+    /// The tree being traversed
+    taffy: &'a Taffy,
+    /// The tags that yielded nodes must have
+    tags: u32,
+    /// The nodes left to visit, with the next one at the end
+    stack: Vec<NodeId>,
+}
+
+impl core::fmt::Debug for TaggedDescendants<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedDescendants")
+            .field("tags", &self.tags)
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for TaggedDescendants<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        while let Some(node) = self.stack.pop() {
+            self.stack.extend(self.taffy.children[node.into()].iter().rev().copied());
+            if self.taffy.nodes[node.into()].tags & self.tags == self.tags {
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+impl Taffy {
+    /// Sets the user-defined tags of the node, a set of up to 32 bitflags whose meaning is up to the application (such
+    /// as whether the node is focusable or can be hovered). Nodes have no tags when they are created.
+    ///
+    /// Tags are stored alongside the rest of the node's data, so traversals filtered by tags don't have to look each
+    /// node up in a separate map. They don't affect layout.
+    pub fn set_tags(&mut self, node: NodeId, tags: u32) -> TaffyResult<()> {
+        match self.nodes.get_mut(node.into()) {
+            Some(data) => {
+                data.tags = tags;
+                Ok(())
+            }
+            None => Err(TaffyError::InvalidInputNode(node)),
+        }
+    }
+
+    /// Adds `tags` to the tags of the node, keeping its existing tags
+    pub fn insert_tags(&mut self, node: NodeId, tags: u32) -> TaffyResult<()> {
+        self.set_tags(node, self.tags(node)? | tags)
+    }
+
+    /// Removes `tags` from the tags of the node, keeping its other tags
+    pub fn remove_tags(&mut self, node: NodeId, tags: u32) -> TaffyResult<()> {
+        self.set_tags(node, self.tags(node)? & !tags)
+    }
+
+    /// Returns the tags of the node set with [`Taffy::set_tags`]
+    pub fn tags(&self, node: NodeId) -> TaffyResult<u32> {
+        self.nodes.get(node.into()).map(|data| data.tags).ok_or(TaffyError::InvalidInputNode(node))
+    }
+
+    /// Returns an iterator over the descendants of `root` (not including `root` itself) that have all of the given
+    /// `tags`, in tree order
+    ///
+    /// Passing no tags visits every descendant.
+    ///
+    /// ```
+    /// use taffy::prelude::*;
+    ///
+    /// const FOCUSABLE: u32 = 1 << 0;
+    ///
+    /// let mut taffy = Taffy::new();
+    /// let button = taffy.new_leaf(Style::default()).unwrap();
+    /// let label = taffy.new_leaf(Style::default()).unwrap();
+    /// let root = taffy.new_with_children(Style::default(), &[button, label]).unwrap();
+    /// taffy.set_tags(button, FOCUSABLE).unwrap();
+    ///
+    /// let focusable: Vec<NodeId> = taffy.tagged_descendants(root, FOCUSABLE).unwrap().collect();
+    /// assert_eq!(focusable, vec![button]);
+    /// ```
+    pub fn tagged_descendants(&self, root: NodeId, tags: u32) -> TaffyResult<TaggedDescendants<'_>> {
+        if !self.nodes.contains_key(root.into()) {
+            return Err(TaffyError::InvalidInputNode(root));
+        }
+        let stack = self.children[root.into()].iter().rev().copied().collect();
+        Ok(TaggedDescendants { taffy: self, tags, stack })
+    }
+
+    /// Returns the closest ancestor of `node` (not including `node` itself) that has all of the given `tags`, if any
+    pub fn tagged_ancestor(&self, node: NodeId, tags: u32) -> TaffyResult<Option<NodeId>> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        let mut ancestor = self.parents[node.into()];
+        while let Some(current) = ancestor {
+            if self.nodes[current.into()].tags & tags == tags {
+                return Ok(Some(current));
+            }
+            ancestor = self.parents[current.into()];
+        }
+        Ok(None)
+    }
+}
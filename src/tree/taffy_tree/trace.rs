@@ -0,0 +1,212 @@
+//! Recording the inputs of a layout computation into a self-contained trace that can be replayed in isolation
+use slotmap::{DefaultKey, SecondaryMap};
+
+use crate::compute::taffy_tree::{check_depth, compute_layout, no_measure};
+use crate::geometry::Size;
+use crate::style::{AvailableSpace, Style};
+use crate::tree::{CellMode, CoordinateOrigin, Layout, Measurable, MeasureFunc, NodeId};
+use crate::util::sys::{Box, Vec};
+
+use super::{Taffy, TaffyResult};
+
+/// The inputs and results of a layout computation, recorded by [`Taffy::record_layout`]
+///
+/// A trace describes the laid out subtree (the structure of the tree and the style of each node), the result of every
+/// call to a measure function, the space that the tree was laid out in and the resulting layouts. It doesn't depend on
+/// the application that recorded it, so it can be replayed against Taffy in isolation with [`LayoutTrace::replay`].
+/// With the `serde` feature enabled a trace can be saved to (and loaded from) a file in any format supported by serde,
+/// which makes layout bugs from large applications reproducible. Nodes can be removed from a trace to minimize it.
+///
+/// Styles are recorded with their context-dependent units (such as viewport-relative units) resolved.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutTrace {
+    /// The index of the root node of the computation in `nodes`
+    pub root: usize,
+    /// The space that the root node was laid out in
+    pub available_space: Size<AvailableSpace>,
+    /// Whether layout values were rounded
+    pub use_rounding: bool,
+    /// The grid of cells that layout values were snapped to, if any
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cell_mode: Option<CellMode>,
+    /// The coordinate convention of the recorded layouts
+    pub coordinate_origin: CoordinateOrigin,
+    /// The nodes of the laid out subtree, in depth-first order
+    pub nodes: Vec<TracedNode>,
+}
+
+/// A node recorded in a [`LayoutTrace`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TracedNode {
+    /// The style that the node was laid out with
+    pub style: Style,
+    /// The indexes of the node's children in [`LayoutTrace::nodes`]
+    pub children: Vec<usize>,
+    /// Whether the node was measured when it was laid out
+    pub needs_measure: bool,
+    /// The distinct results of measuring the node, in the order that they were first computed
+    pub measurements: Vec<TracedMeasurement>,
+    /// The layout computed for the node
+    pub layout: Layout,
+}
+
+/// A single call to the measure function of a node, recorded in a [`LayoutTrace`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TracedMeasurement {
+    /// The known dimensions that the node was measured with
+    pub known_dimensions: Size<Option<f32>>,
+    /// The available space that the node was measured with
+    pub available_space: Size<AvailableSpace>,
+    /// The size returned by the measure function
+    pub size: Size<f32>,
+}
+
+/// Measures a replayed node by looking up the results recorded in a [`LayoutTrace`]
+struct RecordedMeasure(Vec<TracedMeasurement>);
+
+impl Measurable for RecordedMeasure {
+    fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+        // Minimized traces may measure nodes with constraints that weren't recorded, in which case the closest match is used
+        let exact = self.0.iter().find(|measurement| {
+            measurement.known_dimensions == known_dimensions && measurement.available_space == available_space
+        });
+        let same_known_dimensions =
+            || self.0.iter().rev().find(|measurement| measurement.known_dimensions == known_dimensions);
+        exact.or_else(same_known_dimensions).or(self.0.last()).map_or(Size::ZERO, |measurement| measurement.size)
+    }
+}
+
+impl Taffy {
+    /// Updates the stored layout of the provided `node` and its children, recording the inputs and results of the
+    /// computation into a [`LayoutTrace`]
+    ///
+    /// Cached results are discarded, so that every measurement made while laying out the subtree is recorded.
+    pub fn record_layout(&mut self, node: NodeId, available_space: Size<AvailableSpace>) -> TaffyResult<LayoutTrace> {
+        self.record_layout_with_measure(node, available_space, no_measure)
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, measuring nodes using `measure_function` and
+    /// recording the inputs and results of the computation into a [`LayoutTrace`]
+    ///
+    /// See [`Taffy::compute_layout_with_styled_measure`] and [`Taffy::record_layout`].
+    pub fn record_layout_with_measure(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        measure_function: impl FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+    ) -> TaffyResult<LayoutTrace> {
+        check_depth(self, node)?;
+        // Results served from a cache or a memoized subtree skip measuring, so the subtree is laid out from scratch
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(node);
+        while let Some(current) = stack.pop() {
+            self.nodes[current.into()].cache.clear();
+            stack.extend(self.children[current.into()].iter().copied());
+        }
+        let use_subtree_memoization = core::mem::replace(&mut self.config.use_subtree_memoization, false);
+
+        self.measure_recording = Some(Vec::new());
+        let result = compute_layout(self, node, available_space, measure_function, None);
+        let recorded_measurements = self.measure_recording.take().unwrap_or_default();
+        self.config.use_subtree_memoization = use_subtree_memoization;
+        result?;
+
+        // Nodes are numbered in depth-first order once the layout is computed, as virtual children are only created then
+        let mut order: Vec<NodeId> = Vec::new();
+        let mut indexes: SecondaryMap<DefaultKey, usize> = SecondaryMap::new();
+        stack.push(node);
+        while let Some(current) = stack.pop() {
+            indexes.insert(current.into(), order.len());
+            order.push(current);
+            stack.extend(self.children[current.into()].iter().rev().copied());
+        }
+
+        let mut nodes: Vec<TracedNode> = order
+            .iter()
+            .map(|current| TracedNode {
+                style: self.layout_style(*current).clone(),
+                children: self.children[(*current).into()].iter().map(|child| indexes[(*child).into()]).collect(),
+                needs_measure: self.nodes[(*current).into()].needs_measure,
+                measurements: Vec::new(),
+                layout: self.nodes[(*current).into()].layout,
+            })
+            .collect();
+        for (measured, measurement) in recorded_measurements {
+            if let Some(index) = indexes.get(measured.into()) {
+                let measurements = &mut nodes[*index].measurements;
+                if !measurements.contains(&measurement) {
+                    measurements.push(measurement);
+                }
+            }
+        }
+
+        Ok(LayoutTrace {
+            root: 0,
+            available_space,
+            use_rounding: self.config.use_rounding,
+            cell_mode: self.config.cell_mode,
+            coordinate_origin: self.config.coordinate_origin,
+            nodes,
+        })
+    }
+}
+
+impl LayoutTrace {
+    /// Builds a new [`Taffy`] tree containing the recorded nodes, without laying it out
+    ///
+    /// Nodes that were measured are measured by looking up the recorded measurements. Returns the tree along with the id
+    /// of each node in [`LayoutTrace::nodes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node lists a child index that isn't an index of [`LayoutTrace::nodes`].
+    pub fn build(&self) -> TaffyResult<(Taffy, Vec<NodeId>)> {
+        let mut taffy = Taffy::with_capacity(self.nodes.len());
+        taffy.config.use_rounding = self.use_rounding;
+        taffy.config.cell_mode = self.cell_mode;
+        taffy.config.coordinate_origin = self.coordinate_origin;
+
+        let mut ids: Vec<NodeId> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let id = match node.needs_measure {
+                true => taffy.new_leaf_with_measure(
+                    node.style.clone(),
+                    MeasureFunc::Boxed(Box::new(RecordedMeasure(node.measurements.clone()))),
+                )?,
+                false => taffy.new_leaf(node.style.clone())?,
+            };
+            ids.push(id);
+        }
+        for (node, id) in self.nodes.iter().zip(ids.iter()) {
+            let children: Vec<NodeId> = node.children.iter().map(|child| ids[*child]).collect();
+            taffy.set_children(*id, &children)?;
+        }
+
+        Ok((taffy, ids))
+    }
+
+    /// Replays the recorded layout computation, returning the indexes of the nodes whose layout differs from the
+    /// recorded one
+    ///
+    /// An empty result means that the recorded layouts were reproduced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`LayoutTrace::root`], or a child index of a node, isn't an index of [`LayoutTrace::nodes`].
+    pub fn replay(&self) -> TaffyResult<Vec<usize>> {
+        let (mut taffy, ids) = self.build()?;
+        taffy.compute_layout(ids[self.root], self.available_space)?;
+
+        Ok(self
+            .nodes
+            .iter()
+            .zip(ids.iter())
+            .enumerate()
+            .filter(|(_, (node, id))| taffy.nodes[(**id).into()].layout != node.layout)
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
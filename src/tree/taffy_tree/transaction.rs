@@ -0,0 +1,316 @@
+//! Batches of changes to a tree that can be rolled back atomically
+use slotmap::{DefaultKey, SecondaryMap};
+
+use crate::style::Style;
+use crate::tree::{MeasureFunc, NodeId};
+use crate::util::sys::{ChildrenVec, String, Vec};
+
+use super::backup::NodeBackup;
+use super::{Taffy, TaffyResult};
+
+/// The state of a node before it was first changed by a [`Transaction`]
+struct SavedNode {
+    /// The node's style and cached results, or `None` if the node was created by the transaction, in which case it is
+    /// removed when the transaction is rolled back
+    backup: Option<NodeBackup>,
+    /// The node's children
+    children: Option<ChildrenVec<NodeId>>,
+    /// The node's parent
+    parent: Option<Option<NodeId>>,
+    /// The node's key
+    key: Option<Option<String>>,
+    /// Whether the node needs to be measured
+    needs_measure: Option<bool>,
+}
+
+impl SavedNode {
+    /// Creates the saved state of a node, whose backup is `None` if the node was created by the transaction
+    fn new(backup: Option<NodeBackup>) -> Self {
+        Self { backup, children: None, parent: None, key: None, needs_measure: None }
+    }
+
+    /// Returns whether the node was created by the transaction
+    fn created(&self) -> bool {
+        self.backup.is_none()
+    }
+}
+
+/// A batch of structural and style changes to a [`Taffy`] tree, which can be rolled back atomically
+///
+/// Created by [`Taffy::transaction`]. The changes are applied to the tree as they are made, while the previous state of
+/// every node that is changed (its children, parent, style, key and cached results) is recorded. If the transaction is
+/// [rolled back](Transaction::rollback), the nodes created by the transaction are removed and the recorded state is
+/// restored, leaving the tree exactly as it was. Nodes removed by the transaction are only detached until the
+/// transaction is [committed](Transaction::commit), so that they can be restored. Dropping the transaction rolls it back.
+pub struct Transaction<'a> {
+    /// The tree that is being changed
+    taffy: &'a mut Taffy,
+    /// The state of each node before it was first changed by the transaction
+    saved: SecondaryMap<DefaultKey, SavedNode>,
+    /// The nodes removed by the transaction, which are dropped when it is committed
+    removed: Vec<NodeId>,
+    /// The number of deferred dirty-markings when the transaction was created, if it was created within a batch
+    pending_dirty_len: Option<usize>,
+    /// Whether the transaction has been committed, in which case the tree isn't restored when it is dropped
+    committed: bool,
+}
+
+impl core::fmt::Debug for Transaction<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Transaction")
+            .field("changed_nodes", &self.saved.len())
+            .field("removed", &self.removed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Taffy {
+    /// Starts a [`Transaction`], whose changes to the tree can be rolled back atomically
+    ///
+    /// This is useful when applying a diff to the tree that may fail part-way through.
+    pub fn transaction(&mut self) -> Transaction<'_> {
+        let pending_dirty_len = self.pending_dirty.as_ref().map(Vec::len);
+        Transaction {
+            taffy: self,
+            saved: SecondaryMap::new(),
+            removed: Vec::new(),
+            pending_dirty_len,
+            committed: false,
+        }
+    }
+}
+
+impl Transaction<'_> {
+    /// Creates a new unattached leaf node, see [`Taffy::new_leaf`]
+    pub fn new_leaf(&mut self, style: Style) -> TaffyResult<NodeId> {
+        let node = self.taffy.new_leaf(style)?;
+        self.save_created(node);
+        Ok(node)
+    }
+
+    /// Creates a new unattached leaf node with a [`MeasureFunc`], see [`Taffy::new_leaf_with_measure`]
+    pub fn new_leaf_with_measure(&mut self, style: Style, measure: MeasureFunc) -> TaffyResult<NodeId> {
+        let node = self.taffy.new_leaf_with_measure(style, measure)?;
+        self.save_created(node);
+        Ok(node)
+    }
+
+    /// Creates a new node with the provided `children`, see [`Taffy::new_with_children`]
+    pub fn new_with_children(&mut self, style: Style, children: &[NodeId]) -> TaffyResult<NodeId> {
+        for child in children {
+            self.save_parent(*child);
+        }
+        let node = self.taffy.new_with_children(style, children)?;
+        self.save_created(node);
+        Ok(node)
+    }
+
+    /// Detaches the node from its parent and children, and removes it from the tree once the transaction is committed
+    pub fn remove(&mut self, node: NodeId) -> TaffyResult<NodeId> {
+        if let Some(parent) = self.taffy.parents[node.into()] {
+            self.remove_child(parent, node)?;
+        }
+        self.set_children(node, &[])?;
+        self.removed.push(node);
+        Ok(node)
+    }
+
+    /// Adds a `child` node under the supplied `parent`, see [`Taffy::add_child`]
+    pub fn add_child(&mut self, parent: NodeId, child: NodeId) -> TaffyResult<()> {
+        self.save_children(parent);
+        self.save_parent(child);
+        self.taffy.add_child(parent, child)
+    }
+
+    /// Inserts a `child` node at the given `child_index` under the supplied `parent`, see [`Taffy::insert_child_at_index`]
+    pub fn insert_child_at_index(&mut self, parent: NodeId, child_index: usize, child: NodeId) -> TaffyResult<()> {
+        self.save_children(parent);
+        self.save_parent(child);
+        self.taffy.insert_child_at_index(parent, child_index, child)
+    }
+
+    /// Directly sets the `children` of the supplied `parent`, see [`Taffy::set_children`]
+    pub fn set_children(&mut self, parent: NodeId, children: &[NodeId]) -> TaffyResult<()> {
+        self.save_children(parent);
+        for index in 0..self.taffy.children[parent.into()].len() {
+            self.save_parent(self.taffy.children[parent.into()][index]);
+        }
+        for child in children {
+            self.save_parent(*child);
+        }
+        self.taffy.set_children(parent, children)
+    }
+
+    /// Removes the `child` of the parent `node`, see [`Taffy::remove_child`]
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> TaffyResult<NodeId> {
+        self.save_children(parent);
+        self.save_parent(child);
+        self.taffy.remove_child(parent, child)
+    }
+
+    /// Removes the child at the given `index` from the `parent`, see [`Taffy::remove_child_at_index`]
+    pub fn remove_child_at_index(&mut self, parent: NodeId, child_index: usize) -> TaffyResult<NodeId> {
+        let child = self.taffy.child_at_index(parent, child_index)?;
+        self.remove_child(parent, child)
+    }
+
+    /// Replaces the child at the given `child_index` from the `parent` node with the new `child` node, see
+    /// [`Taffy::replace_child_at_index`]
+    pub fn replace_child_at_index(
+        &mut self,
+        parent: NodeId,
+        child_index: usize,
+        new_child: NodeId,
+    ) -> TaffyResult<NodeId> {
+        let old_child = self.taffy.child_at_index(parent, child_index)?;
+        self.save_children(parent);
+        self.save_parent(old_child);
+        self.save_parent(new_child);
+        self.taffy.replace_child_at_index(parent, child_index, new_child)
+    }
+
+    /// Sets the [`Style`] of the provided `node`, see [`Taffy::set_style`]
+    pub fn set_style(&mut self, node: NodeId, style: Style) -> TaffyResult<()> {
+        self.save_ancestors(node);
+        if let Some(backup) = self.saved.get_mut(node.into()).and_then(|saved| saved.backup.as_mut()) {
+            backup.save_style(self.taffy, node.into());
+        }
+        self.taffy.set_style(node, style)
+    }
+
+    /// Assigns a unique `key` to the node, see [`Taffy::set_key`]
+    pub fn set_key(&mut self, node: NodeId, key: Option<&str>) -> TaffyResult<()> {
+        if self.taffy.nodes.contains_key(node.into()) {
+            let previous = self.taffy.keys.get(node.into()).cloned();
+            let saved = self.save(node);
+            if !saved.created() && saved.key.is_none() {
+                saved.key = Some(previous);
+            }
+        }
+        self.taffy.set_key(node, key)
+    }
+
+    /// Sets whether the node needs to be measured, see [`Taffy::set_needs_measure`]
+    pub fn set_needs_measure(&mut self, node: NodeId, needs_measure: bool) -> TaffyResult<()> {
+        self.save_ancestors(node);
+        let previous = self.taffy.nodes[node.into()].needs_measure;
+        let saved = &mut self.saved[node.into()];
+        if !saved.created() && saved.needs_measure.is_none() {
+            saved.needs_measure = Some(previous);
+        }
+        self.taffy.set_needs_measure(node, needs_measure)
+    }
+
+    /// Returns the tree being changed, with the changes made so far by the transaction applied
+    pub fn tree(&self) -> &Taffy {
+        self.taffy
+    }
+
+    /// Gets the [`Style`] of the provided `node`
+    pub fn style(&self, node: NodeId) -> TaffyResult<&Style> {
+        self.taffy.style(node)
+    }
+
+    /// Returns a list of children that belong to the parent node
+    pub fn children(&self, parent: NodeId) -> TaffyResult<Vec<NodeId>> {
+        self.taffy.children(parent)
+    }
+
+    /// Keeps the changes made in the transaction, dropping the nodes that it removed
+    pub fn commit(mut self) {
+        for node in self.removed.drain(..) {
+            // Removed nodes have already been detached, so there is no parent to mark dirty
+            let _ = self.taffy.remove(node);
+        }
+        self.committed = true;
+    }
+
+    /// Undoes the changes made in the transaction, restoring the tree to its state before the transaction was started
+    pub fn rollback(self) {
+        // The tree is restored when the transaction is dropped
+    }
+
+    /// Records that the node was created by the transaction
+    fn save_created(&mut self, node: NodeId) {
+        self.saved.insert(node.into(), SavedNode::new(None));
+    }
+
+    /// Saves the state of the node before it is changed, unless it has already been saved
+    fn save(&mut self, node: NodeId) -> &mut SavedNode {
+        let key = node.into();
+        if !self.saved.contains_key(key) {
+            self.saved.insert(key, SavedNode::new(Some(NodeBackup::new(self.taffy, key))));
+        }
+        &mut self.saved[key]
+    }
+
+    /// Saves the state of the node and its ancestors, whose cached results are invalidated when the node changes
+    fn save_ancestors(&mut self, node: NodeId) {
+        let mut current = Some(node);
+        while let Some(ancestor) = current {
+            self.save(ancestor);
+            current = self.taffy.parents[ancestor.into()];
+        }
+    }
+
+    /// Saves the children of the node before they are changed
+    fn save_children(&mut self, node: NodeId) {
+        self.save_ancestors(node);
+        if self.saved[node.into()].children.is_none() {
+            let children = self.taffy.children[node.into()].clone();
+            self.saved[node.into()].children = Some(children);
+        }
+    }
+
+    /// Saves the parent of the node before it is changed
+    fn save_parent(&mut self, node: NodeId) {
+        let parent = self.taffy.parents[node.into()];
+        let saved = self.save(node);
+        if saved.parent.is_none() {
+            saved.parent = Some(parent);
+        }
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+
+        if let (Some(pending_dirty), Some(len)) = (&mut self.taffy.pending_dirty, self.pending_dirty_len) {
+            pending_dirty.truncate(len);
+        }
+
+        // Keys are released before any is restored, so that keys which moved between nodes can't collide
+        for (key, saved) in self.saved.iter() {
+            if saved.created() || saved.key.is_some() {
+                if let Some(node_key) = self.taffy.keys.remove(key) {
+                    self.taffy.nodes_by_key.remove(&node_key);
+                }
+            }
+        }
+
+        for (key, saved) in self.saved.drain() {
+            let Some(backup) = saved.backup else {
+                self.taffy.remove_node_data(key);
+                continue;
+            };
+
+            backup.restore(self.taffy, key);
+            if let Some(children) = saved.children {
+                self.taffy.children[key] = children;
+            }
+            if let Some(parent) = saved.parent {
+                self.taffy.parents[key] = parent;
+            }
+            if let Some(Some(node_key)) = saved.key {
+                self.taffy.nodes_by_key.insert(node_key.clone(), key.into());
+                self.taffy.keys.insert(key, node_key);
+            }
+            if let Some(needs_measure) = saved.needs_measure {
+                self.taffy.nodes[key].needs_measure = needs_measure;
+            }
+        }
+    }
+}
@@ -1,26 +1,87 @@
 //! UI node types and related data structures.
 //!
 //! Layouts are composed of multiple nodes, which live in a tree-like data structure.
-use slotmap::{DefaultKey, SlotMap, SparseSecondaryMap};
-
-use crate::compute::taffy_tree::{compute_layout, measure_node_size, perform_node_layout};
-use crate::geometry::{Line, Size};
+use slotmap::{DefaultKey, SecondaryMap, SlotMap, SparseSecondaryMap};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::custom_units::CustomUnitResolver;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::virtual_list::VirtualList;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::NodePool;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::{NodeClasses, StyleClass};
+#[cfg(feature = "rayon")]
+use crate::compute::taffy_tree::measure_leaves_in_parallel;
+use crate::compute::taffy_tree::{
+    compute_detached_layout, compute_layout, measure_node_size, no_measure, perform_node_layout, CustomLayoutFunction,
+    DetachedLayout,
+};
+#[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+use crate::compute::FlexItemBuffer;
+use crate::geometry::{AbsoluteAxis, Line, Point, Size};
 use crate::prelude::LayoutTree;
-use crate::style::{AvailableSpace, Style};
-use crate::tree::{Layout, MeasureFunc, NodeData, NodeId, SizeBaselinesAndMargins, SizingMode};
-use crate::util::sys::{new_vec_with_capacity, ChildrenVec, Vec};
-
-use super::{TaffyError, TaffyResult};
+use crate::style::{AvailableSpace, ContextUnits, Position, Style, UnitContext};
+#[cfg(feature = "alloc_audit")]
+use crate::tree::AllocationSite;
+#[cfg(feature = "grid")]
+use crate::tree::AutoRepetitions;
+#[cfg(feature = "std")]
+use crate::tree::MeasureCache;
+#[cfg(feature = "rayon")]
+use crate::tree::MeasureRequest;
+use crate::tree::{
+    Cache, CellMode, CoordinateOrigin, Explanation, InvalidationLevel, Layout, MeasureFunc, NodeData, NodeId,
+    ReplacedContent, SizeBaselinesAndMargins, SizeConstraint, SizingMode,
+};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::tree::{SubtreeMemo, TracedMeasurement};
+use crate::util::sys::{new_vec_with_capacity, round, ChildrenVec, Vec};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::util::sys::{BTreeMap, Box, String};
+#[cfg(feature = "debug")]
+use crate::util::{DebugEvent, DebugSink, PrintDebugSink};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(feature = "std")]
+use super::LayoutBuffers;
+use super::{DamageTracker, LayoutMap, LayoutStats, TaffyError, TaffyResult};
 
 /// Global configuration values for a Taffy instance
 pub(crate) struct TaffyConfig {
     /// Whether to round layout values
     pub(crate) use_rounding: bool,
+    /// The number of physical pixels per logical unit, which layout values are rounded to
+    pub(crate) scale_factor: f32,
+    /// The grid of cells that layout values are snapped to instead of being rounded, if any
+    pub(crate) cell_mode: Option<CellMode>,
+    /// The maximum depth of tree that may be laid out, if any
+    pub(crate) max_depth: Option<usize>,
+    /// The coordinate convention used for the locations of computed layouts
+    pub(crate) coordinate_origin: CoordinateOrigin,
+    /// The size of the viewport that viewport-relative units are resolved against, if set explicitly
+    pub(crate) viewport_size: Option<Size<f32>>,
+    /// The font size that `rem` units are resolved against
+    pub(crate) root_font_size: f32,
+    /// Whether to memoize the layouts of subtrees by their structural fingerprint
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) use_subtree_memoization: bool,
 }
 
 impl Default for TaffyConfig {
     fn default() -> Self {
-        Self { use_rounding: true }
+        Self {
+            use_rounding: true,
+            scale_factor: 1.0,
+            cell_mode: None,
+            max_depth: None,
+            coordinate_origin: CoordinateOrigin::TopLeft,
+            viewport_size: None,
+            root_font_size: 16.0,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            use_subtree_memoization: false,
+        }
     }
 }
 
@@ -29,7 +90,9 @@ pub struct Taffy {
     /// The [`NodeData`] for each node stored in this tree
     pub(crate) nodes: SlotMap<DefaultKey, NodeData>,
 
-    /// Functions/closures that compute the intrinsic size of leaf nodes
+    /// Functions/closures that compute the intrinsic size of nodes
+    ///
+    /// For nodes with children, the measured size is combined with the size derived from the children
     pub(crate) measure_funcs: SparseSecondaryMap<DefaultKey, MeasureFunc>,
 
     /// The children of each node
@@ -44,6 +107,110 @@ pub struct Taffy {
 
     /// Layout mode configuration
     pub(crate) config: TaffyConfig,
+
+    /// Nodes that have been marked dirty during an in-progress [`Taffy::update`] batch
+    ///
+    /// `None` when no batch is in progress, in which case nodes are marked dirty immediately
+    pub(crate) pending_dirty: Option<Vec<(NodeId, InvalidationLevel)>>,
+
+    /// The nodes whose layout changed during the most recent call to [`Taffy::compute_layout`]
+    pub(crate) changed_layouts: Vec<NodeId>,
+
+    /// The layouts computed by interrupted calls to [`Taffy::compute_layout_with_yield`], which are kept aside until
+    /// the next layout computation so that the previous layouts remain visible in the meantime
+    pub(crate) interrupted_layouts: Vec<(NodeId, Layout)>,
+
+    /// Statistics gathered during the most recent call to [`Taffy::compute_layout`]
+    pub(crate) stats: LayoutStats,
+
+    /// The rectangles covered by nodes and the damage between layout computations, once
+    /// [`Taffy::enable_damage_tracking`] has been called
+    pub(crate) damage: Option<DamageTracker>,
+
+    /// The node whose size is explained, chosen with [`Taffy::explain_node`], and the constraints recorded for it
+    pub(crate) explanation: Option<Explanation>,
+
+    /// The resolved style of each node whose style uses context-dependent units (such as viewport-relative units)
+    ///
+    /// These are resolved at the start of each layout computation, see [`Taffy::resolve_context_units`]
+    pub(crate) resolved_styles: SparseSecondaryMap<DefaultKey, Style>,
+
+    /// The tracks produced by the auto-repeated track definitions of each grid, see [`Taffy::auto_repetitions`]
+    #[cfg(feature = "grid")]
+    pub(crate) auto_repetitions: SparseSecondaryMap<DefaultKey, Size<Option<AutoRepetitions>>>,
+
+    /// The font size of each node that has been assigned one with [`Taffy::set_font_size`]
+    pub(crate) font_sizes: SparseSecondaryMap<DefaultKey, f32>,
+
+    /// The content of each replaced element, see [`Taffy::set_replaced_content`]
+    pub(crate) replaced_contents: SparseSecondaryMap<DefaultKey, ReplacedContent>,
+
+    /// The size kept by each node whose layout is skipped, see [`Taffy::skip_layout`]
+    pub(crate) skipped_layouts: SparseSecondaryMap<DefaultKey, Size<f32>>,
+
+    /// The scroll offset of each scroll container that has been assigned one with [`Taffy::set_scroll_offset`]
+    pub(crate) scroll_offsets: SparseSecondaryMap<DefaultKey, Point<f32>>,
+
+    /// The key of each node that has been assigned one with [`Taffy::set_key`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) keys: SparseSecondaryMap<DefaultKey, String>,
+
+    /// The node that each key has been assigned to
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) nodes_by_key: BTreeMap<String, NodeId>,
+
+    /// The style classes defined with [`Taffy::define_style_class`], by name
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) style_classes: BTreeMap<String, StyleClass>,
+
+    /// The style classes of each node that has been assigned some with [`Taffy::set_style_classes`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) node_classes: SparseSecondaryMap<DefaultKey, NodeClasses>,
+
+    /// Memoized subtree layouts, used when [`Taffy::enable_subtree_memoization`] has been called
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) subtree_memo: SubtreeMemo,
+
+    /// The virtual children of nodes that have been assigned some with [`Taffy::set_virtual_children`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) virtual_lists: SparseSecondaryMap<DefaultKey, VirtualList>,
+
+    /// The allocations kept from removed nodes, see [`Taffy::set_node_pool_capacity`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) node_pool: NodePool,
+
+    /// The emptied lists of flex items kept to be reused by the flexbox algorithm, see [`LayoutTree::take_flex_items`].
+    /// There is one for each level of nested flex containers that have been laid out at the same time.
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    pub(crate) flex_item_buffers: Vec<FlexItemBuffer>,
+
+    /// The resolver of custom units set with [`Taffy::set_custom_unit_resolver`], if any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) custom_unit_resolver: Option<Box<dyn CustomUnitResolver>>,
+
+    /// The custom layout algorithms registered with [`Taffy::register_layout_algorithm`], by key
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) layout_algorithms: BTreeMap<u16, CustomLayoutFunction>,
+
+    /// The content hash of each node that has been assigned one with [`Taffy::set_content_hash`]
+    #[cfg(feature = "std")]
+    pub(crate) content_hashes: SparseSecondaryMap<DefaultKey, u64>,
+
+    /// The cache of measured sizes set with [`Taffy::set_measure_cache`], if any
+    #[cfg(feature = "std")]
+    pub(crate) measure_cache: Option<Arc<dyn MeasureCache>>,
+
+    /// The buffers that computed layouts are published to, once [`Taffy::layout_reader`] has been called
+    #[cfg(feature = "std")]
+    pub(crate) layout_buffers: Option<LayoutBuffers>,
+
+    /// The measurements made during the layout computation being recorded by [`Taffy::record_layout`], if any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) measure_recording: Option<Vec<(NodeId, TracedMeasurement)>>,
+
+    /// The sink that debugging events are emitted to, set with [`Taffy::set_debug_sink`]
+    #[cfg(feature = "debug")]
+    pub(crate) debug_sink: Option<Box<dyn DebugSink>>,
 }
 
 impl Default for Taffy {
@@ -52,6 +219,18 @@ impl Default for Taffy {
     }
 }
 
+/// Whether a call to [`Taffy::compute_layout_with_yield`] laid out the entire tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutProgress {
+    /// The layout of the tree has been fully computed
+    Complete,
+    /// The computation was interrupted before it completed
+    ///
+    /// The layouts stored in the tree are those from before the computation started. Results that were computed before
+    /// the interruption remain cached, so calling [`Taffy::compute_layout_with_yield`] again resumes the work.
+    Interrupted,
+}
+
 /// Iterator that wraps a slice of nodes, lazily converting them to u64
 pub struct TaffyChildIter<'a>(core::slice::Iter<'a, NodeId>);
 impl<'a> Iterator for TaffyChildIter<'a> {
@@ -62,6 +241,22 @@ impl<'a> Iterator for TaffyChildIter<'a> {
     }
 }
 
+/// Iterator over all nodes of a [`Taffy`] tree along with their style and layout, returned by [`Taffy::iter`]
+pub struct TaffyIter<'a>(slotmap::basic::Iter<'a, DefaultKey, NodeData>);
+impl<'a> Iterator for TaffyIter<'a> {
+    type Item = (NodeId, &'a Style, &'a Layout);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, data)| (key.into(), &data.style, &data.layout))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TaffyIter<'_> {}
+
 impl LayoutTree for Taffy {
     type ChildIter<'a> = TaffyChildIter<'a>;
 
@@ -77,7 +272,7 @@ impl LayoutTree for Taffy {
 
     #[inline(always)]
     fn style(&self, node: NodeId) -> &Style {
-        &self.nodes[node.into()].style
+        self.layout_style(node)
     }
 
     #[inline(always)]
@@ -95,6 +290,170 @@ impl LayoutTree for Taffy {
         self.children[node.into()][id]
     }
 
+    #[inline(always)]
+    fn measure_child_size(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        sizing_mode: SizingMode,
+        vertical_margins_are_collapsible: Line<bool>,
+    ) -> Size<f32> {
+        measure_node_size(
+            &mut TaffyView::new(self, no_measure),
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+    }
+
+    #[inline(always)]
+    fn perform_child_layout(
+        &mut self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        sizing_mode: SizingMode,
+        vertical_margins_are_collapsible: Line<bool>,
+    ) -> SizeBaselinesAndMargins {
+        perform_node_layout(
+            &mut TaffyView::new(self, no_measure),
+            node,
+            known_dimensions,
+            parent_size,
+            available_space,
+            sizing_mode,
+            vertical_margins_are_collapsible,
+        )
+    }
+    #[inline(always)]
+    fn record_size_constraint(&mut self, node: NodeId, axis: AbsoluteAxis, constraint: SizeConstraint) {
+        Taffy::record_size_constraint(self, node, axis, constraint)
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn record_auto_repetitions(&mut self, node: NodeId, repetitions: Size<Option<AutoRepetitions>>) {
+        Taffy::record_auto_repetitions(self, node, repetitions)
+    }
+
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn take_flex_items(&mut self) -> FlexItemBuffer {
+        self.flex_item_buffers.pop().unwrap_or_default()
+    }
+
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn recycle_flex_items(&mut self, items: FlexItemBuffer) {
+        self.flex_item_buffers.push(items)
+    }
+
+    #[cfg(feature = "debug")]
+    #[inline(always)]
+    fn debug_event(&mut self, event: &DebugEvent<'_>) {
+        Taffy::debug_event(self, event)
+    }
+
+    #[cfg(feature = "alloc_audit")]
+    #[inline(always)]
+    fn record_allocation(&mut self, _node: NodeId, site: AllocationSite) {
+        self.stats.allocations.record(site)
+    }
+}
+
+/// A view over a [`Taffy`] tree that measures nodes which need measuring but don't have a [`MeasureFunc`] using a
+/// single `measure_function`
+///
+/// Used by [`Taffy::compute_layout_with_measure`] to thread the measure function through the layout algorithms.
+pub(crate) struct TaffyView<'t, MeasureFunction> {
+    /// The tree being laid out
+    pub(crate) taffy: &'t mut Taffy,
+    /// The function used to measure nodes that need measuring but don't have a [`MeasureFunc`]
+    pub(crate) measure_function: MeasureFunction,
+    /// Called before each node is laid out, interrupting the computation once it returns true
+    pub(crate) should_yield: Option<&'t mut dyn FnMut() -> bool>,
+    /// The node at which the computation was interrupted, if it has been interrupted
+    pub(crate) interrupted_at: Option<NodeId>,
+    /// The state that the computation reads and writes instead of the tree's, if the computation is detached
+    pub(crate) detached: Option<&'t mut DetachedLayout>,
+}
+
+impl<'t, MeasureFunction> TaffyView<'t, MeasureFunction> {
+    /// Creates a new uninterruptible view over the provided `taffy` tree
+    pub(crate) fn new(taffy: &'t mut Taffy, measure_function: MeasureFunction) -> Self {
+        Self { taffy, measure_function, should_yield: None, interrupted_at: None, detached: None }
+    }
+
+    /// Returns the cached results of the node
+    #[inline(always)]
+    pub(crate) fn cache_mut(&mut self, node: NodeId) -> &mut Cache {
+        match &mut self.detached {
+            Some(detached) => detached.cache_mut(node),
+            None => &mut self.taffy.nodes[node.into()].cache,
+        }
+    }
+
+    /// Returns the statistics of the computation
+    #[inline(always)]
+    pub(crate) fn stats_mut(&mut self) -> &mut LayoutStats {
+        match &mut self.detached {
+            Some(detached) => &mut detached.stats,
+            None => &mut self.taffy.stats,
+        }
+    }
+}
+
+impl<'t, MeasureFunction> LayoutTree for TaffyView<'t, MeasureFunction>
+where
+    MeasureFunction: FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+{
+    type ChildIter<'a>
+        = TaffyChildIter<'a>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn children(&self, node: NodeId) -> Self::ChildIter<'_> {
+        TaffyChildIter(self.taffy.children[node.into()].iter())
+    }
+
+    #[inline(always)]
+    fn child_count(&self, node: NodeId) -> usize {
+        self.taffy.children[node.into()].len()
+    }
+
+    #[inline(always)]
+    fn style(&self, node: NodeId) -> &Style {
+        DetachedLayout::layout_style(self.detached.as_deref(), self.taffy, node)
+    }
+
+    #[inline(always)]
+    fn layout(&self, node: NodeId) -> &Layout {
+        match &self.detached {
+            Some(detached) => detached.layout(node),
+            None => &self.taffy.nodes[node.into()].layout,
+        }
+    }
+
+    #[inline(always)]
+    fn layout_mut(&mut self, node: NodeId) -> &mut Layout {
+        match &mut self.detached {
+            Some(detached) => detached.layout_mut(node),
+            None => &mut self.taffy.nodes[node.into()].layout,
+        }
+    }
+
+    #[inline(always)]
+    fn child(&self, node: NodeId, id: usize) -> NodeId {
+        self.taffy.children[node.into()][id]
+    }
+
     #[inline(always)]
     fn measure_child_size(
         &mut self,
@@ -136,6 +495,62 @@ impl LayoutTree for Taffy {
             vertical_margins_are_collapsible,
         )
     }
+
+    #[cfg(feature = "rayon")]
+    fn measure_child_sizes(&mut self, requests: &[MeasureRequest], sizes: &mut [Size<f32>]) {
+        measure_leaves_in_parallel(self, requests);
+        for (request, size) in requests.iter().zip(sizes.iter_mut()) {
+            *size = measure_node_size(
+                self,
+                request.node,
+                request.known_dimensions,
+                request.parent_size,
+                request.available_space,
+                request.sizing_mode,
+                request.vertical_margins_are_collapsible,
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn record_size_constraint(&mut self, node: NodeId, axis: AbsoluteAxis, constraint: SizeConstraint) {
+        // Explanations and auto-repetitions describe the layouts stored in the tree, so detached computations don't record them
+        if self.detached.is_none() {
+            self.taffy.record_size_constraint(node, axis, constraint)
+        }
+    }
+
+    #[cfg(feature = "grid")]
+    #[inline(always)]
+    fn record_auto_repetitions(&mut self, node: NodeId, repetitions: Size<Option<AutoRepetitions>>) {
+        if self.detached.is_none() {
+            self.taffy.record_auto_repetitions(node, repetitions)
+        }
+    }
+
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn take_flex_items(&mut self) -> FlexItemBuffer {
+        self.taffy.take_flex_items()
+    }
+
+    #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+    #[inline(always)]
+    fn recycle_flex_items(&mut self, items: FlexItemBuffer) {
+        self.taffy.recycle_flex_items(items)
+    }
+
+    #[cfg(feature = "debug")]
+    #[inline(always)]
+    fn debug_event(&mut self, event: &DebugEvent<'_>) {
+        self.taffy.debug_event(event)
+    }
+
+    #[cfg(feature = "alloc_audit")]
+    #[inline(always)]
+    fn record_allocation(&mut self, _node: NodeId, site: AllocationSite) {
+        self.stats_mut().allocations.record(site)
+    }
 }
 
 #[allow(clippy::iter_cloned_collect)] // due to no-std support, we need to use `iter_cloned` instead of `collect`
@@ -159,6 +574,49 @@ impl Taffy {
             parents: SlotMap::with_capacity(capacity),
             measure_funcs: SparseSecondaryMap::with_capacity(capacity),
             config: TaffyConfig::default(),
+            pending_dirty: None,
+            changed_layouts: Vec::new(),
+            interrupted_layouts: Vec::new(),
+            stats: LayoutStats::default(),
+            damage: None,
+            explanation: None,
+            resolved_styles: SparseSecondaryMap::new(),
+            #[cfg(feature = "grid")]
+            auto_repetitions: SparseSecondaryMap::new(),
+            font_sizes: SparseSecondaryMap::new(),
+            replaced_contents: SparseSecondaryMap::new(),
+            skipped_layouts: SparseSecondaryMap::new(),
+            scroll_offsets: SparseSecondaryMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            keys: SparseSecondaryMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            nodes_by_key: BTreeMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            style_classes: BTreeMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            node_classes: SparseSecondaryMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            subtree_memo: SubtreeMemo::default(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            virtual_lists: SparseSecondaryMap::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            node_pool: NodePool::default(),
+            #[cfg(all(feature = "flexbox", any(feature = "std", feature = "alloc")))]
+            flex_item_buffers: Vec::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            custom_unit_resolver: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            layout_algorithms: BTreeMap::new(),
+            #[cfg(feature = "std")]
+            content_hashes: SparseSecondaryMap::new(),
+            #[cfg(feature = "std")]
+            measure_cache: None,
+            #[cfg(feature = "std")]
+            layout_buffers: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            measure_recording: None,
+            #[cfg(feature = "debug")]
+            debug_sink: Some(Box::new(PrintDebugSink::default())),
         }
     }
 
@@ -172,10 +630,239 @@ impl Taffy {
         self.config.use_rounding = false;
     }
 
+    /// Sets the number of physical pixels per logical unit of the display that the tree is laid out for, which must be
+    /// positive. Defaults to `1.0`.
+    ///
+    /// Styles and layouts remain in logical units, but while rounding is enabled, layouts are rounded to whole physical
+    /// pixels rather than to whole logical units, so that fractional scale factors such as `1.25` or `1.5` don't
+    /// introduce seams between nodes once the layouts are scaled. See [`Taffy::physical_layout`] for the layout of a
+    /// node in physical pixels. The cached layout of every node is discarded when the scale factor changes, so that the
+    /// whole tree is rounded again by the next call to [`Taffy::compute_layout`].
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if self.config.scale_factor == scale_factor {
+            return;
+        }
+        self.config.scale_factor = scale_factor;
+        for node in self.nodes.values_mut() {
+            node.cache.clear();
+        }
+    }
+
+    /// Returns the number of physical pixels per logical unit, see [`Taffy::set_scale_factor`]
+    pub fn scale_factor(&self) -> f32 {
+        self.config.scale_factor
+    }
+
+    /// Snaps every computed size and position to a whole number of cells (see [`CellMode`]), or stops snapping them if
+    /// `cell_mode` is `None`. Cell mode is disabled by default.
+    ///
+    /// While enabled, layouts are snapped to cells instead of being rounded, whether or not rounding is enabled. The
+    /// cached layout of every node is discarded when the mode changes, so that the whole tree is snapped again by the
+    /// next call to [`Taffy::compute_layout`].
+    pub fn set_cell_mode(&mut self, cell_mode: Option<CellMode>) {
+        if self.config.cell_mode == cell_mode {
+            return;
+        }
+        self.config.cell_mode = cell_mode;
+        for node in self.nodes.values_mut() {
+            node.cache.clear();
+        }
+    }
+
+    /// Returns the grid of cells that layouts are snapped to, see [`Taffy::set_cell_mode`]
+    pub fn cell_mode(&self) -> Option<CellMode> {
+        self.config.cell_mode
+    }
+
+    /// Sets the sink that receives the structured debugging events emitted while laying out the tree, or discards
+    /// them if `None`
+    ///
+    /// Defaults to a [`PrintDebugSink`], which prints each event to stdout.
+    #[cfg(feature = "debug")]
+    pub fn set_debug_sink(&mut self, sink: Option<Box<dyn DebugSink>>) {
+        self.debug_sink = sink;
+    }
+
+    /// Emits a debugging event to the debug sink, if there is one
+    #[cfg(feature = "debug")]
+    #[inline]
+    pub(crate) fn debug_event(&mut self, event: &DebugEvent<'_>) {
+        if let Some(sink) = &mut self.debug_sink {
+            sink.event(event);
+        }
+    }
+
+    /// Sets the coordinate convention used for [`Layout::location`]. Defaults to [`CoordinateOrigin::TopLeft`].
+    ///
+    /// The locations of layouts that have already been computed are converted to the new convention.
+    pub fn set_coordinate_origin(&mut self, origin: CoordinateOrigin) {
+        let previous = core::mem::replace(&mut self.config.coordinate_origin, origin);
+        if previous == origin {
+            return;
+        }
+
+        for (key, parent) in self.parents.iter() {
+            if let Some(parent) = *parent {
+                let parent_size = self.nodes[parent.into()].layout.size;
+                let layout = &mut self.nodes[key].layout;
+                let location = previous.location_to_top_left(layout.location, layout.size, parent_size);
+                layout.location = origin.top_left_to_location(location, layout.size, parent_size);
+            }
+        }
+    }
+
+    /// Returns the coordinate convention used for [`Layout::location`]
+    pub fn coordinate_origin(&self) -> CoordinateOrigin {
+        self.config.coordinate_origin
+    }
+
+    /// Enable memoization of subtree layouts. Memoization is disabled by default.
+    ///
+    /// When enabled, the layouts of the subtrees rooted at nodes marked with [`Taffy::set_memoization_root`] are
+    /// memoized. A structural fingerprint of each such subtree is computed from the styles, font sizes, scroll offsets
+    /// and structure of its nodes, and the layout of the subtree is memoized along with the constraints and rounding
+    /// configuration it was computed under. Subtrees with the same fingerprint that are laid out under the same
+    /// constraints and configuration then reuse the memoized layouts wholesale, even if they consist of newly created
+    /// nodes (e.g. after regenerating identical content and calling [`Taffy::set_children`]).
+    ///
+    /// Memoized layouts are discarded if they are not reused during the following call to [`Taffy::compute_layout`].
+    /// Subtrees containing nodes that need measuring or use [`Display::Custom`](crate::style::Display::Custom) are never
+    /// memoized, as the results of measure functions and custom layout algorithms can't be fingerprinted.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn enable_subtree_memoization(&mut self) {
+        self.config.use_subtree_memoization = true;
+    }
+
+    /// Disable memoization of subtree layouts, discarding any memoized layouts. Memoization is disabled by default.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn disable_subtree_memoization(&mut self) {
+        self.config.use_subtree_memoization = false;
+        self.subtree_memo.clear();
+    }
+
+    /// Sets whether the layout of the subtree rooted at the node is memoized once
+    /// [`Taffy::enable_subtree_memoization`] has been called. No node is a memoization root by default.
+    ///
+    /// Each memoized layout holds the state of every node in the subtree, so memoization roots should be placed at the
+    /// roots of regenerated content (such as list items or cards) rather than at every node: the cost of memoizing a
+    /// node grows with the size of its subtree.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn set_memoization_root(&mut self, node: NodeId, is_memoization_root: bool) -> TaffyResult<()> {
+        let node_data = self.nodes.get_mut(node.into()).ok_or(TaffyError::InvalidInputNode(node))?;
+        node_data.is_memoization_root = is_memoization_root;
+        Ok(())
+    }
+
+    /// Returns whether the node has been marked as a memoization root with [`Taffy::set_memoization_root`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn is_memoization_root(&self, node: NodeId) -> TaffyResult<bool> {
+        self.nodes
+            .get(node.into())
+            .map(|node_data| node_data.is_memoization_root)
+            .ok_or(TaffyError::InvalidInputNode(node))
+    }
+
+    /// Sets the maximum depth of tree that may be laid out. There is no maximum depth by default.
+    ///
+    /// When set, [`Taffy::compute_layout`] (and the other methods that compute layouts) checks the depth of the tree
+    /// before traversing it in any other way, and returns [`TaffyError::MaxDepthExceeded`] (including the path to the
+    /// offending node) if the tree is deeper than `max_depth`.
+    /// The root node is at depth `0`. This guards against runaway layouts of accidentally self-referencing trees.
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.config.max_depth = max_depth;
+    }
+
+    /// Sets the size of the viewport that viewport-relative units (such as [`Dimension::Vw`](crate::style::Dimension::Vw))
+    /// are resolved against
+    ///
+    /// If `None` (the default), the viewport is the space available to the root node passed to [`Taffy::compute_layout`],
+    /// with indefinite axes treated as zero.
+    pub fn set_viewport_size(&mut self, viewport_size: Option<Size<f32>>) {
+        self.config.viewport_size = viewport_size;
+    }
+
+    /// Returns the size of the viewport set with [`Taffy::set_viewport_size`], if any
+    pub fn viewport_size(&self) -> Option<Size<f32>> {
+        self.config.viewport_size
+    }
+
+    /// Sets the font size that `rem` units (see [`Dimension::Rem`](crate::style::Dimension::Rem)) are resolved against.
+    /// Defaults to `16.0`.
+    ///
+    /// This is also the font size of nodes that don't have one set with [`Taffy::set_font_size`], nor inherit one.
+    pub fn set_root_font_size(&mut self, root_font_size: f32) {
+        self.config.root_font_size = root_font_size;
+    }
+
+    /// Returns the font size that `rem` units are resolved against
+    pub fn root_font_size(&self) -> f32 {
+        self.config.root_font_size
+    }
+
+    /// Sets the font size of the node, which `em` units (see [`Dimension::Em`](crate::style::Dimension::Em)) in the
+    /// styles of the node and its descendants are resolved against, or removes it if `font_size` is `None`
+    ///
+    /// Nodes without a font size inherit the font size of their nearest ancestor that has one, or the root font size
+    /// set with [`Taffy::set_root_font_size`].
+    pub fn set_font_size(&mut self, node: NodeId, font_size: Option<f32>) -> TaffyResult<()> {
+        if !self.nodes.contains_key(node.into()) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+        match font_size {
+            Some(font_size) => self.font_sizes.insert(node.into(), font_size),
+            None => self.font_sizes.remove(node.into()),
+        };
+        self.clear_fingerprints(node);
+        // Nodes whose resolved style changes as a result are marked dirty at the start of the next layout computation
+        Ok(())
+    }
+
+    /// Returns the font size that has been set for the node with [`Taffy::set_font_size`], if any
+    pub fn font_size(&self, node: NodeId) -> Option<f32> {
+        self.font_sizes.get(node.into()).copied()
+    }
+
+    /// Returns the font size that `em` units in the node's style are resolved against
+    fn inherited_font_size(&self, node: NodeId) -> f32 {
+        let mut current = Some(node);
+        while let Some(node) = current {
+            if let Some(font_size) = self.font_sizes.get(node.into()) {
+                return *font_size;
+            }
+            current = self.parents[node.into()];
+        }
+        self.config.root_font_size
+    }
+
+    /// Resolves the custom unit with the given id for the node, using the resolver set with
+    /// [`Taffy::set_custom_unit_resolver`]. Custom units resolve to zero if no resolver has been set.
+    fn resolve_custom_unit(&self, unit: u32, node: NodeId) -> f32 {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(resolver) = &self.custom_unit_resolver {
+            return resolver.resolve(unit, node, self);
+        }
+        #[cfg(not(any(feature = "std", feature = "alloc")))]
+        let _ = (unit, node);
+        0.0
+    }
+
+    /// Returns the custom layout algorithm registered with the given key, if any
+    pub(crate) fn layout_algorithm(&self, key: u16) -> Option<CustomLayoutFunction> {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        return self.layout_algorithms.get(&key).copied();
+        #[cfg(not(any(feature = "std", feature = "alloc")))]
+        {
+            let _ = key;
+            None
+        }
+    }
+
     /// Creates and adds a new unattached leaf node to the tree, and returns the node of the new node
     pub fn new_leaf(&mut self, layout: Style) -> TaffyResult<NodeId> {
         let id = self.nodes.insert(NodeData::new(layout));
-        let _ = self.children.insert(new_vec_with_capacity(0));
+        self.track_context_units(id);
+        let children = self.new_children_vec();
+        let _ = self.children.insert(children);
         let _ = self.parents.insert(None);
 
         Ok(id.into())
@@ -190,32 +877,76 @@ impl Taffy {
 
         let id = self.nodes.insert(data);
         self.measure_funcs.insert(id, measure);
+        self.track_context_units(id);
 
-        let _ = self.children.insert(new_vec_with_capacity(0));
+        let children = self.new_children_vec();
+        let _ = self.children.insert(children);
         let _ = self.parents.insert(None);
 
         Ok(id.into())
     }
 
+    /// Creates and adds a new unattached leaf node with the supplied [`ReplacedContent`], such as an image, and returns
+    /// the node of the new node
+    pub fn new_replaced_leaf(&mut self, layout: Style, content: ReplacedContent) -> TaffyResult<NodeId> {
+        let node = self.new_leaf(layout)?;
+        self.replaced_contents.insert(node.into(), content);
+        Ok(node)
+    }
+
     /// Creates and adds a new node, which may have any number of `children`
     pub fn new_with_children(&mut self, layout: Style, children: &[NodeId]) -> TaffyResult<NodeId> {
         let id = NodeId::from(self.nodes.insert(NodeData::new(layout)));
+        self.track_context_units(id.into());
 
         for child in children {
             self.parents[(*child).into()] = Some(id);
         }
 
-        let _ = self.children.insert(children.iter().copied().collect::<_>());
+        let mut children_vec = self.new_children_vec();
+        children_vec.extend(children.iter().copied());
+        let _ = self.children.insert(children_vec);
         let _ = self.parents.insert(None);
 
         Ok(id)
     }
 
+    /// Returns an empty list of children for a new node, reusing one kept in the node pool if possible
+    fn new_children_vec(&mut self) -> ChildrenVec<NodeId> {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(children) = self.node_pool.take_children() {
+            return children;
+        }
+        new_vec_with_capacity(0)
+    }
+
     /// Drops all nodes in the tree
     pub fn clear(&mut self) {
         self.nodes.clear();
         self.children.clear();
         self.parents.clear();
+        self.changed_layouts.clear();
+        self.interrupted_layouts.clear();
+        if let Some(damage) = &mut self.damage {
+            damage.remove_all_nodes();
+        }
+        self.resolved_styles.clear();
+        #[cfg(feature = "grid")]
+        self.auto_repetitions.clear();
+        self.font_sizes.clear();
+        self.replaced_contents.clear();
+        self.skipped_layouts.clear();
+        self.scroll_offsets.clear();
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            self.keys.clear();
+            self.nodes_by_key.clear();
+            self.node_classes.clear();
+            self.subtree_memo.clear();
+            self.virtual_lists.clear();
+        }
+        #[cfg(feature = "std")]
+        self.content_hashes.clear();
     }
 
     /// Remove a specific node from the tree and drop it
@@ -226,6 +957,7 @@ impl Taffy {
         if let Some(parent) = self.parents[key] {
             if let Some(children) = self.children.get_mut(parent.into()) {
                 children.retain(|f| *f != node);
+                self.mark_dirty(parent)?;
             }
         }
 
@@ -235,22 +967,144 @@ impl Taffy {
             }
         }
 
-        let _ = self.children.remove(key);
-        let _ = self.parents.remove(key);
-        let _ = self.nodes.remove(key);
+        self.remove_node_data(key);
 
         Ok(node)
     }
 
-    /// Sets the [`MeasureFunc`] of the associated node
-    pub fn set_measure(&mut self, node: NodeId, measure: Option<MeasureFunc>) -> TaffyResult<()> {
-        let key = node.into();
-        if let Some(measure) = measure {
-            self.nodes[key].needs_measure = true;
-            self.measure_funcs.insert(key, measure);
-        } else {
-            self.nodes[key].needs_measure = false;
-            self.measure_funcs.remove(key);
+    /// Drops the data of the node and every entry associated with it, without detaching it from its parent or children
+    pub(crate) fn remove_node_data(&mut self, key: DefaultKey) {
+        self.measure_funcs.remove(key);
+        self.resolved_styles.remove(key);
+        #[cfg(feature = "grid")]
+        self.auto_repetitions.remove(key);
+        self.font_sizes.remove(key);
+        self.replaced_contents.remove(key);
+        self.skipped_layouts.remove(key);
+        self.scroll_offsets.remove(key);
+        if let Some(damage) = &mut self.damage {
+            damage.remove_node(key);
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(node_key) = self.keys.remove(key) {
+            self.nodes_by_key.remove(&node_key);
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.node_classes.remove(key);
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.virtual_lists.remove(key);
+        #[cfg(feature = "std")]
+        self.content_hashes.remove(key);
+
+        #[cfg_attr(not(any(feature = "std", feature = "alloc")), allow(unused_variables))]
+        if let Some(children) = self.children.remove(key) {
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            self.node_pool.recycle_children(children);
+        }
+        let _ = self.parents.remove(key);
+        let _ = self.nodes.remove(key);
+    }
+
+    /// Assigns a unique `key` to the node, which can later be used to look the node up with [`Taffy::node_by_key`]
+    ///
+    /// Passing `None` removes the node's existing key. Returns [`TaffyError::DuplicateKey`] if the key has already
+    /// been assigned to a different node. Keys are released when their node is removed from the tree.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn set_key(&mut self, node: NodeId, key: Option<&str>) -> TaffyResult<()> {
+        let node_key = node.into();
+        if !self.nodes.contains_key(node_key) {
+            return Err(TaffyError::InvalidInputNode(node));
+        }
+
+        if let Some(key) = key {
+            match self.nodes_by_key.get(key) {
+                Some(owner) if *owner == node => return Ok(()),
+                Some(owner) => return Err(TaffyError::DuplicateKey { node: *owner }),
+                None => {}
+            }
+        }
+
+        if let Some(previous_key) = self.keys.remove(node_key) {
+            self.nodes_by_key.remove(&previous_key);
+        }
+
+        if let Some(key) = key {
+            self.keys.insert(node_key, String::from(key));
+            self.nodes_by_key.insert(String::from(key), node);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the key assigned to the node with [`Taffy::set_key`], if any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn key(&self, node: NodeId) -> Option<&str> {
+        self.keys.get(node.into()).map(String::as_str)
+    }
+
+    /// Returns the node that has been assigned the provided `key`, if any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn node_by_key(&self, key: &str) -> Option<NodeId> {
+        self.nodes_by_key.get(key).copied()
+    }
+
+    /// Sets whether the node needs to be measured
+    ///
+    /// Nodes that need measuring but don't have a [`MeasureFunc`] are measured by the function passed to
+    /// [`Taffy::compute_layout_with_measure`]. Setting a [`MeasureFunc`] with [`Taffy::set_measure`] also flags the node as needing measurement.
+    pub fn set_needs_measure(&mut self, node: NodeId, needs_measure: bool) -> TaffyResult<()> {
+        self.nodes[node.into()].needs_measure = needs_measure;
+        self.mark_dirty(node)?;
+
+        Ok(())
+    }
+
+    /// Sets the hash of the content measured by the node, which is used to look up its size in the shared [`MeasureCache`]
+    ///
+    /// Nodes with the same content hash must produce the same size when measured under the same constraints. Nodes
+    /// without a content hash are always measured. Changing the hash marks the node's measured size as outdated.
+    #[cfg(feature = "std")]
+    pub fn set_content_hash(&mut self, node: NodeId, content_hash: Option<u64>) -> TaffyResult<()> {
+        let key = node.into();
+        let previous = match content_hash {
+            Some(content_hash) => self.content_hashes.insert(key, content_hash),
+            None => self.content_hashes.remove(key),
+        };
+        if previous != content_hash {
+            self.mark_measure_dirty(node)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash of the content measured by the node, if one has been set with [`Taffy::set_content_hash`]
+    #[cfg(feature = "std")]
+    pub fn content_hash(&self, node: NodeId) -> Option<u64> {
+        self.content_hashes.get(node.into()).copied()
+    }
+
+    /// Sets the [`MeasureCache`] that is consulted before measuring nodes that have a content hash
+    ///
+    /// The cache may be shared between several trees, so that content displayed in each of them is only measured once.
+    /// Passing `None` removes the cache. The results already cached by the nodes of the tree are unaffected.
+    #[cfg(feature = "std")]
+    pub fn set_measure_cache(&mut self, measure_cache: Option<Arc<dyn MeasureCache>>) {
+        self.measure_cache = measure_cache;
+    }
+
+    /// Sets the [`MeasureFunc`] of the associated node
+    ///
+    /// Measure functions may also be set on nodes that have children. In that case the node is sized to fit both the
+    /// measured size and the size derived from its children (taking the larger of the two in each axis), and its
+    /// children are aligned within that size.
+    pub fn set_measure(&mut self, node: NodeId, measure: Option<MeasureFunc>) -> TaffyResult<()> {
+        let key = node.into();
+        if let Some(measure) = measure {
+            self.nodes[key].needs_measure = true;
+            self.measure_funcs.insert(key, measure);
+        } else {
+            self.nodes[key].needs_measure = false;
+            self.measure_funcs.remove(key);
         }
 
         self.mark_dirty(node)?;
@@ -258,6 +1112,53 @@ impl Taffy {
         Ok(())
     }
 
+    /// Sets the [`ReplacedContent`] of the node, or removes it if `content` is `None`
+    ///
+    /// A leaf with replaced content is sized from the natural size and aspect ratio of its content, following the rules
+    /// of CSS replaced elements (see [`compute_replaced`](crate::compute::compute_replaced)). Its measure function, if
+    /// any, is ignored. The content is ignored while the node has children.
+    pub fn set_replaced_content(&mut self, node: NodeId, content: Option<ReplacedContent>) -> TaffyResult<()> {
+        match content {
+            Some(content) => self.replaced_contents.insert(node.into(), content),
+            None => self.replaced_contents.remove(node.into()),
+        };
+        self.mark_dirty(node)
+    }
+
+    /// Returns the [`ReplacedContent`] that has been set for the node, if any
+    pub fn replaced_content(&self, node: NodeId) -> Option<ReplacedContent> {
+        self.replaced_contents.get(node.into()).copied()
+    }
+
+    /// Skips the layout of the node and its descendants until [`Taffy::resume_layout`] is called, like the CSS
+    /// `content-visibility: hidden` property
+    ///
+    /// Unlike a node with [`Display::None`](crate::style::Display::None), a skipped node keeps occupying space: it keeps
+    /// the size of its most recent layout, or `estimated_size` if it hasn't been laid out yet (unless its parent
+    /// determines its size). Its descendants are neither laid out nor measured, and their layouts and cached results are
+    /// left untouched so that they can be reused once its layout is resumed. Useful for offscreen tabs and collapsed
+    /// sections.
+    pub fn skip_layout(&mut self, node: NodeId, estimated_size: Size<f32>) -> TaffyResult<()> {
+        let size = self.nodes[node.into()].previous_layout.map_or(estimated_size, |layout| layout.size);
+        self.skipped_layouts.insert(node.into(), size);
+        self.mark_dirty(node)
+    }
+
+    /// Lays out the node and its descendants again after [`Taffy::skip_layout`], returning `false` if the node's layout
+    /// wasn't skipped
+    pub fn resume_layout(&mut self, node: NodeId) -> TaffyResult<bool> {
+        if self.skipped_layouts.remove(node.into()).is_none() {
+            return Ok(false);
+        }
+        self.mark_dirty(node)?;
+        Ok(true)
+    }
+
+    /// Returns true if the layout of the node is skipped, see [`Taffy::skip_layout`]
+    pub fn is_layout_skipped(&self, node: NodeId) -> bool {
+        self.skipped_layouts.contains_key(node.into())
+    }
+
     /// Adds a `child` node under the supplied `parent`
     pub fn add_child(&mut self, parent: NodeId, child: NodeId) -> TaffyResult<()> {
         let parent_key = parent.into();
@@ -269,6 +1170,15 @@ impl Taffy {
         Ok(())
     }
 
+    /// Adds the `children` nodes under the supplied `parent`, after its existing children
+    ///
+    /// This is equivalent to calling [`Taffy::add_child`] for each child, but grows the parent's list of children and
+    /// marks the parent dirty only once.
+    pub fn add_children(&mut self, parent: NodeId, children: &[NodeId]) -> TaffyResult<()> {
+        let child_count = self.children[parent.into()].len();
+        self.insert_children_at_index(parent, child_count, children)
+    }
+
     /// Inserts a `child` node at the given `child_index` under the supplied `parent`, shifting all children after it to the right.
     pub fn insert_child_at_index(&mut self, parent: NodeId, child_index: usize, child: NodeId) -> TaffyResult<()> {
         let parent_key = parent.into();
@@ -285,23 +1195,74 @@ impl Taffy {
         Ok(())
     }
 
-    /// Directly sets the `children` of the supplied `parent`
-    pub fn set_children(&mut self, parent: NodeId, children: &[NodeId]) -> TaffyResult<()> {
+    /// Inserts the `children` nodes at the given `child_index` under the supplied `parent`, shifting all children after
+    /// them to the right
+    ///
+    /// This is equivalent to calling [`Taffy::insert_child_at_index`] for each child, but moves the existing children
+    /// and marks the parent dirty only once.
+    pub fn insert_children_at_index(
+        &mut self,
+        parent: NodeId,
+        child_index: usize,
+        children: &[NodeId],
+    ) -> TaffyResult<()> {
         let parent_key = parent.into();
 
-        // Remove node as parent from all its current children.
-        for child in &self.children[parent_key] {
-            self.parents[(*child).into()] = None;
+        let child_count = self.children[parent_key].len();
+        if child_index > child_count {
+            return Err(TaffyError::ChildIndexOutOfBounds { parent, child_index, child_count });
+        }
+        if children.is_empty() {
+            return Ok(());
         }
 
-        // Build up relation node <-> child
         for child in children {
             self.parents[(*child).into()] = Some(parent);
         }
+        // The children are appended and then rotated into place, so the existing children are only moved once
+        let parent_children = &mut self.children[parent_key];
+        parent_children.extend(children.iter().copied());
+        parent_children[child_index..].rotate_right(children.len());
+        self.mark_dirty(parent)?;
+
+        Ok(())
+    }
+
+    /// Directly sets the `children` of the supplied `parent`
+    ///
+    /// The new list is compared against the existing children: setting an identical list leaves the tree untouched (so
+    /// no layouts are invalidated), and only the children in the range of positions that changed are detached from or
+    /// attached to the parent. Otherwise the parent is marked dirty, while the caches of its children are retained.
+    pub fn set_children(&mut self, parent: NodeId, children: &[NodeId]) -> TaffyResult<()> {
+        let parent_key = parent.into();
+        let current = &self.children[parent_key];
+        if current.as_slice() == children {
+            return Ok(());
+        }
+
+        // Only the children between the common prefix and the common suffix of the two lists have changed
+        let prefix = current.iter().zip(children).take_while(|(current, new)| current == new).count();
+        let max_suffix = current.len().min(children.len()) - prefix;
+        let suffix = current
+            .iter()
+            .rev()
+            .zip(children.iter().rev())
+            .take(max_suffix)
+            .take_while(|(current, new)| current == new)
+            .count();
+
+        let removed_end = current.len() - suffix;
+        for index in prefix..removed_end {
+            let child = self.children[parent_key][index];
+            self.parents[child.into()] = None;
+        }
+        for child in &children[prefix..children.len() - suffix] {
+            self.parents[(*child).into()] = Some(parent);
+        }
 
         let parent_children = &mut self.children[parent_key];
-        parent_children.clear();
-        children.iter().for_each(|child| parent_children.push(*child));
+        parent_children.truncate(prefix);
+        children[prefix..].iter().for_each(|child| parent_children.push(*child));
 
         self.mark_dirty(parent)?;
 
@@ -334,6 +1295,57 @@ impl Taffy {
         Ok(child)
     }
 
+    /// Removes the children of `parent` for which `keep` returns `false`, in a single pass over its children
+    ///
+    /// The removed children are not removed from the tree entirely, they are simply no longer attached to `parent`.
+    /// Use [`Taffy::retain_children_removing_subtrees`] to drop them and their descendants instead. The parent is only
+    /// marked dirty if a child was removed.
+    pub fn retain_children(&mut self, parent: NodeId, keep: impl FnMut(NodeId) -> bool) -> TaffyResult<()> {
+        let removed = self.retain_children_internal(parent, keep)?;
+        for child in removed {
+            self.parents[child.into()] = None;
+        }
+        Ok(())
+    }
+
+    /// Removes the children of `parent` for which `keep` returns `false`, and drops them and all of their descendants
+    /// from the tree, in a single pass over the children of `parent`
+    ///
+    /// The parent is only marked dirty if a child was removed.
+    pub fn retain_children_removing_subtrees(
+        &mut self,
+        parent: NodeId,
+        keep: impl FnMut(NodeId) -> bool,
+    ) -> TaffyResult<()> {
+        let mut stack = self.retain_children_internal(parent, keep)?;
+        while let Some(node) = stack.pop() {
+            stack.extend(self.children[node.into()].iter().copied());
+            self.remove_node_data(node.into());
+        }
+        Ok(())
+    }
+
+    /// Removes the children of `parent` for which `keep` returns `false`, marking `parent` dirty if any were removed,
+    /// and returns the removed children
+    fn retain_children_internal(
+        &mut self,
+        parent: NodeId,
+        mut keep: impl FnMut(NodeId) -> bool,
+    ) -> TaffyResult<Vec<NodeId>> {
+        let mut removed = Vec::new();
+        self.children[parent.into()].retain(|child| {
+            let retained = keep(*child);
+            if !retained {
+                removed.push(*child);
+            }
+            retained
+        });
+        if !removed.is_empty() {
+            self.mark_dirty(parent)?;
+        }
+        Ok(removed)
+    }
+
     /// Replaces the child at the given `child_index` from the `parent` node with the new `child` node
     ///
     /// The child is not removed from the tree entirely, it is simply no longer attached to its previous parent.
@@ -381,10 +1393,45 @@ impl Taffy {
     }
 
     /// Sets the [`Style`] of the provided `node`
+    ///
+    /// If the new style only differs from the previous style in properties that affect the position of the node or its
+    /// children (content alignment, or the inset of a relatively positioned node), then the cached sizes of the node and
+    /// its ancestors are retained. If the node has style classes (see [`Taffy::set_style_classes`]), this sets its own
+    /// style, which its classes are applied on top of.
     pub fn set_style(&mut self, node: NodeId, style: Style) -> TaffyResult<()> {
-        self.nodes[node.into()].style = style;
-        self.mark_dirty(node)?;
-        Ok(())
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(node_classes) = self.node_classes.get_mut(node.into()) {
+            node_classes.own_style = style;
+            return self.apply_style_classes(node.into());
+        }
+        self.replace_style(node, style)
+    }
+
+    /// Replaces the [`Style`] of the provided `node`, marking it dirty as described by [`Taffy::set_style`]
+    pub(crate) fn replace_style(&mut self, node: NodeId, style: Style) -> TaffyResult<()> {
+        let mut old_style = core::mem::replace(&mut self.nodes[node.into()].style, style);
+        self.track_context_units(node.into());
+        let new_style = &self.nodes[node.into()].style;
+
+        // Copy over the properties that only affect positioning, and check whether anything else has changed
+        let mut only_position_changed = false;
+        if old_style.position == Position::Relative && new_style.position == Position::Relative {
+            only_position_changed |= old_style.inset != new_style.inset;
+            old_style.inset = new_style.inset;
+        }
+        // Content alignment only positions the children of flex containers. Grids also take it into account when
+        // estimating the sizes of items that span several tracks, so it can change their size.
+        #[cfg(feature = "flexbox")]
+        if old_style.display == crate::style::Display::Flex && new_style.display == crate::style::Display::Flex {
+            only_position_changed |= old_style.justify_content != new_style.justify_content
+                || old_style.align_content != new_style.align_content;
+            old_style.justify_content = new_style.justify_content;
+            old_style.align_content = new_style.align_content;
+        }
+        only_position_changed &= old_style == *new_style;
+
+        let level = if only_position_changed { InvalidationLevel::Position } else { InvalidationLevel::SelfSize };
+        self.mark_dirty_with(node, level)
     }
 
     /// Gets the [`Style`] of the provided `node`
@@ -392,43 +1439,367 @@ impl Taffy {
         Ok(&self.nodes[node.into()].style)
     }
 
+    /// Gets the [`Style`] that the provided `node` is laid out with
+    ///
+    /// This differs from the node's own style if it uses context-dependent units (such as viewport-relative units), or
+    /// if it has virtual children (see [`Taffy::set_virtual_children`]).
+    #[inline(always)]
+    pub(crate) fn layout_style(&self, node: NodeId) -> &Style {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if !self.virtual_lists.is_empty() {
+            if let Some(list) = self.virtual_lists.get(node.into()) {
+                return &list.style;
+            }
+        }
+        self.resolved_style(node.into())
+    }
+
+    /// Gets the style of the node with its context-dependent units resolved
+    #[inline(always)]
+    pub(crate) fn resolved_style(&self, key: DefaultKey) -> &Style {
+        if !self.resolved_styles.is_empty() {
+            if let Some(style) = self.resolved_styles.get(key) {
+                return style;
+            }
+        }
+        &self.nodes[key].style
+    }
+
+    /// Starts or stops tracking the node's style for context-dependent units, depending on whether it uses any
+    pub(super) fn track_context_units(&mut self, key: DefaultKey) {
+        let style = &self.nodes[key].style;
+        if style.uses_context_units() {
+            // The style is resolved at the start of the next layout computation
+            self.resolved_styles.insert(key, style.clone());
+        } else {
+            self.resolved_styles.remove(key);
+        }
+    }
+
+    /// Resolves the context-dependent units in the styles of the nodes that use them, marking nodes whose resolved
+    /// style changed as dirty
+    ///
+    /// The viewport is the size set with [`Taffy::set_viewport_size`], or otherwise the definite parts of `available_space`.
+    /// Font-relative units are resolved against the font sizes set at the time of the call.
+    pub(crate) fn resolve_context_units(&mut self, available_space: Size<AvailableSpace>) {
+        for (node, style) in self.context_unit_changes(available_space) {
+            self.resolved_styles.insert(node.into(), style);
+            // The node is known to exist, as it has just been read from
+            let _ = self.mark_dirty(node);
+        }
+    }
+
+    /// Returns the nodes whose resolved style would be changed by [`Taffy::resolve_context_units`], along with their
+    /// new resolved styles
+    pub(crate) fn context_unit_changes(&self, available_space: Size<AvailableSpace>) -> Vec<(NodeId, Style)> {
+        let mut changed: Vec<(NodeId, Style)> = Vec::new();
+        if self.resolved_styles.is_empty() {
+            return changed;
+        }
+
+        let viewport = self.config.viewport_size.unwrap_or(Size {
+            width: available_space.width.into_option().unwrap_or(0.0),
+            height: available_space.height.into_option().unwrap_or(0.0),
+        });
+        let root_font_size = self.config.root_font_size;
+
+        for (key, resolved_style) in self.resolved_styles.iter() {
+            let node = NodeId::from(key);
+            let context = UnitContext {
+                viewport,
+                root_font_size,
+                font_size: self.inherited_font_size(node),
+                resolve_custom: &|unit| self.resolve_custom_unit(unit, node),
+            };
+            let mut style = self.nodes[key].style.clone();
+            style.resolve_context_units(&context);
+            if *resolved_style != style {
+                changed.push((key.into(), style));
+            }
+        }
+        changed
+    }
+
     /// Return this node layout relative to its parent
     pub fn layout(&self, node: NodeId) -> TaffyResult<&Layout> {
         Ok(&self.nodes[node.into()].layout)
     }
 
+    /// Returns the layout of this node relative to its parent in physical pixels, which is its [`Layout`] multiplied by
+    /// the scale factor (see [`Taffy::set_scale_factor`])
+    ///
+    /// While rounding is enabled (and cells aren't used instead, see [`Taffy::set_cell_mode`]), every value is a whole
+    /// number of physical pixels.
+    pub fn physical_layout(&self, node: NodeId) -> TaffyResult<Layout> {
+        let layout = *self.layout(node)?;
+        let scale_factor = self.config.scale_factor;
+        let is_rounded = self.config.use_rounding && self.config.cell_mode.is_none();
+        // The logical layout is a whole number of physical pixels divided by the scale factor, so this only removes the
+        // error introduced by that division
+        let scale = |value: f32| if is_rounded { round(value * scale_factor) } else { value * scale_factor };
+        Ok(Layout {
+            order: layout.order,
+            size: layout.size.map(scale),
+            location: Point { x: scale(layout.location.x), y: scale(layout.location.y) },
+        })
+    }
+
+    /// Returns an iterator over every node in the tree, along with its [`Style`] and [`Layout`]
+    ///
+    /// Nodes are yielded in the order that they are stored in, rather than in tree order, which makes this the fastest way
+    /// to visit all nodes when their hierarchy doesn't matter (for example to draw the whole tree as a flat list).
+    pub fn iter(&self) -> TaffyIter<'_> {
+        TaffyIter(self.nodes.iter())
+    }
+
+    /// Indicates whether the layout of this node changed during the most recent call to [`Taffy::compute_layout`]
+    ///
+    /// Nodes that had not been laid out before are considered to have changed.
+    pub fn layout_changed(&self, node: NodeId) -> TaffyResult<bool> {
+        Ok(self.nodes[node.into()].layout_changed)
+    }
+
+    /// Returns statistics gathered during the most recent call to [`Taffy::compute_layout`]
+    ///
+    /// This includes the number of nodes visited, measure function calls and cache hits, the deepest level of recursion
+    /// reached, and the wall time taken.
+    pub fn layout_stats(&self) -> LayoutStats {
+        self.stats
+    }
+
+    /// Returns an iterator over the nodes whose layout changed during the most recent call to [`Taffy::compute_layout`]
+    pub fn changed_layouts(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.changed_layouts.iter().copied().filter(|node| self.nodes.contains_key((*node).into()))
+    }
+
     /// Marks the layout computation of this node and its children as outdated
     ///
-    /// Performs a recursive depth-first search up the tree until the root node is reached
+    /// Equivalent to calling [`Taffy::mark_dirty_with`] with [`InvalidationLevel::SelfSize`]: the caches of the node and
+    /// its ancestors are cleared, while the caches of its descendants are retained.
     ///
-    /// WARNING: this will stack-overflow if the tree contains a cycle
+    /// WARNING: this will loop forever if the tree contains a cycle
     pub fn mark_dirty(&mut self, node: NodeId) -> TaffyResult<()> {
-        /// WARNING: this will stack-overflow if the tree contains a cycle
-        fn mark_dirty_recursive(
-            nodes: &mut SlotMap<DefaultKey, NodeData>,
-            parents: &SlotMap<DefaultKey, Option<NodeId>>,
-            node_key: DefaultKey,
-        ) {
-            nodes[node_key].mark_dirty();
-
-            if let Some(Some(node)) = parents.get(node_key) {
-                mark_dirty_recursive(nodes, parents, (*node).into());
-            }
+        self.mark_dirty_with(node, InvalidationLevel::SelfSize)
+    }
+
+    /// Marks the measured content size of this node as outdated
+    ///
+    /// Equivalent to calling [`Taffy::mark_dirty_with`] with [`InvalidationLevel::Measure`]. This should be used when the
+    /// content measured by the node's measure function (e.g. its text) has changed but its style has not: cached results
+    /// of the node that don't depend on its content size are retained, and the node's siblings and descendants are unaffected.
+    ///
+    /// WARNING: this will loop forever if the tree contains a cycle
+    pub fn mark_measure_dirty(&mut self, node: NodeId) -> TaffyResult<()> {
+        self.mark_dirty_with(node, InvalidationLevel::Measure)
+    }
+
+    /// Marks the layout computation of this node as outdated at the given [`InvalidationLevel`]
+    ///
+    /// The node's ancestors are walked up to the root and invalidated at the [`InvalidationLevel::ancestor_level`] of
+    /// `level`. If `level` is [`InvalidationLevel::Subtree`] then all of the node's descendants are also invalidated.
+    ///
+    /// WARNING: this will loop forever if the tree contains a cycle
+    pub fn mark_dirty_with(&mut self, node: NodeId, level: InvalidationLevel) -> TaffyResult<()> {
+        // Within a batch, dirty marking is deferred until the batch ends
+        if let Some(pending_dirty) = &mut self.pending_dirty {
+            pending_dirty.push((node, level));
+            return Ok(());
         }
 
-        mark_dirty_recursive(&mut self.nodes, &self.parents, node.into());
+        self.invalidate_node(node, level);
+
+        let ancestor_level = level.ancestor_level();
+        let mut parent = self.parents[node.into()];
+        while let Some(ancestor) = parent {
+            self.nodes[ancestor.into()].mark_dirty(ancestor_level);
+            parent = self.parents[ancestor.into()];
+        }
 
         Ok(())
     }
 
+    /// Invalidates the cache of the node itself (and of all its descendants for [`InvalidationLevel::Subtree`])
+    fn invalidate_node(&mut self, node: NodeId, level: InvalidationLevel) {
+        self.nodes[node.into()].mark_dirty(level);
+
+        if level == InvalidationLevel::Subtree {
+            let mut stack: Vec<NodeId> = self.children[node.into()].iter().copied().collect();
+            while let Some(descendant) = stack.pop() {
+                self.nodes[descendant.into()].mark_dirty(level);
+                stack.extend(self.children[descendant.into()].iter().copied());
+            }
+        }
+    }
+
+    /// Discards the cached subtree fingerprints of the node and its ancestors, without invalidating their layouts
+    ///
+    /// Used when data that is part of the fingerprint but doesn't otherwise mark nodes dirty (such as scroll offsets)
+    /// changes.
+    pub(crate) fn clear_fingerprints(&mut self, node: NodeId) {
+        let mut current = Some(node);
+        while let Some(node) = current {
+            let Some(node_data) = self.nodes.get_mut(node.into()) else { break };
+            node_data.fingerprint = None;
+            current = self.parents[node.into()];
+        }
+    }
+
+    /// Applies a batch of updates to the tree, propagating dirty-marking only once when the batch ends
+    ///
+    /// Every call to [`Taffy::mark_dirty`] (including the implicit calls made by methods such as [`Taffy::set_style`]
+    /// and [`Taffy::set_children`]) made within `updates` is deferred and deduplicated, so each ancestor chain is walked
+    /// at most once. Note that [`Taffy::dirty`] may therefore report stale values until the batch has ended.
+    ///
+    /// Batches may be nested, in which case dirty-marking is deferred until the outermost batch ends.
+    pub fn update<R>(&mut self, updates: impl FnOnce(&mut Taffy) -> R) -> R {
+        if self.pending_dirty.is_some() {
+            return updates(self);
+        }
+
+        self.pending_dirty = Some(Vec::new());
+        // The guard ends the batch even if `updates` panics, so that later dirty-marking isn't deferred forever
+        let batch = UpdateBatch { taffy: self };
+        updates(&mut *batch.taffy)
+    }
+
+    /// Marks each of the supplied nodes and their ancestors as dirty, visiting each ancestor at most once per level
+    fn flush_pending_dirty(&mut self, pending_dirty: Vec<(NodeId, InvalidationLevel)>) {
+        let mut visited: SecondaryMap<DefaultKey, InvalidationLevel> = SecondaryMap::new();
+        for (node, level) in pending_dirty {
+            // Nodes may have been removed after being marked dirty
+            if !self.nodes.contains_key(node.into()) {
+                continue;
+            }
+
+            self.invalidate_node(node, level);
+
+            let ancestor_level = level.ancestor_level();
+            let mut parent = self.parents[node.into()];
+            while let Some(ancestor) = parent {
+                let ancestor_key = ancestor.into();
+                // If the ancestor has already been invalidated at this level, then so have all of its own ancestors
+                if visited.get(ancestor_key).is_some_and(|visited_level| *visited_level >= ancestor_level) {
+                    break;
+                }
+                visited.insert(ancestor_key, ancestor_level);
+                self.nodes[ancestor_key].mark_dirty(ancestor_level);
+                parent = self.parents[ancestor_key];
+            }
+        }
+    }
+
     /// Indicates whether the layout of this node (and its children) need to be recomputed
     pub fn dirty(&self, node: NodeId) -> TaffyResult<bool> {
-        Ok(self.nodes[node.into()].cache.is_empty())
+        Ok(!self.nodes[node.into()].cache.has_layout())
+    }
+
+    /// Returns the layout cache of this node, which can be inspected to diagnose unexpected cache misses
+    ///
+    /// See [`Cache::entries`].
+    pub fn cache(&self, node: NodeId) -> TaffyResult<&Cache> {
+        Ok(&self.nodes[node.into()].cache)
     }
 
     /// Updates the stored layout of the provided `node` and its children
+    ///
+    /// Layout recurses once for each level of the tree. With the `stacker` feature (enabled by default) the call stack
+    /// is grown as required, so trees of any depth can be laid out. Without it, the depth of tree that can be laid out
+    /// is limited by the stack size of the calling thread: each level takes several kilobytes of stack, so only a few
+    /// hundred levels fit in the 2 MiB stack of a spawned thread in a debug build. Use [`Taffy::set_max_depth`] to
+    /// reject deeper trees with an error instead.
     pub fn compute_layout(&mut self, node: NodeId, available_space: Size<AvailableSpace>) -> Result<(), TaffyError> {
-        compute_layout(self, node, available_space)
+        compute_layout(self, node, available_space, no_measure, None)?;
+        Ok(())
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, unless interrupted by `should_yield`
+    ///
+    /// `should_yield` is called before each node that can't be served from its cache is laid out. Once it returns true
+    /// the computation stops, the layouts stored in the tree are left as they were before the call, and
+    /// [`LayoutProgress::Interrupted`] is returned. Results computed before the interruption remain cached, so calling
+    /// this method again (e.g. during the next frame) continues where the interrupted computation left off. This allows
+    /// huge relayouts to be spread over multiple frames.
+    pub fn compute_layout_with_yield(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        mut should_yield: impl FnMut() -> bool,
+    ) -> Result<LayoutProgress, TaffyError> {
+        compute_layout(self, node, available_space, no_measure, Some(&mut should_yield))
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, unless it takes longer than `budget`
+    ///
+    /// Equivalent to calling [`Taffy::compute_layout_with_yield`] with a callback that yields once `budget` has elapsed.
+    #[cfg(feature = "std")]
+    pub fn compute_layout_with_budget(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        budget: std::time::Duration,
+    ) -> Result<LayoutProgress, TaffyError> {
+        let start = std::time::Instant::now();
+        self.compute_layout_with_yield(node, available_space, || start.elapsed() >= budget)
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, measuring nodes using `measure_function`
+    ///
+    /// Nodes flagged with [`Taffy::set_needs_measure`] that don't have a [`MeasureFunc`] are measured by calling
+    /// `measure_function` with the node, its known dimensions and the available space. This avoids storing a boxed
+    /// [`MeasureFunc`] for every node, which is useful for trees that are rebuilt every frame.
+    pub fn compute_layout_with_measure(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        mut measure_function: impl FnMut(NodeId, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+    ) -> Result<(), TaffyError> {
+        self.compute_layout_with_styled_measure(
+            node,
+            available_space,
+            |node, _style, known_dimensions, available_space| measure_function(node, known_dimensions, available_space),
+        )
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, measuring nodes using `measure_function`
+    ///
+    /// This is equivalent to [`Taffy::compute_layout_with_measure`], except that `measure_function` is also passed the
+    /// [`Style`] that the node is laid out with, so that hints such as its overflow or aspect ratio can be taken into
+    /// account when measuring (e.g. when deciding whether text should wrap).
+    pub fn compute_layout_with_styled_measure(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+        measure_function: impl FnMut(NodeId, &Style, Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>,
+    ) -> Result<(), TaffyError> {
+        compute_layout(self, node, available_space, measure_function, None)?;
+        Ok(())
+    }
+
+    /// Computes the layout of the provided `node` and its children without updating the tree
+    ///
+    /// The computed layouts are returned in a [`LayoutMap`] instead of being stored on the nodes. The computation works on
+    /// its own copy of the layouts and caches of the subtree, so the tree is left untouched, which makes this useful for
+    /// "what-if" measurements.
+    pub fn compute_layout_detached(
+        &mut self,
+        node: NodeId,
+        available_space: Size<AvailableSpace>,
+    ) -> TaffyResult<LayoutMap> {
+        compute_detached_layout(self, node, available_space)
+    }
+}
+
+/// Ends a batch of updates started by [`Taffy::update`] when dropped, propagating the deferred dirty-marking
+struct UpdateBatch<'a> {
+    /// The tree that the batch is applied to
+    taffy: &'a mut Taffy,
+}
+
+impl Drop for UpdateBatch<'_> {
+    fn drop(&mut self) {
+        if let Some(pending_dirty) = self.taffy.pending_dirty.take() {
+            self.taffy.flush_pending_dirty(pending_dirty);
+        }
     }
 }
 
@@ -437,7 +1808,7 @@ mod tests {
     #![allow(clippy::bool_assert_comparison)]
 
     use super::*;
-    use crate::style::{Dimension, Display, FlexDirection};
+    use crate::style::{Dimension, Display, FlexDirection, JustifyContent};
     use crate::style_helpers::*;
     use crate::util::sys;
 
@@ -541,6 +1912,37 @@ mod tests {
         taffy.remove(parent).unwrap();
     }
 
+    #[test]
+    fn remove_should_mark_parent_dirty() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style::default()).unwrap();
+        let parent = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+
+        taffy.remove(child).unwrap();
+        assert!(taffy.dirty(parent).unwrap());
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn remove_should_drop_measure_funcs() {
+        let mut taffy = Taffy::new();
+        let measure: sys::Arc<dyn crate::tree::Measurable> =
+            sys::Arc::new(MeasureFunc::Raw(|_, _| Size { width: 10.0, height: 10.0 }));
+        let leaf = taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Shared(measure.clone())).unwrap();
+        let child = taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Shared(measure.clone())).unwrap();
+        let parent = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        assert_eq!(sys::Arc::strong_count(&measure), 3);
+
+        taffy.remove(leaf).unwrap();
+        assert_eq!(sys::Arc::strong_count(&measure), 2);
+
+        // Nodes dropped along with their subtree release their measure functions too
+        let root = taffy.new_with_children(Style::default(), &[parent]).unwrap();
+        taffy.retain_children_removing_subtrees(root, |_| false).unwrap();
+        assert_eq!(sys::Arc::strong_count(&measure), 1);
+    }
+
     #[test]
     fn set_measure() {
         let mut taffy = Taffy::new();
@@ -584,12 +1986,44 @@ mod tests {
     }
 
     #[test]
-    fn insert_child_at_index() {
+    fn add_children() {
         let mut taffy = Taffy::new();
-
         let child0 = taffy.new_leaf(Style::default()).unwrap();
-        let child1 = taffy.new_leaf(Style::default()).unwrap();
-        let child2 = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child0]).unwrap();
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        let children: Vec<NodeId> = (0..3).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        taffy.add_children(node, &children).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![child0, children[0], children[1], children[2]]);
+        assert!(children.iter().all(|child| taffy.parents[(*child).into()] == Some(node)));
+        assert!(taffy.dirty(node).unwrap());
+    }
+
+    #[test]
+    fn insert_children_at_index() {
+        let mut taffy = Taffy::new();
+        let child0 = taffy.new_leaf(Style::default()).unwrap();
+        let child1 = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child0, child1]).unwrap();
+
+        let inserted: Vec<NodeId> = (0..2).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        taffy.insert_children_at_index(node, 1, &inserted).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![child0, inserted[0], inserted[1], child1]);
+        assert_eq!(taffy.parents[inserted[1].into()], Some(node));
+
+        assert!(matches!(
+            taffy.insert_children_at_index(node, 5, &inserted),
+            Err(TaffyError::ChildIndexOutOfBounds { child_index: 5, child_count: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn insert_child_at_index() {
+        let mut taffy = Taffy::new();
+
+        let child0 = taffy.new_leaf(Style::default()).unwrap();
+        let child1 = taffy.new_leaf(Style::default()).unwrap();
+        let child2 = taffy.new_leaf(Style::default()).unwrap();
 
         let node = taffy.new_leaf(Style::default()).unwrap();
         assert_eq!(taffy.child_count(node).unwrap(), 0);
@@ -631,6 +2065,44 @@ mod tests {
         assert_eq!(taffy.children(node).unwrap()[1], child3);
     }
 
+    #[test]
+    fn set_children_should_not_dirty_an_unchanged_list() {
+        let mut taffy = Taffy::new();
+        let children: Vec<NodeId> = (0..3).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        let node = taffy.new_with_children(Style::default(), &children).unwrap();
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        taffy.set_children(node, &children).unwrap();
+        assert!(!taffy.dirty(node).unwrap());
+
+        // Appending a child dirties the parent, but not the existing children
+        let appended = taffy.new_leaf(Style::default()).unwrap();
+        taffy.set_children(node, &[children[0], children[1], children[2], appended]).unwrap();
+        assert!(taffy.dirty(node).unwrap());
+        assert!(!taffy.dirty(children[0]).unwrap());
+        assert_eq!(taffy.parents[appended.into()], Some(node));
+    }
+
+    #[test]
+    fn set_children_should_only_reparent_the_changed_positions() {
+        let mut taffy = Taffy::new();
+        let children: Vec<NodeId> = (0..4).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        let node = taffy.new_with_children(Style::default(), &children).unwrap();
+
+        let replacement = taffy.new_leaf(Style::default()).unwrap();
+        taffy.set_children(node, &[children[0], replacement, children[1], children[3]]).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![children[0], replacement, children[1], children[3]]);
+        assert_eq!(taffy.parents[replacement.into()], Some(node));
+        assert_eq!(taffy.parents[children[1].into()], Some(node));
+        assert_eq!(taffy.parents[children[2].into()], None);
+        assert_eq!(taffy.parents[children[3].into()], Some(node));
+
+        taffy.set_children(node, &[children[3]]).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![children[3]]);
+        assert_eq!(taffy.parents[children[0].into()], None);
+        assert_eq!(taffy.parents[children[3].into()], Some(node));
+    }
+
     /// Test that removing a child works
     #[test]
     fn remove_child() {
@@ -666,6 +2138,39 @@ mod tests {
         assert_eq!(taffy.child_count(node).unwrap(), 0);
     }
 
+    #[test]
+    fn retain_children() {
+        let mut taffy = Taffy::new();
+        let children: Vec<NodeId> = (0..5).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        let node = taffy.new_with_children(Style::default(), &children).unwrap();
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        taffy.retain_children(node, |_| true).unwrap();
+        assert!(!taffy.dirty(node).unwrap());
+
+        taffy.retain_children(node, |child| child != children[1] && child != children[3]).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![children[0], children[2], children[4]]);
+        assert!(taffy.dirty(node).unwrap());
+        // Detached children remain in the tree
+        assert_eq!(taffy.parents[children[1].into()], None);
+        assert!(taffy.layout(children[1]).is_ok());
+    }
+
+    #[test]
+    fn retain_children_removing_subtrees() {
+        let mut taffy = Taffy::new();
+        let grandchild = taffy.new_leaf(Style::default()).unwrap();
+        let removed = taffy.new_with_children(Style::default(), &[grandchild]).unwrap();
+        let kept = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[removed, kept]).unwrap();
+
+        taffy.retain_children_removing_subtrees(node, |child| child == kept).unwrap();
+        assert_eq!(taffy.children(node).unwrap(), vec![kept]);
+        assert_eq!(taffy.nodes.len(), 2);
+        assert!(!taffy.nodes.contains_key(removed.into()));
+        assert!(!taffy.nodes.contains_key(grandchild.into()));
+    }
+
     #[test]
     fn replace_child_at_index() {
         let mut taffy = Taffy::new();
@@ -779,6 +2284,637 @@ mod tests {
         assert_eq!(taffy.dirty(node).unwrap(), true);
     }
 
+    #[test]
+    fn mark_dirty_with_subtree_should_invalidate_descendants() {
+        let mut taffy = Taffy::new();
+        let grandchild = taffy.new_leaf(Style::default()).unwrap();
+        let child = taffy.new_with_children(Style::default(), &[grandchild]).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        taffy.mark_dirty_with(child, InvalidationLevel::SelfSize).unwrap();
+        assert_eq!(taffy.dirty(grandchild).unwrap(), false);
+        assert_eq!(taffy.dirty(child).unwrap(), true);
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        taffy.mark_dirty_with(child, InvalidationLevel::Subtree).unwrap();
+        assert_eq!(taffy.dirty(grandchild).unwrap(), true);
+        assert_eq!(taffy.dirty(child).unwrap(), true);
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+    }
+
+    #[test]
+    fn mark_measure_dirty_should_only_invalidate_node_and_ancestors() {
+        let mut taffy = Taffy::new();
+        let text = taffy.new_leaf(Style::default()).unwrap();
+        taffy.set_needs_measure(text, true).unwrap();
+        let sibling = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+        let node = taffy
+            .new_with_children(Style { size: Size::from_lengths(200.0, 100.0), ..Default::default() }, &[text, sibling])
+            .unwrap();
+
+        taffy
+            .compute_layout_with_measure(node, Size::MAX_CONTENT, |_, _, _| Size { width: 50.0, height: 10.0 })
+            .unwrap();
+        assert_eq!(taffy.layout(text).unwrap().size.width, 50.0);
+
+        taffy.mark_measure_dirty(text).unwrap();
+        assert_eq!(taffy.dirty(sibling).unwrap(), false);
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+
+        taffy
+            .compute_layout_with_measure(node, Size::MAX_CONTENT, |_, _, _| Size { width: 80.0, height: 10.0 })
+            .unwrap();
+        assert_eq!(taffy.layout(text).unwrap().size.width, 80.0);
+        assert_eq!(taffy.layout(sibling).unwrap().location.x, 80.0);
+    }
+
+    #[test]
+    fn subtree_memoization_should_reuse_layouts_of_identical_subtrees() {
+        fn build_card(taffy: &mut Taffy, width: f32) -> NodeId {
+            let title = taffy.new_leaf(Style { size: Size::from_lengths(width, 20.0), ..Default::default() }).unwrap();
+            let body = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+            let card = taffy
+                .new_with_children(
+                    Style {
+                        flex_direction: FlexDirection::Column,
+                        size: Size::from_lengths(100.0, 100.0),
+                        ..Default::default()
+                    },
+                    &[title, body],
+                )
+                .unwrap();
+            taffy.set_memoization_root(card, true).unwrap();
+            card
+        }
+
+        let mut taffy = Taffy::new();
+        taffy.enable_subtree_memoization();
+        let card = build_card(&mut taffy, 50.0);
+        let root = taffy.new_with_children(Style::default(), &[card]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let card_fingerprint = taffy.nodes[card.into()].fingerprint.flatten();
+        assert!(card_fingerprint.is_some());
+        // Only memoization roots are fingerprinted
+        assert_eq!(taffy.nodes[root.into()].fingerprint, None);
+
+        // Regenerating identical content reuses the memoized layout of the previous subtree
+        let new_card = build_card(&mut taffy, 50.0);
+        taffy.set_children(root, &[new_card]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.nodes[new_card.into()].fingerprint.flatten(), card_fingerprint);
+        let new_body = taffy.child_at_index(new_card, 1).unwrap();
+        assert_eq!(taffy.layout(new_body).unwrap().location.y, 20.0);
+        assert_eq!(taffy.layout(new_body).unwrap().size.height, 80.0);
+        // The caches of the reused subtree are restored too, so that it can be relaid out from them
+        assert!(!taffy.nodes[new_body.into()].cache.is_empty());
+
+        // Different content produces a different fingerprint and layout
+        let other_card = build_card(&mut taffy, 60.0);
+        taffy.set_children(root, &[other_card]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_ne!(taffy.nodes[other_card.into()].fingerprint.flatten(), card_fingerprint);
+        let other_title = taffy.child_at_index(other_card, 0).unwrap();
+        assert_eq!(taffy.layout(other_title).unwrap().size.width, 60.0);
+    }
+
+    #[test]
+    fn subtree_fingerprint_should_cover_font_sizes_and_scroll_offsets() {
+        let mut taffy = Taffy::new();
+        taffy.enable_subtree_memoization();
+        let leaf = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+        let card = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        taffy.set_memoization_root(card, true).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[card]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let fingerprint = taffy.nodes[card.into()].fingerprint.flatten();
+        assert!(fingerprint.is_some());
+
+        taffy.set_font_size(leaf, Some(20.0)).unwrap();
+        assert_eq!(taffy.nodes[card.into()].fingerprint, None);
+        taffy.mark_dirty(card).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let font_size_fingerprint = taffy.nodes[card.into()].fingerprint.flatten();
+        assert!(font_size_fingerprint.is_some());
+        assert_ne!(font_size_fingerprint, fingerprint);
+
+        taffy.set_scroll_offset(card, Point { x: 0.0, y: 5.0 }).unwrap();
+        assert_eq!(taffy.nodes[card.into()].fingerprint, None);
+        taffy.mark_dirty(card).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let scroll_offset_fingerprint = taffy.nodes[card.into()].fingerprint.flatten();
+        assert!(scroll_offset_fingerprint.is_some());
+        assert_ne!(scroll_offset_fingerprint, font_size_fingerprint);
+    }
+
+    #[test]
+    #[cfg(feature = "grid")]
+    fn subtree_memoization_should_restore_auto_repetitions_of_reused_grids() {
+        fn build_grid(taffy: &mut Taffy) -> NodeId {
+            let item = taffy.new_leaf(Style::default()).unwrap();
+            let grid = taffy
+                .new_with_children(
+                    Style {
+                        display: Display::Grid,
+                        size: Size::from_lengths(200.0, 50.0),
+                        grid_template_columns: vec![repeat(
+                            crate::style::GridTrackRepetition::AutoFill,
+                            vec![length(40.0)],
+                        )]
+                        .into(),
+                        ..Default::default()
+                    },
+                    &[item],
+                )
+                .unwrap();
+            taffy.set_memoization_root(grid, true).unwrap();
+            grid
+        }
+
+        let mut taffy = Taffy::new();
+        taffy.enable_subtree_memoization();
+        let grid = build_grid(&mut taffy);
+        let root = taffy.new_with_children(Style::default(), &[grid]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let repetitions = taffy.auto_repetitions(grid, AbsoluteAxis::Horizontal).unwrap().cloned();
+        assert!(repetitions.is_some());
+
+        let new_grid = build_grid(&mut taffy);
+        taffy.set_children(root, &[new_grid]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.auto_repetitions(new_grid, AbsoluteAxis::Horizontal).unwrap().cloned(), repetitions);
+    }
+
+    #[test]
+    fn layout_stats_should_describe_most_recent_layout() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy
+            .new_leaf_with_measure(Style::default(), MeasureFunc::Raw(|_, _| Size { width: 10.0, height: 10.0 }))
+            .unwrap();
+        let child = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        let stats = taffy.layout_stats();
+        assert!(stats.nodes_visited >= 3);
+        assert!(stats.measure_calls >= 1);
+        assert_eq!(stats.deepest_recursion, 3);
+
+        // A relayout of an unchanged tree is served entirely from the root's cache
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        let stats = taffy.layout_stats();
+        assert_eq!(stats.nodes_visited, 1);
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.measure_calls, 0);
+    }
+
+    #[test]
+    fn compute_layout_with_yield_should_resume_interrupted_layout() {
+        let mut taffy = Taffy::new();
+        let mut leaves = Vec::new();
+        let mut rows = Vec::new();
+        for i in 0..5 {
+            let row_leaves: Vec<NodeId> = (0..3)
+                .map(|j| {
+                    taffy
+                        .new_leaf(Style { size: Size::from_lengths(10.0 * (i + j) as f32, 10.0), ..Default::default() })
+                })
+                .collect::<Result<_, _>>()
+                .unwrap();
+            let row_style = Style { size: Size::from_lengths(100.0, 10.0), ..Default::default() };
+            rows.push(taffy.new_with_children(row_style, &row_leaves).unwrap());
+            leaves.extend(row_leaves);
+        }
+        let root = taffy
+            .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &rows)
+            .unwrap();
+
+        // Interrupting the computation leaves the previous layouts in place
+        let progress = taffy.compute_layout_with_yield(root, Size::MAX_CONTENT, || true).unwrap();
+        assert_eq!(progress, LayoutProgress::Interrupted);
+        assert_eq!(taffy.layout(root).unwrap().size, Size::ZERO);
+
+        // Each call makes progress, so the computation eventually completes
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let mut budget = 8;
+            let progress = taffy
+                .compute_layout_with_yield(root, Size::MAX_CONTENT, || {
+                    budget -= 1;
+                    budget < 0
+                })
+                .unwrap();
+            if progress == LayoutProgress::Complete {
+                break;
+            }
+            assert!(attempts < 50, "Layout computation did not make progress");
+        }
+        assert!(attempts > 1);
+
+        let layouts: Vec<Layout> = leaves.iter().map(|leaf| *taffy.layout(*leaf).unwrap()).collect();
+        let root_layout = *taffy.layout(root).unwrap();
+        taffy.mark_dirty_with(root, InvalidationLevel::Subtree).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_eq!(*taffy.layout(root).unwrap(), root_layout);
+        for (leaf, layout) in leaves.iter().zip(layouts) {
+            assert_eq!(*taffy.layout(*leaf).unwrap(), layout);
+        }
+    }
+
+    #[test]
+    fn set_style_with_position_only_change_should_retain_sizes() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+        let node = taffy
+            .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &[child])
+            .unwrap();
+        let root = taffy
+            .new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..Default::default() }, &[node])
+            .unwrap();
+
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.layout(child).unwrap().location.y, 0.0);
+
+        let style = Style {
+            flex_direction: FlexDirection::Column,
+            justify_content: Some(JustifyContent::End),
+            ..Default::default()
+        };
+        taffy.set_style(node, style).unwrap();
+
+        // Only the full layouts are discarded, cached sizes are retained
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+        assert_eq!(taffy.dirty(root).unwrap(), true);
+        assert_eq!(taffy.nodes[node.into()].cache.is_empty(), false);
+
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.layout(child).unwrap().location.y, 90.0);
+
+        // Changes to other properties discard all cached results
+        taffy.set_style(node, Style::default()).unwrap();
+        assert_eq!(taffy.nodes[node.into()].cache.is_empty(), true);
+    }
+
+    #[test]
+    #[cfg(feature = "grid")]
+    fn set_style_with_grid_content_alignment_change_should_discard_sizes() {
+        use crate::style::{AlignItems, GridPlacement};
+
+        // The height of the item depends on its width, which is estimated from the tracks it spans along with the
+        // gutters that content alignment adds between them
+        fn grid_tree(justify_content: JustifyContent) -> (Taffy, NodeId, NodeId) {
+            let mut taffy = Taffy::new();
+            let item = taffy
+                .new_leaf_with_measure(
+                    Style {
+                        grid_column: Line { start: GridPlacement::Auto, end: GridPlacement::Span(2) },
+                        ..Default::default()
+                    },
+                    MeasureFunc::Raw(|known_dimensions, available_space| {
+                        let width = known_dimensions.width.or(available_space.width.into_option()).unwrap_or(0.0);
+                        Size { width, height: known_dimensions.height.unwrap_or(10000.0 / width) }
+                    }),
+                )
+                .unwrap();
+            let grid = taffy
+                .new_with_children(
+                    Style {
+                        display: Display::Grid,
+                        size: Size { width: length(300.0), height: auto() },
+                        grid_template_columns: vec![length(50.0), length(50.0)].into(),
+                        justify_content: Some(justify_content),
+                        ..Default::default()
+                    },
+                    &[item],
+                )
+                .unwrap();
+            let root = taffy
+                .new_with_children(
+                    Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: Some(AlignItems::FlexStart),
+                        ..Default::default()
+                    },
+                    &[grid],
+                )
+                .unwrap();
+            taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+            (taffy, root, grid)
+        }
+
+        let (mut taffy, root, grid) = grid_tree(JustifyContent::Start);
+        assert_eq!(taffy.layout(grid).unwrap().size.height, 100.0);
+
+        let style = Style { justify_content: Some(JustifyContent::SpaceBetween), ..taffy.style(grid).unwrap().clone() };
+        taffy.set_style(grid, style).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+        let (fresh_taffy, _, fresh_grid) = grid_tree(JustifyContent::SpaceBetween);
+        assert_ne!(fresh_taffy.layout(fresh_grid).unwrap().size.height, 100.0);
+        assert_eq!(taffy.layout(grid).unwrap().size, fresh_taffy.layout(fresh_grid).unwrap().size);
+    }
+
+    #[test]
+    fn changed_layouts_should_only_contain_changed_nodes() {
+        let mut taffy = Taffy::new();
+        let child0 = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+        let child1 = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child0, child1]).unwrap();
+
+        // All nodes are considered changed on their first layout
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.changed_layouts().count(), 3);
+
+        // Nothing has changed
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.changed_layouts().count(), 0);
+
+        // Resizing the second child only changes the layout of the second child and its parent
+        taffy.set_style(child1, Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        let mut changed = taffy.changed_layouts().collect::<Vec<_>>();
+        changed.sort_by_key(|node| u64::from(*node));
+        let mut expected = vec![node, child1];
+        expected.sort_by_key(|node| u64::from(*node));
+        assert_eq!(changed, expected);
+        assert_eq!(taffy.layout_changed(child0).unwrap(), false);
+        assert_eq!(taffy.layout_changed(child1).unwrap(), true);
+    }
+
+    #[test]
+    fn compute_layout_should_fail_when_max_depth_exceeded() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_leaf(Style::default()).unwrap();
+        let child = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+        taffy.set_max_depth(Some(2));
+        assert!(taffy.compute_layout(node, Size::MAX_CONTENT).is_ok());
+
+        taffy.set_max_depth(Some(1));
+        match taffy.compute_layout(node, Size::MAX_CONTENT) {
+            Err(TaffyError::MaxDepthExceeded { max_depth, path }) => {
+                assert_eq!(max_depth, 1);
+                assert_eq!(path.as_slice(), &[node, child, leaf]);
+            }
+            result => panic!("Expected MaxDepthExceeded error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn compute_layout_should_fail_for_cyclic_trees_when_max_depth_set() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        // Marking nodes dirty would loop forever once the tree contains a cycle, so the cycle is created directly
+        taffy.children[child.into()].push(node);
+        taffy.parents[node.into()] = Some(child);
+        taffy.set_max_depth(Some(10));
+
+        let available_space = Size { width: AvailableSpace::Definite(100.), height: AvailableSpace::Definite(100.) };
+        assert!(matches!(taffy.compute_layout(node, available_space), Err(TaffyError::MaxDepthExceeded { .. })));
+        assert!(matches!(
+            taffy.compute_layout_with_yield(node, available_space, || false),
+            Err(TaffyError::MaxDepthExceeded { .. })
+        ));
+        assert!(matches!(
+            taffy.compute_layout_detached(node, available_space),
+            Err(TaffyError::MaxDepthExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn node_by_key_should_find_keyed_nodes() {
+        let mut taffy = Taffy::new();
+        let sidebar = taffy.new_leaf(Style::default()).unwrap();
+        let content = taffy.new_leaf(Style::default()).unwrap();
+
+        taffy.set_key(sidebar, Some("sidebar")).unwrap();
+        assert_eq!(taffy.node_by_key("sidebar"), Some(sidebar));
+        assert_eq!(taffy.key(sidebar), Some("sidebar"));
+        assert_eq!(taffy.node_by_key("content"), None);
+
+        // Keys are unique within a tree
+        assert!(matches!(
+            taffy.set_key(content, Some("sidebar")),
+            Err(TaffyError::DuplicateKey { node }) if node == sidebar
+        ));
+
+        // Re-keying a node releases its previous key
+        taffy.set_key(sidebar, Some("nav")).unwrap();
+        assert_eq!(taffy.node_by_key("sidebar"), None);
+        taffy.set_key(content, Some("sidebar")).unwrap();
+        assert_eq!(taffy.node_by_key("sidebar"), Some(content));
+
+        // Removing a node releases its key
+        taffy.remove(sidebar).unwrap();
+        assert_eq!(taffy.node_by_key("nav"), None);
+    }
+
+    #[test]
+    fn iter_should_yield_every_node_with_its_style_and_layout() {
+        let mut taffy = Taffy::new();
+        let style = Style { size: Size::from_lengths(10.0, 20.0), ..Default::default() };
+        let leaf = taffy.new_leaf(style.clone()).unwrap();
+        let removed = taffy.new_leaf(Style::default()).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        taffy.remove(removed).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+        let iter = taffy.iter();
+        assert_eq!(iter.len(), 2);
+        let mut nodes: Vec<_> = iter.collect();
+        nodes.sort_by_key(|(node, _, _)| *node);
+        let mut expected = vec![leaf, root];
+        expected.sort();
+        assert_eq!(nodes.iter().map(|(node, _, _)| *node).collect::<Vec<_>>(), expected);
+        let (_, leaf_style, leaf_layout) = nodes.iter().find(|(node, _, _)| *node == leaf).unwrap();
+        assert_eq!(**leaf_style, style);
+        assert_eq!(leaf_layout.size, Size { width: 10.0, height: 20.0 });
+    }
+
+    #[test]
+    fn node_pool_should_reuse_the_children_of_removed_nodes() {
+        let mut taffy = Taffy::new();
+        let leaves: Vec<NodeId> = (0..8).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        let rows: Vec<NodeId> =
+            (0..3).map(|index| taffy.new_with_children(Style::default(), &leaves[index..index + 4]).unwrap()).collect();
+
+        // Removed nodes aren't pooled by default, and leaves have no children to keep
+        taffy.remove(rows[0]).unwrap();
+        taffy.remove(leaves[0]).unwrap();
+        assert_eq!(taffy.node_pool_len(), 0);
+
+        taffy.set_node_pool_capacity(1);
+        taffy.remove(rows[1]).unwrap();
+        taffy.remove(rows[2]).unwrap();
+        assert_eq!(taffy.node_pool_len(), 1);
+
+        let row = taffy.new_with_children(Style::default(), &leaves[4..6]).unwrap();
+        assert_eq!(taffy.node_pool_len(), 0);
+        assert_eq!(taffy.children(row).unwrap(), &leaves[4..6]);
+        assert!(taffy.children[row.into()].capacity() >= 4);
+
+        taffy.remove(row).unwrap();
+        let leaf = taffy.new_leaf(Style::default()).unwrap();
+        assert_eq!(taffy.child_count(leaf).unwrap(), 0);
+        taffy.set_node_pool_capacity(0);
+        assert_eq!(taffy.node_pool_len(), 0);
+    }
+
+    #[test]
+    fn compact_should_remap_nodes_densely() {
+        let mut taffy = Taffy::new();
+        let removed: Vec<NodeId> = (0..4).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        let text = taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Raw(|_, _| Size::ZERO)).unwrap();
+        let leaf = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[leaf, text]).unwrap();
+        taffy.set_key(text, Some("text")).unwrap();
+        for node in removed {
+            taffy.remove(node).unwrap();
+        }
+
+        let mapping = taffy.compact();
+        assert_eq!(mapping.len(), 3);
+        assert_eq!(taffy.nodes.len(), 3);
+        let (root, leaf, text) = (mapping[&root], mapping[&leaf], mapping[&text]);
+
+        // Nodes are stored in depth-first order from the root
+        let nodes: Vec<NodeId> = taffy.iter().map(|(node, _, _)| node).collect();
+        assert_eq!(nodes, vec![root, leaf, text]);
+        assert_eq!(taffy.children(root).unwrap(), vec![leaf, text]);
+        assert_eq!(taffy.parents[text.into()], Some(root));
+        assert_eq!(taffy.style(leaf).unwrap().flex_grow, 1.0);
+        assert!(taffy.nodes[text.into()].needs_measure && taffy.measure_funcs.contains_key(text.into()));
+        assert_eq!(taffy.node_by_key("text"), Some(text));
+        assert_eq!(taffy.key(text), Some("text"));
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    }
+
+    #[test]
+    fn compact_should_release_memoized_layouts_and_pooled_allocations() {
+        let mut taffy = Taffy::new();
+        taffy.enable_subtree_memoization();
+        taffy.set_node_pool_capacity(4);
+        let leaf = taffy.new_leaf(Style::default()).unwrap();
+        let row = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        taffy.set_memoization_root(row, true).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[row]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let empty_memo = format!("{:?}", SubtreeMemo::default());
+        assert_ne!(format!("{:?}", taffy.subtree_memo), empty_memo);
+
+        let removed = taffy.new_leaf(Style::default()).unwrap();
+        let spare = taffy.new_with_children(Style::default(), &[removed]).unwrap();
+        taffy.remove(spare).unwrap();
+        assert_eq!(taffy.node_pool_len(), 1);
+
+        let mapping = taffy.compact();
+        assert_eq!(format!("{:?}", taffy.subtree_memo), empty_memo);
+        assert_eq!(taffy.node_pool_len(), 0);
+        taffy.compute_layout(mapping[&root], Size::MAX_CONTENT).unwrap();
+    }
+
+    #[test]
+    fn error_context_should_locate_the_offending_node() {
+        let mut taffy = Taffy::new();
+        let list = taffy.new_leaf(Style::default()).unwrap();
+        let panel = taffy.new_with_children(Style::default(), &[list]).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[panel]).unwrap();
+        taffy.set_key(root, Some("root")).unwrap();
+        taffy.set_key(list, Some("list")).unwrap();
+
+        let error = taffy.child_at_index(list, 2).unwrap_err();
+        let context = taffy.error_context(error);
+        assert_eq!(context.node, Some(list));
+        assert_eq!(context.label.as_deref(), Some("list"));
+        assert_eq!(
+            context.path,
+            vec![(root, Some(String::from("root"))), (panel, None), (list, Some(String::from("list")))]
+        );
+        assert_eq!(
+            context.to_string(),
+            format!(
+                "Index (is 2) should be < child_count (0) for parent node {list:?} (node \"list\") at \"root\" > {panel:?} > \"list\""
+            )
+        );
+
+        // Nodes that aren't in the tree have no location
+        taffy.remove(panel).unwrap();
+        let context = taffy.error_context(TaffyError::InvalidInputNode(panel));
+        assert_eq!(context.node, Some(panel));
+        assert!(context.path.is_empty());
+    }
+
+    #[test]
+    fn compute_layout_with_context_should_locate_the_offending_node() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_leaf(Style::default()).unwrap();
+        let panel = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[panel]).unwrap();
+        taffy.set_key(panel, Some("panel")).unwrap();
+        taffy.set_max_depth(Some(1));
+
+        let context = taffy.compute_layout_with_context(root, Size::MAX_CONTENT).unwrap_err();
+        assert!(matches!(context.error, TaffyError::MaxDepthExceeded { .. }));
+        assert_eq!(context.node, Some(leaf));
+        assert_eq!(context.path, vec![(root, None), (panel, Some(String::from("panel"))), (leaf, None)]);
+    }
+
+    #[test]
+    fn update_should_defer_dirty_marking() {
+        let mut taffy = Taffy::new();
+        let child0 = taffy.new_leaf(Style::default()).unwrap();
+        let child1 = taffy.new_leaf(Style::default()).unwrap();
+        let child2 = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child0, child1, child2]).unwrap();
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        taffy.update(|batch| {
+            batch.set_style(child0, Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+            batch.set_style(child1, Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+
+            // Dirty marking is deferred until the end of the batch
+            assert_eq!(batch.dirty(child0).unwrap(), false);
+            assert_eq!(batch.dirty(node).unwrap(), false);
+        });
+
+        assert_eq!(taffy.dirty(child0).unwrap(), true);
+        assert_eq!(taffy.dirty(child1).unwrap(), true);
+        assert_eq!(taffy.dirty(child2).unwrap(), false);
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        assert_eq!(taffy.layout(node).unwrap().size.width, 30.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn update_should_end_the_batch_when_it_panics() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            taffy.update(|batch| {
+                batch.set_style(child, Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+                panic!("update failed");
+            })
+        }));
+        assert!(result.is_err());
+
+        // The changes made before the panic have been propagated, and later changes are no longer deferred
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+        taffy.set_style(child, Style::default()).unwrap();
+        assert_eq!(taffy.dirty(node).unwrap(), true);
+    }
+
     #[test]
     fn compute_layout_should_produce_valid_result() {
         let mut taffy = Taffy::new();
@@ -795,6 +2931,130 @@ mod tests {
         assert!(layout_result.is_ok());
     }
 
+    #[test]
+    fn compute_layout_detached_should_not_modify_tree() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+        let node = taffy
+            .new_with_children(Style { size: Size::from_percent(1.0, 1.0), ..Default::default() }, &[child])
+            .unwrap();
+
+        taffy
+            .compute_layout(
+                node,
+                Size { width: AvailableSpace::Definite(100.), height: AvailableSpace::Definite(100.) },
+            )
+            .unwrap();
+        assert_eq!(taffy.layout(child).unwrap().size.width, 100.0);
+
+        let layouts = taffy
+            .compute_layout_detached(
+                node,
+                Size { width: AvailableSpace::Definite(50.), height: AvailableSpace::Definite(50.) },
+            )
+            .unwrap();
+
+        assert_eq!(layouts.len(), 2);
+        assert_eq!(layouts.get(node).unwrap().size.width, 50.0);
+        assert_eq!(layouts.get(child).unwrap().size.width, 50.0);
+        assert_eq!(taffy.layout(node).unwrap().size.width, 100.0);
+        assert_eq!(taffy.layout(child).unwrap().size.width, 100.0);
+        assert_eq!(taffy.dirty(node).unwrap(), false);
+    }
+
+    #[test]
+    fn compute_layout_detached_should_not_modify_context_units_or_stats() {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_leaf(Style { size: Size { width: Dimension::Vw(0.5), height: Dimension::Auto }, ..Default::default() })
+            .unwrap();
+        let node = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        let available_space = Size { width: AvailableSpace::Definite(100.), height: AvailableSpace::Definite(100.) };
+        taffy.compute_layout(node, available_space).unwrap();
+        let stats = taffy.layout_stats();
+
+        // The viewport units of the child resolve against the available space of the detached computation
+        let detached_space = Size { width: AvailableSpace::Definite(40.), height: AvailableSpace::Definite(40.) };
+        let layouts = taffy.compute_layout_detached(node, detached_space).unwrap();
+        assert_eq!(layouts.get(child).unwrap().size.width, 20.0);
+
+        // ...but the tree keeps the units resolved against the previous available space, and stays clean
+        assert_eq!(taffy.dirty(child).unwrap(), false);
+        assert_eq!(taffy.dirty(node).unwrap(), false);
+        assert_eq!(taffy.layout_stats(), stats);
+        taffy.compute_layout(node, available_space).unwrap();
+        assert_eq!(taffy.layout(child).unwrap().size.width, 50.0);
+    }
+
+    #[test]
+    #[cfg(feature = "grid")]
+    fn compute_layout_detached_should_not_modify_tree_that_was_never_laid_out() {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_leaf(Style { size: Size { width: Dimension::Vw(0.5), height: Dimension::Auto }, ..Default::default() })
+            .unwrap();
+        let grid = taffy
+            .new_with_children(
+                Style {
+                    display: Display::Grid,
+                    size: Size::from_lengths(40.0, 40.0),
+                    grid_template_columns: vec![repeat(
+                        crate::style::GridTrackRepetition::AutoFill,
+                        vec![length(10.0)],
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+        let resolved_styles = format!("{:?}", taffy.resolved_styles);
+
+        let available_space = Size { width: AvailableSpace::Definite(40.), height: AvailableSpace::Definite(40.) };
+        let layouts = taffy.compute_layout_detached(grid, available_space).unwrap();
+        assert_eq!(layouts.get(grid).unwrap().size.width, 40.0);
+        assert_eq!(layouts.get(child).unwrap().size.width, 20.0);
+
+        // No state is recorded for nodes that had none before
+        assert_eq!(format!("{:?}", taffy.resolved_styles), resolved_styles);
+        assert!(taffy.auto_repetitions.is_empty());
+        assert_eq!(taffy.layout_stats(), LayoutStats::default());
+        for node in [grid, child] {
+            assert_eq!(taffy.dirty(node).unwrap(), true);
+            assert_eq!(*taffy.layout(node).unwrap(), Layout::new());
+            assert!(taffy.nodes[node.into()].cache.is_empty());
+        }
+
+        // Laying out the tree afterwards gives the same result
+        taffy.compute_layout(grid, available_space).unwrap();
+        assert_eq!(taffy.layout(child).unwrap().size.width, 20.0);
+        assert_eq!(taffy.auto_repetitions[grid.into()].width.as_ref().unwrap().repetitions, 4);
+    }
+
+    #[test]
+    fn compute_layout_detached_should_not_modify_memo_or_explanation() {
+        let mut taffy = Taffy::new();
+        taffy.enable_subtree_memoization();
+        let child = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+        let card = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        taffy.set_memoization_root(card, true).unwrap();
+        let root = taffy.new_with_children(Style::default(), &[card]).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let memo = format!("{:?}", taffy.subtree_memo);
+
+        let explained = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+        let other_root = taffy.new_with_children(Style::default(), &[explained]).unwrap();
+        taffy.explain_node(Some(explained)).unwrap();
+
+        let detached_space = Size { width: AvailableSpace::Definite(40.), height: AvailableSpace::Definite(40.) };
+        taffy.compute_layout_detached(root, detached_space).unwrap();
+        taffy.compute_layout_detached(other_root, detached_space).unwrap();
+
+        assert_eq!(format!("{:?}", taffy.subtree_memo), memo);
+        assert_eq!(taffy.size_explanation(), None);
+        assert_eq!(taffy.dirty(explained).unwrap(), true);
+    }
+
     #[test]
     fn make_sure_layout_location_is_top_left() {
         use crate::prelude::Rect;
@@ -0,0 +1,183 @@
+//! Virtualized children, which are represented by a count and an estimated size instead of real nodes
+use core::fmt;
+use core::ops::Range;
+use slotmap::DefaultKey;
+
+use crate::geometry::AbsoluteAxis;
+use crate::style::{LengthPercentage, Style};
+use crate::tree::NodeId;
+use crate::util::sys::{Box, Vec};
+
+use super::{Taffy, TaffyResult};
+
+/// A list of children that is only partially present in the tree
+///
+/// A node with virtual children stands for `count` children, of which only those in a window starting at index
+/// `first_materialized` exist as real nodes (the node's actual children). The children before and after the window
+/// are not laid out, but the space that they take up along `axis` is reserved using `estimated_size`. This allows
+/// scrollable lists with a huge number of items to only keep the visible items in the tree.
+///
+/// The space is reserved by extending the node's padding along `axis`, so the node's own padding along that axis
+/// should be specified as a length.
+pub struct VirtualChildren {
+    /// The total number of children, including the materialized children
+    pub count: usize,
+    /// The index of the first materialized child
+    pub first_materialized: usize,
+    /// The axis along which the children are stacked
+    pub axis: AbsoluteAxis,
+    /// Returns the estimated size of the child at the provided index along `axis`, including any gap between items
+    pub estimated_size: Box<dyn Fn(usize) -> f32 + Send + Sync>,
+}
+
+impl fmt::Debug for VirtualChildren {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VirtualChildren")
+            .field("count", &self.count)
+            .field("first_materialized", &self.first_materialized)
+            .field("axis", &self.axis)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VirtualChildren {
+    /// Creates a list of `count` virtual children stacked along `axis`, none of which are materialized
+    #[must_use]
+    pub fn new(
+        count: usize,
+        axis: AbsoluteAxis,
+        estimated_size: impl Fn(usize) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        Self { count, first_materialized: 0, axis, estimated_size: Box::new(estimated_size) }
+    }
+
+    /// Returns the summed estimated size of the children in `range`
+    fn estimated_length(&self, range: Range<usize>) -> f32 {
+        range.map(|index| (self.estimated_size)(index)).sum()
+    }
+}
+
+/// The virtual children of a node, along with the style that the node is laid out with
+pub(crate) struct VirtualList {
+    /// The virtual children of the node
+    pub(crate) children: VirtualChildren,
+    /// The style of the node, with its padding extended to reserve space for the children that aren't materialized
+    pub(crate) style: Style,
+}
+
+impl Taffy {
+    /// Sets the virtual children of the node, or removes them if `virtual_children` is `None`
+    ///
+    /// The node's actual children are the materialized children, starting at
+    /// [`VirtualChildren::first_materialized`]. See [`VirtualChildren`] for details.
+    pub fn set_virtual_children(&mut self, node: NodeId, virtual_children: Option<VirtualChildren>) -> TaffyResult<()> {
+        match virtual_children {
+            Some(children) => {
+                let style = self.resolved_style(node.into()).clone();
+                self.virtual_lists.insert(node.into(), VirtualList { children, style });
+            }
+            None => {
+                self.virtual_lists.remove(node.into());
+            }
+        }
+        self.mark_dirty(node)
+    }
+
+    /// Returns the virtual children of the node, if it has any
+    pub fn virtual_children(&self, node: NodeId) -> Option<&VirtualChildren> {
+        self.virtual_lists.get(node.into()).map(|list| &list.children)
+    }
+
+    /// Returns a mutable reference to the virtual children of the node, if it has any
+    ///
+    /// The node is marked dirty, as changing its virtual children (e.g. moving the materialized window) affects its layout.
+    pub fn virtual_children_mut(&mut self, node: NodeId) -> TaffyResult<Option<&mut VirtualChildren>> {
+        if !self.virtual_lists.contains_key(node.into()) {
+            return Ok(None);
+        }
+        self.mark_dirty(node)?;
+        Ok(self.virtual_lists.get_mut(node.into()).map(|list| &mut list.children))
+    }
+
+    /// Returns the range of virtual child indexes that overlap the `length` units following `offset` along the list's axis
+    ///
+    /// Offsets are measured from the start of the node's first child, and are based on the estimated sizes of the
+    /// children. This can be used to decide which children to materialize for the visible part of a scrolled list.
+    /// Returns an empty range if the node doesn't have virtual children.
+    pub fn virtual_window(&self, node: NodeId, offset: f32, length: f32) -> Range<usize> {
+        let children = match self.virtual_lists.get(node.into()) {
+            Some(list) => &list.children,
+            None => return 0..0,
+        };
+
+        let mut start = children.count;
+        let mut position = 0.0;
+        for index in 0..children.count {
+            let end = position + (children.estimated_size)(index);
+            if end > offset {
+                start = index;
+                break;
+            }
+            position = end;
+        }
+
+        let mut end = start;
+        while end < children.count && position < offset + length {
+            position += (children.estimated_size)(end);
+            end += 1;
+        }
+
+        start..end
+    }
+
+    /// Updates the style that each node with virtual children is laid out with, marking nodes whose style changed as dirty
+    ///
+    /// The space reserved for the children that aren't materialized depends on the node's style and children, so this
+    /// is done before each layout computation.
+    pub(crate) fn update_virtual_lists(&mut self) {
+        for (node, style) in self.virtual_list_changes(|_| None) {
+            self.virtual_lists[node.into()].style = style;
+            // The node is known to exist, as it has just been read from
+            let _ = self.mark_dirty(node);
+        }
+    }
+
+    /// Returns the nodes whose style would be changed by [`Taffy::update_virtual_lists`], along with their new styles
+    ///
+    /// The styles are derived from the resolved style of each node, unless `resolved_style` returns a different one.
+    pub(crate) fn virtual_list_changes<'a>(
+        &'a self,
+        resolved_style: impl Fn(DefaultKey) -> Option<&'a Style>,
+    ) -> Vec<(NodeId, Style)> {
+        let mut changed: Vec<(NodeId, Style)> = Vec::new();
+        for (key, list) in self.virtual_lists.iter() {
+            let children = &list.children;
+            let materialized_end = (children.first_materialized + self.children[key].len())
+                .min(children.count)
+                .max(children.first_materialized);
+            let before = children.estimated_length(0..children.first_materialized.min(children.count));
+            let after = children.estimated_length(materialized_end..children.count);
+
+            let mut style = resolved_style(key).unwrap_or_else(|| self.resolved_style(key)).clone();
+            let (start, end) = match children.axis {
+                AbsoluteAxis::Horizontal => (&mut style.padding.left, &mut style.padding.right),
+                AbsoluteAxis::Vertical => (&mut style.padding.top, &mut style.padding.bottom),
+            };
+            *start = extend_padding(*start, before);
+            *end = extend_padding(*end, after);
+
+            if list.style != style {
+                changed.push((key.into(), style));
+            }
+        }
+        changed
+    }
+}
+
+/// Adds `extra` to a padding value, treating padding that isn't a length as zero
+fn extend_padding(padding: LengthPercentage, extra: f32) -> LengthPercentage {
+    match padding {
+        LengthPercentage::Length(length) => LengthPercentage::Length(length + extra),
+        _ => LengthPercentage::Length(extra),
+    }
+}
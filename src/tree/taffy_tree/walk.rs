@@ -0,0 +1,46 @@
+//! Depth-first walks of the computed layouts of a subtree, which the layout exports are built on
+use crate::geometry::Point;
+use crate::tree::{Layout, NodeId};
+use crate::util::sys::Vec;
+
+use super::Taffy;
+
+/// A node visited by [`Taffy::walk_absolute_layouts`]
+pub(super) struct AbsoluteLayout<'a> {
+    /// The visited node
+    pub(super) node: NodeId,
+    /// The computed layout of the node
+    pub(super) layout: &'a Layout,
+    /// The position of the node's top-left corner, relative to the top-left of the root of the walk
+    pub(super) position: Point<f32>,
+    /// The index of the node's parent in the order of the walk, or `u32::MAX` for the root of the walk
+    pub(super) parent_index: u32,
+    /// The number of ancestors of the node below the root of the walk, so 0 for the root
+    pub(super) depth: u32,
+}
+
+impl Taffy {
+    /// Visits the computed layouts of `root` and its descendants in depth-first order, starting with `root`
+    ///
+    /// Positions are absolute, and y increases downwards whichever [`CoordinateOrigin`](crate::tree::CoordinateOrigin)
+    /// the tree uses. They don't take scroll offsets into account. `root` must be a node of the tree.
+    pub(super) fn walk_absolute_layouts(&self, root: NodeId, mut visit: impl FnMut(AbsoluteLayout<'_>)) {
+        // Each entry holds a node along with the index of its parent, the absolute position of its parent and its depth
+        let mut stack: Vec<(NodeId, u32, Point<f32>, u32)> = Vec::new();
+        stack.push((root, u32::MAX, Point::ZERO, 0));
+        let mut index: u32 = 0;
+        while let Some((node, parent_index, parent_position, depth)) = stack.pop() {
+            let layout = &self.nodes[node.into()].layout;
+            let location = match self.parents[node.into()] {
+                Some(parent) if node != root => self.top_left_location(node, parent),
+                _ => Point::ZERO,
+            };
+            let position = Point { x: parent_position.x + location.x, y: parent_position.y + location.y };
+            visit(AbsoluteLayout { node, layout, position, parent_index, depth });
+
+            // Children are pushed in reverse so that they are visited in order
+            stack.extend(self.children[node.into()].iter().rev().map(|child| (*child, index, position, depth + 1)));
+            index += 1;
+        }
+    }
+}
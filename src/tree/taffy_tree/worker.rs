@@ -0,0 +1,155 @@
+//! A helper for computing layouts on a background thread
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+
+use crate::geometry::Size;
+use crate::style::AvailableSpace;
+use crate::tree::NodeId;
+
+use super::{LayoutMap, Taffy, TaffyResult};
+
+/// A batch of changes to apply to the tree owned by a [`LayoutWorker`]
+type Mutation = Box<dyn FnOnce(&mut Taffy) + Send>;
+
+/// A message sent from a [`LayoutWorker`] to its worker thread
+struct Request {
+    /// The changes to apply to the tree before laying it out
+    mutation: Mutation,
+    /// The node to lay out once the changes have been applied
+    root: NodeId,
+    /// The space available to `root`
+    available_space: Size<AvailableSpace>,
+}
+
+/// The layouts of a subtree at a point in time, as computed by a [`LayoutWorker`] or published to a
+/// [`LayoutReader`](crate::tree::LayoutReader)
+#[derive(Debug, Clone)]
+pub struct LayoutSnapshot {
+    /// Identifies the point in time that the snapshot was taken at, increasing with every snapshot
+    ///
+    /// For a [`LayoutWorker`] this is the number of batches that had been applied to the tree, and for a
+    /// [`LayoutReader`](crate::tree::LayoutReader) it is the number of layouts that have been published.
+    pub generation: u64,
+    /// The node that was laid out
+    pub root: NodeId,
+    /// The layout of `root` and each of its descendants
+    pub layouts: LayoutMap,
+}
+
+/// Owns a [`Taffy`] tree on a worker thread, applying batches of changes and computing layouts in the background
+///
+/// Changes are submitted with [`LayoutWorker::submit`]. The worker applies every batch that has been submitted since
+/// it last computed a layout, lays out the tree once, and publishes a [`LayoutSnapshot`] which can be polled with
+/// [`LayoutWorker::try_snapshot`] (e.g. once per frame on the UI thread) or waited for with
+/// [`LayoutWorker::wait_snapshot`].
+#[derive(Debug)]
+pub struct LayoutWorker {
+    /// Sends requests to the worker thread
+    requests: Option<Sender<Request>>,
+    /// Receives the results of layout computations from the worker thread
+    snapshots: Receiver<TaffyResult<LayoutSnapshot>>,
+    /// The worker thread, which returns the tree once all requests have been handled
+    thread: Option<JoinHandle<Taffy>>,
+}
+
+impl LayoutWorker {
+    /// Moves `taffy` onto a new worker thread
+    #[must_use]
+    pub fn spawn(taffy: Taffy) -> Self {
+        let (request_sender, request_receiver) = mpsc::channel();
+        let (snapshot_sender, snapshot_receiver) = mpsc::channel();
+        let thread = thread::spawn(move || run_worker(taffy, request_receiver, snapshot_sender));
+
+        Self { requests: Some(request_sender), snapshots: snapshot_receiver, thread: Some(thread) }
+    }
+
+    /// Queues a batch of changes to the tree, after which `root` is laid out within `available_space`
+    ///
+    /// Batches that are submitted while the worker is busy are applied together, and only the layout requested by the
+    /// most recent batch is computed.
+    pub fn submit(
+        &self,
+        mutation: impl FnOnce(&mut Taffy) + Send + 'static,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+    ) {
+        if let Some(requests) = &self.requests {
+            // The worker thread only stops once the sender is dropped (or if a mutation panicked), in which case there
+            // is nothing left to do with the request
+            let _ = requests.send(Request { mutation: Box::new(mutation), root, available_space });
+        }
+    }
+
+    /// Returns the most recent snapshot that has been published since the last call, without blocking
+    ///
+    /// Older snapshots are discarded. Returns `None` if no snapshot has been published.
+    pub fn try_snapshot(&self) -> Option<TaffyResult<LayoutSnapshot>> {
+        let mut latest = None;
+        loop {
+            match self.snapshots.try_recv() {
+                Ok(snapshot) => latest = Some(snapshot),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return latest,
+            }
+        }
+    }
+
+    /// Blocks until the next snapshot is published
+    ///
+    /// Returns `None` if the worker thread has stopped.
+    pub fn wait_snapshot(&self) -> Option<TaffyResult<LayoutSnapshot>> {
+        self.snapshots.recv().ok()
+    }
+
+    /// Stops the worker thread once it has handled every submitted batch, and returns the tree
+    ///
+    /// # Panics
+    ///
+    /// Panics if the worker thread panicked, which happens if a submitted mutation panicked.
+    #[must_use]
+    pub fn join(mut self) -> Taffy {
+        self.requests = None;
+        let thread = self.thread.take().expect("The worker thread is only joined once");
+        thread.join().expect("The layout worker thread panicked")
+    }
+}
+
+impl Drop for LayoutWorker {
+    fn drop(&mut self) {
+        // Dropping the sender stops the worker thread once it has handled the remaining requests
+        self.requests = None;
+    }
+}
+
+/// The body of the worker thread
+fn run_worker(mut taffy: Taffy, requests: Receiver<Request>, snapshots: Sender<TaffyResult<LayoutSnapshot>>) -> Taffy {
+    let mut generation = 0;
+    while let Ok(mut request) = requests.recv() {
+        // Apply every batch that is already queued before computing a single layout
+        loop {
+            (request.mutation)(&mut taffy);
+            generation += 1;
+            match requests.try_recv() {
+                Ok(next) => request = next,
+                Err(_) => break,
+            }
+        }
+
+        let root = request.root;
+        let result = taffy.compute_layout(root, request.available_space).map(|()| {
+            let mut layouts = LayoutMap::new();
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                layouts.insert(node, taffy.nodes[node.into()].layout);
+                stack.extend(taffy.children[node.into()].iter().copied());
+            }
+            LayoutSnapshot { generation, root, layouts }
+        });
+
+        if snapshots.send(result).is_err() {
+            // The worker has been dropped, so nobody is waiting for further snapshots
+            break;
+        }
+    }
+
+    taffy
+}
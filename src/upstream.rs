@@ -0,0 +1,668 @@
+//! Conversions between this crate's types and those of the upstream [`taffy`](https://docs.rs/taffy/0.9) crate, enabled by the `upstream` feature
+//!
+//! Lets styles produced by ecosystems built on upstream `taffy` be laid out by this crate, and layouts be handed back
+//! to them. Geometry types, keyword enums and [`Layout`] convert with [`From`]. Lengths, track sizing functions and
+//! [`Style`] convert with [`TryFrom`], which fails with a [`ConversionError`] when a value has no equivalent on the
+//! other side, such as an upstream `calc()` length or named grid line, or one of this crate's viewport, font-relative
+//! or custom units.
+//!
+//! Style properties that only exist on one side are set to their defaults when converting to that side, and must be
+//! set to their defaults when converting from it. Upstream's `Overflow::Clip` only differs from `Overflow::Visible` in
+//! how content is painted, so it is converted to [`Overflow::Visible`].
+use core::fmt::{Display as DisplayTrait, Formatter, Result as FmtResult};
+
+use upstream_taffy as up;
+use upstream_taffy::style::CompactLength;
+use upstream_taffy::style_helpers::TaffyGridLine as _;
+
+use crate::compute::grid::GridLine;
+use crate::geometry::{Line, MinMax, Point, Rect, Size};
+use crate::style::{
+    AlignContent, AlignItems, AvailableSpace, Contain, Dimension, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, GridTrackList, GridTrackRepetition, LengthPercentage, LengthPercentageAuto, MaxTrackSizingFunction,
+    MinTrackSizingFunction, NonRepeatedTrackSizingFunction, Overflow, Position, Style, TrackSizingFunction,
+    VerticalPercentageBasis,
+};
+use crate::tree::Layout;
+
+/// An error that occurs when a value can't be converted to or from its upstream equivalent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionError {
+    /// An upstream `calc()` length
+    Calc,
+    /// A length in one of this crate's viewport-relative or font-relative units, which are only resolved during layout
+    RelativeUnit,
+    /// A length in the application-defined unit with the given id
+    CustomUnit(u32),
+    /// A [`Display::Custom`] mode with the given id
+    CustomDisplay(u16),
+    /// An upstream named grid line, named span, line name or grid template area
+    GridName,
+    /// A style property that only exists on one side, set to a value other than its default
+    UnsupportedProperty(&'static str),
+}
+
+impl DisplayTrait for ConversionError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ConversionError::Calc => write!(f, "calc() lengths are not supported"),
+            ConversionError::RelativeUnit => write!(f, "Viewport and font relative lengths are not supported upstream"),
+            ConversionError::CustomUnit(id) => write!(f, "Custom unit {id} is not supported upstream"),
+            ConversionError::CustomDisplay(id) => write!(f, "Custom display mode {id} is not supported upstream"),
+            ConversionError::GridName => write!(f, "Named grid lines and areas are not supported"),
+            ConversionError::UnsupportedProperty(property) => {
+                write!(f, "The {property} property must have its default value")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Fails with [`ConversionError::UnsupportedProperty`] if a one-sided property isn't set to its default
+fn require_default(is_default: bool, property: &'static str) -> Result<(), ConversionError> {
+    if is_default {
+        Ok(())
+    } else {
+        Err(ConversionError::UnsupportedProperty(property))
+    }
+}
+
+// Geometry
+
+impl<T> From<up::geometry::Size<T>> for Size<T> {
+    fn from(size: up::geometry::Size<T>) -> Self {
+        Size { width: size.width, height: size.height }
+    }
+}
+
+impl<T> From<Size<T>> for up::geometry::Size<T> {
+    fn from(size: Size<T>) -> Self {
+        up::geometry::Size { width: size.width, height: size.height }
+    }
+}
+
+impl<T> From<up::geometry::Rect<T>> for Rect<T> {
+    fn from(rect: up::geometry::Rect<T>) -> Self {
+        Rect { left: rect.left, right: rect.right, top: rect.top, bottom: rect.bottom }
+    }
+}
+
+impl<T> From<Rect<T>> for up::geometry::Rect<T> {
+    fn from(rect: Rect<T>) -> Self {
+        up::geometry::Rect { left: rect.left, right: rect.right, top: rect.top, bottom: rect.bottom }
+    }
+}
+
+impl<T> From<up::geometry::Point<T>> for Point<T> {
+    fn from(point: up::geometry::Point<T>) -> Self {
+        Point { x: point.x, y: point.y }
+    }
+}
+
+impl<T> From<Point<T>> for up::geometry::Point<T> {
+    fn from(point: Point<T>) -> Self {
+        up::geometry::Point { x: point.x, y: point.y }
+    }
+}
+
+impl<T> From<up::geometry::Line<T>> for Line<T> {
+    fn from(line: up::geometry::Line<T>) -> Self {
+        Line { start: line.start, end: line.end }
+    }
+}
+
+impl<T> From<Line<T>> for up::geometry::Line<T> {
+    fn from(line: Line<T>) -> Self {
+        up::geometry::Line { start: line.start, end: line.end }
+    }
+}
+
+impl<Min, Max> From<up::geometry::MinMax<Min, Max>> for MinMax<Min, Max> {
+    fn from(min_max: up::geometry::MinMax<Min, Max>) -> Self {
+        MinMax { min: min_max.min, max: min_max.max }
+    }
+}
+
+impl<Min, Max> From<MinMax<Min, Max>> for up::geometry::MinMax<Min, Max> {
+    fn from(min_max: MinMax<Min, Max>) -> Self {
+        up::geometry::MinMax { min: min_max.min, max: min_max.max }
+    }
+}
+
+/// Converts each side of a rectangle, failing if any side can't be converted
+fn try_rect<T, U: TryFrom<T, Error = ConversionError>>(rect: Rect<T>) -> Result<Rect<U>, ConversionError> {
+    Ok(Rect {
+        left: rect.left.try_into()?,
+        right: rect.right.try_into()?,
+        top: rect.top.try_into()?,
+        bottom: rect.bottom.try_into()?,
+    })
+}
+
+/// Converts both dimensions of a size, failing if either can't be converted
+fn try_size<T, U: TryFrom<T, Error = ConversionError>>(size: Size<T>) -> Result<Size<U>, ConversionError> {
+    Ok(Size { width: size.width.try_into()?, height: size.height.try_into()? })
+}
+
+// Layout
+
+impl From<up::tree::Layout> for Layout {
+    fn from(layout: up::tree::Layout) -> Self {
+        Layout { order: layout.order, size: layout.size.into(), location: layout.location.into() }
+    }
+}
+
+/// The content size, scrollbar size, border, padding and margin that upstream layouts carry are zero
+impl From<Layout> for up::tree::Layout {
+    fn from(layout: Layout) -> Self {
+        up::tree::Layout {
+            order: layout.order,
+            size: layout.size.into(),
+            location: layout.location.into(),
+            ..up::tree::Layout::new()
+        }
+    }
+}
+
+// Lengths
+
+impl TryFrom<up::style::LengthPercentage> for LengthPercentage {
+    type Error = ConversionError;
+
+    fn try_from(value: up::style::LengthPercentage) -> Result<Self, Self::Error> {
+        let raw = value.into_raw();
+        match raw.tag() {
+            CompactLength::LENGTH_TAG => Ok(LengthPercentage::Length(raw.value())),
+            CompactLength::PERCENT_TAG => Ok(LengthPercentage::Percent(raw.value())),
+            _ => Err(ConversionError::Calc),
+        }
+    }
+}
+
+impl TryFrom<LengthPercentage> for up::style::LengthPercentage {
+    type Error = ConversionError;
+
+    fn try_from(value: LengthPercentage) -> Result<Self, Self::Error> {
+        match value {
+            LengthPercentage::Length(length) => Ok(Self::length(length)),
+            LengthPercentage::Percent(percent) => Ok(Self::percent(percent)),
+            LengthPercentage::Vw(_)
+            | LengthPercentage::Vh(_)
+            | LengthPercentage::Vmin(_)
+            | LengthPercentage::Vmax(_)
+            | LengthPercentage::Rem(_)
+            | LengthPercentage::Em(_) => Err(ConversionError::RelativeUnit),
+            LengthPercentage::Custom(id) => Err(ConversionError::CustomUnit(id)),
+        }
+    }
+}
+
+impl TryFrom<up::style::LengthPercentageAuto> for LengthPercentageAuto {
+    type Error = ConversionError;
+
+    fn try_from(value: up::style::LengthPercentageAuto) -> Result<Self, Self::Error> {
+        let raw = value.into_raw();
+        match raw.tag() {
+            CompactLength::LENGTH_TAG => Ok(LengthPercentageAuto::Length(raw.value())),
+            CompactLength::PERCENT_TAG => Ok(LengthPercentageAuto::Percent(raw.value())),
+            CompactLength::AUTO_TAG => Ok(LengthPercentageAuto::Auto),
+            _ => Err(ConversionError::Calc),
+        }
+    }
+}
+
+impl TryFrom<LengthPercentageAuto> for up::style::LengthPercentageAuto {
+    type Error = ConversionError;
+
+    fn try_from(value: LengthPercentageAuto) -> Result<Self, Self::Error> {
+        match value {
+            LengthPercentageAuto::Length(length) => Ok(Self::length(length)),
+            LengthPercentageAuto::Percent(percent) => Ok(Self::percent(percent)),
+            LengthPercentageAuto::Auto => Ok(Self::auto()),
+            LengthPercentageAuto::Vw(_)
+            | LengthPercentageAuto::Vh(_)
+            | LengthPercentageAuto::Vmin(_)
+            | LengthPercentageAuto::Vmax(_)
+            | LengthPercentageAuto::Rem(_)
+            | LengthPercentageAuto::Em(_) => Err(ConversionError::RelativeUnit),
+            LengthPercentageAuto::Custom(id) => Err(ConversionError::CustomUnit(id)),
+        }
+    }
+}
+
+impl TryFrom<up::style::Dimension> for Dimension {
+    type Error = ConversionError;
+
+    fn try_from(value: up::style::Dimension) -> Result<Self, Self::Error> {
+        let raw = value.into_raw();
+        match raw.tag() {
+            CompactLength::LENGTH_TAG => Ok(Dimension::Length(raw.value())),
+            CompactLength::PERCENT_TAG => Ok(Dimension::Percent(raw.value())),
+            CompactLength::AUTO_TAG => Ok(Dimension::Auto),
+            _ => Err(ConversionError::Calc),
+        }
+    }
+}
+
+impl TryFrom<Dimension> for up::style::Dimension {
+    type Error = ConversionError;
+
+    fn try_from(value: Dimension) -> Result<Self, Self::Error> {
+        match value {
+            Dimension::Length(length) => Ok(Self::length(length)),
+            Dimension::Percent(percent) => Ok(Self::percent(percent)),
+            Dimension::Auto => Ok(Self::auto()),
+            Dimension::Vw(_)
+            | Dimension::Vh(_)
+            | Dimension::Vmin(_)
+            | Dimension::Vmax(_)
+            | Dimension::Rem(_)
+            | Dimension::Em(_) => Err(ConversionError::RelativeUnit),
+            Dimension::Custom(id) => Err(ConversionError::CustomUnit(id)),
+        }
+    }
+}
+
+impl From<up::style::AvailableSpace> for AvailableSpace {
+    fn from(space: up::style::AvailableSpace) -> Self {
+        match space {
+            up::style::AvailableSpace::Definite(length) => AvailableSpace::Definite(length),
+            up::style::AvailableSpace::MinContent => AvailableSpace::MinContent,
+            up::style::AvailableSpace::MaxContent => AvailableSpace::MaxContent,
+        }
+    }
+}
+
+impl From<AvailableSpace> for up::style::AvailableSpace {
+    fn from(space: AvailableSpace) -> Self {
+        match space {
+            AvailableSpace::Definite(length) => up::style::AvailableSpace::Definite(length),
+            AvailableSpace::MinContent => up::style::AvailableSpace::MinContent,
+            AvailableSpace::MaxContent => up::style::AvailableSpace::MaxContent,
+        }
+    }
+}
+
+// Keyword enums
+
+/// Implements [`From`] in both directions for keyword enums whose variants correspond one to one
+macro_rules! convert_keywords {
+    ($name:ident, [$($variant:ident),+ $(,)?]) => {
+        impl From<up::style::$name> for $name {
+            fn from(value: up::style::$name) -> Self {
+                match value {
+                    $(up::style::$name::$variant => $name::$variant,)+
+                }
+            }
+        }
+
+        impl From<$name> for up::style::$name {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => up::style::$name::$variant,)+
+                }
+            }
+        }
+    };
+}
+
+convert_keywords!(Position, [Relative, Absolute]);
+convert_keywords!(AlignItems, [Start, End, FlexStart, FlexEnd, Center, Baseline, Stretch]);
+convert_keywords!(
+    AlignContent,
+    [Start, End, FlexStart, FlexEnd, Center, Stretch, SpaceBetween, SpaceEvenly, SpaceAround]
+);
+convert_keywords!(FlexDirection, [Row, Column, RowReverse, ColumnReverse]);
+convert_keywords!(FlexWrap, [NoWrap, Wrap, WrapReverse]);
+convert_keywords!(GridAutoFlow, [Row, Column, RowDense, ColumnDense]);
+
+impl From<up::style::RepetitionCount> for GridTrackRepetition {
+    fn from(count: up::style::RepetitionCount) -> Self {
+        match count {
+            up::style::RepetitionCount::AutoFill => GridTrackRepetition::AutoFill,
+            up::style::RepetitionCount::AutoFit => GridTrackRepetition::AutoFit,
+            up::style::RepetitionCount::Count(count) => GridTrackRepetition::Count(count),
+        }
+    }
+}
+
+impl From<GridTrackRepetition> for up::style::RepetitionCount {
+    fn from(count: GridTrackRepetition) -> Self {
+        match count {
+            GridTrackRepetition::AutoFill => up::style::RepetitionCount::AutoFill,
+            GridTrackRepetition::AutoFit => up::style::RepetitionCount::AutoFit,
+            GridTrackRepetition::Count(count) => up::style::RepetitionCount::Count(count),
+        }
+    }
+}
+
+impl From<up::style::Display> for Display {
+    fn from(display: up::style::Display) -> Self {
+        match display {
+            up::style::Display::Block => Display::Block,
+            up::style::Display::Flex => Display::Flex,
+            up::style::Display::Grid => Display::Grid,
+            up::style::Display::None => Display::None,
+        }
+    }
+}
+
+impl TryFrom<Display> for up::style::Display {
+    type Error = ConversionError;
+
+    fn try_from(display: Display) -> Result<Self, Self::Error> {
+        match display {
+            Display::Block => Ok(up::style::Display::Block),
+            Display::Flex => Ok(up::style::Display::Flex),
+            Display::Grid => Ok(up::style::Display::Grid),
+            Display::None => Ok(up::style::Display::None),
+            Display::Custom(id) => Err(ConversionError::CustomDisplay(id)),
+        }
+    }
+}
+
+/// [`Overflow::Clip`](up::style::Overflow::Clip) is converted to [`Overflow::Visible`], as they only differ in painting
+impl From<up::style::Overflow> for Overflow {
+    fn from(overflow: up::style::Overflow) -> Self {
+        match overflow {
+            up::style::Overflow::Visible | up::style::Overflow::Clip => Overflow::Visible,
+            up::style::Overflow::Hidden => Overflow::Hidden,
+            up::style::Overflow::Scroll => Overflow::Scroll,
+        }
+    }
+}
+
+impl From<Overflow> for up::style::Overflow {
+    fn from(overflow: Overflow) -> Self {
+        match overflow {
+            Overflow::Visible => up::style::Overflow::Visible,
+            Overflow::Hidden => up::style::Overflow::Hidden,
+            Overflow::Scroll => up::style::Overflow::Scroll,
+        }
+    }
+}
+
+// Grid
+
+impl TryFrom<up::style::MinTrackSizingFunction> for MinTrackSizingFunction {
+    type Error = ConversionError;
+
+    fn try_from(function: up::style::MinTrackSizingFunction) -> Result<Self, Self::Error> {
+        let raw = function.into_raw();
+        match raw.tag() {
+            CompactLength::LENGTH_TAG => Ok(MinTrackSizingFunction::Fixed(LengthPercentage::Length(raw.value()))),
+            CompactLength::PERCENT_TAG => Ok(MinTrackSizingFunction::Fixed(LengthPercentage::Percent(raw.value()))),
+            CompactLength::AUTO_TAG => Ok(MinTrackSizingFunction::Auto),
+            CompactLength::MIN_CONTENT_TAG => Ok(MinTrackSizingFunction::MinContent),
+            CompactLength::MAX_CONTENT_TAG => Ok(MinTrackSizingFunction::MaxContent),
+            _ => Err(ConversionError::Calc),
+        }
+    }
+}
+
+impl TryFrom<MinTrackSizingFunction> for up::style::MinTrackSizingFunction {
+    type Error = ConversionError;
+
+    fn try_from(function: MinTrackSizingFunction) -> Result<Self, Self::Error> {
+        match function {
+            MinTrackSizingFunction::Fixed(length) => Ok(up::style::LengthPercentage::try_from(length)?.into()),
+            MinTrackSizingFunction::MinContent => Ok(Self::min_content()),
+            MinTrackSizingFunction::MaxContent => Ok(Self::max_content()),
+            MinTrackSizingFunction::Auto => Ok(Self::auto()),
+        }
+    }
+}
+
+impl TryFrom<up::style::MaxTrackSizingFunction> for MaxTrackSizingFunction {
+    type Error = ConversionError;
+
+    fn try_from(function: up::style::MaxTrackSizingFunction) -> Result<Self, Self::Error> {
+        let raw = function.into_raw();
+        match raw.tag() {
+            CompactLength::LENGTH_TAG => Ok(MaxTrackSizingFunction::Fixed(LengthPercentage::Length(raw.value()))),
+            CompactLength::PERCENT_TAG => Ok(MaxTrackSizingFunction::Fixed(LengthPercentage::Percent(raw.value()))),
+            CompactLength::AUTO_TAG => Ok(MaxTrackSizingFunction::Auto),
+            CompactLength::MIN_CONTENT_TAG => Ok(MaxTrackSizingFunction::MinContent),
+            CompactLength::MAX_CONTENT_TAG => Ok(MaxTrackSizingFunction::MaxContent),
+            CompactLength::FIT_CONTENT_PX_TAG => {
+                Ok(MaxTrackSizingFunction::FitContent(LengthPercentage::Length(raw.value())))
+            }
+            CompactLength::FIT_CONTENT_PERCENT_TAG => {
+                Ok(MaxTrackSizingFunction::FitContent(LengthPercentage::Percent(raw.value())))
+            }
+            CompactLength::FR_TAG => Ok(MaxTrackSizingFunction::Fraction(raw.value())),
+            _ => Err(ConversionError::Calc),
+        }
+    }
+}
+
+impl TryFrom<MaxTrackSizingFunction> for up::style::MaxTrackSizingFunction {
+    type Error = ConversionError;
+
+    fn try_from(function: MaxTrackSizingFunction) -> Result<Self, Self::Error> {
+        match function {
+            MaxTrackSizingFunction::Fixed(length) => Ok(up::style::LengthPercentage::try_from(length)?.into()),
+            MaxTrackSizingFunction::MinContent => Ok(Self::min_content()),
+            MaxTrackSizingFunction::MaxContent => Ok(Self::max_content()),
+            MaxTrackSizingFunction::FitContent(limit) => match up::style::LengthPercentage::try_from(limit)?.into_raw()
+            {
+                limit if limit.tag() == CompactLength::PERCENT_TAG => Ok(Self::fit_content_percent(limit.value())),
+                limit => Ok(Self::fit_content_px(limit.value())),
+            },
+            MaxTrackSizingFunction::Auto => Ok(Self::auto()),
+            MaxTrackSizingFunction::Fraction(fraction) => Ok(Self::fr(fraction)),
+        }
+    }
+}
+
+/// Converts an upstream track sizing function, which has the same shape as a [`NonRepeatedTrackSizingFunction`]
+fn track_from_upstream(
+    track: up::style::TrackSizingFunction,
+) -> Result<NonRepeatedTrackSizingFunction, ConversionError> {
+    Ok(MinMax { min: track.min.try_into()?, max: track.max.try_into()? })
+}
+
+/// Converts a [`NonRepeatedTrackSizingFunction`] into the upstream track sizing function of the same shape
+fn track_to_upstream(track: NonRepeatedTrackSizingFunction) -> Result<up::style::TrackSizingFunction, ConversionError> {
+    Ok(up::geometry::MinMax { min: track.min.try_into()?, max: track.max.try_into()? })
+}
+
+impl<S: up::style::CheapCloneStr> TryFrom<up::style::GridTemplateComponent<S>> for TrackSizingFunction {
+    type Error = ConversionError;
+
+    fn try_from(component: up::style::GridTemplateComponent<S>) -> Result<Self, Self::Error> {
+        match component {
+            up::style::GridTemplateComponent::Single(track) => {
+                Ok(TrackSizingFunction::Single(track_from_upstream(track)?))
+            }
+            up::style::GridTemplateComponent::Repeat(repetition) => {
+                if repetition.line_names.iter().any(|names| !names.is_empty()) {
+                    return Err(ConversionError::GridName);
+                }
+                let tracks = repetition.tracks.into_iter().map(track_from_upstream).collect::<Result<_, _>>()?;
+                Ok(TrackSizingFunction::Repeat(repetition.count.into(), tracks))
+            }
+        }
+    }
+}
+
+impl<S: up::style::CheapCloneStr> TryFrom<TrackSizingFunction> for up::style::GridTemplateComponent<S> {
+    type Error = ConversionError;
+
+    fn try_from(function: TrackSizingFunction) -> Result<Self, Self::Error> {
+        match function {
+            TrackSizingFunction::Single(track) => Ok(Self::Single(track_to_upstream(track)?)),
+            TrackSizingFunction::Repeat(count, tracks) => Ok(Self::Repeat(up::style::GridTemplateRepetition {
+                count: count.into(),
+                tracks: tracks.into_iter().map(track_to_upstream).collect::<Result<_, _>>()?,
+                line_names: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<S: up::style::CheapCloneStr> TryFrom<up::style::GridPlacement<S>> for GridPlacement {
+    type Error = ConversionError;
+
+    fn try_from(placement: up::style::GridPlacement<S>) -> Result<Self, Self::Error> {
+        match placement {
+            up::style::GridPlacement::Auto => Ok(GridPlacement::Auto),
+            up::style::GridPlacement::Line(line) => Ok(GridPlacement::Line(GridLine::from(line.as_i16()))),
+            up::style::GridPlacement::Span(span) => Ok(GridPlacement::Span(span)),
+            up::style::GridPlacement::NamedLine(..) | up::style::GridPlacement::NamedSpan(..) => {
+                Err(ConversionError::GridName)
+            }
+        }
+    }
+}
+
+impl<S: up::style::CheapCloneStr> From<GridPlacement> for up::style::GridPlacement<S> {
+    fn from(placement: GridPlacement) -> Self {
+        match placement {
+            GridPlacement::Auto => Self::Auto,
+            GridPlacement::Line(line) => Self::from_line_index(line.as_i16()),
+            GridPlacement::Span(span) => Self::Span(span),
+        }
+    }
+}
+
+/// Converts both placements of a grid item in one axis
+fn placement_from_upstream<S: up::style::CheapCloneStr>(
+    line: up::geometry::Line<up::style::GridPlacement<S>>,
+) -> Result<Line<GridPlacement>, ConversionError> {
+    Ok(Line { start: line.start.try_into()?, end: line.end.try_into()? })
+}
+
+// Style
+
+impl<S: up::style::CheapCloneStr> TryFrom<up::style::Style<S>> for Style {
+    type Error = ConversionError;
+
+    fn try_from(style: up::style::Style<S>) -> Result<Self, Self::Error> {
+        require_default(!style.item_is_table, "item_is_table")?;
+        require_default(!style.item_is_replaced, "item_is_replaced")?;
+        require_default(style.box_sizing == up::style::BoxSizing::BorderBox, "box_sizing")?;
+        require_default(style.text_align == up::style::TextAlign::Auto, "text_align")?;
+        let has_grid_names = !style.grid_template_areas.is_empty()
+            || style.grid_template_row_names.iter().any(|names| !names.is_empty())
+            || style.grid_template_column_names.iter().any(|names| !names.is_empty());
+        if has_grid_names {
+            return Err(ConversionError::GridName);
+        }
+
+        Ok(Style {
+            display: style.display.into(),
+            overflow: Point::from(style.overflow).map(Overflow::from),
+            scrollbar_width: Size { width: style.scrollbar_width, height: style.scrollbar_width },
+            position: style.position.into(),
+            inset: try_rect(style.inset.into())?,
+            size: try_size(style.size.into())?,
+            min_size: try_size(style.min_size.into())?,
+            max_size: try_size(style.max_size.into())?,
+            aspect_ratio: style.aspect_ratio,
+            margin: try_rect(style.margin.into())?,
+            padding: try_rect(style.padding.into())?,
+            border: try_rect(style.border.into())?,
+            align_items: style.align_items.map(AlignItems::from),
+            align_self: style.align_self.map(AlignItems::from),
+            justify_items: style.justify_items.map(AlignItems::from),
+            justify_self: style.justify_self.map(AlignItems::from),
+            align_content: style.align_content.map(AlignContent::from),
+            justify_content: style.justify_content.map(AlignContent::from),
+            gap: try_size(style.gap.into())?,
+            flex_direction: style.flex_direction.into(),
+            flex_wrap: style.flex_wrap.into(),
+            flex_basis: style.flex_basis.try_into()?,
+            flex_grow: style.flex_grow,
+            flex_shrink: style.flex_shrink,
+            grid_template_rows: style
+                .grid_template_rows
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            grid_template_columns: style
+                .grid_template_columns
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            grid_auto_rows: style.grid_auto_rows.into_iter().map(track_from_upstream).collect::<Result<_, _>>()?,
+            grid_auto_columns: style
+                .grid_auto_columns
+                .into_iter()
+                .map(track_from_upstream)
+                .collect::<Result<_, _>>()?,
+            grid_auto_flow: style.grid_auto_flow.into(),
+            grid_row: placement_from_upstream(style.grid_row)?,
+            grid_column: placement_from_upstream(style.grid_column)?,
+            ..Style::DEFAULT
+        })
+    }
+}
+
+impl<S: up::style::CheapCloneStr> TryFrom<Style> for up::style::Style<S> {
+    type Error = ConversionError;
+
+    fn try_from(style: Style) -> Result<Self, Self::Error> {
+        require_default(style.scrollbar_width.width == style.scrollbar_width.height, "scrollbar_width")?;
+        require_default(style.anchor.is_none(), "anchor")?;
+        require_default(style.contain == Contain::None, "contain")?;
+        require_default(
+            style.vertical_percentage_basis == VerticalPercentageBasis::Width,
+            "vertical_percentage_basis",
+        )?;
+        require_default(!style.disable_margin_collapsing, "disable_margin_collapsing")?;
+        require_default(!style.disable_automatic_min_size, "disable_automatic_min_size")?;
+        require_default(!style.flex_justify_self, "flex_justify_self")?;
+
+        Ok(up::style::Style {
+            display: style.display.try_into()?,
+            overflow: style.overflow.map(up::style::Overflow::from).into(),
+            scrollbar_width: style.scrollbar_width.width,
+            position: style.position.into(),
+            inset: try_rect(style.inset)?.into(),
+            size: try_size(style.size)?.into(),
+            min_size: try_size(style.min_size)?.into(),
+            max_size: try_size(style.max_size)?.into(),
+            aspect_ratio: style.aspect_ratio,
+            margin: try_rect(style.margin)?.into(),
+            padding: try_rect(style.padding)?.into(),
+            border: try_rect(style.border)?.into(),
+            align_items: style.align_items.map(Into::into),
+            align_self: style.align_self.map(Into::into),
+            justify_items: style.justify_items.map(Into::into),
+            justify_self: style.justify_self.map(Into::into),
+            align_content: style.align_content.map(Into::into),
+            justify_content: style.justify_content.map(Into::into),
+            gap: try_size(style.gap)?.into(),
+            flex_direction: style.flex_direction.into(),
+            flex_wrap: style.flex_wrap.into(),
+            flex_basis: style.flex_basis.try_into()?,
+            flex_grow: style.flex_grow,
+            flex_shrink: style.flex_shrink,
+            grid_template_rows: tracks_to_upstream(style.grid_template_rows)?,
+            grid_template_columns: tracks_to_upstream(style.grid_template_columns)?,
+            grid_auto_rows: style
+                .grid_auto_rows
+                .iter()
+                .map(|track| track_to_upstream(*track))
+                .collect::<Result<_, _>>()?,
+            grid_auto_columns: style
+                .grid_auto_columns
+                .iter()
+                .map(|track| track_to_upstream(*track))
+                .collect::<Result<_, _>>()?,
+            grid_auto_flow: style.grid_auto_flow.into(),
+            grid_row: style.grid_row.map(Into::into).into(),
+            grid_column: style.grid_column.map(Into::into).into(),
+            ..up::style::Style::DEFAULT
+        })
+    }
+}
+
+/// Converts a grid template into the upstream grid template components
+fn tracks_to_upstream<S: up::style::CheapCloneStr>(
+    tracks: GridTrackList<TrackSizingFunction>,
+) -> Result<Vec<up::style::GridTemplateComponent<S>>, ConversionError> {
+    Vec::from(tracks).into_iter().map(TryInto::try_into).collect()
+}
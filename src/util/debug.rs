@@ -2,8 +2,8 @@
 
 use crate::tree::NodeId;
 use crate::{style, LayoutTree};
-use core::fmt::{Debug, Display, Write};
-use std::sync::Mutex;
+#[cfg(feature = "debug")]
+use core::fmt::Debug;
 
 /// Prints a debug representation of the computed layout for a tree of nodes, starting with the passed root node.
 pub fn print_tree(tree: &impl LayoutTree, root: NodeId) {
@@ -25,6 +25,7 @@ fn print_node(tree: &impl LayoutTree, node: NodeId, has_sibling: bool, lines_str
         (_, style::Display::Flex) => "FLEX",
         #[cfg(feature = "grid")]
         (_, style::Display::Grid) => "GRID",
+        (_, style::Display::Custom(_)) => "CUSTOM",
     };
 
     let fork_string = if has_sibling { "├── " } else { "└── " };
@@ -49,64 +50,110 @@ fn print_node(tree: &impl LayoutTree, node: NodeId, has_sibling: bool, lines_str
     }
 }
 
-#[doc(hidden)]
-pub struct DebugLogger {
-    stack: Mutex<Vec<String>>,
+/// A structured event emitted by the layout algorithms while the `debug` feature is enabled
+///
+/// Events are delivered to the [`DebugSink`] of the tree being laid out, see
+/// [`Taffy::set_debug_sink`](crate::Taffy::set_debug_sink).
+#[cfg(feature = "debug")]
+#[derive(Clone, Copy)]
+pub struct DebugEvent<'a> {
+    /// The node being laid out
+    pub node: NodeId,
+    /// The name of the layout algorithm that emitted the event, or `None` for events that aren't specific to an
+    /// algorithm (such as cache lookups)
+    pub algorithm: Option<&'static str>,
+    /// The step of the computation that the event describes
+    pub phase: &'static str,
+    /// The values of interest at this step, each with a label
+    pub values: &'a [(&'static str, &'a dyn Debug)],
 }
 
-static EMPTY_STRING: String = String::new();
-impl DebugLogger {
-    pub const fn new() -> Self {
-        Self { stack: Mutex::new(Vec::new()) }
+#[cfg(feature = "debug")]
+impl Debug for DebugEvent<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(algorithm) = self.algorithm {
+            write!(f, "{algorithm} ")?;
+        }
+        write!(f, "{}", self.phase)?;
+        for (label, value) in self.values {
+            write!(f, " {label}={value:?}")?;
+        }
+        Ok(())
     }
+}
 
-    pub fn push_node(&self, new_key: NodeId) {
-        let mut stack = self.stack.lock().unwrap();
-        let mut key_string = String::new();
-        write!(&mut key_string, "{:?}", new_key).unwrap();
-        stack.push(key_string);
-    }
+/// Receives the structured [`DebugEvent`]s emitted while laying out a tree, when the `debug` feature is enabled
+///
+/// Set with [`Taffy::set_debug_sink`](crate::Taffy::set_debug_sink). This can be used to route layout debugging
+/// into an application's own logging, or to collect events for an in-app inspector.
+#[cfg(feature = "debug")]
+pub trait DebugSink: Send + Sync {
+    /// Called when the computation of the layout (or size) of `node` starts
+    ///
+    /// Events are emitted for the node until the matching call to [`DebugSink::exit_node`]. Calls are nested when the
+    /// layout of a node requires its descendants to be laid out.
+    fn enter_node(&mut self, _node: NodeId) {}
+
+    /// Called when the computation of the layout (or size) of `node` ends
+    fn exit_node(&mut self, _node: NodeId) {}
+
+    /// Receives an event emitted by the layout algorithms
+    fn event(&mut self, event: &DebugEvent<'_>);
+}
 
-    pub fn pop_node(&self) {
-        let mut stack = self.stack.lock().unwrap();
-        stack.pop();
-    }
+/// A [`DebugSink`] that prints each event to stdout, indented by the depth of the node being laid out
+///
+/// This is the debug sink used by a [`Taffy`](crate::Taffy) tree unless another is set.
+#[cfg(feature = "debug")]
+#[derive(Debug, Default)]
+pub struct PrintDebugSink {
+    /// The number of nodes whose layout is being computed
+    depth: usize,
+}
 
-    pub fn log(&self, message: impl Display) {
-        let stack = self.stack.lock().unwrap();
-        let key = stack.last().unwrap_or(&EMPTY_STRING);
-        let level = stack.len() * 4;
-        let space = " ";
-        println!("{space:level$}{key}: {message}");
-    }
+#[cfg(feature = "debug")]
+impl PrintDebugSink {
+    /// The depth beyond which events are no longer indented further
+    const MAX_INDENTED_DEPTH: usize = 64;
+}
 
-    pub fn labelled_log(&self, label: &str, message: impl Display) {
-        let stack = self.stack.lock().unwrap();
-        let key = stack.last().unwrap_or(&EMPTY_STRING);
-        let level = stack.len() * 4;
-        let space = " ";
-        println!("{space:level$}{key}: {label} {message}");
+#[cfg(feature = "debug")]
+impl DebugSink for PrintDebugSink {
+    fn enter_node(&mut self, _node: NodeId) {
+        self.depth += 1;
+        println!();
     }
 
-    pub fn debug_log(&self, message: impl Debug) {
-        let stack = self.stack.lock().unwrap();
-        let key = stack.last().unwrap_or(&EMPTY_STRING);
-        let level = stack.len() * 4;
-        let space = " ";
-        println!("{space:level$}{key}: {message:?}");
+    fn exit_node(&mut self, _node: NodeId) {
+        self.depth = self.depth.saturating_sub(1);
     }
 
-    pub fn labelled_debug_log(&self, label: &str, message: impl Debug) {
-        let stack = self.stack.lock().unwrap();
-        let key = stack.last().unwrap_or(&EMPTY_STRING);
-        let level = stack.len() * 4;
+    fn event(&mut self, event: &DebugEvent<'_>) {
+        // The indent stops growing past a certain depth, so that the output of very deep trees stays readable and
+        // the width stays within the limits of the formatting machinery
+        let level = self.depth.min(Self::MAX_INDENTED_DEPTH) * 4;
         let space = " ";
-        println!("{space:level$}{key}: {label} {message:?}");
+        println!("{space:level$}{:?}: {event:?}", event.node);
     }
 }
 
-#[cfg(any(feature = "debug", feature = "profile"))]
-pub(crate) static NODE_LOGGER: DebugLogger = DebugLogger::new();
+/// Emits a [`DebugEvent`] for a node to the [`LayoutTree::debug_event`] method of a tree
+///
+/// Used as `debug_event!(tree, node, algorithm, "phase", "label" => value, ...)`.
+#[cfg(feature = "debug")]
+macro_rules! debug_event {
+    ($tree:expr, $node:expr, $algorithm:expr, $phase:expr $(, $label:literal => $value:expr)* $(,)?) => {
+        $tree.debug_event(&$crate::util::DebugEvent {
+            node: $node,
+            algorithm: $algorithm,
+            phase: $phase,
+            values: &[$(($label, &$value as &dyn ::core::fmt::Debug)),*],
+        })
+    };
+}
+
+#[cfg(feature = "debug")]
+pub(crate) use debug_event;
 
 #[cfg(feature = "profile")]
 #[allow(unused_macros)]
@@ -115,7 +162,7 @@ macro_rules! time {
         let start = ::std::time::Instant::now();
         $($code)*
         let duration = ::std::time::Instant::now().duration_since(start);
-        crate::util::debug::NODE_LOGGER.log(format_args!("Performed {} in {}ms", $label, duration.as_millis()));
+        println!("Performed {} in {}ms", $label, duration.as_millis());
     };
 }
 
@@ -1,9 +1,28 @@
 //! Helpful misc. utilities such as a function to debug print a tree
+//!
+//! The [`MaybeMath`], [`MaybeResolve`] and [`ResolveOrZero`] traits are the option-aware arithmetic and style
+//! resolution helpers used by Taffy's own layout algorithms, and can be used to implement custom algorithms on top of
+//! [`LayoutTree`](crate::tree::LayoutTree).
+//!
+//! ```
+//! use taffy::prelude::*;
+//! use taffy::util::{MaybeMath, MaybeResolve, ResolveOrZero};
+//!
+//! let padding = Rect { left: length(4.0), right: length(4.0), top: zero(), bottom: zero() };
+//! let style = Style { size: Size { width: percent(0.5), height: auto() }, padding, ..Default::default() };
+//! let parent_size = Size { width: Some(200.0), height: None };
+//!
+//! let size: Size<Option<f32>> = style.size.maybe_resolve(parent_size);
+//! assert_eq!(size, Size { width: Some(100.0), height: None });
+//!
+//! let padding: Rect<f32> = style.padding.resolve_or_zero(parent_size.width);
+//! assert_eq!(size.width.maybe_sub(padding.left + padding.right), Some(92.0));
+//! ```
 
 mod math;
-pub(crate) use math::MaybeMath;
+pub use math::MaybeMath;
 mod resolve;
-pub(crate) use resolve::{MaybeResolve, ResolveOrZero};
+pub use resolve::{MaybeResolve, ResolveOrZero};
 pub(crate) mod sys;
 
 #[cfg(feature = "std")]
@@ -11,3 +30,5 @@ pub(crate) mod sys;
 pub(crate) mod debug;
 #[cfg(feature = "std")]
 pub use debug::print_tree;
+#[cfg(feature = "debug")]
+pub use debug::{DebugEvent, DebugSink, PrintDebugSink};
@@ -8,8 +8,9 @@ use crate::style_helpers::TaffyZero;
 /// potentially context-dependent size or dimension into
 /// a context-independent size or dimension.
 ///
-/// Will return a `None` if it unable to resolve.
-pub(crate) trait MaybeResolve<In, Out> {
+/// Will return a `None` if it unable to resolve. Units that depend on the context a tree is laid out in (such as
+/// [`Dimension::Vw`]) are resolved by the [`Taffy`](crate::Taffy) tree before layout, and can't be resolved by this trait.
+pub trait MaybeResolve<In, Out> {
     /// Resolve a dimension that might be dependent on a context, with `None` as fallback value
     fn maybe_resolve(self, context: In) -> Out;
 }
@@ -19,7 +20,7 @@ pub(crate) trait MaybeResolve<In, Out> {
 /// a context-independent size or dimension.
 ///
 /// Will return a default value if it unable to resolve.
-pub(crate) trait ResolveOrZero<TContext, TOutput: TaffyZero> {
+pub trait ResolveOrZero<TContext, TOutput: TaffyZero> {
     /// Resolve a dimension that might be dependent on a context, with a default fallback value
     fn resolve_or_zero(self, context: TContext) -> TOutput;
 }
@@ -31,6 +32,14 @@ impl MaybeResolve<Option<f32>, Option<f32>> for LengthPercentage {
         match self {
             LengthPercentage::Length(length) => Some(length),
             LengthPercentage::Percent(percent) => context.map(|dim| dim * percent),
+            // Viewport-relative, font-relative and custom values are resolved into lengths before layout, so they can't be resolved here
+            LengthPercentage::Vw(_)
+            | LengthPercentage::Vh(_)
+            | LengthPercentage::Vmin(_)
+            | LengthPercentage::Vmax(_)
+            | LengthPercentage::Rem(_)
+            | LengthPercentage::Em(_)
+            | LengthPercentage::Custom(_) => None,
         }
     }
 }
@@ -42,7 +51,14 @@ impl MaybeResolve<Option<f32>, Option<f32>> for LengthPercentageAuto {
         match self {
             LengthPercentageAuto::Length(length) => Some(length),
             LengthPercentageAuto::Percent(percent) => context.map(|dim| dim * percent),
-            LengthPercentageAuto::Auto => None,
+            LengthPercentageAuto::Vw(_)
+            | LengthPercentageAuto::Vh(_)
+            | LengthPercentageAuto::Vmin(_)
+            | LengthPercentageAuto::Vmax(_)
+            | LengthPercentageAuto::Rem(_)
+            | LengthPercentageAuto::Em(_)
+            | LengthPercentageAuto::Custom(_)
+            | LengthPercentageAuto::Auto => None,
         }
     }
 }
@@ -55,7 +71,14 @@ impl MaybeResolve<Option<f32>, Option<f32>> for Dimension {
         match self {
             Dimension::Length(length) => Some(length),
             Dimension::Percent(percent) => context.map(|dim| dim * percent),
-            Dimension::Auto => None,
+            Dimension::Vw(_)
+            | Dimension::Vh(_)
+            | Dimension::Vmin(_)
+            | Dimension::Vmax(_)
+            | Dimension::Rem(_)
+            | Dimension::Em(_)
+            | Dimension::Custom(_)
+            | Dimension::Auto => None,
         }
     }
 }
@@ -78,6 +101,20 @@ impl<In, Out, T: MaybeResolve<In, Out>> MaybeResolve<Size<In>, Size<Out>> for Si
     }
 }
 
+// Generic MaybeResolve for resolving Rect against Size
+impl<In: Copy, Out, T: MaybeResolve<In, Out>> MaybeResolve<Size<In>, Rect<Out>> for Rect<T> {
+    /// Converts any `parent`-relative values for Rect into absolute values, resolving horizontal values against the
+    /// width and vertical values against the height
+    fn maybe_resolve(self, context: Size<In>) -> Rect<Out> {
+        Rect {
+            left: self.left.maybe_resolve(context.width),
+            right: self.right.maybe_resolve(context.width),
+            top: self.top.maybe_resolve(context.height),
+            bottom: self.bottom.maybe_resolve(context.height),
+        }
+    }
+}
+
 impl ResolveOrZero<Option<f32>, f32> for LengthPercentage {
     /// Will return a default value of result is evaluated to `None`
     fn resolve_or_zero(self, context: Option<f32>) -> f32 {
@@ -0,0 +1,75 @@
+use taffy::geometry::{Point, Rect};
+use taffy::prelude::*;
+use taffy::style::Overflow;
+use taffy::tree::AccessibleVisibility;
+
+/// A 100x60 column that scrolls vertically, containing three 100x40 items and a hidden item, inside of a root
+fn scrolling_list() -> (Taffy, NodeId, NodeId, [NodeId; 4]) {
+    let mut taffy = Taffy::new();
+    let item_style = Style { size: Size::from_lengths(100.0, 40.0), flex_shrink: 0.0, ..Default::default() };
+    let items = [
+        taffy.new_leaf(item_style.clone()).unwrap(),
+        taffy.new_leaf(item_style.clone()).unwrap(),
+        taffy.new_leaf(item_style.clone()).unwrap(),
+        taffy.new_leaf(Style { display: Display::None, ..item_style }).unwrap(),
+    ];
+    let list = taffy
+        .new_with_children(
+            Style {
+                flex_direction: FlexDirection::Column,
+                size: Size::from_lengths(100.0, 60.0),
+                overflow: Point { x: Overflow::Visible, y: Overflow::Scroll },
+                ..Default::default()
+            },
+            &items,
+        )
+        .unwrap();
+    let root = taffy.new_with_children(Style { padding: length(10.0), ..Default::default() }, &[list]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, list, items)
+}
+
+#[test]
+fn nodes_are_exported_in_logical_order_with_absolute_bounds() {
+    let (taffy, root, list, items) = scrolling_list();
+    let nodes = taffy.accessibility_tree(root, |node, _| Some(node)).unwrap();
+
+    let order: Vec<NodeId> = nodes.iter().map(|entry| entry.node).collect();
+    assert_eq!(order, vec![root, list, items[0], items[1], items[2], items[3]]);
+    assert_eq!(nodes[0].parent, None);
+    assert_eq!(nodes[0].children, vec![1]);
+    assert_eq!(nodes[1].children, vec![2, 3, 4, 5]);
+    assert_eq!(nodes[3].parent, Some(1));
+    assert_eq!(nodes[3].bounds, Rect::new(10.0, 110.0, 50.0, 90.0));
+    assert_eq!(nodes[3].metadata, items[1]);
+}
+
+#[test]
+fn visibility_takes_clipping_and_scrolling_into_account() {
+    let (mut taffy, root, list, _) = scrolling_list();
+    let visibility = |taffy: &Taffy| -> Vec<AccessibleVisibility> {
+        taffy.accessibility_tree(root, |_, _| Some(())).unwrap().iter().map(|entry| entry.visibility).collect()
+    };
+
+    use AccessibleVisibility::*;
+    assert_eq!(visibility(&taffy), vec![Visible, Visible, Visible, PartiallyClipped, Clipped, Hidden]);
+
+    taffy.set_scroll_offset(list, Point { x: 0.0, y: 40.0 }).unwrap();
+    assert_eq!(visibility(&taffy), vec![Visible, Visible, Clipped, Visible, PartiallyClipped, Hidden]);
+    let nodes = taffy.accessibility_tree(root, |_, _| Some(())).unwrap();
+    assert_eq!(nodes[3].bounds, Rect::new(10.0, 110.0, 10.0, 50.0));
+}
+
+#[test]
+fn nodes_without_metadata_are_left_out() {
+    let (taffy, root, list, items) = scrolling_list();
+    let nodes = taffy
+        .accessibility_tree(root, |node, style| (style.display != Display::None && node != list).then_some(node))
+        .unwrap();
+
+    let order: Vec<NodeId> = nodes.iter().map(|entry| entry.metadata).collect();
+    assert_eq!(order, vec![root, items[0], items[1], items[2]]);
+    // The items are adopted by the root in place of the list
+    assert_eq!(nodes[0].children, vec![1, 2, 3]);
+    assert_eq!(nodes[2].parent, Some(0));
+}
@@ -0,0 +1,139 @@
+use taffy::prelude::*;
+
+/// Lays out fixed size items in a 100x100 flex row with the given style, returning their horizontal locations
+fn flex_item_offsets(container: Style, item_sizes: &[f32]) -> Vec<f32> {
+    let mut taffy = Taffy::new();
+    let items: Vec<NodeId> = item_sizes
+        .iter()
+        .map(|size| {
+            let style = Style { size: Size::from_lengths(*size, *size), flex_shrink: 0.0, ..Default::default() };
+            taffy.new_leaf(style).unwrap()
+        })
+        .collect();
+    let root = taffy.new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..container }, &items).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    items.iter().map(|item| taffy.layout(*item).unwrap().location.x).collect()
+}
+
+#[test]
+fn distributed_justify_content_falls_back_when_items_overflow() {
+    for justify_content in [JustifyContent::SpaceBetween, JustifyContent::SpaceAround, JustifyContent::SpaceEvenly] {
+        let container = Style { justify_content: Some(justify_content), ..Default::default() };
+        assert_eq!(flex_item_offsets(container, &[80.0, 80.0]), vec![0.0, 80.0], "{justify_content:?}");
+    }
+}
+
+#[test]
+fn distributed_justify_content_falls_back_with_a_single_item() {
+    let space_between = Style { justify_content: Some(JustifyContent::SpaceBetween), ..Default::default() };
+    assert_eq!(flex_item_offsets(space_between, &[20.0]), vec![0.0]);
+    let space_around = Style { justify_content: Some(JustifyContent::SpaceAround), ..Default::default() };
+    assert_eq!(flex_item_offsets(space_around, &[20.0]), vec![40.0]);
+
+    // The fallback of space-between and stretch is flex-start, which is the end of a reversed container
+    for justify_content in [JustifyContent::SpaceBetween, JustifyContent::Stretch] {
+        let reversed = Style {
+            flex_direction: FlexDirection::RowReverse,
+            justify_content: Some(justify_content),
+            ..Default::default()
+        };
+        assert_eq!(flex_item_offsets(reversed, &[20.0]), vec![80.0], "{justify_content:?}");
+    }
+}
+
+#[test]
+fn distributed_align_content_falls_back_when_lines_overflow() {
+    let container = Style {
+        flex_direction: FlexDirection::Column,
+        flex_wrap: FlexWrap::Wrap,
+        align_content: Some(AlignContent::SpaceBetween),
+        align_items: Some(AlignItems::Start),
+        ..Default::default()
+    };
+    let mut taffy = Taffy::new();
+    let items: Vec<NodeId> = (0..2)
+        .map(|_| taffy.new_leaf(Style { size: Size::from_lengths(80.0, 80.0), ..Default::default() }).unwrap())
+        .collect();
+    let root = taffy.new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..container }, &items).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let offsets: Vec<f32> = items.iter().map(|item| taffy.layout(*item).unwrap().location.x).collect();
+    assert_eq!(offsets, vec![0.0, 80.0]);
+}
+
+#[test]
+#[cfg(feature = "grid")]
+fn grid_tracks_use_the_same_fallback_alignment() {
+    let mut taffy = Taffy::new();
+    let item = taffy.new_leaf(Style::default()).unwrap();
+    let grid = |justify_content, columns| Style {
+        display: Display::Grid,
+        size: Size::from_lengths(100.0, 100.0),
+        justify_content: Some(justify_content),
+        grid_template_columns: columns,
+        ..Default::default()
+    };
+
+    let root = taffy.new_with_children(grid(JustifyContent::SpaceAround, vec![length(20.0)].into()), &[item]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(item).unwrap().location.x, 40.0);
+
+    // Overflowing tracks are aligned to the start rather than centered
+    let overflowing = grid(JustifyContent::SpaceEvenly, vec![length(80.0), length(80.0)].into());
+    taffy.set_style(root, overflowing).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(item).unwrap().location.x, 0.0);
+}
+
+#[test]
+fn self_alignment_falls_back_to_flex_start() {
+    let mut taffy = Taffy::new();
+    let stretch =
+        Style { align_self: Some(AlignSelf::Stretch), size: Size::from_lengths(20.0, 20.0), ..Default::default() };
+    let baseline =
+        Style { align_self: Some(AlignSelf::Baseline), size: Size::from_lengths(20.0, 20.0), ..Default::default() };
+    let items = [taffy.new_leaf(stretch).unwrap(), taffy.new_leaf(baseline).unwrap()];
+    let container = Style {
+        flex_direction: FlexDirection::Column,
+        flex_wrap: FlexWrap::WrapReverse,
+        size: Size::from_lengths(100.0, 100.0),
+        ..Default::default()
+    };
+    let root = taffy.new_with_children(container, &items).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // A definitely sized item can't be stretched, and items in a column can't be baseline-aligned, so both are
+    // aligned to flex-start, which is the right edge of the line in a wrap-reverse container
+    for item in items {
+        assert_eq!(taffy.layout(item).unwrap().location.x, 80.0);
+    }
+}
+
+#[test]
+#[cfg(feature = "grid")]
+fn grid_items_spanning_several_rows_do_not_take_part_in_baseline_alignment() {
+    let mut taffy = Taffy::new();
+    let tall = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 40.0), ..Default::default() }).unwrap();
+    let short = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                align_items: Some(AlignItems::Baseline),
+                grid_template_columns: vec![length(20.0), length(20.0)].into(),
+                ..Default::default()
+            },
+            &[tall, short],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(short).unwrap().location.y, 30.0);
+
+    // Spanning two rows, the tall item is aligned to the start instead and no longer shifts the short item
+    taffy
+        .set_style(tall, Style { grid_row: span(2), size: Size::from_lengths(20.0, 40.0), ..Default::default() })
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(tall).unwrap().location.y, 0.0);
+    assert_eq!(taffy.layout(short).unwrap().location.y, 0.0);
+}
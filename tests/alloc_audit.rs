@@ -0,0 +1,166 @@
+#![cfg(all(feature = "alloc_audit", feature = "std"))]
+
+use std::alloc::{GlobalAlloc, Layout as AllocationLayout, System};
+
+use taffy::prelude::*;
+use taffy::tree::{record_heap_allocation, AllocationCounts, AllocationSite};
+
+/// A global allocator that reports its allocations to the layouts being audited
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: AllocationLayout) -> *mut u8 {
+        record_heap_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: AllocationLayout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: AllocationLayout, new_size: usize) -> *mut u8 {
+        record_heap_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Lays out a container with the given display and three fixed-size children, returning the allocation counts
+fn container_allocations(display: Display) -> AllocationCounts {
+    let mut taffy = Taffy::new();
+    let children: Vec<NodeId> = (0..3)
+        .map(|_| taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap())
+        .collect();
+    let root = taffy.new_with_children(Style { display, ..Default::default() }, &children).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.layout_stats().allocations
+}
+
+#[test]
+fn flexbox_allocations_are_counted() {
+    let allocations = container_allocations(Display::Flex);
+    assert_eq!(allocations.flex_items, 1);
+    assert_eq!(allocations.flex_lines, 1);
+    assert_eq!(allocations.grid_items + allocations.grid_tracks + allocations.block_items, 0);
+}
+
+#[test]
+fn grid_allocations_are_counted() {
+    let allocations = container_allocations(Display::Grid);
+    assert_eq!(allocations.grid_items, 1);
+    // One allocation each for the rows and the columns
+    assert_eq!(allocations.grid_tracks, 2);
+    assert_eq!(allocations.flex_items + allocations.flex_lines + allocations.block_items, 0);
+}
+
+#[test]
+fn block_allocations_are_counted() {
+    let allocations = container_allocations(Display::Block);
+    assert_eq!(allocations.block_items, 1);
+    assert_eq!(allocations.get(AllocationSite::BlockItems), 1);
+}
+
+#[test]
+fn tree_traversals_are_counted() {
+    let allocations = container_allocations(Display::Flex);
+    // The stacks used to round the layouts and to record which layouts changed
+    assert_eq!(allocations.children, 2);
+    assert_eq!(allocations.total(), 4);
+
+    let mut taffy = Taffy::new();
+    taffy.disable_rounding();
+    let root = taffy.new_leaf(Style::default()).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout_stats().allocations.children, 1);
+}
+
+#[test]
+fn counts_are_reset_by_each_layout() {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(Style::default()).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let first = taffy.layout_stats().allocations;
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    // The root's layout is served from its cache, so only the tree traversals allocate
+    assert_eq!(taffy.layout_stats().allocations, AllocationCounts { children: first.children, ..Default::default() });
+}
+
+#[test]
+fn flex_item_lists_are_reused() {
+    let mut taffy = Taffy::new();
+    let rows: Vec<NodeId> = (0..100)
+        .map(|_| {
+            let leaves: Vec<NodeId> = (0..2)
+                .map(|_| taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap())
+                .collect();
+            taffy.new_with_children(Style::default(), &leaves).unwrap()
+        })
+        .collect();
+    let column = Style { flex_direction: FlexDirection::Column, ..Default::default() };
+    let root = taffy.new_with_children(column.clone(), &rows).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    // One list for the root, and one that is reused by each of the rows in turn
+    assert_eq!(taffy.layout_stats().allocations.flex_items, 2);
+
+    // The lists are kept between layouts, so relaying out the root doesn't allocate one
+    taffy.set_style(root, Style { align_items: Some(AlignItems::Start), ..column }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout_stats().allocations.flex_items, 0);
+    assert_eq!(taffy.layout(rows[99]).unwrap().location.y, 990.0);
+}
+
+#[test]
+fn heap_allocations_are_counted() {
+    let mut taffy = Taffy::new();
+    let rows: Vec<NodeId> = (0..10)
+        .map(|_| {
+            let leaves: Vec<NodeId> = (0..3)
+                .map(|_| taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap())
+                .collect();
+            taffy.new_with_children(Style::default(), &leaves).unwrap()
+        })
+        .collect();
+    let root = taffy.new_with_children(Style { display: Display::Grid, ..Default::default() }, &rows).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let first = taffy.layout_stats();
+    // Every collection counted by site is a heap allocation, along with those that aren't attributed to a site
+    assert!(first.heap_allocations > first.allocations.total());
+
+    // Relaying out a clean tree only allocates for the tree traversals
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let second = taffy.layout_stats();
+    assert_eq!(second.allocations.total(), second.allocations.children);
+    assert!(second.heap_allocations >= second.allocations.total());
+    assert!(second.heap_allocations < first.heap_allocations);
+}
+
+#[test]
+fn allocations_made_by_measure_functions_are_counted() {
+    /// Lays out a row of measured leaves, returning the number of measure calls and heap allocations
+    fn measured_row(allocate: bool) -> (usize, usize) {
+        let mut taffy = Taffy::new();
+        let leaves: Vec<NodeId> = (0..5).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+        for leaf in &leaves {
+            taffy.set_needs_measure(*leaf, true).unwrap();
+        }
+        let root = taffy.new_with_children(Style::default(), &leaves).unwrap();
+        taffy
+            .compute_layout_with_measure(root, Size::MAX_CONTENT, |_, known_dimensions, _| {
+                if allocate {
+                    std::hint::black_box(vec![0u8; 16]);
+                }
+                known_dimensions.unwrap_or(Size { width: 10.0, height: 10.0 })
+            })
+            .unwrap();
+        let stats = taffy.layout_stats();
+        (stats.measure_calls, stats.heap_allocations)
+    }
+
+    let (measure_calls, allocations) = measured_row(false);
+    let (_, allocations_when_measuring_allocates) = measured_row(true);
+    assert_eq!(allocations_when_measuring_allocates, allocations + measure_calls);
+}
@@ -0,0 +1,41 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Anchor;
+
+fn anchored_leaf(taffy: &mut Taffy, anchor: Anchor) -> NodeId {
+    taffy
+        .new_leaf(Style {
+            position: Position::Absolute,
+            anchor: Some(anchor),
+            size: Size::from_lengths(20.0, 10.0),
+            ..Default::default()
+        })
+        .unwrap()
+}
+
+#[test]
+fn anchored_nodes_are_positioned_relative_to_their_parent() {
+    let mut taffy = Taffy::new();
+    let center = anchored_leaf(&mut taffy, Anchor::CENTER);
+    let bottom_right = anchored_leaf(
+        &mut taffy,
+        Anchor::new(Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: 1.0 })
+            .with_offset(Point { x: length(-5.0), y: percent(-0.1) }),
+    );
+    let root = taffy
+        .new_with_children(
+            Style { size: Size::from_lengths(200.0, 100.0), ..Default::default() },
+            &[center, bottom_right],
+        )
+        .unwrap();
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(center).unwrap().location, Point { x: 90.0, y: 45.0 });
+    assert_eq!(taffy.layout(bottom_right).unwrap().location, Point { x: 175.0, y: 80.0 });
+
+    // Anchored nodes follow their parent when it is resized
+    taffy.set_style(root, Style { size: Size::from_lengths(400.0, 300.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(center).unwrap().location, Point { x: 190.0, y: 145.0 });
+    assert_eq!(taffy.layout(bottom_right).unwrap().location, Point { x: 375.0, y: 260.0 });
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "async_layout")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use taffy::prelude::*;
+
+/// A waker that only counts how many times it was woken
+#[derive(Default)]
+struct CountingWaker(std::sync::atomic::AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Polls `future` to completion on the current thread, returning its output and the number of times it yielded
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    let mut yields = 0;
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => {
+                // The future asks to be polled again every time it yields
+                assert_eq!(counter.0.load(std::sync::atomic::Ordering::SeqCst), yields);
+                return (output, yields);
+            }
+            Poll::Pending => yields += 1,
+        }
+    }
+}
+
+/// A column of `row_count` rows, each containing three fixed-size leaves
+fn grid_of_leaves(taffy: &mut Taffy, row_count: usize) -> NodeId {
+    let rows: Vec<NodeId> = (0..row_count)
+        .map(|i| {
+            let leaves: Vec<NodeId> = (0..3)
+                .map(|j| {
+                    let style = Style { size: Size::from_lengths((i + j) as f32, 10.0), ..Default::default() };
+                    taffy.new_leaf(style).unwrap()
+                })
+                .collect();
+            taffy.new_with_children(Style::default(), &leaves).unwrap()
+        })
+        .collect();
+    taffy.new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &rows).unwrap()
+}
+
+#[test]
+fn async_layout_matches_sync_layout() {
+    let mut expected = Taffy::new();
+    let expected_root = grid_of_leaves(&mut expected, 100);
+    expected.compute_layout(expected_root, Size::MAX_CONTENT).unwrap();
+
+    let mut taffy = Taffy::new();
+    let root = grid_of_leaves(&mut taffy, 100);
+    let (result, yields) = block_on(taffy.compute_layout_async(root, Size::MAX_CONTENT, 16));
+    result.unwrap();
+
+    assert!(yields > 1, "the layout should have yielded more than once, yielded {yields} times");
+    let expected_layouts: Vec<Layout> = expected.iter().map(|(_, _, layout)| *layout).collect();
+    let layouts: Vec<Layout> = taffy.iter().map(|(_, _, layout)| *layout).collect();
+    assert_eq!(layouts, expected_layouts);
+}
+
+#[test]
+fn async_layout_of_a_laid_out_tree_completes_immediately() {
+    let mut taffy = Taffy::new();
+    let root = grid_of_leaves(&mut taffy, 10);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let (result, yields) = block_on(taffy.compute_layout_async(root, Size::MAX_CONTENT, 1));
+    result.unwrap();
+    assert_eq!(yields, 0);
+}
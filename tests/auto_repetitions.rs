@@ -0,0 +1,95 @@
+use taffy::geometry::AbsoluteAxis;
+use taffy::prelude::*;
+use taffy::style::GridTrackRepetition;
+
+/// Lays out a 200px wide grid with `item_count` items, repeating 40px columns after a fixed 30px column
+fn repeating_grid(repetition: GridTrackRepetition, item_count: usize) -> (Taffy, NodeId) {
+    let mut taffy = Taffy::new();
+    let items: Vec<NodeId> = (0..item_count).map(|_| taffy.new_leaf(Style::default()).unwrap()).collect();
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                grid_template_columns: vec![length(30.0), repeat(repetition, vec![length(40.0)])].into(),
+                size: Size { width: length(200.0), height: auto() },
+                ..Default::default()
+            },
+            &items,
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root)
+}
+
+#[test]
+fn auto_fit_reports_collapsed_repetitions() {
+    let (taffy, root) = repeating_grid(GridTrackRepetition::AutoFit, 3);
+    let repetitions = taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().unwrap();
+
+    assert_eq!(repetitions.kind, GridTrackRepetition::AutoFit);
+    assert_eq!(repetitions.repetitions, 4);
+    assert_eq!(repetitions.first_track, 1);
+    assert_eq!(repetitions.collapsed, vec![false, false, true, true]);
+    assert_eq!(repetitions.visible_track_count(), 2);
+    assert_eq!(taffy.auto_repetitions(root, AbsoluteAxis::Vertical).unwrap(), None);
+}
+
+#[test]
+fn auto_fill_never_collapses_repetitions() {
+    let (taffy, root) = repeating_grid(GridTrackRepetition::AutoFill, 3);
+    let repetitions = taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().unwrap();
+
+    assert_eq!(repetitions.track_count(), 4);
+    assert_eq!(repetitions.visible_track_count(), 4);
+}
+
+#[test]
+fn repetitions_are_forgotten_once_the_template_no_longer_repeats() {
+    let (mut taffy, root) = repeating_grid(GridTrackRepetition::AutoFit, 1);
+    assert!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().is_some());
+
+    let style = Style { grid_template_columns: vec![length(30.0)].into(), ..taffy.style(root).unwrap().clone() };
+    taffy.set_style(root, style).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap(), None);
+}
+
+#[test]
+fn repetitions_are_forgotten_once_the_node_is_no_longer_a_grid() {
+    let (mut taffy, root) = repeating_grid(GridTrackRepetition::AutoFit, 1);
+
+    let style = Style { display: Display::Flex, ..taffy.style(root).unwrap().clone() };
+    taffy.set_style(root, style).unwrap();
+    // The repetitions are those of the most recent layout, until the node is laid out again
+    assert!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().is_some());
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap(), None);
+}
+
+#[test]
+fn repetitions_are_forgotten_once_the_grid_is_hidden() {
+    let (mut taffy, grid) = repeating_grid(GridTrackRepetition::AutoFill, 1);
+    let root = taffy.new_with_children(Style::default(), &[grid]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert!(taffy.auto_repetitions(grid, AbsoluteAxis::Horizontal).unwrap().is_some());
+
+    taffy.set_style(root, Style { display: Display::None, ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.auto_repetitions(grid, AbsoluteAxis::Horizontal).unwrap(), None);
+}
+
+#[test]
+fn discarded_forks_leave_repetitions_untouched() {
+    let (mut taffy, root) = repeating_grid(GridTrackRepetition::AutoFill, 3);
+
+    let mut fork = taffy.speculative_fork();
+    let style = Style { size: Size { width: length(400.0), height: auto() }, ..fork.style(root).unwrap().clone() };
+    fork.set_style(root, style).unwrap();
+    fork.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    fork.discard();
+    assert_eq!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().unwrap().repetitions, 4);
+
+    // The restored caches are hit by the next layout, which therefore doesn't record the repetitions again
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.auto_repetitions(root, AbsoluteAxis::Horizontal).unwrap().unwrap().repetitions, 4);
+}
@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use taffy::prelude::*;
+use taffy::tree::{Measurable, MeasureFunc};
+
+/// A 200x10 piece of unbreakable content, which counts how often it is measured under a min-content constraint
+#[derive(Default)]
+struct WideContent {
+    min_content_measurements: AtomicUsize,
+}
+
+impl Measurable for WideContent {
+    fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+        if available_space.width == AvailableSpace::MinContent {
+            self.min_content_measurements.fetch_add(1, Ordering::Relaxed);
+        }
+        known_dimensions.unwrap_or(Size { width: 200.0, height: 10.0 })
+    }
+}
+
+/// Lays out a 100px wide container with the given style, containing a single item with wide content, and returns the
+/// width of the item along with the number of times its content was measured under a min-content constraint
+fn item_width(container: Style, item: Style) -> (f32, usize) {
+    let content = Arc::new(WideContent::default());
+    let mut taffy = Taffy::new();
+    let item = taffy.new_leaf_with_measure(item, MeasureFunc::Shared(content.clone())).unwrap();
+    let root = taffy.new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..container }, &[item]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy.layout(item).unwrap().size.width, content.min_content_measurements.load(Ordering::Relaxed))
+}
+
+/// The containers whose items have a content-based automatic minimum size
+fn containers() -> Vec<Style> {
+    let mut containers = vec![Style { display: Display::Flex, ..Default::default() }];
+    #[cfg(feature = "grid")]
+    containers.push(Style {
+        display: Display::Grid,
+        grid_template_columns: vec![fr(1.0)].into(),
+        ..Default::default()
+    });
+    containers
+}
+
+#[test]
+fn items_are_not_shrunk_below_their_content_by_default() {
+    for container in containers() {
+        let display = container.display;
+        let (width, _) = item_width(container, Style::default());
+        assert_eq!(width, 200.0, "{display:?}");
+    }
+}
+
+#[test]
+fn disabling_the_automatic_minimum_size_lets_items_shrink() {
+    for container in containers() {
+        let display = container.display;
+        let (width, min_content_measurements) =
+            item_width(container, Style { disable_automatic_min_size: true, ..Default::default() });
+        assert_eq!(width, 100.0, "{display:?}");
+        // Flex items' content is only measured under a min-content constraint to find their automatic minimum size
+        if display == Display::Flex {
+            assert_eq!(min_content_measurements, 0);
+        }
+    }
+}
+
+#[test]
+fn explicit_min_sizes_still_apply() {
+    for container in containers() {
+        let display = container.display;
+        let item =
+            Style { disable_automatic_min_size: true, min_size: Size::from_lengths(150.0, 0.0), ..Default::default() };
+        let (width, _) = item_width(container, item);
+        assert_eq!(width, 150.0, "{display:?}");
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "block_layout")]
+use taffy::prelude::*;
+
+/// A block container with the given style, containing 10px tall children with the given vertical margins
+fn block_stack(container: Style, child_margins: &[(f32, f32)]) -> (Taffy, NodeId, Vec<NodeId>) {
+    let mut taffy = Taffy::new();
+    let children: Vec<NodeId> = child_margins
+        .iter()
+        .map(|(top, bottom)| {
+            let margin = Rect { left: zero(), right: zero(), top: length(*top), bottom: length(*bottom) };
+            taffy
+                .new_leaf(Style { size: Size { width: auto(), height: length(10.0) }, margin, ..Default::default() })
+                .unwrap()
+        })
+        .collect();
+    let root = taffy.new_with_children(Style { display: Display::Block, ..container }, &children).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, children)
+}
+
+/// The vertical location of each of the nodes
+fn y_locations(taffy: &Taffy, nodes: &[NodeId]) -> Vec<f32> {
+    nodes.iter().map(|node| taffy.layout(*node).unwrap().location.y).collect()
+}
+
+#[test]
+fn row_gap_is_inserted_between_block_children_only() {
+    let gap = Size { width: zero(), height: length(5.0) };
+    let (taffy, root, children) = block_stack(Style { gap, ..Default::default() }, &[(0.0, 0.0); 3]);
+
+    assert_eq!(y_locations(&taffy, &children), vec![0.0, 15.0, 30.0]);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 40.0);
+}
+
+#[test]
+fn block_children_have_no_gap_by_default() {
+    let (taffy, root, children) = block_stack(Style::default(), &[(0.0, 0.0); 3]);
+    assert_eq!(y_locations(&taffy, &children), vec![0.0, 10.0, 20.0]);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 30.0);
+}
+
+#[test]
+fn row_gap_is_added_to_collapsed_sibling_margins() {
+    let gap = Size { width: zero(), height: length(5.0) };
+    let (taffy, _, children) = block_stack(Style { gap, ..Default::default() }, &[(0.0, 8.0), (4.0, 0.0)]);
+
+    // The 8px and 4px margins collapse to 8px, and the gap is added on top
+    assert_eq!(y_locations(&taffy, &children), vec![0.0, 23.0]);
+
+    let no_collapse = Style { gap, disable_margin_collapsing: true, ..Default::default() };
+    let (taffy, _, children) = block_stack(no_collapse, &[(0.0, 8.0), (4.0, 0.0)]);
+    assert_eq!(y_locations(&taffy, &children), vec![0.0, 27.0]);
+}
+
+#[test]
+fn row_gap_percentages_resolve_against_the_container_height() {
+    let container = Style {
+        gap: Size { width: zero(), height: percent(0.1) },
+        size: Size { width: auto(), height: length(100.0) },
+        ..Default::default()
+    };
+    let (taffy, _, children) = block_stack(container, &[(0.0, 0.0); 2]);
+    assert_eq!(y_locations(&taffy, &children), vec![0.0, 20.0]);
+}
+
+#[test]
+fn row_gap_separates_later_children_from_the_container_top_margin() {
+    let mut taffy = Taffy::new();
+    let empty =
+        taffy.new_leaf(Style { margin: Rect { top: length(10.0), ..Rect::zero() }, ..Default::default() }).unwrap();
+    let sized = taffy
+        .new_leaf(Style {
+            size: Size { width: auto(), height: length(10.0) },
+            margin: Rect { top: length(10.0), ..Rect::zero() },
+            ..Default::default()
+        })
+        .unwrap();
+    let gap = Size { width: zero(), height: length(5.0) };
+    let container =
+        taffy.new_with_children(Style { display: Display::Block, gap, ..Default::default() }, &[empty, sized]).unwrap();
+    let root = taffy.new_with_children(Style { display: Display::Block, ..Default::default() }, &[container]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The empty child's margin collapses through the container's top edge, while the gap and the second child's
+    // margin stay inside of the container
+    assert_eq!(taffy.layout(container).unwrap().location.y, 10.0);
+    assert_eq!(taffy.layout(sized).unwrap().location.y, 15.0);
+    assert_eq!(taffy.layout(container).unwrap().size.height, 25.0);
+}
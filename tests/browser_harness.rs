@@ -0,0 +1,82 @@
+#![cfg(feature = "browser_harness")]
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::tree::browser_harness::{compare_with_browser, render_html, Browser, BrowserLayout, HarnessError};
+
+/// A browser that returns recorded layouts, and remembers the last document it was asked to render
+struct RecordedBrowser {
+    layouts: Vec<BrowserLayout>,
+    html: String,
+}
+
+impl Browser for RecordedBrowser {
+    fn measure(&mut self, html: &str) -> Result<Vec<BrowserLayout>, HarnessError> {
+        self.html = String::from(html);
+        Ok(self.layouts.clone())
+    }
+}
+
+fn layout(x: f32, y: f32, width: f32, height: f32) -> BrowserLayout {
+    BrowserLayout { location: Point { x, y }, size: Size { width, height } }
+}
+
+fn tree(taffy: &mut Taffy) -> NodeId {
+    let first = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+    let second = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+    let style = Style { size: Size::from_lengths(100.0, 50.0), padding: length(5.0), ..Default::default() };
+    taffy.new_with_children(style, &[first, second]).unwrap()
+}
+
+#[test]
+fn matching_layouts_pass() {
+    let mut taffy = Taffy::new();
+    let root = tree(&mut taffy);
+    let mut browser = RecordedBrowser {
+        layouts: vec![layout(0.0, 0.0, 100.0, 50.0), layout(5.0, 5.0, 70.0, 40.0), layout(75.1, 5.0, 20.0, 10.0)],
+        html: String::new(),
+    };
+
+    compare_with_browser(&mut taffy, root, &mut browser, 0.5).unwrap();
+    assert!(browser.html.contains(
+        r#"<div id="test-root" style="width: 100px; height: 50px; padding-top: 5px; padding-right: 5px; padding-bottom: 5px; padding-left: 5px;"><div style="flex-grow: 1;"></div>"#
+    ));
+}
+
+#[test]
+fn mismatching_layouts_are_reported() {
+    let mut taffy = Taffy::new();
+    let root = tree(&mut taffy);
+    let second = taffy.child_at_index(root, 1).unwrap();
+    let mut browser = RecordedBrowser {
+        layouts: vec![layout(0.0, 0.0, 100.0, 50.0), layout(5.0, 5.0, 70.0, 40.0), layout(75.0, 5.0, 20.0, 40.0)],
+        html: String::new(),
+    };
+
+    match compare_with_browser(&mut taffy, root, &mut browser, 0.5) {
+        Err(HarnessError::Mismatch(mismatches)) => {
+            assert_eq!(mismatches.len(), 1);
+            assert_eq!(mismatches[0].node, second);
+            assert_eq!(mismatches[0].taffy.size.height, 10.0);
+        }
+        other => panic!("Expected a mismatch, got {other:?}"),
+    }
+
+    browser.layouts.pop();
+    assert!(matches!(
+        compare_with_browser(&mut taffy, root, &mut browser, 0.5),
+        Err(HarnessError::NodeCount { expected: 3, found: 2 })
+    ));
+}
+
+#[test]
+fn grid_styles_are_rendered_as_css() {
+    let mut taffy = Taffy::new();
+    let style = Style {
+        display: Display::Grid,
+        grid_template_columns: vec![length(10.0), fr(1.0), minmax(length(5.0), max_content())].into(),
+        ..Default::default()
+    };
+    let root = taffy.new_leaf(style).unwrap();
+    assert!(render_html(&taffy, root)
+        .contains(r#"style="display: grid; grid-template-columns: 10px 1fr minmax(5px, max-content);""#));
+}
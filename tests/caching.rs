@@ -64,4 +64,33 @@ mod caching {
         taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
         assert_eq!(NUM_MEASURES.load(Ordering::SeqCst), 3);
     }
+
+    #[test]
+    fn cache_entries_can_be_inspected() {
+        use taffy::tree::RunMode;
+
+        let mut taffy = Taffy::new();
+        let leaf = taffy
+            .new_leaf_with_measure(
+                Style::default(),
+                MeasureFunc::Raw(|known_dimensions, _available_space| Size {
+                    width: known_dimensions.width.unwrap_or(50.0),
+                    height: known_dimensions.height.unwrap_or(20.0),
+                }),
+            )
+            .unwrap();
+        let root = taffy.new_with_children(Style::DEFAULT, &[leaf]).unwrap();
+        assert_eq!(taffy.cache(leaf).unwrap().entries().count(), 0);
+
+        let available_space = Size { width: AvailableSpace::Definite(100.0), height: AvailableSpace::Definite(100.0) };
+        taffy.compute_layout(root, available_space).unwrap();
+
+        let cache = taffy.cache(leaf).unwrap();
+        let size = taffy.layout(leaf).unwrap().size;
+        assert!(cache.entries().any(|(_, entry)| {
+            entry.run_mode() == RunMode::PerformLayout && entry.size_and_baselines().size == size
+        }));
+        let slots: Vec<usize> = cache.entries().map(|(slot, _)| slot).collect();
+        assert!(slots.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 }
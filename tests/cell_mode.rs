@@ -0,0 +1,103 @@
+use taffy::prelude::*;
+use taffy::tree::{CellMode, CellRemainder};
+
+/// Lays out `count` equally flexible items in a row of the given width and returns their horizontal extents
+fn flexible_items(taffy: &mut Taffy, width: f32, count: usize) -> Vec<(f32, f32)> {
+    let items: Vec<NodeId> =
+        (0..count).map(|_| taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap()).collect();
+    let root = taffy
+        .new_with_children(
+            Style { size: Size { width: length(width), height: length(1.0) }, ..Default::default() },
+            &items,
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    items
+        .iter()
+        .map(|item| {
+            let layout = taffy.layout(*item).unwrap();
+            (layout.location.x, layout.size.width)
+        })
+        .collect()
+}
+
+#[test]
+fn remainder_cells_are_distributed_as_configured() {
+    let cases = [
+        (CellRemainder::Nearest, vec![(0.0, 3.0), (3.0, 4.0), (7.0, 3.0)]),
+        (CellRemainder::Start, vec![(0.0, 4.0), (4.0, 3.0), (7.0, 3.0)]),
+        (CellRemainder::End, vec![(0.0, 3.0), (3.0, 3.0), (6.0, 4.0)]),
+    ];
+    for (remainder, expected) in cases {
+        let mut taffy = Taffy::new();
+        taffy.set_cell_mode(Some(CellMode::TERMINAL.with_remainder(remainder)));
+        assert_eq!(flexible_items(&mut taffy, 10.0, 3), expected, "{remainder:?}");
+    }
+}
+
+#[test]
+fn nested_layouts_do_not_drift() {
+    // Columns of a third of 100 cells, each containing a column of a third of their width, nested several times
+    let mut taffy = Taffy::new();
+    taffy.set_cell_mode(Some(CellMode::TERMINAL));
+    let thirds = |taffy: &mut Taffy, children: &[NodeId]| {
+        let style = Style { flex_grow: 1.0, flex_basis: length(0.0), ..Default::default() };
+        taffy.new_with_children(style, children).unwrap()
+    };
+    let leaf = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+    let mut node = leaf;
+    for _ in 0..4 {
+        let siblings: Vec<NodeId> =
+            (0..2).map(|_| taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap()).collect();
+        node = thirds(&mut taffy, &[siblings[0], node, siblings[1]]);
+    }
+    let root_style = Style { size: Size { width: length(100.0), height: length(1.0) }, ..Default::default() };
+    let root = taffy.new_with_children(root_style, &[node]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // Every node's size and location is a whole number of cells, and the children of each node exactly tile it
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let layout = *taffy.layout(node).unwrap();
+        assert_eq!(layout.size.width.fract(), 0.0);
+        assert_eq!(layout.location.x.fract(), 0.0);
+        let children = taffy.children(node).unwrap();
+        if !children.is_empty() {
+            let widths: f32 = children.iter().map(|child| taffy.layout(*child).unwrap().size.width).sum();
+            assert_eq!(widths, layout.size.width);
+        }
+        let mut end = 0.0;
+        for child in &children {
+            let child_layout = taffy.layout(*child).unwrap();
+            assert_eq!(child_layout.location.x, end);
+            end += child_layout.size.width;
+        }
+        stack.extend(children);
+    }
+}
+
+#[test]
+fn cells_can_be_larger_than_a_point() {
+    let mut taffy = Taffy::new();
+    taffy.set_cell_mode(Some(CellMode::new(Size { width: 8.0, height: 16.0 })));
+    let text = taffy.new_leaf(Style { size: Size::from_lengths(21.0, 20.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style { padding: length(3.0), ..Default::default() }, &[text]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 24.0, height: 32.0 });
+    assert_eq!(taffy.layout(text).unwrap().location, taffy::geometry::Point { x: 0.0, y: 0.0 });
+    assert_eq!(taffy.layout(text).unwrap().size, Size { width: 24.0, height: 16.0 });
+}
+
+#[test]
+fn changing_the_cell_mode_snaps_the_layout_again() {
+    let mut taffy = Taffy::new();
+    assert_eq!(flexible_items(&mut taffy, 10.0, 3), vec![(0.0, 3.0), (3.0, 4.0), (7.0, 3.0)]);
+    taffy.set_cell_mode(Some(CellMode::TERMINAL.with_remainder(CellRemainder::End)));
+    assert_eq!(taffy.cell_mode(), Some(CellMode::TERMINAL.with_remainder(CellRemainder::End)));
+    let root = taffy.iter().find(|(node, _, _)| taffy.child_count(*node).unwrap() == 3).unwrap().0;
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let widths: Vec<f32> =
+        taffy.children(root).unwrap().iter().map(|item| taffy.layout(*item).unwrap().size.width).collect();
+    assert_eq!(widths, vec![3.0, 3.0, 4.0]);
+}
@@ -0,0 +1,46 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::tree::CoordinateOrigin;
+
+fn build_tree(taffy: &mut Taffy) -> (NodeId, NodeId) {
+    let child = taffy
+        .new_leaf(Style {
+            size: Size::from_lengths(20.0, 10.0),
+            margin: Rect { left: length(5.0), right: zero(), top: length(10.0), bottom: zero() },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..Default::default() }, &[child])
+        .unwrap();
+    (root, child)
+}
+
+#[test]
+fn coordinate_origin_converts_computed_locations() {
+    let mut taffy = Taffy::new();
+    let (root, child) = build_tree(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 5.0, y: 10.0 });
+
+    // Existing layouts are converted when the origin changes
+    taffy.set_coordinate_origin(CoordinateOrigin::BottomLeft);
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 5.0, y: 80.0 });
+
+    // Relayouts (including those served from the cache) produce locations in the same convention
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 5.0, y: 80.0 });
+    taffy.mark_dirty(root).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 5.0, y: 80.0 });
+    assert_eq!(taffy.layout(root).unwrap().location, Point::ZERO);
+
+    taffy.set_coordinate_origin(CoordinateOrigin::Center);
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: -35.0, y: 35.0 });
+    let detached = taffy.compute_layout_detached(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(detached.get(child).unwrap().location, Point { x: -35.0, y: 35.0 });
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: -35.0, y: 35.0 });
+
+    taffy.set_coordinate_origin(CoordinateOrigin::TopLeft);
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 5.0, y: 10.0 });
+}
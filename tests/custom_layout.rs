@@ -0,0 +1,139 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::tree::{SizeBaselinesAndMargins, SizingMode};
+use taffy::LayoutAlgorithm;
+
+/// The key that the diagonal layout algorithm is registered with
+const DIAGONAL: u16 = 1;
+
+/// Places each child below and to the right of the previous one, at its max-content size
+struct DiagonalAlgorithm;
+
+impl DiagonalAlgorithm {
+    /// Lays out (or only measures) the children of `node`, returning the size of their bounding box
+    fn layout_children(tree: &mut impl LayoutTree, node: NodeId, perform_layout: bool) -> Size<f32> {
+        let mut offset = Point { x: 0.0, y: 0.0 };
+        for index in 0..tree.child_count(node) {
+            let child = tree.child(node, index);
+            let size = tree.measure_child_size(
+                child,
+                Size::NONE,
+                Size::NONE,
+                Size::MAX_CONTENT,
+                SizingMode::InherentSize,
+                Line::FALSE,
+            );
+            if perform_layout {
+                let result = tree.perform_child_layout(
+                    child,
+                    size.map(Some),
+                    Size::NONE,
+                    Size::MAX_CONTENT,
+                    SizingMode::InherentSize,
+                    Line::FALSE,
+                );
+                // Like the built-in algorithms, the parent stores the final size and location of each child
+                let layout = tree.layout_mut(child);
+                layout.order = index as u32;
+                layout.size = result.size;
+                layout.location = offset;
+            }
+            offset = Point { x: offset.x + size.width, y: offset.y + size.height };
+        }
+        Size { width: offset.x, height: offset.y }
+    }
+}
+
+impl LayoutAlgorithm for DiagonalAlgorithm {
+    const NAME: &'static str = "DIAGONAL";
+
+    fn measure_size(
+        tree: &mut impl LayoutTree,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        _parent_size: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+        _sizing_mode: SizingMode,
+        _vertical_margins_are_collapsible: Line<bool>,
+    ) -> Size<f32> {
+        known_dimensions.unwrap_or(Self::layout_children(tree, node, false))
+    }
+
+    fn perform_layout(
+        tree: &mut impl LayoutTree,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        _parent_size: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+        _sizing_mode: SizingMode,
+        _vertical_margins_are_collapsible: Line<bool>,
+    ) -> SizeBaselinesAndMargins {
+        let content_size = Self::layout_children(tree, node, true);
+        known_dimensions.unwrap_or(content_size).into()
+    }
+}
+
+/// A diagonal container holding a fixed size leaf followed by a flex row of two leaves
+fn diagonal_tree(taffy: &mut Taffy) -> (NodeId, NodeId, NodeId) {
+    let leaf = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 20.0), ..Default::default() }).unwrap();
+    let items: Vec<NodeId> = [5.0, 15.0]
+        .iter()
+        .map(|width| taffy.new_leaf(Style { size: Size::from_lengths(*width, 10.0), ..Default::default() }).unwrap())
+        .collect();
+    let row = taffy.new_with_children(Style::default(), &items).unwrap();
+    let root = taffy
+        .new_with_children(Style { display: Display::Custom(DIAGONAL), ..Default::default() }, &[leaf, row])
+        .unwrap();
+    (root, leaf, row)
+}
+
+#[test]
+fn custom_algorithms_lay_out_nested_standard_containers() {
+    let mut taffy = Taffy::new();
+    taffy.register_layout_algorithm::<DiagonalAlgorithm>(DIAGONAL);
+    let (root, leaf, row) = diagonal_tree(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(leaf).unwrap().location, Point { x: 0.0, y: 0.0 });
+    assert_eq!(taffy.layout(row).unwrap().location, Point { x: 10.0, y: 20.0 });
+    assert_eq!(taffy.layout(row).unwrap().size, Size { width: 20.0, height: 10.0 });
+    let second_item = taffy.child_at_index(row, 1).unwrap();
+    assert_eq!(taffy.layout(second_item).unwrap().location.x, 5.0);
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 30.0, height: 30.0 });
+}
+
+#[test]
+fn unregistered_custom_algorithms_hide_the_node() {
+    let mut taffy = Taffy::new();
+    let (root, _, row) = diagonal_tree(&mut taffy);
+    let parent = taffy.new_with_children(Style::default(), &[root]).unwrap();
+    taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size::ZERO);
+    assert_eq!(taffy.layout(row).unwrap().size, Size::ZERO);
+
+    // Registering the algorithm relays out the nodes that use it
+    taffy.register_layout_algorithm::<DiagonalAlgorithm>(DIAGONAL);
+    taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 30.0, height: 30.0 });
+
+    assert!(taffy.unregister_layout_algorithm(DIAGONAL));
+    assert!(!taffy.unregister_layout_algorithm(DIAGONAL));
+    taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size::ZERO);
+}
+
+#[test]
+fn subtrees_laid_out_by_custom_algorithms_are_not_memoized() {
+    let mut taffy = Taffy::new();
+    taffy.enable_subtree_memoization();
+    let (root, _, _) = diagonal_tree(&mut taffy);
+    taffy.set_memoization_root(root, true).unwrap();
+    let parent = taffy.new_with_children(Style::default(), &[root]).unwrap();
+    taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size::ZERO);
+
+    // The styles of the subtree are unchanged, but the algorithm laying it out is not
+    taffy.register_layout_algorithm::<DiagonalAlgorithm>(DIAGONAL);
+    taffy.compute_layout(parent, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 30.0, height: 30.0 });
+}
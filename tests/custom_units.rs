@@ -0,0 +1,38 @@
+use taffy::prelude::*;
+
+const GRID_UNIT: u32 = 0;
+const HALF_OF_FIRST_CHILD: u32 = 1;
+
+#[test]
+fn custom_units_are_resolved_with_the_resolver() {
+    let mut taffy = Taffy::new();
+    let first = taffy
+        .new_leaf(Style {
+            size: Size { width: Dimension::Length(100.0), height: Dimension::Custom(GRID_UNIT) },
+            ..Default::default()
+        })
+        .unwrap();
+    let second = taffy
+        .new_leaf(Style {
+            size: Size { width: Dimension::Custom(HALF_OF_FIRST_CHILD), height: Dimension::Length(10.0) },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+
+    // Custom units resolve to zero without a resolver
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(first).unwrap().size.height, 0.0);
+    assert_eq!(taffy.layout(second).unwrap().size.width, 0.0);
+
+    // Units may depend on the layouts of other nodes, from the previous layout computation
+    let root_children = taffy.children(root).unwrap();
+    taffy.set_custom_unit_resolver(Some(Box::new(move |unit, _node, taffy: &Taffy| match unit {
+        GRID_UNIT => 8.0,
+        HALF_OF_FIRST_CHILD => taffy.layout(root_children[0]).unwrap().size.width / 2.0,
+        _ => unreachable!(),
+    })));
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(first).unwrap().size.height, 8.0);
+    assert_eq!(taffy.layout(second).unwrap().size.width, 50.0);
+}
@@ -0,0 +1,93 @@
+use taffy::geometry::Rect;
+use taffy::prelude::*;
+use taffy::tree::MAX_DAMAGE_RECTS;
+
+/// A 200x100 row containing two 20x20 leaves, laid out with damage tracking enabled
+fn row() -> (Taffy, NodeId, [NodeId; 2]) {
+    let mut taffy = Taffy::new();
+    taffy.enable_damage_tracking();
+    let first = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+    let second = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(200.0, 100.0), ..Default::default() }, &[first, second])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, [first, second])
+}
+
+#[test]
+fn damage_is_empty_unless_enabled() {
+    let mut taffy = Taffy::new();
+    let root = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert!(taffy.damage_rects().is_empty());
+    assert_eq!(taffy.damage_bounds(), None);
+}
+
+#[test]
+fn the_first_layout_damages_the_whole_tree() {
+    let (taffy, _, _) = row();
+    assert_eq!(taffy.damage_rects(), &[Rect::new(0.0, 200.0, 0.0, 100.0)]);
+}
+
+#[test]
+fn unchanged_layouts_cause_no_damage() {
+    let (mut taffy, root, _) = row();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert!(taffy.damage_rects().is_empty());
+}
+
+#[test]
+fn moved_nodes_damage_their_previous_and_current_rects() {
+    let (mut taffy, root, [first, _]) = row();
+    taffy.set_style(first, Style { size: Size::from_lengths(30.0, 20.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The first leaf grew, which moved the second leaf to the right
+    assert_eq!(taffy.damage_rects(), &[Rect::new(0.0, 50.0, 0.0, 20.0)]);
+}
+
+#[test]
+fn removed_nodes_damage_their_previous_rect() {
+    let (mut taffy, root, [_, second]) = row();
+    taffy.remove(second).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.damage_rects(), &[Rect::new(20.0, 40.0, 0.0, 20.0)]);
+
+    let (mut taffy, root, [_, second]) = row();
+    taffy.remove_child(root, second).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.damage_rects(), &[Rect::new(20.0, 40.0, 0.0, 20.0)]);
+}
+
+#[test]
+fn disjoint_changes_are_reported_separately_up_to_a_limit() {
+    let mut taffy = Taffy::new();
+    taffy.enable_damage_tracking();
+    let leaf_style = Style { size: Size::from_lengths(10.0, 10.0), margin: length(10.0), ..Default::default() };
+    let leaves: Vec<NodeId> = (0..12).map(|_| taffy.new_leaf(leaf_style.clone()).unwrap()).collect();
+    // The root has a fixed size, so that only the leaves are damaged
+    let root_style = Style { size: Size::from_lengths(400.0, 30.0), ..Default::default() };
+    let root = taffy.new_with_children(root_style, &leaves).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let grown = Style { size: Size::from_lengths(10.0, 5.0), ..leaf_style };
+    for leaf in &leaves[..3] {
+        taffy.set_style(*leaf, grown.clone()).unwrap();
+    }
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(
+        taffy.damage_rects(),
+        &[Rect::new(10.0, 20.0, 10.0, 20.0), Rect::new(40.0, 50.0, 10.0, 20.0), Rect::new(70.0, 80.0, 10.0, 20.0)]
+    );
+    assert_eq!(taffy.damage_bounds(), Some(Rect::new(10.0, 80.0, 10.0, 20.0)));
+
+    let shrunk = Style { size: Size::from_lengths(10.0, 2.0), ..grown };
+    for leaf in &leaves {
+        taffy.set_style(*leaf, shrunk.clone()).unwrap();
+    }
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.damage_rects().len(), MAX_DAMAGE_RECTS);
+    assert_eq!(taffy.damage_bounds(), Some(Rect::new(10.0, 350.0, 10.0, 20.0)));
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "debug")]
+
+use std::sync::{Arc, Mutex};
+
+use taffy::prelude::*;
+use taffy::util::{DebugEvent, DebugSink};
+
+/// An event received by a [`RecordingSink`]
+#[derive(Debug, Clone, PartialEq)]
+enum Record {
+    Enter(NodeId),
+    Exit(NodeId),
+    Event { node: NodeId, algorithm: Option<&'static str>, phase: &'static str, labels: Vec<&'static str> },
+}
+
+/// Records every event it receives into a shared list
+struct RecordingSink(Arc<Mutex<Vec<Record>>>);
+
+impl DebugSink for RecordingSink {
+    fn enter_node(&mut self, node: NodeId) {
+        self.0.lock().unwrap().push(Record::Enter(node));
+    }
+
+    fn exit_node(&mut self, node: NodeId) {
+        self.0.lock().unwrap().push(Record::Exit(node));
+    }
+
+    fn event(&mut self, event: &DebugEvent<'_>) {
+        self.0.lock().unwrap().push(Record::Event {
+            node: event.node,
+            algorithm: event.algorithm,
+            phase: event.phase,
+            labels: event.values.iter().map(|(label, _)| *label).collect(),
+        });
+    }
+}
+
+fn flex_tree(taffy: &mut Taffy) -> (NodeId, NodeId) {
+    let child = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { size: Size { width: length(100.0), height: length(50.0) }, ..Default::default() },
+            &[child],
+        )
+        .unwrap();
+    (root, child)
+}
+
+#[test]
+fn sink_receives_structured_events() {
+    let mut taffy = Taffy::new();
+    let (root, child) = flex_tree(&mut taffy);
+    let records = Arc::new(Mutex::new(Vec::new()));
+    taffy.set_debug_sink(Some(Box::new(RecordingSink(Arc::clone(&records)))));
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.first(), Some(&Record::Enter(root)));
+    assert_eq!(records.last(), Some(&Record::Exit(root)));
+    let enters = records.iter().filter(|record| matches!(record, Record::Enter(_))).count();
+    let exits = records.iter().filter(|record| matches!(record, Record::Exit(_))).count();
+    assert_eq!(enters, exits);
+
+    assert!(records.contains(&Record::Event {
+        node: root,
+        algorithm: Some("FLEXBOX"),
+        phase: "resolve_flexible_lengths",
+        labels: Vec::new(),
+    }));
+    assert!(records.iter().any(|record| matches!(
+        record,
+        Record::Event { node, algorithm: Some("FLEXBOX"), phase: "flex_base_size", labels }
+            if *node == child && labels.contains(&"flex_basis")
+    )));
+    assert!(records.iter().any(|record| matches!(
+        record,
+        Record::Event { node, algorithm: None, phase: "result", labels } if *node == root && labels == &["size"]
+    )));
+}
+
+#[test]
+fn events_are_discarded_without_a_sink() {
+    let mut taffy = Taffy::new();
+    let (root, _) = flex_tree(&mut taffy);
+    let records = Arc::new(Mutex::new(Vec::new()));
+    taffy.set_debug_sink(Some(Box::new(RecordingSink(Arc::clone(&records)))));
+    taffy.set_debug_sink(None);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert!(records.lock().unwrap().is_empty());
+}
@@ -0,0 +1,62 @@
+use taffy::prelude::*;
+
+/// Builds a chain of `depth` containers around a 10x10 leaf, cycling through the layout algorithms
+fn deep_tree(taffy: &mut Taffy, depth: usize) -> (NodeId, NodeId) {
+    let leaf = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let displays = [Display::Flex, Display::Grid, Display::Block];
+
+    let mut node = leaf;
+    for level in 0..depth {
+        let style = Style { display: displays[level % displays.len()], ..Default::default() };
+        node = taffy.new_with_children(style, &[node]).unwrap();
+    }
+    (node, leaf)
+}
+
+#[test]
+fn moderately_deep_tree_lays_out_without_growing_the_stack() {
+    // A depth that fits in the stack of a test thread even in a debug build without the `stacker` feature
+    let mut taffy = Taffy::new();
+    let (root, leaf) = deep_tree(&mut taffy, 50);
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 10.0, height: 10.0 });
+    assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 10.0, height: 10.0 });
+}
+
+#[test]
+#[cfg(feature = "stacker")]
+fn very_deep_tree_does_not_overflow_stack() {
+    let mut taffy = Taffy::new();
+    let (root, leaf) = deep_tree(&mut taffy, 20_000);
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 10.0, height: 10.0 });
+    assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 10.0, height: 10.0 });
+}
+
+#[test]
+#[cfg(feature = "stacker")]
+fn very_deep_tree_can_be_hit_tested() {
+    let mut taffy = Taffy::new();
+    let (root, leaf) = deep_tree(&mut taffy, 20_000);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.hit_test(root, taffy::geometry::Point { x: 5.0, y: 5.0 }).unwrap(), Some(leaf));
+}
+
+#[test]
+#[cfg(feature = "stacker")]
+fn very_deep_tree_can_be_culled() {
+    let mut taffy = Taffy::new();
+    let (root, leaf) = deep_tree(&mut taffy, 20_000);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let rect = Rect { left: 0.0, right: 10.0, top: 0.0, bottom: 10.0 };
+    let nodes = taffy.nodes_in_rect(root, rect).unwrap();
+    assert_eq!(nodes.len(), 20_001);
+    assert_eq!(nodes.first(), Some(&root));
+    assert_eq!(nodes.last(), Some(&leaf));
+}
@@ -0,0 +1,80 @@
+use taffy::prelude::*;
+
+/// A 10px tall block with the given vertical margins
+fn block(taffy: &mut Taffy, margin_top: f32, margin_bottom: f32, children: &[NodeId]) -> NodeId {
+    let style = Style {
+        display: Display::Block,
+        size: Size { width: auto(), height: if children.is_empty() { length(10.0) } else { auto() } },
+        margin: Rect { left: zero(), right: zero(), top: length(margin_top), bottom: length(margin_bottom) },
+        ..Default::default()
+    };
+    taffy.new_with_children(style, children).unwrap()
+}
+
+/// Lays out a block root with the given margin collapsing behaviour, containing a block that wraps a child with
+/// margins, followed by an empty block and a sibling. Returns the root along with its children.
+fn stacked_blocks(disable_margin_collapsing: bool) -> (Taffy, NodeId, Vec<NodeId>) {
+    let mut taffy = Taffy::new();
+    let grandchild = block(&mut taffy, 15.0, 15.0, &[]);
+    let wrapper = block(&mut taffy, 10.0, 10.0, &[grandchild]);
+    let empty = taffy
+        .new_leaf(Style {
+            display: Display::Block,
+            margin: Rect { left: zero(), right: zero(), top: length(5.0), bottom: length(5.0) },
+            ..Default::default()
+        })
+        .unwrap();
+    let sibling = block(&mut taffy, 20.0, 0.0, &[]);
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Block,
+                disable_margin_collapsing,
+                size: Size { width: length(100.0), height: auto() },
+                ..Default::default()
+            },
+            &[wrapper, empty, sibling],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, vec![wrapper, empty, sibling])
+}
+
+#[test]
+fn margins_collapse_by_default() {
+    let (taffy, root, children) = stacked_blocks(false);
+    let positions: Vec<f32> = children.iter().map(|child| taffy.layout(*child).unwrap().location.y).collect();
+
+    // The wrapper's margins collapse with the grandchild's, and the empty block is collapsed through
+    assert_eq!((positions[0], positions[2]), (15.0, 45.0));
+    assert_eq!(taffy.layout(children[0]).unwrap().size.height, 10.0);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 55.0);
+}
+
+#[test]
+fn disabled_margin_collapsing_stacks_full_margins() {
+    let (taffy, root, children) = stacked_blocks(true);
+    let positions: Vec<f32> = children.iter().map(|child| taffy.layout(*child).unwrap().location.y).collect();
+
+    assert_eq!(positions, vec![10.0, 65.0, 90.0]);
+    assert_eq!(taffy.layout(children[0]).unwrap().size.height, 40.0);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 100.0);
+}
+
+#[test]
+fn disabled_margin_collapsing_keeps_the_node_apart_from_its_parent() {
+    let mut taffy = Taffy::new();
+    let child = block(&mut taffy, 10.0, 0.0, &[]);
+    let node = taffy
+        .new_with_children(
+            Style { display: Display::Block, disable_margin_collapsing: true, ..Default::default() },
+            &[child],
+        )
+        .unwrap();
+    let root = taffy.new_with_children(Style { display: Display::Block, ..Default::default() }, &[node]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(node).unwrap().location.y, 0.0);
+    assert_eq!(taffy.layout(child).unwrap().location.y, 10.0);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 20.0);
+}
@@ -0,0 +1,31 @@
+use std::thread;
+
+use taffy::prelude::*;
+
+#[test]
+fn layouts_are_published_to_readers() {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+    let reader = taffy.layout_reader();
+    assert!(reader.latest().is_none());
+    assert_eq!(reader.generation(), 0);
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let first = reader.latest().unwrap();
+    assert_eq!(first.generation, 1);
+    assert_eq!(first.root, root);
+    assert_eq!(first.layouts.get(child).unwrap().size, Size { width: 10.0, height: 10.0 });
+
+    // A snapshot that is being read is unaffected by later layouts
+    taffy.set_style(child, Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(first.layouts.get(child).unwrap().size, Size { width: 10.0, height: 10.0 });
+
+    // Readers can be used from other threads
+    let render_thread = thread::spawn(move || reader.latest().unwrap());
+    let second = render_thread.join().unwrap();
+    assert_eq!(second.generation, 2);
+    assert_eq!(second.layouts.get(child).unwrap().size, Size { width: 20.0, height: 20.0 });
+}
@@ -0,0 +1,108 @@
+use taffy::prelude::*;
+use taffy::tree::{MeasureFunc, SizeConstraint};
+
+/// Lays out `child` as the only child of a container with the given style, and returns the explanation of its size
+fn explain_child(container: Style, child: Style) -> Size<SizeConstraint> {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(child).unwrap();
+    let root = taffy.new_with_children(container, &[child]).unwrap();
+    taffy.explain_node(Some(child)).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.size_explanation().unwrap()
+}
+
+fn flex_row(width: f32, height: f32) -> Style {
+    Style { size: Size { width: length(width), height: length(height) }, ..Default::default() }
+}
+
+#[test]
+fn explains_style_sizes_and_clamps() {
+    let explanation = explain_child(
+        Style { align_items: Some(AlignItems::Start), ..flex_row(100.0, 100.0) },
+        Style {
+            size: Size { width: length(37.0), height: length(50.0) },
+            max_size: Size { width: auto(), height: length(20.0) },
+            ..Default::default()
+        },
+    );
+    assert_eq!(explanation, Size { width: SizeConstraint::StyleSize, height: SizeConstraint::MaxSize });
+}
+
+#[test]
+fn explains_flexing_and_stretching() {
+    let grown = explain_child(flex_row(100.0, 50.0), Style { flex_grow: 1.0, ..Default::default() });
+    assert_eq!(grown, Size { width: SizeConstraint::FlexGrow, height: SizeConstraint::Stretch });
+
+    let shrunk = explain_child(
+        flex_row(100.0, 50.0),
+        Style { size: Size { width: length(150.0), height: auto() }, ..Default::default() },
+    );
+    assert_eq!(shrunk.width, SizeConstraint::FlexShrink);
+
+    let capped = explain_child(
+        flex_row(100.0, 50.0),
+        Style { flex_grow: 1.0, max_size: Size { width: length(30.0), height: auto() }, ..Default::default() },
+    );
+    assert_eq!(capped.width, SizeConstraint::MaxSize);
+
+    let based = explain_child(flex_row(100.0, 50.0), Style { flex_basis: length(40.0), ..Default::default() });
+    assert_eq!(based.width, SizeConstraint::FlexBasis);
+}
+
+#[test]
+fn explains_grid_areas() {
+    let explanation = explain_child(
+        Style {
+            display: Display::Grid,
+            grid_template_columns: vec![length(30.0), fr(1.0)].into(),
+            grid_template_rows: vec![length(20.0)].into(),
+            size: Size { width: length(100.0), height: auto() },
+            ..Default::default()
+        },
+        Style::default(),
+    );
+    assert_eq!(explanation, Size { width: SizeConstraint::GridArea, height: SizeConstraint::GridArea });
+}
+
+#[test]
+fn explains_block_widths_and_measured_sizes() {
+    let mut taffy = Taffy::new();
+    let text = taffy
+        .new_leaf_with_measure(Style::default(), MeasureFunc::Raw(|_, _| Size { width: 10.0, height: 15.0 }))
+        .unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Block,
+                size: Size { width: length(100.0), height: auto() },
+                ..Default::default()
+            },
+            &[text],
+        )
+        .unwrap();
+    taffy.explain_node(Some(text)).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(
+        taffy.size_explanation(),
+        Some(Size { width: SizeConstraint::Stretch, height: SizeConstraint::Measured })
+    );
+}
+
+#[test]
+fn explanation_is_only_available_while_explaining() {
+    let mut taffy = Taffy::new();
+    let node = taffy.new_leaf(Style::default()).unwrap();
+    assert_eq!(taffy.size_explanation(), None);
+
+    taffy.explain_node(Some(node)).unwrap();
+    assert_eq!(taffy.size_explanation(), None);
+    taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+    assert_eq!(
+        taffy.size_explanation(),
+        Some(Size { width: SizeConstraint::Content, height: SizeConstraint::Content })
+    );
+
+    taffy.explain_node(None).unwrap();
+    assert_eq!(taffy.size_explanation(), None);
+}
@@ -0,0 +1,83 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Overflow;
+use taffy::tree::MeasureFunc;
+
+/// Lays out a grid with an auto width whose columns are `fit-content(50%)` and `100px`, containing text that is
+/// 20px wide at its min-content size and 200px wide at its max-content size. Returns the widths of the grid and of its
+/// first column.
+fn fit_content_grid(max_width: Dimension, available_width: AvailableSpace) -> (f32, f32) {
+    let mut taffy = Taffy::new();
+    let text = taffy
+        .new_leaf_with_measure(
+            Style::default(),
+            MeasureFunc::Raw(|known_dimensions, available_space| Size {
+                width: known_dimensions.width.unwrap_or(match available_space.width {
+                    AvailableSpace::MinContent => 20.0,
+                    AvailableSpace::MaxContent => 200.0,
+                    AvailableSpace::Definite(width) => width.clamp(20.0, 200.0),
+                }),
+                height: 10.0,
+            }),
+        )
+        .unwrap();
+    let fixed = taffy.new_leaf(Style::default()).unwrap();
+    let grid = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                grid_template_columns: vec![fit_content(percent(0.5)), length(100.0)].into(),
+                max_size: Size { width: max_width, height: auto() },
+                ..Default::default()
+            },
+            &[text, fixed],
+        )
+        .unwrap();
+    taffy.compute_layout(grid, Size { width: available_width, height: AvailableSpace::MaxContent }).unwrap();
+
+    (taffy.layout(grid).unwrap().size.width, taffy.layout(text).unwrap().size.width)
+}
+
+#[test]
+fn fit_content_percentage_resolves_against_the_content_sized_grid() {
+    // The limit is treated as infinite while the grid is sized (200px + 100px), then resolved against that size
+    assert_eq!(fit_content_grid(auto(), AvailableSpace::MaxContent), (300.0, 150.0));
+    assert_eq!(fit_content_grid(auto(), AvailableSpace::Definite(1000.0)), (300.0, 150.0));
+}
+
+#[test]
+fn fit_content_percentage_resolves_against_the_clamped_grid_size() {
+    assert_eq!(fit_content_grid(length(250.0), AvailableSpace::MaxContent), (250.0, 125.0));
+}
+
+#[test]
+fn fit_content_percentage_rows_are_resolved_without_resizing_columns() {
+    let mut taffy = Taffy::new();
+    // Scroll containers can shrink below their min-content contribution, down to the fit-content() limit
+    let scroller = taffy
+        .new_leaf_with_measure(
+            Style { overflow: Point { x: Overflow::Visible, y: Overflow::Scroll }, ..Default::default() },
+            MeasureFunc::Raw(|known_dimensions, _| Size {
+                width: known_dimensions.width.unwrap_or(100.0),
+                height: known_dimensions.height.unwrap_or(200.0),
+            }),
+        )
+        .unwrap();
+    let fixed = taffy.new_leaf(Style::default()).unwrap();
+    let grid = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                grid_template_columns: vec![length(100.0)].into(),
+                grid_template_rows: vec![fit_content(percent(0.5)), length(100.0)].into(),
+                ..Default::default()
+            },
+            &[scroller, fixed],
+        )
+        .unwrap();
+    taffy.compute_layout(grid, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(grid).unwrap().size.height, 300.0);
+    assert_eq!(taffy.layout(scroller).unwrap().size.height, 150.0);
+    assert_eq!(taffy.layout(fixed).unwrap().location.y, 150.0);
+}
@@ -0,0 +1,128 @@
+use taffy::prelude::*;
+use taffy::tree::{FixedError, MeasureFunc, TaffyFixed};
+
+/// The style of a row that wraps its children, so that their layout depends on both flexing and wrapping
+fn row_style() -> Style {
+    Style { flex_wrap: FlexWrap::Wrap, size: Size { width: length(100.0), height: auto() }, ..Default::default() }
+}
+
+/// The style of an item of the row
+fn item_style(width: f32) -> Style {
+    Style { size: Size { width: length(width), height: length(10.0) }, flex_grow: 1.0, ..Default::default() }
+}
+
+#[test]
+fn fixed_tree_matches_the_slotmap_tree() {
+    let widths = [30.0, 45.0, 20.0, 60.0, 15.5];
+
+    let mut taffy = Taffy::new();
+    let items: Vec<NodeId> = widths.iter().map(|width| taffy.new_leaf(item_style(*width)).unwrap()).collect();
+    let root = taffy.new_with_children(row_style(), &items).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let mut fixed = TaffyFixed::<8>::new();
+    let fixed_items: Vec<NodeId> = widths.iter().map(|width| fixed.new_leaf(item_style(*width)).unwrap()).collect();
+    let fixed_root = fixed.new_with_children(row_style(), &fixed_items).unwrap();
+    fixed.compute_layout(fixed_root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(fixed.layout(fixed_root).unwrap(), taffy.layout(root).unwrap());
+    for (fixed_item, item) in fixed_items.iter().zip(&items) {
+        assert_eq!(fixed.layout(*fixed_item).unwrap(), taffy.layout(*item).unwrap());
+    }
+}
+
+#[test]
+fn leaves_are_measured() {
+    let mut tree = TaffyFixed::<2>::new();
+    let text = MeasureFunc::Raw(|known_dimensions, _| Size {
+        width: known_dimensions.width.unwrap_or(42.0),
+        height: known_dimensions.height.unwrap_or(7.0),
+    });
+    let leaf = tree.new_leaf_with_measure(Style::default(), text).unwrap();
+    let root = tree.new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &[leaf]);
+    tree.compute_layout(root.unwrap(), Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(tree.layout(leaf).unwrap().size, Size { width: 42.0, height: 7.0 });
+}
+
+#[test]
+fn full_trees_reject_new_nodes() {
+    let mut tree = TaffyFixed::<2>::new();
+    let first = tree.new_leaf(Style::default()).unwrap();
+    tree.new_leaf(Style::default()).unwrap();
+
+    assert_eq!(tree.new_leaf(Style::default()), Err(FixedError::CapacityExceeded { capacity: 2 }));
+    assert_eq!(tree.len(), 2);
+
+    // Removing a node frees its slot for the next one
+    tree.remove(first).unwrap();
+    assert_eq!(tree.new_leaf(Style::default()), Ok(first));
+}
+
+#[test]
+fn children_can_be_moved_and_removed() {
+    let mut tree = TaffyFixed::<5>::new();
+    let a = tree.new_leaf(Style::default()).unwrap();
+    let b = tree.new_leaf(Style::default()).unwrap();
+    let c = tree.new_leaf(Style::default()).unwrap();
+    let first = tree.new_with_children(Style::default(), &[a, b, c]).unwrap();
+    let second = tree.new_leaf(Style::default()).unwrap();
+
+    tree.add_child(second, b).unwrap();
+    assert_eq!(tree.children(first).unwrap().collect::<Vec<_>>(), vec![a, c]);
+    assert_eq!(tree.children(second).unwrap().collect::<Vec<_>>(), vec![b]);
+    assert_eq!(tree.parent(b), Ok(Some(second)));
+
+    tree.remove_child(first, c).unwrap();
+    assert_eq!(tree.child_count(first), Ok(1));
+    assert_eq!(tree.parent(c), Ok(None));
+    assert_eq!(tree.remove_child(first, c), Err(FixedError::InvalidChildNode(c)));
+
+    // Removing a node detaches its children rather than removing them
+    tree.remove(second).unwrap();
+    assert_eq!(tree.parent(b), Ok(None));
+    assert_eq!(tree.layout(second), Err(FixedError::InvalidInputNode(second)));
+}
+
+#[test]
+fn children_of_interleaved_parents_keep_their_order() {
+    let mut tree = TaffyFixed::<8>::new();
+    let leaves: Vec<NodeId> = (0..5).map(|_| tree.new_leaf(Style::default()).unwrap()).collect();
+    let first = tree.new_with_children(Style::default(), &leaves[0..2]).unwrap();
+    let second = tree.new_with_children(Style::default(), &leaves[2..4]).unwrap();
+
+    // Growing the first node's children moves those of the second node, which follow them
+    tree.add_child(first, leaves[4]).unwrap();
+    tree.add_child(second, leaves[0]).unwrap();
+    assert_eq!(tree.children(first).unwrap().collect::<Vec<_>>(), vec![leaves[1], leaves[4]]);
+    assert_eq!(tree.children(second).unwrap().collect::<Vec<_>>(), vec![leaves[2], leaves[3], leaves[0]]);
+    assert_eq!(LayoutTree::child(&tree, second, 2), leaves[0]);
+
+    tree.remove(first).unwrap();
+    assert_eq!(tree.children(second).unwrap().collect::<Vec<_>>(), vec![leaves[2], leaves[3], leaves[0]]);
+    assert_eq!(tree.parent(leaves[4]), Ok(None));
+}
+
+#[test]
+fn cycles_are_rejected() {
+    let mut tree = TaffyFixed::<3>::new();
+    let leaf = tree.new_leaf(Style::default()).unwrap();
+    let middle = tree.new_with_children(Style::default(), &[leaf]).unwrap();
+    let root = tree.new_with_children(Style::default(), &[middle]).unwrap();
+
+    assert_eq!(tree.add_child(leaf, root), Err(FixedError::CyclicChild { parent: leaf, child: root }));
+    assert_eq!(tree.add_child(leaf, leaf), Err(FixedError::CyclicChild { parent: leaf, child: leaf }));
+}
+
+#[test]
+fn changing_a_style_invalidates_the_layout() {
+    let mut tree = TaffyFixed::<2>::new();
+    let leaf = tree.new_leaf(item_style(30.0)).unwrap();
+    let root = tree.new_with_children(row_style(), &[leaf]).unwrap();
+    tree.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(tree.layout(leaf).unwrap().size.width, 100.0);
+
+    tree.set_style(leaf, Style { flex_grow: 0.0, ..item_style(30.0) }).unwrap();
+    tree.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(tree.layout(leaf).unwrap().size.width, 30.0);
+}
@@ -0,0 +1,86 @@
+use taffy::prelude::*;
+use taffy::tree::FlatLayout;
+use taffy::TaffyError;
+
+/// A row of three 10x10 leaves, the second of which is a column containing two 5x5 leaves
+fn row_tree() -> (Taffy, NodeId, Vec<NodeId>) {
+    let mut taffy = Taffy::new();
+    let leaf = |taffy: &mut Taffy, size: f32| {
+        taffy.new_leaf(Style { size: Size::from_lengths(size, size), ..Default::default() }).unwrap()
+    };
+    let first = leaf(&mut taffy, 10.0);
+    let nested = [leaf(&mut taffy, 5.0), leaf(&mut taffy, 5.0)];
+    let column = taffy
+        .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &nested)
+        .unwrap();
+    let last = leaf(&mut taffy, 10.0);
+    let root = taffy.new_with_children(Style::default(), &[first, column, last]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, vec![root, first, column, nested[0], nested[1], last])
+}
+
+/// Returns the slot index of a node, as stored in [`FlatLayout::node`]
+fn index(node: NodeId) -> u32 {
+    u64::from(node) as u32
+}
+
+#[test]
+fn entries_record_the_structure_of_the_tree() {
+    let (taffy, root, nodes) = row_tree();
+    let mut buffer = Vec::new();
+    taffy.write_flat_layout(root, &mut buffer).unwrap();
+
+    let structure: Vec<(u32, u32, u32, u32)> =
+        buffer.iter().map(|entry| (entry.node, entry.parent, entry.order, entry.depth)).collect();
+    assert_eq!(
+        structure,
+        vec![
+            (index(nodes[0]), FlatLayout::NO_PARENT, 0, 0),
+            (index(nodes[1]), 0, 0, 1),
+            (index(nodes[2]), 0, 1, 1),
+            (index(nodes[3]), 2, 0, 2),
+            (index(nodes[4]), 2, 1, 2),
+            (index(nodes[5]), 0, 2, 1),
+        ]
+    );
+    assert!(buffer[0].is_root());
+    assert!(buffer[1..].iter().all(|entry| !entry.is_root()));
+
+    // Positions are those of the top-left corners, and sizes are copied as is
+    assert_eq!((buffer[4].x, buffer[4].y, buffer[4].width, buffer[4].height), (10.0, 5.0, 5.0, 5.0));
+}
+
+#[test]
+fn entries_can_be_uploaded_without_conversion() {
+    assert_eq!(std::mem::size_of::<FlatLayout>(), 32);
+    assert_eq!(std::mem::align_of::<FlatLayout>(), 4);
+}
+
+#[test]
+fn buffers_are_cleared_and_reused() {
+    let (taffy, root, nodes) = row_tree();
+    let mut buffer = Vec::new();
+    taffy.write_flat_layout(root, &mut buffer).unwrap();
+    let capacity = buffer.capacity();
+
+    taffy.write_flat_layout(nodes[2], &mut buffer).unwrap();
+    assert_eq!(buffer.len(), 3);
+    assert_eq!((buffer[0].x, buffer[0].y, buffer[0].depth, buffer[0].parent), (0.0, 0.0, 0, FlatLayout::NO_PARENT));
+    assert_eq!((buffer[2].x, buffer[2].y, buffer[2].depth, buffer[2].parent), (0.0, 5.0, 1, 0));
+    assert_eq!(buffer.capacity(), capacity);
+}
+
+#[test]
+fn invalid_roots_leave_the_buffer_untouched() {
+    let (mut taffy, root, nodes) = row_tree();
+    let mut buffer = Vec::new();
+    taffy.write_flat_layout(root, &mut buffer).unwrap();
+    let written = buffer.clone();
+
+    taffy.remove(nodes[5]).unwrap();
+    assert!(matches!(
+        taffy.write_flat_layout(nodes[5], &mut buffer),
+        Err(TaffyError::InvalidInputNode(node)) if node == nodes[5]
+    ));
+    assert_eq!(buffer, written);
+}
@@ -0,0 +1,77 @@
+use taffy::prelude::*;
+
+/// Lays out a 100px wide row containing a single 20px wide item with the given `justify_self` and margin
+fn row_item_position(
+    flex_justify_self: bool,
+    flex_direction: FlexDirection,
+    justify_self: AlignSelf,
+    margin: f32,
+) -> f32 {
+    let mut taffy = Taffy::new();
+    let item = taffy
+        .new_leaf(Style {
+            justify_self: Some(justify_self),
+            size: Size { width: length(20.0), height: length(20.0) },
+            margin: Rect { left: length(margin), right: length(margin), top: zero(), bottom: zero() },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                flex_direction,
+                flex_justify_self,
+                size: Size { width: length(100.0), height: length(20.0) },
+                ..Default::default()
+            },
+            &[item],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.layout(item).unwrap().location.x
+}
+
+#[test]
+fn justify_self_is_ignored_without_the_flag() {
+    assert_eq!(row_item_position(false, FlexDirection::Row, AlignSelf::End, 0.0), 0.0);
+    assert_eq!(row_item_position(false, FlexDirection::Row, AlignSelf::Center, 0.0), 0.0);
+}
+
+#[test]
+fn justify_self_aligns_items_within_their_line() {
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::Start, 0.0), 0.0);
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::Center, 0.0), 40.0);
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::End, 0.0), 80.0);
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::FlexEnd, 0.0), 80.0);
+    assert_eq!(row_item_position(true, FlexDirection::RowReverse, AlignSelf::FlexEnd, 0.0), 0.0);
+    assert_eq!(row_item_position(true, FlexDirection::RowReverse, AlignSelf::Start, 0.0), 0.0);
+}
+
+#[test]
+fn justify_self_keeps_item_margins() {
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::End, 5.0), 75.0);
+    assert_eq!(row_item_position(true, FlexDirection::Row, AlignSelf::Center, 5.0), 40.0);
+}
+
+#[test]
+fn justify_self_shares_free_space_with_siblings() {
+    let mut taffy = Taffy::new();
+    let size = Size { width: length(20.0), height: length(20.0) };
+    let first = taffy.new_leaf(Style { size, ..Default::default() }).unwrap();
+    let last = taffy.new_leaf(Style { size, justify_self: Some(AlignSelf::End), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                flex_justify_self: true,
+                justify_content: Some(JustifyContent::Center),
+                size: Size { width: length(100.0), height: length(20.0) },
+                ..Default::default()
+            },
+            &[first, last],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(first).unwrap().location.x, 0.0);
+    assert_eq!(taffy.layout(last).unwrap().location.x, 80.0);
+}
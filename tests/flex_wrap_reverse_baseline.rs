@@ -0,0 +1,76 @@
+use taffy::prelude::*;
+
+/// Lays out a wrapping flex row, 100px tall, containing the given items and returns their vertical positions
+fn item_positions(flex_wrap: FlexWrap, align_content: AlignContent, items: &[Style]) -> Vec<f32> {
+    let mut taffy = Taffy::new();
+    let nodes: Vec<NodeId> = items.iter().map(|style| taffy.new_leaf(style.clone()).unwrap()).collect();
+    let root = taffy
+        .new_with_children(
+            Style {
+                flex_wrap,
+                align_content: Some(align_content),
+                align_items: Some(AlignItems::Baseline),
+                size: Size { width: length(50.0), height: length(100.0) },
+                ..Default::default()
+            },
+            &nodes,
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    nodes.iter().map(|node| taffy.layout(*node).unwrap().location.y).collect()
+}
+
+/// An item of the given height, whose baseline is its bottom edge
+fn item(width: f32, height: f32, margin_bottom: f32) -> Style {
+    Style {
+        size: Size { width: length(width), height: length(height) },
+        margin: Rect { left: zero(), right: zero(), top: zero(), bottom: length(margin_bottom) },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn baseline_aligned_items_are_flush_with_the_cross_start_of_the_line() {
+    let items = [item(20.0, 40.0, 0.0), item(20.0, 20.0, 10.0)];
+    assert_eq!(item_positions(FlexWrap::Wrap, AlignContent::Stretch, &items), vec![0.0, 20.0]);
+    // In wrap-reverse the cross-start edge of the line is its bottom edge, including the margins of the items
+    assert_eq!(item_positions(FlexWrap::WrapReverse, AlignContent::Stretch, &items), vec![50.0, 70.0]);
+}
+
+#[test]
+fn wrap_reverse_mirrors_lines_and_align_content() {
+    let items = [item(30.0, 20.0, 0.0), item(30.0, 30.0, 0.0)];
+    assert_eq!(item_positions(FlexWrap::Wrap, AlignContent::FlexStart, &items), vec![0.0, 20.0]);
+    assert_eq!(item_positions(FlexWrap::WrapReverse, AlignContent::FlexStart, &items), vec![80.0, 50.0]);
+    assert_eq!(item_positions(FlexWrap::WrapReverse, AlignContent::Start, &items), vec![30.0, 0.0]);
+}
+
+#[test]
+fn wrap_reverse_container_baseline_comes_from_its_first_line() {
+    let mut taffy = Taffy::new();
+    let lines: Vec<NodeId> =
+        [20.0, 30.0].iter().map(|height| taffy.new_leaf(item(30.0, *height, 0.0)).unwrap()).collect();
+    let container = taffy
+        .new_with_children(
+            Style {
+                flex_wrap: FlexWrap::WrapReverse,
+                align_content: Some(AlignContent::SpaceAround),
+                size: Size { width: length(50.0), height: length(110.0) },
+                ..Default::default()
+            },
+            &lines,
+        )
+        .unwrap();
+    let sibling = taffy.new_leaf(item(10.0, 10.0, 0.0)).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { align_items: Some(AlignItems::Baseline), ..Default::default() },
+            &[container, sibling],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The first line is laid out below the second one, after the space distributed around the lines
+    assert_eq!(taffy.layout(lines[0]).unwrap().location.y, 75.0);
+    assert_eq!(taffy.layout(sibling).unwrap().location.y, 85.0);
+}
@@ -0,0 +1,47 @@
+use taffy::prelude::*;
+use taffy::tree::MeasureFunc;
+
+#[test]
+fn large_numbers_of_flex_items_are_laid_out_in_order() {
+    let mut taffy = Taffy::new();
+    let item_style = Style { size: Size::from_lengths(100.0, 10.0), flex_shrink: 0.0, ..Default::default() };
+    let children: Vec<NodeId> = (0..5_000).map(|_| taffy.new_leaf(item_style.clone()).unwrap()).collect();
+    let root = taffy
+        .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &children)
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    for (index, child) in children.iter().enumerate() {
+        assert_eq!(taffy.layout(*child).unwrap().location.y, index as f32 * 10.0, "child {index}");
+    }
+
+    // Relayout after a change to one of the items moves all of the items that follow it
+    taffy.set_style(children[10], Style { size: Size::from_lengths(100.0, 20.0), ..item_style }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(children[9]).unwrap().location.y, 90.0);
+    assert_eq!(taffy.layout(children[11]).unwrap().location.y, 120.0);
+    assert_eq!(taffy.layout(children[4_999]).unwrap().location.y, 50_000.0);
+    assert_eq!(taffy.layout(root).unwrap().size.height, 50_010.0);
+}
+
+#[test]
+fn unchanged_flex_items_are_not_measured_again() {
+    let mut taffy = Taffy::new();
+    let measure =
+        |known_dimensions: Size<Option<f32>>, _| known_dimensions.unwrap_or(Size { width: 100.0, height: 10.0 });
+    let children: Vec<NodeId> =
+        (0..5_000).map(|_| taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Raw(measure)).unwrap()).collect();
+    let root = taffy
+        .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &children)
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let first = taffy.layout_stats();
+
+    // Only the changed item is measured again: the flex basis and min-content size of the others are served from
+    // their caches, on top of the hits the first layout already had
+    taffy.mark_dirty(children[10]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let second = taffy.layout_stats();
+    assert_eq!(second.measure_calls * children.len(), first.measure_calls);
+    assert_eq!(second.cache_hits, first.cache_hits + 2 * (children.len() - 1));
+}
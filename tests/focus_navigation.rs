@@ -0,0 +1,121 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Overflow;
+use taffy::tree::FocusDirection;
+
+const FOCUSABLE: u32 = 1 << 0;
+
+/// A 3x3 grid of focusable 40x20 buttons with 10 wide gaps, in rows from top to bottom
+fn button_grid() -> (Taffy, NodeId, [[NodeId; 3]; 3]) {
+    let mut taffy = Taffy::new();
+    let button = Style { size: Size::from_lengths(40.0, 20.0), ..Default::default() };
+    let buttons = [(); 3].map(|_| [(); 3].map(|_| taffy.new_leaf(button.clone()).unwrap()));
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                grid_template_columns: vec![length(40.0); 3].into(),
+                gap: length(10.0),
+                ..Default::default()
+            },
+            &buttons.concat(),
+        )
+        .unwrap();
+    for button in buttons.concat() {
+        taffy.set_tags(button, FOCUSABLE).unwrap();
+    }
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, buttons)
+}
+
+#[test]
+fn focus_moves_along_rows_and_columns() {
+    let (taffy, root, buttons) = button_grid();
+    let center = buttons[1][1];
+    let neighbor = |direction| taffy.focus_neighbor(root, center, direction, FOCUSABLE).unwrap();
+
+    assert_eq!(neighbor(FocusDirection::Up), Some(buttons[0][1]));
+    assert_eq!(neighbor(FocusDirection::Down), Some(buttons[2][1]));
+    assert_eq!(neighbor(FocusDirection::Left), Some(buttons[1][0]));
+    assert_eq!(neighbor(FocusDirection::Right), Some(buttons[1][2]));
+}
+
+#[test]
+fn focus_stays_at_the_edges() {
+    let (taffy, root, buttons) = button_grid();
+
+    assert_eq!(taffy.focus_neighbor(root, buttons[0][0], FocusDirection::Up, FOCUSABLE).unwrap(), None);
+    assert_eq!(taffy.focus_neighbor(root, buttons[0][0], FocusDirection::Left, FOCUSABLE).unwrap(), None);
+    assert_eq!(taffy.focus_neighbor(root, buttons[2][2], FocusDirection::Right, FOCUSABLE).unwrap(), None);
+}
+
+#[test]
+fn only_nodes_with_the_tags_are_candidates() {
+    let (mut taffy, root, buttons) = button_grid();
+    taffy.remove_tags(buttons[1][2], FOCUSABLE).unwrap();
+    taffy.set_style(buttons[2][2], Style { display: Display::None, ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The closest remaining nodes to the right are diagonal, and the first of them in tree order wins
+    let right = taffy.focus_neighbor(root, buttons[1][1], FocusDirection::Right, FOCUSABLE).unwrap();
+    assert_eq!(right, Some(buttons[0][2]));
+    // The hidden node below is skipped, and the focused node doesn't need the tags itself
+    let down = taffy.focus_neighbor(root, buttons[1][2], FocusDirection::Down, FOCUSABLE).unwrap();
+    assert_eq!(down, Some(buttons[2][1]));
+}
+
+#[test]
+fn aligned_nodes_are_preferred_over_closer_diagonal_ones() {
+    let mut taffy = Taffy::new();
+    let node = |taffy: &mut Taffy, x: f32, y: f32| {
+        let style = Style {
+            position: Position::Absolute,
+            inset: Rect { left: length(x), top: length(y), right: auto(), bottom: auto() },
+            size: Size::from_lengths(20.0, 20.0),
+            ..Default::default()
+        };
+        let node = taffy.new_leaf(style).unwrap();
+        taffy.set_tags(node, FOCUSABLE).unwrap();
+        node
+    };
+    let current = node(&mut taffy, 0.0, 100.0);
+    let aligned = node(&mut taffy, 100.0, 105.0);
+    let diagonal = node(&mut taffy, 40.0, 160.0);
+    let root = taffy
+        .new_with_children(
+            Style { size: Size::from_lengths(200.0, 200.0), ..Default::default() },
+            &[current, aligned, diagonal],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The diagonal node is 20 away horizontally but 40 away vertically, the aligned one 80 away
+    assert_eq!(taffy.focus_neighbor(root, current, FocusDirection::Right, FOCUSABLE).unwrap(), Some(aligned));
+}
+
+#[test]
+fn focus_follows_scroll_offsets_into_scrolled_out_items() {
+    let mut taffy = Taffy::new();
+    let item = Style { size: Size::from_lengths(50.0, 50.0), flex_shrink: 0.0, ..Default::default() };
+    let items = [(); 4].map(|_| taffy.new_leaf(item.clone()).unwrap());
+    let list = taffy
+        .new_with_children(
+            Style {
+                flex_direction: FlexDirection::Column,
+                size: Size::from_lengths(50.0, 100.0),
+                overflow: Point { x: Overflow::Visible, y: Overflow::Scroll },
+                ..Default::default()
+            },
+            &items,
+        )
+        .unwrap();
+    for item in items {
+        taffy.set_tags(item, FOCUSABLE).unwrap();
+    }
+    taffy.compute_layout(list, Size::MAX_CONTENT).unwrap();
+    taffy.set_scroll_offset(list, Point { x: 0.0, y: 100.0 }).unwrap();
+
+    assert_eq!(taffy.focus_neighbor(list, items[2], FocusDirection::Up, FOCUSABLE).unwrap(), Some(items[1]));
+    assert_eq!(taffy.focus_neighbor(list, items[1], FocusDirection::Up, FOCUSABLE).unwrap(), Some(items[0]));
+    assert_eq!(taffy.focus_neighbor(list, items[3], FocusDirection::Down, FOCUSABLE).unwrap(), None);
+}
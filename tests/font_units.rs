@@ -0,0 +1,60 @@
+use taffy::prelude::*;
+use taffy::TaffyError;
+
+#[test]
+fn rem_units_resolve_against_root_font_size() {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style {
+            size: Size { width: Dimension::Rem(2.0), height: Dimension::Rem(0.5) },
+            padding: Rect { left: LengthPercentage::Rem(1.0), right: zero(), top: zero(), bottom: zero() },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 32.0, height: 8.0 });
+
+    taffy.set_root_font_size(10.0);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 20.0, height: 5.0 });
+}
+
+#[test]
+fn em_units_resolve_against_inherited_font_size() {
+    let mut taffy = Taffy::new();
+    let style = Style { size: Size { width: Dimension::Em(2.0), height: Dimension::Em(1.0) }, ..Default::default() };
+    let grandchild = taffy.new_leaf(style.clone()).unwrap();
+    let child = taffy
+        .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &[grandchild])
+        .unwrap();
+    let sibling = taffy.new_leaf(style).unwrap();
+    let root = taffy
+        .new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &[child, sibling])
+        .unwrap();
+
+    taffy.set_font_size(child, Some(20.0)).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(grandchild).unwrap().size, Size { width: 40.0, height: 20.0 });
+    assert_eq!(taffy.layout(sibling).unwrap().size, Size { width: 32.0, height: 16.0 });
+
+    // Changing an ancestor's font size relays out the nodes that inherit it
+    taffy.set_font_size(child, None).unwrap();
+    taffy.set_font_size(root, Some(8.0)).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(grandchild).unwrap().size, Size { width: 16.0, height: 8.0 });
+    assert_eq!(taffy.layout(sibling).unwrap().size, Size { width: 16.0, height: 8.0 });
+}
+
+#[test]
+fn set_font_size_rejects_removed_nodes() {
+    let mut taffy = Taffy::new();
+    let node = taffy.new_leaf(Style::default()).unwrap();
+    taffy.remove(node).unwrap();
+
+    assert!(
+        matches!(taffy.set_font_size(node, Some(20.0)), Err(TaffyError::InvalidInputNode(removed)) if removed == node)
+    );
+    assert_eq!(taffy.font_size(node), None);
+}
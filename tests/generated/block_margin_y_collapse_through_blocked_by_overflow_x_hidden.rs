@@ -20,7 +20,7 @@ fn block_margin_y_collapse_through_blocked_by_overflow_x_hidden() {
         .new_leaf(taffy::style::Style {
             display: taffy::style::Display::Block,
             overflow: taffy::geometry::Point { x: taffy::style::Overflow::Hidden, y: taffy::style::Overflow::Visible },
-            scrollbar_width: 15f32,
+            scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
             margin: taffy::geometry::Rect {
                 left: zero(),
                 right: zero(),
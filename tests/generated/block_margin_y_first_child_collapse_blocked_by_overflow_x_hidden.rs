@@ -32,7 +32,7 @@ fn block_margin_y_first_child_collapse_blocked_by_overflow_x_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Visible,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 margin: taffy::geometry::Rect {
                     left: zero(),
                     right: zero(),
@@ -23,7 +23,7 @@ fn block_overflow_scrollbars_overriden_by_max_size() {
                     x: taffy::style::Overflow::Scroll,
                     y: taffy::style::Overflow::Scroll,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 max_size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(2f32),
                     height: taffy::style::Dimension::Length(4f32),
@@ -23,7 +23,7 @@ fn block_overflow_scrollbars_take_up_space_both_axis() {
                     x: taffy::style::Overflow::Scroll,
                     y: taffy::style::Overflow::Scroll,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(50f32),
                     height: taffy::style::Dimension::Length(50f32),
@@ -11,7 +11,7 @@ fn blockflex_overflow_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Hidden,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 flex_grow: 1f32,
                 ..Default::default()
             },
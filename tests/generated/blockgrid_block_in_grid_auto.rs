@@ -24,7 +24,7 @@ fn blockgrid_block_in_grid_auto() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_columns: vec![auto()],
+                grid_template_columns: vec![auto()].into(),
                 ..Default::default()
             },
             &[node0, node1],
@@ -24,7 +24,7 @@ fn blockgrid_block_in_grid_fixed_larger() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_columns: vec![length(50f32)],
+                grid_template_columns: vec![length(50f32)].into(),
                 ..Default::default()
             },
             &[node0, node1],
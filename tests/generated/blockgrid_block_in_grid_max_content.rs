@@ -24,7 +24,7 @@ fn blockgrid_block_in_grid_max_content() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_columns: vec![min_content()],
+                grid_template_columns: vec![min_content()].into(),
                 ..Default::default()
             },
             &[node0, node1],
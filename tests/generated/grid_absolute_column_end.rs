@@ -20,8 +20,8 @@ fn grid_absolute_column_end() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)].into(),
                 padding: taffy::geometry::Rect {
                     left: taffy::style::LengthPercentage::Length(40f32),
                     right: taffy::style::LengthPercentage::Length(20f32),
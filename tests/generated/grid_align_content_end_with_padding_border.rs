@@ -17,8 +17,8 @@ fn grid_align_content_end_with_padding_border() {
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
                 align_content: Some(taffy::style::AlignContent::End),
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(200f32),
                     height: taffy::style::Dimension::Length(200f32),
@@ -58,7 +58,7 @@ fn grid_align_items_baseline_child_multiline() {
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
                 flex_wrap: taffy::style::FlexWrap::Wrap,
-                grid_template_columns: vec![auto(), auto()],
+                grid_template_columns: vec![auto(), auto()].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(50f32), height: auto() },
                 ..Default::default()
             },
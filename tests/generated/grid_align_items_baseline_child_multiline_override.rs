@@ -59,7 +59,7 @@ fn grid_align_items_baseline_child_multiline_override() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_columns: vec![auto(), auto()],
+                grid_template_columns: vec![auto(), auto()].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(50f32),
                     height: taffy::style::Dimension::Length(25f32),
@@ -74,7 +74,7 @@ fn grid_align_items_baseline_multiline_column() {
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
                 align_items: Some(taffy::style::AlignItems::Baseline),
-                grid_template_columns: vec![auto(), auto()],
+                grid_template_columns: vec![auto(), auto()].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(100f32),
                     height: taffy::style::Dimension::Length(100f32),
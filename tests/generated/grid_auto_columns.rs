@@ -20,9 +20,9 @@ fn grid_auto_columns() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(100f32)],
-                grid_template_columns: vec![length(40f32)],
-                grid_auto_columns: vec![length(10f32), length(20f32), length(30f32)],
+                grid_template_rows: vec![length(100f32)].into(),
+                grid_template_columns: vec![length(40f32)].into(),
+                grid_auto_columns: vec![length(10f32), length(20f32), length(30f32)].into(),
                 grid_auto_flow: taffy::style::GridAutoFlow::Column,
                 ..Default::default()
             },
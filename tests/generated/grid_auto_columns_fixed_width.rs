@@ -23,8 +23,8 @@ fn grid_auto_columns_fixed_width() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), auto(), length(40f32), auto()],
-                grid_template_columns: vec![length(40f32), auto(), length(40f32), auto()],
+                grid_template_rows: vec![length(40f32), auto(), length(40f32), auto()].into(),
+                grid_template_columns: vec![length(40f32), auto(), length(40f32), auto()].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(200f32),
                     height: taffy::style::Dimension::Length(200f32),
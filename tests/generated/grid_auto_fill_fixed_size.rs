@@ -16,8 +16,9 @@ fn grid_auto_fill_fixed_size() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), repeat(GridTrackRepetition::AutoFill, vec![length(40f32)])],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), repeat(GridTrackRepetition::AutoFill, vec![length(40f32)])]
+                    .into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(120f32),
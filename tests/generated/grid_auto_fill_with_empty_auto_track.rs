@@ -10,8 +10,8 @@ fn grid_auto_fill_with_empty_auto_track() {
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
                 justify_content: Some(taffy::style::JustifyContent::SpaceEvenly),
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![repeat(GridTrackRepetition::AutoFill, vec![length(40f32)])],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![repeat(GridTrackRepetition::AutoFill, vec![length(40f32)])].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(120f32),
@@ -20,9 +20,9 @@ fn grid_auto_rows() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![length(100f32)],
-                grid_auto_rows: vec![length(10f32), length(20f32), length(30f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![length(100f32)].into(),
+                grid_auto_rows: vec![length(10f32), length(20f32), length(30f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7],
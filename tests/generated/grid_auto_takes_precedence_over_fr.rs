@@ -9,8 +9,8 @@ fn grid_auto_takes_precedence_over_fr() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![auto(), fr(1f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![auto(), fr(1f32)].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(200f32),
                     height: taffy::style::Dimension::Length(200f32),
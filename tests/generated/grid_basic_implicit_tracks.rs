@@ -17,8 +17,8 @@ fn grid_basic_implicit_tracks() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![length(40f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![length(40f32)].into(),
                 ..Default::default()
             },
             &[node0, node1],
@@ -22,8 +22,8 @@ fn grid_fit_content_points_argument() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![fit_content(length(30f32))],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![fit_content(length(30f32))].into(),
                 ..Default::default()
             },
             &[node0],
@@ -10,8 +10,8 @@ fn grid_fr_fixed_size_no_content_proportions() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![fr(1f32), fr(2f32), fr(3f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![fr(1f32), fr(2f32), fr(3f32)].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(200f32), height: auto() },
                 ..Default::default()
             },
@@ -9,8 +9,8 @@ fn grid_fr_fixed_size_no_content_proportions_sub_1_sum() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![fr(0.3f32), fr(0.2f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![fr(0.3f32), fr(0.2f32)].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(100f32), height: auto() },
                 ..Default::default()
             },
@@ -21,8 +21,8 @@ fn grid_fr_fixed_size_single_item() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), fr(1f32), fr(1f32)],
-                grid_template_columns: vec![length(40f32), fr(1f32), fr(1f32)],
+                grid_template_rows: vec![length(40f32), fr(1f32), fr(1f32)].into(),
+                grid_template_columns: vec![length(40f32), fr(1f32), fr(1f32)].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(200f32),
                     height: taffy::style::Dimension::Length(200f32),
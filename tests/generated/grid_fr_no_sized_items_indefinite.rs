@@ -16,8 +16,8 @@ fn grid_fr_no_sized_items_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), fr(1f32), fr(1f32)],
-                grid_template_columns: vec![length(40f32), fr(1f32), fr(1f32)],
+                grid_template_rows: vec![length(40f32), fr(1f32), fr(1f32)].into(),
+                grid_template_columns: vec![length(40f32), fr(1f32), fr(1f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7, node8],
@@ -19,8 +19,8 @@ fn grid_fr_span_2_proportion_zero_sum() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
-                grid_template_columns: vec![fr(0f32), fr(0f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![fr(0f32), fr(0f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2],
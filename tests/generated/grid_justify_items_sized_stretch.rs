@@ -30,8 +30,8 @@ fn grid_justify_items_sized_stretch() {
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
                 justify_items: Some(taffy::style::JustifyItems::Stretch),
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(120f32),
@@ -29,8 +29,8 @@ fn grid_margins_percent_center() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(20f32), length(20f32), length(20f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(20f32), length(20f32), length(20f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5],
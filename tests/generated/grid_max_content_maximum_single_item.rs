@@ -30,8 +30,8 @@ fn grid_max_content_maximum_single_item() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), minmax(length(0f32), max_content()), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), minmax(length(0f32), max_content()), length(40f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7, node8],
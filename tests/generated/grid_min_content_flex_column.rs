@@ -62,8 +62,8 @@ fn grid_min_content_flex_column() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![min_content()],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![min_content()].into(),
                 ..Default::default()
             },
             &[node0],
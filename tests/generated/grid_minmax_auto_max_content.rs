@@ -22,8 +22,8 @@ fn grid_minmax_auto_max_content() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![minmax(auto(), max_content())],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![minmax(auto(), max_content())].into(),
                 ..Default::default()
             },
             &[node0],
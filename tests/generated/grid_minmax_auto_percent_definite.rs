@@ -22,8 +22,8 @@ fn grid_minmax_auto_percent_definite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![minmax(auto(), percent(0.2f32))],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![minmax(auto(), percent(0.2f32))].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(100f32), height: auto() },
                 ..Default::default()
             },
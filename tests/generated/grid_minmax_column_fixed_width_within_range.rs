@@ -16,8 +16,8 @@ fn grid_minmax_column_fixed_width_within_range() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), minmax(length(20f32), length(40f32)), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), minmax(length(20f32), length(40f32)), length(40f32)].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(110f32), height: auto() },
                 ..Default::default()
             },
@@ -9,8 +9,8 @@ fn grid_minmax_column_with_fr_fixed() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![minmax(length(20f32), length(40f32)), fr(1f32)],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![minmax(length(20f32), length(40f32)), fr(1f32)].into(),
                 size: taffy::geometry::Size { width: taffy::style::Dimension::Length(60f32), height: auto() },
                 ..Default::default()
             },
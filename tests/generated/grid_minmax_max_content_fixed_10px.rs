@@ -22,8 +22,8 @@ fn grid_minmax_max_content_fixed_10px() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![minmax(max_content(), length(10f32))],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![minmax(max_content(), length(10f32))].into(),
                 ..Default::default()
             },
             &[node0],
@@ -44,8 +44,8 @@ fn grid_out_of_order_items() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)],
-                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32), length(40f32)].into(),
+                grid_template_columns: vec![length(40f32), length(40f32), length(40f32)].into(),
                 grid_auto_flow: taffy::style::GridAutoFlow::RowDense,
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
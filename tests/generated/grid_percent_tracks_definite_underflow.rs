@@ -13,8 +13,8 @@ fn grid_percent_tracks_definite_underflow() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![percent(0.3f32), percent(0.6f32)],
-                grid_template_columns: vec![percent(0.1f32), percent(0.2f32), percent(0.3f32)],
+                grid_template_rows: vec![percent(0.3f32), percent(0.6f32)].into(),
+                grid_template_columns: vec![percent(0.1f32), percent(0.2f32), percent(0.3f32)].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(60f32),
@@ -30,8 +30,8 @@ fn grid_percent_tracks_indefinite_with_content_overflow() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![percent(0.5f32), percent(0.8f32)],
-                grid_template_columns: vec![percent(0.4f32), percent(0.4f32), percent(0.4f32)],
+                grid_template_rows: vec![percent(0.5f32), percent(0.8f32)].into(),
+                grid_template_columns: vec![percent(0.4f32), percent(0.4f32), percent(0.4f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6],
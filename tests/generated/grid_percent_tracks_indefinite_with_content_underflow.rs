@@ -30,8 +30,8 @@ fn grid_percent_tracks_indefinite_with_content_underflow() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![percent(0.3f32), percent(0.6f32)],
-                grid_template_columns: vec![percent(0.1f32), percent(0.2f32), percent(0.3f32)],
+                grid_template_rows: vec![percent(0.3f32), percent(0.6f32)].into(),
+                grid_template_columns: vec![percent(0.1f32), percent(0.2f32), percent(0.3f32)].into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6],
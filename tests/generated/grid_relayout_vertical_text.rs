@@ -37,8 +37,8 @@ fn grid_relayout_vertical_text() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![min_content()],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![min_content()].into(),
                 ..Default::default()
             },
             &[node0, node1],
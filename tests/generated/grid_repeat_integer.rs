@@ -16,8 +16,8 @@ fn grid_repeat_integer() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![repeat(GridTrackRepetition::Count(3u16), vec![length(40f32)])],
-                grid_template_columns: vec![repeat(GridTrackRepetition::Count(3u16), vec![length(40f32)])],
+                grid_template_rows: vec![repeat(GridTrackRepetition::Count(3u16), vec![length(40f32)])].into(),
+                grid_template_columns: vec![repeat(GridTrackRepetition::Count(3u16), vec![length(40f32)])].into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(120f32),
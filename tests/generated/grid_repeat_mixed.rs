@@ -20,12 +20,14 @@ fn grid_repeat_mixed() {
                     length(40f32),
                     repeat(GridTrackRepetition::Count(1u16), vec![length(40f32)]),
                     repeat(GridTrackRepetition::AutoFill, vec![length(40f32)]),
-                ],
+                ]
+                .into(),
                 grid_template_columns: vec![
                     length(40f32),
                     repeat(GridTrackRepetition::Count(1u16), vec![length(40f32)]),
                     repeat(GridTrackRepetition::AutoFill, vec![length(40f32)]),
-                ],
+                ]
+                .into(),
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(120f32),
                     height: taffy::style::Dimension::Length(120f32),
@@ -38,7 +38,7 @@ fn grid_span_13_most_non_flex_with_minmax_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
                 grid_template_columns: vec![
                     min_content(),
                     max_content(),
@@ -53,7 +53,8 @@ fn grid_span_13_most_non_flex_with_minmax_indefinite() {
                     minmax(min_content(), max_content()),
                     minmax(min_content(), auto()),
                     minmax(max_content(), auto()),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7, node8, node9, node10, node11, node12, node13],
@@ -10,7 +10,7 @@ fn grid_span_13_most_non_flex_with_minmax_indefinite_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Hidden,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 grid_column: taffy::geometry::Line { start: line(1i16), end: taffy::style::GridPlacement::Span(13u16) },
                 ..Default::default()
             },
@@ -43,7 +43,7 @@ fn grid_span_13_most_non_flex_with_minmax_indefinite_hidden() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
                 grid_template_columns: vec![
                     min_content(),
                     max_content(),
@@ -58,7 +58,8 @@ fn grid_span_13_most_non_flex_with_minmax_indefinite_hidden() {
                     minmax(min_content(), max_content()),
                     minmax(min_content(), auto()),
                     minmax(max_content(), auto()),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7, node8, node9, node10, node11, node12, node13],
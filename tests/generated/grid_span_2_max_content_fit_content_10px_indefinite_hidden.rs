@@ -24,7 +24,7 @@ fn grid_span_2_max_content_fit_content_10px_indefinite_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Hidden,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 grid_row: taffy::geometry::Line { start: line(1i16), end: taffy::style::GridPlacement::Auto },
                 grid_column: taffy::geometry::Line { start: line(1i16), end: taffy::style::GridPlacement::Span(2u16) },
                 ..Default::default()
@@ -45,8 +45,8 @@ fn grid_span_2_max_content_fit_content_10px_indefinite_hidden() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![max_content(), fit_content(length(10f32))],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![max_content(), fit_content(length(10f32))].into(),
                 ..Default::default()
             },
             &[node0, node1, node2],
@@ -40,8 +40,8 @@ fn grid_span_2_max_content_max_content_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![max_content(), max_content()],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![max_content(), max_content()].into(),
                 ..Default::default()
             },
             &[node0, node1, node2],
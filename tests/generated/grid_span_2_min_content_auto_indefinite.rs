@@ -27,8 +27,8 @@ fn grid_span_2_min_content_auto_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![min_content(), auto()],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![min_content(), auto()].into(),
                 ..Default::default()
             },
             &[node0, node1, node2],
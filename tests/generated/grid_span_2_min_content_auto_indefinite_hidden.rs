@@ -12,7 +12,7 @@ fn grid_span_2_min_content_auto_indefinite_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Hidden,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 grid_column: taffy::geometry::Line { start: line(1i16), end: taffy::style::GridPlacement::Span(2u16) },
                 ..Default::default()
             },
@@ -32,8 +32,8 @@ fn grid_span_2_min_content_auto_indefinite_hidden() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32)],
-                grid_template_columns: vec![min_content(), auto()],
+                grid_template_rows: vec![length(40f32)].into(),
+                grid_template_columns: vec![min_content(), auto()].into(),
                 ..Default::default()
             },
             &[node0, node1, node2],
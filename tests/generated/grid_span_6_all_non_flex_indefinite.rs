@@ -31,7 +31,7 @@ fn grid_span_6_all_non_flex_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
                 grid_template_columns: vec![
                     min_content(),
                     max_content(),
@@ -39,7 +39,8 @@ fn grid_span_6_all_non_flex_indefinite() {
                     auto(),
                     length(10f32),
                     percent(0.2f32),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6],
@@ -10,7 +10,7 @@ fn grid_span_6_all_non_flex_indefinite_hidden() {
                     x: taffy::style::Overflow::Hidden,
                     y: taffy::style::Overflow::Hidden,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 grid_column: taffy::geometry::Line { start: line(1i16), end: taffy::style::GridPlacement::Span(6u16) },
                 ..Default::default()
             },
@@ -36,7 +36,7 @@ fn grid_span_6_all_non_flex_indefinite_hidden() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
                 grid_template_columns: vec![
                     min_content(),
                     max_content(),
@@ -44,7 +44,8 @@ fn grid_span_6_all_non_flex_indefinite_hidden() {
                     auto(),
                     length(10f32),
                     percent(0.2f32),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6],
@@ -33,7 +33,7 @@ fn grid_span_8_all_track_types_indefinite() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(40f32), length(40f32)],
+                grid_template_rows: vec![length(40f32), length(40f32)].into(),
                 grid_template_columns: vec![
                     min_content(),
                     max_content(),
@@ -43,7 +43,8 @@ fn grid_span_8_all_track_types_indefinite() {
                     percent(0.2f32),
                     fr(1f32),
                     fr(2f32),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
             &[node0, node1, node2, node3, node4, node5, node6, node7, node8],
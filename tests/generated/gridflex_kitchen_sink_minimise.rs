@@ -14,8 +14,8 @@ fn gridflex_kitchen_sink_minimise() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![length(20f32)],
-                grid_template_columns: vec![fr(1f32), fr(1f32)],
+                grid_template_rows: vec![length(20f32)].into(),
+                grid_template_columns: vec![fr(1f32), fr(1f32)].into(),
                 ..Default::default()
             },
             &[node00, node01],
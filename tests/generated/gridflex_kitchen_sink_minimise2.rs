@@ -16,8 +16,8 @@ fn gridflex_kitchen_sink_minimise2() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![auto()],
-                grid_template_columns: vec![auto()],
+                grid_template_rows: vec![auto()].into(),
+                grid_template_columns: vec![auto()].into(),
                 ..Default::default()
             },
             &[node00],
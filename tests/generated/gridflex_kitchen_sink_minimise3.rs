@@ -19,8 +19,8 @@ fn gridflex_kitchen_sink_minimise3() {
         .new_with_children(
             taffy::style::Style {
                 display: taffy::style::Display::Grid,
-                grid_template_rows: vec![percent(0.3f32), percent(0.1f32)],
-                grid_template_columns: vec![auto(), percent(0.1f32)],
+                grid_template_rows: vec![percent(0.3f32), percent(0.1f32)].into(),
+                grid_template_columns: vec![auto(), percent(0.1f32)].into(),
                 ..Default::default()
             },
             &[node00, node01, node02, node03],
@@ -10,7 +10,7 @@ fn leaf_overflow_scrollbars_affect_available_space_y_axis() {
                     x: taffy::style::Overflow::Visible,
                     y: taffy::style::Overflow::Scroll,
                 },
-                scrollbar_width: 15f32,
+                scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
                 size: taffy::geometry::Size {
                     width: taffy::style::Dimension::Length(45f32),
                     height: taffy::style::Dimension::Length(45f32),
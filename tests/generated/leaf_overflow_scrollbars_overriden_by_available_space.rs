@@ -6,7 +6,7 @@ fn leaf_overflow_scrollbars_overriden_by_available_space() {
     let node0 = taffy
         .new_leaf(taffy::style::Style {
             overflow: taffy::geometry::Point { x: taffy::style::Overflow::Scroll, y: taffy::style::Overflow::Scroll },
-            scrollbar_width: 15f32,
+            scrollbar_width: taffy::geometry::Size { width: 15f32, height: 15f32 },
             flex_grow: 1f32,
             ..Default::default()
         })
@@ -0,0 +1,23 @@
+use taffy::geometry::{Point, Rect, Size};
+
+#[test]
+fn sizes_and_points_convert_to_and_from_pairs() {
+    let size = Size { width: 3.0, height: 4.0 };
+    assert_eq!(Size::from((3.0, 4.0)), size);
+    assert_eq!(Size::from([3.0, 4.0]), size);
+    assert_eq!(<(f32, f32)>::from(size), (3.0, 4.0));
+    assert_eq!(<[f32; 2]>::from(size), [3.0, 4.0]);
+
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(Point::from((1, 2)), point);
+    assert_eq!(Point::from([1, 2]), point);
+    let (x, y): (i32, i32) = point.into();
+    assert_eq!((x, y), (1, 2));
+    let array: [i32; 2] = point.into();
+    assert_eq!(array, [1, 2]);
+}
+
+#[test]
+fn rects_are_created_from_arrays_in_field_order() {
+    assert_eq!(Rect::from([1.0, 2.0, 3.0, 4.0]), Rect { left: 1.0, right: 2.0, top: 3.0, bottom: 4.0 });
+}
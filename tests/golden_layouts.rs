@@ -0,0 +1,75 @@
+#![cfg(feature = "testing")]
+use std::panic::AssertUnwindSafe;
+
+use taffy::prelude::*;
+use taffy::testing::{assert_golden_layouts, GoldenDifference, GoldenLayouts, GoldenParseErrorKind};
+
+/// A 200x100 row containing a fixed width sidebar and a content node which takes up the remaining space
+fn sidebar_layout(sidebar_width: f32) -> (Taffy, NodeId) {
+    let mut taffy = Taffy::new();
+    let sidebar = taffy
+        .new_leaf(Style { size: Size { width: length(sidebar_width), height: auto() }, ..Default::default() })
+        .unwrap();
+    taffy.set_key(sidebar, Some("sidebar")).unwrap();
+    let content = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(200.0, 100.0), ..Default::default() }, &[sidebar, content])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root)
+}
+
+#[test]
+fn golden_layouts_round_trip_through_text() {
+    let (taffy, root) = sidebar_layout(50.0);
+    let golden = GoldenLayouts::record(&taffy, root).unwrap();
+    let text = golden.to_string();
+    assert_eq!(text, "tolerance 0.01\n/ 0 0 200 100\n/0 0 0 50 100 # sidebar\n/1 50 0 150 100\n");
+    assert_eq!(GoldenLayouts::parse(&text).unwrap(), golden);
+}
+
+#[test]
+fn golden_layouts_are_compared_with_per_node_tolerances() {
+    let golden = GoldenLayouts::parse("/ 0 0 200 100\n/0 0 0 50 100 # sidebar\n/1 50 0 150 100 ~2\n").unwrap();
+    let (taffy, root) = sidebar_layout(51.0);
+    let mismatch = golden.compare(&GoldenLayouts::record(&taffy, root).unwrap()).unwrap_err();
+
+    // The content node is within its own tolerance, but the sidebar isn't
+    assert_eq!(mismatch.differences.len(), 1);
+    assert!(matches!(&mismatch.differences[0], GoldenDifference::Changed { expected, .. } if expected.path == [0]));
+    assert_eq!(
+        mismatch.to_string(),
+        "1 of 3 nodes differ from the golden layouts:\n  /0 (sidebar)\n    - 0 0 50 100\n    + 0 0 51 100\n"
+    );
+
+    let golden = GoldenLayouts::parse("/ 0 0 200 100\n/0 0 0 50 100\n/2 0 0 0 0\n").unwrap();
+    let mismatch = golden.compare(&GoldenLayouts::record(&taffy, root).unwrap()).unwrap_err();
+    assert!(mismatch.to_string().ends_with("  /2: missing from the tree\n  /1: not in the golden layouts\n"));
+}
+
+#[test]
+fn invalid_golden_layouts_are_rejected() {
+    let error = GoldenLayouts::parse("tolerance 0.5\n\n/0 0 0 fifty 100\n").unwrap_err();
+    assert_eq!((error.line, error.kind), (3, GoldenParseErrorKind::InvalidNumber));
+    assert_eq!(GoldenLayouts::parse("/0 0 0 50\n").unwrap_err().kind, GoldenParseErrorKind::MissingValue);
+    assert_eq!(GoldenLayouts::parse("0 0 0 50 100\n").unwrap_err().kind, GoldenParseErrorKind::InvalidPath);
+    assert_eq!(GoldenLayouts::parse("/0 0 0 50 100 ~-1\n").unwrap_err().kind, GoldenParseErrorKind::InvalidTolerance);
+}
+
+#[test]
+fn golden_files_are_recorded_and_then_asserted_against() {
+    let path = std::env::temp_dir().join(format!("taffy-golden-{}", std::process::id())).join("sidebar.golden");
+    let _ = std::fs::remove_file(&path);
+
+    let (taffy, root) = sidebar_layout(50.0);
+    assert_golden_layouts(&taffy, root, &path);
+    assert!(std::fs::read_to_string(&path).unwrap().contains("/0 0 0 50 100 # sidebar"));
+    assert_golden_layouts(&taffy, root, &path);
+
+    let (taffy, root) = sidebar_layout(60.0);
+    let panic = std::panic::catch_unwind(AssertUnwindSafe(|| assert_golden_layouts(&taffy, root, &path))).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("- 0 0 50 100\n    + 0 0 60 100"), "{message}");
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
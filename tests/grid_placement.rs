@@ -0,0 +1,70 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+
+/// Lays out a grid of 10px tracks with the given auto flow and `track_count` explicit tracks in the primary axis,
+/// containing a definitely placed item followed by `item_count` auto-placed items
+fn auto_flow_grid(grid_auto_flow: GridAutoFlow, track_count: usize, item_count: usize) -> (Taffy, Vec<NodeId>) {
+    let mut taffy = Taffy::new();
+    let tracks: Vec<TrackSizingFunction> = (0..track_count).map(|_| length(10.0)).collect();
+    let is_row_flow = grid_auto_flow.primary_axis() == taffy::geometry::AbsoluteAxis::Horizontal;
+
+    // A 2x2 item in the middle of the first couple of tracks, which auto-placed items have to flow around
+    let mut definite_style = Style::default();
+    if is_row_flow {
+        definite_style.grid_row = Line { start: line(1), end: span(2) };
+        definite_style.grid_column = Line { start: line(3), end: span(2) };
+    } else {
+        definite_style.grid_column = Line { start: line(1), end: span(2) };
+        definite_style.grid_row = Line { start: line(3), end: span(2) };
+    }
+    let mut children = vec![taffy.new_leaf(definite_style).unwrap()];
+    children.extend((0..item_count).map(|_| taffy.new_leaf(Style::default()).unwrap()));
+
+    let mut style = Style {
+        display: Display::Grid,
+        grid_auto_flow,
+        grid_auto_rows: vec![length(10.0)].into(),
+        grid_auto_columns: vec![length(10.0)].into(),
+        ..Default::default()
+    };
+    if is_row_flow {
+        style.grid_template_columns = tracks.into();
+    } else {
+        style.grid_template_rows = tracks.into();
+    }
+    let root = taffy.new_with_children(style, &children).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, children)
+}
+
+#[test]
+fn large_numbers_of_auto_placed_items_flow_around_definite_items() {
+    let (taffy, children) = auto_flow_grid(GridAutoFlow::Row, 10, 5_000);
+
+    assert_eq!(taffy.layout(children[0]).unwrap().location, Point { x: 20.0, y: 0.0 });
+    let locations: Vec<Point<f32>> = children[1..7].iter().map(|item| taffy.layout(*item).unwrap().location).collect();
+    assert_eq!(
+        locations,
+        vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 40.0, y: 0.0 },
+            Point { x: 50.0, y: 0.0 },
+            Point { x: 60.0, y: 0.0 },
+            Point { x: 70.0, y: 0.0 },
+        ]
+    );
+    // The definite item takes up 4 cells, which pushes the last items into an extra row
+    assert_eq!(taffy.layout(*children.last().unwrap()).unwrap().location, Point { x: 30.0, y: 5_000.0 });
+}
+
+#[test]
+fn large_numbers_of_items_can_be_auto_placed_in_columns() {
+    for grid_auto_flow in [GridAutoFlow::Column, GridAutoFlow::ColumnDense] {
+        let (taffy, children) = auto_flow_grid(grid_auto_flow, 10, 5_000);
+
+        assert_eq!(taffy.layout(children[3]).unwrap().location, Point { x: 0.0, y: 40.0 }, "{grid_auto_flow:?}");
+        let last = taffy.layout(*children.last().unwrap()).unwrap().location;
+        assert_eq!(last, Point { x: 5_000.0, y: 30.0 }, "{grid_auto_flow:?}");
+    }
+}
@@ -0,0 +1,20 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+
+#[test]
+fn layout_edges_and_bounds_are_derived_from_location_and_size() {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(30.0, 20.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style { padding: length(10.0), ..Default::default() }, &[child]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let layout = taffy.layout(child).unwrap();
+    assert_eq!((layout.right(), layout.bottom()), (40.0, 30.0));
+    assert_eq!(layout.center(), Point { x: 25.0, y: 20.0 });
+    assert_eq!(layout.rect(), Rect { left: 10.0, right: 40.0, top: 10.0, bottom: 30.0 });
+
+    assert!(layout.contains(Point { x: 10.0, y: 10.0 }));
+    assert!(layout.contains(Point { x: 39.9, y: 29.9 }));
+    assert!(!layout.contains(Point { x: 40.0, y: 20.0 }));
+    assert!(!layout.contains(Point { x: 5.0, y: 20.0 }));
+}
@@ -0,0 +1,35 @@
+use taffy::prelude::*;
+
+#[test]
+fn layouts_are_exported_as_json() {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style { size: Size { width: length(30.0), height: percent(0.5) }, ..Default::default() })
+        .unwrap();
+    taffy.set_key(child, Some("a \"child\"")).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { size: Size { width: length(100.0), height: length(40.0) }, ..Default::default() },
+            &[child],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&taffy.layout_to_json(root)).unwrap();
+    assert_eq!(json["root"], u64::from(root));
+
+    let nodes = json["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0]["id"], u64::from(root));
+    assert_eq!(nodes[0]["label"], serde_json::Value::Null);
+    assert_eq!(nodes[0]["parent"], serde_json::Value::Null);
+    assert_eq!(nodes[0]["style"]["display"], "flex");
+    assert_eq!(nodes[0]["style"]["width"], "100px");
+    assert_eq!(nodes[0]["layout"]["width"], 100.0);
+
+    assert_eq!(nodes[1]["id"], u64::from(child));
+    assert_eq!(nodes[1]["label"], "a \"child\"");
+    assert_eq!(nodes[1]["parent"], u64::from(root));
+    assert_eq!(nodes[1]["style"]["height"], "50%");
+    assert_eq!(nodes[1]["layout"]["height"], 20.0);
+}
@@ -0,0 +1,81 @@
+use taffy::prelude::*;
+use taffy::tree::{LayoutTrace, MeasureFunc};
+
+/// Builds a tree containing a text node that wraps to fit its available width, and records its layout
+fn record_trace() -> LayoutTrace {
+    let mut taffy = Taffy::new();
+    let text = taffy
+        .new_leaf_with_measure(
+            Style::default(),
+            MeasureFunc::Raw(|known_dimensions, available_space| {
+                let width = known_dimensions.width.unwrap_or(match available_space.width {
+                    AvailableSpace::Definite(width) => width.min(120.0),
+                    AvailableSpace::MinContent => 30.0,
+                    AvailableSpace::MaxContent => 120.0,
+                });
+                Size { width, height: known_dimensions.height.unwrap_or((120.0 / width).ceil() * 10.0) }
+            }),
+        )
+        .unwrap();
+    let sidebar =
+        taffy.new_leaf(Style { size: Size { width: length(20.0), height: auto() }, ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { size: Size { width: percent(0.5), height: auto() }, ..Default::default() },
+            &[sidebar, text],
+        )
+        .unwrap();
+
+    // The tree is laid out beforehand, so that the recording can't rely on measurements being made afresh
+    let available_space = Size { width: AvailableSpace::Definite(160.0), height: AvailableSpace::MaxContent };
+    taffy.compute_layout(root, available_space).unwrap();
+    taffy.record_layout(root, available_space).unwrap()
+}
+
+#[test]
+fn trace_records_the_tree_and_measurements() {
+    let trace = record_trace();
+
+    assert_eq!(trace.nodes.len(), 3);
+    assert_eq!(trace.nodes[trace.root].children, vec![1, 2]);
+    assert_eq!(trace.nodes[trace.root].layout.size.width, 80.0);
+    assert!(trace.nodes[1].measurements.is_empty());
+    assert!(trace.nodes[2].needs_measure);
+    assert!(!trace.nodes[2].measurements.is_empty());
+}
+
+#[test]
+fn replaying_a_trace_reproduces_its_layouts() {
+    let trace = record_trace();
+    assert_eq!(trace.replay().unwrap(), Vec::<usize>::new());
+
+    let (taffy, nodes) = trace.build().unwrap();
+    assert_eq!(taffy.children(nodes[0]).unwrap(), vec![nodes[1], nodes[2]]);
+}
+
+#[test]
+fn replaying_a_trace_reports_differing_layouts() {
+    let mut trace = record_trace();
+    trace.nodes[2].layout.size.height += 1.0;
+    assert_eq!(trace.replay().unwrap(), vec![2]);
+}
+
+#[test]
+fn minimized_traces_can_be_replayed() {
+    let mut trace = record_trace();
+    trace.nodes[0].children = vec![2];
+    trace.nodes[0].layout.size.width = 80.0;
+    let (mut taffy, nodes) = trace.build().unwrap();
+    taffy.compute_layout(nodes[0], trace.available_space).unwrap();
+    assert_eq!(taffy.layout(nodes[2]).unwrap().size.width, 80.0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn traces_can_be_saved_and_loaded() {
+    let trace = record_trace();
+    let json = serde_json::to_string(&trace).unwrap();
+    let loaded: LayoutTrace = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, trace);
+    assert_eq!(loaded.replay().unwrap(), Vec::<usize>::new());
+}
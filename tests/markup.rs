@@ -0,0 +1,119 @@
+#![cfg(feature = "markup")]
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::tree::markup::MarkupErrorKind;
+
+#[test]
+fn markup_builds_a_named_tree() {
+    let mut taffy = Taffy::new();
+    let tree = taffy
+        .load_markup(
+            r#"
+            <!-- A sidebar layout -->
+            <div name="root" style="display: flex; width: 200px; height: 100; padding: 10px 0">
+              <div name="sidebar" style="width: 25%; flex-shrink: 0" />
+              <div name="content" style="flex-grow: 1; margin: 0 5px">
+                <span class="ignored" />
+              </div>
+            </div>
+            "#,
+        )
+        .unwrap();
+
+    let root = tree.root;
+    let sidebar = tree.names["sidebar"];
+    let content = tree.names["content"];
+    assert_eq!(tree.names["root"], root);
+    assert_eq!(taffy.children(root).unwrap(), vec![sidebar, content]);
+    assert_eq!(taffy.child_count(content).unwrap(), 1);
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(sidebar).unwrap().size, Size { width: 50.0, height: 80.0 });
+    assert_eq!(taffy.layout(content).unwrap().location, Point { x: 55.0, y: 10.0 });
+    assert_eq!(taffy.layout(content).unwrap().size.width, 140.0);
+}
+
+#[test]
+fn markup_supports_grid_properties() {
+    let mut taffy = Taffy::new();
+    let tree = taffy
+        .load_markup(
+            r#"<grid style="display: grid; grid-template-columns: 10px 1fr; width: 100px">
+                 <cell name="wide" style="grid-column: 1 / span 2" />
+               </grid>"#,
+        )
+        .unwrap();
+
+    taffy.compute_layout(tree.root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(tree.names["wide"]).unwrap().size.width, 100.0);
+}
+
+#[test]
+fn markup_supports_place_shorthands() {
+    let mut taffy = Taffy::new();
+    let tree = taffy
+        .load_markup(
+            r#"<grid style="display: grid; place-items: end center; place-content: space-between start">
+                 <cell name="cell" style="place-self: stretch" />
+               </grid>"#,
+        )
+        .unwrap();
+
+    let style = taffy.style(tree.root).unwrap();
+    assert_eq!((style.align_items, style.justify_items), (Some(AlignItems::End), Some(AlignItems::Center)));
+    assert_eq!(
+        (style.align_content, style.justify_content),
+        (Some(AlignContent::SpaceBetween), Some(AlignContent::Start))
+    );
+    let style = taffy.style(tree.names["cell"]).unwrap();
+    assert_eq!((style.align_self, style.justify_self), (Some(AlignSelf::Stretch), Some(AlignSelf::Stretch)));
+}
+
+#[test]
+fn markup_supports_per_axis_scrollbar_widths() {
+    let mut taffy = Taffy::new();
+    let tree = taffy
+        .load_markup(
+            r#"<div style="overflow: scroll; scrollbar-width: 10px 4px">
+                 <div name="uniform" style="scrollbar-width: 8" />
+               </div>"#,
+        )
+        .unwrap();
+
+    assert_eq!(taffy.style(tree.root).unwrap().scrollbar_width, Size { width: 10.0, height: 4.0 });
+    assert_eq!(taffy.style(tree.names["uniform"]).unwrap().scrollbar_width, Size { width: 8.0, height: 8.0 });
+}
+
+#[test]
+fn invalid_markup_is_reported() {
+    let mut taffy = Taffy::new();
+    let error = taffy.load_markup("<a>\n  <b style=\"width: wide\" />\n</a>").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(
+        error.kind,
+        MarkupErrorKind::InvalidValue { property: String::from("width"), value: String::from("wide") }
+    );
+
+    let error = taffy.load_markup("<a><b></c></a>").unwrap_err();
+    assert!(matches!(error.kind, MarkupErrorKind::MismatchedClosingTag { .. }));
+    let error = taffy.load_markup("<a style=\"colour: red\" />").unwrap_err();
+    assert_eq!(error.kind, MarkupErrorKind::UnknownProperty(String::from("colour")));
+    let error = taffy.load_markup("<a name=\"x\"><b name=\"x\" /></a>").unwrap_err();
+    assert_eq!(error.kind, MarkupErrorKind::DuplicateName(String::from("x")));
+    let error = taffy.load_markup("<a /><b />").unwrap_err();
+    assert_eq!(error.kind, MarkupErrorKind::ExpectedSingleRoot);
+}
+
+#[test]
+fn deeply_nested_markup_is_loaded() {
+    let depth = 20_000;
+    let markup = format!("{}<b name=\"leaf\" />{}", "<a>".repeat(depth), "</a>".repeat(depth));
+    let mut taffy = Taffy::new();
+    let tree = taffy.load_markup(&markup).unwrap();
+
+    let mut node = tree.root;
+    for _ in 0..depth {
+        node = taffy.child_at_index(node, 0).unwrap();
+    }
+    assert_eq!(node, tree.names["leaf"]);
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod measure {
+    use taffy::geometry::Point;
     use taffy::prelude::*;
     use taffy::tree::MeasureFunc;
 
@@ -446,4 +447,129 @@ mod measure {
         assert_eq!(taffy.layout(child).unwrap().size.width, 100.0);
         assert_eq!(taffy.layout(child).unwrap().size.height, 100.0);
     }
+
+    #[test]
+    fn measure_container_combines_with_children() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style { size: Size::from_lengths(50.0, 50.0), ..Default::default() }).unwrap();
+        let container = taffy.new_with_children(Style::default(), &[child]).unwrap();
+        taffy
+            .set_measure(
+                container,
+                Some(MeasureFunc::Raw(|known_dimensions, _available_space| Size {
+                    width: known_dimensions.width.unwrap_or(100.0),
+                    height: known_dimensions.height.unwrap_or(20.0),
+                })),
+            )
+            .unwrap();
+        let node = taffy.new_with_children(Style::default(), &[container]).unwrap();
+
+        taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+        assert_eq!(taffy.layout(container).unwrap().size.width, 100.0);
+        assert_eq!(taffy.layout(container).unwrap().size.height, 50.0);
+        assert_eq!(taffy.layout(child).unwrap().size.width, 50.0);
+    }
+
+    #[test]
+    fn measure_container_aligns_children_within_measured_size() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+        let positioned = taffy
+            .new_leaf(Style {
+                position: Position::Absolute,
+                inset: Rect { left: percent(0.5), top: percent(0.5), right: auto(), bottom: auto() },
+                size: Size::from_lengths(10.0, 10.0),
+                ..Default::default()
+            })
+            .unwrap();
+        let container = taffy
+            .new_with_children(
+                Style {
+                    justify_content: Some(JustifyContent::Center),
+                    align_items: Some(AlignItems::Center),
+                    ..Default::default()
+                },
+                &[child, positioned],
+            )
+            .unwrap();
+        taffy
+            .set_measure(
+                container,
+                Some(MeasureFunc::Raw(|known_dimensions, _available_space| Size {
+                    width: known_dimensions.width.unwrap_or(100.0),
+                    height: known_dimensions.height.unwrap_or(80.0),
+                })),
+            )
+            .unwrap();
+
+        taffy.compute_layout(container, Size::MAX_CONTENT).unwrap();
+
+        // The children are aligned and positioned within the measured size rather than the size of the children
+        assert_eq!(taffy.layout(container).unwrap().size, Size { width: 100.0, height: 80.0 });
+        assert_eq!(taffy.layout(child).unwrap().location, Point { x: 40.0, y: 30.0 });
+        assert_eq!(taffy.layout(positioned).unwrap().location, Point { x: 50.0, y: 40.0 });
+    }
+
+    #[test]
+    fn compute_layout_with_measure_measures_flagged_leaves() {
+        let mut taffy = Taffy::new();
+        let text = taffy.new_leaf(Style::default()).unwrap();
+        taffy.set_needs_measure(text, true).unwrap();
+        let image = taffy
+            .new_leaf_with_measure(Style::default(), MeasureFunc::Raw(|_, _| Size { width: 30.0, height: 30.0 }))
+            .unwrap();
+        let spacer = taffy.new_leaf(Style::default()).unwrap();
+        let node = taffy.new_with_children(Style::default(), &[text, image, spacer]).unwrap();
+
+        let mut measured_nodes = Vec::new();
+        taffy
+            .compute_layout_with_measure(node, Size::MAX_CONTENT, |node, known_dimensions, _available_space| {
+                measured_nodes.push(node);
+                Size { width: known_dimensions.width.unwrap_or(50.0), height: known_dimensions.height.unwrap_or(10.0) }
+            })
+            .unwrap();
+
+        // Only the flagged node without a stored measure function is measured by the closure
+        assert!(measured_nodes.iter().all(|measured| *measured == text));
+        assert!(!measured_nodes.is_empty());
+        assert_eq!(taffy.layout(text).unwrap().size.width, 50.0);
+        assert_eq!(taffy.layout(image).unwrap().size.width, 30.0);
+        assert_eq!(taffy.layout(spacer).unwrap().size.width, 0.0);
+        assert_eq!(taffy.layout(node).unwrap().size.width, 80.0);
+    }
+
+    #[test]
+    fn shared_measure_cache_is_consulted_across_trees() {
+        use std::sync::Arc;
+        use taffy::tree::SharedMeasureCache;
+
+        fn build_tree(cache: &Arc<SharedMeasureCache>) -> (Taffy, NodeId) {
+            let mut taffy = Taffy::new();
+            taffy.set_measure_cache(Some(cache.clone()));
+            let text = taffy.new_leaf(Style::default()).unwrap();
+            taffy.set_needs_measure(text, true).unwrap();
+            taffy.set_content_hash(text, Some(42)).unwrap();
+            let node = taffy.new_with_children(Style::default(), &[text]).unwrap();
+            (taffy, node)
+        }
+
+        let cache = Arc::new(SharedMeasureCache::new());
+        let mut measure_calls = 0;
+        let mut measure = |_node, known_dimensions: Size<Option<f32>>, _available_space| {
+            measure_calls += 1;
+            Size { width: known_dimensions.width.unwrap_or(50.0), height: known_dimensions.height.unwrap_or(10.0) }
+        };
+
+        let (mut first, first_root) = build_tree(&cache);
+        first.compute_layout_with_measure(first_root, Size::MAX_CONTENT, &mut measure).unwrap();
+        assert!(!cache.is_empty());
+
+        // The second tree reuses the sizes measured by the first
+        let (mut second, second_root) = build_tree(&cache);
+        second.compute_layout_with_measure(second_root, Size::MAX_CONTENT, &mut measure).unwrap();
+        assert_eq!(second.layout_stats().measure_calls, 0);
+        assert_eq!(second.layout(second_root).unwrap().size, Size { width: 50.0, height: 10.0 });
+        assert!(measure_calls > 0);
+    }
 }
@@ -0,0 +1,64 @@
+#![cfg(feature = "monospace_text")]
+use taffy::prelude::*;
+use taffy::tree::{Measurable, MeasureFunc, MonospaceText};
+
+/// Measures the text with no known dimensions under the given available width
+fn measure(text: &MonospaceText, width: AvailableSpace) -> Size<f32> {
+    text.measure(Size::NONE, Size { width, height: AvailableSpace::MaxContent })
+}
+
+#[test]
+fn max_content_width_is_the_longest_line() {
+    let text = MonospaceText::new("a short line\nand a longer line\n");
+    assert_eq!(measure(&text, AvailableSpace::MaxContent), Size { width: 17.0, height: 3.0 });
+}
+
+#[test]
+fn min_content_width_is_the_longest_word() {
+    let text = MonospaceText::new("one three fifteen");
+    assert_eq!(measure(&text, AvailableSpace::MinContent), Size { width: 7.0, height: 3.0 });
+}
+
+#[test]
+fn text_is_wrapped_between_words_to_fit_the_available_width() {
+    let text = MonospaceText::new("the quick brown fox");
+    // A line that exactly fills the available width isn't wrapped
+    assert_eq!(measure(&text, AvailableSpace::Definite(15.0)), Size { width: 15.0, height: 2.0 });
+    assert_eq!(measure(&text, AvailableSpace::Definite(14.0)), Size { width: 9.0, height: 2.0 });
+    assert_eq!(measure(&text, AvailableSpace::Definite(8.0)), Size { width: 5.0, height: 4.0 });
+    assert_eq!(measure(&text, AvailableSpace::Definite(19.0)), Size { width: 19.0, height: 1.0 });
+}
+
+#[test]
+fn words_wider_than_the_available_width_overflow() {
+    let text = MonospaceText::new("a supercalifragilistic b");
+    assert_eq!(measure(&text, AvailableSpace::Definite(5.0)), Size { width: 20.0, height: 3.0 });
+}
+
+#[test]
+fn empty_text_has_no_size() {
+    assert_eq!(measure(&MonospaceText::new(""), AvailableSpace::MaxContent), Size::ZERO);
+    // An empty line still takes up a line
+    assert_eq!(measure(&MonospaceText::new("\n"), AvailableSpace::MaxContent), Size { width: 0.0, height: 2.0 });
+}
+
+#[test]
+fn cells_can_have_any_size() {
+    let text = MonospaceText::new("ab cd").with_cell_size(Size { width: 8.4, height: 16.0 });
+    assert_eq!(measure(&text, AvailableSpace::Definite(42.0)), Size { width: 42.0, height: 16.0 });
+    assert_eq!(measure(&text, AvailableSpace::Definite(41.0)), Size { width: 16.8, height: 32.0 });
+}
+
+#[test]
+fn text_wraps_inside_of_a_layout() {
+    let mut taffy = Taffy::new();
+    let text = MeasureFunc::Boxed(Box::new(MonospaceText::new("one two three four")));
+    let label = taffy.new_leaf_with_measure(Style { flex_shrink: 1.0, ..Default::default() }, text).unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size { width: length(9.0), height: auto() }, ..Default::default() }, &[label])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(label).unwrap().size, Size { width: 9.0, height: 3.0 });
+    assert_eq!(taffy.layout(root).unwrap().size.height, 3.0);
+}
@@ -0,0 +1,153 @@
+use taffy::prelude::*;
+
+/// A fixed size leaf with the given margins
+fn sized_leaf(taffy: &mut Taffy, width: f32, height: f32, margin: Rect<LengthPercentageAuto>) -> NodeId {
+    taffy.new_leaf(Style { size: Size::from_lengths(width, height), margin, ..Default::default() }).unwrap()
+}
+
+/// Margins of the given lengths, in `[left, right, top, bottom]` order
+fn margins(left: f32, right: f32, top: f32, bottom: f32) -> Rect<LengthPercentageAuto> {
+    Rect { left: length(left), right: length(right), top: length(top), bottom: length(bottom) }
+}
+
+/// Lays out the root under max-content and returns its size and the location of each of its children
+fn layout(taffy: &mut Taffy, root: NodeId) -> (Size<f32>, Vec<(f32, f32)>) {
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let locations = taffy
+        .children(root)
+        .unwrap()
+        .into_iter()
+        .map(|child| {
+            let location = taffy.layout(child).unwrap().location;
+            (location.x, location.y)
+        })
+        .collect();
+    (taffy.layout(root).unwrap().size, locations)
+}
+
+#[test]
+fn negative_margins_reduce_intrinsic_size_and_pull_siblings_back() {
+    let mut containers = vec![Style { display: Display::Flex, ..Default::default() }];
+    #[cfg(feature = "grid")]
+    containers.push(Style {
+        display: Display::Grid,
+        grid_template_columns: vec![auto(), auto()].into(),
+        ..Default::default()
+    });
+
+    for container in containers {
+        let display = container.display;
+        let mut taffy = Taffy::new();
+        let first = sized_leaf(&mut taffy, 50.0, 10.0, margins(0.0, 0.0, 0.0, 0.0));
+        let second = sized_leaf(&mut taffy, 50.0, 10.0, margins(-20.0, 0.0, 0.0, 0.0));
+        let root = taffy.new_with_children(container, &[first, second]).unwrap();
+
+        let (size, locations) = layout(&mut taffy, root);
+        assert_eq!(size, Size { width: 80.0, height: 10.0 }, "{display:?}");
+        assert_eq!(locations, vec![(0.0, 0.0), (30.0, 0.0)], "{display:?}");
+    }
+}
+
+#[test]
+fn negative_margins_never_make_a_container_smaller_than_zero() {
+    let mut taffy = Taffy::new();
+    let first = sized_leaf(&mut taffy, 10.0, 10.0, margins(-30.0, 0.0, -30.0, 0.0));
+    let second = sized_leaf(&mut taffy, 10.0, 10.0, margins(0.0, 0.0, 0.0, 0.0));
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+
+    let (size, locations) = layout(&mut taffy, root);
+    assert_eq!(size, Size { width: 0.0, height: 10.0 });
+    assert_eq!(locations, vec![(-30.0, -30.0), (-20.0, 0.0)]);
+}
+
+#[test]
+fn negative_margins_enlarge_stretched_and_flexible_items() {
+    let mut containers = vec![Style { display: Display::Flex, ..Default::default() }];
+    #[cfg(feature = "grid")]
+    containers.push(Style { display: Display::Grid, ..Default::default() });
+
+    for container in containers {
+        let display = container.display;
+        let mut taffy = Taffy::new();
+        let item = taffy
+            .new_leaf(Style { margin: margins(-20.0, 0.0, -10.0, 0.0), flex_grow: 1.0, ..Default::default() })
+            .unwrap();
+        let root =
+            taffy.new_with_children(Style { size: Size::from_lengths(100.0, 100.0), ..container }, &[item]).unwrap();
+
+        let (_, locations) = layout(&mut taffy, root);
+        assert_eq!(locations, vec![(-20.0, -10.0)], "{display:?}");
+        assert_eq!(taffy.layout(item).unwrap().size, Size { width: 120.0, height: 110.0 }, "{display:?}");
+    }
+}
+
+#[test]
+fn negative_margins_make_room_for_items_that_would_otherwise_shrink() {
+    let mut taffy = Taffy::new();
+    let first = sized_leaf(&mut taffy, 60.0, 10.0, margins(0.0, 0.0, 0.0, 0.0));
+    let second = sized_leaf(&mut taffy, 60.0, 10.0, margins(-20.0, 0.0, 0.0, 0.0));
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(100.0, 10.0), ..Default::default() }, &[first, second])
+        .unwrap();
+
+    let (_, locations) = layout(&mut taffy, root);
+    assert_eq!(locations, vec![(0.0, 0.0), (40.0, 0.0)]);
+    assert_eq!(taffy.layout(first).unwrap().size.width, 60.0);
+    assert_eq!(taffy.layout(second).unwrap().size.width, 60.0);
+}
+
+#[test]
+fn items_are_aligned_by_their_margin_box() {
+    let mut containers = vec![Style { display: Display::Flex, ..Default::default() }];
+    #[cfg(feature = "grid")]
+    containers.push(Style { display: Display::Grid, justify_items: Some(JustifyItems::Center), ..Default::default() });
+
+    for container in containers {
+        let display = container.display;
+        let mut taffy = Taffy::new();
+        let item = sized_leaf(&mut taffy, 20.0, 20.0, margins(-10.0, -10.0, -10.0, -10.0));
+        let style = Style {
+            size: Size::from_lengths(100.0, 100.0),
+            align_items: Some(AlignItems::Center),
+            justify_content: Some(JustifyContent::Center),
+            ..container
+        };
+        let root = taffy.new_with_children(style, &[item]).unwrap();
+
+        // The item's margin box is empty, so it is centered on the middle of the container
+        let (_, locations) = layout(&mut taffy, root);
+        assert_eq!(locations, vec![(40.0, 40.0)], "{display:?}");
+    }
+}
+
+#[test]
+fn negative_margins_reduce_the_shrink_to_fit_width_of_blocks_and_flex_containers() {
+    let mut displays = vec![Display::Flex];
+    #[cfg(feature = "block_layout")]
+    displays.push(Display::Block);
+    #[cfg(feature = "grid")]
+    displays.push(Display::Grid);
+
+    for display in displays {
+        let mut taffy = Taffy::new();
+        let item = sized_leaf(&mut taffy, 20.0, 20.0, margins(0.0, -15.0, 0.0, 0.0));
+        let root = taffy.new_with_children(Style { display, ..Default::default() }, &[item]).unwrap();
+
+        let (size, _) = layout(&mut taffy, root);
+        assert_eq!(size, Size { width: 5.0, height: 20.0 }, "{display:?}");
+    }
+}
+
+#[test]
+#[cfg(feature = "block_layout")]
+fn negative_margins_pull_following_blocks_upwards() {
+    let mut taffy = Taffy::new();
+    let first = sized_leaf(&mut taffy, 20.0, 20.0, margins(0.0, 0.0, 0.0, -15.0));
+    let second = sized_leaf(&mut taffy, 20.0, 20.0, margins(-5.0, 0.0, 0.0, 0.0));
+    let root =
+        taffy.new_with_children(Style { display: Display::Block, ..Default::default() }, &[first, second]).unwrap();
+
+    let (size, locations) = layout(&mut taffy, root);
+    assert_eq!(size, Size { width: 20.0, height: 25.0 });
+    assert_eq!(locations, vec![(0.0, 0.0), (-5.0, 5.0)]);
+}
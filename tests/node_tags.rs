@@ -0,0 +1,67 @@
+use taffy::prelude::*;
+
+const FOCUSABLE: u32 = 1 << 0;
+const SCROLLABLE: u32 = 1 << 1;
+
+/// A root containing a scrollable panel with two buttons, followed by a focusable, scrollable list
+fn tagged_tree() -> (Taffy, NodeId, [NodeId; 4]) {
+    let mut taffy = Taffy::new();
+    let first = taffy.new_leaf(Style::default()).unwrap();
+    let second = taffy.new_leaf(Style::default()).unwrap();
+    let panel = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+    let list = taffy.new_leaf(Style::default()).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[panel, list]).unwrap();
+
+    taffy.set_tags(panel, SCROLLABLE).unwrap();
+    taffy.set_tags(first, FOCUSABLE).unwrap();
+    taffy.set_tags(second, FOCUSABLE).unwrap();
+    taffy.set_tags(list, FOCUSABLE | SCROLLABLE).unwrap();
+    (taffy, root, [panel, first, second, list])
+}
+
+#[test]
+fn tagged_descendants_are_visited_in_tree_order() {
+    let (taffy, root, [panel, first, second, list]) = tagged_tree();
+
+    let focusable: Vec<NodeId> = taffy.tagged_descendants(root, FOCUSABLE).unwrap().collect();
+    assert_eq!(focusable, [first, second, list]);
+    let scrollable: Vec<NodeId> = taffy.tagged_descendants(root, SCROLLABLE).unwrap().collect();
+    assert_eq!(scrollable, [panel, list]);
+    let both: Vec<NodeId> = taffy.tagged_descendants(root, FOCUSABLE | SCROLLABLE).unwrap().collect();
+    assert_eq!(both, [list]);
+
+    // The root itself is never yielded, and no tags matches every descendant
+    let all: Vec<NodeId> = taffy.tagged_descendants(root, 0).unwrap().collect();
+    assert_eq!(all, [panel, first, second, list]);
+    assert_eq!(taffy.tagged_descendants(panel, SCROLLABLE).unwrap().count(), 0);
+}
+
+#[test]
+fn tags_can_be_inserted_and_removed() {
+    let (mut taffy, _, [panel, ..]) = tagged_tree();
+    taffy.insert_tags(panel, FOCUSABLE).unwrap();
+    assert_eq!(taffy.tags(panel).unwrap(), FOCUSABLE | SCROLLABLE);
+
+    taffy.remove_tags(panel, SCROLLABLE).unwrap();
+    assert_eq!(taffy.tags(panel).unwrap(), FOCUSABLE);
+}
+
+#[test]
+fn tagged_ancestor_finds_the_closest_match() {
+    let (mut taffy, root, [panel, first, ..]) = tagged_tree();
+    taffy.set_tags(root, SCROLLABLE).unwrap();
+
+    assert_eq!(taffy.tagged_ancestor(first, SCROLLABLE).unwrap(), Some(panel));
+    assert_eq!(taffy.tagged_ancestor(panel, SCROLLABLE).unwrap(), Some(root));
+    assert_eq!(taffy.tagged_ancestor(first, FOCUSABLE).unwrap(), None);
+}
+
+#[test]
+fn tags_are_dropped_with_their_node() {
+    let (mut taffy, _, [.., list]) = tagged_tree();
+    taffy.remove(list).unwrap();
+
+    assert!(taffy.tags(list).is_err());
+    let node = taffy.new_leaf(Style::default()).unwrap();
+    assert_eq!(taffy.tags(node).unwrap(), 0);
+}
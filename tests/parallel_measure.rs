@@ -0,0 +1,84 @@
+#![cfg(feature = "rayon")]
+
+use taffy::prelude::*;
+use taffy::tree::{Measurable, MeasureFunc};
+
+/// The size of the text in the cell with the given index, which wraps to fit the available width
+fn measure_cell(index: usize, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+    let text_width = 10.0 + (index % 7) as f32 * 15.0;
+    let width = known_dimensions.width.unwrap_or(match available_space.width {
+        AvailableSpace::Definite(width) => width.min(text_width),
+        AvailableSpace::MinContent => 10.0,
+        AvailableSpace::MaxContent => text_width,
+    });
+    Size { width, height: known_dimensions.height.unwrap_or((text_width / width).ceil() * 12.0) }
+}
+
+/// A text cell measured with a stored measure function
+struct TextCell(usize);
+
+impl Measurable for TextCell {
+    fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+        measure_cell(self.0, known_dimensions, available_space)
+    }
+}
+
+/// Builds a grid of text cells, with a nested container every few cells. Cells are measured with stored measure
+/// functions if `stored_measure` is true, and by the measure function passed to the layout computation otherwise.
+fn text_grid(taffy: &mut Taffy, stored_measure: bool) -> (NodeId, Vec<NodeId>) {
+    let mut cells = Vec::new();
+    for index in 0..200 {
+        let style = Style { grid_column: span((index % 3 == 0) as u16 + 1), ..Default::default() };
+        let cell = match stored_measure {
+            true => taffy.new_leaf_with_measure(style, MeasureFunc::Boxed(Box::new(TextCell(index)))).unwrap(),
+            false => {
+                let cell = taffy.new_leaf(style).unwrap();
+                taffy.set_needs_measure(cell, true).unwrap();
+                cell
+            }
+        };
+        let cell = match index % 10 {
+            0 => taffy.new_with_children(Style { padding: length(4.0), ..Default::default() }, &[cell]).unwrap(),
+            _ => cell,
+        };
+        cells.push(cell);
+    }
+    let root = taffy
+        .new_with_children(
+            Style {
+                display: Display::Grid,
+                grid_template_columns: vec![auto(), min_content(), max_content(), fr(1.0), length(40.0)].into(),
+                size: Size { width: length(500.0), height: auto() },
+                ..Default::default()
+            },
+            &cells,
+        )
+        .unwrap();
+    (root, cells)
+}
+
+#[test]
+fn parallel_measurement_matches_sequential_measurement() {
+    let mut parallel = Taffy::new();
+    let (parallel_root, parallel_cells) = text_grid(&mut parallel, true);
+    parallel.compute_layout(parallel_root, Size::MAX_CONTENT).unwrap();
+
+    let mut sequential = Taffy::new();
+    let (sequential_root, sequential_cells) = text_grid(&mut sequential, false);
+    let leaf_indexes: Vec<(NodeId, usize)> = sequential_cells
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| (sequential.children(*cell).unwrap().first().copied().unwrap_or(*cell), index))
+        .collect();
+    sequential
+        .compute_layout_with_measure(sequential_root, Size::MAX_CONTENT, |node, known_dimensions, available_space| {
+            let index = leaf_indexes.iter().find(|(leaf, _)| *leaf == node).unwrap().1;
+            measure_cell(index, known_dimensions, available_space)
+        })
+        .unwrap();
+
+    assert_eq!(parallel.layout(parallel_root).unwrap(), sequential.layout(sequential_root).unwrap());
+    for (parallel_cell, sequential_cell) in parallel_cells.iter().zip(sequential_cells.iter()) {
+        assert_eq!(parallel.layout(*parallel_cell).unwrap(), sequential.layout(*sequential_cell).unwrap());
+    }
+}
@@ -0,0 +1,109 @@
+use taffy::prelude::*;
+use taffy::tree::{CoordinateOrigin, QUANTIZED_HEADER_SIZE, QUANTIZED_RECORD_SIZE};
+
+/// A node record decoded from a quantized layout buffer
+#[derive(Debug, PartialEq)]
+struct Record {
+    node: u64,
+    parent: u32,
+    x: i16,
+    y: i16,
+    width: u16,
+    height: u16,
+}
+
+/// Decodes the header and records of a quantized layout buffer
+fn decode(buffer: &[u8]) -> (u8, Vec<Record>) {
+    let count = u32::from_le_bytes(buffer[0..4].try_into().unwrap()) as usize;
+    assert_eq!(&buffer[5..8], &[0, 0, 0]);
+    assert_eq!(buffer.len(), QUANTIZED_HEADER_SIZE + count * QUANTIZED_RECORD_SIZE);
+    let records = buffer[QUANTIZED_HEADER_SIZE..]
+        .chunks_exact(QUANTIZED_RECORD_SIZE)
+        .map(|record| Record {
+            node: u64::from_le_bytes(record[0..8].try_into().unwrap()),
+            parent: u32::from_le_bytes(record[8..12].try_into().unwrap()),
+            x: i16::from_le_bytes(record[12..14].try_into().unwrap()),
+            y: i16::from_le_bytes(record[14..16].try_into().unwrap()),
+            width: u16::from_le_bytes(record[16..18].try_into().unwrap()),
+            height: u16::from_le_bytes(record[18..20].try_into().unwrap()),
+        })
+        .collect();
+    (buffer[4], records)
+}
+
+/// A 100x50 root with padding, containing a 40x30 child which contains a 10.5x10 grandchild, then a 20x20 child
+fn nested_tree() -> (Taffy, [NodeId; 4]) {
+    let mut taffy = Taffy::new();
+    taffy.disable_rounding();
+    let grandchild = taffy.new_leaf(Style { size: Size::from_lengths(10.5, 10.0), ..Default::default() }).unwrap();
+    let first = taffy
+        .new_with_children(
+            Style { size: Size::from_lengths(40.0, 30.0), padding: length(2.0), ..Default::default() },
+            &[grandchild],
+        )
+        .unwrap();
+    let second = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 20.0), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { size: Size::from_lengths(100.0, 50.0), padding: length(5.0), ..Default::default() },
+            &[first, second],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, [root, first, grandchild, second])
+}
+
+#[test]
+fn records_are_written_depth_first_with_absolute_positions() {
+    let (taffy, [root, first, grandchild, second]) = nested_tree();
+    let (fractional_bits, records) = decode(&taffy.layout_to_quantized(root, 0).unwrap());
+
+    assert_eq!(fractional_bits, 0);
+    assert_eq!(
+        records,
+        vec![
+            Record { node: root.into(), parent: u32::MAX, x: 0, y: 0, width: 100, height: 50 },
+            Record { node: first.into(), parent: 0, x: 5, y: 5, width: 40, height: 30 },
+            // 10.5 is rounded to the nearest whole pixel
+            Record { node: grandchild.into(), parent: 1, x: 7, y: 7, width: 11, height: 10 },
+            Record { node: second.into(), parent: 0, x: 45, y: 5, width: 20, height: 20 },
+        ]
+    );
+}
+
+#[test]
+fn fractional_bits_keep_subpixel_precision() {
+    let (taffy, [root, _, grandchild, _]) = nested_tree();
+    let (fractional_bits, records) = decode(&taffy.layout_to_quantized(root, 4).unwrap());
+
+    assert_eq!(fractional_bits, 4);
+    assert_eq!(
+        records[2],
+        Record { node: grandchild.into(), parent: 1, x: 7 * 16, y: 7 * 16, width: 168, height: 160 }
+    );
+
+    // At most 15 fractional bits are used, and values saturate instead of wrapping
+    let (fractional_bits, records) = decode(&taffy.layout_to_quantized(root, 20).unwrap());
+    assert_eq!(fractional_bits, 15);
+    assert_eq!((records[0].width, records[1].x), (u16::MAX, i16::MAX));
+}
+
+#[test]
+fn positions_are_top_left_whichever_the_coordinate_origin() {
+    let (mut taffy, [root, ..]) = nested_tree();
+    let top_left = taffy.layout_to_quantized(root, 0).unwrap();
+    taffy.set_coordinate_origin(CoordinateOrigin::BottomLeft);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout_to_quantized(root, 0).unwrap(), top_left);
+}
+
+#[test]
+fn subtrees_are_exported_relative_to_their_root() {
+    let (taffy, [_, first, grandchild, _]) = nested_tree();
+    let (_, records) = decode(&taffy.layout_to_quantized(first, 0).unwrap());
+
+    assert_eq!(records.len(), 2);
+    assert_eq!((records[0].parent, records[0].x, records[0].y), (u32::MAX, 0, 0));
+    assert_eq!(records[1], Record { node: grandchild.into(), parent: 0, x: 2, y: 2, width: 11, height: 10 });
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "testing")]
+use taffy::prelude::*;
+use taffy::testing::{RandomTreeGenerator, StyleDistribution, TreeShape};
+
+/// Returns the number of nodes in the subtree rooted at `node`, and its depth
+fn subtree_size(taffy: &Taffy, node: NodeId) -> (usize, u32) {
+    taffy.children(node).unwrap().into_iter().fold((1, 0), |(count, depth), child| {
+        let (child_count, child_depth) = subtree_size(taffy, child);
+        (count + child_count, depth.max(child_depth + 1))
+    })
+}
+
+#[test]
+fn generated_trees_respect_the_shape() {
+    let mut taffy = Taffy::new();
+    let shape = TreeShape { max_nodes: 300, max_depth: 4, ..Default::default() };
+    let root = RandomTreeGenerator::new(1, shape, StyleDistribution::mixed()).generate(&mut taffy).unwrap();
+    let (count, depth) = subtree_size(&taffy, root);
+    assert!(count <= 300, "{count}");
+    assert!(depth <= 4, "{depth}");
+
+    let root =
+        RandomTreeGenerator::new(1, TreeShape::wide(100), StyleDistribution::flexbox()).generate(&mut taffy).unwrap();
+    assert_eq!(subtree_size(&taffy, root), (100, 1));
+    let root =
+        RandomTreeGenerator::new(1, TreeShape::deep(127, 2), StyleDistribution::block()).generate(&mut taffy).unwrap();
+    assert_eq!(subtree_size(&taffy, root), (127, 6));
+}
+
+#[test]
+fn generated_trees_are_determined_by_the_seed() {
+    let layouts = |seed| {
+        let mut taffy = Taffy::new();
+        let mut generator = RandomTreeGenerator::new(seed, TreeShape::default(), StyleDistribution::mixed());
+        let root = generator.generate(&mut taffy).unwrap();
+        taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        let mut layouts = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            layouts.push(*taffy.layout(node).unwrap());
+            stack.extend(taffy.children(node).unwrap());
+        }
+        layouts
+    };
+    assert_eq!(layouts(7), layouts(7));
+    assert_ne!(layouts(7), layouts(8));
+}
+
+#[test]
+fn generated_trees_can_be_laid_out() {
+    for styles in [StyleDistribution::block(), StyleDistribution::flexbox(), StyleDistribution::grid()] {
+        for seed in 0..10 {
+            let mut taffy = Taffy::new();
+            let shape = TreeShape { max_nodes: 200, ..Default::default() };
+            let root = RandomTreeGenerator::new(seed, shape, styles.clone()).generate(&mut taffy).unwrap();
+            taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+            let size = taffy.layout(root).unwrap().size;
+            assert!(size.width.is_finite() && size.height.is_finite(), "seed {seed}: {size:?}");
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use taffy::prelude::*;
+use taffy::tree::NodeDescription;
+use taffy::TaffyError;
+
+fn keyed(key: &str, style: Style, children: Vec<NodeDescription>) -> NodeDescription {
+    NodeDescription { key: Some(key.to_string()), style, children, ..Default::default() }
+}
+
+fn fixed(width: f32, height: f32) -> Style {
+    Style { size: Size::from_lengths(width, height), ..Default::default() }
+}
+
+#[test]
+fn reconcile_should_build_a_new_tree() {
+    let mut taffy = Taffy::new();
+    let description = keyed(
+        "root",
+        Style::default(),
+        vec![keyed("sidebar", fixed(50.0, 100.0), vec![]), NodeDescription::new(fixed(100.0, 100.0))],
+    );
+
+    let root = taffy.reconcile(None, &description).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.node_by_key("root"), Some(root));
+    assert_eq!(taffy.child_count(root).unwrap(), 2);
+    assert_eq!(taffy.layout(root).unwrap().size.width, 150.0);
+    let sidebar = taffy.node_by_key("sidebar").unwrap();
+    assert_eq!(taffy.layout(sidebar).unwrap().size.width, 50.0);
+}
+
+#[test]
+fn reconcile_should_reuse_unchanged_nodes() {
+    let mut taffy = Taffy::new();
+    let description = keyed(
+        "root",
+        Style::default(),
+        vec![keyed("sidebar", fixed(50.0, 100.0), vec![]), NodeDescription::new(fixed(100.0, 100.0))],
+    );
+    let root = taffy.reconcile(None, &description).unwrap();
+    let sidebar = taffy.node_by_key("sidebar").unwrap();
+    let content = taffy.child_at_index(root, 1).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // Reconciling against an identical description doesn't invalidate anything
+    assert_eq!(taffy.reconcile(Some(root), &description).unwrap(), root);
+    assert!(!taffy.dirty(root).unwrap());
+    assert_eq!(taffy.child_at_index(root, 1).unwrap(), content);
+
+    // Keyed nodes are matched by key, and unkeyed nodes by position
+    let reordered = keyed(
+        "root",
+        Style::default(),
+        vec![NodeDescription::new(fixed(120.0, 100.0)), keyed("sidebar", fixed(50.0, 100.0), vec![])],
+    );
+    assert_eq!(taffy.reconcile(Some(root), &reordered).unwrap(), root);
+    assert_eq!(taffy.children(root).unwrap(), vec![content, sidebar]);
+    assert!(!taffy.dirty(sidebar).unwrap());
+    assert!(taffy.dirty(content).unwrap());
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(sidebar).unwrap().location.x, 120.0);
+}
+
+#[test]
+fn reconcile_should_move_keyed_nodes_and_remove_stale_nodes() {
+    let mut taffy = Taffy::new();
+    let description = keyed(
+        "root",
+        Style::default(),
+        vec![
+            keyed("left", Style::default(), vec![keyed("widget", fixed(10.0, 10.0), vec![])]),
+            keyed("right", Style::default(), vec![NodeDescription::new(fixed(20.0, 20.0))]),
+        ],
+    );
+    let root = taffy.reconcile(None, &description).unwrap();
+    let widget = taffy.node_by_key("widget").unwrap();
+    let right = taffy.node_by_key("right").unwrap();
+
+    let moved = keyed(
+        "root",
+        Style::default(),
+        vec![
+            keyed("left", Style::default(), vec![]),
+            keyed("right", Style::default(), vec![keyed("widget", fixed(10.0, 10.0), vec![])]),
+        ],
+    );
+    taffy.reconcile(Some(root), &moved).unwrap();
+
+    assert_eq!(taffy.node_by_key("widget"), Some(widget));
+    assert_eq!(taffy.children(right).unwrap(), vec![widget]);
+    assert_eq!(taffy.child_count(taffy.node_by_key("left").unwrap()).unwrap(), 0);
+
+    // The moved node is laid out as part of its new parent
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(right).unwrap().size.width, 10.0);
+}
+
+#[test]
+fn reconcile_should_reject_duplicate_keys() {
+    let mut taffy = Taffy::new();
+    let description = keyed(
+        "root",
+        Style::default(),
+        vec![keyed("item", Style::default(), vec![]), keyed("item", Style::default(), vec![])],
+    );
+
+    assert!(matches!(taffy.reconcile(None, &description), Err(TaffyError::DuplicateKey { .. })));
+}
+
+#[test]
+fn reconcile_should_detach_keyed_nodes_from_outside_the_subtree() {
+    let mut taffy = Taffy::new();
+    let widget = taffy.new_leaf(fixed(10.0, 10.0)).unwrap();
+    taffy.set_key(widget, Some("widget")).unwrap();
+    let other = taffy.new_with_children(Style::default(), &[widget]).unwrap();
+    let root = taffy.new_leaf(Style::default()).unwrap();
+
+    let description = NodeDescription {
+        children: vec![keyed("widget", fixed(10.0, 10.0), vec![])],
+        ..NodeDescription::new(Style::default())
+    };
+    assert_eq!(taffy.reconcile(Some(root), &description).unwrap(), root);
+
+    assert_eq!(taffy.children(root).unwrap(), vec![widget]);
+    assert_eq!(taffy.child_count(other).unwrap(), 0);
+
+    // Removing the previous parent leaves the moved node in place
+    taffy.remove(other).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size.width, 10.0);
+}
+
+#[test]
+fn reconcile_should_leave_the_tree_unchanged_on_error() {
+    let mut taffy = Taffy::new();
+    let description = keyed("root", Style::default(), vec![NodeDescription::new(fixed(20.0, 20.0))]);
+    let root = taffy.reconcile(None, &description).unwrap();
+    let child = taffy.child_at_index(root, 0).unwrap();
+    let node_count = taffy.iter().count();
+
+    let invalid = keyed(
+        "root",
+        fixed(50.0, 50.0),
+        vec![keyed("item", Style::default(), vec![]), keyed("item", Style::default(), vec![])],
+    );
+    assert!(matches!(taffy.reconcile(Some(root), &invalid), Err(TaffyError::DuplicateKey { .. })));
+
+    assert_eq!(taffy.iter().count(), node_count);
+    assert_eq!(taffy.node_by_key("item"), None);
+    assert_eq!(taffy.children(root).unwrap(), vec![child]);
+    assert_eq!(taffy.style(root).unwrap(), &Style::default());
+}
@@ -0,0 +1,97 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use taffy::prelude::*;
+use taffy::tree::{InvalidationLevel, Measurable, MeasureFunc, RelayoutScheduler};
+
+/// A measured leaf whose width is read from an atomic, so that tests can change its content
+struct Text(&'static AtomicU32);
+
+impl Measurable for Text {
+    fn measure(&self, _: Size<Option<f32>>, _: Size<AvailableSpace>) -> Size<f32> {
+        Size { width: self.0.load(Ordering::SeqCst) as f32, height: 10.0 }
+    }
+}
+
+/// A row containing a measured leaf whose width is read from `width`, then a fixed-size leaf
+fn row(width: &'static AtomicU32) -> (Taffy, NodeId, [NodeId; 2]) {
+    let mut taffy = Taffy::new();
+    let text = taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Boxed(Box::new(Text(width)))).unwrap();
+    let icon = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[text, icon]).unwrap();
+    (taffy, root, [text, icon])
+}
+
+#[test]
+fn the_first_run_lays_out_the_tree_and_later_runs_only_when_needed() {
+    static WIDTH: AtomicU32 = AtomicU32::new(30);
+    let (mut taffy, root, _) = row(&WIDTH);
+    let mut scheduler = RelayoutScheduler::new(root, Size::MAX_CONTENT);
+
+    assert!(scheduler.needs_relayout(&taffy));
+    let report = scheduler.run(&mut taffy).unwrap().unwrap();
+    assert_eq!(report.changed.len(), 3);
+    assert_eq!(taffy.layout(root).unwrap().size.width, 40.0);
+
+    assert!(!scheduler.needs_relayout(&taffy));
+    assert_eq!(scheduler.run(&mut taffy).unwrap(), None);
+}
+
+#[test]
+fn notifications_are_coalesced_into_one_relayout() {
+    static WIDTH: AtomicU32 = AtomicU32::new(30);
+    let (mut taffy, root, [text, icon]) = row(&WIDTH);
+    let mut scheduler = RelayoutScheduler::new(root, Size::MAX_CONTENT);
+    scheduler.run(&mut taffy).unwrap();
+
+    WIDTH.store(50, Ordering::SeqCst);
+    scheduler.notify_with(text, InvalidationLevel::Measure);
+    scheduler.notify(text);
+    scheduler.notify_with(text, InvalidationLevel::Measure);
+    assert!(scheduler.needs_relayout(&taffy));
+
+    let report = scheduler.run(&mut taffy).unwrap().unwrap();
+    assert_eq!(report.notifications, 3);
+    assert_eq!(report.dirty_nodes, 1);
+    // The text grew, which moved the icon and grew the root
+    assert_eq!(report.changed.len(), 3);
+    assert!(report.changed.contains(&icon));
+    assert_eq!(taffy.layout(icon).unwrap().location.x, 50.0);
+    assert_eq!(scheduler.run(&mut taffy).unwrap(), None);
+}
+
+#[test]
+fn direct_changes_and_resizes_schedule_a_relayout() {
+    static WIDTH: AtomicU32 = AtomicU32::new(30);
+    let (mut taffy, root, [_, icon]) = row(&WIDTH);
+    let mut scheduler = RelayoutScheduler::new(root, Size::MAX_CONTENT);
+    scheduler.run(&mut taffy).unwrap();
+
+    taffy.set_style(icon, Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+    assert!(scheduler.needs_relayout(&taffy));
+    let report = scheduler.run(&mut taffy).unwrap().unwrap();
+    assert_eq!((report.notifications, report.dirty_nodes), (0, 0));
+    assert_eq!(taffy.layout(root).unwrap().size.width, 50.0);
+
+    scheduler.set_available_space(Size::MAX_CONTENT);
+    assert!(!scheduler.needs_relayout(&taffy));
+    scheduler.set_available_space(Size { width: AvailableSpace::Definite(100.0), height: AvailableSpace::MaxContent });
+    assert!(scheduler.needs_relayout(&taffy));
+    assert!(scheduler.run(&mut taffy).unwrap().is_some());
+}
+
+#[test]
+fn removed_nodes_are_ignored() {
+    static WIDTH: AtomicU32 = AtomicU32::new(30);
+    let (mut taffy, root, [text, _]) = row(&WIDTH);
+    let mut scheduler = RelayoutScheduler::new(root, Size::MAX_CONTENT);
+    scheduler.run(&mut taffy).unwrap();
+
+    scheduler.notify(text);
+    taffy.remove(text).unwrap();
+    let report = scheduler.run(&mut taffy).unwrap().unwrap();
+    assert_eq!((report.notifications, report.dirty_nodes), (1, 0));
+    assert_eq!(taffy.layout(root).unwrap().size.width, 10.0);
+
+    taffy.remove(root).unwrap();
+    assert!(scheduler.run(&mut taffy).is_err());
+}
@@ -0,0 +1,94 @@
+use taffy::prelude::*;
+use taffy::tree::ReplacedContent;
+
+/// Lays out a 200x100 image with the given style in a 400px wide container, returning the size of the image
+fn image_size(style: Style) -> Size<f32> {
+    replaced_size(ReplacedContent::from_size(200.0, 100.0), style)
+}
+
+/// Lays out a replaced element with the given content and style in a 400px wide container, returning its size
+fn replaced_size(content: ReplacedContent, style: Style) -> Size<f32> {
+    let mut taffy = Taffy::new();
+    let image = taffy.new_replaced_leaf(style, content).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                flex_direction: FlexDirection::Column,
+                align_items: Some(AlignItems::Start),
+                size: Size { width: length(400.0), height: auto() },
+                ..Default::default()
+            },
+            &[image],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.layout(image).unwrap().size
+}
+
+#[test]
+fn replaced_elements_default_to_their_natural_size() {
+    assert_eq!(image_size(Style::default()), Size { width: 200.0, height: 100.0 });
+    let padded = Style {
+        padding: Rect { left: length(10.0), right: length(10.0), top: zero(), bottom: zero() },
+        ..Default::default()
+    };
+    assert_eq!(image_size(padded), Size { width: 220.0, height: 100.0 });
+}
+
+#[test]
+fn a_missing_dimension_is_derived_from_the_aspect_ratio() {
+    assert_eq!(
+        image_size(Style { size: Size { width: length(100.0), height: auto() }, ..Default::default() }),
+        Size { width: 100.0, height: 50.0 }
+    );
+    assert_eq!(
+        image_size(Style { size: Size { width: auto(), height: length(20.0) }, ..Default::default() }),
+        Size { width: 40.0, height: 20.0 }
+    );
+    assert_eq!(
+        image_size(Style { size: Size { width: percent(0.5), height: auto() }, ..Default::default() }),
+        Size { width: 200.0, height: 100.0 }
+    );
+    let both = Style { size: Size { width: length(30.0), height: length(30.0) }, ..Default::default() };
+    assert_eq!(image_size(both), Size { width: 30.0, height: 30.0 });
+}
+
+#[test]
+fn min_and_max_sizes_preserve_the_aspect_ratio() {
+    let max_width = Style { max_size: Size { width: length(50.0), height: auto() }, ..Default::default() };
+    assert_eq!(image_size(max_width), Size { width: 50.0, height: 25.0 });
+    let min_height = Style { min_size: Size { width: auto(), height: length(200.0) }, ..Default::default() };
+    assert_eq!(image_size(min_height), Size { width: 400.0, height: 200.0 });
+    let max_both = Style { max_size: Size { width: length(100.0), height: length(25.0) }, ..Default::default() };
+    assert_eq!(image_size(max_both), Size { width: 50.0, height: 25.0 });
+    // Conflicting constraints can't preserve the aspect ratio
+    let conflicting = Style {
+        min_size: Size { width: length(300.0), height: auto() },
+        max_size: Size { width: auto(), height: length(50.0) },
+        ..Default::default()
+    };
+    assert_eq!(image_size(conflicting), Size { width: 300.0, height: 50.0 });
+}
+
+#[test]
+fn content_without_a_natural_size_fills_the_available_width() {
+    let size = replaced_size(ReplacedContent::from_aspect_ratio(4.0), Style::default());
+    assert_eq!(size, Size { width: 400.0, height: 100.0 });
+}
+
+#[test]
+fn replaced_content_can_be_changed() {
+    let mut taffy = Taffy::new();
+    let image = taffy.new_leaf(Style::default()).unwrap();
+    taffy.compute_layout(image, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(image).unwrap().size, Size::ZERO);
+
+    taffy.set_replaced_content(image, Some(ReplacedContent::from_size(20.0, 10.0))).unwrap();
+    assert_eq!(taffy.replaced_content(image), Some(ReplacedContent::from_size(20.0, 10.0)));
+    taffy.compute_layout(image, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(image).unwrap().size, Size { width: 20.0, height: 10.0 });
+
+    taffy.set_replaced_content(image, None).unwrap();
+    taffy.compute_layout(image, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(image).unwrap().size, Size::ZERO);
+}
@@ -27,3 +27,64 @@ fn rounding_doesnt_leave_gaps() {
     taffy::util::print_tree(&taffy, root_node);
     assert_eq!(layout_a.location.x + layout_a.size.width, layout_b.location.x);
 }
+
+/// A 101 wide row of three equally growing children
+fn three_columns(taffy: &mut Taffy) -> (NodeId, [NodeId; 3]) {
+    let children = [(); 3].map(|_| taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap());
+    let root = taffy
+        .new_with_children(
+            Style { size: Size { width: length(101.0), height: length(10.0) }, ..Default::default() },
+            &children,
+        )
+        .unwrap();
+    (root, children)
+}
+
+/// The x position and width of each node in physical pixels
+fn physical_columns(taffy: &Taffy, nodes: [NodeId; 3]) -> [(f32, f32); 3] {
+    nodes.map(|node| {
+        let layout = taffy.physical_layout(node).unwrap();
+        (layout.location.x, layout.size.width)
+    })
+}
+
+#[test]
+fn rounding_uses_physical_pixels_at_fractional_scale_factors() {
+    let mut taffy = Taffy::new();
+    taffy.set_scale_factor(1.25);
+    let (root, children) = three_columns(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // The columns end at 42.08, 84.17 and 126.25 physical pixels before rounding
+    assert_eq!(physical_columns(&taffy, children), [(0.0, 42.0), (42.0, 42.0), (84.0, 42.0)]);
+    let logical = taffy.layout(children[1]).unwrap();
+    assert_eq!((logical.location.x, logical.size.width), (42.0 / 1.25, 42.0 / 1.25));
+    assert_eq!(taffy.physical_layout(root).unwrap().size, Size { width: 126.0, height: 13.0 });
+}
+
+#[test]
+fn changing_the_scale_factor_rounds_the_tree_again() {
+    let mut taffy = Taffy::new();
+    let (root, children) = three_columns(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(physical_columns(&taffy, children), [(0.0, 34.0), (34.0, 33.0), (67.0, 34.0)]);
+
+    taffy.set_scale_factor(2.0);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.scale_factor(), 2.0);
+    assert_eq!(physical_columns(&taffy, children), [(0.0, 67.0), (67.0, 68.0), (135.0, 67.0)]);
+}
+
+#[test]
+fn physical_layouts_are_only_rounded_with_rounding_enabled() {
+    let mut taffy = Taffy::new();
+    taffy.disable_rounding();
+    taffy.set_scale_factor(1.5);
+    let (root, children) = three_columns(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let logical = *taffy.layout(children[1]).unwrap();
+    let physical = taffy.physical_layout(children[1]).unwrap();
+    assert_eq!(physical.location.x, logical.location.x * 1.5);
+    assert_eq!(physical.size.width, logical.size.width * 1.5);
+}
@@ -0,0 +1,112 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Overflow;
+
+/// A 100x100 scroll container at (10, 10) in the root, containing a 50x200 column of four 50x50 items
+fn scrolled_list(overflow: Overflow) -> (Taffy, NodeId, NodeId, Vec<NodeId>) {
+    let mut taffy = Taffy::new();
+    let items: Vec<NodeId> = (0..4)
+        .map(|_| {
+            let style = Style { size: Size::from_lengths(50.0, 50.0), flex_shrink: 0.0, ..Default::default() };
+            taffy.new_leaf(style).unwrap()
+        })
+        .collect();
+    let container_style = Style {
+        flex_direction: FlexDirection::Column,
+        size: Size::from_lengths(100.0, 100.0),
+        overflow: Point { x: overflow, y: overflow },
+        ..Default::default()
+    };
+    let container = taffy.new_with_children(container_style, &items).unwrap();
+    let root_style = Style { padding: length(10.0), ..Default::default() };
+    let root = taffy.new_with_children(root_style, &[container]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, container, items)
+}
+
+#[test]
+fn scroll_offsets_move_descendants_without_affecting_layout() {
+    let (mut taffy, root, container, items) = scrolled_list(Overflow::Scroll);
+    assert_eq!(taffy.absolute_location(items[2]).unwrap(), Point { x: 10.0, y: 110.0 });
+
+    taffy.set_scroll_offset(container, Point { x: 0.0, y: 80.0 }).unwrap();
+    assert_eq!(taffy.scroll_offset(container), Point { x: 0.0, y: 80.0 });
+    assert!(!taffy.dirty(root).unwrap());
+    assert_eq!(taffy.layout(items[2]).unwrap().location, Point { x: 0.0, y: 100.0 });
+    assert_eq!(taffy.absolute_location(items[2]).unwrap(), Point { x: 10.0, y: 30.0 });
+    // The container itself isn't moved by its own scroll offset
+    assert_eq!(taffy.absolute_location(container).unwrap(), Point { x: 10.0, y: 10.0 });
+
+    taffy.set_scroll_offset(container, Point::ZERO).unwrap();
+    assert_eq!(taffy.absolute_location(items[2]).unwrap(), Point { x: 10.0, y: 110.0 });
+}
+
+#[test]
+fn hit_testing_takes_scroll_offsets_into_account() {
+    let (mut taffy, root, container, items) = scrolled_list(Overflow::Scroll);
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 20.0 }).unwrap(), Some(items[0]));
+    assert_eq!(taffy.hit_test(root, Point { x: 80.0, y: 20.0 }).unwrap(), Some(container));
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 500.0 }).unwrap(), None);
+
+    taffy.set_scroll_offset(container, Point { x: 0.0, y: 80.0 }).unwrap();
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 20.0 }).unwrap(), Some(items[1]));
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 40.0 }).unwrap(), Some(items[2]));
+}
+
+#[test]
+fn only_scroll_containers_clip_hit_testing() {
+    // The third item overflows the container, and the root, below y = 110
+    let (taffy, root, _, items) = scrolled_list(Overflow::Visible);
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 130.0 }).unwrap(), Some(items[2]));
+
+    let (taffy, root, _, _) = scrolled_list(Overflow::Hidden);
+    assert_eq!(taffy.hit_test(root, Point { x: 20.0, y: 130.0 }).unwrap(), None);
+}
+
+#[test]
+fn later_siblings_are_hit_first() {
+    let mut taffy = Taffy::new();
+    let first = taffy.new_leaf(Style { size: Size::from_lengths(50.0, 50.0), ..Default::default() }).unwrap();
+    let second = taffy
+        .new_leaf(Style {
+            position: Position::Absolute,
+            inset: Rect { left: length(25.0), right: auto(), top: length(0.0), bottom: auto() },
+            size: Size::from_lengths(50.0, 50.0),
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.hit_test(root, Point { x: 10.0, y: 10.0 }).unwrap(), Some(first));
+    assert_eq!(taffy.hit_test(root, Point { x: 30.0, y: 10.0 }).unwrap(), Some(second));
+}
+
+#[test]
+fn nodes_in_rect_include_overflowing_descendants() {
+    let (taffy, root, container, items) = scrolled_list(Overflow::Visible);
+    let rect = Rect { left: 0.0, right: 200.0, top: 100.0, bottom: 150.0 };
+
+    assert_eq!(taffy.nodes_in_rect(root, rect).unwrap(), [root, container, items[1], items[2]]);
+}
+
+#[test]
+fn nodes_in_rect_are_clipped_by_scroll_containers() {
+    let (mut taffy, root, container, items) = scrolled_list(Overflow::Scroll);
+    taffy.set_scroll_offset(container, Point { x: 0.0, y: 80.0 }).unwrap();
+
+    let rect = Rect { left: 0.0, right: 200.0, top: 0.0, bottom: 40.0 };
+    assert_eq!(taffy.nodes_in_rect(root, rect).unwrap(), [root, container, items[1], items[2]]);
+    // The last item is displayed from 80 to 130, but the container clips it at 110
+    let rect = Rect { left: 0.0, right: 200.0, top: 115.0, bottom: 200.0 };
+    assert_eq!(taffy.nodes_in_rect(root, rect).unwrap(), [root]);
+}
+
+#[test]
+fn nodes_in_rect_must_share_some_area() {
+    let (taffy, root, container, _) = scrolled_list(Overflow::Visible);
+    let rect = Rect { left: 60.0, right: 100.0, top: 10.0, bottom: 20.0 };
+
+    assert_eq!(taffy.nodes_in_rect(root, rect).unwrap(), [root, container]);
+    assert_eq!(taffy.nodes_in_rect(container, rect).unwrap(), [container]);
+}
@@ -0,0 +1,60 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Overflow;
+
+/// Scrollbars that are thicker vertically (10) than horizontally (4)
+const SCROLLBAR_WIDTH: Size<f32> = Size { width: 10.0, height: 4.0 };
+
+/// Lays out a 100x100 container with the given display and overflow, containing a single item which fills its
+/// content box, and returns the size of the item
+fn content_box_size(display: Display, overflow: Point<Overflow>) -> Size<f32> {
+    let mut taffy = Taffy::new();
+    let item = taffy.new_leaf(Style { size: Size::from_percent(1.0, 1.0), ..Default::default() }).unwrap();
+    let style = Style {
+        display,
+        overflow,
+        scrollbar_width: SCROLLBAR_WIDTH,
+        size: Size::from_lengths(100.0, 100.0),
+        ..Default::default()
+    };
+    let root = taffy.new_with_children(style, &[item]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.layout(item).unwrap().size
+}
+
+/// The displays of the algorithms that reserve scrollbar gutters for their children
+fn container_displays() -> Vec<Display> {
+    let mut displays = vec![Display::Flex];
+    #[cfg(feature = "grid")]
+    displays.push(Display::Grid);
+    #[cfg(feature = "block_layout")]
+    displays.push(Display::Block);
+    displays
+}
+
+#[test]
+fn each_axis_reserves_the_thickness_of_its_own_scrollbar() {
+    for display in container_displays() {
+        let both = content_box_size(display, Point { x: Overflow::Scroll, y: Overflow::Scroll });
+        assert_eq!(both, Size { width: 90.0, height: 96.0 }, "{display:?}");
+
+        // A node that only scrolls vertically only reserves space for the vertical scrollbar, and vice versa
+        let vertical = content_box_size(display, Point { x: Overflow::Hidden, y: Overflow::Scroll });
+        assert_eq!(vertical, Size { width: 90.0, height: 100.0 }, "{display:?}");
+        let horizontal = content_box_size(display, Point { x: Overflow::Scroll, y: Overflow::Visible });
+        assert_eq!(horizontal, Size { width: 100.0, height: 96.0 }, "{display:?}");
+    }
+}
+
+#[test]
+fn leaves_reserve_the_thickness_of_each_scrollbar() {
+    let mut taffy = Taffy::new();
+    let style = Style {
+        overflow: Point { x: Overflow::Scroll, y: Overflow::Scroll },
+        scrollbar_width: SCROLLBAR_WIDTH,
+        ..Default::default()
+    };
+    let leaf = taffy.new_leaf(style).unwrap();
+    taffy.compute_layout(leaf, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(leaf).unwrap().size, SCROLLBAR_WIDTH);
+}
@@ -52,4 +52,26 @@ mod serde {
         }"###;
         let _: Value = serde_json::from_str(&json).unwrap();
     }
+
+    #[test]
+    fn node_ids_are_serialized_as_u64s() {
+        use taffy::tree::NodeId;
+
+        let id = NodeId::new(u64::MAX - 1);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, (u64::MAX - 1).to_string());
+        assert_eq!(serde_json::from_str::<NodeId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    #[cfg(feature = "grid")]
+    fn grid_track_lists_are_serialized_as_lists() {
+        use taffy::prelude::*;
+
+        let style = Style { grid_template_columns: vec![length(10.0), fr(1.0)].into(), ..Default::default() };
+        let json = serde_json::to_value(&style).unwrap();
+        assert_eq!(json["grid_template_columns"].as_array().unwrap().len(), 2);
+        assert_eq!(json["grid_template_rows"], Value::Array(Vec::new()));
+        assert_eq!(serde_json::from_value::<Style>(json).unwrap(), style);
+    }
 }
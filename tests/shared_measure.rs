@@ -0,0 +1,80 @@
+use std::sync::{Arc, RwLock};
+
+use taffy::prelude::*;
+use taffy::tree::{Measurable, MeasureFunc};
+
+/// Measures text nodes by looking up the text of each node, as a single measurer shared between all of them
+#[derive(Default)]
+struct TextMeasurer {
+    texts: RwLock<Vec<(NodeId, &'static str)>>,
+}
+
+impl Measurable for TextMeasurer {
+    fn measure(&self, _known_dimensions: Size<Option<f32>>, _available_space: Size<AvailableSpace>) -> Size<f32> {
+        Size::ZERO
+    }
+
+    fn measure_node(
+        &self,
+        node: NodeId,
+        known_dimensions: Size<Option<f32>>,
+        _available_space: Size<AvailableSpace>,
+        _style: &Style,
+    ) -> Size<f32> {
+        let length = self.texts.read().unwrap().iter().find(|(id, _)| *id == node).map_or(0, |(_, text)| text.len());
+        known_dimensions.unwrap_or(Size { width: length as f32 * 10.0, height: 10.0 })
+    }
+}
+
+#[test]
+fn shared_measurer_is_passed_the_measured_node() {
+    let measurer = Arc::new(TextMeasurer::default());
+    let mut taffy = Taffy::new();
+    let texts = ["a", "abc", "abcdef"];
+    let nodes: Vec<NodeId> = texts
+        .iter()
+        .map(|text| {
+            let node = taffy.new_leaf_with_measure(Style::default(), MeasureFunc::Shared(measurer.clone())).unwrap();
+            measurer.texts.write().unwrap().push((node, text));
+            node
+        })
+        .collect();
+    let root = taffy.new_with_children(Style::default(), &nodes).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let widths: Vec<f32> = nodes.iter().map(|node| taffy.layout(*node).unwrap().size.width).collect();
+    assert_eq!(widths, vec![10.0, 30.0, 60.0]);
+    assert_eq!(taffy.layout(root).unwrap().size.width, 100.0);
+}
+
+#[test]
+fn boxed_measurers_are_passed_the_measured_node() {
+    /// Records the node it was last asked to measure
+    struct NodeRecorder(Arc<RwLock<Option<NodeId>>>);
+
+    impl Measurable for NodeRecorder {
+        fn measure(&self, _known_dimensions: Size<Option<f32>>, _available_space: Size<AvailableSpace>) -> Size<f32> {
+            Size::ZERO
+        }
+
+        fn measure_node(
+            &self,
+            node: NodeId,
+            _known_dimensions: Size<Option<f32>>,
+            _available_space: Size<AvailableSpace>,
+            _style: &Style,
+        ) -> Size<f32> {
+            *self.0.write().unwrap() = Some(node);
+            Size::ZERO
+        }
+    }
+
+    let measured = Arc::new(RwLock::new(None));
+    let mut taffy = Taffy::new();
+    let node = taffy
+        .new_leaf_with_measure(Style::default(), MeasureFunc::Boxed(Box::new(NodeRecorder(measured.clone()))))
+        .unwrap();
+    taffy.compute_layout(node, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(*measured.read().unwrap(), Some(node));
+}
@@ -0,0 +1,73 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Contain;
+use taffy::tree::MeasureFunc;
+
+#[test]
+fn size_contained_nodes_ignore_their_children_when_sized() {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style { size: Size::from_lengths(100.0, 100.0), flex_shrink: 0.0, ..Default::default() })
+        .unwrap();
+    let contained = taffy
+        .new_with_children(
+            Style {
+                contain: Contain::Size,
+                size: Size { width: length(50.0), height: auto() },
+                padding: Rect { left: zero(), right: zero(), top: length(5.0), bottom: length(5.0) },
+                ..Default::default()
+            },
+            &[child],
+        )
+        .unwrap();
+    let sibling = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { flex_direction: FlexDirection::Column, align_items: Some(AlignItems::Start), ..Default::default() },
+            &[contained, sibling],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(contained).unwrap().size, Size { width: 50.0, height: 10.0 });
+    assert_eq!(taffy.layout(sibling).unwrap().location, Point { x: 0.0, y: 10.0 });
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 50.0, height: 20.0 });
+    // The children of the node are still laid out, overflowing it
+    assert_eq!(taffy.layout(child).unwrap().location, Point { x: 0.0, y: 5.0 });
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 100.0, height: 100.0 });
+}
+
+#[test]
+fn size_contained_leaves_are_not_measured() {
+    let mut taffy = Taffy::new();
+    let leaf = taffy
+        .new_leaf_with_measure(
+            Style { contain: Contain::Size, ..Default::default() },
+            MeasureFunc::Raw(|_, _| panic!("size contained leaves shouldn't be measured")),
+        )
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[leaf]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(leaf).unwrap().size, Size::ZERO);
+}
+
+#[test]
+fn size_contained_nodes_are_still_sized_by_their_parent() {
+    let mut taffy = Taffy::new();
+    let child =
+        taffy.new_leaf(Style { size: Size { width: auto(), height: length(30.0) }, ..Default::default() }).unwrap();
+    let contained = taffy
+        .new_with_children(Style { display: Display::Block, contain: Contain::Size, ..Default::default() }, &[child])
+        .unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { display: Display::Block, size: Size::from_lengths(300.0, 200.0), ..Default::default() },
+            &[contained],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // Block children stretch to the width of their parent, and their content is laid out within that width
+    assert_eq!(taffy.layout(contained).unwrap().size, Size { width: 300.0, height: 0.0 });
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 300.0, height: 30.0 });
+}
@@ -0,0 +1,34 @@
+use taffy::prelude::*;
+use taffy::style::SizeRange;
+
+#[test]
+fn size_ranges_set_the_min_preferred_and_max_sizes_together() {
+    let style = Style::default().with_size_range(
+        Size::from_lengths(10.0, 10.0),
+        Size::from_percent(1.0, 1.0),
+        Size::from_lengths(50.0, 30.0),
+    );
+    assert_eq!(style.size_range(), SizeRange::new(style.min_size, style.size, style.max_size));
+
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(style).unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(100.0, 20.0), ..Default::default() }, &[child])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 50.0, height: 20.0 });
+}
+
+#[test]
+fn size_range_constructors_leave_the_other_sizes_auto() {
+    let size = Size::from_lengths(10.0, 20.0);
+    assert_eq!(SizeRange::exactly(size), SizeRange::new(size, size, size));
+    assert_eq!(SizeRange::at_most(size), SizeRange::new(Size::auto(), Size::auto(), size));
+    assert_eq!(SizeRange::between(Size::from_lengths(5.0, 5.0), size).preferred, Size::auto());
+
+    let mut style = Style::default();
+    style.set_size_range(SizeRange::exactly(size));
+    assert_eq!((style.min_size, style.size, style.max_size), (size, size, size));
+    style.set_size_range(SizeRange::default());
+    assert_eq!(style, Style::default());
+}
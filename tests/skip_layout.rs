@@ -0,0 +1,64 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::tree::MeasureFunc;
+
+/// A column holding a tab, which contains a block of text, followed by a footer
+fn tabs(taffy: &mut Taffy) -> (NodeId, NodeId, NodeId, NodeId) {
+    let text = taffy
+        .new_leaf_with_measure(
+            Style::default(),
+            MeasureFunc::Raw(|known_dimensions, _| known_dimensions.unwrap_or(Size { width: 100.0, height: 50.0 })),
+        )
+        .unwrap();
+    let tab = taffy.new_with_children(Style::default(), &[text]).unwrap();
+    let footer = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style { flex_direction: FlexDirection::Column, align_items: Some(AlignItems::Start), ..Default::default() },
+            &[tab, footer],
+        )
+        .unwrap();
+    (root, tab, text, footer)
+}
+
+#[test]
+fn skipped_nodes_keep_their_size_until_resumed() {
+    let mut taffy = Taffy::new();
+    let (root, tab, text, footer) = tabs(&mut taffy);
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(tab).unwrap().size, Size { width: 100.0, height: 50.0 });
+
+    taffy.skip_layout(tab, Size::ZERO).unwrap();
+    assert!(taffy.is_layout_skipped(tab));
+    let padding = Style {
+        padding: Rect { left: zero(), right: zero(), top: length(20.0), bottom: zero() },
+        ..Default::default()
+    };
+    taffy.set_style(tab, padding).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // Changes to the skipped subtree don't affect the rest of the tree, and it isn't measured, until its layout is resumed
+    assert_eq!(taffy.layout(tab).unwrap().size, Size { width: 100.0, height: 50.0 });
+    assert_eq!(taffy.layout(footer).unwrap().location, Point { x: 0.0, y: 50.0 });
+    assert_eq!(taffy.layout(text).unwrap().location, Point { x: 0.0, y: 0.0 });
+    assert_eq!(taffy.layout_stats().measure_calls, 0);
+
+    assert!(taffy.resume_layout(tab).unwrap());
+    assert!(!taffy.resume_layout(tab).unwrap());
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(tab).unwrap().size, Size { width: 100.0, height: 70.0 });
+    assert_eq!(taffy.layout(text).unwrap().location, Point { x: 0.0, y: 20.0 });
+    assert_eq!(taffy.layout(footer).unwrap().location, Point { x: 0.0, y: 70.0 });
+}
+
+#[test]
+fn skipped_nodes_that_have_not_been_laid_out_use_the_estimated_size() {
+    let mut taffy = Taffy::new();
+    let (root, tab, text, footer) = tabs(&mut taffy);
+    taffy.skip_layout(tab, Size { width: 30.0, height: 20.0 }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.layout(tab).unwrap().size, Size { width: 30.0, height: 20.0 });
+    assert_eq!(taffy.layout(text).unwrap().size, Size::ZERO);
+    assert_eq!(taffy.layout(footer).unwrap().location, Point { x: 0.0, y: 20.0 });
+}
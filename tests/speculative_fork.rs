@@ -0,0 +1,75 @@
+use taffy::prelude::*;
+use taffy::TaffyError;
+
+fn build_tree(taffy: &mut Taffy) -> (NodeId, NodeId) {
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (root, child)
+}
+
+fn bigger() -> Style {
+    Style { size: Size::from_lengths(50.0, 20.0), ..Default::default() }
+}
+
+#[test]
+fn discarded_forks_leave_the_tree_untouched() {
+    let mut taffy = Taffy::new();
+    let (root, child) = build_tree(&mut taffy);
+
+    let mut fork = taffy.speculative_fork();
+    fork.set_style(child, bigger()).unwrap();
+    fork.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(fork.layout(root).unwrap().size, Size { width: 50.0, height: 20.0 });
+    fork.discard();
+
+    assert_eq!(taffy.style(child).unwrap().size, Size::from_lengths(10.0, 10.0));
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 10.0, height: 10.0 });
+    assert!(!taffy.dirty(root).unwrap());
+    assert!(!taffy.dirty(child).unwrap());
+}
+
+#[test]
+fn committed_forks_keep_their_changes() {
+    let mut taffy = Taffy::new();
+    let (root, child) = build_tree(&mut taffy);
+
+    let mut fork = taffy.speculative_fork();
+    fork.set_style(child, bigger()).unwrap();
+    fork.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    fork.commit();
+
+    assert_eq!(taffy.style(child).unwrap().size, Size::from_lengths(50.0, 20.0));
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 50.0, height: 20.0 });
+    assert!(taffy.dirty(root).unwrap());
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 50.0, height: 20.0 });
+}
+
+#[test]
+fn discarded_forks_restore_the_own_style_of_classed_nodes() {
+    let mut taffy = Taffy::new();
+    let (root, child) = build_tree(&mut taffy);
+    taffy.define_style_class("padded", |style| style.padding = length(1.0));
+    taffy.set_style_classes(child, &["padded"]).unwrap();
+
+    let mut fork = taffy.speculative_fork();
+    fork.set_style(child, bigger()).unwrap();
+    fork.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    fork.discard();
+
+    // Redefining the class reapplies it to the node's own style, which must be the one from before the fork
+    taffy.define_style_class("padded", |style| style.padding = length(2.0));
+    assert_eq!(taffy.style(child).unwrap().size, Size::from_lengths(10.0, 10.0));
+    assert_eq!(taffy.style(child).unwrap().padding, length(2.0));
+}
+
+#[test]
+fn forks_reject_invalid_nodes() {
+    let mut taffy = Taffy::new();
+    let (_, child) = build_tree(&mut taffy);
+    taffy.remove(child).unwrap();
+
+    let mut fork = taffy.speculative_fork();
+    assert!(matches!(fork.set_style(child, bigger()), Err(TaffyError::InvalidInputNode(node)) if node == child));
+}
@@ -0,0 +1,86 @@
+use taffy::prelude::*;
+use taffy::TaffyError;
+
+/// A 100 wide root containing a leaf with the "card" class and a leaf without classes
+fn themed_tree() -> (Taffy, NodeId, NodeId, NodeId) {
+    let mut taffy = Taffy::new();
+    taffy.define_style_class("card", |style| style.size.height = length(20.0));
+    let card = taffy.new_leaf(Style { flex_grow: 1.0, ..Default::default() }).unwrap();
+    let plain = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    taffy.set_style_classes(card, &["card"]).unwrap();
+    let root = taffy
+        .new_with_children(Style { size: Size::from_lengths(100.0, 50.0), ..Default::default() }, &[card, plain])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, card, plain)
+}
+
+#[test]
+fn classes_are_applied_on_top_of_the_own_style() {
+    let (taffy, _, card, _) = themed_tree();
+
+    assert_eq!(taffy.style_classes(card), ["card"]);
+    assert_eq!(taffy.style(card).unwrap().size.height, length(20.0));
+    assert_eq!(taffy.own_style(card).unwrap().size.height, Dimension::Auto);
+    assert_eq!(taffy.style(card).unwrap().flex_grow, 1.0);
+    assert_eq!(taffy.layout(card).unwrap().size, Size { width: 90.0, height: 20.0 });
+}
+
+#[test]
+fn redefining_a_class_dirties_only_the_nodes_using_it() {
+    let (mut taffy, root, card, plain) = themed_tree();
+    taffy.define_style_class("card", |style| style.size.height = length(30.0));
+
+    assert!(taffy.dirty(card).unwrap());
+    assert!(!taffy.dirty(plain).unwrap());
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(card).unwrap().size.height, 30.0);
+
+    // Defining an unused class or redefining a class with the same effect leaves every node clean
+    taffy.define_style_class("unused", |style| style.flex_grow = 5.0);
+    taffy.define_style_class("card", |style| style.size.height = length(30.0));
+    assert!(!taffy.dirty(card).unwrap());
+}
+
+#[test]
+fn later_classes_override_earlier_ones() {
+    let (mut taffy, _, card, _) = themed_tree();
+    taffy.define_style_class("tall", |style| style.size.height = length(40.0));
+    taffy.define_style_class("padded", |style| style.padding = length(2.0));
+    taffy.set_style_classes(card, &["tall", "card", "padded", "undefined"]).unwrap();
+
+    assert_eq!(taffy.style(card).unwrap().size.height, length(20.0));
+    assert_eq!(taffy.style(card).unwrap().padding, length(2.0));
+
+    assert!(taffy.remove_style_class("card"));
+    assert!(!taffy.has_style_class("card"));
+    assert_eq!(taffy.style(card).unwrap().size.height, length(40.0));
+}
+
+#[test]
+fn setting_the_style_of_a_node_with_classes_sets_its_own_style() {
+    let (mut taffy, _, card, _) = themed_tree();
+    taffy.set_style(card, Style { size: Size::from_lengths(5.0, 5.0), ..Default::default() }).unwrap();
+    assert_eq!(taffy.style(card).unwrap().size, Size { width: length(5.0), height: length(20.0) });
+
+    taffy.style_mut(card).unwrap().size.width = length(7.0);
+    assert_eq!(taffy.own_style(card).unwrap().size, Size::from_lengths(7.0, 5.0));
+    assert_eq!(taffy.style(card).unwrap().size, Size { width: length(7.0), height: length(20.0) });
+
+    // Removing every class restores the own style
+    taffy.set_style_classes(card, &[]).unwrap();
+    assert!(taffy.style_classes(card).is_empty());
+    assert_eq!(taffy.style(card).unwrap().size, Size::from_lengths(7.0, 5.0));
+}
+
+#[test]
+fn removed_nodes_lose_their_classes() {
+    let (mut taffy, _, card, _) = themed_tree();
+    taffy.remove(card).unwrap();
+    let node = taffy.new_leaf(Style::default()).unwrap();
+
+    assert!(taffy.style_classes(node).is_empty());
+    assert!(
+        matches!(taffy.set_style_classes(card, &["card"]), Err(TaffyError::InvalidInputNode(node)) if node == card)
+    );
+}
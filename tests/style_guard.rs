@@ -0,0 +1,28 @@
+use taffy::prelude::*;
+
+#[test]
+fn modifying_a_style_in_place_marks_the_node_dirty() {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    taffy.style_mut(child).unwrap().size.width = length(40.0);
+    assert!(taffy.dirty(child).unwrap());
+    assert!(taffy.dirty(root).unwrap());
+
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 40.0, height: 10.0 });
+}
+
+#[test]
+fn reading_a_style_through_the_guard_does_not_mark_the_node_dirty() {
+    let mut taffy = Taffy::new();
+    let root = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let style = taffy.style_mut(root).unwrap();
+    assert_eq!(style.size.width, length(10.0));
+    drop(style);
+    assert!(!taffy.dirty(root).unwrap());
+}
@@ -0,0 +1,72 @@
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::Overflow;
+use taffy::tree::{Measurable, MeasureFunc};
+
+/// Measures a single line of text that is 100 wide and 10 high, which wraps onto two lines to fit its available width
+/// unless its overflow is `Hidden`
+struct Text;
+
+impl Measurable for Text {
+    fn measure(&self, known_dimensions: Size<Option<f32>>, available_space: Size<AvailableSpace>) -> Size<f32> {
+        self.measure_with_style(known_dimensions, available_space, &Style::default())
+    }
+
+    fn measure_with_style(
+        &self,
+        _known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        style: &Style,
+    ) -> Size<f32> {
+        let wraps = style.overflow.x != Overflow::Hidden;
+        match available_space.width {
+            AvailableSpace::Definite(width) if wraps && width < 100.0 => Size { width: 50.0, height: 20.0 },
+            _ => Size { width: 100.0, height: 10.0 },
+        }
+    }
+}
+
+fn text_size(overflow: Overflow) -> Size<f32> {
+    let mut taffy = Taffy::new();
+    let style = Style { overflow: Point { x: overflow, y: overflow }, ..Default::default() };
+    let text = taffy.new_leaf_with_measure(style, MeasureFunc::Boxed(Box::new(Text))).unwrap();
+    let root = taffy
+        .new_with_children(
+            Style {
+                flex_direction: FlexDirection::Column,
+                size: Size { width: length(60.0), height: auto() },
+                ..Default::default()
+            },
+            &[text],
+        )
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    taffy.layout(text).unwrap().size
+}
+
+#[test]
+fn measure_funcs_are_passed_the_node_style() {
+    assert_eq!(text_size(Overflow::Visible).height, 20.0);
+    assert_eq!(text_size(Overflow::Hidden).height, 10.0);
+}
+
+#[test]
+fn tree_measure_functions_are_passed_the_node_style() {
+    let mut taffy = Taffy::new();
+    let style = Style { aspect_ratio: Some(2.0), ..Default::default() };
+    let leaf = taffy.new_leaf(style).unwrap();
+    taffy.set_needs_measure(leaf, true).unwrap();
+
+    taffy
+        .compute_layout_with_styled_measure(
+            leaf,
+            Size::MAX_CONTENT,
+            |_node, style, _known_dimensions, _available_space| {
+                let width = 40.0;
+                Size { width, height: width / style.aspect_ratio.unwrap_or(1.0) }
+            },
+        )
+        .unwrap();
+
+    assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 40.0, height: 20.0 });
+}
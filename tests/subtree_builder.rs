@@ -0,0 +1,71 @@
+use taffy::prelude::*;
+use taffy::tree::{MeasureFunc, SubtreeBuilder};
+
+/// Builds a column of `row_count` rows, each containing a fixed-size leaf and a measured leaf
+fn build_column(row_count: usize) -> SubtreeBuilder {
+    let mut builder = SubtreeBuilder::with_capacity(row_count * 3 + 1);
+    let rows: Vec<_> = (0..row_count)
+        .map(|_| {
+            let icon = builder.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() });
+            let text = builder
+                .new_leaf_with_measure(Style::default(), MeasureFunc::Raw(|_, _| Size { width: 30.0, height: 12.0 }));
+            builder.new_with_children(Style::default(), &[icon, text])
+        })
+        .collect();
+    builder.new_with_children(Style { flex_direction: FlexDirection::Column, ..Default::default() }, &rows);
+    builder
+}
+
+#[test]
+fn subtrees_built_on_other_threads_can_be_inserted() {
+    let builders: Vec<SubtreeBuilder> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| build_column(50))).collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut taffy = Taffy::new();
+    let columns: Vec<NodeId> = builders
+        .into_iter()
+        .map(|builder| {
+            let ids = taffy.insert_subtree(builder).unwrap();
+            // The root of each column is the last node to be created
+            *ids.last().unwrap()
+        })
+        .collect();
+    let root = taffy.new_with_children(Style::default(), &columns).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    assert_eq!(taffy.iter().count(), 4 * (50 * 3 + 1) + 1);
+    assert_eq!(taffy.layout(root).unwrap().size, Size { width: 4.0 * 40.0, height: 50.0 * 12.0 });
+    let last_row = taffy.child_at_index(columns[3], 49).unwrap();
+    assert_eq!(taffy.layout(last_row).unwrap().location.y, 49.0 * 12.0);
+}
+
+#[test]
+fn inserted_ids_are_in_creation_order() {
+    let mut builder = SubtreeBuilder::new();
+    let leaf = builder.new_leaf(Style::default());
+    let parent = builder.new_with_children(Style::default(), &[leaf]);
+
+    let mut taffy = Taffy::new();
+    let ids = taffy.insert_subtree(builder).unwrap();
+    assert_eq!(ids.len(), 2);
+    assert_eq!(taffy.children(ids[parent.index()]).unwrap(), vec![ids[leaf.index()]]);
+}
+
+#[test]
+#[should_panic(expected = "is used as a child more than once")]
+fn children_cannot_have_two_parents() {
+    let mut builder = SubtreeBuilder::new();
+    let leaf = builder.new_leaf(Style::default());
+    builder.new_with_children(Style::default(), &[leaf]);
+    builder.new_with_children(Style::default(), &[leaf]);
+}
+
+#[test]
+#[should_panic(expected = "is used as a child more than once")]
+fn children_cannot_be_listed_twice() {
+    let mut builder = SubtreeBuilder::new();
+    let leaf = builder.new_leaf(Style::default());
+    builder.new_with_children(Style::default(), &[leaf, leaf]);
+}
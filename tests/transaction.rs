@@ -0,0 +1,101 @@
+use taffy::prelude::*;
+
+fn leaf(taffy: &mut Taffy, width: f32) -> NodeId {
+    taffy.new_leaf(Style { size: Size::from_lengths(width, 10.0), ..Default::default() }).unwrap()
+}
+
+#[test]
+fn rolled_back_transactions_restore_the_tree() {
+    let mut taffy = Taffy::new();
+    let first = leaf(&mut taffy, 10.0);
+    let second = leaf(&mut taffy, 20.0);
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let mut transaction = taffy.transaction();
+    let added = transaction.new_leaf(Style::default()).unwrap();
+    let wrapper = transaction.new_with_children(Style::default(), &[second]).unwrap();
+    transaction.set_children(root, &[wrapper, added]).unwrap();
+    transaction.set_style(first, Style { size: Size::from_lengths(50.0, 50.0), ..Default::default() }).unwrap();
+    transaction.remove(first).unwrap();
+    transaction.rollback();
+
+    assert_eq!(taffy.children(root).unwrap(), vec![first, second]);
+    assert_eq!(taffy.style(first).unwrap().size, Size::from_lengths(10.0, 10.0));
+    assert!(!taffy.dirty(root).unwrap());
+    assert!(!taffy.dirty(second).unwrap());
+
+    // The parents of the children were restored as well
+    taffy.remove(first).unwrap();
+    assert_eq!(taffy.children(root).unwrap(), vec![second]);
+}
+
+#[test]
+fn committed_transactions_keep_their_changes() {
+    let mut taffy = Taffy::new();
+    let first = leaf(&mut taffy, 10.0);
+    let second = leaf(&mut taffy, 20.0);
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    let mut transaction = taffy.transaction();
+    let added = transaction.new_leaf(Style { size: Size::from_lengths(5.0, 10.0), ..Default::default() }).unwrap();
+    transaction.replace_child_at_index(root, 0, added).unwrap();
+    transaction.remove(first).unwrap();
+    transaction.commit();
+
+    assert_eq!(taffy.children(root).unwrap(), vec![added, second]);
+    assert!(taffy.dirty(root).unwrap());
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(root).unwrap().size.width, 25.0);
+}
+
+#[test]
+fn dropping_a_transaction_rolls_it_back() {
+    let mut taffy = Taffy::new();
+    let root = taffy.new_leaf(Style::default()).unwrap();
+
+    {
+        let mut transaction = taffy.transaction();
+        let child = transaction.new_leaf(Style::default()).unwrap();
+        transaction.add_child(root, child).unwrap();
+    }
+
+    assert_eq!(taffy.child_count(root).unwrap(), 0);
+}
+
+#[test]
+fn rolled_back_transactions_restore_the_own_style_of_classed_nodes() {
+    let mut taffy = Taffy::new();
+    let node = leaf(&mut taffy, 10.0);
+    taffy.define_style_class("padded", |style| style.padding = length(1.0));
+    taffy.set_style_classes(node, &["padded"]).unwrap();
+
+    let mut transaction = taffy.transaction();
+    transaction.set_style(node, Style { size: Size::from_lengths(50.0, 50.0), ..Default::default() }).unwrap();
+    transaction.rollback();
+
+    // Redefining the class reapplies it to the node's own style, which must be the one from before the transaction
+    taffy.define_style_class("padded", |style| style.padding = length(2.0));
+    assert_eq!(taffy.style(node).unwrap().size, Size::from_lengths(10.0, 10.0));
+    assert_eq!(taffy.style(node).unwrap().padding, length(2.0));
+}
+
+#[test]
+fn rolled_back_transactions_restore_keys() {
+    let mut taffy = Taffy::new();
+    let first = leaf(&mut taffy, 10.0);
+    let second = leaf(&mut taffy, 20.0);
+    taffy.set_key(first, Some("first")).unwrap();
+
+    let mut transaction = taffy.transaction();
+    transaction.set_key(first, None).unwrap();
+    transaction.set_key(second, Some("first")).unwrap();
+    let added = transaction.new_leaf(Style::default()).unwrap();
+    transaction.set_key(added, Some("added")).unwrap();
+    transaction.rollback();
+
+    assert_eq!(taffy.node_by_key("first"), Some(first));
+    assert_eq!(taffy.key(second), None);
+    assert_eq!(taffy.node_by_key("added"), None);
+}
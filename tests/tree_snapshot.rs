@@ -0,0 +1,84 @@
+use taffy::prelude::*;
+use taffy::tree::TreeDelta;
+
+/// A row containing two fixed-size leaves, laid out
+fn row() -> (Taffy, NodeId, [NodeId; 2]) {
+    let mut taffy = Taffy::new();
+    let first = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    let second = taffy.new_leaf(Style { size: Size::from_lengths(20.0, 10.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[first, second]).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    (taffy, root, [first, second])
+}
+
+#[test]
+fn snapshots_record_the_subtree() {
+    let (taffy, root, [first, second]) = row();
+    let snapshot = taffy.snapshot(root).unwrap();
+
+    assert_eq!(snapshot.root, root);
+    assert_eq!(snapshot.nodes.len(), 3);
+    assert_eq!(snapshot.nodes[&root].children, vec![first, second]);
+    assert_eq!(snapshot.nodes[&second].layout, *taffy.layout(second).unwrap());
+    assert_eq!(taffy.snapshot(first).unwrap().nodes.len(), 1);
+}
+
+#[test]
+fn identical_snapshots_have_an_empty_delta() {
+    let (taffy, root, _) = row();
+    let snapshot = taffy.snapshot(root).unwrap();
+
+    assert!(snapshot.delta(&taffy.snapshot(root).unwrap()).is_empty());
+}
+
+#[test]
+fn deltas_list_only_what_changed() {
+    let (mut taffy, root, [first, second]) = row();
+    let before = taffy.snapshot(root).unwrap();
+
+    let third = taffy.new_leaf(Style { size: Size::from_lengths(5.0, 5.0), ..Default::default() }).unwrap();
+    taffy.remove(first).unwrap();
+    taffy.add_child(root, third).unwrap();
+    let second_style = Style { size: Size::from_lengths(30.0, 10.0), ..Default::default() };
+    taffy.set_style(second, second_style.clone()).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let after = taffy.snapshot(root).unwrap();
+
+    let delta = before.delta(&after);
+    assert_eq!(delta.removed, vec![first]);
+    assert_eq!(delta.added.iter().map(|(node, _)| *node).collect::<Vec<_>>(), vec![third]);
+    assert_eq!(delta.styles, vec![(second, second_style)]);
+    assert_eq!(delta.children, vec![(root, vec![second, third])]);
+    // The root grew and the second leaf moved to the start of the row and grew, which are listed in order of their ids
+    let changed_layouts: Vec<NodeId> = delta.layouts.iter().map(|(node, _)| *node).collect();
+    assert_eq!(changed_layouts, vec![second, root]);
+}
+
+#[test]
+fn applying_a_delta_produces_the_newer_snapshot() {
+    let (mut taffy, root, [first, _]) = row();
+    let mut replica = taffy.snapshot(root).unwrap();
+
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(8.0, 8.0), ..Default::default() }).unwrap();
+    taffy.add_child(first, child).unwrap();
+    taffy.set_style(root, Style { flex_direction: FlexDirection::Column, ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let after = taffy.snapshot(root).unwrap();
+
+    let delta: TreeDelta = replica.delta(&after);
+    replica.apply(&delta);
+    assert_eq!(replica, after);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn deltas_can_be_serialized() {
+    let (mut taffy, root, [first, _]) = row();
+    let before = taffy.snapshot(root).unwrap();
+    taffy.set_style(first, Style { size: Size::from_lengths(12.0, 10.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    let delta = before.delta(&taffy.snapshot(root).unwrap());
+
+    let json = serde_json::to_string(&delta).unwrap();
+    assert_eq!(serde_json::from_str::<TreeDelta>(&json).unwrap(), delta);
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "upstream")]
+
+use taffy::geometry::Point;
+use taffy::prelude::*;
+use taffy::style::{Anchor, GridTrackRepetition, Overflow};
+use taffy::upstream::ConversionError;
+use upstream_taffy as up;
+use upstream_taffy::style_helpers as up_helpers;
+
+/// An upstream flex container with a fixed size child, a growing child and a percentage sized child
+fn upstream_styles() -> [up::Style; 4] {
+    let fixed = up::Style {
+        size: up::Size { width: up::Dimension::length(30.0), height: up::Dimension::length(20.0) },
+        margin: up::Rect {
+            left: up::LengthPercentageAuto::length(5.0),
+            right: up::LengthPercentageAuto::auto(),
+            top: up::LengthPercentageAuto::length(0.0),
+            bottom: up::LengthPercentageAuto::length(0.0),
+        },
+        ..Default::default()
+    };
+    let growing = up::Style { flex_grow: 1.0, ..Default::default() };
+    let percent = up::Style {
+        size: up::Size { width: up::Dimension::percent(0.25), height: up::Dimension::auto() },
+        ..Default::default()
+    };
+    let root = up::Style {
+        size: up::Size { width: up::Dimension::length(200.0), height: up::Dimension::length(100.0) },
+        padding: up::Rect {
+            left: up::LengthPercentage::length(10.0),
+            right: up::LengthPercentage::length(10.0),
+            top: up::LengthPercentage::length(4.0),
+            bottom: up::LengthPercentage::length(4.0),
+        },
+        gap: up::Size { width: up::LengthPercentage::length(6.0), height: up::LengthPercentage::length(0.0) },
+        align_items: Some(up::AlignItems::Center),
+        ..Default::default()
+    };
+    [root, fixed, growing, percent]
+}
+
+/// Converts a style into an upstream style with the default string type
+fn to_upstream(style: Style) -> Result<up::Style, ConversionError> {
+    style.try_into()
+}
+
+#[test]
+fn converted_upstream_styles_can_be_laid_out() {
+    let [root_style, fixed, growing, percent] = upstream_styles();
+    let mut taffy = Taffy::new();
+    let children = [fixed, growing, percent].map(|style| taffy.new_leaf(style.try_into().unwrap()).unwrap());
+    let root = taffy.new_with_children(root_style.try_into().unwrap(), &children).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+
+    // 180 wide content box, minus two gaps, the fixed child and its margin, and 25% for the percentage sized child
+    let expected = [(15.0, 40.0, 30.0, 20.0), (51.0, 50.0, 88.0, 0.0), (145.0, 50.0, 45.0, 0.0)];
+    for (node, (x, y, width, height)) in children.into_iter().zip(expected) {
+        let layout = taffy.layout(node).unwrap();
+        assert_eq!((layout.location, layout.size), (Point { x, y }, Size { width, height }));
+    }
+    let upstream = up::Layout::from(*taffy.layout(root).unwrap());
+    assert_eq!(upstream.size, up::Size { width: 200.0, height: 100.0 });
+}
+
+#[test]
+fn styles_round_trip_through_upstream() {
+    let style = Style {
+        display: Display::Grid,
+        overflow: Point { x: Overflow::Hidden, y: Overflow::Scroll },
+        scrollbar_width: Size { width: 8.0, height: 8.0 },
+        position: Position::Absolute,
+        inset: Rect { left: length(1.0), right: auto(), top: percent(0.5), bottom: auto() },
+        max_size: Size { width: length(300.0), height: Dimension::Auto },
+        aspect_ratio: Some(2.0),
+        justify_content: Some(JustifyContent::SpaceEvenly),
+        justify_items: Some(JustifyItems::End),
+        grid_template_columns: vec![
+            length(40.0),
+            repeat(GridTrackRepetition::AutoFill, vec![minmax(length(20.0), fr(1.0))]),
+            fit_content(percent(0.5)),
+        ]
+        .into(),
+        grid_auto_rows: vec![auto(), min_content()].into(),
+        grid_auto_flow: GridAutoFlow::ColumnDense,
+        grid_row: Line { start: line(2), end: span(3) },
+        grid_column: Line { start: line(-1), end: GridPlacement::Auto },
+        ..Default::default()
+    };
+
+    let upstream: up::Style = style.clone().try_into().unwrap();
+    assert_eq!(upstream.scrollbar_width, 8.0);
+    assert_eq!(upstream.grid_row.start, up_helpers::line(2));
+
+    assert_eq!(Style::try_from(upstream).unwrap(), style);
+}
+
+#[test]
+fn layouts_convert_in_both_directions() {
+    let layout = Layout { order: 3, size: Size { width: 10.0, height: 20.0 }, location: Point { x: 1.0, y: 2.0 } };
+    let upstream = up::Layout::from(layout);
+
+    assert_eq!((upstream.order, upstream.size.width, upstream.location.y), (3, 10.0, 2.0));
+    assert_eq!(upstream.padding, up::Rect::zero());
+    assert_eq!(Layout::from(upstream), layout);
+}
+
+#[test]
+fn clip_overflow_becomes_visible() {
+    let style: up::Style =
+        up::Style { overflow: up::Point { x: up::Overflow::Clip, y: up::Overflow::Hidden }, ..Default::default() };
+    let style = Style::try_from(style).unwrap();
+
+    assert_eq!(style.overflow, Point { x: Overflow::Visible, y: Overflow::Hidden });
+}
+
+#[test]
+fn unrepresentable_values_are_rejected() {
+    let named = up::Style {
+        grid_row: up::Line {
+            start: up::GridPlacement::NamedLine(String::from("header"), 1),
+            end: up::GridPlacement::Auto,
+        },
+        ..Default::default()
+    };
+    assert_eq!(Style::try_from(named), Err(ConversionError::GridName));
+
+    let content_box: up::Style = up::Style { box_sizing: up::BoxSizing::ContentBox, ..Default::default() };
+    assert_eq!(Style::try_from(content_box), Err(ConversionError::UnsupportedProperty("box_sizing")));
+
+    let viewport = Style { size: Size { width: Dimension::Vw(0.5), height: Dimension::Auto }, ..Default::default() };
+    assert_eq!(to_upstream(viewport).unwrap_err(), ConversionError::RelativeUnit);
+
+    let custom = Style { padding: Rect { left: LengthPercentage::Custom(7), ..Rect::zero() }, ..Default::default() };
+    assert_eq!(to_upstream(custom).unwrap_err(), ConversionError::CustomUnit(7));
+
+    let display = Style { display: Display::Custom(2), ..Default::default() };
+    assert_eq!(to_upstream(display).unwrap_err(), ConversionError::CustomDisplay(2));
+
+    let anchored = Style { anchor: Some(Anchor::CENTER), ..Default::default() };
+    assert_eq!(to_upstream(anchored).unwrap_err(), ConversionError::UnsupportedProperty("anchor"));
+}
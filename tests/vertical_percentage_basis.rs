@@ -0,0 +1,38 @@
+use taffy::prelude::*;
+use taffy::style::VerticalPercentageBasis;
+
+fn child_layout(basis: VerticalPercentageBasis, display: Display) -> Layout {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style {
+            size: Size::from_lengths(10.0, 10.0),
+            margin: Rect { left: zero(), right: zero(), top: percent(0.1), bottom: zero() },
+            padding: Rect { left: zero(), right: zero(), top: percent(0.2), bottom: zero() },
+            vertical_percentage_basis: basis,
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy
+        .new_with_children(Style { display, size: Size::from_lengths(400.0, 100.0), ..Default::default() }, &[child])
+        .unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    *taffy.layout(child).unwrap()
+}
+
+#[test]
+fn vertical_percentages_resolve_against_width_by_default() {
+    for display in [Display::Flex, Display::Grid, Display::Block] {
+        let layout = child_layout(VerticalPercentageBasis::Width, display);
+        assert_eq!(layout.location.y, 40.0, "{display:?}");
+        assert_eq!(layout.size.height, 80.0, "{display:?}");
+    }
+}
+
+#[test]
+fn vertical_percentages_resolve_against_height() {
+    for display in [Display::Flex, Display::Grid, Display::Block] {
+        let layout = child_layout(VerticalPercentageBasis::Height, display);
+        assert_eq!(layout.location.y, 10.0, "{display:?}");
+        assert_eq!(layout.size.height, 20.0, "{display:?}");
+    }
+}
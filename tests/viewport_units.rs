@@ -0,0 +1,44 @@
+use taffy::prelude::*;
+
+#[test]
+fn viewport_units_resolve_against_available_space() {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style {
+            size: Size { width: Dimension::Vw(0.5), height: Dimension::Vh(0.25) },
+            margin: Rect { left: LengthPercentageAuto::Vmin(0.1), right: zero(), top: zero(), bottom: zero() },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+    taffy.compute_layout(root, Size { width: length(400.0), height: length(200.0) }).unwrap();
+    let layout = taffy.layout(child).unwrap();
+    assert_eq!(layout.size, Size { width: 200.0, height: 50.0 });
+    assert_eq!(layout.location.x, 20.0);
+
+    // A change in viewport invalidates the nodes that depend on it
+    taffy.compute_layout(root, Size { width: length(800.0), height: length(200.0) }).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 400.0, height: 50.0 });
+}
+
+#[test]
+fn viewport_units_resolve_against_explicit_viewport() {
+    let mut taffy = Taffy::new();
+    let child = taffy
+        .new_leaf(Style {
+            size: Size { width: Dimension::Vmax(0.1), height: Dimension::Vh(1.0) },
+            ..Default::default()
+        })
+        .unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+    taffy.set_viewport_size(Some(Size { width: 1000.0, height: 500.0 }));
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 100.0, height: 500.0 });
+
+    // Replacing the style with one that doesn't use viewport units stops it from being resolved
+    taffy.set_style(child, Style { size: Size::from_lengths(10.0, 10.0), ..Default::default() }).unwrap();
+    taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(child).unwrap().size, Size { width: 10.0, height: 10.0 });
+}
@@ -0,0 +1,46 @@
+use taffy::geometry::AbsoluteAxis;
+use taffy::prelude::*;
+use taffy::tree::VirtualChildren;
+
+fn row() -> Style {
+    Style { size: Size { width: percent(1.0), height: length(20.0) }, flex_shrink: 0.0, ..Default::default() }
+}
+
+#[test]
+fn virtual_children_reserve_space_for_unmaterialized_children() {
+    let mut taffy = Taffy::new();
+    let list = taffy
+        .new_leaf(Style {
+            flex_direction: FlexDirection::Column,
+            size: Size { width: length(100.0), height: auto() },
+            ..Default::default()
+        })
+        .unwrap();
+    taffy.set_virtual_children(list, Some(VirtualChildren::new(100_000, AbsoluteAxis::Vertical, |_| 20.0))).unwrap();
+
+    // Without any materialized children, the list is as large as the estimated size of all of its children
+    taffy.compute_layout(list, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(list).unwrap().size.height, 2_000_000.0);
+
+    // Materialize the children that are visible in a 60px viewport scrolled to 1000px
+    let window = taffy.virtual_window(list, 1000.0, 60.0);
+    assert_eq!(window, 50..53);
+    let rows: Vec<NodeId> = window.clone().map(|_| taffy.new_leaf(row()).unwrap()).collect();
+    taffy.set_children(list, &rows).unwrap();
+    taffy.virtual_children_mut(list).unwrap().unwrap().first_materialized = window.start;
+
+    taffy.compute_layout(list, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(list).unwrap().size.height, 2_000_000.0);
+    assert_eq!(taffy.layout(rows[0]).unwrap().location.y, 1000.0);
+    assert_eq!(taffy.layout(rows[2]).unwrap().location.y, 1040.0);
+    assert_eq!(taffy.layout(rows[2]).unwrap().size, Size { width: 100.0, height: 20.0 });
+
+    // The reserved space tracks changes to the materialized children
+    taffy.remove_child(list, rows[2]).unwrap();
+    taffy.compute_layout(list, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(list).unwrap().size.height, 2_000_000.0);
+
+    taffy.set_virtual_children(list, None).unwrap();
+    taffy.compute_layout(list, Size::MAX_CONTENT).unwrap();
+    assert_eq!(taffy.layout(list).unwrap().size.height, 40.0);
+}
@@ -0,0 +1,33 @@
+use taffy::prelude::*;
+use taffy::tree::LayoutWorker;
+
+#[test]
+fn layout_worker_should_publish_snapshots() {
+    let mut taffy = Taffy::new();
+    let child = taffy.new_leaf(Style { size: Size::from_lengths(10.0, 20.0), ..Default::default() }).unwrap();
+    let root = taffy.new_with_children(Style::default(), &[child]).unwrap();
+
+    let worker = LayoutWorker::spawn(taffy);
+    worker.submit(|_| {}, root, Size::MAX_CONTENT);
+    let snapshot = worker.wait_snapshot().unwrap().unwrap();
+    assert_eq!(snapshot.generation, 1);
+    assert_eq!(snapshot.root, root);
+    assert_eq!(snapshot.layouts.len(), 2);
+    assert_eq!(snapshot.layouts.get(root).unwrap().size, Size { width: 10.0, height: 20.0 });
+
+    worker.submit(
+        move |taffy| {
+            taffy.set_style(child, Style { size: Size::from_lengths(30.0, 20.0), ..Default::default() }).unwrap();
+        },
+        root,
+        Size::MAX_CONTENT,
+    );
+    let snapshot = worker.wait_snapshot().unwrap().unwrap();
+    assert_eq!(snapshot.generation, 2);
+    assert_eq!(snapshot.layouts.get(child).unwrap().size.width, 30.0);
+    assert!(worker.try_snapshot().is_none());
+
+    // The tree is handed back once the worker is joined
+    let taffy = worker.join();
+    assert_eq!(taffy.layout(root).unwrap().size.width, 30.0);
+}